@@ -386,6 +386,27 @@ impl CudaBatchedLuSolver {
             });
         }
 
+        let factorization = self.factor_batch(matrices, n, batch_size)?;
+        self.solve_with_factors(&factorization, rhs)
+    }
+
+    /// Factor a batch of matrices once, keeping the LU factors and pivots on
+    /// the GPU for [`solve_with_factors`](Self::solve_with_factors).
+    ///
+    /// Matrices use the same column-major layout as
+    /// [`solve_batch`](Self::solve_batch).
+    pub fn factor_batch(
+        &self,
+        matrices: &[f64],
+        n: usize,
+        batch_size: usize,
+    ) -> Result<BatchedFactorization> {
+        if batch_size == 0 {
+            return Err(CudaError::InvalidDimension(
+                "Cannot factor an empty batch".into(),
+            ));
+        }
+
         if batch_size > self.config.max_batch_per_launch {
             return Err(CudaError::BatchTooLarge {
                 size: batch_size,
@@ -393,16 +414,13 @@ impl CudaBatchedLuSolver {
             });
         }
 
-        // Upload matrices and RHS to GPU
         let gpu_matrices = BatchedMatrices::from_host(&self.ctx, matrices, n, batch_size)?;
-        let gpu_rhs = BatchedVectors::from_host(&self.ctx, rhs, n, batch_size)?;
         let mut pivots = BatchedPivots::allocate(&self.ctx, n, batch_size)?;
 
         let n_i32 = n as i32;
         let batch_size_i32 = batch_size as i32;
         let stream = &self.ctx.stream;
 
-        // Step 1: LU factorization
         {
             let (a_ptrs, _a_guard) = gpu_matrices.pointers.device_ptr(stream);
             let (pivot_ptr, _pivot_guard) = pivots.pivots.device_ptr_mut(stream);
@@ -442,10 +460,34 @@ impl CudaBatchedLuSolver {
             );
         }
 
-        // Step 2: Solve with factored matrices
+        Ok(BatchedFactorization {
+            matrices: gpu_matrices,
+            pivots,
+            singular_indices,
+        })
+    }
+
+    /// Solve `A x = b` for one new right-hand side per system, reusing the
+    /// factors from [`factor_batch`](Self::factor_batch).
+    ///
+    /// Only the triangular solves run, so the factorization cost is paid
+    /// once however many right-hand sides follow.
+    pub fn solve_with_factors(
+        &self,
+        factorization: &BatchedFactorization,
+        rhs: &[f64],
+    ) -> Result<BatchedSolveResult> {
+        let n = factorization.n();
+        let batch_size = factorization.batch_size();
+        let gpu_rhs = BatchedVectors::from_host(&self.ctx, rhs, n, batch_size)?;
+
+        let n_i32 = n as i32;
+        let batch_size_i32 = batch_size as i32;
+        let stream = &self.ctx.stream;
+
         {
-            let (a_ptrs, _a_guard) = gpu_matrices.pointers.device_ptr(stream);
-            let (pivot_ptr, _pivot_guard) = pivots.pivots.device_ptr(stream);
+            let (a_ptrs, _a_guard) = factorization.matrices.pointers.device_ptr(stream);
+            let (pivot_ptr, _pivot_guard) = factorization.pivots.pivots.device_ptr(stream);
             let (b_ptrs, _b_guard) = gpu_rhs.pointers.device_ptr(stream);
 
             let mut getrs_info: CudaSlice<i32> = self.ctx.stream.alloc_zeros(1).map_err(|e| {
@@ -487,13 +529,44 @@ impl CudaBatchedLuSolver {
 
         Ok(BatchedSolveResult {
             solutions,
-            singular_indices,
+            singular_indices: factorization.singular_indices.clone(),
             n,
             batch_size,
         })
     }
 }
 
+/// LU factors of a batch of matrices, held on the GPU.
+///
+/// Produced by [`CudaBatchedLuSolver::factor_batch`] and reused by
+/// [`CudaBatchedLuSolver::solve_with_factors`] for any number of
+/// right-hand sides, e.g. transient steps where only the sources change.
+pub struct BatchedFactorization {
+    /// Matrices overwritten in place by their L and U factors.
+    matrices: BatchedMatrices,
+    /// Pivot indices and info from `cublasDgetrfBatched`.
+    pivots: BatchedPivots,
+    /// Indices of matrices found singular during factorization.
+    singular_indices: Vec<usize>,
+}
+
+impl BatchedFactorization {
+    /// Matrix dimension.
+    pub fn n(&self) -> usize {
+        self.matrices.matrix_size()
+    }
+
+    /// Number of factored matrices.
+    pub fn batch_size(&self) -> usize {
+        self.matrices.batch_size()
+    }
+
+    /// Indices of matrices found singular during factorization.
+    pub fn singular_indices(&self) -> &[usize] {
+        &self.singular_indices
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -651,6 +724,40 @@ mod tests {
         assert!(!result.is_singular(0), "Matrix 0 should not be singular");
     }
 
+    #[test]
+    fn test_factor_then_solve_matches_one_shot() {
+        let ctx = match try_create_cuda_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no CUDA device available");
+                return;
+            }
+        };
+
+        let solver = CudaBatchedLuSolver::new(ctx);
+        let n = 3;
+        let batch_size = 2;
+
+        // Both need pivoting (zero leading entry). Column-major.
+        let matrices = vec![
+            0.0, 2.0, 1.0, 1.0, 1.0, 3.0, 4.0, 0.0, 1.0, // [[0,1,4],[2,1,0],[1,3,1]]
+            0.0, 1.0, 5.0, 3.0, 2.0, 1.0, 1.0, 0.0, 2.0, // [[0,3,1],[1,2,0],[5,1,2]]
+        ];
+        let factorization = solver.factor_batch(&matrices, n, batch_size).unwrap();
+        assert!(factorization.singular_indices().is_empty());
+
+        for k in 0..3 {
+            let rhs: Vec<f64> = (0..batch_size * n).map(|i| (i + k) as f64 - 2.0).collect();
+            let reused = solver.solve_with_factors(&factorization, &rhs).unwrap();
+            let one_shot = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+            for (x, e) in reused.solutions.iter().zip(&one_shot.solutions) {
+                assert!((x - e).abs() < 1e-12, "rhs {k}: {x} vs {e}");
+            }
+        }
+
+        assert!(solver.solve_with_factors(&factorization, &[1.0]).is_err());
+    }
+
     #[test]
     fn test_config_thresholds() {
         let config = GpuBatchedSweepConfig::default();
@@ -9,8 +9,8 @@ pub mod ilu_preconditioner;
 pub mod sparse_context;
 
 pub use batched_lu::{
-    BatchedMatrices, BatchedPivots, BatchedSolveResult, BatchedVectors, CudaBatchedLuSolver,
-    GpuBatchedSweepConfig, MAX_BATCH_SIZE, MIN_BATCH_SIZE, MIN_MATRIX_SIZE,
+    BatchedFactorization, BatchedMatrices, BatchedPivots, BatchedSolveResult, BatchedVectors,
+    CudaBatchedLuSolver, GpuBatchedSweepConfig, MAX_BATCH_SIZE, MIN_BATCH_SIZE, MIN_MATRIX_SIZE,
 };
 pub use batched_sweep::{GpuBatchedSweepResult, solve_batched_sweep_gpu};
 pub use context::CudaContext;
@@ -0,0 +1,55 @@
+//! Benchmark of the batched LU kernel's thread mappings.
+//!
+//! A small batch of large matrices is the case the cooperative mapping
+//! targets: one thread per matrix leaves most of the GPU idle.
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use spicier_backend_metal::{GpuBatchConfig, LuMapping, MetalBatchedLuSolver, WgpuContext};
+use std::sync::Arc;
+
+fn bench_lu_mapping(c: &mut Criterion) {
+    let Ok(ctx) = WgpuContext::new() else {
+        eprintln!("Skipping benchmark: no GPU available");
+        return;
+    };
+    let ctx = Arc::new(ctx);
+
+    let n = 128;
+    let batch_size = 10;
+    // Diagonally dominant, column-major
+    let matrices: Vec<f64> = (0..batch_size * n * n)
+        .map(|k| {
+            let (row, col) = (k % n, (k / n) % n);
+            if row == col {
+                n as f64
+            } else {
+                ((row + 2 * col + k / (n * n)) % 7) as f64 / 7.0 - 0.5
+            }
+        })
+        .collect();
+    let rhs: Vec<f64> = (0..batch_size * n).map(|i| (i % 5) as f64).collect();
+
+    let mut group = c.benchmark_group("batched_lu_10x128");
+    for mapping in [LuMapping::ThreadPerMatrix, LuMapping::Cooperative] {
+        let config = GpuBatchConfig {
+            mapping: Some(mapping),
+            ..Default::default()
+        };
+        let solver = MetalBatchedLuSolver::with_config(ctx.clone(), config).unwrap();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{mapping:?}")),
+            &mapping,
+            |bencher, _| {
+                bencher.iter(|| {
+                    solver
+                        .solve_batch(black_box(&matrices), black_box(&rhs), n, batch_size)
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_lu_mapping);
+criterion_main!(benches);
@@ -116,3 +116,86 @@ pub fn pack_rhs_f32(rhs: &[f64]) -> Vec<f32> {
 pub fn unpack_solutions_f64(solutions_f32: &[f32]) -> Vec<f64> {
     solutions_f32.iter().map(|&v| v as f64).collect()
 }
+
+/// Split `v` into the double-single pair `(hi, lo)` with `hi + lo ≈ v`.
+fn split_f64(v: f64) -> (f32, f32) {
+    let hi = v as f32;
+    (hi, (v - hi as f64) as f32)
+}
+
+/// Pack f64 column-major matrices as double-single pairs: the high words in
+/// the layout of [`pack_matrices_f32`], followed by the low words.
+pub fn pack_matrices_ds(
+    matrices: &[f64],
+    n: usize,
+    batch_size: usize,
+    layout: &BatchLayout,
+) -> Vec<f32> {
+    let total = layout.total_matrix_elements();
+    let mut packed = vec![0.0f32; 2 * total];
+
+    for batch_idx in 0..batch_size {
+        let src_offset = batch_idx * n * n;
+        for row in 0..n {
+            for col in 0..n {
+                let (hi, lo) = split_f64(matrices[src_offset + col * n + row]);
+                let dst_idx = layout.matrix_offset(batch_idx, row, col);
+                packed[dst_idx] = hi;
+                packed[total + dst_idx] = lo;
+            }
+        }
+    }
+
+    packed
+}
+
+/// Pack f64 RHS vectors as double-single pairs, high words first.
+pub fn pack_rhs_ds(rhs: &[f64]) -> Vec<f32> {
+    let (hi, lo): (Vec<f32>, Vec<f32>) = rhs.iter().map(|&v| split_f64(v)).unzip();
+    [hi, lo].concat()
+}
+
+/// Reassemble f64 solutions from double-single pairs, high words first.
+pub fn unpack_solutions_ds(solutions_ds: &[f32]) -> Vec<f64> {
+    let (hi, lo) = solutions_ds.split_at(solutions_ds.len() / 2);
+    hi.iter()
+        .zip(lo)
+        .map(|(&h, &l)| h as f64 + l as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_single_round_trip() {
+        let values = [1.0 / 3.0, -std::f64::consts::PI * 1e5, 1e-20, 0.0];
+        let unpacked = unpack_solutions_ds(&pack_rhs_ds(&values));
+        for (v, u) in values.iter().zip(&unpacked) {
+            assert!((v - u).abs() <= 1e-14 * v.abs(), "{v} vs {u}");
+        }
+        // A single f32 keeps only ~7 digits
+        assert!((values[0] - values[0] as f32 as f64).abs() > 1e-9);
+    }
+
+    #[test]
+    fn test_pack_matrices_ds_layout() {
+        let n = 2;
+        let layout = BatchLayout::new(n, 1);
+        let total = layout.total_matrix_elements();
+        // Column-major [[1, 0.1], [2, 0.2]]
+        let matrices = [1.0, 2.0, 0.1, 0.2];
+        let packed = pack_matrices_ds(&matrices, n, 1, &layout);
+
+        assert_eq!(packed.len(), 2 * total);
+        let at = |row, col| {
+            let idx = layout.matrix_offset(0, row, col);
+            packed[idx] as f64 + packed[total + idx] as f64
+        };
+        assert_eq!(at(0, 0), 1.0);
+        assert!((at(0, 1) - 0.1).abs() < 1e-15);
+        assert!((at(1, 1) - 0.2).abs() < 1e-15);
+        assert_eq!(packed[total + layout.matrix_offset(0, 1, 0)], 0.0);
+    }
+}
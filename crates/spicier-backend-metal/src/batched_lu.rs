@@ -3,11 +3,16 @@
 //! Each matrix in the batch is processed by a separate workgroup, enabling
 //! massive parallelism for Monte Carlo, corner analysis, and parameter sweeps.
 
-use crate::batch_layout::{BatchLayout, pack_matrices_f32, pack_rhs_f32, unpack_solutions_f64};
+use crate::batch_layout::{
+    BatchLayout, pack_matrices_ds, pack_matrices_f32, pack_rhs_ds, pack_rhs_f32,
+    unpack_solutions_ds, unpack_solutions_f64,
+};
 use crate::context::WgpuContext;
 use crate::error::{Result, WgpuError};
 use bytemuck::{Pod, Zeroable};
+use faer::prelude::*;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use wgpu::util::DeviceExt;
 
 /// Maximum matrix dimension supported (limited by workgroup shared memory).
@@ -21,6 +26,64 @@ pub const MIN_BATCH_SIZE: usize = 2000;
 /// Note: Current implementation needs large matrices to amortize overhead.
 pub const MIN_MATRIX_SIZE: usize = 100;
 
+/// Threads per workgroup in [`LuMapping::Cooperative`] (fixed in the shader).
+pub const COOPERATIVE_THREADS: usize = 64;
+
+/// Smallest matrix dimension for which the cooperative mapping is chosen.
+const COOPERATIVE_MIN_MATRIX_SIZE: usize = 16;
+
+/// Batch size from which one thread per matrix already fills the GPU.
+const COOPERATIVE_MAX_BATCH_SIZE: usize = 1024;
+
+/// How the batched LU kernel maps matrices onto GPU threads.
+///
+/// Both mappings launch one workgroup per matrix and produce the same
+/// pivoting sequence; they differ in how many threads share the work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuMapping {
+    /// A single thread factors and solves each matrix. Best when the batch
+    /// alone is large enough to occupy the GPU.
+    ThreadPerMatrix,
+    /// [`COOPERATIVE_THREADS`] threads share each elimination step: the
+    /// pivot search, the row updates and the back-substitution sums. Best
+    /// for small batches of large matrices.
+    Cooperative,
+}
+
+impl LuMapping {
+    /// Choose a mapping for `batch_size` systems of dimension `n`.
+    pub fn select(n: usize, batch_size: usize) -> Self {
+        if n >= COOPERATIVE_MIN_MATRIX_SIZE && batch_size < COOPERATIVE_MAX_BATCH_SIZE {
+            Self::Cooperative
+        } else {
+            Self::ThreadPerMatrix
+        }
+    }
+
+    /// Shader entry point implementing this mapping.
+    fn entry_point(self) -> &'static str {
+        match self {
+            Self::ThreadPerMatrix => "main",
+            Self::Cooperative => "main_cooperative",
+        }
+    }
+}
+
+/// Row pivoting performed by the batched LU kernel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PivotMode {
+    /// Swap in the largest remaining entry of each column (the default).
+    #[default]
+    Partial,
+    /// Eliminate on the diagonal as stored. Skips the pivot search and row
+    /// swaps, which is safe for diagonally dominant matrices such as most
+    /// circuit conductance matrices. A (near-)zero diagonal entry still
+    /// marks the system singular, and with
+    /// [`cpu_fallback`](GpuBatchConfig::cpu_fallback) it is re-solved on the
+    /// CPU with pivoting.
+    None,
+}
+
 /// Uniform buffer layout for shader parameters.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
@@ -29,6 +92,23 @@ struct Uniforms {
     batch_size: u32,
     row_stride: u32,
     matrix_stride: u32,
+    /// 1 for [`PivotMode::Partial`], 0 for [`PivotMode::None`].
+    pivoting: u32,
+    /// Pads the struct to a multiple of 16 bytes.
+    _padding: [u32; 3],
+}
+
+impl Uniforms {
+    fn new(n: usize, batch_size: usize, layout: &BatchLayout, pivoting: PivotMode) -> Self {
+        Self {
+            n: n as u32,
+            batch_size: batch_size as u32,
+            row_stride: layout.padded_row_stride() as u32,
+            matrix_stride: layout.padded_matrix_size() as u32,
+            pivoting: u32::from(pivoting == PivotMode::Partial),
+            _padding: [0; 3],
+        }
+    }
 }
 
 /// Result of a batched LU solve operation.
@@ -42,6 +122,29 @@ pub struct BatchedSolveResult {
     pub n: usize,
     /// Number of systems solved.
     pub batch_size: usize,
+    /// Thread mapping the solve ran with.
+    pub mapping: LuMapping,
+    /// Phase timings, when [`GpuBatchConfig::collect_timings`] is set.
+    pub timings: Option<BatchTimings>,
+}
+
+/// Wall-clock time spent in each phase of a batched solve.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BatchTimings {
+    /// Packing the batch to f32 and writing it to GPU buffers.
+    pub upload: Duration,
+    /// Submitting the LU kernel and waiting for it to finish.
+    pub compute: Duration,
+    /// Mapping the results back and unpacking them to f64, including any
+    /// CPU fallback solves.
+    pub readback: Duration,
+}
+
+impl BatchTimings {
+    /// Sum of all phases.
+    pub fn total(&self) -> Duration {
+        self.upload + self.compute + self.readback
+    }
 }
 
 impl BatchedSolveResult {
@@ -75,6 +178,22 @@ pub struct GpuBatchConfig {
     pub min_matrix_size: usize,
     /// Maximum matrix size (limited by shader).
     pub max_matrix_size: usize,
+    /// Re-solve systems whose GPU solution is not finite on the CPU in f64.
+    pub cpu_fallback: bool,
+    /// Force a thread mapping instead of choosing one by problem size.
+    pub mapping: Option<LuMapping>,
+    /// Record per-phase [`BatchTimings`]. Off by default because waiting
+    /// for the kernel separately from the readback costs a device poll.
+    pub collect_timings: bool,
+    /// Factor and solve in double-single arithmetic (each value carried as
+    /// a pair of f32s, about 48 mantissa bits) instead of plain f32. Costs
+    /// several times the arithmetic and twice the memory. Only
+    /// [`solve_batch`](MetalBatchedLuSolver::solve_batch) with the
+    /// thread-per-matrix mapping supports it; other paths return
+    /// [`WgpuError::Unsupported`].
+    pub use_double_single: bool,
+    /// Row pivoting strategy, for every mapping and the factor-once path.
+    pub pivoting: PivotMode,
 }
 
 impl Default for GpuBatchConfig {
@@ -83,6 +202,11 @@ impl Default for GpuBatchConfig {
             min_batch_size: MIN_BATCH_SIZE,
             min_matrix_size: MIN_MATRIX_SIZE,
             max_matrix_size: MAX_MATRIX_SIZE,
+            cpu_fallback: true,
+            mapping: None,
+            collect_timings: false,
+            use_double_single: false,
+            pivoting: PivotMode::Partial,
         }
     }
 }
@@ -94,6 +218,18 @@ impl GpuBatchConfig {
             && matrix_size <= self.max_matrix_size
             && batch_size >= self.min_batch_size
     }
+
+    /// The thread mapping used for `batch_size` systems of dimension `n`.
+    ///
+    /// An explicit `mapping` always wins. Otherwise double-single solves use
+    /// their only kernel, thread per matrix.
+    pub fn mapping_for(&self, n: usize, batch_size: usize) -> LuMapping {
+        match self.mapping {
+            Some(mapping) => mapping,
+            None if self.use_double_single => LuMapping::ThreadPerMatrix,
+            None => LuMapping::select(n, batch_size),
+        }
+    }
 }
 
 /// Minimum buffer capacity to allocate (prevents thrashing for tiny allocations).
@@ -176,7 +312,16 @@ pub struct MetalBatchedLuSolver {
     ctx: Arc<WgpuContext>,
     config: GpuBatchConfig,
     pipeline: wgpu::ComputePipeline,
+    cooperative_pipeline: wgpu::ComputePipeline,
+    /// Double-single variant of `pipeline`.
+    ds_pipeline: wgpu::ComputePipeline,
     bind_group_layout: wgpu::BindGroupLayout,
+    /// Pipeline and layout for `factor_batch`.
+    factor_pipeline: wgpu::ComputePipeline,
+    factor_bind_group_layout: wgpu::BindGroupLayout,
+    /// Pipeline and layout for `solve_with_factors`.
+    solve_factored_pipeline: wgpu::ComputePipeline,
+    solve_factored_bind_group_layout: wgpu::BindGroupLayout,
     /// Cached buffers with interior mutability for reuse across calls.
     cached: RwLock<CachedBuffers>,
 }
@@ -202,49 +347,13 @@ impl MetalBatchedLuSolver {
             label: Some("Batched LU Bind Group Layout"),
             entries: &[
                 // Uniforms
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
+                uniform_entry(0),
                 // Matrices (read-write, modified in place during factorization)
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
+                storage_entry(1),
                 // RHS/Solution vectors (read-write)
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
+                storage_entry(2),
                 // Info array (singularity flags)
-                wgpu::BindGroupLayoutEntry {
-                    binding: 3,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
+                storage_entry(3),
             ],
         });
 
@@ -254,14 +363,59 @@ impl MetalBatchedLuSolver {
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: Some("Batched LU Pipeline"),
-            layout: Some(&pipeline_layout),
-            module: &shader,
-            entry_point: Some("main"),
-            compilation_options: Default::default(),
-            cache: None,
-        });
+        let create_pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Batched LU Pipeline"),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let pipeline = create_pipeline(LuMapping::ThreadPerMatrix.entry_point());
+        let cooperative_pipeline = create_pipeline(LuMapping::Cooperative.entry_point());
+        let ds_pipeline = create_pipeline("main_ds");
+
+        // Factor-once, solve-many: each half binds only what it touches
+        let split_pipeline = |entry_point: &str, entries: &[wgpu::BindGroupLayoutEntry]| {
+            let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Batched LU Split Bind Group Layout"),
+                entries,
+            });
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Batched LU Split Pipeline Layout"),
+                bind_group_layouts: &[&layout],
+                push_constant_ranges: &[],
+            });
+            let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Batched LU Split Pipeline"),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+            (pipeline, layout)
+        };
+        let (factor_pipeline, factor_bind_group_layout) = split_pipeline(
+            "factor",
+            &[
+                uniform_entry(0),
+                storage_entry(1),
+                storage_entry(3),
+                storage_entry(4),
+            ],
+        );
+        let (solve_factored_pipeline, solve_factored_bind_group_layout) = split_pipeline(
+            "solve_factored",
+            &[
+                uniform_entry(0),
+                storage_entry(1),
+                storage_entry(2),
+                storage_entry(4),
+            ],
+        );
 
         log::info!(
             "Created Metal batched LU solver (GPU: {})",
@@ -272,7 +426,13 @@ impl MetalBatchedLuSolver {
             ctx,
             config,
             pipeline,
+            cooperative_pipeline,
+            ds_pipeline,
             bind_group_layout,
+            factor_pipeline,
+            factor_bind_group_layout,
+            solve_factored_pipeline,
+            solve_factored_bind_group_layout,
             cached: RwLock::new(CachedBuffers::new()),
         })
     }
@@ -301,6 +461,11 @@ impl MetalBatchedLuSolver {
         self.config.should_use_gpu(matrix_size, batch_size)
     }
 
+    /// The thread mapping [`solve_batch`](Self::solve_batch) would use.
+    pub fn mapping(&self, n: usize, batch_size: usize) -> LuMapping {
+        self.config.mapping_for(n, batch_size)
+    }
+
     /// Solve a batch of linear systems Ax = b.
     ///
     /// # Arguments
@@ -318,30 +483,14 @@ impl MetalBatchedLuSolver {
         n: usize,
         batch_size: usize,
     ) -> Result<BatchedSolveResult> {
-        let expected_matrix_len = batch_size * n * n;
-        let expected_rhs_len = batch_size * n;
-
-        if matrices.len() != expected_matrix_len {
-            return Err(WgpuError::InvalidDimension(format!(
-                "Expected {} matrix elements, got {}",
-                expected_matrix_len,
-                matrices.len()
-            )));
-        }
-
-        if rhs.len() != expected_rhs_len {
-            return Err(WgpuError::InvalidDimension(format!(
-                "Expected {} RHS elements, got {}",
-                expected_rhs_len,
-                rhs.len()
-            )));
-        }
-
-        if n > self.config.max_matrix_size {
-            return Err(WgpuError::InvalidDimension(format!(
-                "Matrix size {} exceeds maximum {}",
-                n, self.config.max_matrix_size
-            )));
+        self.check_matrices(matrices, n, batch_size)?;
+        check_rhs(rhs, n, batch_size)?;
+
+        let mapping = self.mapping(n, batch_size);
+        if self.config.use_double_single && mapping == LuMapping::Cooperative {
+            return Err(WgpuError::Unsupported(
+                "double-single has no cooperative kernel; use the thread-per-matrix mapping".into(),
+            ));
         }
 
         if batch_size == 0 {
@@ -350,11 +499,15 @@ impl MetalBatchedLuSolver {
                 singular_indices: vec![],
                 n,
                 batch_size: 0,
+                mapping,
+                timings: None,
             });
         }
 
         let device = &self.ctx.device;
         let queue = &self.ctx.queue;
+        let timing = self.config.collect_timings;
+        let upload_start = Instant::now();
 
         // Create batch layout for aligned memory access
         let layout = BatchLayout::new(n, batch_size);
@@ -362,8 +515,18 @@ impl MetalBatchedLuSolver {
         let matrix_stride = layout.padded_matrix_size();
 
         // Convert f64 -> f32 with col-major -> row-major transpose and alignment padding
-        let matrices_f32 = pack_matrices_f32(matrices, n, batch_size, &layout);
-        let rhs_f32 = pack_rhs_f32(rhs);
+        let double_single = self.config.use_double_single;
+        let (matrices_f32, rhs_f32) = if double_single {
+            (
+                pack_matrices_ds(matrices, n, batch_size, &layout),
+                pack_rhs_ds(rhs),
+            )
+        } else {
+            (
+                pack_matrices_f32(matrices, n, batch_size, &layout),
+                pack_rhs_f32(rhs),
+            )
+        };
 
         // Calculate required buffer capacities
         let needed_matrix_elems = matrices_f32.len();
@@ -451,12 +614,7 @@ impl MetalBatchedLuSolver {
 
         if need_bind_group_update {
             // Create uniform buffer with stride information for shader
-            let uniforms = Uniforms {
-                n: n as u32,
-                batch_size: batch_size as u32,
-                row_stride: row_stride as u32,
-                matrix_stride: matrix_stride as u32,
-            };
+            let uniforms = Uniforms::new(n, batch_size, &layout, self.config.pivoting);
 
             let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Batched LU Uniforms"),
@@ -532,9 +690,14 @@ impl MetalBatchedLuSolver {
                 label: Some("Batched LU Pass"),
                 timestamp_writes: None,
             });
-            compute_pass.set_pipeline(&self.pipeline);
+            let pipeline = match mapping {
+                LuMapping::ThreadPerMatrix if double_single => &self.ds_pipeline,
+                LuMapping::ThreadPerMatrix => &self.pipeline,
+                LuMapping::Cooperative => &self.cooperative_pipeline,
+            };
+            compute_pass.set_pipeline(pipeline);
             compute_pass.set_bind_group(0, bind_group, &[]);
-            // One workgroup per matrix in the batch
+            // One workgroup per matrix in the batch, for either mapping
             compute_pass.dispatch_workgroups(batch_size as u32, 1, 1);
         }
 
@@ -544,7 +707,7 @@ impl MetalBatchedLuSolver {
             0,
             solution_staging,
             0,
-            (expected_rhs_len * std::mem::size_of::<f32>()) as u64,
+            (rhs_f32.len() * std::mem::size_of::<f32>()) as u64,
         );
         encoder.copy_buffer_to_buffer(
             info_buffer,
@@ -554,7 +717,14 @@ impl MetalBatchedLuSolver {
             (batch_size * std::mem::size_of::<i32>()) as u64,
         );
 
+        let upload = upload_start.elapsed();
+        let compute_start = Instant::now();
         queue.submit(std::iter::once(encoder.finish()));
+        if timing {
+            device.poll(wgpu::Maintain::Wait);
+        }
+        let compute = compute_start.elapsed();
+        let readback_start = Instant::now();
 
         // Drop cache lock before blocking on GPU readback
         drop(cache);
@@ -565,51 +735,135 @@ impl MetalBatchedLuSolver {
         let info_staging = cache.info_staging.as_ref().unwrap();
 
         // Read solutions
-        let solutions = {
-            let buffer_slice = solution_staging.slice(..);
-            let (sender, receiver) = std::sync::mpsc::channel();
-            buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-                sender.send(result).unwrap();
-            });
-            device.poll(wgpu::Maintain::Wait);
-            receiver
-                .recv()
-                .map_err(|e| WgpuError::Buffer(format!("Failed to receive map result: {}", e)))?
-                .map_err(|e| WgpuError::Buffer(format!("Buffer mapping failed: {:?}", e)))?;
-
-            let data = buffer_slice.get_mapped_range();
-            let solutions_f32: &[f32] = bytemuck::cast_slice(&data);
-            let solutions = unpack_solutions_f64(&solutions_f32[..expected_rhs_len]);
-            drop(data);
-            solution_staging.unmap();
-            solutions
+        let solutions_f32: Vec<f32> = read_back(device, solution_staging, rhs_f32.len())?;
+        let mut solutions = if double_single {
+            unpack_solutions_ds(&solutions_f32)
+        } else {
+            unpack_solutions_f64(&solutions_f32)
         };
 
         // Read info
-        let singular_indices = {
-            let buffer_slice = info_staging.slice(..);
-            let (sender, receiver) = std::sync::mpsc::channel();
-            buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-                sender.send(result).unwrap();
+        let info: Vec<i32> = read_back(device, info_staging, batch_size)?;
+        let mut singular_indices = singular_from_info(&info);
+
+        // The shader's pivot check misses f32 overflow, which shows up as
+        // NaN or infinity in an otherwise unflagged solution.
+        flag_non_finite(&solutions, n, &mut singular_indices);
+        if self.config.cpu_fallback && !singular_indices.is_empty() {
+            resolve_on_cpu(matrices, rhs, n, &mut solutions, &mut singular_indices);
+        }
+
+        if !singular_indices.is_empty() {
+            log::warn!(
+                "{} of {} matrices were singular",
+                singular_indices.len(),
+                batch_size
+            );
+        }
+
+        let timings = timing.then(|| BatchTimings {
+            upload,
+            compute,
+            readback: readback_start.elapsed(),
+        });
+        Ok(BatchedSolveResult {
+            solutions,
+            singular_indices,
+            n,
+            batch_size,
+            mapping,
+            timings,
+        })
+    }
+
+    /// Factor a batch of matrices once, keeping the LU factors and pivots on
+    /// the GPU for [`solve_with_factors`](Self::solve_with_factors).
+    ///
+    /// Matrices use the same column-major layout as
+    /// [`solve_batch`](Self::solve_batch). The factorization always runs one
+    /// thread per matrix, in f32; double-single is not supported here.
+    pub fn factor_batch(
+        &self,
+        matrices: &[f64],
+        n: usize,
+        batch_size: usize,
+    ) -> Result<BatchedFactorization> {
+        self.check_single_precision("factor_batch")?;
+        self.check_matrices(matrices, n, batch_size)?;
+
+        let device = &self.ctx.device;
+        let layout = BatchLayout::new(n, batch_size);
+        let matrices_f32 = pack_matrices_f32(matrices, n, batch_size, &layout);
+
+        let uniforms = Uniforms::new(n, batch_size, &layout, self.config.pivoting);
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Batched LU Factor Uniforms"),
+            contents: bytemuck::bytes_of(&uniforms),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let matrix_buffer = storage_buffer_init(
+            device,
+            "Batched LU Factors",
+            bytemuck::cast_slice(&matrices_f32),
+        );
+        let pivot_buffer = storage_buffer_init(
+            device,
+            "Batched LU Pivots",
+            bytemuck::cast_slice(&vec![0u32; batch_size * n]),
+        );
+        let info_buffer = storage_buffer_init(
+            device,
+            "Batched LU Factor Info",
+            bytemuck::cast_slice(&vec![0i32; batch_size]),
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Batched LU Factor Bind Group"),
+            layout: &self.factor_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: matrix_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: info_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: pivot_buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let info_staging = staging_buffer(device, "Factor Info Staging", batch_size);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Batched LU Factor Encoder"),
+        });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Batched LU Factor Pass"),
+                timestamp_writes: None,
             });
-            device.poll(wgpu::Maintain::Wait);
-            receiver
-                .recv()
-                .map_err(|e| WgpuError::Buffer(format!("Failed to receive map result: {}", e)))?
-                .map_err(|e| WgpuError::Buffer(format!("Buffer mapping failed: {:?}", e)))?;
-
-            let data = buffer_slice.get_mapped_range();
-            let info_array: &[i32] = bytemuck::cast_slice(&data);
-            let singular: Vec<usize> = info_array[..batch_size]
-                .iter()
-                .enumerate()
-                .filter_map(|(i, &v)| if v > 0 { Some(i) } else { None })
-                .collect();
-            drop(data);
-            info_staging.unmap();
-            singular
-        };
+            compute_pass.set_pipeline(&self.factor_pipeline);
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.dispatch_workgroups(batch_size as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(
+            &info_buffer,
+            0,
+            &info_staging,
+            0,
+            (batch_size.max(1) * std::mem::size_of::<i32>()) as u64,
+        );
+        self.ctx.queue.submit(std::iter::once(encoder.finish()));
 
+        let info: Vec<i32> = read_back(device, &info_staging, batch_size)?;
+        let singular_indices = singular_from_info(&info);
         if !singular_indices.is_empty() {
             log::warn!(
                 "{} of {} matrices were singular",
@@ -618,13 +872,313 @@ impl MetalBatchedLuSolver {
             );
         }
 
+        Ok(BatchedFactorization {
+            uniform_buffer,
+            matrix_buffer,
+            pivot_buffer,
+            singular_indices,
+            n,
+            batch_size,
+        })
+    }
+
+    /// Solve `A x = b` for one new right-hand side per system, reusing the
+    /// factors from [`factor_batch`](Self::factor_batch).
+    ///
+    /// Systems singular at factorization, or whose solution is not finite,
+    /// are reported in `singular_indices`. Without the original matrices
+    /// there is no CPU fallback here.
+    pub fn solve_with_factors(
+        &self,
+        factorization: &BatchedFactorization,
+        rhs: &[f64],
+    ) -> Result<BatchedSolveResult> {
+        self.check_single_precision("solve_with_factors")?;
+        let (n, batch_size) = (factorization.n, factorization.batch_size);
+        check_rhs(rhs, n, batch_size)?;
+
+        let device = &self.ctx.device;
+        let rhs_f32 = pack_rhs_f32(rhs);
+        let rhs_buffer = storage_buffer_init(
+            device,
+            "Batched LU Factored RHS",
+            bytemuck::cast_slice(&rhs_f32),
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Batched LU Solve Bind Group"),
+            layout: &self.solve_factored_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: factorization.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: factorization.matrix_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rhs_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: factorization.pivot_buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let solution_staging = staging_buffer(device, "Factored Solution Staging", rhs_f32.len());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Batched LU Solve Encoder"),
+        });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Batched LU Solve Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.solve_factored_pipeline);
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.dispatch_workgroups(batch_size as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(
+            &rhs_buffer,
+            0,
+            &solution_staging,
+            0,
+            (rhs_f32.len().max(1) * std::mem::size_of::<f32>()) as u64,
+        );
+        self.ctx.queue.submit(std::iter::once(encoder.finish()));
+
+        let solutions_f32: Vec<f32> = read_back(device, &solution_staging, rhs_f32.len())?;
+        let solutions = unpack_solutions_f64(&solutions_f32);
+        let mut singular_indices = factorization.singular_indices.clone();
+        flag_non_finite(&solutions, n, &mut singular_indices);
+
         Ok(BatchedSolveResult {
             solutions,
             singular_indices,
             n,
             batch_size,
+            mapping: LuMapping::ThreadPerMatrix,
+            timings: None,
         })
     }
+
+    /// Reject `use_double_single` on paths that only have f32 kernels.
+    fn check_single_precision(&self, operation: &str) -> Result<()> {
+        if self.config.use_double_single {
+            return Err(WgpuError::Unsupported(format!(
+                "{operation} has no double-single kernel"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check a flattened batch of matrices against `n` and `batch_size`.
+    fn check_matrices(&self, matrices: &[f64], n: usize, batch_size: usize) -> Result<()> {
+        let expected_matrix_len = batch_size * n * n;
+        if matrices.len() != expected_matrix_len {
+            return Err(WgpuError::InvalidDimension(format!(
+                "Expected {} matrix elements, got {}",
+                expected_matrix_len,
+                matrices.len()
+            )));
+        }
+
+        if n > self.config.max_matrix_size {
+            return Err(WgpuError::InvalidDimension(format!(
+                "Matrix size {} exceeds maximum {}",
+                n, self.config.max_matrix_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// LU factors of a batch of matrices, held on the GPU.
+///
+/// Produced by [`MetalBatchedLuSolver::factor_batch`] and reused by
+/// [`MetalBatchedLuSolver::solve_with_factors`] for any number of
+/// right-hand sides, e.g. transient steps where only the sources change.
+pub struct BatchedFactorization {
+    /// Shader parameters (dimension, batch size, strides).
+    uniform_buffer: wgpu::Buffer,
+    /// Packed L (unit diagonal, below) and U (on and above) factors, in f32.
+    matrix_buffer: wgpu::Buffer,
+    /// Pivot row chosen at each elimination step.
+    pivot_buffer: wgpu::Buffer,
+    /// Indices of matrices found singular during factorization.
+    singular_indices: Vec<usize>,
+    /// Matrix dimension.
+    n: usize,
+    /// Number of factored matrices.
+    batch_size: usize,
+}
+
+impl BatchedFactorization {
+    /// Matrix dimension.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of factored matrices.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Indices of matrices found singular during factorization.
+    pub fn singular_indices(&self) -> &[usize] {
+        &self.singular_indices
+    }
+}
+
+/// Check a flattened batch of right-hand sides against `n` and `batch_size`.
+fn check_rhs(rhs: &[f64], n: usize, batch_size: usize) -> Result<()> {
+    let expected_rhs_len = batch_size * n;
+    if rhs.len() != expected_rhs_len {
+        return Err(WgpuError::InvalidDimension(format!(
+            "Expected {} RHS elements, got {}",
+            expected_rhs_len,
+            rhs.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Layout entry for the uniform buffer.
+fn uniform_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Layout entry for a read-write storage buffer.
+fn storage_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only: false },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Storage buffer initialised from `contents`, which may be read back.
+///
+/// Padded to at least one word, since empty buffers cannot be bound.
+fn storage_buffer_init(device: &wgpu::Device, label: &str, contents: &[u8]) -> wgpu::Buffer {
+    let padded;
+    let contents = if contents.is_empty() {
+        padded = [0u8; 4];
+        &padded[..]
+    } else {
+        contents
+    };
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+    })
+}
+
+/// Mappable staging buffer for reading back `len` 4-byte elements.
+fn staging_buffer(device: &wgpu::Device, label: &str, len: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size: (len.max(1) * 4) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Block until `staging` can be mapped, then copy out its first `len` elements.
+fn read_back<T: Pod>(device: &wgpu::Device, staging: &wgpu::Buffer, len: usize) -> Result<Vec<T>> {
+    let buffer_slice = staging.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+        sender.send(result).unwrap();
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver
+        .recv()
+        .map_err(|e| WgpuError::Buffer(format!("Failed to receive map result: {}", e)))?
+        .map_err(|e| WgpuError::Buffer(format!("Buffer mapping failed: {:?}", e)))?;
+
+    let data = buffer_slice.get_mapped_range();
+    let values = bytemuck::cast_slice::<u8, T>(&data)[..len].to_vec();
+    drop(data);
+    staging.unmap();
+    Ok(values)
+}
+
+/// Indices of systems the shader flagged singular (info > 0).
+fn singular_from_info(info: &[i32]) -> Vec<usize> {
+    info.iter()
+        .enumerate()
+        .filter_map(|(i, &v)| if v > 0 { Some(i) } else { None })
+        .collect()
+}
+
+/// Add every system whose solution contains NaN or infinity to
+/// `singular_indices`, keeping it sorted and free of duplicates.
+fn flag_non_finite(solutions: &[f64], n: usize, singular_indices: &mut Vec<usize>) {
+    if n == 0 {
+        return;
+    }
+    for (i, x) in solutions.chunks_exact(n).enumerate() {
+        if x.iter().any(|v| !v.is_finite()) {
+            singular_indices.push(i);
+        }
+    }
+    singular_indices.sort_unstable();
+    singular_indices.dedup();
+}
+
+/// Smallest pivot magnitude the LU shaders accept, matching `batched_lu.wgsl`.
+const PIVOT_THRESHOLD: f64 = 1e-10;
+
+/// Re-solve the flagged systems in f64 with faer, writing back the solution
+/// and un-flagging a system only when every pivot clears
+/// [`PIVOT_THRESHOLD`] and the solution is finite. Singular and
+/// near-singular systems stay flagged.
+fn resolve_on_cpu(
+    matrices: &[f64],
+    rhs: &[f64],
+    n: usize,
+    solutions: &mut [f64],
+    singular_indices: &mut Vec<usize>,
+) {
+    singular_indices.retain(|&i| {
+        // Column-major, matching the input layout.
+        let a = &matrices[i * n * n..(i + 1) * n * n];
+        let matrix = Mat::<f64>::from_fn(n, n, |row, col| a[col * n + row]);
+        let b = Col::<f64>::from_fn(n, |row| rhs[i * n + row]);
+        let lu = matrix.partial_piv_lu();
+        let u = lu.U();
+        if (0..n).any(|j| u[(j, j)].abs() <= PIVOT_THRESHOLD) {
+            return true;
+        }
+
+        let x = lu.solve(&b);
+        if (0..n).any(|j| !x[j].is_finite()) {
+            return true;
+        }
+        for (j, out) in solutions[i * n..(i + 1) * n].iter_mut().enumerate() {
+            *out = x[j];
+        }
+        false
+    });
 }
 
 #[cfg(test)]
@@ -635,6 +1189,20 @@ mod tests {
         WgpuContext::new().ok().map(Arc::new)
     }
 
+    /// One solver per thread mapping, so each test covers both kernels.
+    fn solvers_for_each_mapping(ctx: Arc<WgpuContext>) -> Vec<MetalBatchedLuSolver> {
+        [LuMapping::ThreadPerMatrix, LuMapping::Cooperative]
+            .into_iter()
+            .map(|mapping| {
+                let config = GpuBatchConfig {
+                    mapping: Some(mapping),
+                    ..Default::default()
+                };
+                MetalBatchedLuSolver::with_config(ctx.clone(), config).unwrap()
+            })
+            .collect()
+    }
+
     #[test]
     fn test_batched_lu_identity() {
         let ctx = match try_create_context() {
@@ -645,49 +1213,50 @@ mod tests {
             }
         };
 
-        let solver = MetalBatchedLuSolver::new(ctx).unwrap();
-        let n = 2;
-        let batch_size = 2;
+        for solver in solvers_for_each_mapping(ctx) {
+            let n = 2;
+            let batch_size = 2;
 
-        // Two 2x2 identity matrices in column-major order
-        let matrices = vec![
-            1.0, 0.0, 0.0, 1.0, // Identity 0
-            1.0, 0.0, 0.0, 1.0, // Identity 1
-        ];
+            // Two 2x2 identity matrices in column-major order
+            let matrices = vec![
+                1.0, 0.0, 0.0, 1.0, // Identity 0
+                1.0, 0.0, 0.0, 1.0, // Identity 1
+            ];
 
-        let rhs = vec![
-            1.0, 2.0, // b0 = [1, 2]
-            3.0, 4.0, // b1 = [3, 4]
-        ];
+            let rhs = vec![
+                1.0, 2.0, // b0 = [1, 2]
+                3.0, 4.0, // b1 = [3, 4]
+            ];
 
-        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+            let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
 
-        assert_eq!(result.batch_size, 2);
-        assert!(result.singular_indices.is_empty());
+            assert_eq!(result.batch_size, 2);
+            assert!(result.singular_indices.is_empty());
 
-        let sol0 = result.solution(0).unwrap();
-        assert!(
-            (sol0[0] - 1.0).abs() < 1e-4,
-            "sol0[0] = {} (expected 1.0)",
-            sol0[0]
-        );
-        assert!(
-            (sol0[1] - 2.0).abs() < 1e-4,
-            "sol0[1] = {} (expected 2.0)",
-            sol0[1]
-        );
+            let sol0 = result.solution(0).unwrap();
+            assert!(
+                (sol0[0] - 1.0).abs() < 1e-4,
+                "sol0[0] = {} (expected 1.0)",
+                sol0[0]
+            );
+            assert!(
+                (sol0[1] - 2.0).abs() < 1e-4,
+                "sol0[1] = {} (expected 2.0)",
+                sol0[1]
+            );
 
-        let sol1 = result.solution(1).unwrap();
-        assert!(
-            (sol1[0] - 3.0).abs() < 1e-4,
-            "sol1[0] = {} (expected 3.0)",
-            sol1[0]
-        );
-        assert!(
-            (sol1[1] - 4.0).abs() < 1e-4,
-            "sol1[1] = {} (expected 4.0)",
-            sol1[1]
-        );
+            let sol1 = result.solution(1).unwrap();
+            assert!(
+                (sol1[0] - 3.0).abs() < 1e-4,
+                "sol1[0] = {} (expected 3.0)",
+                sol1[0]
+            );
+            assert!(
+                (sol1[1] - 4.0).abs() < 1e-4,
+                "sol1[1] = {} (expected 4.0)",
+                sol1[1]
+            );
+        }
     }
 
     #[test]
@@ -700,35 +1269,366 @@ mod tests {
             }
         };
 
-        let solver = MetalBatchedLuSolver::new(ctx).unwrap();
+        for solver in solvers_for_each_mapping(ctx) {
+            let n = 2;
+            let batch_size = 1;
+
+            // Matrix: [[2, 1], [1, 3]] in column-major: [2, 1, 1, 3]
+            // Solving Ax = b where b = [5, 5]
+            // Solution should be x = [2, 1]
+            let matrices = vec![2.0, 1.0, 1.0, 3.0];
+            let rhs = vec![5.0, 5.0];
+
+            let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+
+            assert!(result.singular_indices.is_empty());
+
+            let sol = result.solution(0).unwrap();
+            assert!(
+                (sol[0] - 2.0).abs() < 1e-4,
+                "x[0] = {} (expected 2.0)",
+                sol[0]
+            );
+            assert!(
+                (sol[1] - 1.0).abs() < 1e-4,
+                "x[1] = {} (expected 1.0)",
+                sol[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_batched_lu_singular() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        for solver in solvers_for_each_mapping(ctx) {
+            let n = 2;
+            let batch_size = 2;
+
+            // Matrix 0: identity (non-singular)
+            // Matrix 1: [[1, 2], [1, 2]] (singular - rows are identical) in column-major: [1, 1, 2, 2]
+            let matrices = vec![
+                1.0, 0.0, 0.0, 1.0, // Identity
+                1.0, 1.0, 2.0, 2.0, // Singular
+            ];
+            let rhs = vec![1.0, 2.0, 1.0, 2.0];
+
+            let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+
+            assert!(
+                result.is_singular(1),
+                "Matrix 1 should be detected as singular"
+            );
+            assert!(!result.is_singular(0), "Matrix 0 should not be singular");
+        }
+    }
+
+    /// Two systems: an identity, and `1e39 · I` with RHS `1e39`, whose
+    /// entries overflow f32 but whose f64 solution is all ones.
+    fn overflow_batch() -> (Vec<f64>, Vec<f64>) {
+        let matrices = vec![
+            1.0, 0.0, 0.0, 1.0, // Identity
+            1e39, 0.0, 0.0, 1e39, // Overflows in f32
+        ];
+        let rhs = vec![1.0, 2.0, 1e39, 1e39];
+        (matrices, rhs)
+    }
+
+    #[test]
+    fn test_non_finite_solutions_resolved_on_cpu() {
+        let (matrices, rhs) = overflow_batch();
         let n = 2;
-        let batch_size = 1;
 
-        // Matrix: [[2, 1], [1, 3]] in column-major: [2, 1, 1, 3]
-        // Solving Ax = b where b = [5, 5]
-        // Solution should be x = [2, 1]
-        let matrices = vec![2.0, 1.0, 1.0, 3.0];
-        let rhs = vec![5.0, 5.0];
+        // What the GPU reads back: inf / inf = NaN in the second system.
+        let mut solutions = vec![1.0, 2.0, f64::NAN, f64::NAN];
+        let mut singular_indices = vec![];
+        flag_non_finite(&solutions, n, &mut singular_indices);
+        assert_eq!(singular_indices, vec![1]);
+
+        resolve_on_cpu(&matrices, &rhs, n, &mut solutions, &mut singular_indices);
+        assert!(singular_indices.is_empty());
+        assert_eq!(solutions, vec![1.0, 2.0, 1.0, 1.0]);
+
+        // A singular system stays flagged after the CPU attempt.
+        let matrices = vec![1.0, 1.0, 2.0, 2.0];
+        let mut solutions = vec![f64::INFINITY, 0.0];
+        let mut singular_indices = vec![0];
+        flag_non_finite(&solutions, n, &mut singular_indices);
+        resolve_on_cpu(
+            &matrices,
+            &rhs[..2],
+            n,
+            &mut solutions,
+            &mut singular_indices,
+        );
+        assert_eq!(singular_indices, vec![0]);
+
+        // A near-singular system has a finite f64 solution but a pivot below
+        // the shader threshold, so it stays flagged too.
+        let matrices = vec![1.0, 1.0, 1.0, 1.0 + 1e-13];
+        let mut solutions = vec![f64::NAN, f64::NAN];
+        let mut singular_indices = vec![0];
+        resolve_on_cpu(
+            &matrices,
+            &rhs[..2],
+            n,
+            &mut solutions,
+            &mut singular_indices,
+        );
+        assert_eq!(singular_indices, vec![0]);
+        assert!(solutions[0].is_nan());
+    }
+
+    #[test]
+    fn test_batched_lu_f32_overflow() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        let (matrices, rhs) = overflow_batch();
 
-        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+        let config = GpuBatchConfig {
+            cpu_fallback: false,
+            ..Default::default()
+        };
+        let solver = MetalBatchedLuSolver::with_config(ctx.clone(), config).unwrap();
+        let result = solver.solve_batch(&matrices, &rhs, 2, 2).unwrap();
+        assert_eq!(result.singular_indices, vec![1]);
 
+        let solver = MetalBatchedLuSolver::new(ctx).unwrap();
+        let result = solver.solve_batch(&matrices, &rhs, 2, 2).unwrap();
         assert!(result.singular_indices.is_empty());
+        let sol = result.solution(1).unwrap();
+        assert!((sol[0] - 1.0).abs() < 1e-12 && (sol[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_batched_lu_double_single_hilbert() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        // 8x8 Hilbert matrix, condition number ~1.5e10
+        let n = 8;
+        let hilbert = |row: usize, col: usize| 1.0 / (row + col + 1) as f64;
+        let matrices: Vec<f64> = (0..n * n).map(|k| hilbert(k % n, k / n)).collect();
+        let expected: Vec<f64> = (0..n).map(|i| 1.0 + 0.1 * i as f64).collect();
+        let rhs: Vec<f64> = (0..n)
+            .map(|row| (0..n).map(|col| hilbert(row, col) * expected[col]).sum())
+            .collect();
+
+        let max_error = |use_double_single| {
+            let config = GpuBatchConfig {
+                cpu_fallback: false,
+                use_double_single,
+                ..Default::default()
+            };
+            let solver = MetalBatchedLuSolver::with_config(ctx.clone(), config).unwrap();
+            let result = solver.solve_batch(&matrices, &rhs, n, 1).unwrap();
+            result
+                .solutions
+                .iter()
+                .zip(&expected)
+                .map(|(x, e)| (x - e).abs())
+                .fold(0.0, f64::max)
+        };
 
-        let sol = result.solution(0).unwrap();
+        let f32_error = max_error(false);
+        let ds_error = max_error(true);
+        assert!(ds_error < 1e-4, "double-single error {ds_error}");
         assert!(
-            (sol[0] - 2.0).abs() < 1e-4,
-            "x[0] = {} (expected 2.0)",
-            sol[0]
+            f32_error.is_nan() || f32_error > 100.0 * ds_error,
+            "f32 error {f32_error} vs double-single error {ds_error}"
         );
-        assert!(
-            (sol[1] - 1.0).abs() < 1e-4,
-            "x[1] = {} (expected 1.0)",
-            sol[1]
+    }
+
+    #[test]
+    fn test_double_single_rejects_unsupported_paths() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        let matrices = [2.0, 0.0, 0.0, 2.0];
+        let rhs = [1.0, 1.0];
+        let config = GpuBatchConfig {
+            use_double_single: true,
+            ..Default::default()
+        };
+        let solver = MetalBatchedLuSolver::with_config(ctx.clone(), config.clone()).unwrap();
+        assert!(matches!(
+            solver.factor_batch(&matrices, 2, 1),
+            Err(WgpuError::Unsupported(_))
+        ));
+
+        let config = GpuBatchConfig {
+            mapping: Some(LuMapping::Cooperative),
+            ..config
+        };
+        let solver = MetalBatchedLuSolver::with_config(ctx, config).unwrap();
+        assert!(matches!(
+            solver.solve_batch(&matrices, &rhs, 2, 1),
+            Err(WgpuError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_batched_lu_timings() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        let (matrices, rhs) = overflow_batch();
+
+        let solver = MetalBatchedLuSolver::new(ctx.clone()).unwrap();
+        let result = solver.solve_batch(&matrices, &rhs, 2, 2).unwrap();
+        assert!(result.timings.is_none());
+
+        let config = GpuBatchConfig {
+            collect_timings: true,
+            ..Default::default()
+        };
+        let solver = MetalBatchedLuSolver::with_config(ctx, config).unwrap();
+        let result = solver.solve_batch(&matrices, &rhs, 2, 2).unwrap();
+        let timings = result.timings.unwrap();
+        assert!(timings.total() > Duration::ZERO);
+        assert_eq!(
+            timings.total(),
+            timings.upload + timings.compute + timings.readback
         );
     }
 
     #[test]
-    fn test_batched_lu_singular() {
+    fn test_batched_lu_mappings_agree_on_large_matrices() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        // Larger than COOPERATIVE_THREADS, so every cooperative loop strides.
+        let n = 100;
+        let batch_size = 3;
+        let mut matrices = Vec::with_capacity(batch_size * n * n);
+        for b in 0..batch_size {
+            for col in 0..n {
+                for row in 0..n {
+                    let v = if row == col {
+                        n as f64
+                    } else {
+                        ((row * 7 + col * 3 + b) % 11) as f64 / 11.0 - 0.5
+                    };
+                    matrices.push(v);
+                }
+            }
+        }
+        let rhs: Vec<f64> = (0..batch_size * n).map(|i| (i % 13) as f64).collect();
+
+        let mut expected = rhs.clone();
+        let mut singular = vec![0, 1, 2];
+        resolve_on_cpu(&matrices, &rhs, n, &mut expected, &mut singular);
+        assert!(singular.is_empty());
+
+        for solver in solvers_for_each_mapping(ctx) {
+            let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+            assert!(result.singular_indices.is_empty());
+            for (x, e) in result.solutions.iter().zip(&expected) {
+                assert!(
+                    (x - e).abs() < 1e-4,
+                    "{:?}: {x} (expected {e})",
+                    result.mapping
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pivot_modes_agree_on_diagonally_dominant_batch() {
+        let ctx = match try_create_context() {
+            Some(c) => c,
+            None => {
+                eprintln!("Skipping test: no GPU available");
+                return;
+            }
+        };
+
+        // Column diagonally dominant, so partial pivoting never swaps rows
+        // and both modes run the same elimination
+        let n = 24;
+        let batch_size = 5;
+        let mut matrices = Vec::with_capacity(batch_size * n * n);
+        for b in 0..batch_size {
+            for col in 0..n {
+                for row in 0..n {
+                    let v = if row == col {
+                        n as f64 + b as f64
+                    } else {
+                        ((row * 5 + col * 3 + b) % 7) as f64 / 7.0 - 0.5
+                    };
+                    matrices.push(v);
+                }
+            }
+        }
+        let rhs: Vec<f64> = (0..batch_size * n).map(|i| (i % 9) as f64 - 4.0).collect();
+
+        for mapping in [LuMapping::ThreadPerMatrix, LuMapping::Cooperative] {
+            let solve = |pivoting| {
+                let config = GpuBatchConfig {
+                    mapping: Some(mapping),
+                    pivoting,
+                    ..Default::default()
+                };
+                let solver = MetalBatchedLuSolver::with_config(ctx.clone(), config).unwrap();
+                solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap()
+            };
+            let partial = solve(PivotMode::Partial);
+            let unpivoted = solve(PivotMode::None);
+
+            assert!(partial.singular_indices.is_empty());
+            assert!(unpivoted.singular_indices.is_empty());
+            assert_eq!(partial.solutions, unpivoted.solutions, "{mapping:?}");
+        }
+
+        // Without pivoting a zero diagonal is reported, not swapped away
+        let swap = [0.0, 1.0, 1.0, 0.0];
+        let solve = |pivoting| {
+            let config = GpuBatchConfig {
+                cpu_fallback: false,
+                pivoting,
+                ..Default::default()
+            };
+            let solver = MetalBatchedLuSolver::with_config(ctx.clone(), config).unwrap();
+            solver.solve_batch(&swap, &[2.0, 3.0], 2, 1).unwrap()
+        };
+        assert!(solve(PivotMode::Partial).singular_indices.is_empty());
+        assert_eq!(solve(PivotMode::None).singular_indices, vec![0]);
+    }
+
+    #[test]
+    fn test_factor_then_solve_matches_one_shot() {
         let ctx = match try_create_context() {
             Some(c) => c,
             None => {
@@ -738,24 +1638,58 @@ mod tests {
         };
 
         let solver = MetalBatchedLuSolver::new(ctx).unwrap();
-        let n = 2;
+        let n = 3;
         let batch_size = 2;
 
-        // Matrix 0: identity (non-singular)
-        // Matrix 1: [[1, 2], [1, 2]] (singular - rows are identical) in column-major: [1, 1, 2, 2]
+        // Matrix 0 needs pivoting (zero leading entry); matrix 1 is singular.
+        // Column-major.
         let matrices = vec![
-            1.0, 0.0, 0.0, 1.0, // Identity
-            1.0, 1.0, 2.0, 2.0, // Singular
+            0.0, 2.0, 1.0, 1.0, 1.0, 3.0, 4.0, 0.0, 1.0, // [[0,1,4],[2,1,0],[1,3,1]]
+            1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0, // Column 1 is twice column 0
         ];
-        let rhs = vec![1.0, 2.0, 1.0, 2.0];
+        let factorization = solver.factor_batch(&matrices, n, batch_size).unwrap();
+        assert_eq!(factorization.singular_indices(), &[1]);
+
+        for k in 0..3 {
+            let rhs: Vec<f64> = (0..batch_size * n).map(|i| (i + k) as f64 - 2.0).collect();
+            let reused = solver.solve_with_factors(&factorization, &rhs).unwrap();
+            let one_shot = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+
+            assert!(reused.is_singular(1));
+            let (x, e) = (reused.solution(0).unwrap(), one_shot.solution(0).unwrap());
+            for j in 0..n {
+                assert!((x[j] - e[j]).abs() < 1e-4, "rhs {k}: {x:?} vs {e:?}");
+            }
+        }
 
-        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+        assert!(solver.solve_with_factors(&factorization, &[1.0]).is_err());
+    }
 
-        assert!(
-            result.is_singular(1),
-            "Matrix 1 should be detected as singular"
-        );
-        assert!(!result.is_singular(0), "Matrix 0 should not be singular");
+    #[test]
+    fn test_mapping_selection() {
+        // Small batches of large matrices share threads per matrix.
+        assert_eq!(LuMapping::select(128, 10), LuMapping::Cooperative);
+        // Small matrices, or batches that fill the GPU, do not.
+        assert_eq!(LuMapping::select(8, 10), LuMapping::ThreadPerMatrix);
+        assert_eq!(LuMapping::select(128, 4096), LuMapping::ThreadPerMatrix);
+
+        let config = GpuBatchConfig {
+            mapping: Some(LuMapping::ThreadPerMatrix),
+            ..Default::default()
+        };
+        assert_eq!(config.mapping_for(128, 10), LuMapping::ThreadPerMatrix);
+
+        // Double-single defaults to its only kernel but keeps an explicit mapping.
+        let config = GpuBatchConfig {
+            use_double_single: true,
+            ..Default::default()
+        };
+        assert_eq!(config.mapping_for(128, 10), LuMapping::ThreadPerMatrix);
+        let config = GpuBatchConfig {
+            mapping: Some(LuMapping::Cooperative),
+            ..config
+        };
+        assert_eq!(config.mapping_for(8, 4096), LuMapping::Cooperative);
     }
 
     #[test]
@@ -1,8 +1,20 @@
 // Batched LU factorization and solve compute shader.
 //
 // Each workgroup processes one matrix in the batch.
-// Uses Doolittle's LU decomposition with partial pivoting.
-// Operates directly on global memory (no workgroup shared memory for simplicity).
+// Uses Doolittle's LU decomposition with partial pivoting, or without
+// pivoting when uniforms.pivoting is 0 (for diagonally dominant matrices,
+// where the pivot search only costs time and divergence).
+// Operates directly on global memory; workgroup memory is only used for
+// reductions in the cooperative entry point.
+//
+// Entry points:
+// - main: one thread per matrix, for batches large enough to fill the GPU
+// - main_cooperative: COOP_THREADS threads per matrix share each step's
+//   pivot search, row updates and back-substitution sums
+// - factor / solve_factored: the two halves of main, split so one
+//   factorization serves many right-hand sides (pivots recorded in binding 4)
+// - main_ds: main in double-single arithmetic; matrices and rhs hold the
+//   high f32 words of every value followed by the low words
 //
 // Layout:
 // - matrices: batch_size matrices with row_stride padding for coalesced access
@@ -14,12 +26,15 @@ struct Uniforms {
     batch_size: u32,
     row_stride: u32,    // Padded row stride (aligned to warp size)
     matrix_stride: u32, // Padded matrix size (row_stride * n)
+    pivoting: u32,      // 1 = partial pivoting, 0 = take each diagonal as is
 }
 
 @group(0) @binding(0) var<uniform> uniforms: Uniforms;
 @group(0) @binding(1) var<storage, read_write> matrices: array<f32>;
 @group(0) @binding(2) var<storage, read_write> rhs: array<f32>;
 @group(0) @binding(3) var<storage, read_write> info: array<i32>;
+// Pivot row chosen at each step: batch_size * n entries (factor/solve_factored only)
+@group(0) @binding(4) var<storage, read_write> pivots: array<u32>;
 
 // Get matrix element A[row, col] from global memory using stride-based layout
 fn get_a(batch_idx: u32, row: u32, col: u32) -> f32 {
@@ -56,15 +71,18 @@ fn main(@builtin(workgroup_id) workgroup_id: vec3<u32>) {
 
     // LU factorization with partial pivoting
     for (var k = 0u; k < n; k = k + 1u) {
-        // Find pivot (largest absolute value in column k, rows k to n-1)
+        // Find pivot (largest absolute value in column k, rows k to n-1).
+        // Without pivoting the diagonal is used, and still checked below.
         var max_val = abs(get_a(batch_idx, k, k));
         var max_row = k;
 
-        for (var i = k + 1u; i < n; i = i + 1u) {
-            let val = abs(get_a(batch_idx, i, k));
-            if (val > max_val) {
-                max_val = val;
-                max_row = i;
+        if (uniforms.pivoting != 0u) {
+            for (var i = k + 1u; i < n; i = i + 1u) {
+                let val = abs(get_a(batch_idx, i, k));
+                if (val > max_val) {
+                    max_val = val;
+                    max_row = i;
+                }
             }
         }
 
@@ -126,3 +144,401 @@ fn main(@builtin(workgroup_id) workgroup_id: vec3<u32>) {
 
     info[batch_idx] = singular_row;
 }
+
+// Threads per workgroup in main_cooperative (MetalBatchedLuSolver's
+// COOPERATIVE_THREADS must match).
+const COOP_THREADS: u32 = 64u;
+
+var<workgroup> red_val: array<f32, 64>;
+var<workgroup> red_idx: array<u32, 64>;
+
+// Tree-reduce red_val/red_idx to the largest value, ties to the lowest row.
+fn reduce_argmax(t: u32) {
+    for (var s = COOP_THREADS / 2u; s > 0u; s = s / 2u) {
+        if (t < s) {
+            let v = red_val[t + s];
+            let idx = red_idx[t + s];
+            if (v > red_val[t] || (v == red_val[t] && idx < red_idx[t])) {
+                red_val[t] = v;
+                red_idx[t] = idx;
+            }
+        }
+        workgroupBarrier();
+    }
+}
+
+// Tree-reduce red_val to its sum.
+fn reduce_sum(t: u32) {
+    for (var s = COOP_THREADS / 2u; s > 0u; s = s / 2u) {
+        if (t < s) {
+            red_val[t] = red_val[t] + red_val[t + s];
+        }
+        workgroupBarrier();
+    }
+}
+
+@compute @workgroup_size(64)
+fn main_cooperative(
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+) {
+    let batch_idx = workgroup_id.x;
+    let t = local_id.x;
+    let n = uniforms.n;
+
+    if (batch_idx >= uniforms.batch_size) {
+        return;
+    }
+
+    var singular_row: i32 = 0;
+
+    for (var k = 0u; k < n; k = k + 1u) {
+        // Pivot search: each thread scans a strided slice of column k.
+        // The flag is uniform, so the whole workgroup takes the same branch.
+        var max_val = abs(get_a(batch_idx, k, k));
+        var max_row = k;
+        if (uniforms.pivoting != 0u) {
+            var best_val = -1.0;
+            var best_row = k;
+            for (var i = k + t; i < n; i = i + COOP_THREADS) {
+                let val = abs(get_a(batch_idx, i, k));
+                if (val > best_val) {
+                    best_val = val;
+                    best_row = i;
+                }
+            }
+            red_val[t] = best_val;
+            red_idx[t] = best_row;
+            workgroupBarrier();
+            reduce_argmax(t);
+
+            max_val = red_val[0];
+            max_row = red_idx[0];
+        }
+        if (max_val < 1e-10) {
+            singular_row = i32(k + 1u);
+        }
+        // Everyone has read the pivot before red_val is reused
+        workgroupBarrier();
+
+        // Swap rows k and max_row, columns split across threads
+        if (max_row != k) {
+            for (var j = t; j < n; j = j + COOP_THREADS) {
+                let tmp = get_a(batch_idx, k, j);
+                set_a(batch_idx, k, j, get_a(batch_idx, max_row, j));
+                set_a(batch_idx, max_row, j, tmp);
+            }
+            if (t == 0u) {
+                let tmp_b = get_b(batch_idx, k);
+                set_b(batch_idx, k, get_b(batch_idx, max_row));
+                set_b(batch_idx, max_row, tmp_b);
+            }
+        }
+        storageBarrier();
+        workgroupBarrier();
+
+        // Gaussian elimination, rows split across threads; row k is read-only
+        let diag = get_a(batch_idx, k, k);
+        if (abs(diag) > 1e-10) {
+            let bk = get_b(batch_idx, k);
+            for (var i = k + 1u + t; i < n; i = i + COOP_THREADS) {
+                let factor = get_a(batch_idx, i, k) / diag;
+                set_a(batch_idx, i, k, factor);  // Store L factor
+
+                for (var j = k + 1u; j < n; j = j + 1u) {
+                    let aij = get_a(batch_idx, i, j);
+                    let akj = get_a(batch_idx, k, j);
+                    set_a(batch_idx, i, j, aij - factor * akj);
+                }
+
+                let bi = get_b(batch_idx, i);
+                set_b(batch_idx, i, bi - factor * bk);
+            }
+        }
+        storageBarrier();
+        workgroupBarrier();
+    }
+
+    // Backward substitution, each row's dot product split across threads
+    for (var i_plus_one = n; i_plus_one > 0u; i_plus_one = i_plus_one - 1u) {
+        let i = i_plus_one - 1u;
+
+        var partial = 0.0;
+        for (var j = i + 1u + t; j < n; j = j + COOP_THREADS) {
+            partial = partial + get_a(batch_idx, i, j) * get_b(batch_idx, j);
+        }
+        red_val[t] = partial;
+        workgroupBarrier();
+        reduce_sum(t);
+
+        if (t == 0u) {
+            let sum = get_b(batch_idx, i) - red_val[0];
+            let diag = get_a(batch_idx, i, i);
+            if (abs(diag) > 1e-10) {
+                set_b(batch_idx, i, sum / diag);
+            } else {
+                set_b(batch_idx, i, 0.0);
+            }
+        }
+        storageBarrier();
+        workgroupBarrier();
+    }
+
+    if (t == 0u) {
+        info[batch_idx] = singular_row;
+    }
+}
+
+// LU factorization only: same elimination as main, but recording the pivot
+// rows instead of applying them to a right-hand side.
+@compute @workgroup_size(1)
+fn factor(@builtin(workgroup_id) workgroup_id: vec3<u32>) {
+    let batch_idx = workgroup_id.x;
+    let n = uniforms.n;
+
+    if (batch_idx >= uniforms.batch_size) {
+        return;
+    }
+
+    var singular_row: i32 = 0;
+
+    for (var k = 0u; k < n; k = k + 1u) {
+        var max_val = abs(get_a(batch_idx, k, k));
+        var max_row = k;
+
+        if (uniforms.pivoting != 0u) {
+            for (var i = k + 1u; i < n; i = i + 1u) {
+                let val = abs(get_a(batch_idx, i, k));
+                if (val > max_val) {
+                    max_val = val;
+                    max_row = i;
+                }
+            }
+        }
+
+        if (max_val < 1e-10) {
+            singular_row = i32(k + 1u);
+        }
+
+        pivots[batch_idx * n + k] = max_row;
+
+        // Swap whole rows, so the stored L factors follow the permutation
+        if (max_row != k) {
+            for (var j = 0u; j < n; j = j + 1u) {
+                let tmp = get_a(batch_idx, k, j);
+                set_a(batch_idx, k, j, get_a(batch_idx, max_row, j));
+                set_a(batch_idx, max_row, j, tmp);
+            }
+        }
+
+        let diag = get_a(batch_idx, k, k);
+        if (abs(diag) > 1e-10) {
+            for (var i = k + 1u; i < n; i = i + 1u) {
+                let factor = get_a(batch_idx, i, k) / diag;
+                set_a(batch_idx, i, k, factor);  // Store L factor
+
+                for (var j = k + 1u; j < n; j = j + 1u) {
+                    let aij = get_a(batch_idx, i, j);
+                    let akj = get_a(batch_idx, k, j);
+                    set_a(batch_idx, i, j, aij - factor * akj);
+                }
+            }
+        }
+    }
+
+    info[batch_idx] = singular_row;
+}
+
+// Solve with factors from `factor`: apply the row interchanges, then
+// Ly = Pb (unit lower triangle) and Ux = y.
+@compute @workgroup_size(1)
+fn solve_factored(@builtin(workgroup_id) workgroup_id: vec3<u32>) {
+    let batch_idx = workgroup_id.x;
+    let n = uniforms.n;
+
+    if (batch_idx >= uniforms.batch_size) {
+        return;
+    }
+
+    for (var k = 0u; k < n; k = k + 1u) {
+        let p = pivots[batch_idx * n + k];
+        if (p != k) {
+            let tmp_b = get_b(batch_idx, k);
+            set_b(batch_idx, k, get_b(batch_idx, p));
+            set_b(batch_idx, p, tmp_b);
+        }
+    }
+
+    // Forward substitution: Ly = Pb
+    for (var i = 1u; i < n; i = i + 1u) {
+        var sum = get_b(batch_idx, i);
+        for (var j = 0u; j < i; j = j + 1u) {
+            sum = sum - get_a(batch_idx, i, j) * get_b(batch_idx, j);
+        }
+        set_b(batch_idx, i, sum);
+    }
+
+    // Backward substitution: Ux = y
+    for (var i_plus_one = n; i_plus_one > 0u; i_plus_one = i_plus_one - 1u) {
+        let i = i_plus_one - 1u;
+        var sum = get_b(batch_idx, i);
+
+        for (var j = i + 1u; j < n; j = j + 1u) {
+            sum = sum - get_a(batch_idx, i, j) * get_b(batch_idx, j);
+        }
+
+        let diag = get_a(batch_idx, i, i);
+        if (abs(diag) > 1e-10) {
+            set_b(batch_idx, i, sum / diag);
+        } else {
+            set_b(batch_idx, i, 0.0);
+        }
+    }
+}
+
+// Double-single arithmetic: a value is the unevaluated sum hi + lo of two
+// f32s (vec2 x = hi, y = lo), giving about 48 mantissa bits. The error-free
+// transforms below assume round-to-nearest f32 operations that are not
+// reassociated, and a fused fma.
+
+fn two_sum(a: f32, b: f32) -> vec2<f32> {
+    let s = a + b;
+    let bb = s - a;
+    return vec2<f32>(s, (a - (s - bb)) + (b - bb));
+}
+
+// two_sum for |a| >= |b|
+fn quick_two_sum(a: f32, b: f32) -> vec2<f32> {
+    let s = a + b;
+    return vec2<f32>(s, b - (s - a));
+}
+
+fn two_prod(a: f32, b: f32) -> vec2<f32> {
+    let p = a * b;
+    return vec2<f32>(p, fma(a, b, -p));
+}
+
+fn ds_add(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    var s = two_sum(a.x, b.x);
+    let t = two_sum(a.y, b.y);
+    s = quick_two_sum(s.x, s.y + t.x);
+    return quick_two_sum(s.x, s.y + t.y);
+}
+
+fn ds_sub(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    return ds_add(a, -b);
+}
+
+fn ds_mul(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    let p = two_prod(a.x, b.x);
+    return quick_two_sum(p.x, p.y + (a.x * b.y + a.y * b.x));
+}
+
+fn ds_div(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    let q1 = a.x / b.x;
+    let r = ds_sub(a, ds_mul(b, vec2<f32>(q1, 0.0)));
+    let q2 = r.x / b.x;
+    return quick_two_sum(q1, q2);
+}
+
+// The low words start after batch_size padded matrices / rhs vectors
+fn get_a_ds(batch_idx: u32, row: u32, col: u32) -> vec2<f32> {
+    let idx = batch_idx * uniforms.matrix_stride + row * uniforms.row_stride + col;
+    return vec2<f32>(matrices[idx], matrices[idx + uniforms.batch_size * uniforms.matrix_stride]);
+}
+
+fn set_a_ds(batch_idx: u32, row: u32, col: u32, val: vec2<f32>) {
+    let idx = batch_idx * uniforms.matrix_stride + row * uniforms.row_stride + col;
+    matrices[idx] = val.x;
+    matrices[idx + uniforms.batch_size * uniforms.matrix_stride] = val.y;
+}
+
+fn get_b_ds(batch_idx: u32, i: u32) -> vec2<f32> {
+    let idx = batch_idx * uniforms.n + i;
+    return vec2<f32>(rhs[idx], rhs[idx + uniforms.batch_size * uniforms.n]);
+}
+
+fn set_b_ds(batch_idx: u32, i: u32, val: vec2<f32>) {
+    let idx = batch_idx * uniforms.n + i;
+    rhs[idx] = val.x;
+    rhs[idx + uniforms.batch_size * uniforms.n] = val.y;
+}
+
+// main in double-single arithmetic. Pivots are compared on the high words.
+@compute @workgroup_size(1)
+fn main_ds(@builtin(workgroup_id) workgroup_id: vec3<u32>) {
+    let batch_idx = workgroup_id.x;
+    let n = uniforms.n;
+
+    if (batch_idx >= uniforms.batch_size) {
+        return;
+    }
+
+    var singular_row: i32 = 0;
+
+    for (var k = 0u; k < n; k = k + 1u) {
+        var max_val = abs(get_a_ds(batch_idx, k, k).x);
+        var max_row = k;
+
+        if (uniforms.pivoting != 0u) {
+            for (var i = k + 1u; i < n; i = i + 1u) {
+                let val = abs(get_a_ds(batch_idx, i, k).x);
+                if (val > max_val) {
+                    max_val = val;
+                    max_row = i;
+                }
+            }
+        }
+
+        if (max_val < 1e-10) {
+            singular_row = i32(k + 1u);
+        }
+
+        if (max_row != k) {
+            for (var j = 0u; j < n; j = j + 1u) {
+                let tmp = get_a_ds(batch_idx, k, j);
+                set_a_ds(batch_idx, k, j, get_a_ds(batch_idx, max_row, j));
+                set_a_ds(batch_idx, max_row, j, tmp);
+            }
+            let tmp_b = get_b_ds(batch_idx, k);
+            set_b_ds(batch_idx, k, get_b_ds(batch_idx, max_row));
+            set_b_ds(batch_idx, max_row, tmp_b);
+        }
+
+        let diag = get_a_ds(batch_idx, k, k);
+        if (abs(diag.x) > 1e-10) {
+            for (var i = k + 1u; i < n; i = i + 1u) {
+                let factor = ds_div(get_a_ds(batch_idx, i, k), diag);
+                set_a_ds(batch_idx, i, k, factor);
+
+                for (var j = k + 1u; j < n; j = j + 1u) {
+                    let aij = get_a_ds(batch_idx, i, j);
+                    let akj = get_a_ds(batch_idx, k, j);
+                    set_a_ds(batch_idx, i, j, ds_sub(aij, ds_mul(factor, akj)));
+                }
+
+                let bi = get_b_ds(batch_idx, i);
+                let bk = get_b_ds(batch_idx, k);
+                set_b_ds(batch_idx, i, ds_sub(bi, ds_mul(factor, bk)));
+            }
+        }
+    }
+
+    for (var i_plus_one = n; i_plus_one > 0u; i_plus_one = i_plus_one - 1u) {
+        let i = i_plus_one - 1u;
+        var sum = get_b_ds(batch_idx, i);
+
+        for (var j = i + 1u; j < n; j = j + 1u) {
+            sum = ds_sub(sum, ds_mul(get_a_ds(batch_idx, i, j), get_b_ds(batch_idx, j)));
+        }
+
+        let diag = get_a_ds(batch_idx, i, i);
+        if (abs(diag.x) > 1e-10) {
+            set_b_ds(batch_idx, i, ds_div(sum, diag));
+        } else {
+            set_b_ds(batch_idx, i, vec2<f32>(0.0, 0.0));
+        }
+    }
+
+    info[batch_idx] = singular_row;
+}
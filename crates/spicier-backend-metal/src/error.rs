@@ -29,6 +29,8 @@ pub enum WgpuError {
     },
     /// Out of GPU memory.
     OutOfMemory(String),
+    /// The requested configuration has no kernel.
+    Unsupported(String),
 }
 
 impl fmt::Display for WgpuError {
@@ -52,6 +54,7 @@ impl fmt::Display for WgpuError {
                 )
             }
             WgpuError::OutOfMemory(msg) => write!(f, "Out of GPU memory: {}", msg),
+            WgpuError::Unsupported(msg) => write!(f, "Unsupported GPU configuration: {}", msg),
         }
     }
 }
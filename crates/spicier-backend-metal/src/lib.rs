@@ -28,8 +28,8 @@ pub use batched_gmres::{
     BatchedGmresConfig, BatchedGmresResult, GpuBatchedGmres, GpuBatchedVectorOps,
 };
 pub use batched_lu::{
-    BatchedSolveResult, GpuBatchConfig, MAX_MATRIX_SIZE, MIN_BATCH_SIZE, MIN_MATRIX_SIZE,
-    MetalBatchedLuSolver,
+    BatchTimings, BatchedFactorization, BatchedSolveResult, COOPERATIVE_THREADS, GpuBatchConfig,
+    LuMapping, MAX_MATRIX_SIZE, MIN_BATCH_SIZE, MIN_MATRIX_SIZE, MetalBatchedLuSolver, PivotMode,
 };
 pub use batched_spmv::{BatchedCsrMatrix, GpuBatchedSpmv};
 pub use buffer_pool::{BufferPool, BufferPoolStats};
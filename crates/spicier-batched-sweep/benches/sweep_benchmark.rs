@@ -168,6 +168,7 @@ fn run_metal_sweep(batch_size: usize, matrix_size: usize) {
         min_batch_size: 1,
         min_matrix_size: 1,
         max_batch_per_launch: 65535,
+        ..Default::default()
     });
 
     let _ = solve_batched_sweep_gpu(
@@ -196,6 +197,7 @@ fn run_mps_sweep(batch_size: usize, matrix_size: usize) {
         min_batch_size: 1,
         min_matrix_size: 1,
         max_batch_per_launch: 65535,
+        ..Default::default()
     });
 
     let _ = solve_batched_sweep_gpu(
@@ -273,6 +275,7 @@ fn bench_sweep_backends(c: &mut Criterion) {
                 min_batch_size: 1,
                 min_matrix_size: 1,
                 max_batch_per_launch: 65535,
+                ..Default::default()
             });
 
             if let Ok(solver) = metal_backend.create_solver() {
@@ -293,6 +296,7 @@ fn bench_sweep_backends(c: &mut Criterion) {
                 min_batch_size: 1,
                 min_matrix_size: 1,
                 max_batch_per_launch: 65535,
+                ..Default::default()
             });
 
             if let Ok(solver) = mps_backend.create_solver() {
@@ -541,6 +545,7 @@ fn bench_gpu_crossover(c: &mut Criterion) {
                 min_batch_size: 1,
                 min_matrix_size: 1,
                 max_batch_per_launch: 65535,
+                ..Default::default()
             });
 
             if let Ok(solver) = backend.create_solver() {
@@ -139,6 +139,9 @@ impl BatchedLuSolver for AccelerateBatchedSolver {
             singular_indices,
             n,
             batch_size,
+            timings: None,
+            condition_numbers: None,
+            ill_conditioned_indices: Vec::new(),
         })
     }
 
@@ -35,6 +35,17 @@
 //! let active = tracker.active_indices();
 //! assert_eq!(active.len(), 998);
 //! ```
+//!
+//! # Failure diagnostics
+//!
+//! Given the sweep points via [`ConvergenceTracker::with_parameters`], the
+//! tracker records a [`FailureReport`] whenever a point is marked `Failed` or
+//! `Singular`, carrying its parameter values and the last residual norm seen
+//! by [`ConvergenceTracker::check_residual_convergence`] (or passed to
+//! [`ConvergenceTracker::record_residual`]).
+
+use crate::error::{BatchedSweepError, Result};
+use spicier_solver::SweepPoint;
 
 /// Convergence status for a single sweep point.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -69,6 +80,35 @@ impl ConvergenceStatus {
     }
 }
 
+/// Diagnostics captured when a point is marked `Failed` or `Singular`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureReport {
+    /// Index of the point in the batch.
+    pub index: usize,
+    /// Final status (`Failed` or `Singular`).
+    pub status: ConvergenceStatus,
+    /// Iterations the point had taken.
+    pub iterations: u32,
+    /// Parameter values of the point (empty if none were given).
+    pub parameters: Vec<f64>,
+    /// Last recorded residual norm, if any.
+    pub residual_norm: Option<f64>,
+}
+
+impl std::fmt::Display for FailureReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "point {} {:?} after {} iterations",
+            self.index, self.status, self.iterations
+        )?;
+        if let Some(norm) = self.residual_norm {
+            write!(f, ", residual {:.3e}", norm)?;
+        }
+        write!(f, ", parameters {:?}", self.parameters)
+    }
+}
+
 /// Tracks convergence status for a batch of sweep points.
 ///
 /// This is the main type for managing early termination in batched NR solves.
@@ -82,6 +122,12 @@ pub struct ConvergenceTracker {
     max_iterations: u32,
     /// Number of currently active (unconverged) points.
     active_count: usize,
+    /// Parameter values of each point, if known.
+    parameters: Vec<Vec<f64>>,
+    /// Last residual norm recorded for each point.
+    residual_norms: Vec<Option<f64>>,
+    /// Reports for points marked failed or singular, in marking order.
+    failures: Vec<FailureReport>,
 }
 
 impl ConvergenceTracker {
@@ -99,7 +145,25 @@ impl ConvergenceTracker {
             iterations: vec![0; batch_size],
             max_iterations,
             active_count: batch_size,
+            parameters: Vec::new(),
+            residual_norms: vec![None; batch_size],
+            failures: Vec::new(),
+        }
+    }
+
+    /// Attach the sweep points, so failure reports carry their parameters.
+    ///
+    /// Fails if `points` does not have one entry per batch point.
+    pub fn with_parameters(mut self, points: &[SweepPoint]) -> Result<Self> {
+        if points.len() != self.batch_size() {
+            return Err(BatchedSweepError::InvalidDimension(format!(
+                "{} sweep points for a batch of {}",
+                points.len(),
+                self.batch_size()
+            )));
         }
+        self.parameters = points.iter().map(|p| p.parameters.clone()).collect();
+        Ok(self)
     }
 
     /// Total number of points in the batch.
@@ -145,6 +209,23 @@ impl ConvergenceTracker {
         self.iterations[index]
     }
 
+    /// Last residual norm recorded for a point.
+    #[inline]
+    pub fn residual_norm(&self, index: usize) -> Option<f64> {
+        self.residual_norms[index]
+    }
+
+    /// Record a point's residual norm for its failure report.
+    #[inline]
+    pub fn record_residual(&mut self, index: usize, norm: f64) {
+        self.residual_norms[index] = Some(norm);
+    }
+
+    /// Reports for every point marked failed or singular, in marking order.
+    pub fn failure_reports(&self) -> &[FailureReport] {
+        &self.failures
+    }
+
     /// Mark a point as converged.
     ///
     /// Returns true if the point was previously active.
@@ -165,6 +246,7 @@ impl ConvergenceTracker {
         if self.status[index].is_active() {
             self.status[index] = ConvergenceStatus::Failed;
             self.active_count -= 1;
+            self.record_failure(index);
             true
         } else {
             false
@@ -178,12 +260,23 @@ impl ConvergenceTracker {
         if self.status[index].is_active() {
             self.status[index] = ConvergenceStatus::Singular;
             self.active_count -= 1;
+            self.record_failure(index);
             true
         } else {
             false
         }
     }
 
+    fn record_failure(&mut self, index: usize) {
+        self.failures.push(FailureReport {
+            index,
+            status: self.status[index],
+            iterations: self.iterations[index],
+            parameters: self.parameters.get(index).cloned().unwrap_or_default(),
+            residual_norm: self.residual_norms[index],
+        });
+    }
+
     /// Increment iteration count for a point.
     ///
     /// If the point exceeds max_iterations, it's marked as failed.
@@ -315,7 +408,8 @@ impl ConvergenceTracker {
 
     /// Check convergence for multiple points based on residual norm.
     ///
-    /// Marks points as converged if their residual norm is below tolerance.
+    /// Marks points as converged if their residual norm is below tolerance,
+    /// and records each active point's norm for its failure report.
     ///
     /// # Arguments
     /// * `residuals` - Flattened array of residuals (batch_size * n)
@@ -347,7 +441,9 @@ impl ConvergenceTracker {
                 norm_sq += residuals[offset + j].powi(2);
             }
 
-            if norm_sq.sqrt() < tolerance {
+            let norm = norm_sq.sqrt();
+            self.residual_norms[i] = Some(norm);
+            if norm < tolerance {
                 self.mark_converged(i);
                 newly_converged += 1;
             }
@@ -525,6 +621,83 @@ mod tests {
         // All points should have exceeded limit and be marked failed
         assert!(tracker.all_finished());
         assert_eq!(tracker.failed_count(), 5);
+        assert_eq!(tracker.failure_reports().len(), 5);
+        assert!(tracker.failure_reports()[0].parameters.is_empty());
+    }
+
+    #[test]
+    fn test_failure_report_captures_singular_point() {
+        // Batched Newton on G·v + Is·(exp(v/Vt) - 1) = I, one scalar circuit
+        // per point with parameters [G, Is, I]. Point 2 has no conductance
+        // and no diode, so its Jacobian is exactly zero.
+        let vt = 0.025852;
+        let points: Vec<SweepPoint> = [
+            [1e-3, 1e-14, 1e-3],
+            [2e-3, 1e-14, 1e-3],
+            [0.0, 0.0, 1e-3],
+            [1e-3, 1e-12, 2e-3],
+        ]
+        .iter()
+        .map(|p| SweepPoint {
+            parameters: p.to_vec(),
+        })
+        .collect();
+        let mut tracker = ConvergenceTracker::new(points.len())
+            .with_parameters(&points)
+            .unwrap();
+        let mut v = vec![0.0; points.len()];
+
+        while !tracker.all_finished() {
+            let residuals: Vec<f64> = points
+                .iter()
+                .zip(&v)
+                .map(|(p, &v)| {
+                    let [g, is, i] = p.parameters[..] else {
+                        unreachable!()
+                    };
+                    g * v + is * ((v / vt).exp() - 1.0) - i
+                })
+                .collect();
+            tracker.check_residual_convergence(&residuals, 1, 1e-12);
+
+            for k in tracker.active_indices() {
+                let [g, is, _] = points[k].parameters[..] else {
+                    unreachable!()
+                };
+                let jacobian = g + is / vt * (v[k] / vt).exp();
+                if jacobian == 0.0 {
+                    tracker.mark_singular(k);
+                } else {
+                    v[k] -= (residuals[k] / jacobian).clamp(-0.1, 0.1);
+                }
+            }
+            tracker.increment_all_active();
+        }
+
+        assert_eq!(tracker.converged_count(), 3);
+        let reports = tracker.failure_reports();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.index, 2);
+        assert_eq!(report.status, ConvergenceStatus::Singular);
+        assert_eq!(report.iterations, 0);
+        assert_eq!(report.parameters, vec![0.0, 0.0, 1e-3]);
+        assert_eq!(report.residual_norm, Some(1e-3));
+        assert_eq!(
+            report.to_string(),
+            "point 2 Singular after 0 iterations, residual 1.000e-3, parameters [0.0, 0.0, 0.001]"
+        );
+    }
+
+    #[test]
+    fn test_with_parameters_rejects_wrong_count() {
+        let points = vec![SweepPoint {
+            parameters: vec![1.0],
+        }];
+        assert!(matches!(
+            ConvergenceTracker::new(2).with_parameters(&points),
+            Err(BatchedSweepError::InvalidDimension(_))
+        ));
     }
 
     #[test]
@@ -70,6 +70,9 @@ impl BatchedLuSolver for CudaBatchedSolver {
             singular_indices: cuda_result.singular_indices,
             n: cuda_result.n,
             batch_size: cuda_result.batch_size,
+            timings: None,
+            condition_numbers: None,
+            ill_conditioned_indices: Vec::new(),
         })
     }
 
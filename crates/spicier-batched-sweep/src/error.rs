@@ -25,6 +25,10 @@ pub enum BatchedSweepError {
     #[error("Invalid dimensions: {0}")]
     InvalidDimension(String),
 
+    /// A Monte Carlo correlation matrix is not a valid correlation matrix.
+    #[error("Invalid correlation matrix: {0}")]
+    InvalidCorrelation(String),
+
     /// Batch size exceeds backend limit.
     #[error("Batch size {size} exceeds maximum {max}")]
     BatchTooLarge { size: usize, max: usize },
@@ -33,6 +37,10 @@ pub enum BatchedSweepError {
     #[error("{} of {} matrices were singular", indices.len(), total)]
     SingularBatch { indices: Vec<usize>, total: usize },
 
+    /// A cached sparsity pattern does not match the matrices being solved.
+    #[error("Sparsity pattern mismatch: {0}")]
+    PatternMismatch(String),
+
     /// Backend-specific error.
     #[error("Backend error: {0}")]
     Backend(String),
@@ -4,9 +4,13 @@
 //! which is a modern, high-performance linear algebra library for Rust.
 //! Faer uses SIMD optimizations for efficient computation on modern CPUs.
 
+use crate::condition::{estimate_lu_condition, ill_conditioned_indices, norm1_dense};
 use crate::error::{BatchedSweepError, Result};
-use crate::solver::{BackendType, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig};
+use crate::solver::{
+    BackendType, BatchTimings, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig,
+};
 use faer::prelude::*;
+use std::time::Instant;
 
 /// Faer-backed batched LU solver.
 ///
@@ -53,6 +57,8 @@ impl BatchedLuSolver for FaerBatchedSolver {
 
         let mut solutions = Vec::with_capacity(expected_rhs_len);
         let mut singular_indices = Vec::new();
+        let mut condition_numbers = Vec::new();
+        let compute_start = Instant::now();
 
         for i in 0..batch_size {
             // Extract matrix (column-major order) - faer uses column-major
@@ -89,13 +95,33 @@ impl BatchedLuSolver for FaerBatchedSolver {
                     solutions.push(x[j]);
                 }
             }
+
+            if self.config.estimate_condition {
+                condition_numbers.push(if is_singular {
+                    f64::INFINITY
+                } else {
+                    estimate_lu_condition(&plu, norm1_dense(mat_data, n), n)
+                });
+            }
         }
 
+        let timings = self.config.collect_timings.then(|| BatchTimings {
+            compute: compute_start.elapsed(),
+            ..Default::default()
+        });
+        let ill_conditioned_indices = ill_conditioned_indices(
+            &condition_numbers,
+            &singular_indices,
+            self.config.condition_threshold,
+        );
         Ok(BatchedSolveResult {
             solutions,
             singular_indices,
             n,
             batch_size,
+            timings,
+            condition_numbers: self.config.estimate_condition.then_some(condition_numbers),
+            ill_conditioned_indices,
         })
     }
 
@@ -196,4 +222,92 @@ mod tests {
         let solver = FaerBatchedSolver::new(GpuBatchConfig::default());
         assert_eq!(solver.backend_type(), BackendType::Faer);
     }
+
+    #[test]
+    fn test_faer_solver_timings() {
+        let n = 16;
+        let batch_size = 8;
+        let mut matrices = vec![0.0; batch_size * n * n];
+        for (b, matrix) in matrices.chunks_mut(n * n).enumerate() {
+            for i in 0..n {
+                matrix[i * n + i] = 2.0 + b as f64;
+            }
+        }
+        let rhs = vec![1.0; batch_size * n];
+
+        let solver = FaerBatchedSolver::new(GpuBatchConfig::default());
+        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+        assert!(result.timings.is_none());
+
+        let solver = FaerBatchedSolver::new(GpuBatchConfig {
+            collect_timings: true,
+            ..Default::default()
+        });
+        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+        let timings = result.timings.unwrap();
+        assert!(timings.total() > std::time::Duration::ZERO);
+        assert_eq!(timings.total(), timings.compute);
+    }
+
+    #[test]
+    fn test_faer_solver_condition_estimates() {
+        let n = 3;
+        let matrices = vec![
+            // Diagonally dominant
+            4.0,
+            1.0,
+            0.0,
+            1.0,
+            4.0,
+            1.0,
+            0.0,
+            1.0,
+            4.0,
+            // Third column is the sum of the first two, up to 1e-14
+            1.0,
+            0.0,
+            1.0,
+            0.0,
+            1.0,
+            1.0,
+            1.0,
+            1.0,
+            2.0 + 1e-14,
+            // Exactly singular
+            1.0,
+            2.0,
+            3.0,
+            1.0,
+            2.0,
+            3.0,
+            0.0,
+            0.0,
+            1.0,
+        ];
+        let rhs = vec![1.0; 3 * n];
+        let solver = FaerBatchedSolver::new(GpuBatchConfig {
+            estimate_condition: true,
+            ..Default::default()
+        });
+
+        let result = solver.solve_batch(&matrices, &rhs, n, 3).unwrap();
+        let conditions = result.condition_numbers.unwrap();
+
+        // κ₁ = 6 · 24/56 for this tridiag(1, 4, 1)
+        assert!(
+            (conditions[0] - 18.0 / 7.0).abs() < 1e-12,
+            "{}",
+            conditions[0]
+        );
+        assert!(conditions[1] > 1e13, "condition {}", conditions[1]);
+        assert_eq!(result.singular_indices, vec![2]);
+        assert_eq!(conditions[2], f64::INFINITY);
+        assert_eq!(result.ill_conditioned_indices, vec![1]);
+
+        // Estimation is opt-in
+        let solver = FaerBatchedSolver::new(GpuBatchConfig::default());
+        let result = solver.solve_batch(&matrices, &rhs, n, 3).unwrap();
+        assert!(result.condition_numbers.is_none());
+        assert!(result.ill_conditioned_indices.is_empty());
+    }
 }
@@ -7,13 +7,128 @@
 //! Since all sweep points share the same circuit topology (sparsity pattern),
 //! the symbolic factorization can be computed once and reused, providing
 //! significant speedup for large sweeps.
-
+//!
+//! A [`SymbolicCache`] taken from one solver can be installed in another, so
+//! a repeated sweep over the same topology skips the symbolic step. The cache
+//! lives in memory only: faer cannot rebuild a symbolic LU from a saved
+//! column ordering, so there is no on-disk form that would save work.
+
+use crate::condition::{
+    estimate_lu_condition, ill_conditioned_indices, norm1_dense, norm1_triplets,
+};
 use crate::error::{BatchedSweepError, Result};
-use crate::solver::{BackendType, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig};
+use crate::solver::{
+    BackendType, BatchTimings, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig,
+};
 use faer::prelude::*;
 use faer::sparse::linalg::solvers::{Lu, SymbolicLu};
-use faer::sparse::{SparseColMat, Triplet};
+use faer::sparse::{SparseColMat, SymbolicSparseColMatRef, Triplet};
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+/// Symbolic LU factorization together with the sparsity pattern it was
+/// computed for.
+#[derive(Clone)]
+pub struct SymbolicCache {
+    n: usize,
+    col_ptr: Vec<usize>,
+    row_idx: Vec<usize>,
+    symbolic: SymbolicLu<usize>,
+}
+
+impl SymbolicCache {
+    /// Compute the symbolic factorization for an `n`×`n` sparsity pattern.
+    ///
+    /// Duplicate positions are merged.
+    pub fn from_pattern(n: usize, entries: &[(usize, usize)]) -> Result<Self> {
+        if let Some(&(row, col)) = entries.iter().find(|&&(r, c)| r >= n || c >= n) {
+            return Err(BatchedSweepError::InvalidDimension(format!(
+                "Entry ({}, {}) is outside a {}x{} matrix",
+                row, col, n, n
+            )));
+        }
+
+        let mut sorted: Vec<(usize, usize)> = entries.to_vec();
+        sorted.sort_by_key(|&(r, c)| (c, r));
+        sorted.dedup();
+
+        let mut col_ptr = vec![0; n + 1];
+        for &(_, col) in &sorted {
+            col_ptr[col + 1] += 1;
+        }
+        for col in 0..n {
+            col_ptr[col + 1] += col_ptr[col];
+        }
+        let row_idx = sorted.iter().map(|&(r, _)| r).collect();
+
+        Self::from_compressed(n, col_ptr, row_idx)
+    }
+
+    /// Compute the symbolic factorization for the nonzero pattern of a
+    /// column-major dense matrix.
+    pub fn from_dense(data: &[f64], n: usize) -> Result<Self> {
+        let entries: Vec<(usize, usize)> = dense_to_sparse_triplets(data, n)
+            .iter()
+            .map(|t| (t.row, t.col))
+            .collect();
+        Self::from_pattern(n, &entries)
+    }
+
+    fn from_compressed(n: usize, col_ptr: Vec<usize>, row_idx: Vec<usize>) -> Result<Self> {
+        let pattern = SymbolicSparseColMatRef::new_checked(n, n, &col_ptr, None, &row_idx);
+        let symbolic = SymbolicLu::try_new(pattern).map_err(|e| {
+            BatchedSweepError::Backend(format!("Symbolic factorization failed: {:?}", e))
+        })?;
+
+        Ok(Self {
+            n,
+            col_ptr,
+            row_idx,
+            symbolic,
+        })
+    }
+
+    /// Matrix dimension.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Number of structural nonzeros.
+    pub fn nnz(&self) -> usize {
+        self.row_idx.len()
+    }
+
+    /// Check whether a column-major dense matrix has this sparsity pattern.
+    pub fn matches_dense(&self, data: &[f64], n: usize) -> bool {
+        if n != self.n || data.len() != n * n {
+            return false;
+        }
+        let mut k = 0;
+        for col in 0..n {
+            for row in 0..n {
+                if data[col * n + row].abs() > 1e-15 {
+                    if k >= self.col_ptr[col + 1] || self.row_idx[k] != row {
+                        return false;
+                    }
+                    k += 1;
+                }
+            }
+            if k != self.col_ptr[col + 1] {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl std::fmt::Debug for SymbolicCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymbolicCache")
+            .field("n", &self.n)
+            .field("nnz", &self.nnz())
+            .finish()
+    }
+}
 
 /// Faer-backed sparse batched LU solver with symbolic caching.
 ///
@@ -34,8 +149,13 @@ pub struct FaerSparseCachedBatchedSolver {
 
 /// Internal cache for symbolic factorization.
 struct CachedSymbolic {
-    symbolic: Option<Arc<SymbolicLu<usize>>>,
-    expected_size: Option<usize>,
+    symbolic: Option<Arc<SymbolicCache>>,
+    /// Whether the cached pattern has been checked against a batch.
+    ///
+    /// False for a cache installed with
+    /// [`FaerSparseCachedBatchedSolver::set_symbolic_cache`] until the next
+    /// batch confirms it matches.
+    verified: bool,
 }
 
 impl FaerSparseCachedBatchedSolver {
@@ -45,7 +165,7 @@ impl FaerSparseCachedBatchedSolver {
             config,
             cached: RwLock::new(CachedSymbolic {
                 symbolic: None,
-                expected_size: None,
+                verified: false,
             }),
         }
     }
@@ -56,7 +176,7 @@ impl FaerSparseCachedBatchedSolver {
     pub fn reset_cache(&self) {
         let mut cache = self.cached.write().unwrap();
         cache.symbolic = None;
-        cache.expected_size = None;
+        cache.verified = false;
     }
 
     /// Check if symbolic factorization is cached.
@@ -64,6 +184,24 @@ impl FaerSparseCachedBatchedSolver {
         let cache = self.cached.read().unwrap();
         cache.symbolic.is_some()
     }
+
+    /// The cached symbolic factorization, if any, e.g. to install it in
+    /// another solver with [`set_symbolic_cache`](Self::set_symbolic_cache).
+    pub fn symbolic_cache(&self) -> Option<SymbolicCache> {
+        let cache = self.cached.read().unwrap();
+        cache.symbolic.as_deref().cloned()
+    }
+
+    /// Install a symbolic factorization, e.g. one from
+    /// [`symbolic_cache`](Self::symbolic_cache) of another solver.
+    ///
+    /// The next batch checks that its first matrix has the cached sparsity
+    /// pattern and fails with [`BatchedSweepError::PatternMismatch`] if not.
+    pub fn set_symbolic_cache(&self, symbolic: SymbolicCache) {
+        let mut cache = self.cached.write().unwrap();
+        cache.symbolic = Some(Arc::new(symbolic));
+        cache.verified = false;
+    }
 }
 
 impl BatchedLuSolver for FaerSparseCachedBatchedSolver {
@@ -97,47 +235,56 @@ impl BatchedLuSolver for FaerSparseCachedBatchedSolver {
         let mut singular_indices = Vec::new();
 
         // Try to get cached symbolic factorization, or build one
+        let symbolic_start = Instant::now();
         let symbolic = {
             // First, try to read from cache
             let cache_read = self.cached.read().unwrap();
-            if let Some(ref sym) = cache_read.symbolic {
-                if cache_read.expected_size != Some(n) {
-                    return Err(BatchedSweepError::InvalidDimension(format!(
-                        "Matrix size {} doesn't match cached size {:?}",
-                        n, cache_read.expected_size
-                    )));
+            match cache_read.symbolic.clone() {
+                Some(sym) if cache_read.verified => {
+                    if sym.size() != n {
+                        return Err(BatchedSweepError::InvalidDimension(format!(
+                            "Matrix size {} doesn't match cached size {}",
+                            n,
+                            sym.size()
+                        )));
+                    }
+                    sym
+                }
+                Some(sym) => {
+                    // Installed from outside: check it against this batch once
+                    drop(cache_read);
+                    if batch_size > 0 && !sym.matches_dense(&matrices[0..n * n], n) {
+                        return Err(BatchedSweepError::PatternMismatch(format!(
+                            "cached symbolic factorization (size {}, {} nonzeros) \
+                             does not match the matrix pattern",
+                            sym.size(),
+                            sym.nnz()
+                        )));
+                    }
+                    self.cached.write().unwrap().verified = true;
+                    sym
+                }
+                None => {
+                    // Need to build symbolic - drop read lock and acquire write lock
+                    drop(cache_read);
+
+                    // Build symbolic from first matrix
+                    let symbolic = Arc::new(SymbolicCache::from_dense(&matrices[0..n * n], n)?);
+
+                    // Cache the symbolic factorization for future batches
+                    let mut cache_write = self.cached.write().unwrap();
+                    cache_write.symbolic = Some(symbolic.clone());
+                    cache_write.verified = true;
+
+                    symbolic
                 }
-                sym.clone()
-            } else {
-                // Need to build symbolic - drop read lock and acquire write lock
-                drop(cache_read);
-
-                // Build symbolic from first matrix
-                let mat_data = &matrices[0..n * n];
-                let triplets = dense_to_sparse_triplets(mat_data, n);
-                let sparse_mat = SparseColMat::<usize, f64>::try_new_from_triplets(n, n, &triplets)
-                    .map_err(|e| {
-                        BatchedSweepError::Backend(format!(
-                            "Failed to build sparse matrix: {:?}",
-                            e
-                        ))
-                    })?;
-
-                let new_symbolic = SymbolicLu::try_new(sparse_mat.symbolic()).map_err(|e| {
-                    BatchedSweepError::Backend(format!("Symbolic factorization failed: {:?}", e))
-                })?;
-
-                let symbolic = Arc::new(new_symbolic);
-
-                // Cache the symbolic factorization for future batches
-                let mut cache_write = self.cached.write().unwrap();
-                cache_write.symbolic = Some(symbolic.clone());
-                cache_write.expected_size = Some(n);
-
-                symbolic
             }
         };
 
+        let symbolic_time = symbolic_start.elapsed();
+        let compute_start = Instant::now();
+        let mut condition_numbers = Vec::new();
+
         // Solve each system using the cached symbolic factorization
         for i in 0..batch_size {
             let mat_start = i * n * n;
@@ -146,22 +293,42 @@ impl BatchedLuSolver for FaerSparseCachedBatchedSolver {
             let rhs_start = i * n;
             let rhs_data = &rhs[rhs_start..rhs_start + n];
 
-            match solve_with_symbolic(&symbolic, mat_data, rhs_data, n) {
-                Ok(sol) => {
+            let condition = match solve_with_symbolic(&symbolic.symbolic, mat_data, rhs_data, n) {
+                Ok((sol, lu)) => {
                     solutions.extend(sol);
+                    self.config
+                        .estimate_condition
+                        .then(|| estimate_lu_condition(&lu, norm1_dense(mat_data, n), n))
                 }
                 Err(_) => {
                     solutions.extend(std::iter::repeat(0.0).take(n));
                     singular_indices.push(i);
+                    Some(f64::INFINITY)
                 }
+            };
+            if self.config.estimate_condition {
+                condition_numbers.extend(condition);
             }
         }
 
+        let timings = self.config.collect_timings.then(|| BatchTimings {
+            symbolic: symbolic_time,
+            compute: compute_start.elapsed(),
+            ..Default::default()
+        });
+        let ill_conditioned_indices = ill_conditioned_indices(
+            &condition_numbers,
+            &singular_indices,
+            self.config.condition_threshold,
+        );
         Ok(BatchedSolveResult {
             solutions,
             singular_indices,
             n,
             batch_size,
+            timings,
+            condition_numbers: self.config.estimate_condition.then_some(condition_numbers),
+            ill_conditioned_indices,
         })
     }
 
@@ -192,13 +359,14 @@ fn dense_to_sparse_triplets(data: &[f64], n: usize) -> Vec<Triplet<usize, usize,
     triplets
 }
 
-/// Solve a single system using cached symbolic factorization.
+/// Solve a single system using cached symbolic factorization, returning the
+/// solution and the numeric factorization.
 fn solve_with_symbolic(
     symbolic: &SymbolicLu<usize>,
     mat_data: &[f64],
     rhs_data: &[f64],
     n: usize,
-) -> std::result::Result<Vec<f64>, ()> {
+) -> std::result::Result<(Vec<f64>, Lu<usize, f64>), ()> {
     // Convert dense to sparse
     let triplets = dense_to_sparse_triplets(mat_data, n);
     let sparse_mat =
@@ -221,7 +389,7 @@ fn solve_with_symbolic(
         result.push(val);
     }
 
-    Ok(result)
+    Ok((result, lu))
 }
 
 /// Batched solver that accepts triplets directly (avoiding dense→sparse conversion).
@@ -267,10 +435,14 @@ impl FaerTripletBatchedSolver {
                 singular_indices: vec![],
                 n,
                 batch_size: 0,
+                timings: None,
+                condition_numbers: self.config.estimate_condition.then(Vec::new),
+                ill_conditioned_indices: vec![],
             });
         }
 
         // Build symbolic factorization from first system
+        let symbolic_start = Instant::now();
         let first_triplets: Vec<Triplet<usize, usize, f64>> = triplets_per_system[0]
             .iter()
             .map(|&(r, c, v)| Triplet::new(r, c, v))
@@ -285,8 +457,17 @@ impl FaerTripletBatchedSolver {
             BatchedSweepError::Backend(format!("Symbolic factorization failed: {:?}", e))
         })?;
 
+        let symbolic_time = symbolic_start.elapsed();
+        let compute_start = Instant::now();
+
         let mut solutions = Vec::with_capacity(batch_size * n);
         let mut singular_indices = Vec::new();
+        // Singular systems keep their infinite condition number
+        let mut condition_numbers = if self.config.estimate_condition {
+            vec![f64::INFINITY; batch_size]
+        } else {
+            Vec::new()
+        };
 
         for (i, (triplets, rhs)) in triplets_per_system
             .iter()
@@ -344,14 +525,31 @@ impl FaerTripletBatchedSolver {
                 for j in 0..n {
                     solutions.push(x[j]);
                 }
+                if self.config.estimate_condition {
+                    condition_numbers[i] =
+                        estimate_lu_condition(&lu, norm1_triplets(triplets, n), n);
+                }
             }
         }
 
+        let timings = self.config.collect_timings.then(|| BatchTimings {
+            symbolic: symbolic_time,
+            compute: compute_start.elapsed(),
+            ..Default::default()
+        });
+        let ill_conditioned_indices = ill_conditioned_indices(
+            &condition_numbers,
+            &singular_indices,
+            self.config.condition_threshold,
+        );
         Ok(BatchedSolveResult {
             solutions,
             singular_indices,
             n,
             batch_size,
+            timings,
+            condition_numbers: self.config.estimate_condition.then_some(condition_numbers),
+            ill_conditioned_indices,
         })
     }
 
@@ -556,4 +754,125 @@ mod tests {
         solver.reset_cache();
         assert!(!solver.has_cached_symbolic());
     }
+
+    /// Tridiagonal, column-major test matrix scaled by `scale`.
+    fn tridiagonal(n: usize, scale: f64) -> Vec<f64> {
+        let mut m = vec![0.0; n * n];
+        for i in 0..n {
+            m[i * n + i] = 4.0 * scale;
+            if i + 1 < n {
+                m[i * n + i + 1] = -scale;
+                m[(i + 1) * n + i] = -1.5 * scale;
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_symbolic_cache_shared_between_solvers() {
+        let n = 12;
+        let fresh = FaerSparseCachedBatchedSolver::new(GpuBatchConfig::default());
+        let matrices: Vec<f64> = [1.0, 2.5, 0.7]
+            .iter()
+            .flat_map(|&s| tridiagonal(n, s))
+            .collect();
+        let rhs: Vec<f64> = (0..3 * n).map(|i| (i % 5) as f64 - 1.0).collect();
+        let expected = fresh.solve_batch(&matrices, &rhs, n, 3).unwrap();
+
+        let cache = fresh.symbolic_cache().unwrap();
+        assert_eq!(cache.nnz(), 3 * n - 2);
+
+        // A solver starting from the shared structure gives identical results
+        let shared = FaerSparseCachedBatchedSolver::new(GpuBatchConfig::default());
+        shared.set_symbolic_cache(cache);
+        let result = shared.solve_batch(&matrices, &rhs, n, 3).unwrap();
+        assert!(result.singular_indices.is_empty());
+        assert_eq!(result.solutions, expected.solutions);
+    }
+
+    #[test]
+    fn test_sparse_solver_timings() {
+        let n = 20;
+        let batch_size = 4;
+        let matrices: Vec<f64> = (0..batch_size)
+            .flat_map(|b| tridiagonal(n, 1.0 + b as f64))
+            .collect();
+        let rhs = vec![1.0; batch_size * n];
+        let config = GpuBatchConfig {
+            collect_timings: true,
+            ..Default::default()
+        };
+
+        let solver = FaerSparseCachedBatchedSolver::new(config.clone());
+        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+        let timings = result.timings.unwrap();
+        assert!(timings.total() > std::time::Duration::ZERO);
+        assert_eq!(timings.total(), timings.symbolic + timings.compute);
+
+        let triplets: Vec<Vec<(usize, usize, f64)>> = matrices
+            .chunks(n * n)
+            .map(|m| {
+                (0..n * n)
+                    .filter(|&k| m[k] != 0.0)
+                    .map(|k| (k % n, k / n, m[k]))
+                    .collect()
+            })
+            .collect();
+        let rhs_per_system = vec![vec![1.0; n]; batch_size];
+        let solver = FaerTripletBatchedSolver::new(config);
+        let result = solver
+            .solve_batch_triplets(&triplets, &rhs_per_system, n)
+            .unwrap();
+        assert!(result.timings.unwrap().total() > std::time::Duration::ZERO);
+
+        let solver = FaerSparseCachedBatchedSolver::new(GpuBatchConfig::default());
+        let result = solver.solve_batch(&matrices, &rhs, n, batch_size).unwrap();
+        assert!(result.timings.is_none());
+    }
+
+    #[test]
+    fn test_sparse_condition_estimates_match_dense() {
+        use crate::faer_solver::FaerBatchedSolver;
+
+        let n = 12;
+        let batch_size = 3;
+        let matrices: Vec<f64> = (0..batch_size)
+            .flat_map(|b| tridiagonal(n, 1.0 + b as f64))
+            .collect();
+        let rhs = vec![1.0; batch_size * n];
+        let config = GpuBatchConfig {
+            estimate_condition: true,
+            ..Default::default()
+        };
+
+        let dense = FaerBatchedSolver::new(config.clone())
+            .solve_batch(&matrices, &rhs, n, batch_size)
+            .unwrap();
+        let sparse = FaerSparseCachedBatchedSolver::new(config)
+            .solve_batch(&matrices, &rhs, n, batch_size)
+            .unwrap();
+
+        let dense = dense.condition_numbers.unwrap();
+        let sparse = sparse.condition_numbers.unwrap();
+        assert_eq!(sparse.len(), batch_size);
+        for (d, s) in dense.iter().zip(&sparse) {
+            assert!((d - s).abs() <= 1e-9 * d, "dense {d} vs sparse {s}");
+        }
+    }
+
+    #[test]
+    fn test_symbolic_cache_rejects_mismatched_pattern() {
+        let n = 6;
+        let cache = SymbolicCache::from_dense(&tridiagonal(n, 1.0), n).unwrap();
+
+        // Same size, but a dense first column
+        let mut other = tridiagonal(n, 1.0);
+        other[..n].fill(2.0);
+        assert!(!cache.matches_dense(&other, n));
+
+        let solver = FaerSparseCachedBatchedSolver::new(GpuBatchConfig::default());
+        solver.set_symbolic_cache(cache);
+        let err = solver.solve_batch(&other, &vec![1.0; n], n, 1).unwrap_err();
+        assert!(matches!(err, BatchedSweepError::PatternMismatch(_)));
+    }
 }
@@ -42,6 +42,7 @@
 pub mod batch_layout;
 pub mod convergence;
 mod error;
+pub mod monte_carlo;
 pub mod pipeline;
 pub mod rng;
 mod solver;
@@ -58,6 +59,9 @@ mod metal;
 #[cfg(feature = "mps")]
 mod mps;
 
+#[cfg(feature = "faer")]
+mod condition;
+
 #[cfg(feature = "faer")]
 mod faer_solver;
 
@@ -72,10 +76,18 @@ mod parallel;
 
 pub use error::{BatchedSweepError, Result};
 pub use solver::{
-    BackendSelector, BackendType, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig,
-    MAX_BATCH_SIZE, MIN_BATCH_SIZE, MIN_MATRIX_SIZE,
+    BackendSelector, BackendType, BatchTimings, BatchedLuSolver, BatchedSolveResult,
+    DEFAULT_CONDITION_THRESHOLD, GpuBatchConfig, MAX_BATCH_SIZE, MIN_BATCH_SIZE, MIN_MATRIX_SIZE,
+};
+pub use sweep::{
+    GpuBatchedSweepResult, solve_batched_ac_gpu, solve_batched_sweep_auto, solve_batched_sweep_gpu,
+};
+
+// Re-export Monte Carlo sampling types
+pub use monte_carlo::{
+    Distribution, LatinHypercubeSampler, MonteCarloParameter, MonteCarloSampler, MonteCarloSamples,
+    ParameterSampler,
 };
-pub use sweep::{GpuBatchedSweepResult, solve_batched_sweep_auto, solve_batched_sweep_gpu};
 
 // Re-export key RNG types for convenience
 pub use rng::{
@@ -109,7 +121,9 @@ pub use mps::MpsBatchedSolver;
 pub use faer_solver::FaerBatchedSolver;
 
 #[cfg(feature = "faer")]
-pub use faer_sparse_solver::{FaerSparseCachedBatchedSolver, FaerTripletBatchedSolver};
+pub use faer_sparse_solver::{
+    FaerSparseCachedBatchedSolver, FaerTripletBatchedSolver, SymbolicCache,
+};
 
 #[cfg(feature = "accelerate")]
 pub use accelerate_solver::AccelerateBatchedSolver;
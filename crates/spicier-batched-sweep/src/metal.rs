@@ -4,7 +4,9 @@
 //! using Metal via WebGPU (wgpu) compute shaders.
 
 use crate::error::{BatchedSweepError, Result};
-use crate::solver::{BackendType, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig};
+use crate::solver::{
+    BackendType, BatchTimings, BatchedLuSolver, BatchedSolveResult, GpuBatchConfig,
+};
 use spicier_backend_metal::{MetalBatchedLuSolver as MetalSolver, WgpuContext};
 use std::sync::Arc;
 
@@ -31,6 +33,8 @@ impl MetalBatchedSolver {
             min_batch_size: config.min_batch_size,
             min_matrix_size: config.min_matrix_size,
             max_matrix_size: spicier_backend_metal::MAX_MATRIX_SIZE,
+            collect_timings: config.collect_timings,
+            ..Default::default()
         };
 
         let solver = MetalSolver::with_config(Arc::new(ctx), metal_config).map_err(|e| {
@@ -64,6 +68,14 @@ impl BatchedLuSolver for MetalBatchedSolver {
             singular_indices: metal_result.singular_indices,
             n: metal_result.n,
             batch_size: metal_result.batch_size,
+            timings: metal_result.timings.map(|t| BatchTimings {
+                upload: t.upload,
+                symbolic: Default::default(),
+                compute: t.compute,
+                readback: t.readback,
+            }),
+            condition_numbers: None,
+            ill_conditioned_indices: Vec::new(),
         })
     }
 
@@ -0,0 +1,597 @@
+//! Monte Carlo parameter generation for batched sweeps.
+//!
+//! [`MonteCarloSampler`] draws per-sample parameter vectors from a list of
+//! parameters, each with a nominal value and a [`Distribution`]. Draws use
+//! the stateless hash RNG in [`crate::rng`], so sample `i` is the same no
+//! matter how many samples are generated.
+//!
+//! Parameters can be correlated through a correlation matrix. Independent
+//! standard normals are mixed with its Cholesky factor, then each
+//! parameter's normal is mapped onto its own distribution (a Gaussian
+//! copula), so uniform and log-normal parameters keep their marginals.
+//!
+//! [`LatinHypercubeSampler`] covers the same parameters with fewer points:
+//! each parameter's probability range is split into `n` equal strata and
+//! every stratum is sampled exactly once. Both samplers implement
+//! [`ParameterSampler`] and produce [`MonteCarloSamples`].
+//!
+//! # Example
+//!
+//! ```
+//! use spicier_batched_sweep::monte_carlo::{
+//!     Distribution, MonteCarloParameter, MonteCarloSampler, ParameterSampler,
+//! };
+//!
+//! let sampler = MonteCarloSampler::new(vec![
+//!     MonteCarloParameter::new("R1", 1e3, Distribution::Normal { sigma: 0.01 }),
+//!     MonteCarloParameter::new("C1", 1e-9, Distribution::Uniform { tol: 0.1 }),
+//! ])
+//! .with_seed(7);
+//!
+//! let samples = sampler.generate(1000);
+//! assert_eq!(samples.num_samples(), 1000);
+//! let r1 = samples.sample(0)[0];
+//! assert!((r1 - 1e3).abs() < 100.0);
+//! ```
+
+use std::f64::consts::SQRT_2;
+
+use nalgebra::DMatrix;
+
+use crate::error::{BatchedSweepError, Result};
+use crate::rng::{gaussian, uniform};
+
+/// Distribution of a parameter around its nominal value.
+///
+/// Spreads are relative to the nominal value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// `nominal * (1 + sigma * z)` with `z` standard normal.
+    Normal {
+        /// Standard deviation as a fraction of nominal.
+        sigma: f64,
+    },
+    /// Uniform over `nominal * (1 ± tol)`.
+    Uniform {
+        /// Half-width as a fraction of nominal.
+        tol: f64,
+    },
+    /// `nominal * exp(sigma * z)`: always the sign of nominal, median at
+    /// nominal.
+    LogNormal {
+        /// Standard deviation of the logarithm.
+        sigma: f64,
+    },
+}
+
+impl Distribution {
+    /// Map a standard normal draw onto this distribution around `nominal`.
+    fn apply(&self, nominal: f64, z: f64) -> f64 {
+        match *self {
+            Distribution::Normal { sigma } => nominal * (1.0 + sigma * z),
+            Distribution::Uniform { tol } => nominal * (1.0 + tol * (2.0 * normal_cdf(z) - 1.0)),
+            Distribution::LogNormal { sigma } => nominal * (sigma * z).exp(),
+        }
+    }
+
+    /// Inverse CDF: the value at cumulative probability `p` in (0, 1).
+    fn quantile(&self, nominal: f64, p: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { tol } => nominal * (1.0 + tol * (2.0 * p - 1.0)),
+            _ => self.apply(nominal, normal_quantile(p)),
+        }
+    }
+}
+
+/// A parameter varied by the Monte Carlo sampler.
+#[derive(Debug, Clone)]
+pub struct MonteCarloParameter {
+    /// Parameter name (for identification).
+    pub name: String,
+    /// Nominal value.
+    pub nominal: f64,
+    /// Distribution around the nominal value.
+    pub distribution: Distribution,
+}
+
+impl MonteCarloParameter {
+    /// Create a new parameter.
+    pub fn new(name: impl Into<String>, nominal: f64, distribution: Distribution) -> Self {
+        Self {
+            name: name.into(),
+            nominal,
+            distribution,
+        }
+    }
+}
+
+/// A source of parameter vectors for batched sweeps.
+pub trait ParameterSampler: Send + Sync {
+    /// Draw `num_samples` parameter vectors.
+    fn generate(&self, num_samples: usize) -> MonteCarloSamples;
+}
+
+/// Draws Monte Carlo parameter vectors.
+#[derive(Debug, Clone)]
+pub struct MonteCarloSampler {
+    parameters: Vec<MonteCarloParameter>,
+    seed: u64,
+    /// Lower Cholesky factor of the correlation matrix.
+    cholesky: Option<DMatrix<f64>>,
+}
+
+impl MonteCarloSampler {
+    /// Create a sampler for independent parameters.
+    pub fn new(parameters: Vec<MonteCarloParameter>) -> Self {
+        Self {
+            parameters,
+            seed: 12345,
+            cholesky: None,
+        }
+    }
+
+    /// Set the random seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Correlate the parameters.
+    ///
+    /// `correlation` is the `n × n` correlation matrix of the parameters'
+    /// underlying normals, in parameter order. It must be symmetric with a
+    /// unit diagonal and positive definite.
+    pub fn with_correlation(mut self, correlation: DMatrix<f64>) -> Result<Self> {
+        let n = self.parameters.len();
+        if correlation.nrows() != n || correlation.ncols() != n {
+            return Err(BatchedSweepError::InvalidDimension(format!(
+                "correlation matrix is {}x{}, expected {}x{}",
+                correlation.nrows(),
+                correlation.ncols(),
+                n,
+                n
+            )));
+        }
+        for i in 0..n {
+            if (correlation[(i, i)] - 1.0).abs() > 1e-12 {
+                return Err(BatchedSweepError::InvalidCorrelation(format!(
+                    "diagonal entry {} is {}, expected 1",
+                    i,
+                    correlation[(i, i)]
+                )));
+            }
+            for j in 0..i {
+                if (correlation[(i, j)] - correlation[(j, i)]).abs() > 1e-12 {
+                    return Err(BatchedSweepError::InvalidCorrelation(format!(
+                        "entries ({i}, {j}) and ({j}, {i}) differ"
+                    )));
+                }
+            }
+        }
+
+        let cholesky = correlation.cholesky().ok_or_else(|| {
+            BatchedSweepError::InvalidCorrelation("matrix is not positive definite".to_string())
+        })?;
+        self.cholesky = Some(cholesky.l());
+        Ok(self)
+    }
+
+    /// The parameters being sampled.
+    pub fn parameters(&self) -> &[MonteCarloParameter] {
+        &self.parameters
+    }
+}
+
+impl ParameterSampler for MonteCarloSampler {
+    fn generate(&self, num_samples: usize) -> MonteCarloSamples {
+        let num_params = self.parameters.len();
+        let mut values = Vec::with_capacity(num_samples * num_params);
+        let mut z = vec![0.0; num_params];
+
+        for sample in 0..num_samples {
+            for (j, zj) in z.iter_mut().enumerate() {
+                *zj = gaussian(self.seed, sample as u32, j as u32);
+            }
+            if let Some(l) = &self.cholesky {
+                // Lower triangular, so fill from the bottom to reuse `z`
+                for i in (0..num_params).rev() {
+                    z[i] = (0..=i).map(|k| l[(i, k)] * z[k]).sum();
+                }
+            }
+            values.extend(
+                self.parameters
+                    .iter()
+                    .zip(&z)
+                    .map(|(p, &zj)| p.distribution.apply(p.nominal, zj)),
+            );
+        }
+
+        MonteCarloSamples {
+            names: self.parameters.iter().map(|p| p.name.clone()).collect(),
+            num_params,
+            values,
+        }
+    }
+}
+
+/// Draws Latin Hypercube parameter vectors.
+///
+/// For `n` samples, each parameter's CDF is split into `n` equal-probability
+/// strata. Each stratum is sampled once at a random point inside it and the
+/// strata are shuffled independently per parameter, then mapped through the
+/// parameter's inverse CDF.
+#[derive(Debug, Clone)]
+pub struct LatinHypercubeSampler {
+    parameters: Vec<MonteCarloParameter>,
+    seed: u64,
+}
+
+impl LatinHypercubeSampler {
+    /// Create a sampler for independent parameters.
+    pub fn new(parameters: Vec<MonteCarloParameter>) -> Self {
+        Self {
+            parameters,
+            seed: 12345,
+        }
+    }
+
+    /// Set the random seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The parameters being sampled.
+    pub fn parameters(&self) -> &[MonteCarloParameter] {
+        &self.parameters
+    }
+}
+
+impl ParameterSampler for LatinHypercubeSampler {
+    fn generate(&self, num_samples: usize) -> MonteCarloSamples {
+        let num_params = self.parameters.len();
+        let mut values = vec![0.0; num_samples * num_params];
+        let mut strata: Vec<usize> = Vec::with_capacity(num_samples);
+
+        for (j, param) in self.parameters.iter().enumerate() {
+            // Fisher-Yates shuffle on its own RNG coordinates; the jitter
+            // inside each stratum uses the next parameter index
+            let (shuffle_idx, jitter_idx) = (2 * j as u32, 2 * j as u32 + 1);
+            strata.clear();
+            strata.extend(0..num_samples);
+            for i in (1..num_samples).rev() {
+                let k = (uniform(self.seed, i as u32, shuffle_idx) * (i + 1) as f64) as usize;
+                strata.swap(i, k.min(i));
+            }
+
+            for (sample, &stratum) in strata.iter().enumerate() {
+                let jitter = uniform(self.seed, sample as u32, jitter_idx);
+                // Keep p strictly inside (0, 1) for the unbounded quantiles
+                let p = ((stratum as f64 + jitter) / num_samples as f64)
+                    .clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+                values[sample * num_params + j] = param.distribution.quantile(param.nominal, p);
+            }
+        }
+
+        MonteCarloSamples {
+            names: self.parameters.iter().map(|p| p.name.clone()).collect(),
+            num_params,
+            values,
+        }
+    }
+}
+
+/// Parameter vectors drawn by a [`MonteCarloSampler`].
+#[derive(Debug, Clone)]
+pub struct MonteCarloSamples {
+    /// Parameter names, in column order.
+    pub names: Vec<String>,
+    /// Number of parameters per sample.
+    pub num_params: usize,
+    /// Parameter values in row-major order (sample-major).
+    pub values: Vec<f64>,
+}
+
+impl MonteCarloSamples {
+    /// Number of samples.
+    pub fn num_samples(&self) -> usize {
+        self.values.len().checked_div(self.num_params).unwrap_or(0)
+    }
+
+    /// Parameter vector of sample `index`.
+    pub fn sample(&self, index: usize) -> &[f64] {
+        &self.values[index * self.num_params..(index + 1) * self.num_params]
+    }
+
+    /// Iterate over the per-sample parameter vectors.
+    pub fn iter(&self) -> impl Iterator<Item = &[f64]> {
+        self.values.chunks_exact(self.num_params.max(1))
+    }
+
+    /// Values of parameter `param_idx` across all samples.
+    pub fn column(&self, param_idx: usize) -> Vec<f64> {
+        self.iter().map(|s| s[param_idx]).collect()
+    }
+
+    /// Build one system per sample for the triplet batched solver.
+    ///
+    /// `build` maps a parameter vector to that sample's matrix triplets and
+    /// right-hand side. The returned pair is the `triplets_per_system` and
+    /// `rhs_per_system` arguments of `FaerTripletBatchedSolver::solve_batch_triplets`.
+    #[allow(clippy::type_complexity)]
+    pub fn systems(
+        &self,
+        build: impl Fn(&[f64]) -> (Vec<(usize, usize, f64)>, Vec<f64>),
+    ) -> (Vec<Vec<(usize, usize, f64)>>, Vec<Vec<f64>>) {
+        self.iter().map(build).unzip()
+    }
+}
+
+/// Standard normal CDF.
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / SQRT_2)
+}
+
+/// Standard normal inverse CDF (Acklam's rational approximation, relative
+/// error below 1.2e-9).
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, relative error
+/// below 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean(values: &[f64]) -> f64 {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+
+    #[test]
+    fn test_normal_mean_within_three_sigma() {
+        let n = 10_000;
+        let nominal = 1e3;
+        let sigma = 0.05;
+        let samples = MonteCarloSampler::new(vec![MonteCarloParameter::new(
+            "R1",
+            nominal,
+            Distribution::Normal { sigma },
+        )])
+        .with_seed(42)
+        .generate(n);
+
+        let m = mean(&samples.column(0));
+        assert!(
+            (m - nominal).abs() < 3.0 * sigma * nominal / (n as f64).sqrt(),
+            "mean {m}"
+        );
+
+        // Same seed, same samples
+        let again = MonteCarloSampler::new(vec![MonteCarloParameter::new(
+            "R1",
+            nominal,
+            Distribution::Normal { sigma },
+        )])
+        .with_seed(42)
+        .generate(10);
+        assert_eq!(again.values, samples.values[..10]);
+    }
+
+    #[test]
+    fn test_uniform_and_lognormal_ranges() {
+        let samples = MonteCarloSampler::new(vec![
+            MonteCarloParameter::new("C1", 1e-9, Distribution::Uniform { tol: 0.1 }),
+            MonteCarloParameter::new("IS", 1e-14, Distribution::LogNormal { sigma: 0.5 }),
+        ])
+        .generate(5_000);
+
+        let c = samples.column(0);
+        assert!(c.iter().all(|&v| (0.9e-9..=1.1e-9).contains(&v)));
+        // Both halves of the range get used
+        assert!(c.iter().any(|&v| v < 0.92e-9) && c.iter().any(|&v| v > 1.08e-9));
+        assert!((mean(&c) - 1e-9).abs() < 0.01e-9);
+
+        let is = samples.column(1);
+        assert!(is.iter().all(|&v| v > 0.0));
+        let log_mean = is.iter().map(|v| (v / 1e-14).ln()).sum::<f64>() / is.len() as f64;
+        assert!(log_mean.abs() < 0.05);
+    }
+
+    #[test]
+    fn test_correlated_parameters() {
+        let rho = 0.8;
+        let samples = MonteCarloSampler::new(vec![
+            MonteCarloParameter::new("R1", 1.0, Distribution::Normal { sigma: 1.0 }),
+            MonteCarloParameter::new("R2", 1.0, Distribution::Normal { sigma: 1.0 }),
+        ])
+        .with_correlation(DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0]))
+        .unwrap()
+        .generate(20_000);
+
+        let a: Vec<f64> = samples.column(0).iter().map(|v| v - 1.0).collect();
+        let b: Vec<f64> = samples.column(1).iter().map(|v| v - 1.0).collect();
+        let cov = a.iter().zip(&b).map(|(x, y)| x * y).sum::<f64>() / a.len() as f64;
+        let var_a = a.iter().map(|x| x * x).sum::<f64>() / a.len() as f64;
+        let var_b = b.iter().map(|x| x * x).sum::<f64>() / b.len() as f64;
+        let measured = cov / (var_a * var_b).sqrt();
+        assert!((measured - rho).abs() < 0.02, "correlation {measured}");
+    }
+
+    #[test]
+    fn test_invalid_correlation() {
+        let params = vec![
+            MonteCarloParameter::new("A", 1.0, Distribution::Normal { sigma: 0.1 }),
+            MonteCarloParameter::new("B", 1.0, Distribution::Normal { sigma: 0.1 }),
+        ];
+        let sampler = MonteCarloSampler::new(params);
+
+        assert!(matches!(
+            sampler.clone().with_correlation(DMatrix::identity(3, 3)),
+            Err(BatchedSweepError::InvalidDimension(_))
+        ));
+        assert!(matches!(
+            sampler
+                .clone()
+                .with_correlation(DMatrix::from_row_slice(2, 2, &[1.0, 1.5, 1.5, 1.0])),
+            Err(BatchedSweepError::InvalidCorrelation(_))
+        ));
+        assert!(matches!(
+            sampler.with_correlation(DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.4, 1.0])),
+            Err(BatchedSweepError::InvalidCorrelation(_))
+        ));
+    }
+
+    #[test]
+    fn test_systems_for_triplet_solver() {
+        // Voltage divider: conductances from sampled resistors
+        let samples = MonteCarloSampler::new(vec![
+            MonteCarloParameter::new("R1", 1e3, Distribution::Normal { sigma: 0.01 }),
+            MonteCarloParameter::new("R2", 1e3, Distribution::Normal { sigma: 0.01 }),
+        ])
+        .generate(8);
+
+        let (triplets, rhs) = samples.systems(|p| {
+            let (g1, g2) = (1.0 / p[0], 1.0 / p[1]);
+            (vec![(0, 0, g1 + g2)], vec![g1])
+        });
+
+        assert_eq!(triplets.len(), 8);
+        assert_eq!(rhs.len(), 8);
+        let p = samples.sample(3);
+        assert_eq!(triplets[3], vec![(0, 0, 1.0 / p[0] + 1.0 / p[1])]);
+    }
+
+    #[test]
+    fn test_latin_hypercube_hits_each_stratum_once() {
+        let n = 50;
+        let (nominal, tol) = (1e3, 0.2);
+        let samples = LatinHypercubeSampler::new(vec![
+            MonteCarloParameter::new("R1", nominal, Distribution::Uniform { tol }),
+            MonteCarloParameter::new("R2", nominal, Distribution::Uniform { tol }),
+        ])
+        .with_seed(3)
+        .generate(n);
+        assert_eq!(samples.num_samples(), n);
+
+        for j in 0..2 {
+            let mut hits = vec![0; n];
+            for v in samples.column(j) {
+                let p = (v / nominal - 1.0) / tol * 0.5 + 0.5;
+                hits[(p * n as f64) as usize] += 1;
+            }
+            assert!(hits.iter().all(|&h| h == 1), "parameter {j}: {hits:?}");
+        }
+        // Independent shuffles per parameter
+        assert_ne!(samples.column(0), samples.column(1));
+    }
+
+    #[test]
+    fn test_latin_hypercube_normal_and_lognormal() {
+        let n = 200;
+        let params = vec![
+            MonteCarloParameter::new("R1", 1e3, Distribution::Normal { sigma: 0.05 }),
+            MonteCarloParameter::new("IS", 1e-14, Distribution::LogNormal { sigma: 0.5 }),
+        ];
+        // Swappable with the Monte Carlo sampler over the same parameters
+        let lhs = LatinHypercubeSampler::new(params.clone());
+        let mc = MonteCarloSampler::new(params);
+        let samplers: [&dyn ParameterSampler; 2] = [&lhs, &mc];
+        let [samples, mc_samples] = samplers.map(|s| s.generate(n));
+        for drawn in [&samples, &mc_samples] {
+            assert_eq!(drawn.names, ["R1", "IS"]);
+            assert_eq!(drawn.num_samples(), n);
+            assert!(drawn.column(1).iter().all(|&v| v > 0.0));
+        }
+        let mc_r1 = mc_samples.column(0);
+        assert!((mean(&mc_r1) - 1e3).abs() < 3.0 * 0.05 * 1e3 / (n as f64).sqrt());
+
+        // Stratification pins the sample mean far tighter than 3 sigma/sqrt(n)
+        let r1 = samples.column(0);
+        assert!((mean(&r1) - 1e3).abs() < 0.1 * 0.05 * 1e3 / (n as f64).sqrt());
+        let mut sorted = r1.clone();
+        sorted.sort_by(f64::total_cmp);
+        assert!(sorted[0] < 1e3 * (1.0 - 2.0 * 0.05));
+        assert!(sorted[n - 1] > 1e3 * (1.0 + 2.0 * 0.05));
+
+        let is = samples.column(1);
+        assert!(is.iter().all(|&v| v > 0.0));
+        let below = is.iter().filter(|&&v| v < 1e-14).count();
+        assert_eq!(below, n / 2);
+    }
+
+    #[test]
+    fn test_normal_quantile_inverts_cdf() {
+        for p in [1e-6, 0.01, 0.2, 0.5, 0.8, 0.99, 1.0 - 1e-6] {
+            assert!((normal_cdf(normal_quantile(p)) - p).abs() < 2e-7, "p={p}");
+        }
+        assert!((normal_quantile(0.975) - 1.959_963_985).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.0) - 0.841_344_746).abs() < 1e-7);
+        assert!((normal_cdf(-1.96) - 0.024_997_895).abs() < 1e-7);
+    }
+}
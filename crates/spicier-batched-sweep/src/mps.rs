@@ -64,6 +64,9 @@ impl BatchedLuSolver for MpsBatchedSolver {
             singular_indices: mps_result.singular_indices,
             n: mps_result.n,
             batch_size: mps_result.batch_size,
+            timings: None,
+            condition_numbers: None,
+            ill_conditioned_indices: Vec::new(),
         })
     }
 
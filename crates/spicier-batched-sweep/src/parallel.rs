@@ -14,7 +14,7 @@
 
 use crate::error::Result;
 use crate::solver::{BackendSelector, BackendType};
-use crate::sweep::GpuBatchedSweepResult;
+use crate::sweep::{GpuBatchedSweepResult, singular_point_reports};
 use nalgebra::DVector;
 use rayon::prelude::*;
 use spicier_solver::{
@@ -97,6 +97,7 @@ pub fn solve_batched_sweep_parallel(
             converged_count: 0,
             total_count: 0,
             singular_indices: vec![],
+            failures: vec![],
             used_gpu: false,
             backend_used: BackendType::Cpu,
         });
@@ -170,6 +171,7 @@ pub fn solve_batched_sweep_parallel(
     }
 
     let converged_count = total_count - singular_indices.len();
+    let failures = singular_point_reports(&points, &singular_indices)?;
 
     Ok(GpuBatchedSweepResult {
         solutions,
@@ -177,6 +179,7 @@ pub fn solve_batched_sweep_parallel(
         converged_count,
         total_count,
         singular_indices,
+        failures,
         used_gpu: false,
         backend_used: backend_type,
     })
@@ -483,6 +486,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_monte_carlo_reproducible_across_thread_counts() {
+        let backend = BackendSelector::cpu_only();
+        let config = DispatchConfig::default();
+        let parallel_config = ParallelSweepConfig::default();
+        let factory = SimpleDividerFactory { r2_nominal: 1000.0 };
+        let generator = MonteCarloGenerator::new(200).with_seed(2024);
+        let variations = vec![
+            ParameterVariation::new("R1", 1000.0)
+                .with_bounds(500.0, 1500.0)
+                .with_sigma(0.1),
+        ];
+
+        let run = |threads: usize| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    solve_batched_sweep_parallel(
+                        &backend,
+                        &factory,
+                        &generator,
+                        &variations,
+                        &ConvergenceCriteria::default(),
+                        &config,
+                        &parallel_config,
+                    )
+                })
+                .unwrap()
+        };
+
+        let single = run(1);
+        let multi = run(8);
+
+        assert_eq!(single.total_count, multi.total_count);
+        for i in 0..single.total_count {
+            assert_eq!(
+                single.points[i].parameters, multi.points[i].parameters,
+                "sample {i} drew different parameters"
+            );
+            assert_eq!(single.solution(i), multi.solution(i), "sample {i} differs");
+        }
+    }
+
     #[test]
     fn test_chunked_parallel() {
         let backend = BackendSelector::cpu_only();
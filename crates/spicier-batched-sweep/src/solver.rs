@@ -1,6 +1,7 @@
 //! Unified batched LU solver trait and backend selection.
 
 use crate::error::Result;
+use std::time::Duration;
 
 /// Maximum batch size supported by most GPU backends.
 pub const MAX_BATCH_SIZE: usize = 65535;
@@ -11,6 +12,11 @@ pub const MIN_BATCH_SIZE: usize = 16;
 /// Minimum matrix size for GPU to be worthwhile.
 pub const MIN_MATRIX_SIZE: usize = 32;
 
+/// Default 1-norm condition number above which a system is reported in
+/// [`BatchedSolveResult::ill_conditioned_indices`]. At this condition, f64
+/// solutions keep only about four significant digits.
+pub const DEFAULT_CONDITION_THRESHOLD: f64 = 1e12;
+
 /// Configuration for GPU batched operations.
 #[derive(Debug, Clone)]
 pub struct GpuBatchConfig {
@@ -20,6 +26,15 @@ pub struct GpuBatchConfig {
     pub min_matrix_size: usize,
     /// Maximum batch size per GPU launch.
     pub max_batch_per_launch: usize,
+    /// Record per-phase [`BatchTimings`] in each [`BatchedSolveResult`].
+    /// Off by default; backends that do not time their phases ignore it.
+    pub collect_timings: bool,
+    /// Estimate each system's 1-norm condition number after factoring it.
+    /// Costs a few extra triangular solves per system; only the faer
+    /// backends support it.
+    pub estimate_condition: bool,
+    /// Condition number above which a system counts as ill-conditioned.
+    pub condition_threshold: f64,
 }
 
 impl Default for GpuBatchConfig {
@@ -28,6 +43,9 @@ impl Default for GpuBatchConfig {
             min_batch_size: MIN_BATCH_SIZE,
             min_matrix_size: MIN_MATRIX_SIZE,
             max_batch_per_launch: MAX_BATCH_SIZE,
+            collect_timings: false,
+            estimate_condition: false,
+            condition_threshold: DEFAULT_CONDITION_THRESHOLD,
         }
     }
 }
@@ -50,6 +68,39 @@ pub struct BatchedSolveResult {
     pub n: usize,
     /// Number of systems solved.
     pub batch_size: usize,
+    /// Phase timings, when [`GpuBatchConfig::collect_timings`] is set and
+    /// the backend records them.
+    pub timings: Option<BatchTimings>,
+    /// Estimated 1-norm condition number of each system, when
+    /// [`GpuBatchConfig::estimate_condition`] is set and the backend
+    /// supports it. Singular systems report infinity.
+    pub condition_numbers: Option<Vec<f64>>,
+    /// Non-singular systems whose estimated condition number exceeds
+    /// [`GpuBatchConfig::condition_threshold`].
+    pub ill_conditioned_indices: Vec<usize>,
+}
+
+/// Wall-clock time spent in each phase of a batched solve.
+///
+/// CPU backends leave `upload` and `readback` at zero; dense backends
+/// leave `symbolic` at zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BatchTimings {
+    /// Host-to-device transfer, including packing the batch.
+    pub upload: Duration,
+    /// Symbolic (sparsity structure) factorization.
+    pub symbolic: Duration,
+    /// Numeric factorization and triangular solves.
+    pub compute: Duration,
+    /// Device-to-host transfer of the solutions.
+    pub readback: Duration,
+}
+
+impl BatchTimings {
+    /// Sum of all phases.
+    pub fn total(&self) -> Duration {
+        self.upload + self.symbolic + self.compute + self.readback
+    }
 }
 
 impl BatchedSolveResult {
@@ -385,6 +436,9 @@ impl BatchedLuSolver for CpuBatchedSolver {
             singular_indices,
             n,
             batch_size,
+            timings: None,
+            condition_numbers: None,
+            ill_conditioned_indices: Vec::new(),
         })
     }
 
@@ -1,14 +1,16 @@
 //! Unified GPU-accelerated batched sweep solving.
 
-use crate::error::Result;
+use crate::convergence::{ConvergenceTracker, FailureReport};
+use crate::error::{BatchedSweepError, Result};
 use crate::solver::{BackendSelector, BackendType, BatchedLuSolver};
-use nalgebra::DVector;
+use nalgebra::{Complex, DVector};
 #[cfg(test)]
 use spicier_solver::SweepStamper;
 use spicier_solver::{
-    ConvergenceCriteria, DispatchConfig, ParameterVariation, SweepPoint, SweepPointGenerator,
-    SweepStamperFactory, SweepStatistics,
+    AcSweepStamperFactory, BatchedAcResult, ComplexMna, ConvergenceCriteria, DispatchConfig,
+    ParameterVariation, SweepPoint, SweepPointGenerator, SweepStamperFactory, SweepStatistics,
 };
+use std::f64::consts::PI;
 #[cfg(test)]
 use std::sync::Arc;
 
@@ -25,6 +27,8 @@ pub struct GpuBatchedSweepResult {
     pub total_count: usize,
     /// Indices of singular systems.
     pub singular_indices: Vec<usize>,
+    /// Index and parameter values of every singular point.
+    pub failures: Vec<FailureReport>,
     /// Whether GPU was actually used (vs CPU fallback).
     pub used_gpu: bool,
     /// Which backend was used.
@@ -116,6 +120,7 @@ pub fn solve_batched_sweep_gpu(
             converged_count: 0,
             total_count: 0,
             singular_indices: vec![],
+            failures: vec![],
             used_gpu: false,
             backend_used: BackendType::Cpu,
         });
@@ -204,6 +209,7 @@ fn solve_with_solver(
 
     let converged_count = total_count - batch_result.singular_indices.len();
     let used_gpu = solver.backend_type() != BackendType::Cpu;
+    let failures = singular_point_reports(points, &batch_result.singular_indices)?;
 
     Ok(GpuBatchedSweepResult {
         solutions,
@@ -211,11 +217,126 @@ fn solve_with_solver(
         converged_count,
         total_count,
         singular_indices: batch_result.singular_indices,
+        failures,
         used_gpu,
         backend_used: solver.backend_type(),
     })
 }
 
+/// Execute a batched AC sweep on a batched LU backend.
+///
+/// The batched counterpart of [`spicier_solver::solve_batched_ac`]: for each
+/// frequency, every sample's complex MNA system is assembled and the whole
+/// set is solved in one [`BatchedLuSolver::solve_batch`] call. The backends
+/// are real-valued, so each `n × n` system `(Ar + jAi)(xr + jxi) = br + jbi`
+/// is passed as its `2n × 2n` real equivalent `[Ar -Ai; Ai Ar] [xr; xi] =
+/// [br; bi]`, which is singular exactly when the complex system is.
+///
+/// Every sample must have the same system size. Samples that are singular
+/// at some frequency get a zero solution there and are listed in
+/// [`BatchedAcResult::failed_indices`].
+pub fn solve_batched_ac_gpu(
+    backend: &BackendSelector,
+    factory: &dyn AcSweepStamperFactory,
+    generator: &dyn SweepPointGenerator,
+    variations: &[ParameterVariation],
+    frequencies: &[f64],
+) -> Result<BatchedAcResult> {
+    let points = generator.generate(variations);
+    let total_count = points.len();
+    let stampers: Vec<_> = points
+        .iter()
+        .map(|p| factory.create_ac_stamper(&p.parameters))
+        .collect();
+    let n = stampers
+        .first()
+        .map_or(0, |s| s.num_nodes() + s.num_vsources());
+    let m = 2 * n;
+
+    let solver = backend.create_solver()?;
+    log::debug!(
+        "Using {} for batched AC sweep (size={}, batch={}, frequencies={})",
+        solver.backend_type(),
+        n,
+        total_count,
+        frequencies.len()
+    );
+
+    let mut failed = vec![false; total_count];
+    let mut solutions = Vec::with_capacity(frequencies.len());
+    let mut matrices = vec![0.0; total_count * m * m];
+    let mut rhs_vectors = vec![0.0; total_count * m];
+
+    for &freq in frequencies {
+        let omega = 2.0 * PI * freq;
+        for (i, stamper) in stampers.iter().enumerate() {
+            let size = stamper.num_nodes() + stamper.num_vsources();
+            if size != n {
+                return Err(BatchedSweepError::InvalidDimension(format!(
+                    "sample {} has {} unknowns, expected {}",
+                    i, size, n
+                )));
+            }
+            let mut mna = ComplexMna::new(stamper.num_nodes(), stamper.num_vsources());
+            stamper.stamp_ac(&mut mna, omega);
+            let a = mna.to_dense_matrix();
+
+            // Column-major 2n × 2n real equivalent
+            let matrix = &mut matrices[i * m * m..(i + 1) * m * m];
+            for col in 0..n {
+                for row in 0..n {
+                    let z = a[(row, col)];
+                    matrix[col * m + row] = z.re;
+                    matrix[col * m + n + row] = z.im;
+                    matrix[(n + col) * m + row] = -z.im;
+                    matrix[(n + col) * m + n + row] = z.re;
+                }
+            }
+            let rhs = &mut rhs_vectors[i * m..(i + 1) * m];
+            for (k, z) in mna.rhs().iter().enumerate() {
+                rhs[k] = z.re;
+                rhs[n + k] = z.im;
+            }
+        }
+
+        let batch_result = solver.solve_batch(&matrices, &rhs_vectors, m, total_count)?;
+        for &i in &batch_result.singular_indices {
+            failed[i] = true;
+        }
+        solutions.push(
+            (0..total_count)
+                .map(|i| match batch_result.solution(i) {
+                    Some(x) => DVector::from_fn(n, |k, _| Complex::new(x[k], x[n + k])),
+                    None => DVector::zeros(n),
+                })
+                .collect(),
+        );
+    }
+
+    let failed_indices: Vec<usize> = (0..total_count).filter(|&i| failed[i]).collect();
+
+    Ok(BatchedAcResult {
+        frequencies: frequencies.to_vec(),
+        solutions,
+        points,
+        converged_count: total_count - failed_indices.len(),
+        total_count,
+        failed_indices,
+    })
+}
+
+/// Failure reports, with parameter values, for the singular points of a sweep.
+pub(crate) fn singular_point_reports(
+    points: &[SweepPoint],
+    singular_indices: &[usize],
+) -> Result<Vec<FailureReport>> {
+    let mut tracker = ConvergenceTracker::new(points.len()).with_parameters(points)?;
+    for &index in singular_indices {
+        tracker.mark_singular(index);
+    }
+    Ok(tracker.failure_reports().to_vec())
+}
+
 /// Convenience function to solve with automatic backend selection.
 pub fn solve_batched_sweep_auto(
     factory: &dyn SweepStamperFactory,
@@ -392,4 +513,131 @@ mod tests {
         assert_eq!(result.total_count, 2); // 2^1 corners
         assert_eq!(result.converged_count, 2);
     }
+
+    /// Both divider resistors share one swept conductance, so a zero
+    /// conductance leaves node 1 floating.
+    struct SharedConductanceFactory;
+
+    impl SweepStamperFactory for SharedConductanceFactory {
+        fn create_stamper(&self, parameters: &[f64]) -> Arc<dyn SweepStamper> {
+            let g = parameters[0];
+            Arc::new(SimpleDividerStamper {
+                r1: 1.0 / g,
+                r2: 1.0 / g,
+                v_source: 10.0,
+            })
+        }
+    }
+
+    #[test]
+    fn test_sweep_reports_singular_point_parameters() {
+        let backend = BackendSelector::cpu_only();
+        let generator = LinearSweepGenerator::new(3);
+        let variations = vec![ParameterVariation::new("G", 1e-3).with_bounds(0.0, 2e-3)];
+
+        let result = solve_batched_sweep_gpu(
+            &backend,
+            &SharedConductanceFactory,
+            &generator,
+            &variations,
+            &ConvergenceCriteria::default(),
+            &DispatchConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.singular_indices, vec![0]);
+        assert_eq!(result.failures.len(), 1);
+        let report = &result.failures[0];
+        assert_eq!(report.index, 0);
+        assert_eq!(
+            report.status,
+            crate::convergence::ConvergenceStatus::Singular
+        );
+        assert_eq!(report.parameters, vec![0.0]);
+    }
+
+    /// V1 at node 0, conductance `G` from node 0 to 1, 1 µF from 1 to ground.
+    struct RcAcFactory;
+
+    impl AcSweepStamperFactory for RcAcFactory {
+        fn create_ac_stamper(&self, parameters: &[f64]) -> Box<dyn spicier_solver::AcStamper> {
+            Box::new(RcAcStamper { g: parameters[0] })
+        }
+    }
+
+    struct RcAcStamper {
+        g: f64,
+    }
+
+    impl spicier_solver::AcStamper for RcAcStamper {
+        fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, Complex::new(1.0, 0.0));
+            mna.stamp_conductance(Some(0), Some(1), self.g);
+            mna.stamp_admittance(Some(1), None, Complex::new(0.0, omega * 1e-6));
+        }
+
+        fn num_nodes(&self) -> usize {
+            2
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_batched_ac_matches_serial_sweep() {
+        let generator = MonteCarloGenerator::new(50).with_seed(3);
+        let variations = vec![ParameterVariation::new("G", 1e-3).with_sigma(0.05)];
+        let frequencies = [10.0, 159.0, 1e4];
+
+        let batched = solve_batched_ac_gpu(
+            &BackendSelector::cpu_only(),
+            &RcAcFactory,
+            &generator,
+            &variations,
+            &frequencies,
+        )
+        .unwrap();
+        let serial =
+            spicier_solver::solve_batched_ac(&RcAcFactory, &generator, &variations, &frequencies)
+                .unwrap();
+
+        assert_eq!(batched.total_count, 50);
+        assert_eq!(batched.converged_count, 50);
+        for fi in 0..frequencies.len() {
+            for i in 0..50 {
+                let (a, b) = (
+                    batched.solution(fi, i).unwrap(),
+                    serial.solution(fi, i).unwrap(),
+                );
+                for k in 0..3 {
+                    assert!((a[k] - b[k]).norm() < 1e-12, "f[{fi}] sample {i} x[{k}]");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_batched_ac_reports_singular_samples() {
+        // G = 0 leaves node 1 tied to ground only through the capacitor,
+        // which is open at DC.
+        let generator = LinearSweepGenerator::new(3);
+        let variations = vec![ParameterVariation::new("G", 1e-3).with_bounds(0.0, 2e-3)];
+
+        let result = solve_batched_ac_gpu(
+            &BackendSelector::cpu_only(),
+            &RcAcFactory,
+            &generator,
+            &variations,
+            &[0.0, 1e3],
+        )
+        .unwrap();
+
+        assert_eq!(result.failed_indices, vec![0]);
+        assert_eq!(result.converged_count, 2);
+        assert_eq!(result.solution(0, 0).unwrap()[1], Complex::new(0.0, 0.0));
+        // At DC the capacitor is open, so the other samples pass V1 through
+        assert!((result.solution(0, 2).unwrap()[1] - Complex::new(1.0, 0.0)).norm() < 1e-12);
+    }
 }
@@ -3,28 +3,27 @@
 use std::f64::consts::PI;
 
 use anyhow::Result;
-use spicier_core::NodeId;
 use spicier_parser::{AcSweepType, Measurement, OutputVariable};
 use spicier_solver::{
-    AcParams, AcSweepType as SolverAcSweepType, ConvergenceCriteria, MeasureEvaluator, solve_ac,
-    solve_newton_raphson,
+    AcParams, AcSweepType as SolverAcSweepType, AnalysisContext, AnalysisResult, MeasureEvaluator,
+    SimulationAnalysis,
 };
 use std::collections::HashMap;
 
-use crate::output::get_ac_print_nodes;
-use crate::stampers::{NetlistAcStamper, NetlistNonlinearStamper};
+use crate::output::{Column, Dataset, OutputFormat, get_ac_print_nodes, report};
 
 /// Run AC small-signal analysis.
 #[allow(clippy::too_many_arguments)]
 pub fn run_ac_analysis(
-    netlist: &spicier_core::Netlist,
+    netlist: &mut spicier_core::Netlist,
     sweep_type: AcSweepType,
     num_points: usize,
     fstart: f64,
     fstop: f64,
     print_vars: &[&OutputVariable],
-    node_map: &HashMap<String, NodeId>,
+    context: &AnalysisContext<'_>,
     measurements: &[&Measurement],
+    format: OutputFormat,
 ) -> Result<()> {
     let type_name = match sweep_type {
         AcSweepType::Dec => "DEC",
@@ -32,104 +31,107 @@ pub fn run_ac_analysis(
         AcSweepType::Lin | _ => "LIN",
     };
 
-    println!(
+    report!(
+        format,
         "AC Analysis (.AC {} {} {} {})",
-        type_name, num_points, fstart, fstop
+        type_name,
+        num_points,
+        fstart,
+        fstop
     );
-    println!("==========================================");
-    println!();
+    report!(format, "==========================================");
+    report!(format);
 
     // Get nodes to print from .PRINT AC variables
+    let node_map = context.node_map;
     let nodes_to_print = get_ac_print_nodes(print_vars, node_map, netlist.num_nodes());
 
-    // For nonlinear circuits, first compute DC operating point
-    let dc_solution = if netlist.has_nonlinear_devices() {
-        println!("Computing DC operating point for linearization...");
-
-        let stamper = NetlistNonlinearStamper { netlist };
-        let criteria = ConvergenceCriteria::default();
-        let nr_result = solve_newton_raphson(
-            netlist.num_nodes(),
-            netlist.num_current_vars(),
-            &stamper,
-            &criteria,
-            None,
-        )
-        .map_err(|e| anyhow::anyhow!("Newton-Raphson error: {}", e))?;
-
-        if !nr_result.converged {
-            eprintln!(
-                "Warning: DC operating point did not converge after {} iterations",
-                nr_result.iterations
-            );
-        } else {
-            println!(
-                "DC operating point converged in {} iterations.",
-                nr_result.iterations
-            );
-        }
-        println!();
-
-        Some(nr_result.solution)
-    } else {
-        None
-    };
-
     let solver_sweep_type = match sweep_type {
         AcSweepType::Dec => SolverAcSweepType::Decade,
         AcSweepType::Oct => SolverAcSweepType::Octave,
         AcSweepType::Lin | _ => SolverAcSweepType::Linear,
     };
 
-    let stamper = NetlistAcStamper {
-        netlist,
-        dc_solution: dc_solution.as_ref(),
+    // Nonlinear devices are linearized at the DC operating point
+    let analysis = SimulationAnalysis::Ac {
+        params: AcParams {
+            fstart,
+            fstop,
+            num_points,
+            sweep_type: solver_sweep_type,
+        },
     };
-
-    let params = AcParams {
-        fstart,
-        fstop,
-        num_points,
-        sweep_type: solver_sweep_type,
+    let AnalysisResult::Ac(result) = analysis
+        .run(netlist, context)
+        .map_err(|e| anyhow::anyhow!("Solver error: {}", e))?
+    else {
+        unreachable!(".AC yields an AC result");
     };
 
-    let result = solve_ac(&stamper, &params).map_err(|e| anyhow::anyhow!("Solver error: {}", e))?;
-
-    // Print header
-    print!("{:>14}", "Freq(Hz)");
-    for (name, _) in &nodes_to_print {
-        print!(
-            "{:>14}{:>14}",
-            format!("VM({})", name),
-            format!("VP({})", name)
-        );
-    }
-    println!();
-
-    let width = 14 + 28 * nodes_to_print.len();
-    println!("{}", "-".repeat(width));
-
-    // Print AC data
-    for point in &result.points {
-        print!("{:>14.4e}", point.frequency);
-        for (_, node_id) in &nodes_to_print {
-            let idx = (node_id.as_u32() - 1) as usize;
-            let v = point.solution[idx];
-            let mag_db = 20.0 * v.norm().log10();
-            let phase_deg = v.arg() * 180.0 / PI;
-            print!("{:>14.4}{:>14.4}", mag_db, phase_deg);
+    if format == OutputFormat::Table {
+        // Print header
+        print!("{:>14}", "Freq(Hz)");
+        for (name, _) in &nodes_to_print {
+            print!(
+                "{:>14}{:>14}",
+                format!("VM({})", name),
+                format!("VP({})", name)
+            );
         }
         println!();
+
+        let width = 14 + 28 * nodes_to_print.len();
+        println!("{}", "-".repeat(width));
+
+        // Print AC data
+        for point in &result.points {
+            print!("{:>14.4e}", point.frequency);
+            for (_, node_id) in &nodes_to_print {
+                let idx = (node_id.as_u32() - 1) as usize;
+                let v = point.solution[idx];
+                let mag_db = 20.0 * v.norm().log10();
+                let phase_deg = v.arg() * 180.0 / PI;
+                print!("{:>14.4}{:>14.4}", mag_db, phase_deg);
+            }
+            println!();
+        }
+    } else {
+        // Machine-readable formats carry the complex phasors themselves.
+        Dataset {
+            title: netlist.title().unwrap_or_default().to_string(),
+            plotname: "AC Analysis",
+            scale: Some(Column::real(
+                "frequency",
+                "frequency",
+                result.points.iter().map(|p| p.frequency).collect(),
+            )),
+            columns: nodes_to_print
+                .iter()
+                .map(|(name, node_id)| {
+                    let idx = (node_id.as_u32() - 1) as usize;
+                    Column::complex(
+                        format!("V({})", name),
+                        "voltage",
+                        result.points.iter().map(|p| p.solution[idx]).collect(),
+                    )
+                })
+                .collect(),
+        }
+        .emit(format)?;
     }
 
-    println!();
-    println!("AC analysis complete ({} points).", result.points.len());
+    report!(format);
+    report!(
+        format,
+        "AC analysis complete ({} points).",
+        result.points.len()
+    );
 
     // Evaluate and print measurements
     if !measurements.is_empty() {
-        println!();
-        println!("Measurements:");
-        println!("{}", "-".repeat(50));
+        report!(format);
+        report!(format, "Measurements:");
+        report!(format, "{}", "-".repeat(50));
 
         // Build node name to MNA index map for measurement evaluation
         let mna_node_map: HashMap<String, usize> = node_map
@@ -146,14 +148,14 @@ pub fn run_ac_analysis(
         for meas in measurements {
             let meas_result = MeasureEvaluator::eval_ac(meas, &result, &mna_node_map);
             if let Some(value) = meas_result.value {
-                println!("{} = {:12.6e}", meas_result.name, value);
+                report!(format, "{} = {:12.6e}", meas_result.name, value);
             } else if let Some(err) = meas_result.error {
-                println!("{} = FAILED ({})", meas_result.name, err);
+                report!(format, "{} = FAILED ({})", meas_result.name, err);
             }
         }
-        println!();
+        report!(format);
     }
 
-    println!();
+    report!(format);
     Ok(())
 }
@@ -6,6 +6,6 @@ pub mod noise;
 pub mod transient;
 
 pub use ac::run_ac_analysis;
-pub use dc::{run_dc_op, run_dc_param_sweep, run_dc_sweep};
+pub use dc::{run_dc_op, run_dc_sweep, run_dc_temp_sweep};
 pub use noise::run_noise_analysis;
-pub use transient::run_transient;
+pub use transient::{TransientLimits, run_transient};
@@ -13,7 +13,8 @@ use spicier_solver::{
     NoiseSweepType, compute_noise, solve_newton_raphson,
 };
 
-use crate::stampers::NetlistNonlinearStamper;
+use crate::output::{Column, Dataset, OutputFormat, report};
+use spicier_solver::NetlistNonlinearStamper;
 
 /// Netlist stamper for noise analysis.
 pub struct NetlistNoiseStamper<'a> {
@@ -71,11 +72,39 @@ impl<'a> NoiseStamper for NetlistNoiseStamper<'a> {
                     }
                 }
                 AcDeviceInfo::Mosfet {
-                    drain, source, gds, ..
+                    drain,
+                    source,
+                    gds,
+                    gm,
+                    saturated,
+                    ..
+                }
+                | AcDeviceInfo::Bsim1Mosfet {
+                    drain,
+                    source,
+                    gds,
+                    gm,
+                    saturated,
+                    ..
+                }
+                | AcDeviceInfo::Bsim3Mosfet {
+                    drain,
+                    source,
+                    gds,
+                    gm,
+                    saturated,
+                    ..
                 } => {
-                    // MOSFET thermal noise from channel (simplified)
-                    // For simplicity, model as equivalent resistance Req = 1/gds
-                    if gds > 0.0 {
+                    // MOSFET channel thermal noise: 8kT*gm/3 in saturation,
+                    // otherwise the 4kT*gds of the resistive channel
+                    if saturated && gm.abs() > 0.0 {
+                        sources.push(NoiseSource::channel_thermal(
+                            format!("{}_chan", name),
+                            drain,
+                            source,
+                            gm,
+                        ));
+                    } else if !saturated && gds > 0.0 {
                         sources.push(NoiseSource::thermal(
                             format!("{}_chan", name),
                             drain,
@@ -103,13 +132,16 @@ impl<'a> NoiseStamper for NetlistNoiseStamper<'a> {
                     emitter,
                     gm,
                     gpi,
+                    gmu,
                     ..
                 } => {
                     // BJT shot noise from base and collector currents
-                    // Ic ≈ gm * Vt, Ib ≈ gpi * Vt (rough estimate)
+                    // Ic ≈ gm * Vt, Ib ≈ gpi * Vt (rough estimate), plus the
+                    // base-collector diode current ≈ gmu * Vt
                     let vt: f64 = 0.026;
                     let ic = gm * vt;
                     let ib = gpi * vt;
+                    let ibc = gmu * vt;
 
                     if ic.abs() > 1e-15 {
                         sources.push(NoiseSource::shot(
@@ -122,32 +154,12 @@ impl<'a> NoiseStamper for NetlistNoiseStamper<'a> {
                     if ib.abs() > 1e-15 {
                         sources.push(NoiseSource::shot(format!("{}_ib", name), base, emitter, ib));
                     }
-                }
-                AcDeviceInfo::Bsim3Mosfet {
-                    drain, source, gds, ..
-                } => {
-                    // BSIM3 MOSFET thermal noise from channel
-                    // Similar to Level 1 MOSFET, model as equivalent resistance Req = 1/gds
-                    if gds > 0.0 {
-                        sources.push(NoiseSource::thermal(
-                            format!("{}_chan", name),
-                            drain,
-                            source,
-                            1.0 / gds,
-                        ));
-                    }
-                }
-                AcDeviceInfo::Bsim1Mosfet {
-                    drain, source, gds, ..
-                } => {
-                    // BSIM1 MOSFET thermal noise from channel
-                    // Model as equivalent resistance Req = 1/gds
-                    if gds > 0.0 {
-                        sources.push(NoiseSource::thermal(
-                            format!("{}_chan", name),
-                            drain,
-                            source,
-                            1.0 / gds,
+                    if ibc.abs() > 1e-15 {
+                        sources.push(NoiseSource::shot(
+                            format!("{}_ibc", name),
+                            base,
+                            collector,
+                            ibc,
                         ));
                     }
                 }
@@ -251,6 +263,7 @@ fn stamp_ac_device(mna: &mut ComplexMna, info: &AcDeviceInfo, omega: f64) {
             source,
             gds,
             gm,
+            ..
         } => {
             // Stamp output conductance gds between drain and source
             mna.stamp_conductance(*drain, *source, *gds);
@@ -274,9 +287,12 @@ fn stamp_ac_device(mna: &mut ComplexMna, info: &AcDeviceInfo, omega: f64) {
             gm,
             go,
             gpi,
+            gmu,
         } => {
             // Input conductance gpi between base and emitter
             mna.stamp_conductance(*base, *emitter, *gpi);
+            // Base-collector conductance gmu
+            mna.stamp_conductance(*base, *collector, *gmu);
             // Output conductance go between collector and emitter
             mna.stamp_conductance(*collector, *emitter, *go);
             // Transconductance gm as VCCS from base-emitter to collector-emitter
@@ -305,6 +321,7 @@ fn stamp_ac_device(mna: &mut ComplexMna, info: &AcDeviceInfo, omega: f64) {
             gds,
             gm,
             gmbs,
+            ..
         } => {
             // BSIM1 small-signal model (same as BSIM3 but without capacitances)
             mna.stamp_conductance(*drain, *source, *gds);
@@ -335,8 +352,10 @@ pub fn run_noise_analysis(
     fstart: f64,
     fstop: f64,
     node_map: &HashMap<String, NodeId>,
+    format: OutputFormat,
 ) -> Result<()> {
-    println!(
+    report!(
+        format,
         "Noise Analysis (.NOISE V({}{}) {} {} {} {} {})",
         output_node,
         output_ref_node
@@ -353,8 +372,8 @@ pub fn run_noise_analysis(
         fstart,
         fstop
     );
-    println!("==========================================");
-    println!();
+    report!(format, "==========================================");
+    report!(format);
 
     // First compute DC operating point
     let dc_solution = if netlist.has_nonlinear_devices() {
@@ -455,31 +474,52 @@ pub fn run_noise_analysis(
         .map_err(|e| anyhow::anyhow!("Noise analysis error: {}", e))?;
 
     // Print results
-    println!("Frequency (Hz)    Output Noise (V/√Hz)    Input Noise (V/√Hz)    Equiv Rn (Ω)");
-    println!("{}", "-".repeat(78));
-
-    for i in 0..result.frequencies.len() {
-        let freq = result.frequencies[i];
-        let out_noise = result.output_noise[i];
-        let in_noise = if !result.input_noise.is_empty() {
-            result.input_noise[i]
-        } else {
-            0.0
-        };
-        let rn = result.equiv_input_noise_resistance[i];
-
-        println!(
-            "{:>14.4e}    {:>18.4e}    {:>18.4e}    {:>12.2}",
-            freq, out_noise, in_noise, rn
-        );
+    let input_noise = if !result.input_noise.is_empty() {
+        result.input_noise.clone()
+    } else {
+        vec![0.0; result.frequencies.len()]
+    };
+    if format == OutputFormat::Table {
+        println!("Frequency (Hz)    Output Noise (V/√Hz)    Input Noise (V/√Hz)    Equiv Rn (Ω)");
+        println!("{}", "-".repeat(78));
+
+        for i in 0..result.frequencies.len() {
+            println!(
+                "{:>14.4e}    {:>18.4e}    {:>18.4e}    {:>12.2}",
+                result.frequencies[i],
+                result.output_noise[i],
+                input_noise[i],
+                result.equiv_input_noise_resistance[i]
+            );
+        }
+    } else {
+        Dataset {
+            title: netlist.title().unwrap_or_default().to_string(),
+            plotname: "Noise Spectral Density Curves",
+            scale: Some(Column::real(
+                "frequency",
+                "frequency",
+                result.frequencies.clone(),
+            )),
+            columns: vec![
+                Column::real("onoise_spectrum", "voltage", result.output_noise.clone()),
+                Column::real("inoise_spectrum", "voltage", input_noise),
+                Column::real(
+                    "equiv_rn",
+                    "impedance",
+                    result.equiv_input_noise_resistance.clone(),
+                ),
+            ],
+        }
+        .emit(format)?;
     }
 
-    println!();
+    report!(format);
 
     // Print noise contributions at a representative frequency (geometric mean)
     let mid_freq = (fstart * fstop).sqrt();
-    println!("Noise Contributions at {:.2e} Hz:", mid_freq);
-    println!("{}", "-".repeat(50));
+    report!(format, "Noise Contributions at {:.2e} Hz:", mid_freq);
+    report!(format, "{}", "-".repeat(50));
 
     // Find the index closest to mid frequency
     let mid_idx = result
@@ -505,21 +545,24 @@ pub fn run_noise_analysis(
     for contrib in sorted_contribs.iter().take(10) {
         let percent = contrib.contribution_percent[mid_idx];
         if percent > 0.01 {
-            println!("  {:<20} {:>6.2}%", contrib.source_name, percent);
+            report!(format, "  {:<20} {:>6.2}%", contrib.source_name, percent);
         }
     }
 
-    println!();
+    report!(format);
 
     // Compute integrated noise over the frequency range
     let integrated = result.integrated_noise(fstart, fstop);
-    println!(
+    report!(
+        format,
         "Integrated Output Noise ({:.0} Hz - {:.0} Hz): {:.4e} V RMS",
-        fstart, fstop, integrated
+        fstart,
+        fstop,
+        integrated
     );
-    println!();
-    println!("Noise analysis complete.");
-    println!();
+    report!(format);
+    report!(format, "Noise analysis complete.");
+    report!(format);
 
     Ok(())
 }
@@ -1,170 +1,216 @@
 //! Transient time-domain analysis.
 
 use anyhow::Result;
-use nalgebra::DVector;
 use spicier_core::NodeId;
-use spicier_parser::{InitialCondition, Measurement, OutputVariable};
+use spicier_parser::{Measurement, OutputVariable};
+use spicier_solver::transient::TimePoint;
 use spicier_solver::{
-    ConvergenceCriteria, InitialConditions, IntegrationMethod, MeasureEvaluator, TransientParams,
-    TransientStamper, solve_dc, solve_newton_raphson, solve_transient,
+    AnalysisContext, AnalysisResult, IntegrationMethod, MeasureEvaluator, SimulationAnalysis,
+    TransientParams,
 };
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use crate::output::{Column, Dataset, OutputFormat, get_dc_print_nodes, report};
+
+/// Budgets that stop a transient run early, keeping the points computed so far.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransientLimits {
+    /// Wall-clock time allowed for the run, including the DC operating point.
+    pub max_wall_time: Option<Duration>,
+    /// Number of timesteps allowed.
+    pub max_steps: Option<usize>,
+}
+
+/// Why a transient run stopped before `tstop`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransientStop {
+    /// The wall-clock budget ran out.
+    WallTime(Duration),
+    /// The step budget ran out.
+    Steps(usize),
+}
 
-use crate::output::get_dc_print_nodes;
-use crate::stampers::{NetlistNonlinearStamper, NetlistTransientStamper, build_transient_state};
+impl fmt::Display for TransientStop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WallTime(budget) => {
+                write!(f, "wall-time budget of {}s exceeded", budget.as_secs_f64())
+            }
+            Self::Steps(budget) => write!(f, "step budget of {} steps reached", budget),
+        }
+    }
+}
 
 /// Run transient time-domain analysis.
+///
+/// Returns the reason the run stopped early if one of `limits` cut it short;
+/// the partial waveform has still been printed.
 #[allow(clippy::too_many_arguments)]
 pub fn run_transient(
-    netlist: &spicier_core::Netlist,
+    netlist: &mut spicier_core::Netlist,
     tstep: f64,
     tstop: f64,
     tstart: f64,
     uic: bool,
-    initial_conditions: &[InitialCondition],
-    node_map: &HashMap<String, NodeId>,
+    context: &AnalysisContext<'_>,
     print_vars: &[&OutputVariable],
     measurements: &[&Measurement],
-) -> Result<()> {
-    println!(
+    limits: &TransientLimits,
+    format: OutputFormat,
+) -> Result<Option<TransientStop>> {
+    let started = Instant::now();
+    report!(
+        format,
         "Transient Analysis (.TRAN {} {} {}{})",
         tstep,
         tstop,
         tstart,
         if uic { " UIC" } else { "" }
     );
-    println!("==========================================");
-    println!();
-
-    // 1. Get initial conditions - either from DC operating point or from .IC values (if UIC)
-    let mut dc_solution = if uic {
-        // UIC: Skip DC operating point, start from zero and apply .IC values
-        println!("UIC: Skipping DC operating point calculation.");
-        DVector::zeros(netlist.num_nodes() + netlist.num_current_vars())
-    } else if netlist.has_nonlinear_devices() {
-        let stamper = NetlistNonlinearStamper { netlist };
-        let criteria = ConvergenceCriteria::default();
-        let nr_result = solve_newton_raphson(
-            netlist.num_nodes(),
-            netlist.num_current_vars(),
-            &stamper,
-            &criteria,
-            None,
-        )
-        .map_err(|e| anyhow::anyhow!("DC operating point error: {}", e))?;
-        nr_result.solution
-    } else {
-        let mna = netlist.assemble_mna();
-        let dc = solve_dc(&mna).map_err(|e| anyhow::anyhow!("DC operating point error: {}", e))?;
-        // Reconstruct full solution vector
-        let mut full = DVector::zeros(netlist.num_nodes() + netlist.num_current_vars());
-        for i in 0..dc.num_nodes {
-            full[i] = dc.node_voltages[i];
-        }
-        for i in 0..dc.branch_currents.len() {
-            full[dc.num_nodes + i] = dc.branch_currents[i];
-        }
-        full
-    };
-
-    // 1b. Apply .IC initial conditions (override DC solution)
-    if !initial_conditions.is_empty() {
-        // Convert parser's InitialCondition to solver's InitialConditions
-        let mut ic = InitialConditions::new();
-        for parsed_ic in initial_conditions {
-            ic.set_voltage(&parsed_ic.node, parsed_ic.voltage);
-        }
-        // Build MNA index map from node_map
-        // NodeId.as_u32() is the node number (1-based), MNA index is (node_number - 1)
-        let mna_index_map: HashMap<String, usize> = node_map
-            .iter()
-            .filter_map(|(name, node_id)| {
-                if node_id.is_ground() {
-                    None
-                } else {
-                    Some((name.clone(), node_id.as_u32() as usize - 1))
-                }
-            })
-            .collect();
-        ic.apply(&mut dc_solution, &mna_index_map);
+    report!(format, "==========================================");
+    report!(format);
 
-        println!("Applied initial conditions:");
-        for parsed_ic in initial_conditions {
-            println!("  V({}) = {} V", parsed_ic.node, parsed_ic.voltage);
+    if uic {
+        report!(format, "UIC: Skipping DC operating point calculation.");
+    }
+    if !context.initial_conditions.is_empty() {
+        report!(format, "Applied initial conditions:");
+        for parsed_ic in context.initial_conditions {
+            report!(format, "  V({}) = {} V", parsed_ic.node, parsed_ic.voltage);
         }
-        println!();
+        report!(format);
     }
 
-    // 2. Build reactive element state vectors
-    let (mut caps, mut inds) = build_transient_state(netlist);
-
-    // 3. Build transient stamper (stamps non-reactive devices)
-    let stamper = NetlistTransientStamper { netlist };
-
-    // 4. Run transient simulation with Trapezoidal method
-    let params = TransientParams {
-        tstop,
-        tstep,
-        method: IntegrationMethod::Trapezoidal,
+    let analysis = SimulationAnalysis::Tran {
+        params: TransientParams {
+            tstop,
+            tstep,
+            method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        },
+        tstart,
+        uic,
     };
 
-    // Adjust DC solution size if inductor companion models change MNA dimensions
-    let tran_size = stamper.num_nodes() + stamper.num_vsources();
-    let dc_for_tran = if dc_solution.len() != tran_size {
-        let mut adjusted = DVector::zeros(tran_size);
-        for i in 0..tran_size.min(dc_solution.len()) {
-            adjusted[i] = dc_solution[i];
-        }
-        adjusted
-    } else {
-        dc_solution
+    let mut stop = None;
+    let mut steps = 0;
+    let result = analysis
+        .run_with_progress(netlist, context, &mut |_| {
+            steps += 1;
+            stop = if limits.max_steps.is_some_and(|max| steps >= max) {
+                limits.max_steps.map(TransientStop::Steps)
+            } else if limits
+                .max_wall_time
+                .is_some_and(|max| started.elapsed() >= max)
+            {
+                limits.max_wall_time.map(TransientStop::WallTime)
+            } else {
+                None
+            };
+            match stop {
+                Some(_) => ControlFlow::Break(()),
+                None => ControlFlow::Continue(()),
+            }
+        })
+        .map_err(|e| anyhow::anyhow!("Transient error: {}", e))?;
+    let AnalysisResult::Tran(result) = result else {
+        unreachable!(".TRAN yields a transient result");
     };
 
-    let result = solve_transient(&stamper, &mut caps, &mut inds, &params, &dc_for_tran)
-        .map_err(|e| anyhow::anyhow!("Transient error: {}", e))?;
+    // Warn if tstep is too coarse to resolve the fastest time constant
+    let tau = result
+        .points
+        .first()
+        .and_then(|point| netlist.estimate_min_time_constant_at(&point.solution));
+    if let Some(tau) = tau {
+        if tstep > tau {
+            eprintln!(
+                "Warning: tstep ({:.3e}s) exceeds the smallest estimated time constant ({:.3e}s); \
+                 fast transients may be aliased",
+                tstep, tau
+            );
+        }
+    }
 
-    // 5. Print tabular output
+    let node_map = context.node_map;
     let nodes_to_print = get_dc_print_nodes(print_vars, node_map, netlist.num_nodes());
+    let voltage = |point: &TimePoint, node_id: &NodeId| {
+        let idx = (node_id.as_u32() - 1) as usize;
+        if idx < point.solution.len() {
+            point.solution[idx]
+        } else {
+            0.0
+        }
+    };
+    let points = &result.points;
 
-    // Header
-    print!("{:>14}", "Time");
-    for (name, _) in &nodes_to_print {
-        print!("{:>14}", format!("V({})", name));
-    }
-    println!();
+    if format == OutputFormat::Table {
+        // Header
+        print!("{:>14}", "Time");
+        for (name, _) in &nodes_to_print {
+            print!("{:>14}", format!("V({})", name));
+        }
+        println!();
 
-    let width = 14 * (1 + nodes_to_print.len());
-    println!("{}", "-".repeat(width));
+        let width = 14 * (1 + nodes_to_print.len());
+        println!("{}", "-".repeat(width));
 
-    // Data (skip points before tstart)
-    for point in &result.points {
-        if point.time < tstart - tstep * 0.5 {
-            continue;
+        for point in points {
+            print!("{:>14.6e}", point.time);
+            for (_, node_id) in &nodes_to_print {
+                print!("{:>14.6}", voltage(point, node_id));
+            }
+            println!();
         }
-        print!("{:>14.6e}", point.time);
-        for (_, node_id) in &nodes_to_print {
-            let idx = (node_id.as_u32() - 1) as usize;
-            let v = if idx < point.solution.len() {
-                point.solution[idx]
-            } else {
-                0.0
-            };
-            print!("{:>14.6}", v);
+    } else {
+        Dataset {
+            title: netlist.title().unwrap_or_default().to_string(),
+            plotname: "Transient Analysis",
+            scale: Some(Column::real(
+                "time",
+                "time",
+                points.iter().map(|p| p.time).collect(),
+            )),
+            columns: nodes_to_print
+                .iter()
+                .map(|(name, node_id)| {
+                    Column::real(
+                        format!("V({})", name),
+                        "voltage",
+                        points.iter().map(|p| voltage(p, node_id)).collect(),
+                    )
+                })
+                .collect(),
         }
-        println!();
+        .emit(format)?;
     }
 
-    println!();
-    println!(
-        "Transient analysis complete ({} points).",
-        result.points.len()
-    );
+    report!(format);
+    match stop {
+        None => report!(
+            format,
+            "Transient analysis complete ({} points).",
+            result.points.len()
+        ),
+        Some(reason) => report!(
+            format,
+            "Transient analysis stopped at t = {:.6e}s of {:.6e}s ({}; {} points).",
+            result.points.last().map_or(0.0, |p| p.time),
+            tstop,
+            reason,
+            result.points.len()
+        ),
+    }
 
     // Evaluate and print measurements
     if !measurements.is_empty() {
-        println!();
-        println!("Measurements:");
-        println!("{}", "-".repeat(50));
+        report!(format);
+        report!(format, "Measurements:");
+        report!(format, "{}", "-".repeat(50));
 
         // Build node name to MNA index map for measurement evaluation
         let mna_node_map: HashMap<String, usize> = node_map
@@ -181,14 +227,14 @@ pub fn run_transient(
         for meas in measurements {
             let meas_result = MeasureEvaluator::eval_tran(meas, &result, &mna_node_map);
             if let Some(value) = meas_result.value {
-                println!("{} = {:12.6e}", meas_result.name, value);
+                report!(format, "{} = {:12.6e}", meas_result.name, value);
             } else if let Some(err) = meas_result.error {
-                println!("{} = FAILED ({})", meas_result.name, err);
+                report!(format, "{} = FAILED ({})", meas_result.name, err);
             }
         }
-        println!();
+        report!(format);
     }
 
-    println!();
-    Ok(())
+    report!(format);
+    Ok(stop)
 }
@@ -3,21 +3,27 @@
 mod analysis;
 mod backend;
 mod output;
-mod stampers;
 
 use std::fs;
 use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use spicier_core::{TopologyRepair, ValidationOptions};
 use spicier_parser::{
-    AnalysisCommand, DcSweepType, MeasureAnalysis, Measurement, PrintAnalysisType, parse_full,
+    AnalysisCommand, DcSweepType, MeasureAnalysis, Measurement, OutputVariable, PrintAnalysisType,
+    UnsupportedPolicy, parse_full_with_policy,
 };
+use spicier_solver::{AnalysisContext, ConvergenceCriteria};
 
 use analysis::{
-    run_ac_analysis, run_dc_op, run_dc_param_sweep, run_dc_sweep, run_noise_analysis, run_transient,
+    TransientLimits, run_ac_analysis, run_dc_op, run_dc_sweep, run_dc_temp_sweep,
+    run_noise_analysis, run_transient,
 };
 use backend::detect_backend;
+use output::{OutputFormat, report};
 
 #[derive(Parser)]
 #[command(name = "spicier")]
@@ -36,12 +42,33 @@ struct Cli {
     #[arg(long, default_value = "auto")]
     backend: String,
 
+    /// Output format for analysis results
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Stop a transient run after this many seconds, keeping the partial result
+    #[arg(long, value_name = "SECONDS")]
+    max_wall_time: Option<f64>,
+
+    /// Stop a transient run after this many timesteps, keeping the partial result
+    #[arg(long, value_name = "N")]
+    max_steps: Option<usize>,
+
+    /// Warn about unsupported devices and directives and simulate the rest,
+    /// instead of failing
+    #[arg(long)]
+    allow_unsupported: bool,
+
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
 }
 
-fn main() -> Result<()> {
+/// Exit status when a transient run was cut short by `--max-wall-time` or
+/// `--max-steps`; its partial output has still been written.
+const EXIT_TRANSIENT_STOPPED: u8 = 2;
+
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
 
     if let Some(ref input) = cli.input {
@@ -49,10 +76,12 @@ fn main() -> Result<()> {
         let backend = detect_backend(&cli.backend);
 
         if cli.verbose {
-            println!("Backend: {}", backend);
+            report!(cli.format, "Backend: {}", backend);
         }
 
-        run_simulation(input, &cli)?;
+        if !run_simulation(input, &cli)? {
+            return Ok(ExitCode::from(EXIT_TRANSIENT_STOPPED));
+        }
     } else {
         println!("Spicier - High Performance SPICE Simulator");
         println!();
@@ -61,34 +90,92 @@ fn main() -> Result<()> {
         println!("Options:");
         println!("  -o, --op           Run DC operating point analysis");
         println!("  --backend <NAME>   Compute backend: auto, cpu, cuda, metal");
+        println!("  --format <FORMAT>  Output format: table, csv, json, raw");
+        println!("  --max-wall-time <SECONDS>  Stop a transient run after SECONDS");
+        println!("  --max-steps <N>    Stop a transient run after N timesteps");
+        println!("  --allow-unsupported  Skip unsupported devices and directives with a warning");
         println!("  -v, --verbose      Verbose output");
         println!("  -h, --help         Show help");
         println!("  -V, --version      Show version");
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
+/// Run every analysis in the netlist, returning `false` if a transient run
+/// was stopped early by its limits.
+fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<bool> {
     // Read netlist file
     let content = fs::read_to_string(input)
         .with_context(|| format!("Failed to read netlist: {}", input.display()))?;
 
     // Parse netlist with analysis commands
-    let result = parse_full(&content).map_err(|e| anyhow::anyhow!("Parse error: {}", e))?;
-    let netlist = result.netlist;
+    let policy = if cli.allow_unsupported {
+        UnsupportedPolicy::Warn
+    } else {
+        UnsupportedPolicy::Error
+    };
+    let result = parse_full_with_policy(&content, policy)
+        .map_err(|e| anyhow::anyhow!("Parse error: {}", e))?;
+    for construct in &result.unsupported {
+        eprintln!("Warning: {} is not supported and was ignored", construct);
+    }
+    let mut netlist = result.netlist;
     let analyses = result.analyses;
     let initial_conditions = result.initial_conditions;
     let node_map = result.node_map;
     let print_commands = result.print_commands;
     let measurements = result.measurements;
 
+    // Apply the circuit temperature from .TEMP / .OPTIONS TEMP=
+    if let Some(temp) = result.temperature {
+        netlist.set_temperature_all(temp);
+    }
+
+    // A circuit with nothing tied to node 0 has no voltage reference
+    netlist
+        .check_ground_reference()
+        .map_err(|e| anyhow::anyhow!("Netlist error: {}", e))?;
+
+    // Report degenerate devices (same-node terminals, zero-valued R/C)
+    let degenerate = netlist
+        .validate(&ValidationOptions::default())
+        .map_err(|e| anyhow::anyhow!("Netlist error: {}", e))?;
+    for device in &degenerate {
+        eprintln!("Warning: device {} has {}", device.name, device.kind);
+    }
+
+    // Break inductor loops and capacitor cutsets that leave the DC system singular
+    for issue in netlist.repair_topology(&TopologyRepair::default()) {
+        eprintln!("Warning: {issue}; inserted a small parasitic to make it solvable");
+    }
+
+    // Splice sense sources in for .PRINT I(device) on devices without a branch current
+    for print in &print_commands {
+        for var in &print.variables {
+            if let OutputVariable::Current { device } = var {
+                if let Err(e) = netlist.add_current_probe(device) {
+                    eprintln!("Warning: cannot print I({device}): {e}");
+                }
+            }
+        }
+    }
+
     if cli.verbose {
-        println!("Circuit: {}", netlist.title().unwrap_or("(untitled)"));
-        println!("Nodes: {}", netlist.num_nodes());
-        println!("Devices: {}", netlist.num_devices());
-        println!("Current variables: {}", netlist.num_current_vars());
-        println!(
+        report!(
+            cli.format,
+            "Circuit: {}",
+            netlist.title().unwrap_or("(untitled)")
+        );
+        report!(cli.format, "Nodes: {}", netlist.num_nodes());
+        report!(cli.format, "Devices: {}", netlist.num_devices());
+        report!(
+            cli.format,
+            "Current variables: {}",
+            netlist.num_current_vars()
+        );
+        report!(
+            cli.format,
             "Analysis commands: {}",
             if analyses.is_empty() {
                 "none (defaulting to .OP)".to_string()
@@ -112,11 +199,27 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
             }
         );
         if !print_commands.is_empty() {
-            println!("Print commands: {}", print_commands.len());
+            report!(cli.format, "Print commands: {}", print_commands.len());
         }
-        println!();
+        report!(cli.format);
     }
 
+    let limits = TransientLimits {
+        max_wall_time: cli
+            .max_wall_time
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .context("Invalid --max-wall-time")?,
+        max_steps: cli.max_steps,
+    };
+    let tolerances = ConvergenceCriteria::default();
+    let context = AnalysisContext {
+        initial_conditions: &initial_conditions,
+        node_map: &node_map,
+        tolerances: &tolerances,
+    };
+    let mut completed = true;
+
     // Helper to find print commands for an analysis type
     let get_print_vars = |analysis_type: PrintAnalysisType| -> Vec<&_> {
         print_commands
@@ -138,7 +241,13 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
     if cli.dc_op || analyses.is_empty() {
         let print_vars = get_print_vars(PrintAnalysisType::Dc);
         let dc_measurements = get_measurements(MeasureAnalysis::Dc);
-        run_dc_op(&netlist, &print_vars, &node_map, &dc_measurements)?;
+        run_dc_op(
+            &mut netlist,
+            &print_vars,
+            &context,
+            &dc_measurements,
+            cli.format,
+        )?;
     }
 
     // Run each analysis command
@@ -147,19 +256,45 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
             AnalysisCommand::Op => {
                 let print_vars = get_print_vars(PrintAnalysisType::Dc);
                 let dc_measurements = get_measurements(MeasureAnalysis::Dc);
-                run_dc_op(&netlist, &print_vars, &node_map, &dc_measurements)?;
+                run_dc_op(
+                    &mut netlist,
+                    &print_vars,
+                    &context,
+                    &dc_measurements,
+                    cli.format,
+                )?;
             }
             AnalysisCommand::Dc { sweeps } => {
                 let print_vars = get_print_vars(PrintAnalysisType::Dc);
                 let dc_measurements = get_measurements(MeasureAnalysis::Dc);
 
-                // Check if this is a parameter sweep
-                let has_param_sweep = sweeps.iter().any(|s| s.sweep_type == DcSweepType::Param);
+                let temp_sweep = sweeps.iter().find(|s| s.sweep_type == DcSweepType::Temp);
 
-                if has_param_sweep {
-                    run_dc_param_sweep(&content, sweeps, &print_vars, &dc_measurements)?;
+                if let Some(sweep) = temp_sweep {
+                    if sweeps.len() > 1 {
+                        return Err(anyhow::anyhow!(
+                            "Nested temperature sweeps are not yet supported"
+                        ));
+                    }
+                    run_dc_temp_sweep(
+                        &mut netlist,
+                        sweep,
+                        &print_vars,
+                        &node_map,
+                        &dc_measurements,
+                        cli.format,
+                    )?;
+                    // Later analyses run at the circuit temperature again
+                    netlist.set_temperature_all(result.temperature.unwrap_or(300.15));
                 } else {
-                    run_dc_sweep(&netlist, sweeps, &print_vars, &node_map, &dc_measurements)?;
+                    run_dc_sweep(
+                        &mut netlist,
+                        sweeps,
+                        &print_vars,
+                        &context,
+                        &dc_measurements,
+                        cli.format,
+                    )?;
                 }
             }
             AnalysisCommand::Ac {
@@ -171,14 +306,15 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
                 let print_vars = get_print_vars(PrintAnalysisType::Ac);
                 let ac_measurements = get_measurements(MeasureAnalysis::Ac);
                 run_ac_analysis(
-                    &netlist,
+                    &mut netlist,
                     *sweep_type,
                     *num_points,
                     *fstart,
                     *fstop,
                     &print_vars,
-                    &node_map,
+                    &context,
                     &ac_measurements,
+                    cli.format,
                 )?;
             }
             AnalysisCommand::Tran {
@@ -189,17 +325,21 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
             } => {
                 let print_vars = get_print_vars(PrintAnalysisType::Tran);
                 let tran_measurements = get_measurements(MeasureAnalysis::Tran);
-                run_transient(
-                    &netlist,
+                if let Some(reason) = run_transient(
+                    &mut netlist,
                     *tstep,
                     *tstop,
                     *tstart,
                     *uic,
-                    &initial_conditions,
-                    &node_map,
+                    &context,
                     &print_vars,
                     &tran_measurements,
-                )?;
+                    &limits,
+                    cli.format,
+                )? {
+                    eprintln!("Warning: transient analysis stopped early: {reason}");
+                    completed = false;
+                }
             }
             AnalysisCommand::Noise {
                 output_node,
@@ -220,6 +360,7 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
                     *fstart,
                     *fstop,
                     &node_map,
+                    cli.format,
                 )?;
             }
             _ => {
@@ -228,5 +369,5 @@ fn run_simulation(input: &PathBuf, cli: &Cli) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(completed)
 }
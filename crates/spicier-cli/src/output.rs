@@ -1,9 +1,12 @@
 //! Output formatting and print variable handling.
 
+use anyhow::Result;
+use num_complex::Complex;
 use spicier_core::NodeId;
 use spicier_parser::OutputVariable;
-use spicier_solver::DcSolution;
+use spicier_solver::{DcSolution, RawEncoding, RawValues, RawVariable, write_raw};
 use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
 
 /// Get list of (name, NodeId) pairs to print based on .PRINT variables.
 /// If print_vars is empty, prints all nodes.
@@ -40,6 +43,23 @@ pub fn get_dc_print_nodes(
     }
 }
 
+/// Get list of (device name, branch index) pairs for the `I(device)`
+/// variables in a .PRINT, skipping devices without a branch current.
+pub fn get_print_currents(
+    print_vars: &[&OutputVariable],
+    netlist: &spicier_core::Netlist,
+) -> Vec<(String, usize)> {
+    print_vars
+        .iter()
+        .filter_map(|v| match v {
+            OutputVariable::Current { device } => netlist
+                .find_vsource_branch_index(device)
+                .map(|branch| (device.clone(), branch)),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Get list of (name, NodeId) pairs to print based on .PRINT AC variables.
 /// Handles V(), VM(), VP(), VDB(), VR(), VI() output types.
 /// If print_vars is empty, prints all nodes.
@@ -115,6 +135,281 @@ pub fn print_dc_solution(
             let current = solution.current(i);
             println!("  I(branch{}) = {:.6} A", i, current);
         }
+    } else {
+        let currents = get_print_currents(print_vars, netlist);
+        if !currents.is_empty() {
+            println!();
+            println!("Branch Currents:");
+            for (name, branch) in &currents {
+                println!("  I({}) = {:.6} A", name, solution.current(*branch));
+            }
+        }
     }
     println!();
 }
+
+/// DC operating point as a single-point dataset, with the same variables
+/// [`print_dc_solution`] prints.
+pub fn dc_op_dataset(
+    netlist: &spicier_core::Netlist,
+    solution: &DcSolution,
+    print_vars: &[&OutputVariable],
+    node_map: &HashMap<String, NodeId>,
+) -> Dataset {
+    let nodes = get_dc_print_nodes(print_vars, node_map, netlist.num_nodes());
+    let mut columns = dc_voltage_columns(&nodes, &[solution]);
+    if print_vars.is_empty() {
+        columns.extend((0..netlist.num_current_vars()).map(|i| {
+            Column::real(
+                format!("I(branch{})", i),
+                "current",
+                vec![solution.current(i)],
+            )
+        }));
+    } else {
+        columns.extend(get_print_currents(print_vars, netlist).into_iter().map(
+            |(name, branch)| {
+                Column::real(
+                    format!("I({})", name),
+                    "current",
+                    vec![solution.current(branch)],
+                )
+            },
+        ));
+    }
+    Dataset {
+        title: netlist.title().unwrap_or_default().to_string(),
+        plotname: "Operating Point",
+        scale: None,
+        columns,
+    }
+}
+
+/// One `V(name)` column per node across a sequence of DC solutions.
+pub fn dc_voltage_columns(nodes: &[(String, NodeId)], solutions: &[&DcSolution]) -> Vec<Column> {
+    nodes
+        .iter()
+        .map(|(name, node_id)| {
+            Column::real(
+                format!("V({})", name),
+                "voltage",
+                solutions.iter().map(|s| s.voltage(*node_id)).collect(),
+            )
+        })
+        .collect()
+}
+
+/// How analysis results are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables.
+    #[default]
+    Table,
+    /// Comma-separated values with a header row, one block per analysis
+    /// followed by a blank line.
+    Csv,
+    /// One JSON object per analysis, one per line.
+    Json,
+    /// ngspice-compatible ASCII rawfile.
+    Raw,
+}
+
+/// Print human-readable text: to stdout for tables, and to stderr for the
+/// machine-readable formats so stdout stays parseable.
+macro_rules! report {
+    ($format:expr) => {
+        report!($format, "")
+    };
+    ($format:expr, $($arg:tt)*) => {
+        if $format == $crate::output::OutputFormat::Table {
+            println!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use report;
+
+/// Values of one output column.
+pub enum Values {
+    Real(Vec<f64>),
+    Complex(Vec<Complex<f64>>),
+}
+
+impl Values {
+    fn len(&self) -> usize {
+        match self {
+            Values::Real(v) => v.len(),
+            Values::Complex(v) => v.len(),
+        }
+    }
+}
+
+/// One named output column.
+pub struct Column {
+    /// Column name, e.g. `V(out)`.
+    pub name: String,
+    /// Rawfile variable type: `time`, `frequency`, `voltage`, `current`, ...
+    pub kind: &'static str,
+    pub values: Values,
+}
+
+impl Column {
+    pub fn real(name: impl Into<String>, kind: &'static str, values: Vec<f64>) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            values: Values::Real(values),
+        }
+    }
+
+    pub fn complex(name: impl Into<String>, kind: &'static str, values: Vec<Complex<f64>>) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            values: Values::Complex(values),
+        }
+    }
+}
+
+/// One analysis's results in a form every machine-readable format can write.
+pub struct Dataset {
+    /// Circuit title.
+    pub title: String,
+    /// Analysis name, e.g. `Transient Analysis`.
+    pub plotname: &'static str,
+    /// Independent variable (time, frequency, swept source); `None` for a
+    /// single operating point.
+    pub scale: Option<Column>,
+    /// Dependent variables, each with one value per point.
+    pub columns: Vec<Column>,
+}
+
+impl Dataset {
+    fn num_points(&self) -> usize {
+        self.scale
+            .iter()
+            .chain(&self.columns)
+            .map(|c| c.values.len())
+            .next()
+            .unwrap_or(0)
+    }
+
+    /// Write the dataset to stdout. Tables are printed by each analysis
+    /// runner itself, so this is a no-op for [`OutputFormat::Table`].
+    pub fn emit(&self, format: OutputFormat) -> Result<()> {
+        let mut out = io::stdout().lock();
+        match format {
+            OutputFormat::Table => return Ok(()),
+            OutputFormat::Csv => self.write_csv(&mut out)?,
+            OutputFormat::Json => self.write_json(&mut out)?,
+            OutputFormat::Raw => self.write_raw(&mut out)?,
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// CSV with one header row. Complex columns split into `Re(...)` and
+    /// `Im(...)` columns.
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut header = Vec::new();
+        for column in self.scale.iter().chain(&self.columns) {
+            match column.values {
+                Values::Real(_) => header.push(csv_field(&column.name)),
+                Values::Complex(_) => {
+                    header.push(csv_field(&format!("Re({})", column.name)));
+                    header.push(csv_field(&format!("Im({})", column.name)));
+                }
+            }
+        }
+        writeln!(out, "{}", header.join(","))?;
+
+        for i in 0..self.num_points() {
+            let mut row = Vec::new();
+            for column in self.scale.iter().chain(&self.columns) {
+                match &column.values {
+                    Values::Real(v) => row.push(format!("{:e}", v[i])),
+                    Values::Complex(v) => {
+                        row.push(format!("{:e}", v[i].re));
+                        row.push(format!("{:e}", v[i].im));
+                    }
+                }
+            }
+            writeln!(out, "{}", row.join(","))?;
+        }
+        writeln!(out)
+    }
+
+    /// A single-line JSON object:
+    ///
+    /// ```text
+    /// {"title": ..., "analysis": ..., "scale": {"name", "type", "values"} | null,
+    ///  "variables": [{"name", "type", "values"} | {"name", "type", "real", "imag"}]}
+    /// ```
+    pub fn write_json(&self, out: &mut impl Write) -> io::Result<()> {
+        let column_json = |column: &Column| match &column.values {
+            Values::Real(v) => serde_json::json!({
+                "name": column.name,
+                "type": column.kind,
+                "values": v,
+            }),
+            Values::Complex(v) => serde_json::json!({
+                "name": column.name,
+                "type": column.kind,
+                "real": v.iter().map(|c| c.re).collect::<Vec<_>>(),
+                "imag": v.iter().map(|c| c.im).collect::<Vec<_>>(),
+            }),
+        };
+        let json = serde_json::json!({
+            "title": self.title,
+            "analysis": self.plotname,
+            "scale": self.scale.as_ref().map(column_json),
+            "variables": self.columns.iter().map(column_json).collect::<Vec<_>>(),
+        });
+        serde_json::to_writer(&mut *out, &json)?;
+        writeln!(out)
+    }
+
+    /// ngspice ASCII rawfile. In complex plots every value, including the
+    /// scale, is written as `re,im`.
+    pub fn write_raw(&self, out: &mut impl Write) -> io::Result<()> {
+        let variables: Vec<RawVariable<'_>> = self
+            .scale
+            .iter()
+            .chain(&self.columns)
+            .map(|column| RawVariable {
+                name: &column.name,
+                kind: column.kind,
+                values: match &column.values {
+                    Values::Real(v) => RawValues::Real(v),
+                    Values::Complex(v) => RawValues::Complex(v),
+                },
+            })
+            .collect();
+        write_raw(
+            out,
+            &self.title,
+            self.plotname,
+            &variables,
+            RawEncoding::Ascii,
+        )
+    }
+}
+
+/// Quote a CSV field if it contains a separator or quote.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Rawfile variable type of a swept source, from its name.
+pub fn sweep_kind(source_name: &str) -> &'static str {
+    match source_name.chars().next().map(|c| c.to_ascii_uppercase()) {
+        Some('V') => "voltage",
+        Some('I') => "current",
+        _ => "notype",
+    }
+}
@@ -0,0 +1,157 @@
+//! `--format` writes machine-readable results to stdout.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// 1k/1µF low-pass driven by a 1 V step at t = 0 (RC = 1 ms).
+const RC_STEP: &str = "\
+RC step response
+V1 1 0 PULSE(0 1 0 1n 1n 1 2)
+R1 1 2 1k
+C1 2 0 1u
+.TRAN 10u 5m
+.AC DEC 5 10 10k
+.END
+";
+
+fn run(name: &str, format: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, RC_STEP).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_spicier"))
+        .arg(&path)
+        .args(["--format", format])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_transient_csv_output() {
+    let stdout = run("rc_step_csv.sp", "csv");
+    let mut blocks = stdout.split("\n\n");
+
+    // The transient comes first, as in the netlist.
+    let tran: Vec<&str> = blocks.next().unwrap().lines().collect();
+    assert_eq!(tran[0], "time,V(1),V(2)");
+    // 5 ms in 10 µs steps plus a step onto the end of the 1 ns rise, give
+    // or take a rounded-up final step.
+    assert!(
+        (1 + 502..=1 + 503).contains(&tran.len()),
+        "{} rows",
+        tran.len()
+    );
+    let time = |row: usize| tran[row].split(',').next().unwrap().parse::<f64>().unwrap();
+    assert!(
+        (time(2) - 1e-9).abs() < 1e-18,
+        "rise corner: t = {}",
+        time(2)
+    );
+
+    for &step in &[0, 50, 99, 249, 500] {
+        let row = if step == 0 { 1 } else { step + 2 };
+        let fields: Vec<f64> = tran[row].split(',').map(|f| f.parse().unwrap()).collect();
+        let (t, v1, v2) = (fields[0], fields[1], fields[2]);
+        assert!(
+            (t - step as f64 * 10e-6).abs() < 1e-12,
+            "row {row}: t = {t}"
+        );
+        if t > 0.0 {
+            assert!((v1 - 1.0).abs() < 1e-9, "row {row}: V(1) = {v1}");
+        }
+        let expected = 1.0 - (-t / 1e-3).exp();
+        assert!((v2 - expected).abs() < 5e-3, "row {row}: V(2) = {v2}");
+    }
+
+    // The AC block splits the complex node voltages into real and imaginary columns.
+    let ac: Vec<&str> = blocks.next().unwrap().lines().collect();
+    assert_eq!(ac[0], "frequency,Re(V(1)),Im(V(1)),Re(V(2)),Im(V(2))");
+    assert_eq!(ac.len(), 1 + 16);
+}
+
+#[test]
+fn test_ac_json_output() {
+    let stdout = run("rc_step_json.sp", "json");
+    let ac = stdout
+        .lines()
+        .find(|line| line.contains(r#""analysis":"AC Analysis""#))
+        .expect("AC analysis object");
+    let json: serde_json::Value = serde_json::from_str(ac).unwrap();
+
+    let freqs = json["scale"]["values"].as_array().unwrap();
+    let v2 = &json["variables"][1];
+    assert_eq!(v2["name"], "V(2)");
+    for (i, f) in freqs.iter().enumerate() {
+        let wrc = 2.0 * std::f64::consts::PI * f.as_f64().unwrap() * 1e-3;
+        // H = 1 / (1 + jωRC)
+        let re = v2["real"][i].as_f64().unwrap();
+        let im = v2["imag"][i].as_f64().unwrap();
+        assert!((re - 1.0 / (1.0 + wrc * wrc)).abs() < 1e-9);
+        assert!((im + wrc / (1.0 + wrc * wrc)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_raw_output_header() {
+    let stdout = run("rc_step_raw.sp", "raw");
+    let tran = stdout
+        .split("Title: ")
+        .find(|plot| plot.contains("Plotname: Transient Analysis"))
+        .unwrap();
+    assert!(tran.contains("Flags: real\n"));
+    assert!(tran.contains("No. Variables: 3\n"));
+    let points = tran.lines().filter(|line| line.starts_with(' ')).count();
+    assert!(tran.contains(&format!("No. Points: {points}\n")));
+    assert!(tran.contains("\t0\ttime\ttime\n\t1\tV(1)\tvoltage\n\t2\tV(2)\tvoltage\n"));
+}
+
+#[test]
+fn test_print_current_through_resistor() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("divider_current.sp");
+    std::fs::write(
+        &path,
+        "Divider current\nV1 1 0 DC 12\nR1 1 2 1k\nR2 2 0 3k\n.OP\n.PRINT DC V(2) I(R1)\n.END\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_spicier"))
+        .arg(&path)
+        .args(["--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(rows[0], "V(2),I(R1)");
+    let fields: Vec<f64> = rows[1].split(',').map(|f| f.parse().unwrap()).collect();
+    // 12 V across 4k: 3 mA through R1, leaving 9 V at node 2
+    assert!((fields[0] - 9.0).abs() < 1e-9, "V(2) = {}", fields[0]);
+    assert!((fields[1] - 3e-3).abs() < 1e-12, "I(R1) = {}", fields[1]);
+}
+
+#[test]
+fn test_dc_temp_sweep_diode_drop() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("diode_temp.sp");
+    std::fs::write(
+        &path,
+        "Diode vs temperature\nI1 0 1 1m\nD1 1 0\n.DC TEMP 0 100 50\n.PRINT DC V(1)\n.END\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_spicier"))
+        .arg(&path)
+        .args(["--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(rows[0], "TEMP,V(1)");
+    let vd: Vec<f64> = rows[1..4]
+        .iter()
+        .map(|row| row.split(',').nth(1).unwrap().parse().unwrap())
+        .collect();
+    // Roughly -2 mV/°C
+    let slope = (vd[2] - vd[0]) / 100.0;
+    assert!((-2.5e-3..-1.5e-3).contains(&slope), "slope = {slope}");
+}
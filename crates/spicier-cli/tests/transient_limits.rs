@@ -0,0 +1,90 @@
+//! The transient budget options stop a run early with partial output.
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// An RC circuit asked for far more timesteps than any test could finish.
+const LONG_RC: &str = "\
+Long RC transient
+V1 1 0 PULSE(0 1 0 1n 1n 1m 2m)
+R1 1 2 1k
+C1 2 0 1u
+.TRAN 1n 1
+.END
+";
+
+fn write_netlist(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, LONG_RC).unwrap();
+    path
+}
+
+fn run(name: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_spicier"))
+        .arg(write_netlist(name))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Number of rows in the printed waveform table.
+fn data_rows(stdout: &str) -> usize {
+    stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .take_while(|line| !line.trim().is_empty())
+        .count()
+}
+
+#[test]
+fn test_max_wall_time_returns_partial_result() {
+    let output = run("wall_time.sp", &["--max-wall-time", "0.05"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2), "stderr: {stderr}");
+    assert!(
+        stderr.contains("wall-time budget of 0.05s exceeded"),
+        "{stderr}"
+    );
+    assert!(
+        stdout.contains("Transient analysis stopped at t ="),
+        "{stdout}"
+    );
+
+    // At least the initial point and one step, nowhere near the 1e9 requested.
+    let rows = data_rows(&stdout);
+    assert!((2..1_000_000_000).contains(&rows), "{rows} rows");
+}
+
+#[test]
+fn test_max_steps_returns_exact_partial_result() {
+    let output = run("max_steps.sp", &["--max-steps", "25"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2), "stderr: {stderr}");
+    assert!(
+        stderr.contains("step budget of 25 steps reached"),
+        "{stderr}"
+    );
+    // The initial point plus 25 steps.
+    assert_eq!(data_rows(&stdout), 26);
+    assert!(stdout.contains("(step budget of 25 steps reached; 26 points)"));
+}
+
+#[test]
+fn test_completed_transient_exits_successfully() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("short.sp");
+    std::fs::write(&path, LONG_RC.replace(".TRAN 1n 1", ".TRAN 10u 1m")).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_spicier"))
+        .arg(&path)
+        .args(["--max-steps", "1000"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Transient analysis complete ("), "{stdout}");
+}
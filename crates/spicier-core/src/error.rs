@@ -2,12 +2,20 @@
 
 use thiserror::Error;
 
+use crate::node::NodeId;
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
     #[error("node not found: {0}")]
     NodeNotFound(String),
 
+    #[error("device not found: {0}")]
+    DeviceNotFound(String),
+
+    #[error("parameter not found: {0}")]
+    ParameterNotFound(String),
+
     #[error("duplicate node: {0}")]
     DuplicateNode(String),
 
@@ -16,6 +24,12 @@ pub enum Error {
 
     #[error("matrix error: {0}")]
     MatrixError(String),
+
+    #[error(
+        "node {node} has no connection to ground (node 0), so its voltage has no reference; \
+         connect it to node 0 (or GND)"
+    )]
+    NoGroundReference { node: NodeId },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
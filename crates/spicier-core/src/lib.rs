@@ -78,10 +78,19 @@ pub mod error;
 pub mod mna;
 pub mod netlist;
 pub mod node;
+pub mod parameter;
+pub mod small_signal;
+pub mod topology;
 pub mod units;
 
 pub use circuit::Circuit;
 pub use element::Element;
 pub use error::{Error, Result};
-pub use netlist::{AcDeviceInfo, Netlist, Stamper, TransientDeviceInfo};
+pub use netlist::{
+    AcDeviceInfo, DegenerateDevice, DegenerateKind, DegeneratePolicy, LimitState, Netlist,
+    NodeRemap, Stamper, TransientDeviceInfo, ValidationOptions,
+};
 pub use node::{Node, NodeId};
+pub use parameter::{ParameterHandle, ParameterTable};
+pub use small_signal::{BjtSmallSignal, DiodeSmallSignal, FetSmallSignal, SmallSignalReport};
+pub use topology::{TopologyIssue, TopologyRepair};
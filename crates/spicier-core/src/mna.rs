@@ -1,7 +1,11 @@
 //! Modified Nodal Analysis (MNA) matrix structures.
 
+use std::ops::AddAssign;
+
 use nalgebra::{DMatrix, DVector};
 
+use crate::node::NodeId;
+
 /// MNA system: Ax = b
 /// Where A is the conductance/coefficient matrix,
 /// x is the solution vector (node voltages + branch currents),
@@ -167,6 +171,260 @@ impl MnaSystem {
             self.add_element(i, i, gmin);
         }
     }
+
+    /// Report asymmetric entries of the node-conductance block.
+    ///
+    /// A circuit built only from passive elements (R, C, L companions, diodes
+    /// linearized at a point) stamps a symmetric node block, so any entry
+    /// with `A[i][j] != A[j][i]` points at a miswired device stamp. Branch
+    /// rows and columns (voltage sources, inductors, CCVS) are legitimately
+    /// asymmetric and are skipped, and so is the node block of a circuit
+    /// with controlled sources (VCCS/CCCS), which makes this a development
+    /// aid for passive networks rather than a general validity check.
+    ///
+    /// Returns `(i, j, A[i][j] - A[j][i])` for each flagged pair with
+    /// `i < j`, in row-major order. Differences within a relative `1e-12`
+    /// are treated as rounding.
+    pub fn check_passive_symmetry(&self) -> Vec<(usize, usize, f64)> {
+        let mut entries = std::collections::BTreeMap::new();
+        for &(row, col, value) in &self.triplets {
+            if row < self.num_nodes && col < self.num_nodes && row != col {
+                *entries.entry((row, col)).or_insert(0.0) += value;
+            }
+        }
+
+        let mut asymmetric = Vec::new();
+        for (&(i, j), &a_ij) in &entries {
+            let a_ji = entries.get(&(j, i)).copied().unwrap_or(0.0);
+            // Visit each pair once: from the i < j side when both are stamped
+            if i > j && entries.contains_key(&(j, i)) {
+                continue;
+            }
+            let diff = a_ij - a_ji;
+            if diff.abs() > 1e-12 * a_ij.abs().max(a_ji.abs()) {
+                let (lo, hi, d) = if i < j { (i, j, diff) } else { (j, i, -diff) };
+                asymmetric.push((lo, hi, d));
+            }
+        }
+        asymmetric.sort_by_key(|&(i, j, _)| (i, j));
+        asymmetric
+    }
+
+    /// Describe what each solution vector index holds.
+    ///
+    /// Indices `0..num_nodes` are node voltages (index `i` is node `i + 1`),
+    /// followed by `num_vsources` branch currents. Branches are unlabeled;
+    /// use [`Netlist::variable_layout`](crate::Netlist::variable_layout) to get
+    /// the owning device names.
+    pub fn variable_layout(&self) -> VariableLayout {
+        VariableLayout::new(self.num_nodes, vec![None; self.num_vsources])
+    }
+
+    /// Sum duplicate triplets in place, leaving them sorted column-major.
+    ///
+    /// After compression each (row, col) appears once, in the same order as
+    /// the entries of a CSC matrix, so sparse backends no longer need to
+    /// deduplicate. To compress repeatedly for a fixed stamping pattern
+    /// (e.g. every transient timestep), build a [`TripletPattern`] once with
+    /// [`triplet_pattern`](Self::triplet_pattern) and use
+    /// [`compress_with`](Self::compress_with).
+    pub fn compress_triplets(&mut self) {
+        let pattern = self.triplet_pattern();
+        self.triplets = pattern.compress(&self.triplets);
+    }
+
+    /// Compute the compressed sparsity pattern of the current triplets.
+    pub fn triplet_pattern(&self) -> TripletPattern {
+        TripletPattern::new(&self.triplets)
+    }
+
+    /// Compress the triplets using a precomputed pattern.
+    ///
+    /// Falls back to a full [`compress_triplets`](Self::compress_triplets) if
+    /// the triplets were not stamped in the same order as the pattern.
+    pub fn compress_with(&mut self, pattern: &TripletPattern) {
+        if pattern.matches(&self.triplets) {
+            self.triplets = pattern.compress(&self.triplets);
+        } else {
+            self.compress_triplets();
+        }
+    }
+}
+
+/// Meaning of one entry of an MNA solution vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MnaVariable {
+    /// Voltage of a node relative to ground.
+    NodeVoltage(NodeId),
+    /// Current through a branch current variable.
+    BranchCurrent {
+        /// Branch index (0-based among current variables).
+        branch: usize,
+        /// Name of the device owning the branch, if known.
+        device: Option<String>,
+    },
+}
+
+/// Layout of an MNA solution vector: which index is which node or branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableLayout {
+    variables: Vec<MnaVariable>,
+    num_nodes: usize,
+}
+
+impl VariableLayout {
+    /// Create a layout for `num_nodes` node voltages followed by one branch
+    /// current per entry of `branch_devices`.
+    pub fn new(num_nodes: usize, branch_devices: Vec<Option<String>>) -> Self {
+        let nodes = (1..=num_nodes as u32).map(|n| MnaVariable::NodeVoltage(NodeId::new(n)));
+        let branches = branch_devices
+            .into_iter()
+            .enumerate()
+            .map(|(branch, device)| MnaVariable::BranchCurrent { branch, device });
+        Self {
+            variables: nodes.chain(branches).collect(),
+            num_nodes,
+        }
+    }
+
+    /// Total number of solution variables.
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Whether the layout has no variables.
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// Number of node voltage variables.
+    pub fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    /// Number of branch current variables.
+    pub fn num_branches(&self) -> usize {
+        self.variables.len() - self.num_nodes
+    }
+
+    /// Meaning of solution index `index`.
+    pub fn get(&self, index: usize) -> Option<&MnaVariable> {
+        self.variables.get(index)
+    }
+
+    /// All variables in solution-vector order.
+    pub fn variables(&self) -> &[MnaVariable] {
+        &self.variables
+    }
+
+    /// Solution index of a node's voltage (`None` for ground or unknown nodes).
+    pub fn node_index(&self, node: NodeId) -> Option<usize> {
+        let n = node.as_u32() as usize;
+        (n >= 1 && n <= self.num_nodes).then(|| n - 1)
+    }
+
+    /// Solution index of a branch current.
+    pub fn branch_index(&self, branch: usize) -> Option<usize> {
+        (branch < self.num_branches()).then(|| self.num_nodes + branch)
+    }
+
+    /// Solution index of the branch current owned by the named device
+    /// (case-insensitive).
+    pub fn device_current_index(&self, name: &str) -> Option<usize> {
+        self.variables.iter().position(|v| {
+            matches!(v, MnaVariable::BranchCurrent { device: Some(d), .. } if d.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+/// Mapping from a raw triplet list to its sorted, deduplicated form.
+///
+/// Valid for any triplet list stamped with the same sequence of (row, col)
+/// positions, which holds across timesteps for a circuit whose topology
+/// does not change.
+#[derive(Debug, Clone)]
+pub struct TripletPattern {
+    /// Raw (row, col) positions in stamping order.
+    raw: Vec<(usize, usize)>,
+    /// Compressed slot for each raw triplet.
+    slots: Vec<usize>,
+    /// Unique (row, col) positions, sorted column-major.
+    entries: Vec<(usize, usize)>,
+}
+
+impl TripletPattern {
+    /// Build the pattern for a raw triplet list.
+    pub fn new<T>(triplets: &[(usize, usize, T)]) -> Self {
+        let raw: Vec<(usize, usize)> = triplets.iter().map(|&(r, c, _)| (r, c)).collect();
+
+        let mut order: Vec<usize> = (0..raw.len()).collect();
+        order.sort_by_key(|&k| (raw[k].1, raw[k].0));
+
+        let mut slots = vec![0; raw.len()];
+        let mut entries: Vec<(usize, usize)> = Vec::new();
+        for k in order {
+            if entries.last() != Some(&raw[k]) {
+                entries.push(raw[k]);
+            }
+            slots[k] = entries.len() - 1;
+        }
+
+        Self {
+            raw,
+            slots,
+            entries,
+        }
+    }
+
+    /// Number of unique (row, col) entries.
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Unique (row, col) positions, sorted column-major.
+    pub fn entries(&self) -> &[(usize, usize)] {
+        &self.entries
+    }
+
+    /// Check whether a triplet list has the same positions as this pattern.
+    pub fn matches<T>(&self, triplets: &[(usize, usize, T)]) -> bool {
+        triplets.len() == self.raw.len()
+            && triplets
+                .iter()
+                .zip(&self.raw)
+                .all(|(&(r, c, _), &pos)| (r, c) == pos)
+    }
+
+    /// Sum the values of a matching triplet list into compressed form.
+    ///
+    /// The caller must ensure [`matches`](Self::matches) holds.
+    pub fn compress<T>(&self, triplets: &[(usize, usize, T)]) -> Vec<(usize, usize, T)>
+    where
+        T: Copy + Default + AddAssign,
+    {
+        let mut values = vec![T::default(); self.entries.len()];
+        self.sum_into(triplets, &mut values);
+        self.entries
+            .iter()
+            .zip(values)
+            .map(|(&(r, c), v)| (r, c, v))
+            .collect()
+    }
+
+    /// Overwrite `values` with the summed values of a matching triplet list,
+    /// in [`entries`](Self::entries) order.
+    ///
+    /// This is the column-major order of a CSC matrix, so `values` can be a
+    /// sparse matrix's value array built from this pattern.
+    pub fn sum_into<T>(&self, triplets: &[(usize, usize, T)], values: &mut [T])
+    where
+        T: Copy + Default + AddAssign,
+    {
+        values.fill(T::default());
+        for (&(_, _, v), &slot) in triplets.iter().zip(&self.slots) {
+            values[slot] += v;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -233,4 +491,119 @@ mod tests {
         // Check RHS
         assert_eq!(sys.rhs[2], 5.0);
     }
+
+    #[test]
+    fn test_variable_layout() {
+        let sys = MnaSystem::new(2, 1);
+        let layout = sys.variable_layout();
+        assert_eq!(layout.len(), 3);
+        assert_eq!(
+            layout.get(0),
+            Some(&MnaVariable::NodeVoltage(NodeId::new(1)))
+        );
+        assert_eq!(
+            layout.get(2),
+            Some(&MnaVariable::BranchCurrent {
+                branch: 0,
+                device: None
+            })
+        );
+        assert_eq!(layout.node_index(NodeId::GROUND), None);
+        assert_eq!(layout.node_index(NodeId::new(2)), Some(1));
+        assert_eq!(layout.branch_index(0), Some(2));
+        assert_eq!(layout.branch_index(1), None);
+    }
+
+    #[test]
+    fn test_compress_triplets() {
+        let mut sys = MnaSystem::new(3, 1);
+        sys.stamp_conductance(Some(0), Some(1), 1.0);
+        sys.stamp_conductance(Some(1), Some(2), 0.5);
+        sys.stamp_conductance(Some(0), Some(1), 2.0);
+        sys.stamp_conductance(Some(2), None, 0.25);
+        sys.stamp_voltage_source(Some(0), None, 0, 1.0);
+        sys.stamp_gmin(1e-12);
+
+        let dense = sys.to_dense_matrix();
+        let raw_len = sys.triplets.len();
+        sys.compress_triplets();
+
+        // Duplicates summed: 3 diagonal + 4 off-diagonal + 2 vsource entries
+        assert!(sys.triplets.len() < raw_len);
+        assert_eq!(sys.triplets.len(), 9);
+        assert!(
+            sys.triplets
+                .windows(2)
+                .all(|w| (w[0].1, w[0].0) < (w[1].1, w[1].0))
+        );
+        assert_eq!(sys.to_dense_matrix(), dense);
+        assert!(sys.triplets.contains(&(0, 1, -3.0)));
+
+        // Same action on a vector
+        let x = DVector::from_vec(vec![1.0, -2.0, 3.0, 0.5]);
+        assert_eq!(sys.to_dense_matrix() * &x, dense * &x);
+    }
+
+    #[test]
+    fn test_triplet_pattern_reuse() {
+        let stamp = |g: f64| {
+            let mut sys = MnaSystem::new(2, 0);
+            sys.stamp_conductance(Some(0), Some(1), g);
+            sys.stamp_conductance(Some(0), None, g);
+            sys
+        };
+
+        let pattern = stamp(1.0).triplet_pattern();
+        assert_eq!(pattern.nnz(), 4);
+
+        let mut sys = stamp(2.0);
+        let dense = sys.to_dense_matrix();
+        assert!(pattern.matches(&sys.triplets));
+        sys.compress_with(&pattern);
+        assert_eq!(sys.triplets.len(), 4);
+        assert_eq!(sys.to_dense_matrix(), dense);
+
+        // A different stamping order falls back to a full compression
+        let mut other = MnaSystem::new(2, 0);
+        other.stamp_conductance(Some(0), None, 2.0);
+        other.stamp_conductance(Some(0), Some(1), 2.0);
+        assert!(!pattern.matches(&other.triplets));
+        other.compress_with(&pattern);
+        assert_eq!(other.to_dense_matrix(), dense);
+    }
+
+    #[test]
+    fn test_check_passive_symmetry() {
+        // Resistive ladder driven by a voltage source: symmetric node block,
+        // and the source's branch row/column are not inspected.
+        let mut sys = MnaSystem::new(3, 1);
+        sys.stamp_voltage_source(Some(0), None, 0, 1.0);
+        sys.stamp_conductance(Some(0), Some(1), 1e-3);
+        sys.stamp_conductance(Some(1), Some(2), 2e-3);
+        sys.stamp_conductance(Some(2), None, 5e-4);
+        sys.stamp_conductance(Some(0), Some(2), 1e-4);
+        assert!(sys.check_passive_symmetry().is_empty());
+
+        // A VCCS sensing its own output pair acts as a conductance and stays
+        // symmetric; wired to sense V(2) - V(3) instead, it is flagged.
+        let vccs = |sys: &mut MnaSystem, ctrl: (usize, usize), gm: f64| {
+            sys.add_element(0, ctrl.0, gm);
+            sys.add_element(0, ctrl.1, -gm);
+            sys.add_element(1, ctrl.0, -gm);
+            sys.add_element(1, ctrl.1, gm);
+        };
+        let mut good = sys.clone();
+        vccs(&mut good, (0, 1), 3e-3);
+        assert!(good.check_passive_symmetry().is_empty());
+
+        let mut miswired = sys.clone();
+        vccs(&mut miswired, (1, 2), 3e-3);
+        let flagged = miswired.check_passive_symmetry();
+        assert_eq!(
+            flagged.iter().map(|&(i, j, _)| (i, j)).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 2), (1, 2)]
+        );
+        // A[0][1] gained +gm that A[1][0] did not
+        assert!((flagged[0].2 - 3e-3).abs() < 1e-15);
+    }
 }
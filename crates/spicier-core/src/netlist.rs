@@ -2,8 +2,10 @@
 
 use nalgebra::DVector;
 
-use crate::mna::MnaSystem;
+use crate::error::{Error, Result};
+use crate::mna::{MnaSystem, VariableLayout};
 use crate::node::NodeId;
+use crate::parameter::ParameterTable;
 
 /// A boxed device that can stamp into an MNA matrix.
 pub type BoxedStamper = Box<dyn Stamper>;
@@ -92,6 +94,8 @@ pub enum AcDeviceInfo {
         gds: f64,
         /// Transconductance gm = dIds/dVgs at DC operating point.
         gm: f64,
+        /// Whether the channel is pinched off (saturation) at the operating point.
+        saturated: bool,
     },
     /// BSIM1 MOSFET (Level 4): linearized as gds + gm*Vgs + gmbs*Vbs at operating point.
     /// DC model only - no intrinsic capacitances.
@@ -104,6 +108,8 @@ pub enum AcDeviceInfo {
         gds: f64,
         /// Transconductance gm = dIds/dVgs at DC operating point.
         gm: f64,
+        /// Whether the channel is pinched off (saturation) at the operating point.
+        saturated: bool,
         /// Body transconductance gmbs = dIds/dVbs at DC operating point.
         gmbs: f64,
     },
@@ -117,6 +123,8 @@ pub enum AcDeviceInfo {
         gds: f64,
         /// Transconductance gm = dIds/dVgs at DC operating point.
         gm: f64,
+        /// Whether the channel is pinched off (saturation) at the operating point.
+        saturated: bool,
         /// Body transconductance gmbs = dIds/dVbs at DC operating point.
         gmbs: f64,
         /// Gate-source capacitance (F) - intrinsic + overlap.
@@ -147,9 +155,12 @@ pub enum AcDeviceInfo {
         emitter: Option<usize>,
         /// Transconductance gm = dIc/dVbe at DC operating point.
         gm: f64,
-        /// Input conductance gpi = gm/beta at DC operating point.
+        /// Input conductance gpi = ∂Ib/∂Vbe at DC operating point.
         gpi: f64,
-        /// Output conductance go = Ic/Vaf at DC operating point.
+        /// Base-collector conductance gmu = ∂Ib/∂Vbc at DC operating point.
+        gmu: f64,
+        /// Output conductance go = ∂Ict/∂Vce at DC operating point; about
+        /// Ic/(Vaf + Vcb) in forward active.
         go: f64,
     },
     /// Mutual inductance: coupling between two inductors.
@@ -186,6 +197,96 @@ pub enum AcDeviceInfo {
     None,
 }
 
+impl AcDeviceInfo {
+    /// Node indices of every terminal, `None` for ground.
+    ///
+    /// Returns `None` for [`AcDeviceInfo::None`], whose connections are
+    /// unknown.
+    pub(crate) fn terminals(&self) -> Option<Vec<Option<usize>>> {
+        Some(match self {
+            AcDeviceInfo::Resistor {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::Capacitor {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::Inductor {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::VoltageSource {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::CurrentSource {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::Diode {
+                node_pos, node_neg, ..
+            } => vec![*node_pos, *node_neg],
+            AcDeviceInfo::Vcvs {
+                out_pos,
+                out_neg,
+                ctrl_pos,
+                ctrl_neg,
+                ..
+            }
+            | AcDeviceInfo::Vccs {
+                out_pos,
+                out_neg,
+                ctrl_pos,
+                ctrl_neg,
+                ..
+            } => vec![*out_pos, *out_neg, *ctrl_pos, *ctrl_neg],
+            AcDeviceInfo::Cccs {
+                out_pos, out_neg, ..
+            }
+            | AcDeviceInfo::Ccvs {
+                out_pos, out_neg, ..
+            } => vec![*out_pos, *out_neg],
+            AcDeviceInfo::Mosfet {
+                drain,
+                gate,
+                source,
+                ..
+            }
+            | AcDeviceInfo::Jfet {
+                drain,
+                gate,
+                source,
+                ..
+            } => vec![*drain, *gate, *source],
+            AcDeviceInfo::Bsim1Mosfet {
+                drain,
+                gate,
+                source,
+                bulk,
+                ..
+            }
+            | AcDeviceInfo::Bsim3Mosfet {
+                drain,
+                gate,
+                source,
+                bulk,
+                ..
+            } => vec![*drain, *gate, *source, *bulk],
+            AcDeviceInfo::Bjt {
+                collector,
+                base,
+                emitter,
+                ..
+            } => vec![*collector, *base, *emitter],
+            AcDeviceInfo::MutualInductance { .. } => Vec::new(),
+            AcDeviceInfo::TransmissionLine {
+                port1_pos,
+                port1_neg,
+                port2_pos,
+                port2_neg,
+                ..
+            } => vec![*port1_pos, *port1_neg, *port2_pos, *port2_neg],
+            AcDeviceInfo::None => return None,
+        })
+    }
+}
+
 /// Information about a device for transient analysis.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -213,7 +314,7 @@ pub enum TransientDeviceInfo {
         /// Mutual inductance M = k * sqrt(L1 * L2).
         mutual_inductance: f64,
     },
-    /// Transmission line: lumped LC model.
+    /// Transmission line: Branin delayed-wave model (lumped LC for DC).
     TransmissionLine {
         /// Port 1 positive node index.
         port1_pos: Option<usize>,
@@ -289,6 +390,23 @@ pub trait Stamper: std::fmt::Debug + Send + Sync {
         self.stamp(mna);
     }
 
+    /// Stamp the device linearized at `solution`, limiting how far its
+    /// junction voltages may move in one Newton iteration.
+    ///
+    /// `state` holds the voltages this device was linearized at in the
+    /// previous iteration, empty on the first. Devices with exponential or
+    /// otherwise steep characteristics override this to apply SPICE-style
+    /// limiting (`pnjlim`, `limvds`) relative to them and store the voltages
+    /// they used. The default implementation ignores `state`.
+    fn stamp_nonlinear_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        _state: &mut Vec<f64>,
+    ) {
+        self.stamp_nonlinear(mna, solution);
+    }
+
     /// Provide transient analysis information for this device.
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
@@ -303,6 +421,14 @@ pub trait Stamper: std::fmt::Debug + Send + Sync {
         self.stamp(mna);
     }
 
+    /// Times before `tstop` at which this device's waveform has a corner,
+    /// such as PULSE edges and PWL points.
+    ///
+    /// Default implementation has none.
+    fn breakpoints(&self, _tstop: f64) -> Vec<f64> {
+        Vec::new()
+    }
+
     /// Whether this device is an independent source (V or I).
     ///
     /// Used by source stepping convergence aid to identify which devices
@@ -326,19 +452,255 @@ pub trait Stamper: std::fmt::Debug + Send + Sync {
     ) {
         self.stamp_nonlinear(mna, solution);
     }
+
+    /// Whether this device is an ideal short (a 0Ω resistor, or a DC 0V
+    /// source that is not an AC stimulus).
+    ///
+    /// Ideal shorts are removed by [`Netlist::merge_shorted_nodes`], which
+    /// merges their terminals into a single node.
+    fn is_ideal_short(&self) -> bool {
+        false
+    }
+
+    /// Return a copy of this device with node and branch references rewritten.
+    ///
+    /// Returns `None` if the device does not support remapping, in which case
+    /// [`Netlist::merge_shorted_nodes`] leaves the netlist unchanged.
+    fn remapped(&self, _remap: &NodeRemap) -> Option<BoxedStamper> {
+        None
+    }
+
+    /// Set the operating temperature (K) and rescale temperature-dependent
+    /// parameters.
+    ///
+    /// Default implementation ignores the temperature.
+    fn set_temperature(&mut self, _temp: f64) {}
+
+    /// The device's primary value, as replaced by
+    /// [`set_value`](Self::set_value).
+    ///
+    /// Returns `None` if the device has no single editable value.
+    fn value(&self) -> Option<f64> {
+        None
+    }
+
+    /// Replace the device's primary value: resistance, capacitance,
+    /// inductance, or an independent source's DC value.
+    ///
+    /// Returns `false` if the device has no single editable value.
+    fn set_value(&mut self, _value: f64) -> bool {
+        false
+    }
+}
+
+/// Node and branch renumbering produced by [`Netlist::merge_shorted_nodes`].
+#[derive(Debug, Clone)]
+pub struct NodeRemap {
+    /// New node ID for each old node ID (indexed by `NodeId::as_u32()`).
+    nodes: Vec<NodeId>,
+    /// New branch index for each old branch index; `None` if removed.
+    branches: Vec<Option<usize>>,
+    /// Number of nodes after merging (excluding ground).
+    num_nodes: usize,
+    /// Number of current variables after merging.
+    num_current_vars: usize,
+}
+
+impl NodeRemap {
+    /// Map an old node ID to its merged node ID.
+    ///
+    /// Node IDs outside the original netlist are returned unchanged.
+    pub fn node(&self, old: NodeId) -> NodeId {
+        self.nodes
+            .get(old.as_u32() as usize)
+            .copied()
+            .unwrap_or(old)
+    }
+
+    /// Map an old branch current index to its new index.
+    ///
+    /// Returns `None` if the branch belonged to a removed short.
+    pub fn branch(&self, old: usize) -> Option<usize> {
+        self.branches.get(old).copied().flatten()
+    }
+
+    /// Map an old 0-based MNA node index (ground = `None`) to its new index.
+    pub fn node_index(&self, old: Option<usize>) -> Option<usize> {
+        let id = self.node(NodeId::new(old.map_or(0, |i| i as u32 + 1)));
+        (!id.is_ground()).then(|| (id.as_u32() - 1) as usize)
+    }
+
+    /// Number of nodes after merging (excluding ground).
+    pub fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    /// Number of current variables after merging.
+    pub fn num_current_vars(&self) -> usize {
+        self.num_current_vars
+    }
+}
+
+/// What to do with a degenerate device found by [`Netlist::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DegeneratePolicy {
+    /// Keep the device and report it.
+    #[default]
+    Warn,
+    /// Remove the device if doing so leaves the circuit unchanged; report the rest.
+    Drop,
+    /// Fail validation with [`Error::InvalidCircuit`].
+    Error,
+}
+
+/// Options for [`Netlist::validate`].
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Policy applied to every degenerate device.
+    pub policy: DegeneratePolicy,
+}
+
+/// Kind of degeneracy detected in a two-terminal device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DegenerateKind {
+    /// Both terminals are on the same node (e.g. `R1 a a 1k`).
+    SameNodeTerminals,
+    /// A resistor with zero resistance (infinite conductance).
+    ZeroResistance,
+    /// A capacitor with zero capacitance.
+    ZeroCapacitance,
+}
+
+impl std::fmt::Display for DegenerateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DegenerateKind::SameNodeTerminals => write!(f, "both terminals on the same node"),
+            DegenerateKind::ZeroResistance => write!(f, "zero resistance"),
+            DegenerateKind::ZeroCapacitance => write!(f, "zero capacitance"),
+        }
+    }
+}
+
+/// A degenerate device reported by [`Netlist::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegenerateDevice {
+    /// Device name (e.g., "R1").
+    pub name: String,
+    /// What is wrong with the device.
+    pub kind: DegenerateKind,
+    /// Whether the device was removed from the netlist.
+    pub dropped: bool,
+}
+
+/// Voltages each device was linearized at in the previous Newton iteration.
+///
+/// Junction limiting bounds the step from the voltage a device actually
+/// linearized at, not from the raw previous iterate, which may be far out
+/// (that is what limiting is for). A Newton solve owns one `LimitState` and
+/// passes it to every [`Netlist::stamp_nonlinear_into_limited`] call.
+#[derive(Debug, Clone, Default)]
+pub struct LimitState {
+    devices: Vec<Vec<f64>>,
+}
+
+impl LimitState {
+    /// Create an empty state, as at the start of a Newton solve.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Voltages stored by device `index`; empty until its first limited stamp.
+    pub fn device(&mut self, index: usize) -> &mut Vec<f64> {
+        if index >= self.devices.len() {
+            self.devices.resize_with(index + 1, Vec::new);
+        }
+        &mut self.devices[index]
+    }
+
+    /// Forget all stored voltages.
+    pub fn clear(&mut self) {
+        self.devices.clear();
+    }
+}
+
+/// 0V sense source spliced in series with a device by
+/// [`Netlist::add_current_probe`].
+#[derive(Debug, Clone)]
+struct CurrentProbe {
+    /// Name of the probed device.
+    name: String,
+    node_pos: NodeId,
+    node_neg: NodeId,
+    branch: usize,
+}
+
+impl CurrentProbe {
+    fn index(node: NodeId) -> Option<usize> {
+        (!node.is_ground()).then(|| (node.as_u32() - 1) as usize)
+    }
+}
+
+impl Stamper for CurrentProbe {
+    fn stamp(&self, mna: &mut MnaSystem) {
+        mna.stamp_voltage_source(
+            Self::index(self.node_pos),
+            Self::index(self.node_neg),
+            self.branch,
+            0.0,
+        );
+    }
+
+    fn num_current_vars(&self) -> usize {
+        1
+    }
+
+    fn device_name(&self) -> &str {
+        &self.name
+    }
+
+    fn branch_index(&self) -> Option<usize> {
+        Some(self.branch)
+    }
+
+    fn ac_info(&self) -> AcDeviceInfo {
+        AcDeviceInfo::VoltageSource {
+            node_pos: Self::index(self.node_pos),
+            node_neg: Self::index(self.node_neg),
+            branch_idx: self.branch,
+            ac_mag: 0.0,
+        }
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        Some(Box::new(CurrentProbe {
+            name: self.name.clone(),
+            node_pos: remap.node(self.node_pos),
+            node_neg: remap.node(self.node_neg),
+            branch: remap.branch(self.branch)?,
+        }))
+    }
 }
 
 /// A complete netlist ready for simulation.
+///
+/// Devices are stored in insertion order, which for a parsed circuit is
+/// declaration order. Every pass over the devices (stamping, AC and
+/// transient setup, reports) sees that order, so results are reproducible
+/// run to run. Node IDs and branch indices are likewise assigned in order
+/// of first appearance, which fixes the layout of the solution vector.
 #[derive(Debug, Default)]
 pub struct Netlist {
     /// Circuit title.
     title: Option<String>,
     /// Highest node number used.
     max_node: u32,
-    /// All devices in the netlist.
+    /// All devices in the netlist, in insertion order.
     devices: Vec<BoxedStamper>,
     /// Total number of current variables (voltage sources + inductors).
     num_current_vars: usize,
+    /// `.PARAM` and model card values the netlist was built from.
+    parameters: ParameterTable,
 }
 
 impl Netlist {
@@ -408,27 +770,455 @@ impl Netlist {
 
     /// Find the branch current variable index for a named voltage source.
     ///
-    /// Returns `None` if no device with that name has a branch variable.
+    /// Also finds the sense source of a device probed with
+    /// [`add_current_probe`](Self::add_current_probe). Returns `None` if no
+    /// device with that name has a branch variable.
     pub fn find_vsource_branch_index(&self, name: &str) -> Option<usize> {
         let name_upper = name.to_uppercase();
+        self.devices
+            .iter()
+            .filter(|device| device.device_name().to_uppercase() == name_upper)
+            .find_map(|device| device.branch_index())
+    }
+
+    /// Make the current through a two-terminal device observable as a
+    /// branch current, and return its index.
+    ///
+    /// Splices a 0V sense source in series with the device: one terminal is
+    /// moved to a new internal node, and the source joins that node back to
+    /// the original one. The source carries the device's name, so
+    /// [`find_vsource_branch_index`](Self::find_vsource_branch_index) and
+    /// [`variable_layout`](Self::variable_layout) report it as `I(device)`,
+    /// positive for current flowing into the device's positive terminal.
+    ///
+    /// Devices that already have a branch current (voltage sources,
+    /// inductors) are left alone and their existing index is returned.
+    pub fn add_current_probe(&mut self, device_name: &str) -> Result<usize> {
+        let name_upper = device_name.to_uppercase();
+        let position = self
+            .devices
+            .iter()
+            .position(|d| d.device_name().to_uppercase() == name_upper)
+            .ok_or_else(|| Error::DeviceNotFound(device_name.to_string()))?;
+        let device = &self.devices[position];
+        if let Some(branch) = device.branch_index() {
+            return Ok(branch);
+        }
+
+        let (node_pos, node_neg) = match device.ac_info() {
+            AcDeviceInfo::Resistor {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::Capacitor {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::CurrentSource {
+                node_pos, node_neg, ..
+            }
+            | AcDeviceInfo::Diode {
+                node_pos, node_neg, ..
+            } => (node_pos, node_neg),
+            _ => {
+                return Err(Error::InvalidCircuit(format!(
+                    "cannot probe the current of {}: not a two-terminal device",
+                    device.device_name()
+                )));
+            }
+        };
+        if node_pos == node_neg {
+            return Err(Error::InvalidCircuit(format!(
+                "cannot probe the current of {}: both terminals on the same node",
+                device.device_name()
+            )));
+        }
+
+        // Detach a non-ground terminal onto a new node
+        let to_id = |index: Option<usize>| NodeId::new(index.map_or(0, |i| i as u32 + 1));
+        let internal = NodeId::new(self.max_node + 1);
+        let detached = if node_pos.is_some() {
+            node_pos
+        } else {
+            node_neg
+        };
+        let mut nodes: Vec<NodeId> = (0..=self.max_node).map(NodeId::new).collect();
+        nodes[to_id(detached).as_u32() as usize] = internal;
+        let remap = NodeRemap {
+            nodes,
+            branches: (0..self.num_current_vars).map(Some).collect(),
+            num_nodes: internal.as_u32() as usize,
+            num_current_vars: self.num_current_vars + 1,
+        };
+        let rewired = device.remapped(&remap).ok_or_else(|| {
+            Error::InvalidCircuit(format!(
+                "cannot probe the current of {}: device cannot be rewired",
+                device.device_name()
+            ))
+        })?;
+
+        // The sense source runs along the device's own pos -> neg direction
+        let (probe_pos, probe_neg) = if node_pos.is_some() {
+            (to_id(node_pos), internal)
+        } else {
+            (internal, to_id(node_neg))
+        };
+        let branch = self.num_current_vars;
+        let probe = CurrentProbe {
+            name: rewired.device_name().to_string(),
+            node_pos: probe_pos,
+            node_neg: probe_neg,
+            branch,
+        };
+
+        self.devices[position] = rewired;
+        self.max_node = internal.as_u32();
+        self.add_device(probe);
+        Ok(branch)
+    }
+
+    /// Describe what each solution vector index holds.
+    ///
+    /// Like [`MnaSystem::variable_layout`], but branch currents are labeled
+    /// with the name of the device that owns them.
+    pub fn variable_layout(&self) -> VariableLayout {
+        let mut branch_devices = vec![None; self.num_current_vars];
         for device in &self.devices {
-            if device.device_name().to_uppercase() == name_upper {
-                return device.branch_index();
+            if let Some(branch) = device.branch_index() {
+                let end = (branch + device.num_current_vars()).min(self.num_current_vars);
+                for slot in branch_devices.iter_mut().take(end).skip(branch) {
+                    *slot = Some(device.device_name().to_string());
+                }
             }
         }
-        None
+        VariableLayout::new(self.num_nodes(), branch_devices)
     }
 
-    /// Get an iterator over devices.
+    /// The devices, in insertion order.
+    ///
+    /// The order is stable: removing devices (validation, short merging)
+    /// keeps the survivors' relative order, in-place repairs keep a device's
+    /// position, and added devices go at the end.
     pub fn devices(&self) -> &[BoxedStamper] {
         &self.devices
     }
 
+    /// Set the operating temperature (K) of every device.
+    ///
+    /// Call before running an analysis; devices without a temperature model
+    /// are unaffected.
+    pub fn set_temperature_all(&mut self, temp: f64) {
+        for device in &mut self.devices {
+            device.set_temperature(temp);
+        }
+    }
+
+    /// Waveform corner times of every source before `tstop`, sorted and
+    /// deduplicated, for the transient solver's breakpoints.
+    pub fn source_breakpoints(&self, tstop: f64) -> Vec<f64> {
+        let mut times: Vec<f64> = self
+            .devices
+            .iter()
+            .flat_map(|device| device.breakpoints(tstop))
+            .collect();
+        times.sort_by(f64::total_cmp);
+        times.dedup();
+        times
+    }
+
+    /// Remove and return every device, keeping the node and current-variable
+    /// counts.
+    ///
+    /// Devices added afterwards still get fresh branch indices, so a producer
+    /// can hand devices off in batches without holding the whole circuit.
+    pub fn take_devices(&mut self) -> Vec<BoxedStamper> {
+        std::mem::take(&mut self.devices)
+    }
+
+    /// Change a named device's value and return the resulting change to the
+    /// DC MNA system.
+    ///
+    /// The returned system holds the device's new stamp minus its old one,
+    /// so adding its triplets and RHS to the previously assembled system
+    /// gives the same matrix as re-running [`assemble_mna`](Self::assemble_mna).
+    /// Only the edited device is stamped, which lets an incremental solver
+    /// update its factorization instead of rebuilding the whole system.
+    pub fn set_device_value(&mut self, name: &str, value: f64) -> Result<MnaSystem> {
+        let (num_nodes, num_current_vars) = (self.num_nodes(), self.num_current_vars);
+        let name_upper = name.to_uppercase();
+        let device = self
+            .devices
+            .iter_mut()
+            .find(|d| d.device_name().to_uppercase() == name_upper)
+            .ok_or_else(|| Error::DeviceNotFound(name.to_string()))?;
+
+        let mut old = MnaSystem::new(num_nodes, num_current_vars);
+        device.stamp(&mut old);
+        if !device.set_value(value) {
+            return Err(Error::InvalidCircuit(format!(
+                "device {name} has no editable value"
+            )));
+        }
+
+        let mut delta = MnaSystem::new(num_nodes, num_current_vars);
+        device.stamp(&mut delta);
+        delta
+            .triplets
+            .extend(old.triplets.iter().map(|&(r, c, v)| (r, c, -v)));
+        delta.rhs -= &old.rhs;
+        Ok(delta)
+    }
+
+    /// The `.PARAM` and model card values this netlist was built from.
+    ///
+    /// Empty for a netlist built by hand. See
+    /// [`ParameterHandle`](crate::ParameterHandle) for changing them.
+    pub fn parameters(&self) -> &ParameterTable {
+        &self.parameters
+    }
+
+    /// Mutable access to the parameter table, for parsers to fill in.
+    pub fn parameters_mut(&mut self) -> &mut ParameterTable {
+        &mut self.parameters
+    }
+
+    /// Mutable access to the device list, for in-place rewrites.
+    pub(crate) fn devices_mut(&mut self) -> &mut Vec<BoxedStamper> {
+        &mut self.devices
+    }
+
     /// Get the number of devices.
     pub fn num_devices(&self) -> usize {
         self.devices.len()
     }
 
+    /// Find two-terminal devices that would stamp a degenerate matrix entry.
+    ///
+    /// Flags devices whose terminals are on the same node, zero-ohm
+    /// resistors, and zero-valued capacitors. Returns `(device index, kind)`.
+    pub fn find_degenerate_devices(&self) -> Vec<(usize, DegenerateKind)> {
+        let mut found = Vec::new();
+        for (idx, device) in self.devices.iter().enumerate() {
+            let (node_pos, node_neg, kind) = match device.ac_info() {
+                AcDeviceInfo::Resistor {
+                    node_pos,
+                    node_neg,
+                    conductance,
+                } => (
+                    node_pos,
+                    node_neg,
+                    (!conductance.is_finite()).then_some(DegenerateKind::ZeroResistance),
+                ),
+                AcDeviceInfo::Capacitor {
+                    node_pos,
+                    node_neg,
+                    capacitance,
+                } => (
+                    node_pos,
+                    node_neg,
+                    (capacitance == 0.0).then_some(DegenerateKind::ZeroCapacitance),
+                ),
+                AcDeviceInfo::Inductor {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::VoltageSource {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::CurrentSource {
+                    node_pos, node_neg, ..
+                } => (node_pos, node_neg, None),
+                _ => continue,
+            };
+
+            if node_pos == node_neg {
+                found.push((idx, DegenerateKind::SameNodeTerminals));
+            } else if let Some(kind) = kind {
+                found.push((idx, kind));
+            }
+        }
+        found
+    }
+
+    /// Validate the netlist for degenerate two-terminal devices.
+    ///
+    /// With [`DegeneratePolicy::Drop`], same-node devices without a branch
+    /// current and zero-valued capacitors are removed, since neither
+    /// contributes to the circuit. Zero-ohm resistors and same-node voltage
+    /// sources or inductors are kept and reported.
+    ///
+    /// Returns every degenerate device found, or an error on the first one
+    /// under [`DegeneratePolicy::Error`].
+    pub fn validate(&mut self, options: &ValidationOptions) -> Result<Vec<DegenerateDevice>> {
+        let found = self.find_degenerate_devices();
+
+        if options.policy == DegeneratePolicy::Error {
+            if let Some(&(idx, kind)) = found.first() {
+                return Err(Error::InvalidCircuit(format!(
+                    "device {} has {}",
+                    self.devices[idx].device_name(),
+                    kind
+                )));
+            }
+        }
+
+        let mut report = Vec::with_capacity(found.len());
+        let mut drop = vec![false; self.devices.len()];
+        for &(idx, kind) in &found {
+            let device = &self.devices[idx];
+            let droppable = options.policy == DegeneratePolicy::Drop
+                && device.num_current_vars() == 0
+                && kind != DegenerateKind::ZeroResistance;
+            drop[idx] = droppable;
+            report.push(DegenerateDevice {
+                name: device.device_name().to_string(),
+                kind,
+                dropped: droppable,
+            });
+        }
+
+        if drop.iter().any(|&d| d) {
+            let mut idx = 0;
+            self.devices.retain(|_| {
+                let keep = !drop[idx];
+                idx += 1;
+                keep
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Merge nodes joined by ideal shorts and remove the shorting devices.
+    ///
+    /// Zero-ohm resistors and DC 0V voltage sources (see
+    /// [`Stamper::is_ideal_short`]) would otherwise stamp an infinite
+    /// conductance or an extra branch equation that only ties two nodes
+    /// together. This pass collapses each group of shorted nodes into one
+    /// (ground if the group touches ground), renumbers the remaining nodes and
+    /// branch currents contiguously, and rewrites every device accordingly.
+    ///
+    /// Voltage sources whose current controls a CCCS/CCVS are kept, since
+    /// their branch current is observed. Use
+    /// [`merge_shorted_nodes_keeping`](Self::merge_shorted_nodes_keeping) to
+    /// also keep 0V ammeters that are printed or measured.
+    ///
+    /// Returns the applied renumbering, or `None` if there was nothing to
+    /// merge or some device does not support [`Stamper::remapped`] (the
+    /// netlist is then left unchanged).
+    pub fn merge_shorted_nodes(&mut self) -> Option<NodeRemap> {
+        self.merge_shorted_nodes_keeping(&[])
+    }
+
+    /// [`merge_shorted_nodes`](Self::merge_shorted_nodes), keeping the
+    /// voltage sources named in `observed` (case-insensitive), e.g. the sources of
+    /// `.PRINT I(Vx)`, so their branch currents remain available.
+    pub fn merge_shorted_nodes_keeping(&mut self, observed: &[&str]) -> Option<NodeRemap> {
+        let mut sensed_branches = Vec::new();
+        for device in &self.devices {
+            if observed
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(device.device_name()))
+            {
+                sensed_branches.extend(device.branch_index());
+            }
+            if let AcDeviceInfo::Cccs {
+                vsource_branch_idx, ..
+            }
+            | AcDeviceInfo::Ccvs {
+                vsource_branch_idx, ..
+            } = device.ac_info()
+            {
+                sensed_branches.push(vsource_branch_idx);
+            }
+        }
+
+        // Union-find over node IDs (0 = ground)
+        let num_ids = self.max_node as usize + 1;
+        let mut parent: Vec<usize> = (0..num_ids).collect();
+        fn find(parent: &mut [usize], mut x: usize) -> usize {
+            while parent[x] != x {
+                parent[x] = parent[parent[x]];
+                x = parent[x];
+            }
+            x
+        }
+
+        let mut is_short = vec![false; self.devices.len()];
+        let mut removed_branches = Vec::new();
+        for (idx, device) in self.devices.iter().enumerate() {
+            if !device.is_ideal_short() {
+                continue;
+            }
+            let (node_pos, node_neg) = match device.ac_info() {
+                AcDeviceInfo::Resistor {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::VoltageSource {
+                    node_pos, node_neg, ..
+                } => (node_pos, node_neg),
+                _ => continue,
+            };
+            if let Some(branch) = device.branch_index() {
+                if sensed_branches.contains(&branch) {
+                    continue;
+                }
+                removed_branches.push(branch);
+            }
+            is_short[idx] = true;
+
+            let a = find(&mut parent, node_pos.map_or(0, |i| i + 1));
+            let b = find(&mut parent, node_neg.map_or(0, |i| i + 1));
+            // Keep the lower ID as representative so ground always wins
+            let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+            parent[hi] = lo;
+        }
+
+        if !is_short.iter().any(|&s| s) {
+            return None;
+        }
+
+        // Roots get contiguous IDs in order; every member then takes its root's ID
+        let roots: Vec<usize> = (0..num_ids).map(|id| find(&mut parent, id)).collect();
+        let mut nodes = vec![NodeId::GROUND; num_ids];
+        let mut next_id = 0u32;
+        for (id, &root) in roots.iter().enumerate().skip(1) {
+            if root == id {
+                next_id += 1;
+                nodes[id] = NodeId::new(next_id);
+            }
+        }
+        for (id, &root) in roots.iter().enumerate() {
+            nodes[id] = nodes[root];
+        }
+
+        let mut branches = vec![None; self.num_current_vars];
+        let mut next_branch = 0;
+        for (old, slot) in branches.iter_mut().enumerate() {
+            if !removed_branches.contains(&old) {
+                *slot = Some(next_branch);
+                next_branch += 1;
+            }
+        }
+
+        let remap = NodeRemap {
+            nodes,
+            branches,
+            num_nodes: next_id as usize,
+            num_current_vars: next_branch,
+        };
+
+        let mut devices = Vec::with_capacity(self.devices.len());
+        for (idx, device) in self.devices.iter().enumerate() {
+            if is_short[idx] {
+                continue;
+            }
+            devices.push(device.remapped(&remap)?);
+        }
+
+        self.devices = devices;
+        self.max_node = next_id;
+        self.num_current_vars = next_branch;
+        Some(remap)
+    }
+
     /// Check if the netlist contains any nonlinear devices.
     pub fn has_nonlinear_devices(&self) -> bool {
         self.devices.iter().any(|d| d.is_nonlinear())
@@ -444,6 +1234,22 @@ impl Netlist {
         }
     }
 
+    /// Stamp all devices linearized at `solution`, with junction voltage
+    /// limiting relative to the previous Newton iteration.
+    ///
+    /// See [`Stamper::stamp_nonlinear_limited`]. Pass the same `state` for
+    /// every iteration of one Newton solve, starting from an empty one.
+    pub fn stamp_nonlinear_into_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        state: &mut LimitState,
+    ) {
+        for (index, device) in self.devices.iter().enumerate() {
+            device.stamp_nonlinear_limited(mna, solution, state.device(index));
+        }
+    }
+
     /// Stamp all devices with source scaling for source stepping.
     ///
     /// Independent sources (V, I) have their values multiplied by `source_factor`.
@@ -458,6 +1264,116 @@ impl Netlist {
             device.stamp_nonlinear_scaled(mna, solution, source_factor);
         }
     }
+
+    /// Estimate the smallest RC/LR time constant in the circuit.
+    ///
+    /// Nonlinear devices are linearized with their default stamp. Use
+    /// [`estimate_min_time_constant_at`](Self::estimate_min_time_constant_at)
+    /// to linearize at a known operating point instead.
+    pub fn estimate_min_time_constant(&self) -> Option<f64> {
+        self.estimate_time_constant_from(&self.assemble_mna())
+    }
+
+    /// Estimate the smallest RC/LR time constant linearized at `solution`.
+    ///
+    /// This is a cheap per-node bound on the largest eigenvalue of C⁻¹G rather
+    /// than a full eigen-decomposition: each capacitive node contributes
+    /// `C_ii / G_ii` and each inductor contributes `L / (R_pos + R_neg)`, where
+    /// the resistance seen at a node is `1 / G_ii` (zero for ground and nodes
+    /// pinned by a grounded voltage source). For a single RC or RL section the
+    /// estimate is exact; for coupled networks it is within a small factor of
+    /// the true fastest mode.
+    ///
+    /// Returns `None` if the circuit has no capacitor or inductor with a
+    /// resistive path, i.e. no finite time constant to resolve.
+    pub fn estimate_min_time_constant_at(&self, solution: &DVector<f64>) -> Option<f64> {
+        let mut mna = MnaSystem::new(self.num_nodes(), self.num_current_vars);
+        self.stamp_nonlinear_into(&mut mna, solution);
+        self.estimate_time_constant_from(&mna)
+    }
+
+    fn estimate_time_constant_from(&self, mna: &MnaSystem) -> Option<f64> {
+        let num_nodes = self.num_nodes();
+
+        // Inductor branches look like grounded voltage sources in the DC stamp,
+        // so they must not mark their terminals as pinned.
+        let mut inductor_branches = Vec::new();
+        let mut node_cap = vec![0.0; num_nodes];
+        for device in &self.devices {
+            match device.transient_info() {
+                TransientDeviceInfo::Capacitor {
+                    node_pos,
+                    node_neg,
+                    capacitance,
+                } => {
+                    for idx in [node_pos, node_neg].into_iter().flatten() {
+                        node_cap[idx] += capacitance.abs();
+                    }
+                }
+                TransientDeviceInfo::Inductor { branch_index, .. } => {
+                    inductor_branches.push(branch_index);
+                }
+                _ => {}
+            }
+        }
+
+        // Diagonal of the node block of G, plus the node columns touched by
+        // each branch row.
+        let mut g_diag = vec![0.0; num_nodes];
+        let mut branch_nodes: Vec<Vec<usize>> = vec![Vec::new(); mna.num_vsources];
+        for &(row, col, value) in &mna.triplets {
+            if row < num_nodes && col == row {
+                g_diag[row] += value;
+            } else if row >= num_nodes && col < num_nodes && value != 0.0 {
+                let nodes = &mut branch_nodes[row - num_nodes];
+                if !nodes.contains(&col) {
+                    nodes.push(col);
+                }
+            }
+        }
+
+        let mut pinned = vec![false; num_nodes];
+        for (branch, nodes) in branch_nodes.iter().enumerate() {
+            if nodes.len() == 1 && !inductor_branches.contains(&branch) {
+                pinned[nodes[0]] = true;
+            }
+        }
+
+        let resistance_at = |node: Option<usize>| -> f64 {
+            match node {
+                None => 0.0,
+                Some(i) if pinned[i] => 0.0,
+                Some(i) if g_diag[i] > 0.0 => 1.0 / g_diag[i],
+                Some(_) => f64::INFINITY,
+            }
+        };
+
+        let mut tau_min = f64::INFINITY;
+
+        for i in 0..num_nodes {
+            if pinned[i] || node_cap[i] <= 0.0 || g_diag[i] <= 0.0 {
+                continue;
+            }
+            tau_min = tau_min.min(node_cap[i] / g_diag[i]);
+        }
+
+        for device in &self.devices {
+            if let TransientDeviceInfo::Inductor {
+                node_pos,
+                node_neg,
+                inductance,
+                ..
+            } = device.transient_info()
+            {
+                let r = resistance_at(node_pos) + resistance_at(node_neg);
+                if r > 0.0 && r.is_finite() {
+                    tau_min = tau_min.min(inductance.abs() / r);
+                }
+            }
+        }
+
+        tau_min.is_finite().then_some(tau_min)
+    }
 }
 
 #[cfg(test)]
@@ -486,6 +1402,115 @@ mod tests {
             };
             mna.stamp_conductance(i, j, self.conductance);
         }
+
+        fn ac_info(&self) -> AcDeviceInfo {
+            let index = |n: NodeId| (!n.is_ground()).then(|| (n.as_u32() - 1) as usize);
+            AcDeviceInfo::Resistor {
+                node_pos: index(self.node_pos),
+                node_neg: index(self.node_neg),
+                conductance: self.conductance,
+            }
+        }
+
+        fn is_ideal_short(&self) -> bool {
+            self.conductance.is_infinite()
+        }
+
+        fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+            Some(Box::new(TestResistor {
+                node_pos: remap.node(self.node_pos),
+                node_neg: remap.node(self.node_neg),
+                conductance: self.conductance,
+            }))
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestCapacitor {
+        node_pos: Option<usize>,
+        capacitance: f64,
+    }
+
+    impl Stamper for TestCapacitor {
+        fn stamp(&self, _mna: &mut MnaSystem) {}
+
+        fn transient_info(&self) -> TransientDeviceInfo {
+            TransientDeviceInfo::Capacitor {
+                node_pos: self.node_pos,
+                node_neg: None,
+                capacitance: self.capacitance,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestVoltageSource {
+        node_pos: Option<usize>,
+        branch: usize,
+        voltage: f64,
+    }
+
+    impl Stamper for TestVoltageSource {
+        fn stamp(&self, mna: &mut MnaSystem) {
+            mna.stamp_voltage_source(self.node_pos, None, self.branch, self.voltage);
+        }
+
+        fn num_current_vars(&self) -> usize {
+            1
+        }
+
+        fn branch_index(&self) -> Option<usize> {
+            Some(self.branch)
+        }
+
+        fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+            Some(Box::new(TestVoltageSource {
+                node_pos: remap.node_index(self.node_pos),
+                branch: remap.branch(self.branch)?,
+                voltage: self.voltage,
+            }))
+        }
+    }
+
+    #[test]
+    fn test_estimate_min_time_constant_rc() {
+        // V1 -- R (1k) -- node 2 -- C (1µ) -- GND: tau = RC = 1ms
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(2));
+        netlist.add_device(TestVoltageSource {
+            node_pos: Some(0),
+            branch: 0,
+            voltage: 1.0,
+        });
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::new(2),
+            conductance: 1e-3,
+        });
+        netlist.add_device(TestCapacitor {
+            node_pos: Some(1),
+            capacitance: 1e-6,
+        });
+
+        let rc = 1e-3;
+        let tau = netlist.estimate_min_time_constant().unwrap();
+        assert!(tau > rc / 2.0 && tau < rc * 2.0, "tau = {tau}");
+
+        let solution = DVector::from_vec(vec![1.0, 1.0, 0.0]);
+        let tau_at = netlist.estimate_min_time_constant_at(&solution).unwrap();
+        assert!((tau_at - tau).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_estimate_min_time_constant_no_reactive() {
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(1));
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::GROUND,
+            conductance: 1e-3,
+        });
+        assert!(netlist.estimate_min_time_constant().is_none());
     }
 
     #[test]
@@ -522,4 +1547,194 @@ mod tests {
         let matrix = mna.to_dense_matrix();
         assert!((matrix[(0, 0)] - 0.001).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_check_ground_reference() {
+        // R1 (1-2) and R2 (2-3) with no connection to node 0
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(3));
+        for (a, b) in [(1, 2), (2, 3)] {
+            netlist.add_device(TestResistor {
+                node_pos: NodeId::new(a),
+                node_neg: NodeId::new(b),
+                conductance: 1e-3,
+            });
+        }
+        assert!(matches!(
+            netlist.check_ground_reference(),
+            Err(Error::NoGroundReference { node }) if node == NodeId::new(1)
+        ));
+
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(3),
+            node_neg: NodeId::GROUND,
+            conductance: 1e-3,
+        });
+        assert!(netlist.check_ground_reference().is_ok());
+        assert!(Netlist::new().check_ground_reference().is_ok());
+
+        // A grounded element elsewhere does not reference a separate island
+        netlist.register_node(NodeId::new(5));
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(4),
+            node_neg: NodeId::new(5),
+            conductance: 1e-3,
+        });
+        assert!(matches!(
+            netlist.check_ground_reference(),
+            Err(Error::NoGroundReference { node }) if node == NodeId::new(4)
+        ));
+    }
+
+    #[test]
+    fn test_validate_same_node_resistor() {
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(1));
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::new(1),
+            conductance: 1e-3,
+        });
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::GROUND,
+            conductance: 1e-3,
+        });
+
+        let report = netlist.validate(&ValidationOptions::default()).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].kind, DegenerateKind::SameNodeTerminals);
+        assert!(!report[0].dropped);
+        assert_eq!(netlist.num_devices(), 2);
+
+        let options = ValidationOptions {
+            policy: DegeneratePolicy::Drop,
+        };
+        let report = netlist.validate(&options).unwrap();
+        assert!(report[0].dropped);
+        assert_eq!(netlist.num_devices(), 1);
+        assert!(netlist.find_degenerate_devices().is_empty());
+    }
+
+    #[test]
+    fn test_validate_zero_ohm_resistor() {
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(2));
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::new(2),
+            conductance: 1.0 / 0.0,
+        });
+
+        // A zero-ohm resistor is a short, so Drop must keep it
+        let options = ValidationOptions {
+            policy: DegeneratePolicy::Drop,
+        };
+        let report = netlist.validate(&options).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].kind, DegenerateKind::ZeroResistance);
+        assert!(!report[0].dropped);
+        assert_eq!(netlist.num_devices(), 1);
+
+        let options = ValidationOptions {
+            policy: DegeneratePolicy::Error,
+        };
+        assert!(matches!(
+            netlist.validate(&options),
+            Err(Error::InvalidCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_zero_capacitance() {
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(1));
+        netlist.add_device(ZeroCap);
+
+        let options = ValidationOptions {
+            policy: DegeneratePolicy::Drop,
+        };
+        let report = netlist.validate(&options).unwrap();
+        assert_eq!(report[0].kind, DegenerateKind::ZeroCapacitance);
+        assert!(report[0].dropped);
+        assert_eq!(netlist.num_devices(), 0);
+    }
+
+    #[test]
+    fn test_merge_shorted_nodes() {
+        // V1 -- R1 (1k) -- node 2 == 0Ω == node 3 -- R2 (1k) -- GND
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(3));
+        netlist.add_device(TestVoltageSource {
+            node_pos: Some(0),
+            branch: 0,
+            voltage: 10.0,
+        });
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::new(2),
+            conductance: 1e-3,
+        });
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(2),
+            node_neg: NodeId::new(3),
+            conductance: f64::INFINITY,
+        });
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(3),
+            node_neg: NodeId::GROUND,
+            conductance: 1e-3,
+        });
+
+        let remap = netlist.merge_shorted_nodes().expect("short should merge");
+        assert_eq!(netlist.num_nodes(), 2);
+        assert_eq!(netlist.num_devices(), 3);
+        assert_eq!(remap.node(NodeId::new(2)), remap.node(NodeId::new(3)));
+        assert_eq!(remap.node(NodeId::new(3)), NodeId::new(2));
+        assert_eq!(remap.node(NodeId::GROUND), NodeId::GROUND);
+
+        let matrix = netlist.assemble_mna().to_dense_matrix();
+        assert_eq!(matrix.nrows(), 3);
+        assert!(matrix.iter().all(|v| v.is_finite()));
+        assert!((matrix[(1, 1)] - 2e-3).abs() < 1e-15);
+
+        // Nothing left to merge
+        assert!(netlist.merge_shorted_nodes().is_none());
+    }
+
+    #[test]
+    fn test_merge_short_to_ground() {
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(2));
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::new(2),
+            conductance: 1e-3,
+        });
+        netlist.add_device(TestResistor {
+            node_pos: NodeId::new(1),
+            node_neg: NodeId::GROUND,
+            conductance: f64::INFINITY,
+        });
+
+        let remap = netlist.merge_shorted_nodes().unwrap();
+        assert!(remap.node(NodeId::new(1)).is_ground());
+        assert_eq!(remap.node(NodeId::new(2)), NodeId::new(1));
+        assert_eq!(netlist.num_nodes(), 1);
+    }
+
+    #[derive(Debug)]
+    struct ZeroCap;
+
+    impl Stamper for ZeroCap {
+        fn stamp(&self, _mna: &mut MnaSystem) {}
+
+        fn ac_info(&self) -> AcDeviceInfo {
+            AcDeviceInfo::Capacitor {
+                node_pos: Some(0),
+                node_neg: None,
+                capacitance: 0.0,
+            }
+        }
+    }
 }
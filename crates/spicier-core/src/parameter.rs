@@ -0,0 +1,453 @@
+//! Addressing circuit parameters by name.
+//!
+//! Parameter sweeps, sensitivity analysis, Monte Carlo and corner runs all
+//! need to name "this parameter of this device". A [`ParameterHandle`] is
+//! that name, with [`get`](ParameterHandle::get) and
+//! [`set`](ParameterHandle::set) against a [`Netlist`].
+//!
+//! Device and source values live on the devices and are edited in place.
+//! `.PARAM` values and model card parameters are folded into device values
+//! when the netlist is built, so changing one rebuilds the netlist from its
+//! [`ParameterTable`], which the parser fills in and attaches a rebuild
+//! function to.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::{Error, Result};
+use crate::netlist::Netlist;
+
+/// Names one value in a netlist.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParameterHandle {
+    /// Primary value of a passive device: resistance, capacitance or
+    /// inductance (`R1`).
+    Device(String),
+    /// DC value of an independent voltage or current source (`V1`).
+    Source(String),
+    /// A model card parameter (`NMOS.VTH0`), shared by every device that
+    /// uses the model.
+    Model {
+        /// Model name.
+        model: String,
+        /// Parameter name.
+        param: String,
+    },
+    /// A `.PARAM` value.
+    Param(String),
+}
+
+impl ParameterHandle {
+    /// Parse a handle from SPICE-style text.
+    ///
+    /// `MODEL.PARAM` names a model parameter, names starting with `V` or `I`
+    /// a source, and any other name a device. `.PARAM` values share their
+    /// namespace with device names, so they are only reachable through
+    /// [`ParameterHandle::Param`].
+    pub fn parse(spec: &str) -> Self {
+        let spec = spec.trim();
+        if let Some((model, param)) = spec.split_once('.') {
+            return ParameterHandle::Model {
+                model: model.to_string(),
+                param: param.to_string(),
+            };
+        }
+        match spec.chars().next().map(|c| c.to_ascii_uppercase()) {
+            Some('V' | 'I') => ParameterHandle::Source(spec.to_string()),
+            _ => ParameterHandle::Device(spec.to_string()),
+        }
+    }
+
+    /// Current value of the parameter in `netlist`.
+    ///
+    /// Model parameters are reported as written on the model card; ones
+    /// left at their defaults are [`Error::ParameterNotFound`].
+    pub fn get(&self, netlist: &Netlist) -> Result<f64> {
+        match self {
+            ParameterHandle::Device(name) | ParameterHandle::Source(name) => {
+                let device = netlist
+                    .devices()
+                    .iter()
+                    .find(|d| d.device_name().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| Error::DeviceNotFound(name.clone()))?;
+                self.check_kind(device.is_source())?;
+                device
+                    .value()
+                    .ok_or_else(|| Error::InvalidCircuit(format!("{name} has no editable value")))
+            }
+            ParameterHandle::Model { model, param } => netlist
+                .parameters()
+                .model_param(model, param)
+                .ok_or_else(|| Error::ParameterNotFound(self.to_string())),
+            ParameterHandle::Param(name) => netlist
+                .parameters()
+                .param(name)
+                .ok_or_else(|| Error::ParameterNotFound(self.to_string())),
+        }
+    }
+
+    /// Set the parameter in `netlist`.
+    ///
+    /// Device and source values are replaced in place. `.PARAM` and model
+    /// parameters rebuild the whole netlist, so in-place edits made since
+    /// it was built (device values, temperature) are reset. Setting a
+    /// `.PARAM` the netlist does not define adds it.
+    pub fn set(&self, netlist: &mut Netlist, value: f64) -> Result<()> {
+        match self {
+            ParameterHandle::Device(name) | ParameterHandle::Source(name) => {
+                let is_source = netlist
+                    .devices()
+                    .iter()
+                    .find(|d| d.device_name().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| Error::DeviceNotFound(name.clone()))?
+                    .is_source();
+                self.check_kind(is_source)?;
+                netlist.set_device_value(name, value).map(|_| ())
+            }
+            ParameterHandle::Model { model, param } => {
+                let mut table = netlist.parameters().clone();
+                if table.model_params(model).is_none() {
+                    return Err(Error::ParameterNotFound(self.to_string()));
+                }
+                table.set_model_param(model, param, value);
+                *netlist = table.rebuild()?;
+                Ok(())
+            }
+            ParameterHandle::Param(name) => {
+                let mut table = netlist.parameters().clone();
+                table.set_param(name, value);
+                *netlist = table.rebuild()?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Reject a device handle on a source and vice versa.
+    fn check_kind(&self, is_source: bool) -> Result<()> {
+        match self {
+            ParameterHandle::Device(name) if is_source => Err(Error::InvalidCircuit(format!(
+                "{name} is a source; use ParameterHandle::Source"
+            ))),
+            ParameterHandle::Source(name) if !is_source => Err(Error::InvalidCircuit(format!(
+                "{name} is not an independent source"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for ParameterHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterHandle::Device(name)
+            | ParameterHandle::Source(name)
+            | ParameterHandle::Param(name) => write!(f, "{name}"),
+            ParameterHandle::Model { model, param } => write!(f, "{model}.{param}"),
+        }
+    }
+}
+
+/// Rebuilds a netlist from its source with the values in a
+/// [`ParameterTable`].
+pub type RebuildFn = dyn Fn(&ParameterTable) -> Result<Netlist> + Send + Sync;
+
+/// The `.PARAM` values and model card parameters a netlist was built from.
+///
+/// Values are recorded as the parser evaluated them. Values changed with
+/// [`set_param`](Self::set_param) or [`set_model_param`](Self::set_model_param)
+/// are also pinned: a rebuild uses them in place of the netlist's own
+/// definitions, while everything else, including `.PARAM`s defined in terms
+/// of a pinned one, is evaluated afresh.
+///
+/// Names are case-insensitive and stored uppercase.
+#[derive(Clone, Default)]
+pub struct ParameterTable {
+    params: HashMap<String, f64>,
+    models: HashMap<String, HashMap<String, f64>>,
+    pinned_params: HashSet<String>,
+    pinned_model_params: HashSet<(String, String)>,
+    rebuild: Option<Arc<RebuildFn>>,
+}
+
+impl ParameterTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value of a `.PARAM`.
+    pub fn param(&self, name: &str) -> Option<f64> {
+        self.params.get(&name.to_uppercase()).copied()
+    }
+
+    /// All `.PARAM` values, keyed by uppercase name.
+    pub fn params(&self) -> &HashMap<String, f64> {
+        &self.params
+    }
+
+    /// Define or replace a `.PARAM` and pin it for rebuilds.
+    pub fn set_param(&mut self, name: &str, value: f64) {
+        let name = name.to_uppercase();
+        self.params.insert(name.clone(), value);
+        self.pinned_params.insert(name);
+    }
+
+    /// Record a `.PARAM` value as evaluated while building the netlist.
+    pub fn record_param(&mut self, name: &str, value: f64) {
+        self.params.insert(name.to_uppercase(), value);
+    }
+
+    /// Value of a `.PARAM` pinned with [`set_param`](Self::set_param).
+    pub fn pinned_param(&self, name: &str) -> Option<f64> {
+        let name = name.to_uppercase();
+        self.pinned_params
+            .contains(&name)
+            .then(|| self.params[&name])
+    }
+
+    /// All pinned `.PARAM` values.
+    pub fn pinned_params(&self) -> impl Iterator<Item = (&str, f64)> + '_ {
+        self.pinned_params
+            .iter()
+            .map(|name| (name.as_str(), self.params[name]))
+    }
+
+    /// A parameter as written on a model card.
+    pub fn model_param(&self, model: &str, param: &str) -> Option<f64> {
+        self.model_params(model)?
+            .get(&param.to_uppercase())
+            .copied()
+    }
+
+    /// All parameters written on a model card, keyed by uppercase name.
+    pub fn model_params(&self, model: &str) -> Option<&HashMap<String, f64>> {
+        self.models.get(&model.to_uppercase())
+    }
+
+    /// Define or replace a model card parameter and pin it for rebuilds.
+    pub fn set_model_param(&mut self, model: &str, param: &str, value: f64) {
+        let key = (model.to_uppercase(), param.to_uppercase());
+        self.record_model_param(model, param, value);
+        self.pinned_model_params.insert(key);
+    }
+
+    /// Record a model card parameter as evaluated while building the
+    /// netlist.
+    pub fn record_model_param(&mut self, model: &str, param: &str, value: f64) {
+        self.models
+            .entry(model.to_uppercase())
+            .or_default()
+            .insert(param.to_uppercase(), value);
+    }
+
+    /// Parameters of `model` pinned with
+    /// [`set_model_param`](Self::set_model_param).
+    pub fn pinned_model_params(&self, model: &str) -> Vec<(String, f64)> {
+        let model = model.to_uppercase();
+        self.pinned_model_params
+            .iter()
+            .filter(|(m, _)| *m == model)
+            .map(|(m, p)| (p.clone(), self.models[m][p]))
+            .collect()
+    }
+
+    /// Install the function that rebuilds the netlist from this table.
+    pub fn set_rebuild(
+        &mut self,
+        rebuild: impl Fn(&ParameterTable) -> Result<Netlist> + Send + Sync + 'static,
+    ) {
+        self.rebuild = Some(Arc::new(rebuild));
+    }
+
+    /// Whether a rebuild function is installed.
+    pub fn can_rebuild(&self) -> bool {
+        self.rebuild.is_some()
+    }
+
+    /// Rebuild the netlist with the values in this table.
+    pub fn rebuild(&self) -> Result<Netlist> {
+        let rebuild = self.rebuild.as_ref().ok_or_else(|| {
+            Error::InvalidCircuit(
+                "netlist has no source to rebuild from; .PARAM and model parameters \
+                 can only be changed on a parsed netlist"
+                    .to_string(),
+            )
+        })?;
+        rebuild(self)
+    }
+}
+
+impl fmt::Debug for ParameterTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParameterTable")
+            .field("params", &self.params)
+            .field("models", &self.models)
+            .field("pinned_params", &self.pinned_params)
+            .field("pinned_model_params", &self.pinned_model_params)
+            .field("can_rebuild", &self.can_rebuild())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mna::MnaSystem;
+    use crate::netlist::Stamper;
+
+    /// Two-terminal device with one editable value.
+    #[derive(Debug)]
+    struct Valued {
+        name: &'static str,
+        value: f64,
+        source: bool,
+    }
+
+    impl Stamper for Valued {
+        fn stamp(&self, _mna: &mut MnaSystem) {}
+
+        fn device_name(&self) -> &str {
+            self.name
+        }
+
+        fn is_source(&self) -> bool {
+            self.source
+        }
+
+        fn value(&self) -> Option<f64> {
+            Some(self.value)
+        }
+
+        fn set_value(&mut self, value: f64) -> bool {
+            self.value = value;
+            true
+        }
+    }
+
+    /// A netlist with R1 = 1k, V1 = 5 and the table's `RVAL` and `NMOS.VTH0`
+    /// baked into R2 and R3 when it is built.
+    fn build(table: &ParameterTable) -> Result<Netlist> {
+        let mut netlist = Netlist::new();
+        let r2 = table.param("rval").unwrap_or(1e3);
+        let r3 = table.model_param("nmos", "vth0").unwrap_or(0.5);
+        for (name, value, source) in [("R1", 1e3, false), ("V1", 5.0, true)] {
+            netlist.add_device(Valued {
+                name,
+                value,
+                source,
+            });
+        }
+        netlist.add_device(Valued {
+            name: "R2",
+            value: r2,
+            source: false,
+        });
+        netlist.add_device(Valued {
+            name: "R3",
+            value: r3,
+            source: false,
+        });
+        *netlist.parameters_mut() = table.clone();
+        netlist.parameters_mut().set_rebuild(build);
+        Ok(netlist)
+    }
+
+    fn parameterized() -> Netlist {
+        let mut table = ParameterTable::new();
+        table.set_param("RVAL", 2e3);
+        table.set_model_param("NMOS", "VTH0", 0.45);
+        build(&table).unwrap()
+    }
+
+    #[test]
+    fn test_parse_handle() {
+        assert_eq!(
+            ParameterHandle::parse("R1"),
+            ParameterHandle::Device("R1".into())
+        );
+        assert_eq!(
+            ParameterHandle::parse("i2"),
+            ParameterHandle::Source("i2".into())
+        );
+        let handle = ParameterHandle::parse("NMOS.vth0");
+        assert_eq!(
+            handle,
+            ParameterHandle::Model {
+                model: "NMOS".into(),
+                param: "vth0".into()
+            }
+        );
+        assert_eq!(handle.to_string(), "NMOS.vth0");
+    }
+
+    #[test]
+    fn test_device_handle() {
+        let mut netlist = parameterized();
+        let r1 = ParameterHandle::Device("r1".into());
+        assert_eq!(r1.get(&netlist).unwrap(), 1e3);
+        r1.set(&mut netlist, 4.7e3).unwrap();
+        assert_eq!(r1.get(&netlist).unwrap(), 4.7e3);
+
+        assert!(matches!(
+            ParameterHandle::Device("R9".into()).get(&netlist),
+            Err(Error::DeviceNotFound(_))
+        ));
+        assert!(ParameterHandle::Device("V1".into()).get(&netlist).is_err());
+    }
+
+    #[test]
+    fn test_source_handle() {
+        let mut netlist = parameterized();
+        let v1 = ParameterHandle::Source("V1".into());
+        assert_eq!(v1.get(&netlist).unwrap(), 5.0);
+        v1.set(&mut netlist, 3.3).unwrap();
+        assert_eq!(v1.get(&netlist).unwrap(), 3.3);
+
+        assert!(
+            ParameterHandle::Source("R1".into())
+                .set(&mut netlist, 1.0)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_model_handle() {
+        let mut netlist = parameterized();
+        let vth0 = ParameterHandle::parse("nmos.VTH0");
+        assert_eq!(vth0.get(&netlist).unwrap(), 0.45);
+
+        vth0.set(&mut netlist, 0.4).unwrap();
+        assert_eq!(vth0.get(&netlist).unwrap(), 0.4);
+        let r3 = ParameterHandle::Device("R3".into());
+        assert_eq!(r3.get(&netlist).unwrap(), 0.4);
+
+        let unknown = ParameterHandle::parse("PMOS.VTH0");
+        assert!(matches!(
+            unknown.set(&mut netlist, 0.1),
+            Err(Error::ParameterNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_param_handle() {
+        let mut netlist = parameterized();
+        let rval = ParameterHandle::Param("rval".into());
+        assert_eq!(rval.get(&netlist).unwrap(), 2e3);
+
+        // Rebuilding resets in-place edits and applies the new value.
+        ParameterHandle::Device("R1".into())
+            .set(&mut netlist, 10.0)
+            .unwrap();
+        rval.set(&mut netlist, 3e3).unwrap();
+        assert_eq!(rval.get(&netlist).unwrap(), 3e3);
+        let device = |name: &str| ParameterHandle::Device(name.into()).get(&netlist).unwrap();
+        assert_eq!(device("R2"), 3e3);
+        assert_eq!(device("R1"), 1e3);
+
+        // A netlist built by hand has nothing to rebuild from.
+        let mut plain = Netlist::new();
+        assert!(matches!(rval.get(&plain), Err(Error::ParameterNotFound(_))));
+        assert!(rval.set(&mut plain, 1.0).is_err());
+    }
+}
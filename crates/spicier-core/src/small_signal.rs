@@ -0,0 +1,318 @@
+//! Per-device small-signal parameters at a DC operating point.
+//!
+//! [`Netlist::small_signal_report`] linearizes every nonlinear device with
+//! [`Stamper::ac_info_at`](crate::Stamper::ac_info_at) and collects the
+//! result in physical units, with the derived figures SPICE prints alongside
+//! `.op`: a FET's transit frequency `ft = gm / (2π (Cgs + Cgd))` and
+//! intrinsic gain `gm / gds`, a BJT's `rπ`, `ro` and small-signal β.
+
+use crate::netlist::{AcDeviceInfo, Netlist};
+use nalgebra::DVector;
+use std::f64::consts::PI;
+
+/// Small-signal parameters of a MOSFET or JFET.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetSmallSignal {
+    /// Device name.
+    pub name: String,
+    /// Transconductance dIds/dVgs (S).
+    pub gm: f64,
+    /// Output conductance dIds/dVds (S).
+    pub gds: f64,
+    /// Body transconductance dIds/dVbs (S); zero for models without a bulk.
+    pub gmbs: f64,
+    /// Gate-source capacitance (F); zero for DC-only models.
+    pub cgs: f64,
+    /// Gate-drain capacitance (F); zero for DC-only models.
+    pub cgd: f64,
+}
+
+impl FetSmallSignal {
+    /// Transit frequency `gm / (2π (Cgs + Cgd))` (Hz).
+    ///
+    /// `None` for models that report no gate capacitance.
+    pub fn ft(&self) -> Option<f64> {
+        let cg = self.cgs + self.cgd;
+        (cg > 0.0).then(|| self.gm / (2.0 * PI * cg))
+    }
+
+    /// Intrinsic voltage gain `gm / gds`.
+    pub fn intrinsic_gain(&self) -> f64 {
+        self.gm / self.gds
+    }
+
+    /// Output resistance `1 / gds` (Ω).
+    pub fn ro(&self) -> f64 {
+        1.0 / self.gds
+    }
+}
+
+/// Hybrid-π parameters of a BJT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BjtSmallSignal {
+    /// Device name.
+    pub name: String,
+    /// Transconductance dIc/dVbe (S).
+    pub gm: f64,
+    /// Base input resistance rπ (Ω).
+    pub rpi: f64,
+    /// Output resistance ro (Ω); infinite without Early effect.
+    pub ro: f64,
+}
+
+impl BjtSmallSignal {
+    /// Small-signal current gain `gm · rπ`.
+    pub fn beta(&self) -> f64 {
+        self.gm * self.rpi
+    }
+
+    /// Intrinsic voltage gain `gm · ro`.
+    pub fn intrinsic_gain(&self) -> f64 {
+        self.gm * self.ro
+    }
+}
+
+/// Small-signal conductance of a diode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiodeSmallSignal {
+    /// Device name.
+    pub name: String,
+    /// Junction conductance dId/dVd (S).
+    pub gd: f64,
+}
+
+impl DiodeSmallSignal {
+    /// Dynamic resistance `1 / gd` (Ω).
+    pub fn rd(&self) -> f64 {
+        1.0 / self.gd
+    }
+}
+
+/// Small-signal parameters of every nonlinear device, in netlist order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SmallSignalReport {
+    /// MOSFETs (all levels).
+    pub mosfets: Vec<FetSmallSignal>,
+    /// JFETs.
+    pub jfets: Vec<FetSmallSignal>,
+    /// BJTs.
+    pub bjts: Vec<BjtSmallSignal>,
+    /// Diodes.
+    pub diodes: Vec<DiodeSmallSignal>,
+}
+
+impl SmallSignalReport {
+    /// Find a MOSFET by name (case-insensitive).
+    pub fn mosfet(&self, name: &str) -> Option<&FetSmallSignal> {
+        self.mosfets
+            .iter()
+            .find(|m| m.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Find a JFET by name (case-insensitive).
+    pub fn jfet(&self, name: &str) -> Option<&FetSmallSignal> {
+        self.jfets
+            .iter()
+            .find(|j| j.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Find a BJT by name (case-insensitive).
+    pub fn bjt(&self, name: &str) -> Option<&BjtSmallSignal> {
+        self.bjts.iter().find(|q| q.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Find a diode by name (case-insensitive).
+    pub fn diode(&self, name: &str) -> Option<&DiodeSmallSignal> {
+        self.diodes
+            .iter()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether the circuit has no nonlinear devices to report.
+    pub fn is_empty(&self) -> bool {
+        self.mosfets.is_empty()
+            && self.jfets.is_empty()
+            && self.bjts.is_empty()
+            && self.diodes.is_empty()
+    }
+}
+
+impl Netlist {
+    /// Collect the small-signal parameters of every nonlinear device,
+    /// linearized at `dc_solution`.
+    pub fn small_signal_report(&self, dc_solution: &DVector<f64>) -> SmallSignalReport {
+        let mut report = SmallSignalReport::default();
+        for device in self.devices() {
+            let name = device.device_name().to_string();
+            match device.ac_info_at(dc_solution) {
+                AcDeviceInfo::Mosfet { gds, gm, .. } => report.mosfets.push(FetSmallSignal {
+                    name,
+                    gm,
+                    gds,
+                    gmbs: 0.0,
+                    cgs: 0.0,
+                    cgd: 0.0,
+                }),
+                AcDeviceInfo::Bsim1Mosfet { gds, gm, gmbs, .. } => {
+                    report.mosfets.push(FetSmallSignal {
+                        name,
+                        gm,
+                        gds,
+                        gmbs,
+                        cgs: 0.0,
+                        cgd: 0.0,
+                    })
+                }
+                AcDeviceInfo::Bsim3Mosfet {
+                    gds,
+                    gm,
+                    gmbs,
+                    cgs,
+                    cgd,
+                    ..
+                } => report.mosfets.push(FetSmallSignal {
+                    name,
+                    gm,
+                    gds,
+                    gmbs,
+                    cgs,
+                    cgd,
+                }),
+                AcDeviceInfo::Jfet { gds, gm, .. } => report.jfets.push(FetSmallSignal {
+                    name,
+                    gm,
+                    gds,
+                    gmbs: 0.0,
+                    cgs: 0.0,
+                    cgd: 0.0,
+                }),
+                AcDeviceInfo::Bjt { gm, gpi, go, .. } => report.bjts.push(BjtSmallSignal {
+                    name,
+                    gm,
+                    rpi: 1.0 / gpi,
+                    ro: 1.0 / go,
+                }),
+                AcDeviceInfo::Diode { gd, .. } => report.diodes.push(DiodeSmallSignal { name, gd }),
+                _ => {}
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mna::MnaSystem;
+    use crate::netlist::Stamper;
+
+    /// Device that reports fixed small-signal info.
+    #[derive(Debug)]
+    struct Linearized {
+        name: &'static str,
+        info: AcDeviceInfo,
+    }
+
+    impl Stamper for Linearized {
+        fn stamp(&self, _mna: &mut MnaSystem) {}
+
+        fn device_name(&self) -> &str {
+            self.name
+        }
+
+        fn ac_info(&self) -> AcDeviceInfo {
+            self.info.clone()
+        }
+
+        fn is_nonlinear(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_small_signal_report() {
+        let mut netlist = Netlist::new();
+        netlist.add_device(Linearized {
+            name: "M1",
+            info: AcDeviceInfo::Bsim3Mosfet {
+                drain: Some(0),
+                gate: Some(1),
+                source: None,
+                bulk: None,
+                gds: 2e-5,
+                gm: 1e-3,
+                saturated: true,
+                gmbs: 2e-4,
+                cgs: 1.5e-15,
+                cgd: 0.5e-15,
+                cgb: 0.0,
+                cbs: 0.0,
+                cbd: 0.0,
+            },
+        });
+        netlist.add_device(Linearized {
+            name: "M2",
+            info: AcDeviceInfo::Mosfet {
+                drain: Some(0),
+                gate: Some(1),
+                source: None,
+                gds: 1e-4,
+                gm: 2e-3,
+                saturated: true,
+            },
+        });
+        netlist.add_device(Linearized {
+            name: "Q1",
+            info: AcDeviceInfo::Bjt {
+                collector: Some(0),
+                base: Some(1),
+                emitter: None,
+                gm: 0.04,
+                gpi: 4e-4,
+                gmu: 0.0,
+                go: 1e-5,
+            },
+        });
+        netlist.add_device(Linearized {
+            name: "D1",
+            info: AcDeviceInfo::Diode {
+                node_pos: Some(0),
+                node_neg: None,
+                gd: 0.02,
+            },
+        });
+        netlist.add_device(Linearized {
+            name: "R1",
+            info: AcDeviceInfo::Resistor {
+                node_pos: Some(0),
+                node_neg: None,
+                conductance: 1e-3,
+            },
+        });
+
+        let report = netlist.small_signal_report(&DVector::zeros(2));
+        assert_eq!(report.mosfets.len(), 2);
+        assert!(report.jfets.is_empty());
+
+        let m1 = report.mosfet("m1").unwrap();
+        let ft = m1.ft().unwrap();
+        assert!((ft - 1e-3 / (2.0 * PI * 2e-15)).abs() / ft < 1e-12);
+        assert!((m1.intrinsic_gain() - 50.0).abs() < 1e-9);
+        assert_eq!(m1.gmbs, 2e-4);
+
+        // Level 1 has no gate capacitance, hence no ft.
+        let m2 = report.mosfet("M2").unwrap();
+        assert_eq!(m2.ft(), None);
+        assert!((m2.ro() - 1e4).abs() < 1e-6);
+
+        let q1 = report.bjt("Q1").unwrap();
+        assert!((q1.rpi - 2500.0).abs() < 1e-9);
+        assert!((q1.ro - 1e5).abs() < 1e-6);
+        assert!((q1.beta() - 100.0).abs() < 1e-9);
+        assert!((q1.intrinsic_gain() - 4000.0).abs() < 1e-6);
+
+        assert!((report.diode("D1").unwrap().rd() - 50.0).abs() < 1e-12);
+        assert!(report.diode("R1").is_none());
+        assert!(!report.is_empty());
+    }
+}
@@ -0,0 +1,698 @@
+//! Structural checks for circuits whose MNA system is singular by topology.
+//!
+//! Two classic problems make the DC operating point (and therefore the start
+//! of every transient) singular no matter what the element values are:
+//!
+//! - a loop made only of inductors and voltage sources, which at DC is a loop
+//!   of ideal voltage constraints with an undetermined circulating current;
+//! - a group of nodes reached from ground only through capacitors (or current
+//!   sources), which at DC has no path to ground and an undetermined voltage.
+//!
+//! [`Netlist::find_topology_issues`] reports both and
+//! [`Netlist::repair_topology`] breaks them with small parasitics.
+//! [`Netlist::check_ground_reference`] catches the more basic case of a node
+//! with no connection to ground at all, which no parasitic should paper over.
+
+use crate::error::{Error, Result};
+use crate::mna::MnaSystem;
+use crate::netlist::{
+    AcDeviceInfo, BoxedStamper, Netlist, NodeRemap, Stamper, TransientDeviceInfo,
+};
+use crate::node::NodeId;
+use nalgebra::DVector;
+
+/// A structural problem found by [`Netlist::find_topology_issues`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TopologyIssue {
+    /// The named inductor closes a loop of inductors and voltage sources.
+    InductorLoop {
+        /// Inductor that closes the loop.
+        inductor: String,
+    },
+    /// These nodes connect to ground only through capacitors or current sources.
+    CapacitorCutset {
+        /// Nodes with no DC path to ground.
+        nodes: Vec<NodeId>,
+    },
+}
+
+impl std::fmt::Display for TopologyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopologyIssue::InductorLoop { inductor } => write!(
+                f,
+                "inductor {inductor} closes a loop of inductors and voltage sources"
+            ),
+            TopologyIssue::CapacitorCutset { nodes } => {
+                let names: Vec<String> = nodes.iter().map(|n| n.as_u32().to_string()).collect();
+                write!(
+                    f,
+                    "node(s) {} have no DC path to ground (capacitor cutset)",
+                    names.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// Parasitic values inserted by [`Netlist::repair_topology`].
+#[derive(Debug, Clone)]
+pub struct TopologyRepair {
+    /// Series resistance (Ω) added to an inductor that closes a loop.
+    pub series_resistance: f64,
+    /// Shunt resistance (Ω) to ground added at a floating node group.
+    ///
+    /// A resistor rather than a shunt capacitor: the cutset is singular at
+    /// the DC operating point that starts every transient, where a capacitor
+    /// is an open circuit and would leave the node just as undetermined.
+    pub shunt_resistance: f64,
+}
+
+impl Default for TopologyRepair {
+    fn default() -> Self {
+        Self {
+            series_resistance: 1e-3,
+            shunt_resistance: 1e12,
+        }
+    }
+}
+
+impl Netlist {
+    /// Find inductor loops and capacitor cutsets.
+    ///
+    /// Devices the check does not recognize are assumed to connect every node
+    /// they stamp to ground, so they never cause a cutset to be reported.
+    pub fn find_topology_issues(&self) -> Vec<TopologyIssue> {
+        let num_ids = self.num_nodes() + 1;
+        let id = |idx: Option<usize>| idx.map_or(0, |i| i + 1);
+        let mut issues = Vec::new();
+
+        // Inductor loops: join voltage-defined branches first, then any
+        // inductor whose terminals are already joined closes a loop.
+        let mut loops = DisjointSet::new(num_ids);
+        let mut inductors = Vec::new();
+        for device in self.devices() {
+            match device.ac_info() {
+                AcDeviceInfo::VoltageSource {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::Vcvs {
+                    out_pos: node_pos,
+                    out_neg: node_neg,
+                    ..
+                }
+                | AcDeviceInfo::Ccvs {
+                    out_pos: node_pos,
+                    out_neg: node_neg,
+                    ..
+                } => loops.union(id(node_pos), id(node_neg)),
+                AcDeviceInfo::Inductor {
+                    node_pos, node_neg, ..
+                } if node_pos != node_neg => {
+                    inductors.push((device.device_name(), id(node_pos), id(node_neg)))
+                }
+                _ => {}
+            }
+        }
+        for (name, a, b) in inductors {
+            if loops.find(a) == loops.find(b) {
+                issues.push(TopologyIssue::InductorLoop {
+                    inductor: name.to_string(),
+                });
+            } else {
+                loops.union(a, b);
+            }
+        }
+
+        // Capacitor cutsets: nodes not joined to ground by a DC path.
+        let mut dc = DisjointSet::new(num_ids);
+        for device in self.devices() {
+            let nodes: Vec<Option<usize>> = match device.ac_info() {
+                AcDeviceInfo::Resistor {
+                    node_pos,
+                    node_neg,
+                    conductance,
+                } if conductance != 0.0 => vec![node_pos, node_neg],
+                AcDeviceInfo::Inductor {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::VoltageSource {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::Diode {
+                    node_pos, node_neg, ..
+                }
+                | AcDeviceInfo::Vcvs {
+                    out_pos: node_pos,
+                    out_neg: node_neg,
+                    ..
+                }
+                | AcDeviceInfo::Ccvs {
+                    out_pos: node_pos,
+                    out_neg: node_neg,
+                    ..
+                } => vec![node_pos, node_neg],
+                AcDeviceInfo::Mosfet { drain, source, .. } => vec![drain, source],
+                AcDeviceInfo::Bsim1Mosfet {
+                    drain,
+                    source,
+                    bulk,
+                    ..
+                }
+                | AcDeviceInfo::Bsim3Mosfet {
+                    drain,
+                    source,
+                    bulk,
+                    ..
+                } => vec![drain, source, bulk],
+                AcDeviceInfo::Jfet {
+                    drain,
+                    gate,
+                    source,
+                    ..
+                } => vec![drain, gate, source],
+                AcDeviceInfo::Bjt {
+                    collector,
+                    base,
+                    emitter,
+                    ..
+                } => vec![collector, base, emitter],
+                AcDeviceInfo::TransmissionLine {
+                    port1_pos,
+                    port2_pos,
+                    internal_nodes,
+                    ..
+                } => {
+                    let mut chain = vec![port1_pos, port2_pos];
+                    chain.extend(internal_nodes);
+                    chain
+                }
+                AcDeviceInfo::Capacitor { .. }
+                | AcDeviceInfo::CurrentSource { .. }
+                | AcDeviceInfo::Vccs { .. }
+                | AcDeviceInfo::Cccs { .. }
+                | AcDeviceInfo::MutualInductance { .. } => continue,
+                _ => {
+                    // Unknown device: tie whatever it stamps to ground.
+                    for idx in self.stamped_nodes(device.as_ref()) {
+                        dc.union(0, idx + 1);
+                    }
+                    continue;
+                }
+            };
+            for pair in nodes.windows(2) {
+                dc.union(id(pair[0]), id(pair[1]));
+            }
+        }
+
+        let ground = dc.find(0);
+        let mut groups: Vec<(usize, Vec<NodeId>)> = Vec::new();
+        for node in 1..num_ids {
+            let root = dc.find(node);
+            if root == ground {
+                continue;
+            }
+            let node_id = NodeId::new(node as u32);
+            match groups.iter_mut().find(|(r, _)| *r == root) {
+                Some((_, nodes)) => nodes.push(node_id),
+                None => groups.push((root, vec![node_id])),
+            }
+        }
+        issues.extend(
+            groups
+                .into_iter()
+                .map(|(_, nodes)| TopologyIssue::CapacitorCutset { nodes }),
+        );
+
+        issues
+    }
+
+    /// Check that every node connects to ground (node 0) through some chain
+    /// of devices.
+    ///
+    /// Without one the node's voltage has no reference and the DC system is
+    /// singular; this reports the lowest such node as
+    /// [`Error::NoGroundReference`] instead of a solver failure. The check is
+    /// structural: a device joins all of its terminals (and a transmission
+    /// line its internal nodes), except that a controlled source's
+    /// controlling pair is joined only to itself, and devices without
+    /// terminal information tie whatever they stamp to ground.
+    pub fn check_ground_reference(&self) -> Result<()> {
+        let num_ids = self.num_nodes() + 1;
+        let id = |idx: Option<usize>| idx.map_or(0, |i| i + 1);
+        let mut connected = DisjointSet::new(num_ids);
+        for device in self.devices() {
+            let info = device.ac_info();
+            let groups = match info {
+                AcDeviceInfo::Vcvs {
+                    out_pos,
+                    out_neg,
+                    ctrl_pos,
+                    ctrl_neg,
+                    ..
+                }
+                | AcDeviceInfo::Vccs {
+                    out_pos,
+                    out_neg,
+                    ctrl_pos,
+                    ctrl_neg,
+                    ..
+                } => vec![vec![out_pos, out_neg], vec![ctrl_pos, ctrl_neg]],
+                AcDeviceInfo::TransmissionLine {
+                    port1_pos,
+                    port1_neg,
+                    port2_pos,
+                    port2_neg,
+                    ref internal_nodes,
+                    ..
+                } => {
+                    let mut nodes = vec![port1_pos, port1_neg, port2_pos, port2_neg];
+                    nodes.extend(internal_nodes);
+                    vec![nodes]
+                }
+                _ => match info.terminals() {
+                    Some(terminals) => vec![terminals],
+                    None => {
+                        for idx in self.stamped_nodes(device.as_ref()) {
+                            connected.union(0, idx + 1);
+                        }
+                        continue;
+                    }
+                },
+            };
+            for pair in groups.iter().flat_map(|group| group.windows(2)) {
+                connected.union(id(pair[0]), id(pair[1]));
+            }
+        }
+
+        let ground = connected.find(0);
+        match (1..num_ids).find(|&node| connected.find(node) != ground) {
+            Some(node) => Err(Error::NoGroundReference {
+                node: NodeId::new(node as u32),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Node indices a device touches in its DC stamp.
+    fn stamped_nodes(&self, device: &dyn Stamper) -> Vec<usize> {
+        let mut mna = MnaSystem::new(self.num_nodes(), self.num_current_vars());
+        device.stamp(&mut mna);
+        mna.triplets
+            .iter()
+            .flat_map(|&(row, col, _)| [row, col])
+            .filter(|&idx| idx < self.num_nodes())
+            .collect()
+    }
+
+    /// Break inductor loops and capacitor cutsets with small parasitics.
+    ///
+    /// Each loop-closing inductor gets `series_resistance` in its branch
+    /// equation, and each floating node group gets `shunt_resistance` to
+    /// ground from its first node. Returns the issues that were repaired so
+    /// the caller can report them. Wrapped inductors still report as
+    /// inductors, so call this once per netlist.
+    pub fn repair_topology(&mut self, repair: &TopologyRepair) -> Vec<TopologyIssue> {
+        let issues = self.find_topology_issues();
+        for issue in &issues {
+            match issue {
+                TopologyIssue::InductorLoop { inductor } => {
+                    let devices = self.devices_mut();
+                    if let Some(idx) = devices.iter().position(|d| d.device_name() == inductor) {
+                        let inner = devices.remove(idx);
+                        devices.insert(
+                            idx,
+                            Box::new(SeriesResistance {
+                                inner,
+                                resistance: repair.series_resistance,
+                            }),
+                        );
+                    }
+                }
+                TopologyIssue::CapacitorCutset { nodes } => {
+                    let node = nodes[0];
+                    self.add_device(ShuntResistor {
+                        name: format!("RSHUNT_{}", node.as_u32()),
+                        node,
+                        conductance: 1.0 / repair.shunt_resistance,
+                    });
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// Union-find over node IDs (0 = ground).
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra != rb {
+            self.parent[rb] = ra;
+        }
+    }
+}
+
+/// Wraps a branch-current device, adding a series resistance to its branch
+/// equation: `V(pos) - V(neg) - R·I = ...`.
+#[derive(Debug)]
+struct SeriesResistance {
+    inner: BoxedStamper,
+    resistance: f64,
+}
+
+impl SeriesResistance {
+    fn stamp_resistance(&self, mna: &mut MnaSystem) {
+        if let Some(branch) = self.inner.branch_index() {
+            let br = mna.num_nodes + branch;
+            mna.add_element(br, br, -self.resistance);
+        }
+    }
+}
+
+impl Stamper for SeriesResistance {
+    fn stamp(&self, mna: &mut MnaSystem) {
+        self.inner.stamp(mna);
+        self.stamp_resistance(mna);
+    }
+
+    fn num_current_vars(&self) -> usize {
+        self.inner.num_current_vars()
+    }
+
+    fn device_name(&self) -> &str {
+        self.inner.device_name()
+    }
+
+    fn branch_index(&self) -> Option<usize> {
+        self.inner.branch_index()
+    }
+
+    fn ac_info(&self) -> AcDeviceInfo {
+        self.inner.ac_info()
+    }
+
+    fn ac_info_at(&self, solution: &DVector<f64>) -> AcDeviceInfo {
+        self.inner.ac_info_at(solution)
+    }
+
+    fn is_nonlinear(&self) -> bool {
+        self.inner.is_nonlinear()
+    }
+
+    fn stamp_nonlinear(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+        self.inner.stamp_nonlinear(mna, solution);
+        self.stamp_resistance(mna);
+    }
+
+    fn stamp_nonlinear_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        state: &mut Vec<f64>,
+    ) {
+        self.inner.stamp_nonlinear_limited(mna, solution, state);
+        self.stamp_resistance(mna);
+    }
+
+    fn transient_info(&self) -> TransientDeviceInfo {
+        self.inner.transient_info()
+    }
+
+    fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
+        self.inner.stamp_at_time(mna, time);
+        self.stamp_resistance(mna);
+    }
+
+    fn is_source(&self) -> bool {
+        self.inner.is_source()
+    }
+
+    fn stamp_nonlinear_scaled(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        source_factor: f64,
+    ) {
+        self.inner
+            .stamp_nonlinear_scaled(mna, solution, source_factor);
+        self.stamp_resistance(mna);
+    }
+
+    fn set_temperature(&mut self, temp: f64) {
+        self.inner.set_temperature(temp);
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.inner.value()
+    }
+
+    fn set_value(&mut self, value: f64) -> bool {
+        self.inner.set_value(value)
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        Some(Box::new(SeriesResistance {
+            inner: self.inner.remapped(remap)?,
+            resistance: self.resistance,
+        }))
+    }
+}
+
+/// Large resistance from a node to ground.
+#[derive(Debug)]
+struct ShuntResistor {
+    name: String,
+    node: NodeId,
+    conductance: f64,
+}
+
+impl ShuntResistor {
+    fn index(&self) -> Option<usize> {
+        (!self.node.is_ground()).then(|| self.node.as_u32() as usize - 1)
+    }
+}
+
+impl Stamper for ShuntResistor {
+    fn stamp(&self, mna: &mut MnaSystem) {
+        mna.stamp_conductance(self.index(), None, self.conductance);
+    }
+
+    fn device_name(&self) -> &str {
+        &self.name
+    }
+
+    fn ac_info(&self) -> AcDeviceInfo {
+        AcDeviceInfo::Resistor {
+            node_pos: self.index(),
+            node_neg: None,
+            conductance: self.conductance,
+        }
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        Some(Box::new(ShuntResistor {
+            name: self.name.clone(),
+            node: remap.node(self.node),
+            conductance: self.conductance,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two-terminal test device described only by its AC info.
+    #[derive(Debug)]
+    struct TwoTerminal {
+        name: &'static str,
+        info: AcDeviceInfo,
+        branch: Option<usize>,
+    }
+
+    impl Stamper for TwoTerminal {
+        fn stamp(&self, mna: &mut MnaSystem) {
+            match self.info {
+                AcDeviceInfo::Resistor {
+                    node_pos,
+                    node_neg,
+                    conductance,
+                } => mna.stamp_conductance(node_pos, node_neg, conductance),
+                AcDeviceInfo::VoltageSource {
+                    node_pos,
+                    node_neg,
+                    branch_idx,
+                    ..
+                }
+                | AcDeviceInfo::Inductor {
+                    node_pos,
+                    node_neg,
+                    branch_idx,
+                    ..
+                } => mna.stamp_voltage_source(
+                    node_pos,
+                    node_neg,
+                    branch_idx,
+                    if self.name.starts_with('V') { 1.0 } else { 0.0 },
+                ),
+                _ => {}
+            }
+        }
+
+        fn num_current_vars(&self) -> usize {
+            self.branch.map_or(0, |_| 1)
+        }
+
+        fn device_name(&self) -> &str {
+            self.name
+        }
+
+        fn branch_index(&self) -> Option<usize> {
+            self.branch
+        }
+
+        fn ac_info(&self) -> AcDeviceInfo {
+            self.info.clone()
+        }
+    }
+
+    fn resistor(name: &'static str, a: Option<usize>, b: Option<usize>) -> TwoTerminal {
+        TwoTerminal {
+            name,
+            info: AcDeviceInfo::Resistor {
+                node_pos: a,
+                node_neg: b,
+                conductance: 1e-3,
+            },
+            branch: None,
+        }
+    }
+
+    fn inductor(name: &'static str, a: Option<usize>, b: Option<usize>, br: usize) -> TwoTerminal {
+        TwoTerminal {
+            name,
+            info: AcDeviceInfo::Inductor {
+                node_pos: a,
+                node_neg: b,
+                inductance: 1e-3,
+                branch_idx: br,
+            },
+            branch: Some(br),
+        }
+    }
+
+    fn capacitor(name: &'static str, a: Option<usize>, b: Option<usize>) -> TwoTerminal {
+        TwoTerminal {
+            name,
+            info: AcDeviceInfo::Capacitor {
+                node_pos: a,
+                node_neg: b,
+                capacitance: 1e-9,
+            },
+            branch: None,
+        }
+    }
+
+    fn is_singular(netlist: &Netlist) -> bool {
+        let a = netlist.assemble_mna().to_dense_matrix();
+        let n = a.nrows();
+        a.lu().solve(&DVector::zeros(n)).is_none()
+    }
+
+    fn register(netlist: &mut Netlist, n: u32) {
+        for i in 1..=n {
+            netlist.register_node(NodeId::new(i));
+        }
+    }
+
+    #[test]
+    fn test_inductor_loop_detected_and_repaired() {
+        // V1 drives node 1 through R1 into node 2; L1 and L2 form a loop
+        // between node 2 and ground.
+        let mut netlist = Netlist::new();
+        register(&mut netlist, 2);
+        netlist.add_device(TwoTerminal {
+            name: "V1",
+            info: AcDeviceInfo::VoltageSource {
+                node_pos: Some(0),
+                node_neg: None,
+                branch_idx: 0,
+                ac_mag: 0.0,
+            },
+            branch: Some(0),
+        });
+        netlist.add_device(resistor("R1", Some(0), Some(1)));
+        netlist.add_device(inductor("L1", Some(1), None, 1));
+        netlist.add_device(inductor("L2", Some(1), None, 2));
+        assert!(is_singular(&netlist));
+
+        let issues = netlist.find_topology_issues();
+        assert_eq!(
+            issues,
+            vec![TopologyIssue::InductorLoop {
+                inductor: "L2".to_string()
+            }]
+        );
+
+        let repaired = netlist.repair_topology(&TopologyRepair::default());
+        assert_eq!(repaired, issues);
+        assert!(!is_singular(&netlist));
+        assert_eq!(netlist.devices()[3].device_name(), "L2");
+    }
+
+    #[test]
+    fn test_capacitor_cutset_detected_and_repaired() {
+        // R1 from node 1 to ground; C1 couples node 1 to node 2, and C2
+        // ties node 2 to node 3, which also floats.
+        let mut netlist = Netlist::new();
+        register(&mut netlist, 3);
+        netlist.add_device(resistor("R1", Some(0), None));
+        netlist.add_device(capacitor("C1", Some(0), Some(1)));
+        netlist.add_device(resistor("R2", Some(1), Some(2)));
+        netlist.add_device(capacitor("C2", Some(2), None));
+        assert!(is_singular(&netlist));
+
+        let issues = netlist.find_topology_issues();
+        assert_eq!(
+            issues,
+            vec![TopologyIssue::CapacitorCutset {
+                nodes: vec![NodeId::new(2), NodeId::new(3)]
+            }]
+        );
+        assert!(issues[0].to_string().contains("2, 3"));
+
+        netlist.repair_topology(&TopologyRepair::default());
+        assert!(netlist.find_topology_issues().is_empty());
+        assert!(!is_singular(&netlist));
+    }
+
+    #[test]
+    fn test_well_posed_circuit_has_no_issues() {
+        let mut netlist = Netlist::new();
+        register(&mut netlist, 2);
+        netlist.add_device(resistor("R1", Some(0), Some(1)));
+        netlist.add_device(inductor("L1", Some(1), None, 0));
+        netlist.add_device(capacitor("C1", Some(0), None));
+        assert!(netlist.find_topology_issues().is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Forward-mode automatic differentiation with dual numbers.
+//!
+//! A device equation written over [`DualF64`] yields its value and its
+//! derivative with respect to one chosen input in a single evaluation, so
+//! conductances can never drift out of sync with the current they belong to.
+//!
+//! ```
+//! use spicier_devices::autodiff::DualF64;
+//!
+//! // d/dx (x · exp(x)) at x = 1 is 2e.
+//! let x = DualF64::variable(1.0);
+//! let y = x * x.exp();
+//! assert!((y.eps - 2.0 * std::f64::consts::E).abs() < 1e-15);
+//! ```
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A dual number `re + eps·ε` with `ε² = 0`.
+///
+/// `re` carries the value and `eps` the derivative with respect to the
+/// variable seeded by [`DualF64::variable`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DualF64 {
+    /// Value.
+    pub re: f64,
+    /// Derivative.
+    pub eps: f64,
+}
+
+impl DualF64 {
+    /// The independent variable: value `x`, derivative 1.
+    pub fn variable(x: f64) -> Self {
+        Self { re: x, eps: 1.0 }
+    }
+
+    /// A constant: value `c`, derivative 0.
+    pub fn constant(c: f64) -> Self {
+        Self { re: c, eps: 0.0 }
+    }
+
+    /// e^x.
+    pub fn exp(self) -> Self {
+        let e = self.re.exp();
+        Self {
+            re: e,
+            eps: self.eps * e,
+        }
+    }
+
+    /// Natural logarithm.
+    pub fn ln(self) -> Self {
+        Self {
+            re: self.re.ln(),
+            eps: self.eps / self.re,
+        }
+    }
+
+    /// ln(1 + x), accurate for small x.
+    pub fn ln_1p(self) -> Self {
+        Self {
+            re: self.re.ln_1p(),
+            eps: self.eps / (1.0 + self.re),
+        }
+    }
+
+    /// Square root.
+    pub fn sqrt(self) -> Self {
+        let r = self.re.sqrt();
+        Self {
+            re: r,
+            eps: self.eps / (2.0 * r),
+        }
+    }
+
+    /// x^n for a constant exponent.
+    pub fn powf(self, n: f64) -> Self {
+        Self {
+            re: self.re.powf(n),
+            eps: self.eps * n * self.re.powf(n - 1.0),
+        }
+    }
+
+    /// The larger of `self` and `other`, compared by value.
+    pub fn max(self, other: Self) -> Self {
+        if other.re > self.re { other } else { self }
+    }
+}
+
+impl From<f64> for DualF64 {
+    fn from(c: f64) -> Self {
+        Self::constant(c)
+    }
+}
+
+impl Neg for DualF64 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            re: -self.re,
+            eps: -self.eps,
+        }
+    }
+}
+
+impl Add for DualF64 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            re: self.re + rhs.re,
+            eps: self.eps + rhs.eps,
+        }
+    }
+}
+
+impl Sub for DualF64 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            re: self.re - rhs.re,
+            eps: self.eps - rhs.eps,
+        }
+    }
+}
+
+impl Mul for DualF64 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            re: self.re * rhs.re,
+            eps: self.eps * rhs.re + self.re * rhs.eps,
+        }
+    }
+}
+
+impl Div for DualF64 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self {
+            re: self.re / rhs.re,
+            eps: (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        }
+    }
+}
+
+/// Mixed dual/f64 arithmetic, treating the f64 as a constant.
+macro_rules! impl_scalar_ops {
+    ($($trait:ident, $method:ident);*) => {$(
+        impl $trait<f64> for DualF64 {
+            type Output = DualF64;
+
+            fn $method(self, rhs: f64) -> DualF64 {
+                self.$method(DualF64::constant(rhs))
+            }
+        }
+
+        impl $trait<DualF64> for f64 {
+            type Output = DualF64;
+
+            fn $method(self, rhs: DualF64) -> DualF64 {
+                DualF64::constant(self).$method(rhs)
+            }
+        }
+    )*};
+}
+
+impl_scalar_ops!(Add, add; Sub, sub; Mul, mul; Div, div);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dual_arithmetic_rules() {
+        let x = DualF64::variable(2.0);
+
+        // Product and quotient rules.
+        let y = x * x * 3.0 - 1.0 / x;
+        assert_eq!(y.re, 11.5);
+        assert_eq!(y.eps, 12.0 + 0.25);
+
+        // Chain rule through elementary functions.
+        let z = (x * 0.5).ln() + x.sqrt() + x.powf(3.0) + (x - 2.0).ln_1p();
+        let expected = 1.0 / 2.0 + 0.5 / 2f64.sqrt() + 12.0 + 1.0;
+        assert!((z.eps - expected).abs() < 1e-15);
+
+        assert_eq!(DualF64::constant(5.0).exp().eps, 0.0);
+        assert_eq!((-x).max(DualF64::constant(0.0)), DualF64::constant(0.0));
+    }
+}
@@ -23,7 +23,7 @@ use crate::expression::{EvalContext, Expr};
 use crate::stamp::Stamp;
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
-use spicier_core::netlist::{AcDeviceInfo, Stamper, TransientDeviceInfo};
+use spicier_core::netlist::{AcDeviceInfo, BoxedStamper, NodeRemap, Stamper, TransientDeviceInfo};
 use spicier_core::{Element, NodeId};
 
 /// Convert a NodeId to an MNA matrix index (None for ground).
@@ -168,6 +168,19 @@ impl Stamper for BehavioralVoltageSource {
         let voltage = self.expression.eval(&ctx);
         self.stamp_voltage(mna, voltage);
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        // Expressions refer to nodes and sources by name; only
+        // self-contained expressions can be renumbered safely.
+        if self.expression.has_voltage_or_current() {
+            return None;
+        }
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        device.branch_index = remap.branch(self.branch_index)?;
+        Some(Box::new(device))
+    }
 }
 
 /// Behavioral current source: B name n+ n- I=expression
@@ -329,6 +342,18 @@ impl Stamper for BehavioralCurrentSource {
             current,
         );
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        // Expressions refer to nodes and sources by name; only
+        // self-contained expressions can be renumbered safely.
+        if self.expression.has_voltage_or_current() {
+            return None;
+        }
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
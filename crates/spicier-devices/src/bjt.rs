@@ -1,12 +1,14 @@
-//! BJT (Bipolar Junction Transistor) device model using Ebers-Moll equations.
+//! BJT (Bipolar Junction Transistor) device model using the Gummel-Poon
+//! transport equations.
 
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 use crate::diode::thermal_voltage;
-use crate::stamp::Stamp;
+use crate::stamp::{GMIN, Stamp};
 
 /// BJT type (NPN or PNP).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -98,6 +100,36 @@ pub enum BjtRegion {
     Saturation,
 }
 
+/// Result of evaluating a BJT at a bias point.
+///
+/// Currents flow into the collector and base terminals, so a PNP in forward
+/// active has negative `ic` and `ib`. The hybrid-π conductances are the same
+/// for both polarities.
+#[derive(Debug, Clone, Copy)]
+pub struct BjtEvalResult {
+    /// Collector current (A).
+    pub ic: f64,
+    /// Base current (A).
+    pub ib: f64,
+    /// Transconductance of the transport current, ∂Ict/∂Vbe at fixed Vce (S).
+    pub gm: f64,
+    /// Output conductance of the transport current, ∂Ict/∂Vce at fixed Vbe (S).
+    pub go: f64,
+    /// Base-emitter conductance ∂Ib/∂Vbe (S).
+    pub gpi: f64,
+    /// Base-collector conductance ∂Ib/∂Vbc (S).
+    pub gmu: f64,
+    /// Operating region.
+    pub region: BjtRegion,
+}
+
+impl BjtEvalResult {
+    /// Emitter current (A), flowing out of the emitter.
+    pub fn ie(&self) -> f64 {
+        self.ic + self.ib
+    }
+}
+
 /// A BJT element.
 #[derive(Debug, Clone)]
 pub struct Bjt {
@@ -109,6 +141,8 @@ pub struct Bjt {
     pub node_base: NodeId,
     /// Emitter node.
     pub node_emitter: NodeId,
+    /// Substrate node (ground unless given).
+    pub node_substrate: NodeId,
     /// BJT type.
     pub bjt_type: BjtType,
     /// Model parameters.
@@ -123,6 +157,7 @@ impl Bjt {
             node_collector: collector,
             node_base: base,
             node_emitter: emitter,
+            node_substrate: NodeId::GROUND,
             bjt_type: BjtType::Npn,
             params: BjtParams::npn_default(),
         }
@@ -135,6 +170,7 @@ impl Bjt {
             node_collector: collector,
             node_base: base,
             node_emitter: emitter,
+            node_substrate: NodeId::GROUND,
             bjt_type: BjtType::Pnp,
             params: BjtParams::pnp_default(),
         }
@@ -154,87 +190,90 @@ impl Bjt {
             node_collector: collector,
             node_base: base,
             node_emitter: emitter,
+            node_substrate: NodeId::GROUND,
             bjt_type,
             params,
         }
     }
 
-    /// Evaluate BJT currents and small-signal parameters using Ebers-Moll model.
+    /// Set the substrate node (ground by default).
     ///
-    /// For NPN:
-    /// - Forward current: If = Is * (exp(Vbe/(Nf*Vt)) - 1)
-    /// - Reverse current: Ir = Is * (exp(Vbc/(Nr*Vt)) - 1)
-    /// - Ic = If - Ir/Br (with Early effect: multiply by (1 + Vce/Vaf))
-    /// - Ie = If/Bf - Ir
-    /// - Ib = If/Bf + Ir/Br
+    /// The substrate junction is not modeled, so the node only records the
+    /// fourth terminal of `Q c b e s model`. It is not one of
+    /// [`nodes`](Element::nodes), since nothing is stamped there; a node
+    /// that only reaches a substrate terminal is left floating.
+    pub fn with_substrate(mut self, substrate: NodeId) -> Self {
+        self.node_substrate = substrate;
+        self
+    }
+
+    /// Evaluate the BJT at junction voltages `vbe` and `vbc`.
     ///
-    /// Returns (ic, ib, ie, gm, gpi, go, region) where:
-    /// - ic, ib, ie: terminal currents
-    /// - gm: transconductance ∂Ic/∂Vbe
-    /// - gpi: input conductance ∂Ib/∂Vbe = gm/β
-    /// - go: output conductance ∂Ic/∂Vce
-    pub fn evaluate(&self, vbe: f64, vce: f64) -> (f64, f64, f64, f64, f64, f64, BjtRegion) {
-        // For PNP, flip polarities
-        let (vbe, vce, sign) = match self.bjt_type {
-            BjtType::Npn => (vbe, vce, 1.0),
-            BjtType::Pnp => (-vbe, -vce, -1.0),
-        };
+    /// Gummel-Poon transport model without high injection (IKF/IKR) or
+    /// charge storage. For an NPN:
+    /// - If = Is * (exp(Vbe/(Nf*Vt)) - 1), Ir = Is * (exp(Vbc/(Nr*Vt)) - 1)
+    /// - qb = 1 / (1 - Vbc/Vaf - Vbe/Var) (forward and reverse Early effect),
+    ///   with the divisor kept above [`MIN_EARLY_DIVISOR`]
+    /// - Ict = (If - Ir) / qb
+    /// - Ic = Ict - Ir/Br, Ib = If/Bf + Ir/Br
+    ///
+    /// A PNP is evaluated with both voltages and all currents negated.
+    pub fn evaluate(&self, vbe: f64, vbc: f64) -> BjtEvalResult {
+        let sign = self.polarity();
+        let (result, _) = self.evaluate_normalized(sign * vbe, sign * vbc);
+        BjtEvalResult {
+            ic: sign * result.ic,
+            ib: sign * result.ib,
+            ..result
+        }
+    }
 
-        let vbc = vbe - vce;
+    /// +1 for NPN, -1 for PNP.
+    fn polarity(&self) -> f64 {
+        match self.bjt_type {
+            BjtType::Npn => 1.0,
+            BjtType::Pnp => -1.0,
+        }
+    }
 
+    /// Evaluate in NPN polarity, also returning the branch currents at the
+    /// limited junction voltages.
+    fn evaluate_normalized(&self, vbe: f64, vbc: f64) -> (BjtEvalResult, BranchBias) {
+        let p = &self.params;
         let vt = thermal_voltage(300.15); // Room temperature
-        let nf_vt = self.params.nf * vt;
-        let nr_vt = self.params.nr * vt;
+        let nf_vt = p.nf * vt;
+        let nr_vt = p.nr * vt;
 
         // Apply voltage limiting to prevent exp() overflow
-        let vbe_limited = limit_voltage(vbe, nf_vt);
-        let vbc_limited = limit_voltage(vbc, nr_vt);
-
-        // Forward and reverse currents (Ebers-Moll)
-        let exp_be = (vbe_limited / nf_vt).exp();
-        let exp_bc = (vbc_limited / nr_vt).exp();
-
-        let if_current = self.params.is * (exp_be - 1.0);
-        let ir_current = self.params.is * (exp_bc - 1.0);
-
-        // Early effect factor
-        let early_factor = if self.params.vaf.is_finite() && vce > 0.0 {
-            1.0 + vce / self.params.vaf
+        let vbe = limit_voltage(vbe, nf_vt);
+        let vbc = limit_voltage(vbc, nr_vt);
+
+        let exp_be = (vbe / nf_vt).exp();
+        let exp_bc = (vbc / nr_vt).exp();
+        let if_current = p.is * (exp_be - 1.0);
+        let ir_current = p.is * (exp_bc - 1.0);
+        let gif = p.is * exp_be / nf_vt;
+        let gir = p.is * exp_bc / nr_vt;
+
+        // Base charge from the Early effect; 1/inf = 0 disables each side.
+        // Past an Early voltage the divisor would reach zero and flip the
+        // sign of the transport current, so it is clamped, which also
+        // freezes its dependence on the junction voltages.
+        let (inv_vaf, inv_var) = (1.0 / p.vaf, 1.0 / p.var);
+        let divisor = 1.0 - vbc * inv_vaf - vbe * inv_var;
+        let (inv_q1, inv_vaf, inv_var) = if divisor > MIN_EARLY_DIVISOR {
+            (divisor, inv_vaf, inv_var)
         } else {
-            1.0
+            (MIN_EARLY_DIVISOR, 0.0, 0.0)
         };
 
-        // Terminal currents
-        let ic = (if_current - ir_current / self.params.br) * early_factor;
-        let ie = if_current / self.params.bf + ir_current;
-        let ib = if_current / self.params.bf + ir_current / self.params.br;
+        // Transport current and its partials in (vbe, vbc)
+        let ict = (if_current - ir_current) * inv_q1;
+        let dict_dvbe = gif * inv_q1 - (if_current - ir_current) * inv_var;
+        let dict_dvbc = -gir * inv_q1 - (if_current - ir_current) * inv_vaf;
 
-        // Small-signal parameters
-        // gm = ∂Ic/∂Vbe ≈ Ic/Vt (in forward active)
-        let gm = if if_current > 1e-20 {
-            (self.params.is * exp_be / nf_vt) * early_factor
-        } else {
-            1e-12
-        };
-
-        // gpi = gm / BF
-        let gpi = gm / self.params.bf;
-
-        // go = ∂Ic/∂Vce includes:
-        // 1. Early effect: (if - ir/βr) * (1/Vaf)
-        // 2. Reverse current sensitivity: ∂ir/∂Vbc * ∂Vbc/∂Vce = gbc * (-1)
-        //    where gbc = is * exp(vbc/nvt) / nvt
-        // In saturation, the reverse path is active, so we need term 2
-        let go_early = if self.params.vaf.is_finite() && ic.abs() > 1e-20 {
-            ic.abs() / self.params.vaf
-        } else {
-            0.0
-        };
-        // Reverse current derivative: ∂ir/∂vce = -is*exp(vbc/nvt)/nvt
-        // Its contribution to ic: +(ir/βr contribution) since vbc = vbe - vce
-        let gbc = self.params.is * exp_bc / nr_vt;
-        let go_reverse = gbc / self.params.br * early_factor;
-        let go = (go_early + go_reverse).max(1e-12);
+        let gpi = gif / p.bf;
+        let gmu = gir / p.br;
 
         // Determine operating region
         let region = if vbe < 0.5 && vbc < 0.5 {
@@ -247,89 +286,95 @@ impl Bjt {
             BjtRegion::Saturation
         };
 
-        (
-            sign * ic,
-            sign * ib,
-            sign * ie,
-            gm.max(1e-12),
-            gpi.max(1e-12),
-            go.max(1e-12),
+        let bias = BranchBias {
+            vbe,
+            vbc,
+            ict,
+            ibe: if_current / p.bf,
+            ibc: ir_current / p.br,
+        };
+        let result = BjtEvalResult {
+            ic: bias.ict - bias.ibc,
+            ib: bias.ibe + bias.ibc,
+            // Hybrid-π: with vbc = vbe - vce, ∂/∂vbe at fixed vce picks up
+            // both partials and ∂/∂vce is minus the vbc partial.
+            gm: dict_dvbe + dict_dvbc,
+            go: -dict_dvbc,
+            gpi,
+            gmu,
             region,
-        )
+        };
+        (result, bias)
     }
 
     /// Stamp the linearized BJT model into the MNA system.
     ///
     /// The BJT is modeled using the hybrid-π equivalent circuit:
-    /// - gpi: base-emitter conductance (input)
+    /// - gpi: base-emitter conductance
+    /// - gmu: base-collector conductance
     /// - gm: voltage-controlled current source (Vbe controls Ic)
     /// - go: collector-emitter conductance (output)
     /// - Ieq terms for Newton-Raphson
     pub fn stamp_linearized_at(&self, mna: &mut MnaSystem, vbe: f64, vce: f64) {
-        // Apply voltage limiting consistently with evaluate() to ensure
-        // the equivalent current sources match the device currents.
-        let vt = crate::diode::thermal_voltage(300.15);
-        let nf_vt = self.params.nf * vt;
-
-        // For PNP, flip polarities (same as in evaluate)
-        let vbe_internal = match self.bjt_type {
-            BjtType::Npn => vbe,
-            BjtType::Pnp => -vbe,
-        };
-
-        // Limit the BE voltage to prevent overflow
-        let vbe_limited = limit_voltage(vbe_internal, nf_vt);
-
-        // Convert back to external polarity for ieq calculation
-        let vbe_for_ieq = match self.bjt_type {
-            BjtType::Npn => vbe_limited,
-            BjtType::Pnp => -vbe_limited,
-        };
-
-        let (ic, ib, _ie, gm, gpi, go, _region) = self.evaluate(vbe, vce);
+        let sign = self.polarity();
+        let (r, bias) = self.evaluate_normalized(sign * vbe, sign * (vbe - vce));
 
         let c = node_to_index(self.node_collector);
         let b = node_to_index(self.node_base);
         let e = node_to_index(self.node_emitter);
 
-        // Stamp gpi (base-emitter conductance representing base current)
-        mna.stamp_conductance(b, e, gpi);
-
-        // Stamp go (collector-emitter output conductance)
-        mna.stamp_conductance(c, e, go);
+        // Junction and output conductances, the outer two with the Gmin shunt
+        mna.stamp_conductance(b, e, r.gpi + GMIN);
+        mna.stamp_conductance(b, c, r.gmu);
+        mna.stamp_conductance(c, e, r.go + GMIN);
 
         // Stamp gm (transconductance) as VCCS: I = gm * Vbe flowing from collector to emitter
         // Collector row: +gm*Vb - gm*Ve
         // Emitter row: -gm*Vb + gm*Ve
         if let Some(ci) = c {
             if let Some(bi) = b {
-                mna.add_element(ci, bi, gm);
+                mna.add_element(ci, bi, r.gm);
             }
             if let Some(ei) = e {
-                mna.add_element(ci, ei, -gm);
+                mna.add_element(ci, ei, -r.gm);
             }
         }
         if let Some(ei) = e {
             if let Some(bi) = b {
-                mna.add_element(ei, bi, -gm);
+                mna.add_element(ei, bi, -r.gm);
             }
-            mna.add_element(ei, ei, gm);
+            mna.add_element(ei, ei, r.gm);
         }
 
-        // Equivalent current sources for linearization
-        // Use limited vbe for consistency with evaluate()
-        // Collector current: ic = gm*Vbe + go*Vce + ic_eq
-        // ic_eq = Ic - gm*Vbe - go*Vce
-        let ic_eq = ic - gm * vbe_for_ieq - go * vce;
-        mna.stamp_current_source(c, e, ic_eq);
-
-        // Base current: ib = gpi*Vbe + ib_eq
-        // ib_eq = Ib - gpi*Vbe
-        let ib_eq = ib - gpi * vbe_for_ieq;
-        mna.stamp_current_source(b, e, ib_eq);
+        // Equivalent current sources, one per branch, taken at the limited
+        // voltages evaluate() used: I = G*V + I_eq. The conductances do not
+        // depend on polarity, so a PNP only flips the sources.
+        let vce = bias.vbe - bias.vbc;
+        let ict_eq = bias.ict - r.gm * bias.vbe - r.go * vce;
+        let ibe_eq = bias.ibe - r.gpi * bias.vbe;
+        let ibc_eq = bias.ibc - r.gmu * bias.vbc;
+        mna.stamp_current_source(c, e, sign * ict_eq);
+        mna.stamp_current_source(b, e, sign * ibe_eq);
+        mna.stamp_current_source(b, c, sign * ibc_eq);
     }
 }
 
+/// Smallest value of the Early-effect divisor 1 - Vbc/Vaf - Vbe/Var, i.e.
+/// the largest base charge factor q1 is 1/MIN_EARLY_DIVISOR.
+const MIN_EARLY_DIVISOR: f64 = 1e-4;
+
+/// Branch currents of the NPN-polarity model at a limited bias point.
+struct BranchBias {
+    vbe: f64,
+    vbc: f64,
+    /// Collector-to-emitter transport current.
+    ict: f64,
+    /// Base-emitter diode current, If/Bf.
+    ibe: f64,
+    /// Base-collector diode current, Ir/Br.
+    ibc: f64,
+}
+
 /// Voltage limiting to prevent numerical overflow.
 fn limit_voltage(v: f64, nvt: f64) -> f64 {
     let vcrit = nvt * (nvt / (std::f64::consts::SQRT_2 * 1e-14)).ln();
@@ -357,8 +402,8 @@ impl Stamp for Bjt {
         let b = node_to_index(self.node_base);
         let e = node_to_index(self.node_emitter);
 
-        mna.stamp_conductance(b, e, 1e-12);
-        mna.stamp_conductance(c, e, 1e-12);
+        mna.stamp_conductance(b, e, GMIN);
+        mna.stamp_conductance(c, e, GMIN);
     }
 }
 
@@ -411,24 +456,31 @@ impl Stamper for Bjt {
         let ve = node_to_index(self.node_emitter)
             .map(|i| solution[i])
             .unwrap_or(0.0);
-        let vbe = vb - ve;
-        let vce = vc - ve;
-
-        let (_ic, _ib, _ie, gm, gpi, go, _region) = self.evaluate(vbe, vce);
+        let r = self.evaluate(vb - ve, vb - vc);
 
         AcDeviceInfo::Bjt {
             collector: node_to_index(self.node_collector),
             base: node_to_index(self.node_base),
             emitter: node_to_index(self.node_emitter),
-            gm,
-            gpi,
-            go,
+            gm: r.gm,
+            gpi: r.gpi,
+            gmu: r.gmu,
+            go: r.go,
         }
     }
 
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_collector = remap.node(self.node_collector);
+        device.node_base = remap.node(self.node_base);
+        device.node_emitter = remap.node(self.node_emitter);
+        device.node_substrate = remap.node(self.node_substrate);
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
@@ -440,11 +492,11 @@ mod tests {
         let q = Bjt::npn("Q1", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
 
         // Both junctions reverse biased: Vbe = -0.5V, Vce = 5V
-        let (ic, ib, _ie, _gm, _gpi, _go, region) = q.evaluate(-0.5, 5.0);
+        let r = q.evaluate(-0.5, -5.5);
 
-        assert_eq!(region, BjtRegion::Cutoff);
-        assert!(ic.abs() < 1e-12, "Ic should be ~0 in cutoff: {}", ic);
-        assert!(ib.abs() < 1e-12, "Ib should be ~0 in cutoff: {}", ib);
+        assert_eq!(r.region, BjtRegion::Cutoff);
+        assert!(r.ic.abs() < 1e-12, "Ic should be ~0 in cutoff: {}", r.ic);
+        assert!(r.ib.abs() < 1e-12, "Ib should be ~0 in cutoff: {}", r.ib);
     }
 
     #[test]
@@ -452,9 +504,10 @@ mod tests {
         let q = Bjt::npn("Q1", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
 
         // Forward active: Vbe = 0.7V, Vce = 5V
-        let (ic, ib, ie, gm, gpi, _go, region) = q.evaluate(0.7, 5.0);
+        let r = q.evaluate(0.7, -4.3);
+        let (ic, ib, ie, gm, gpi) = (r.ic, r.ib, r.ie(), r.gm, r.gpi);
 
-        assert_eq!(region, BjtRegion::ForwardActive);
+        assert_eq!(r.region, BjtRegion::ForwardActive);
         assert!(ic > 0.0, "Ic should be positive: {}", ic);
         assert!(ib > 0.0, "Ib should be positive: {}", ib);
         assert!(ie > 0.0, "Ie should be positive: {}", ie);
@@ -493,9 +546,9 @@ mod tests {
 
         // Saturation: both junctions forward biased
         // Vbe = 0.7V, Vce = 0.1V → Vbc = 0.7 - 0.1 = 0.6V > 0.5
-        let (_ic, _ib, _ie, _gm, _gpi, _go, region) = q.evaluate(0.7, 0.1);
+        let r = q.evaluate(0.7, 0.6);
 
-        assert_eq!(region, BjtRegion::Saturation);
+        assert_eq!(r.region, BjtRegion::Saturation);
     }
 
     #[test]
@@ -503,13 +556,19 @@ mod tests {
         let q = Bjt::pnp("Q1", NodeId::new(1), NodeId::new(2), NodeId::new(3));
 
         // For PNP: Veb = 0.7V means Vbe = -0.7V
-        // Vce = -5V (collector more negative than emitter)
-        let (ic, ib, _ie, _gm, _gpi, _go, region) = q.evaluate(-0.7, -5.0);
+        // Vce = -5V (collector more negative than emitter), so Vbc = 4.3V
+        let r = q.evaluate(-0.7, 4.3);
 
-        assert_eq!(region, BjtRegion::ForwardActive);
+        assert_eq!(r.region, BjtRegion::ForwardActive);
         // PNP currents flow in opposite direction
-        assert!(ic < 0.0, "PNP Ic should be negative: {}", ic);
-        assert!(ib < 0.0, "PNP Ib should be negative: {}", ib);
+        assert!(r.ic < 0.0, "PNP Ic should be negative: {}", r.ic);
+        assert!(r.ib < 0.0, "PNP Ib should be negative: {}", r.ib);
+
+        // Mirror image of the NPN at the negated bias
+        let npn =
+            Bjt::npn("Q2", NodeId::new(1), NodeId::new(2), NodeId::new(3)).evaluate(0.7, -4.3);
+        assert!((r.ic + npn.ic).abs() < 1e-15);
+        assert!((r.gm - npn.gm).abs() < 1e-12);
     }
 
     #[test]
@@ -517,10 +576,10 @@ mod tests {
         let q = Bjt::npn("Q1", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
 
         // Very large Vbe should not cause overflow
-        let (ic, _ib, _ie, _gm, _gpi, _go, _region) = q.evaluate(100.0, 5.0);
+        let r = q.evaluate(100.0, -5.0);
 
-        assert!(ic.is_finite(), "Ic should be finite even with large Vbe");
-        assert!(ic > 0.0, "Ic should still be positive");
+        assert!(r.ic.is_finite(), "Ic should be finite even with large Vbe");
+        assert!(r.ic > 0.0, "Ic should still be positive");
     }
 
     #[test]
@@ -537,23 +596,134 @@ mod tests {
             params,
         );
 
-        // Measure Ic at two different Vce values
-        let (ic1, _, _, _, _, go1, _) = q.evaluate(0.7, 5.0);
-        let (ic2, _, _, _, _, _, _) = q.evaluate(0.7, 10.0);
+        // Measure Ic at Vce = 5V and 10V
+        let first = q.evaluate(0.7, -4.3);
+        let (ic1, go1) = (first.ic, first.go);
+        let ic2 = q.evaluate(0.7, -9.3).ic;
 
         // Ic should increase with Vce due to Early effect
         assert!(ic2 > ic1, "Ic should increase with Vce due to Early effect");
 
-        // go = Ic / Vaf
-        let expected_go = ic1 / 100.0;
+        // In forward active Ic = If·(1 + Vcb/Vaf), so go = If/Vaf
+        let expected_go = ic1 / (100.0 + 4.3);
         assert!(
-            (go1 - expected_go).abs() / expected_go < 0.2,
+            (go1 - expected_go).abs() / expected_go < 1e-3,
             "go = {} (expected ~{})",
             go1,
             expected_go
         );
     }
 
+    #[test]
+    fn test_early_divisor_clamped() {
+        // With Var = 0.5V, Vbe = 0.7V drives 1 - Vbe/Var negative
+        let mut params = BjtParams::npn_default();
+        params.var = 0.5;
+        let q = Bjt::with_params(
+            "Q1",
+            NodeId::new(1),
+            NodeId::new(2),
+            NodeId::GROUND,
+            BjtType::Npn,
+            params,
+        );
+        let r = q.evaluate(0.7, -4.3);
+        let ifwd = r.ib * q.params.bf;
+        assert!(r.ic > 0.0, "Ic = {}", r.ic);
+        assert!((r.ic - ifwd * MIN_EARLY_DIVISOR).abs() < 1e-6 * r.ic);
+
+        // The clamped divisor no longer depends on Vce
+        assert!(r.gm.is_finite());
+        assert!(r.go.abs() < 1e-12 * r.ic, "go = {}", r.go);
+    }
+
+    #[test]
+    fn test_conductances_match_finite_differences() {
+        let mut params = BjtParams::npn_default();
+        params.is = 1e-15;
+        params.bf = 80.0;
+        params.br = 2.0;
+        params.vaf = 50.0;
+        params.var = 10.0;
+
+        for bjt_type in [BjtType::Npn, BjtType::Pnp] {
+            let q = Bjt::with_params(
+                "Q1",
+                NodeId::new(1),
+                NodeId::new(2),
+                NodeId::GROUND,
+                bjt_type,
+                params.clone(),
+            );
+            let sign = q.polarity();
+            // Forward active, saturation and reverse active
+            for (vbe, vbc) in [(0.68, -3.0), (0.7, 0.55), (-2.0, 0.65)] {
+                let (vbe, vbc) = (sign * vbe, sign * vbc);
+                let r = q.evaluate(vbe, vbc);
+                let h = 1e-7;
+                let d = |f: fn(&BjtEvalResult) -> f64, dvbe: f64, dvbc: f64| {
+                    (f(&q.evaluate(vbe + dvbe, vbc + dvbc))
+                        - f(&q.evaluate(vbe - dvbe, vbc - dvbc)))
+                        / (2.0 * h)
+                };
+                let ib = |r: &BjtEvalResult| r.ib;
+                let scale = r.gm.abs().max(r.go).max(r.gpi).max(r.gmu);
+                let close = |a: f64, b: f64| (a - b).abs() <= 1e-4 * b.abs() + 1e-7 * scale;
+                // Ib partials
+                assert!(close(d(ib, h, 0.0), r.gpi), "gpi at ({vbe}, {vbc})");
+                assert!(close(d(ib, 0.0, h), r.gmu), "gmu at ({vbe}, {vbc})");
+                // Ic = Ict - Ibc: at fixed Vce, Vbe and Vbc move together
+                // and the base-collector diode takes gmu back out
+                let ic = |r: &BjtEvalResult| r.ic;
+                assert!(close(d(ic, h, h), r.gm - r.gmu), "gm at ({vbe}, {vbc})");
+                assert!(close(d(ic, 0.0, -h), r.go + r.gmu), "go at ({vbe}, {vbc})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_linearized_stamp_reproduces_currents() {
+        let mut params = BjtParams::npn_default();
+        params.vaf = 50.0;
+        params.var = 10.0;
+        for bjt_type in [BjtType::Npn, BjtType::Pnp] {
+            // Collector node 1, base node 2, emitter node 3
+            let q = Bjt::with_params(
+                "Q1",
+                NodeId::new(1),
+                NodeId::new(2),
+                NodeId::new(3),
+                bjt_type,
+                params.clone(),
+            );
+            let sign = q.polarity();
+            let (vc, vb, ve) = (sign * 3.0, sign * 1.7, sign * 1.0);
+            let mut mna = MnaSystem::new(3, 0);
+            q.stamp_linearized_at(&mut mna, vb - ve, vc - ve);
+
+            // At the bias point, G*v - rhs is the current into each terminal
+            let v = DVector::from_vec(vec![vc, vb, ve]);
+            let into = mna.to_dense_matrix() * &v - mna.rhs();
+            let r = q.evaluate(vb - ve, vb - vc);
+            let gmin_ce = GMIN * (vc - ve);
+            let gmin_be = GMIN * (vb - ve);
+            assert!((into[0] - r.ic - gmin_ce).abs() < 1e-12 * r.ic.abs().max(1.0));
+            assert!((into[1] - r.ib - gmin_be).abs() < 1e-12 * r.ic.abs().max(1.0));
+            assert!((into[2] + r.ie() + gmin_ce + gmin_be).abs() < 1e-12 * r.ic.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_substrate_node() {
+        let q = Bjt::npn("Q1", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
+        assert_eq!(q.nodes().len(), 3);
+
+        // The substrate is recorded but not connected to anything
+        let q = q.with_substrate(NodeId::new(4));
+        assert_eq!(q.node_substrate, NodeId::new(4));
+        assert_eq!(q.nodes().len(), 3);
+    }
+
     #[test]
     fn test_ac_info_at() {
         let q = Bjt::npn("Q1", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
@@ -570,6 +740,7 @@ mod tests {
                 emitter,
                 gm,
                 gpi,
+                gmu,
                 go,
             } => {
                 assert_eq!(collector, Some(0));
@@ -577,6 +748,7 @@ mod tests {
                 assert_eq!(emitter, None);
                 assert!(gm > 0.0, "gm should be positive: {}", gm);
                 assert!(gpi > 0.0, "gpi should be positive: {}", gpi);
+                assert_eq!(gmu, q.evaluate(0.7, -4.3).gmu);
                 assert!(go > 0.0, "go should be positive: {}", go);
             }
             _ => panic!("Expected AcDeviceInfo::Bjt"),
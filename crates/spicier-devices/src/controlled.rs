@@ -2,6 +2,7 @@
 
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::AcDeviceInfo;
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 use crate::stamp::Stamp;
@@ -130,6 +131,16 @@ impl Stamper for Vcvs {
             gain: self.gain,
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.out_pos = remap.node(self.out_pos);
+        device.out_neg = remap.node(self.out_neg);
+        device.ctrl_pos = remap.node(self.ctrl_pos);
+        device.ctrl_neg = remap.node(self.ctrl_neg);
+        device.current_index = remap.branch(self.current_index)?;
+        Some(Box::new(device))
+    }
 }
 
 // ────────────────────── VCCS (G element) ──────────────────────
@@ -228,6 +239,15 @@ impl Stamper for Vccs {
             gm: self.gm,
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.out_pos = remap.node(self.out_pos);
+        device.out_neg = remap.node(self.out_neg);
+        device.ctrl_pos = remap.node(self.ctrl_pos);
+        device.ctrl_neg = remap.node(self.ctrl_neg);
+        Some(Box::new(device))
+    }
 }
 
 // ────────────────────── CCCS (F element) ──────────────────────
@@ -309,6 +329,14 @@ impl Stamper for Cccs {
             gain: self.gain,
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.out_pos = remap.node(self.out_pos);
+        device.out_neg = remap.node(self.out_neg);
+        device.vsource_branch_idx = remap.branch(self.vsource_branch_idx)?;
+        Some(Box::new(device))
+    }
 }
 
 // ────────────────────── CCVS (H element) ──────────────────────
@@ -414,6 +442,15 @@ impl Stamper for Ccvs {
             gain: self.gain,
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.out_pos = remap.node(self.out_pos);
+        device.out_neg = remap.node(self.out_neg);
+        device.vsource_branch_idx = remap.branch(self.vsource_branch_idx)?;
+        device.current_index = remap.branch(self.current_index)?;
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
@@ -3,9 +3,12 @@
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
-use crate::stamp::Stamp;
+use crate::autodiff::DualF64;
+use crate::limiting::pnjlim;
+use crate::stamp::{GMIN, Stamp};
 
 /// Diode model parameters.
 #[derive(Debug, Clone)]
@@ -22,6 +25,14 @@ pub struct DiodeParams {
     pub vj: f64,
     /// Breakdown voltage (V). Default: f64::INFINITY.
     pub bv: f64,
+    /// Reverse current at `-bv` (A). Default: 1e-3.
+    pub ibv: f64,
+    /// Bandgap energy (eV). Default: 1.11.
+    pub eg: f64,
+    /// Saturation current temperature exponent. Default: 3.0.
+    pub xti: f64,
+    /// Nominal temperature at which `is` is specified (K). Default: 300.15.
+    pub tnom: f64,
 }
 
 impl Default for DiodeParams {
@@ -33,6 +44,10 @@ impl Default for DiodeParams {
             cj0: 0.0,
             vj: 1.0,
             bv: f64::INFINITY,
+            ibv: 1e-3,
+            eg: 1.11,
+            xti: 3.0,
+            tnom: 300.15,
         }
     }
 }
@@ -55,6 +70,12 @@ pub struct Diode {
     pub node_neg: NodeId,
     /// Model parameters.
     pub params: DiodeParams,
+    /// Operating temperature (K). Default: 300.15.
+    pub temp: f64,
+    /// Device multiplier M, scaling the junction area: the saturation and
+    /// breakdown currents (and so the conductance) are multiplied by M.
+    /// Default: 1.0.
+    pub multiplier: f64,
 }
 
 impl Diode {
@@ -65,6 +86,8 @@ impl Diode {
             node_pos,
             node_neg,
             params: DiodeParams::default(),
+            temp: 300.15,
+            multiplier: 1.0,
         }
     }
 
@@ -80,29 +103,79 @@ impl Diode {
             node_pos,
             node_neg,
             params,
+            temp: 300.15,
+            multiplier: 1.0,
         }
     }
 
+    /// Set the operating temperature (K).
+    pub fn set_temperature(&mut self, temp: f64) {
+        self.temp = temp;
+    }
+
+    /// Saturation current at the operating temperature, scaled by the
+    /// multiplier.
+    ///
+    /// Is(T) = M · Is · (T/Tnom)^(XTI/N) · exp((T/Tnom − 1) · EG / (N · Vt(T)))
+    pub fn saturation_current(&self) -> f64 {
+        let p = &self.params;
+        let ratio = self.temp / p.tnom;
+        let nvt = p.n * thermal_voltage(self.temp);
+        self.multiplier * p.is * ratio.powf(p.xti / p.n) * ((ratio - 1.0) * p.eg / nvt).exp()
+    }
+
+    /// Current at `-bv`, scaled by the multiplier.
+    fn breakdown_current(&self) -> f64 {
+        self.multiplier * self.params.ibv
+    }
+
+    /// Critical voltage for the saturation current at the operating
+    /// temperature (SPICE `vcrit`), used by Newton step limiting.
+    fn critical_voltage(&self) -> f64 {
+        let nvt = self.params.n * thermal_voltage(self.temp);
+        nvt * (nvt / (std::f64::consts::SQRT_2 * self.saturation_current())).ln()
+    }
+
+    /// Voltage above which the exponential in the I-V equation is
+    /// compressed to prevent overflow.
+    ///
+    /// This is the critical voltage of a 1e-14 A junction, raised for
+    /// smaller saturation currents so that a cold junction's forward region
+    /// stays exponential.
+    fn compression_voltage(&self) -> f64 {
+        let nvt = self.params.n * thermal_voltage(self.temp);
+        let is = self.saturation_current().min(1e-14);
+        nvt * (nvt / (std::f64::consts::SQRT_2 * is)).ln()
+    }
+
     /// Evaluate diode current and conductance at a given voltage.
     ///
     /// Returns (current, conductance) where:
-    /// - current = Is * (exp(Vd / (n * Vt)) - 1)
-    /// - conductance = dI/dV = Is / (n * Vt) * exp(Vd / (n * Vt))
+    /// - current = Is * (exp(Vd / (n * Vt)) - 1) - IBV * exp(-(Vd + BV) / (n * Vt))
+    /// - conductance = dI/dV
+    ///
+    /// The breakdown term is only present when `bv` is finite; it carries
+    /// exactly `ibv` of reverse current at Vd = -BV. The current equation is
+    /// written once over [`DualF64`]; the conductance is the derivative
+    /// carried by the dual part rather than a hand-written formula.
     pub fn evaluate(&self, vd: f64) -> (f64, f64) {
-        let vt = thermal_voltage(300.15); // Room temperature (27°C)
-        let nvt = self.params.n * vt;
-
-        // Limit voltage to prevent overflow in exp()
-        let vd_limited = limit_voltage(vd, nvt);
-
-        let exp_term = (vd_limited / nvt).exp();
-        let id = self.params.is * (exp_term - 1.0);
-        let gd = self.params.is * exp_term / nvt;
+        let nvt = self.params.n * thermal_voltage(self.temp);
+        let is = self.saturation_current();
+        let vcrit = self.compression_voltage();
+        let vd = DualF64::variable(vd);
+
+        let mut id = is * (limited_exp(vd, nvt, vcrit) - 1.0);
+        if self.params.bv.is_finite() {
+            id = id - self.breakdown_current() * limited_exp(-(vd + self.params.bv), nvt, vcrit);
+        }
 
-        // Ensure minimum conductance for numerical stability
-        let gd = gd.max(1e-12);
+        (id.re, id.eps)
+    }
 
-        (id, gd)
+    /// Anode-cathode voltage in a solution vector.
+    fn junction_voltage(&self, solution: &DVector<f64>) -> f64 {
+        let v = |node| node_to_index(node).map(|i| solution[i]).unwrap_or(0.0);
+        v(self.node_pos) - v(self.node_neg)
     }
 
     /// Stamp the linearized diode model into the MNA system.
@@ -111,40 +184,33 @@ impl Diode {
     /// - A conductance Gd = dI/dV(Vd0)
     /// - A current source Ieq = Id(Vd0) - Gd * Vd0
     pub fn stamp_linearized_at(&self, mna: &mut MnaSystem, vd: f64) {
-        // Apply voltage limiting consistently: evaluate uses limited voltage
-        // internally, so we must use the same limited voltage for ieq.
-        let vt = thermal_voltage(300.15);
-        let nvt = self.params.n * vt;
-        let vd = limit_voltage(vd, nvt);
-
+        // evaluate() limits the voltage internally and returns the slope of
+        // the limited curve, so the linearization is taken at the raw vd.
         let (id, gd) = self.evaluate(vd);
         let ieq = id - gd * vd;
 
         let i = node_to_index(self.node_pos);
         let j = node_to_index(self.node_neg);
 
-        // Stamp conductance
-        mna.stamp_conductance(i, j, gd);
+        // Stamp conductance with the Gmin shunt
+        mna.stamp_conductance(i, j, gd + GMIN);
 
         // Stamp equivalent current source
         mna.stamp_current_source(i, j, ieq);
     }
 }
 
-/// Voltage limiting to prevent numerical overflow.
+/// exp(v / nvt) with voltage limiting.
 ///
-/// Limits the step in diode voltage to prevent exp() overflow
-/// while still allowing convergence.
-fn limit_voltage(vd: f64, nvt: f64) -> f64 {
-    let vcrit = nvt * (nvt / (std::f64::consts::SQRT_2 * 1e-14)).ln();
-
-    if vd > vcrit {
-        // Limit using log compression
-        let arg = (vd - vcrit) / nvt;
-        vcrit + nvt * (1.0 + arg.ln_1p())
+/// Above the critical voltage `vcrit` the exponent is log-compressed,
+/// continuous and with matching slope, which prevents overflow in exp().
+fn limited_exp(v: DualF64, nvt: f64, vcrit: f64) -> DualF64 {
+    let v = if v.re > vcrit {
+        vcrit + nvt * ((v - vcrit) / nvt).ln_1p()
     } else {
-        vd
-    }
+        v
+    };
+    (v / nvt).exp()
 }
 
 fn node_to_index(node: NodeId) -> Option<usize> {
@@ -158,10 +224,9 @@ fn node_to_index(node: NodeId) -> Option<usize> {
 impl Stamp for Diode {
     fn stamp(&self, mna: &mut MnaSystem) {
         // For initial DC guess, use Gmin shunt
-        let gmin = 1e-12;
         let i = node_to_index(self.node_pos);
         let j = node_to_index(self.node_neg);
-        mna.stamp_conductance(i, j, gmin);
+        mna.stamp_conductance(i, j, GMIN);
     }
 }
 
@@ -199,6 +264,22 @@ impl Stamper for Diode {
         self.stamp_linearized_at(mna, vd);
     }
 
+    fn stamp_nonlinear_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        state: &mut Vec<f64>,
+    ) {
+        let mut vd = self.junction_voltage(solution);
+        if let [vd_old] = state[..] {
+            let nvt = self.params.n * thermal_voltage(self.temp);
+            vd = pnjlim(vd, vd_old, nvt, self.critical_voltage());
+        }
+        state.clear();
+        state.push(vd);
+        self.stamp_linearized_at(mna, vd);
+    }
+
     fn ac_info_at(&self, solution: &DVector<f64>) -> AcDeviceInfo {
         // Extract operating point voltage from DC solution
         let vp = node_to_index(self.node_pos)
@@ -215,13 +296,24 @@ impl Stamper for Diode {
         AcDeviceInfo::Diode {
             node_pos: node_to_index(self.node_pos),
             node_neg: node_to_index(self.node_neg),
-            gd,
+            gd: gd + GMIN,
         }
     }
 
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        Some(Box::new(device))
+    }
+
+    fn set_temperature(&mut self, temp: f64) {
+        Diode::set_temperature(self, temp);
+    }
 }
 
 #[cfg(test)]
@@ -256,6 +348,112 @@ mod tests {
         assert!(id.abs() < 1e-15, "Zero-bias current should be ≈ 0: {}", id);
     }
 
+    #[test]
+    fn test_diode_breakdown() {
+        let params = DiodeParams {
+            bv: 5.1,
+            ibv: 1e-3,
+            ..Default::default()
+        };
+        let d = Diode::with_params("D1", NodeId::new(1), NodeId::GROUND, params);
+
+        // Exactly IBV of reverse current at -BV (plus the negligible Is).
+        let (id, _) = d.evaluate(-5.1);
+        assert!((id + 1e-3).abs() < 1e-9, "I(-BV) = {}", id);
+
+        // Leakage only well before breakdown, heavy conduction beyond it.
+        assert!(d.evaluate(-4.0).0.abs() < 1e-9);
+        assert!(d.evaluate(-5.3).0 < -1e-2);
+
+        // Conductance matches the numerical derivative through the knee.
+        for vd in [-5.3, -5.1, -5.0, -4.9] {
+            let h = 1e-7;
+            let numeric = (d.evaluate(vd + h).0 - d.evaluate(vd - h).0) / (2.0 * h);
+            let (_, gd) = d.evaluate(vd);
+            assert!(
+                (gd - numeric).abs() <= 1e-4 * numeric.abs() + 1e-12,
+                "gd({}) = {} vs {}",
+                vd,
+                gd,
+                numeric
+            );
+        }
+    }
+
+    /// Voltage limiting to prevent numerical overflow.
+    fn limit_voltage(vd: f64, nvt: f64, vcrit: f64) -> f64 {
+        if vd > vcrit {
+            // Limit using log compression (continuous at vcrit)
+            let arg = (vd - vcrit) / nvt;
+            vcrit + nvt * arg.ln_1p()
+        } else {
+            vd
+        }
+    }
+
+    /// The limited exponential and its hand-derived derivative.
+    fn analytic_limited_exp(v: f64, nvt: f64, vcrit: f64) -> (f64, f64) {
+        let v_limited = limit_voltage(v, nvt, vcrit);
+        let slope = if v_limited < v {
+            nvt / (nvt + v - vcrit)
+        } else {
+            1.0
+        };
+        let exp_term = (v_limited / nvt).exp();
+        (exp_term, exp_term / nvt * slope)
+    }
+
+    /// Diode current and conductance with the derivative written by hand.
+    fn analytic_evaluate(d: &Diode, vd: f64) -> (f64, f64) {
+        let nvt = d.params.n * thermal_voltage(d.temp);
+        let is = d.saturation_current();
+        let vcrit = d.compression_voltage();
+
+        let (exp_term, dexp) = analytic_limited_exp(vd, nvt, vcrit);
+        let mut id = is * (exp_term - 1.0);
+        let mut gd = is * dexp;
+        if d.params.bv.is_finite() {
+            let (exp_term, dexp) = analytic_limited_exp(-(vd + d.params.bv), nvt, vcrit);
+            id -= d.breakdown_current() * exp_term;
+            gd += d.breakdown_current() * dexp;
+        }
+        (id, gd)
+    }
+
+    #[test]
+    fn test_autodiff_matches_analytic() {
+        let params = DiodeParams {
+            bv: 5.1,
+            ..Default::default()
+        };
+        let zener = Diode::with_params("D1", NodeId::new(1), NodeId::GROUND, params);
+        let plain = Diode::new("D2", NodeId::new(1), NodeId::GROUND);
+
+        // Sweep through breakdown, reverse leakage, forward bias and the
+        // limited region above the critical voltage.
+        for d in [&zener, &plain] {
+            for i in 0..=800 {
+                let vd = -6.0 + i as f64 * 0.01;
+                let (id, gd) = analytic_evaluate(d, vd);
+                let (id_ad, gd_ad) = d.evaluate(vd);
+                assert!(
+                    (id - id_ad).abs() <= 4.0 * f64::EPSILON * id.abs(),
+                    "Id({}) = {} vs {}",
+                    vd,
+                    id,
+                    id_ad
+                );
+                assert!(
+                    (gd - gd_ad).abs() <= 8.0 * f64::EPSILON * gd.abs(),
+                    "gd({}) = {} vs {}",
+                    vd,
+                    gd,
+                    gd_ad
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_thermal_voltage() {
         let vt = thermal_voltage(300.15);
@@ -267,15 +465,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_forward_voltage_temperature_coefficient() {
+        // Solve Id(Vf) = 1mA by bisection at two temperatures.
+        let vf_at = |temp: f64| {
+            let mut d = Diode::new("D1", NodeId::new(1), NodeId::GROUND);
+            d.set_temperature(temp);
+            let (mut lo, mut hi) = (0.0, 1.0);
+            for _ in 0..100 {
+                let mid = 0.5 * (lo + hi);
+                if d.evaluate(mid).0 < 1e-3 {
+                    lo = mid
+                } else {
+                    hi = mid
+                }
+            }
+            lo
+        };
+        let tc = (vf_at(310.15) - vf_at(300.15)) / 10.0;
+        assert!((-2.5e-3..-1.5e-3).contains(&tc), "dVf/dT = {} V/K", tc);
+    }
+
+    #[test]
+    fn test_forward_voltage_tempco_over_sweep() {
+        let vf_at = |temp: f64| {
+            let mut d = Diode::new("D1", NodeId::new(1), NodeId::GROUND);
+            d.set_temperature(temp);
+            let (mut lo, mut hi) = (0.0, 1.2);
+            for _ in 0..100 {
+                let mid = 0.5 * (lo + hi);
+                if d.evaluate(mid).0 < 1e-3 {
+                    lo = mid
+                } else {
+                    hi = mid
+                }
+            }
+            lo
+        };
+
+        // -40°C to 125°C in 15°C steps: Vf falls by roughly 2mV/°C throughout.
+        let temps: Vec<f64> = (0..=11).map(|i| 233.15 + 15.0 * i as f64).collect();
+        let vf: Vec<f64> = temps.iter().map(|&t| vf_at(t)).collect();
+        for (i, pair) in vf.windows(2).enumerate() {
+            let tc = (pair[1] - pair[0]) / 15.0;
+            assert!(
+                (-2.6e-3..-1.4e-3).contains(&tc),
+                "dVf/dT at {} K = {} V/K",
+                temps[i],
+                tc
+            );
+        }
+    }
+
     #[test]
     fn test_voltage_limiting() {
         let nvt = 0.02585;
         // Very large voltage should be limited but not explode
-        let limited = limit_voltage(100.0, nvt);
+        let limited = limit_voltage(100.0, nvt, 0.7);
         assert!(limited < 100.0, "Should be limited: {}", limited);
         assert!(limited > 0.0, "Should be positive: {}", limited);
     }
 
+    #[test]
+    fn test_limited_region_is_continuous() {
+        // Newton linearizes at the raw voltage, so the compressed curve must
+        // join the exponential without a step and gd must be its slope.
+        let d = Diode::new("D1", NodeId::new(1), NodeId::GROUND);
+        let vcrit = d.compression_voltage();
+        let h = 1e-9;
+        let (below, _) = d.evaluate(vcrit - h);
+        let (above, _) = d.evaluate(vcrit + h);
+        assert!((above - below).abs() <= 1e-6 * below, "step at vcrit");
+
+        for vd in [vcrit + 0.01, 1.0, 5.0] {
+            let h = 1e-7;
+            let numeric = (d.evaluate(vd + h).0 - d.evaluate(vd - h).0) / (2.0 * h);
+            let (_, gd) = d.evaluate(vd);
+            assert!(
+                (gd - numeric).abs() <= 1e-4 * numeric,
+                "gd({}) = {} vs {}",
+                vd,
+                gd,
+                numeric
+            );
+        }
+    }
+
     #[test]
     fn test_ac_info_at_forward_bias() {
         let d = Diode::new("D1", NodeId::new(1), NodeId::GROUND);
@@ -299,4 +574,32 @@ mod tests {
             _ => panic!("Expected AcDeviceInfo::Diode"),
         }
     }
+
+    #[test]
+    fn test_stamp_nonlinear_limited_applies_pnjlim() {
+        let d = Diode::new("D1", NodeId::new(1), NodeId::GROUND);
+        let nvt = thermal_voltage(d.temp);
+        let vcrit = nvt * (nvt / (std::f64::consts::SQRT_2 * d.saturation_current())).ln();
+        let solution = DVector::from_vec(vec![40.0]);
+
+        // The first stamp of a solve has no history and is not limited.
+        let mut state = Vec::new();
+        let mut first = MnaSystem::new(1, 0);
+        d.stamp_nonlinear_limited(&mut first, &DVector::from_vec(vec![0.65]), &mut state);
+        assert_eq!(state, [0.65]);
+
+        // A step from 0.65V straight to 40V forward bias is compressed.
+        let vd = pnjlim(40.0, 0.65, nvt, vcrit);
+        assert!(vd < 1.0, "limited vd = {vd}");
+        let mut limited = MnaSystem::new(1, 0);
+        d.stamp_nonlinear_limited(&mut limited, &solution, &mut state);
+        let mut expected = MnaSystem::new(1, 0);
+        d.stamp_linearized_at(&mut expected, vd);
+        assert_eq!(limited.to_dense_matrix(), expected.to_dense_matrix());
+        assert_eq!(limited.rhs(), expected.rhs());
+
+        // The next step is limited from where the diode was linearized,
+        // not from the raw 40V iterate.
+        assert_eq!(state, [vd]);
+    }
 }
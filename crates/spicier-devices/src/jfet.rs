@@ -3,9 +3,10 @@
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
-use crate::stamp::Stamp;
+use crate::stamp::{GMIN, Stamp};
 
 /// JFET type (N-channel or P-channel).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -219,8 +220,8 @@ impl Jfet {
         let g = node_to_index(self.node_gate);
         let s = node_to_index(self.node_source);
 
-        // Stamp gds (drain-source conductance)
-        mna.stamp_conductance(d, s, gds);
+        // Stamp gds (drain-source conductance) with the Gmin shunt
+        mna.stamp_conductance(d, s, gds + GMIN);
 
         // Stamp gm (transconductance) as VCCS: I = gm * Vgs flowing from drain to source
         if let Some(di) = d {
@@ -257,7 +258,7 @@ impl Stamp for Jfet {
         // Initial stamp: Gmin shunt between drain and source for numerical stability
         let d = node_to_index(self.node_drain);
         let s = node_to_index(self.node_source);
-        mna.stamp_conductance(d, s, 1e-12);
+        mna.stamp_conductance(d, s, GMIN);
     }
 }
 
@@ -327,6 +328,14 @@ impl Stamper for Jfet {
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_drain = remap.node(self.node_drain);
+        device.node_gate = remap.node(self.node_gate);
+        device.node_source = remap.node(self.node_source);
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
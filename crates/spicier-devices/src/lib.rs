@@ -9,7 +9,10 @@
 //! - Mutual inductance: K (coupling between inductors)
 //! - Transmission lines: T (lossless, lumped LC model)
 //! - Batched device evaluation with SIMD-friendly SoA layout
+//! - Forward-mode automatic differentiation for device equations
+//! - SPICE-style junction voltage limiting for Newton iterations
 
+pub mod autodiff;
 pub mod batch;
 pub mod behavioral;
 pub mod bjt;
@@ -18,6 +21,7 @@ pub mod diode;
 pub mod error;
 pub mod expression;
 pub mod jfet;
+pub mod limiting;
 pub mod mosfet;
 pub mod mutual;
 pub mod passive;
@@ -33,7 +37,7 @@ pub use batch::{BatchMosfetType, DiodeBatch, MosfetBatch, SIMD_LANES_AVX2, round
 pub use behavioral::{BehavioralCurrentSource, BehavioralVoltageSource};
 
 // Re-export BJT
-pub use bjt::{Bjt, BjtParams, BjtRegion, BjtType};
+pub use bjt::{Bjt, BjtEvalResult, BjtParams, BjtRegion, BjtType};
 
 // Re-export controlled sources
 pub use controlled::{Cccs, Ccvs, Vccs, Vcvs};
@@ -75,7 +79,7 @@ pub use tline::TransmissionLine;
 pub use sources::{CurrentSource, VoltageSource};
 
 // Re-export stamp trait
-pub use stamp::Stamp;
+pub use stamp::{GMIN, Stamp};
 
 // Re-export waveforms
 pub use waveforms::Waveform;
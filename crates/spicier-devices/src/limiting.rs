@@ -0,0 +1,135 @@
+//! Per-iteration voltage limiting for Newton-Raphson.
+//!
+//! Linearizing a steep characteristic far from the previous iterate gives
+//! huge currents and steps that overshoot the solution, so Newton bounces
+//! or diverges. These are the SPICE3 limiting functions: each bounds how
+//! far a controlling voltage may move from `vold`, its value at the previous
+//! iteration, and the device linearizes at the limited voltage instead.
+
+/// Limit a pn-junction voltage step (SPICE `pnjlim`).
+///
+/// Above `vcrit`, a forward step of more than `2·vt` is compressed
+/// logarithmically, so the junction current grows by at most a factor of
+/// `1 + Δv/vt` per iteration instead of `exp(Δv/vt)`.
+pub fn pnjlim(vnew: f64, vold: f64, vt: f64, vcrit: f64) -> f64 {
+    if vnew > vcrit && (vnew - vold).abs() > 2.0 * vt {
+        if vold > 0.0 {
+            let arg = 1.0 + (vnew - vold) / vt;
+            if arg > 0.0 {
+                vold + vt * arg.ln()
+            } else {
+                vcrit
+            }
+        } else {
+            vt * (vnew / vt).ln()
+        }
+    } else {
+        vnew
+    }
+}
+
+/// Limit a FET gate-source voltage step relative to the threshold `vto`
+/// (SPICE `fetlim`).
+pub fn fetlim(vnew: f64, vold: f64, vto: f64) -> f64 {
+    let vtsthi = (2.0 * (vold - vto)).abs() + 2.0;
+    let vtstlo = vtsthi / 2.0 + 2.0;
+    let vtox = vto + 3.5;
+    let delv = vnew - vold;
+
+    if vold >= vto {
+        if vold >= vtox {
+            if delv <= 0.0 {
+                // Turning off
+                if vnew >= vtox {
+                    if -delv > vtstlo {
+                        return vold - vtstlo;
+                    }
+                    vnew
+                } else {
+                    vnew.max(vto + 2.0)
+                }
+            } else if delv >= vtsthi {
+                // Staying on
+                vold + vtsthi
+            } else {
+                vnew
+            }
+        } else if delv <= 0.0 {
+            // Middle region, decreasing
+            vnew.max(vto - 0.5)
+        } else {
+            // Middle region, increasing
+            vnew.min(vto + 4.0)
+        }
+    } else if delv <= 0.0 {
+        // Off and going further off
+        if -delv > vtsthi { vold - vtsthi } else { vnew }
+    } else if vnew <= vto + 0.5 {
+        // Off and turning on
+        if delv > vtstlo { vold + vtstlo } else { vnew }
+    } else {
+        vto + 0.5
+    }
+}
+
+/// Limit a FET drain-source voltage step (SPICE `limvds`).
+pub fn limvds(vnew: f64, vold: f64) -> f64 {
+    if vold >= 3.5 {
+        if vnew > vold {
+            vnew.min(3.0 * vold + 2.0)
+        } else if vnew < 3.5 {
+            vnew.max(2.0)
+        } else {
+            vnew
+        }
+    } else if vnew > vold {
+        vnew.min(4.0)
+    } else {
+        vnew.max(-0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VT: f64 = 0.025852;
+    const VCRIT: f64 = 0.7276;
+
+    #[test]
+    fn test_pnjlim_passes_small_steps() {
+        assert_eq!(pnjlim(0.65, 0.6, VT, VCRIT), 0.65);
+        assert_eq!(pnjlim(0.75, 0.74, VT, VCRIT), 0.75);
+        // Reverse bias is never limited.
+        assert_eq!(pnjlim(-20.0, 0.7, VT, VCRIT), -20.0);
+    }
+
+    #[test]
+    fn test_pnjlim_compresses_forward_steps() {
+        // From a forward-biased junction the step grows logarithmically.
+        let v = pnjlim(30.0, 0.7, VT, VCRIT);
+        assert!((v - (0.7 + VT * (1.0 + 29.3 / VT).ln())).abs() < 1e-12);
+        assert!(v < 1.0);
+
+        // From an off junction the new voltage is compressed around vt.
+        let v = pnjlim(30.0, 0.0, VT, VCRIT);
+        assert!((v - VT * (30.0 / VT).ln()).abs() < 1e-12);
+
+        // A large drop from far above vcrit lands on vcrit.
+        assert_eq!(pnjlim(5.0, 30.0, VT, VCRIT), VCRIT);
+    }
+
+    #[test]
+    fn test_fet_limits() {
+        // Turning on from cutoff stops just above threshold.
+        assert_eq!(fetlim(10.0, 0.0, 1.0), 1.5);
+        // Fully on: steps are bounded by the distance from threshold.
+        assert_eq!(fetlim(30.0, 5.0, 1.0), 5.0 + 10.0);
+        assert_eq!(fetlim(5.2, 5.0, 1.0), 5.2);
+
+        assert_eq!(limvds(20.0, 1.0), 4.0);
+        assert_eq!(limvds(20.0, 4.0), 14.0);
+        assert_eq!(limvds(-3.0, 1.0), -0.5);
+        assert_eq!(limvds(1.0, 5.0), 2.0);
+    }
+}
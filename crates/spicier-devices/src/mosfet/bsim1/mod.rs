@@ -41,11 +41,12 @@ pub use evaluate::{Bsim1EvalResult, Bsim1Region, evaluate as bsim1_evaluate};
 pub use params::Bsim1Params;
 
 use super::level1::MosfetType;
-use crate::stamp::Stamp;
+use crate::stamp::{GMIN, Stamp};
 
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 /// A BSIM1 (Level 4) MOSFET device.
@@ -136,6 +137,17 @@ impl Bsim1Mosfet {
         self.derived = Bsim1Derived::from_params(&self.params);
     }
 
+    /// Set the operating temperature (K) and update derived parameters.
+    pub fn set_temperature(&mut self, temp: f64) {
+        self.params.temp = temp;
+        self.update_derived();
+    }
+
+    /// Get the current operating temperature (K).
+    pub fn temperature(&self) -> f64 {
+        self.params.temp
+    }
+
     /// Get the MOSFET type.
     pub fn mos_type(&self) -> MosfetType {
         self.params.mos_type
@@ -174,8 +186,8 @@ impl Bsim1Mosfet {
         let gm = result.gm;
         let gmbs = result.gmbs;
 
-        // Stamp gds (drain-source conductance)
-        mna.stamp_conductance(d, s, gds);
+        // Stamp gds (drain-source conductance) with the Gmin shunt
+        mna.stamp_conductance(d, s, gds + GMIN);
 
         // Stamp gm (transconductance) as VCCS: I = gm * Vgs flowing from drain to source
         if let Some(di) = d {
@@ -228,7 +240,7 @@ impl Stamp for Bsim1Mosfet {
         // Initial stamp: Gmin shunt between drain and source
         let d = node_to_index(self.node_drain);
         let s = node_to_index(self.node_source);
-        mna.stamp_conductance(d, s, 1e-12);
+        mna.stamp_conductance(d, s, GMIN);
     }
 }
 
@@ -309,6 +321,7 @@ impl Stamper for Bsim1Mosfet {
             bulk: node_to_index(self.node_bulk),
             gds: result.gds,
             gm: result.gm,
+            saturated: result.region == Bsim1Region::Saturation,
             gmbs: result.gmbs,
         }
     }
@@ -316,6 +329,19 @@ impl Stamper for Bsim1Mosfet {
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_drain = remap.node(self.node_drain);
+        device.node_gate = remap.node(self.node_gate);
+        device.node_source = remap.node(self.node_source);
+        device.node_bulk = remap.node(self.node_bulk);
+        Some(Box::new(device))
+    }
+
+    fn set_temperature(&mut self, temp: f64) {
+        Bsim1Mosfet::set_temperature(self, temp);
+    }
 }
 
 #[cfg(test)]
@@ -434,6 +460,7 @@ mod tests {
                 bulk,
                 gds,
                 gm,
+                saturated,
                 gmbs,
             } => {
                 assert_eq!(drain, Some(0));
@@ -443,6 +470,8 @@ mod tests {
                 assert!(gm > 0.0);
                 assert!(gds > 0.0);
                 assert!(gmbs >= 0.0);
+                // Vds = Vgs puts the device in saturation
+                assert!(saturated);
             }
             _ => panic!("Expected AcDeviceInfo::Bsim1Mosfet"),
         }
@@ -37,11 +37,12 @@ pub use evaluate::{
 pub use params::Bsim3Params;
 
 use super::level1::MosfetType;
-use crate::stamp::Stamp;
+use crate::stamp::{GMIN, Stamp};
 
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 /// A BSIM3v3.3 MOSFET device.
@@ -186,8 +187,8 @@ impl Bsim3Mosfet {
         let gm = result.gm;
         let gmbs = result.gmbs;
 
-        // Stamp gds (drain-source conductance)
-        mna.stamp_conductance(d, s, gds);
+        // Stamp gds (drain-source conductance) with the Gmin shunt
+        mna.stamp_conductance(d, s, gds + GMIN);
 
         // Stamp gm (transconductance) as VCCS: I = gm * Vgs flowing from drain to source
         if let Some(di) = d {
@@ -240,7 +241,7 @@ impl Stamp for Bsim3Mosfet {
         // Initial stamp: Gmin shunt between drain and source
         let d = node_to_index(self.node_drain);
         let s = node_to_index(self.node_source);
-        mna.stamp_conductance(d, s, 1e-12);
+        mna.stamp_conductance(d, s, GMIN);
     }
 }
 
@@ -332,6 +333,7 @@ impl Stamper for Bsim3Mosfet {
             bulk: node_to_index(self.node_bulk),
             gds: result.gds,
             gm: result.gm,
+            saturated: result.region == Bsim3Region::Saturation,
             gmbs: result.gmbs,
             cgs: caps.cgs,
             cgd: caps.cgd,
@@ -344,6 +346,19 @@ impl Stamper for Bsim3Mosfet {
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_drain = remap.node(self.node_drain);
+        device.node_gate = remap.node(self.node_gate);
+        device.node_source = remap.node(self.node_source);
+        device.node_bulk = remap.node(self.node_bulk);
+        Some(Box::new(device))
+    }
+
+    fn set_temperature(&mut self, temp: f64) {
+        Bsim3Mosfet::set_temperature(self, temp);
+    }
 }
 
 #[cfg(test)]
@@ -462,6 +477,7 @@ mod tests {
                 bulk,
                 gds,
                 gm,
+                saturated,
                 gmbs,
                 cgs,
                 cgd,
@@ -476,6 +492,8 @@ mod tests {
                 assert!(gm > 0.0);
                 assert!(gds > 0.0);
                 assert!(gmbs >= 0.0);
+                // Vds = Vgs puts the device in saturation
+                assert!(saturated);
                 // Capacitances should be non-negative
                 assert!(cgs >= 0.0);
                 assert!(cgd >= 0.0);
@@ -36,11 +36,12 @@ pub use evaluate::{
 pub use params::Bsim4Params;
 
 use super::level1::MosfetType;
-use crate::stamp::Stamp;
+use crate::stamp::{GMIN, Stamp};
 
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 /// A BSIM4 MOSFET device.
@@ -165,8 +166,8 @@ impl Bsim4Mosfet {
         let gm = result.gm;
         let gmbs = result.gmbs;
 
-        // Stamp gds (drain-source conductance)
-        mna.stamp_conductance(d, s, gds);
+        // Stamp gds (drain-source conductance) with the Gmin shunt
+        mna.stamp_conductance(d, s, gds + GMIN);
 
         // Stamp gm (transconductance) as VCCS: I = gm * Vgs
         if let Some(di) = d {
@@ -219,7 +220,7 @@ impl Stamp for Bsim4Mosfet {
         // Initial stamp: Gmin shunt between drain and source
         let d = node_to_index(self.node_drain);
         let s = node_to_index(self.node_source);
-        mna.stamp_conductance(d, s, 1e-12);
+        mna.stamp_conductance(d, s, GMIN);
     }
 }
 
@@ -312,6 +313,7 @@ impl Stamper for Bsim4Mosfet {
             bulk: node_to_index(self.node_bulk),
             gds: result.gds,
             gm: result.gm,
+            saturated: result.region == Bsim4Region::Saturation,
             gmbs: result.gmbs,
             cgs: caps.cgs,
             cgd: caps.cgd,
@@ -324,6 +326,19 @@ impl Stamper for Bsim4Mosfet {
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_drain = remap.node(self.node_drain);
+        device.node_gate = remap.node(self.node_gate);
+        device.node_source = remap.node(self.node_source);
+        device.node_bulk = remap.node(self.node_bulk);
+        Some(Box::new(device))
+    }
+
+    fn set_temperature(&mut self, temp: f64) {
+        Bsim4Mosfet::set_temperature(self, temp);
+    }
 }
 
 #[cfg(test)]
@@ -6,9 +6,11 @@
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
-use crate::stamp::Stamp;
+use crate::limiting::{fetlim, limvds};
+use crate::stamp::{GMIN, Stamp};
 
 /// MOSFET type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -190,6 +192,13 @@ impl Mosfet {
         }
     }
 
+    /// Gate-source and drain-source voltages in a solution vector.
+    fn terminal_voltages(&self, solution: &DVector<f64>) -> (f64, f64) {
+        let v = |node| node_to_index(node).map(|i| solution[i]).unwrap_or(0.0);
+        let vs = v(self.node_source);
+        (v(self.node_gate) - vs, v(self.node_drain) - vs)
+    }
+
     /// Stamp the linearized MOSFET model into the MNA system.
     ///
     /// The MOSFET is linearized as:
@@ -206,8 +215,8 @@ impl Mosfet {
         let g = node_to_index(self.node_gate);
         let s = node_to_index(self.node_source);
 
-        // Stamp gds (drain-source conductance)
-        mna.stamp_conductance(d, s, gds);
+        // Stamp gds (drain-source conductance) with the Gmin shunt
+        mna.stamp_conductance(d, s, gds + GMIN);
 
         // Stamp gm (transconductance) as VCCS: I = gm * Vgs flowing from drain to source
         //
@@ -261,7 +270,7 @@ impl Stamp for Mosfet {
         // Initial stamp: Gmin shunt between drain and source
         let d = node_to_index(self.node_drain);
         let s = node_to_index(self.node_source);
-        mna.stamp_conductance(d, s, 1e-12);
+        mna.stamp_conductance(d, s, GMIN);
     }
 }
 
@@ -304,6 +313,40 @@ impl Stamper for Mosfet {
         self.stamp_linearized_at(mna, vgs, vds);
     }
 
+    fn stamp_nonlinear_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        state: &mut Vec<f64>,
+    ) {
+        // Limit in NMOS-equivalent terms, as SPICE does: fetlim on the
+        // controlling gate voltage, limvds on the drain-source voltage, with
+        // drain and source swapped when the device runs in reverse.
+        let sign = match self.mos_type {
+            MosfetType::Nmos => 1.0,
+            MosfetType::Pmos => -1.0,
+        };
+        let (vgs, vds) = self.terminal_voltages(solution);
+        let (mut vgs, mut vds) = (sign * vgs, sign * vds);
+
+        if let [vgs_old, vds_old] = state[..] {
+            let vto = self.params.vto.abs();
+            if vds_old >= 0.0 {
+                let vgd = vgs - vds;
+                vgs = fetlim(vgs, vgs_old, vto);
+                vds = limvds(vgs - vgd, vds_old);
+            } else {
+                let vgd = fetlim(vgs - vds, vgs_old - vds_old, vto);
+                vds = -limvds(vgd - vgs, -vds_old);
+                vgs = vgd + vds;
+            }
+        }
+        state.clear();
+        state.extend([vgs, vds]);
+
+        self.stamp_linearized_at(mna, sign * vgs, sign * vds);
+    }
+
     fn ac_info_at(&self, solution: &DVector<f64>) -> AcDeviceInfo {
         // Extract operating point voltages from DC solution
         let vg = node_to_index(self.node_gate)
@@ -319,7 +362,7 @@ impl Stamper for Mosfet {
         let vds = vd - vs;
 
         // Get small-signal parameters at operating point
-        let (_ids, gds, gm, _region) = self.evaluate(vgs, vds);
+        let (_ids, gds, gm, region) = self.evaluate(vgs, vds);
 
         AcDeviceInfo::Mosfet {
             drain: node_to_index(self.node_drain),
@@ -327,12 +370,21 @@ impl Stamper for Mosfet {
             source: node_to_index(self.node_source),
             gds,
             gm,
+            saturated: region == MosfetRegion::Saturation,
         }
     }
 
     fn transient_info(&self) -> TransientDeviceInfo {
         TransientDeviceInfo::None
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_drain = remap.node(self.node_drain);
+        device.node_gate = remap.node(self.node_gate);
+        device.node_source = remap.node(self.node_source);
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
@@ -437,12 +489,14 @@ mod tests {
                 source,
                 gds,
                 gm,
+                saturated,
             } => {
                 assert_eq!(drain, Some(0));
                 assert_eq!(gate, Some(1));
                 assert_eq!(source, None);
                 // In saturation, gm should be significant
                 assert!(gm > 1e-6, "gm should be positive in saturation: {}", gm);
+                assert!(saturated);
                 // gds may be small (lambda=0 by default)
                 assert!(gds >= 0.0, "gds should be non-negative: {}", gds);
             }
@@ -503,14 +557,16 @@ mod tests {
         let matrix = mna.to_dense_matrix();
         let rhs = mna.rhs();
 
-        // Verify the matrix stamps match expected VCCS + ieq pattern
+        // Verify the matrix stamps match expected VCCS + ieq pattern, with
+        // the Gmin shunt in parallel with gds
         let eps = 1e-15;
-        assert!((matrix[(0, 0)] - gds).abs() < eps, "G[0,0] wrong");
+        let g_ds = gds + GMIN;
+        assert!((matrix[(0, 0)] - g_ds).abs() < eps, "G[0,0] wrong");
         assert!((matrix[(0, 1)] - gm).abs() < eps, "G[0,1] wrong");
-        assert!((matrix[(0, 2)] - (-gds - gm)).abs() < eps, "G[0,2] wrong");
-        assert!((matrix[(2, 0)] - (-gds)).abs() < eps, "G[2,0] wrong");
+        assert!((matrix[(0, 2)] - (-g_ds - gm)).abs() < eps, "G[0,2] wrong");
+        assert!((matrix[(2, 0)] - (-g_ds)).abs() < eps, "G[2,0] wrong");
         assert!((matrix[(2, 1)] - (-gm)).abs() < eps, "G[2,1] wrong");
-        assert!((matrix[(2, 2)] - (gds + gm)).abs() < eps, "G[2,2] wrong");
+        assert!((matrix[(2, 2)] - (g_ds + gm)).abs() < eps, "G[2,2] wrong");
         assert!((rhs[0] - (-ieq)).abs() < eps, "RHS[0] wrong");
         assert!((rhs[2] - ieq).abs() < eps, "RHS[2] wrong");
     }
@@ -619,4 +675,31 @@ mod tests {
         assert!((matrix[(0, 2)] - (-gds - gm)).abs() < eps, "G[0,2] wrong");
         assert!((mna.rhs()[0] - (-ieq)).abs() < eps, "RHS[0] wrong");
     }
+
+    #[test]
+    fn test_stamp_nonlinear_limited_bounds_steps() {
+        let nmos = Mosfet::nmos("M1", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
+        let solution = DVector::from_vec(vec![20.0, 10.0]);
+
+        // From cutoff (vgs = 0, vds = 1), vgs stops just above threshold and
+        // vds moves by at most limvds's bound.
+        let mut state = vec![0.0, 1.0];
+        let mut limited = MnaSystem::new(2, 0);
+        nmos.stamp_nonlinear_limited(&mut limited, &solution, &mut state);
+        let mut expected = MnaSystem::new(2, 0);
+        nmos.stamp_linearized_at(&mut expected, 0.7 + 0.5, 4.0);
+        assert_eq!(limited.to_dense_matrix(), expected.to_dense_matrix());
+        assert_eq!(limited.rhs(), expected.rhs());
+        assert_eq!(state, [0.7 + 0.5, 4.0]);
+
+        // PMOS limits in the mirrored polarity.
+        let pmos = Mosfet::pmos("M2", NodeId::new(1), NodeId::new(2), NodeId::GROUND);
+        let mut state = vec![0.0, 1.0];
+        let mut limited = MnaSystem::new(2, 0);
+        pmos.stamp_nonlinear_limited(&mut limited, &(-&solution), &mut state);
+        let mut expected = MnaSystem::new(2, 0);
+        pmos.stamp_linearized_at(&mut expected, -(0.7 + 0.5), -4.0);
+        assert_eq!(limited.to_dense_matrix(), expected.to_dense_matrix());
+        assert_eq!(limited.rhs(), expected.rhs());
+    }
 }
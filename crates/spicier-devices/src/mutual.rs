@@ -7,6 +7,7 @@
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 use crate::stamp::Stamp;
@@ -165,6 +166,19 @@ impl Stamper for MutualInductance {
             TransientDeviceInfo::None
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.l1_branch_idx = match self.l1_branch_idx {
+            Some(idx) => Some(remap.branch(idx)?),
+            None => None,
+        };
+        device.l2_branch_idx = match self.l2_branch_idx {
+            Some(idx) => Some(remap.branch(idx)?),
+            None => None,
+        };
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@
 
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 use crate::stamp::Stamp;
@@ -16,6 +17,9 @@ fn node_to_index(node: NodeId) -> Option<usize> {
 }
 
 /// A resistor element.
+///
+/// The resistance is scaled with temperature as
+/// `R(T) = R · (1 + TC1·ΔT + TC2·ΔT²)`, where `ΔT` is measured from 27°C.
 #[derive(Debug, Clone)]
 pub struct Resistor {
     /// Device name (e.g., "R1").
@@ -24,8 +28,16 @@ pub struct Resistor {
     pub node_pos: NodeId,
     /// Negative terminal node.
     pub node_neg: NodeId,
-    /// Resistance value in ohms.
+    /// Resistance value in ohms at the nominal temperature.
     pub resistance: f64,
+    /// Device multiplier M: the number of identical resistors in parallel.
+    pub multiplier: f64,
+    /// First-order temperature coefficient (1/°C). Default: 0.0.
+    pub tc1: f64,
+    /// Second-order temperature coefficient (1/°C²). Default: 0.0.
+    pub tc2: f64,
+    /// Operating temperature (K). Default: 300.15.
+    pub temp: f64,
 }
 
 impl Resistor {
@@ -41,12 +53,22 @@ impl Resistor {
             node_pos,
             node_neg,
             resistance,
+            multiplier: 1.0,
+            tc1: 0.0,
+            tc2: 0.0,
+            temp: 300.15,
         }
     }
 
-    /// Get the conductance (1/R).
+    /// Resistance at the operating temperature.
+    pub fn resistance_at_temp(&self) -> f64 {
+        let dt = self.temp - 300.15;
+        self.resistance * (1.0 + self.tc1 * dt + self.tc2 * dt * dt)
+    }
+
+    /// Get the conductance (M/R) at the operating temperature.
     pub fn conductance(&self) -> f64 {
-        1.0 / self.resistance
+        self.multiplier / self.resistance_at_temp()
     }
 }
 
@@ -84,6 +106,30 @@ impl Stamper for Resistor {
             conductance: self.conductance(),
         }
     }
+
+    fn is_ideal_short(&self) -> bool {
+        self.resistance == 0.0
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        Some(Box::new(device))
+    }
+
+    fn set_temperature(&mut self, temp: f64) {
+        self.temp = temp;
+    }
+
+    fn value(&self) -> Option<f64> {
+        Some(self.resistance)
+    }
+
+    fn set_value(&mut self, value: f64) -> bool {
+        self.resistance = value;
+        true
+    }
 }
 
 /// Capacitor model parameters for `.MODEL` definitions.
@@ -181,6 +227,8 @@ pub struct Capacitor {
     pub capacitance: f64,
     /// Optional model parameters (for model-based capacitors).
     pub params: Option<CapacitorParams>,
+    /// Device multiplier M: the number of identical capacitors in parallel.
+    pub multiplier: f64,
 }
 
 impl Capacitor {
@@ -197,6 +245,7 @@ impl Capacitor {
             node_neg,
             capacitance,
             params: None,
+            multiplier: 1.0,
         }
     }
 
@@ -214,18 +263,21 @@ impl Capacitor {
             node_neg,
             capacitance,
             params: Some(params),
+            multiplier: 1.0,
         }
     }
 
     /// Get the effective capacitance at given voltage and temperature.
     ///
     /// For fixed-value capacitors, returns the constant capacitance.
-    /// For model-based capacitors, evaluates C(V,T).
+    /// For model-based capacitors, evaluates C(V,T). Both are scaled by
+    /// the multiplier.
     pub fn effective_capacitance(&self, voltage: f64, temp: f64) -> f64 {
-        match &self.params {
+        let c = match &self.params {
             Some(p) => p.capacitance_at(voltage, temp),
             None => self.capacitance,
-        }
+        };
+        self.multiplier * c
     }
 }
 
@@ -239,7 +291,7 @@ impl Stamp for Capacitor {
             if p.rp > 0.0 {
                 let i = node_to_index(self.node_pos);
                 let j = node_to_index(self.node_neg);
-                mna.stamp_conductance(i, j, 1.0 / p.rp);
+                mna.stamp_conductance(i, j, self.multiplier / p.rp);
             }
         }
     }
@@ -268,7 +320,7 @@ impl Stamper for Capacitor {
         AcDeviceInfo::Capacitor {
             node_pos: node_to_index(self.node_pos),
             node_neg: node_to_index(self.node_neg),
-            capacitance: self.capacitance,
+            capacitance: self.multiplier * self.capacitance,
         }
     }
 
@@ -276,8 +328,28 @@ impl Stamper for Capacitor {
         TransientDeviceInfo::Capacitor {
             node_pos: node_to_index(self.node_pos),
             node_neg: node_to_index(self.node_neg),
-            capacitance: self.capacitance,
+            capacitance: self.multiplier * self.capacitance,
+        }
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        Some(Box::new(device))
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.params.is_none().then_some(self.capacitance)
+    }
+
+    fn set_value(&mut self, value: f64) -> bool {
+        // Model-based capacitance comes from the model card, not one value.
+        if self.params.is_some() {
+            return false;
         }
+        self.capacitance = value;
+        true
     }
 }
 
@@ -294,6 +366,8 @@ pub struct Inductor {
     pub inductance: f64,
     /// Index of the current variable for this inductor.
     pub current_index: usize,
+    /// Device multiplier M: the number of identical inductors in parallel.
+    pub multiplier: f64,
 }
 
 impl Inductor {
@@ -311,8 +385,14 @@ impl Inductor {
             node_neg,
             inductance,
             current_index,
+            multiplier: 1.0,
         }
     }
+
+    /// Effective inductance L/M of the parallel combination.
+    pub fn effective_inductance(&self) -> f64 {
+        self.inductance / self.multiplier
+    }
 }
 
 impl Stamp for Inductor {
@@ -359,7 +439,7 @@ impl Stamper for Inductor {
         AcDeviceInfo::Inductor {
             node_pos: node_to_index(self.node_pos),
             node_neg: node_to_index(self.node_neg),
-            inductance: self.inductance,
+            inductance: self.effective_inductance(),
             branch_idx: self.current_index,
         }
     }
@@ -368,10 +448,27 @@ impl Stamper for Inductor {
         TransientDeviceInfo::Inductor {
             node_pos: node_to_index(self.node_pos),
             node_neg: node_to_index(self.node_neg),
-            inductance: self.inductance,
+            inductance: self.effective_inductance(),
             branch_index: self.current_index,
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        device.current_index = remap.branch(self.current_index)?;
+        Some(Box::new(device))
+    }
+
+    fn value(&self) -> Option<f64> {
+        Some(self.inductance)
+    }
+
+    fn set_value(&mut self, value: f64) -> bool {
+        self.inductance = value;
+        true
+    }
 }
 
 #[cfg(test)]
@@ -393,6 +490,57 @@ mod tests {
         assert!((matrix[(1, 0)] + g).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_negative_resistor_stamp() {
+        let mut mna = MnaSystem::new(2, 0);
+        let r = Resistor::new("RN", NodeId::new(1), NodeId::new(2), -2000.0);
+
+        Stamp::stamp(&r, &mut mna);
+        let matrix = mna.to_dense_matrix();
+
+        // A negative resistor is a negative conductance, signs and all.
+        let g = -0.0005;
+        assert!((matrix[(0, 0)] - g).abs() < 1e-12);
+        assert!((matrix[(1, 1)] - g).abs() < 1e-12);
+        assert!((matrix[(0, 1)] + g).abs() < 1e-12);
+        assert!((matrix[(1, 0)] + g).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_multiplier_scales_passives() {
+        let mut r = Resistor::new("R1", NodeId::new(1), NodeId::GROUND, 1000.0);
+        r.multiplier = 2.0;
+        assert!((r.conductance() - 2e-3).abs() < 1e-15);
+
+        let mut c = Capacitor::new("C1", NodeId::new(1), NodeId::GROUND, 1e-12);
+        c.multiplier = 3.0;
+        assert!((c.effective_capacitance(0.0, 300.15) - 3e-12).abs() < 1e-24);
+        match Stamper::transient_info(&c) {
+            TransientDeviceInfo::Capacitor { capacitance, .. } => {
+                assert!((capacitance - 3e-12).abs() < 1e-24)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let mut l = Inductor::new("L1", NodeId::new(1), NodeId::GROUND, 1e-3, 0);
+        l.multiplier = 4.0;
+        assert!((l.effective_inductance() - 0.25e-3).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_resistor_temperature_coefficients() {
+        let mut r = Resistor::new("R1", NodeId::new(1), NodeId::GROUND, 1000.0);
+        r.tc1 = 1e-3;
+        r.tc2 = 1e-6;
+        assert!((r.resistance_at_temp() - 1000.0).abs() < 1e-12);
+
+        // 127°C: ΔT = 100, R = 1k · (1 + 0.1 + 0.01)
+        Stamper::set_temperature(&mut r, 400.15);
+        assert!((r.resistance_at_temp() - 1110.0).abs() < 1e-9);
+        assert!((r.conductance() - 1.0 / 1110.0).abs() < 1e-15);
+        assert_eq!(Stamper::value(&r), Some(1000.0));
+    }
+
     #[test]
     fn test_resistor_to_ground() {
         let mut mna = MnaSystem::new(1, 0);
@@ -3,6 +3,7 @@
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::AcDeviceInfo;
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 use crate::stamp::Stamp;
@@ -32,6 +33,10 @@ pub struct VoltageSource {
     pub current_index: usize,
     /// Optional time-varying waveform for transient analysis.
     pub waveform: Option<Waveform>,
+    /// AC stimulus magnitude from the netlist's `AC` specification.
+    ///
+    /// Sources without one still drive AC analysis with magnitude 1.
+    pub ac_magnitude: Option<f64>,
 }
 
 impl VoltageSource {
@@ -50,6 +55,7 @@ impl VoltageSource {
             voltage,
             current_index,
             waveform: None,
+            ac_magnitude: None,
         }
     }
 
@@ -69,6 +75,7 @@ impl VoltageSource {
             voltage,
             current_index,
             waveform: Some(waveform),
+            ac_magnitude: None,
         }
     }
 
@@ -91,6 +98,17 @@ impl VoltageSource {
     pub fn is_time_varying(&self) -> bool {
         self.waveform.is_some()
     }
+
+    /// Stamp the source with its waveform value at `time`.
+    ///
+    /// Convenience for hand-written solver `TransientStamper`s, which receive
+    /// the time in `stamp_at_time` and would otherwise evaluate the waveform
+    /// themselves.
+    pub fn stamp_transient_at(&self, mna: &mut MnaSystem, time: f64) {
+        let i = node_to_index(self.node_pos);
+        let j = node_to_index(self.node_neg);
+        mna.stamp_voltage_source(i, j, self.current_index, self.value_at(time));
+    }
 }
 
 impl Stamp for VoltageSource {
@@ -137,7 +155,7 @@ impl Stamper for VoltageSource {
             node_pos: node_to_index(self.node_pos),
             node_neg: node_to_index(self.node_neg),
             branch_idx: self.current_index,
-            ac_mag: 1.0, // Default AC magnitude
+            ac_mag: self.ac_magnitude.unwrap_or(1.0),
         }
     }
 
@@ -157,10 +175,36 @@ impl Stamper for VoltageSource {
     }
 
     fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
-        let i = node_to_index(self.node_pos);
-        let j = node_to_index(self.node_neg);
-        let value = self.value_at(time);
-        mna.stamp_voltage_source(i, j, self.current_index, value);
+        self.stamp_transient_at(mna, time);
+    }
+
+    fn breakpoints(&self, tstop: f64) -> Vec<f64> {
+        self.waveform
+            .as_ref()
+            .map(|w| w.breakpoints(tstop))
+            .unwrap_or_default()
+    }
+
+    fn is_ideal_short(&self) -> bool {
+        // An explicit AC spec makes a 0V source the AC input, not a wire
+        self.voltage == 0.0 && self.waveform.is_none() && self.ac_magnitude.is_none()
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        device.current_index = remap.branch(self.current_index)?;
+        Some(Box::new(device))
+    }
+
+    fn value(&self) -> Option<f64> {
+        Some(self.voltage)
+    }
+
+    fn set_value(&mut self, value: f64) -> bool {
+        self.voltage = value;
+        true
     }
 }
 
@@ -177,6 +221,8 @@ pub struct CurrentSource {
     pub current: f64,
     /// Optional time-varying waveform for transient analysis.
     pub waveform: Option<Waveform>,
+    /// Device multiplier M: the number of identical sources in parallel.
+    pub multiplier: f64,
 }
 
 impl CurrentSource {
@@ -188,6 +234,7 @@ impl CurrentSource {
             node_neg,
             current,
             waveform: None,
+            multiplier: 1.0,
         }
     }
 
@@ -205,20 +252,24 @@ impl CurrentSource {
             node_neg,
             current,
             waveform: Some(waveform),
+            multiplier: 1.0,
         }
     }
 
-    /// Get the DC value (for operating point calculation).
+    /// Get the DC value (for operating point calculation), scaled by the
+    /// multiplier.
     pub fn dc_value(&self) -> f64 {
-        self.current
+        self.multiplier * self.current
     }
 
-    /// Get the value at a specific time (for transient analysis).
+    /// Get the value at a specific time (for transient analysis), scaled by
+    /// the multiplier.
     pub fn value_at(&self, time: f64) -> f64 {
-        match &self.waveform {
+        let value = match &self.waveform {
             Some(w) => w.value_at(time),
             None => self.current,
-        }
+        };
+        self.multiplier * value
     }
 
     /// Check if this source has a time-varying waveform.
@@ -232,7 +283,7 @@ impl Stamp for CurrentSource {
         // Current flows from node_pos to node_neg (out of pos, into neg)
         let i = node_to_index(self.node_pos);
         let j = node_to_index(self.node_neg);
-        mna.stamp_current_source(i, j, self.current);
+        mna.stamp_current_source(i, j, self.dc_value());
     }
 }
 
@@ -275,7 +326,7 @@ impl Stamper for CurrentSource {
     ) {
         let i = node_to_index(self.node_pos);
         let j = node_to_index(self.node_neg);
-        mna.stamp_current_source(i, j, self.current * source_factor);
+        mna.stamp_current_source(i, j, self.dc_value() * source_factor);
     }
 
     fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
@@ -284,6 +335,29 @@ impl Stamper for CurrentSource {
         let value = self.value_at(time);
         mna.stamp_current_source(i, j, value);
     }
+
+    fn breakpoints(&self, tstop: f64) -> Vec<f64> {
+        self.waveform
+            .as_ref()
+            .map(|w| w.breakpoints(tstop))
+            .unwrap_or_default()
+    }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.node_pos = remap.node(self.node_pos);
+        device.node_neg = remap.node(self.node_neg);
+        Some(Box::new(device))
+    }
+
+    fn value(&self) -> Option<f64> {
+        Some(self.current)
+    }
+
+    fn set_value(&mut self, value: f64) -> bool {
+        self.current = value;
+        true
+    }
 }
 
 #[cfg(test)]
@@ -305,6 +379,19 @@ mod tests {
         assert_eq!(mna.rhs()[2], 5.0);
     }
 
+    #[test]
+    fn test_voltage_source_stamp_transient_at() {
+        let waveform = Waveform::pulse(0.0, 5.0, 1e-3, 1e-3, 1e-3, 2e-3, 0.0);
+        let v = VoltageSource::with_waveform("V1", NodeId::new(1), NodeId::GROUND, waveform, 0);
+
+        for (time, expected) in [(0.0, 0.0), (1.5e-3, 2.5), (3e-3, 5.0)] {
+            let mut mna = MnaSystem::new(1, 1);
+            v.stamp_transient_at(&mut mna, time);
+            assert_eq!(mna.rhs()[1], expected, "t={time}");
+            assert_eq!(mna.to_dense_matrix()[(0, 1)], 1.0);
+        }
+    }
+
     #[test]
     fn test_current_source_stamp() {
         let mut mna = MnaSystem::new(2, 0);
@@ -2,6 +2,13 @@
 
 use spicier_core::mna::MnaSystem;
 
+/// Minimum conductance shunted across every nonlinear device's junctions.
+///
+/// Devices add it in both their initial [`Stamp::stamp`] and their
+/// linearized Newton stamp, so a node reached only through cut-off devices
+/// still has a path and the MNA matrix stays nonsingular.
+pub const GMIN: f64 = 1e-12;
+
 /// Trait for devices that can stamp into an MNA matrix.
 pub trait Stamp {
     /// Stamp this device's contribution into the MNA system.
@@ -11,9 +11,13 @@
 //! - C_section = TD / (Z0 × N)
 //!
 //! The lumped model is accurate up to frequency f_max where N ≥ 10 × TD × f_max.
+//! It is used for DC and AC analysis; transient analysis uses the exact
+//! Branin model instead, which delays the waves travelling between the ports
+//! by TD.
 
 use spicier_core::mna::MnaSystem;
 use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::netlist::{BoxedStamper, NodeRemap};
 use spicier_core::{Element, NodeId, Stamper};
 
 use crate::stamp::Stamp;
@@ -39,8 +43,9 @@ const MIN_SECTIONS: usize = 2;
 /// positive and negative terminals. It is characterized by its characteristic
 /// impedance Z0 and propagation delay TD.
 ///
-/// Internally, it is modeled as a cascade of LC pi-sections for DC, AC, and
-/// transient analysis.
+/// Internally, it is modeled as a cascade of LC pi-sections for DC and AC
+/// analysis. In transient analysis the internal nodes follow the line
+/// voltage at their position.
 #[derive(Debug, Clone)]
 pub struct TransmissionLine {
     /// Device name (e.g., "T1").
@@ -287,6 +292,24 @@ impl Stamper for TransmissionLine {
             current_base_index: self.current_base_index,
         }
     }
+
+    fn remapped(&self, remap: &NodeRemap) -> Option<BoxedStamper> {
+        let mut device = self.clone();
+        device.port1_pos = remap.node(self.port1_pos);
+        device.port1_neg = remap.node(self.port1_neg);
+        device.port2_pos = remap.node(self.port2_pos);
+        device.port2_neg = remap.node(self.port2_neg);
+        device.internal_nodes = self.internal_nodes.iter().map(|&n| remap.node(n)).collect();
+
+        // Section currents must stay contiguous after renumbering
+        device.current_base_index = remap.branch(self.current_base_index)?;
+        for k in 1..self.num_sections {
+            if remap.branch(self.current_base_index + k)? != device.current_base_index + k {
+                return None;
+            }
+        }
+        Some(Box::new(device))
+    }
 }
 
 #[cfg(test)]
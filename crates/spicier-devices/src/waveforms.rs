@@ -39,6 +39,9 @@ pub enum Waveform {
     /// - TD: Delay time (before sinusoid starts)
     /// - THETA: Damping factor (1/s), 0 for undamped
     /// - PHASE: Phase in degrees
+    ///
+    /// As in ngspice, the phase applies during the delay too: for `t <= TD`
+    /// the value is `VO + VA·sin(PHASE)`.
     Sin {
         vo: f64,
         va: f64,
@@ -48,12 +51,81 @@ pub enum Waveform {
         phase: f64,
     },
 
-    /// Piecewise linear waveform: PWL(T1 V1 T2 V2 ...)
+    /// Exponential waveform: EXP(V1 V2 TD1 TAU1 TD2 TAU2)
+    ///
+    /// - V1: Initial value
+    /// - V2: Pulsed value
+    /// - TD1: Rise delay time
+    /// - TAU1: Rise time constant
+    /// - TD2: Fall delay time
+    /// - TAU2: Fall time constant
+    ///
+    /// Rises from V1 toward V2 starting at TD1, then decays back toward V1
+    /// starting at TD2.
+    Exp {
+        v1: f64,
+        v2: f64,
+        td1: f64,
+        tau1: f64,
+        td2: f64,
+        tau2: f64,
+    },
+
+    /// Single-frequency FM waveform: SFFM(VO VA FC MDI FS PHASEC PHASES)
+    ///
+    /// - VO: DC offset
+    /// - VA: Amplitude
+    /// - FC: Carrier frequency in Hz
+    /// - MDI: Modulation index
+    /// - FS: Signal frequency in Hz
+    /// - PHASEC: Carrier phase in degrees
+    /// - PHASES: Signal phase in degrees
+    ///
+    /// `VO + VA·sin(2π·FC·t + PHASEC + MDI·sin(2π·FS·t + PHASES))`
+    Sffm {
+        vo: f64,
+        va: f64,
+        fc: f64,
+        mdi: f64,
+        fs: f64,
+        phasec: f64,
+        phases: f64,
+    },
+
+    /// Amplitude-modulated waveform: AM(VA VO MF FC TD PHASEM PHASEC)
     ///
-    /// Linear interpolation between specified (time, value) points.
+    /// - VA: Amplitude
+    /// - VO: Modulation offset
+    /// - MF: Modulating frequency in Hz
+    /// - FC: Carrier frequency in Hz
+    /// - TD: Delay time (zero output before it)
+    /// - PHASEM: Modulation phase in degrees
+    /// - PHASEC: Carrier phase in degrees
+    ///
+    /// `VA·(VO + sin(2π·MF·t' + PHASEM))·sin(2π·FC·t' + PHASEC)` with
+    /// `t' = t - TD`.
+    Am {
+        va: f64,
+        vo: f64,
+        mf: f64,
+        fc: f64,
+        td: f64,
+        phasem: f64,
+        phasec: f64,
+    },
+
+    /// Piecewise linear waveform: PWL(T1 V1 T2 V2 ...) [R=TR]
+    ///
+    /// Linear interpolation between specified (time, value) points, holding
+    /// the first value before the first point. Two points at the same time
+    /// make a step; as with PULSE edges, the step time itself still reads the
+    /// old level. After the last point the value is held, or with `R=TR` the
+    /// section from TR to the last point repeats indefinitely.
     Pwl {
         /// Time-value pairs, sorted by time.
         points: Vec<(f64, f64)>,
+        /// Start of the repeated section (`R=`), if any.
+        repeat_from: Option<f64>,
     },
 }
 
@@ -112,9 +184,61 @@ impl Waveform {
         }
     }
 
+    /// Create an exponential waveform.
+    pub fn exp(v1: f64, v2: f64, td1: f64, tau1: f64, td2: f64, tau2: f64) -> Self {
+        Waveform::Exp {
+            v1,
+            v2,
+            td1,
+            tau1,
+            td2,
+            tau2,
+        }
+    }
+
+    /// Create a single-frequency FM waveform.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sffm(vo: f64, va: f64, fc: f64, mdi: f64, fs: f64, phasec: f64, phases: f64) -> Self {
+        Waveform::Sffm {
+            vo,
+            va,
+            fc,
+            mdi,
+            fs,
+            phasec,
+            phases,
+        }
+    }
+
+    /// Create an amplitude-modulated waveform.
+    #[allow(clippy::too_many_arguments)]
+    pub fn am(va: f64, vo: f64, mf: f64, fc: f64, td: f64, phasem: f64, phasec: f64) -> Self {
+        Waveform::Am {
+            va,
+            vo,
+            mf,
+            fc,
+            td,
+            phasem,
+            phasec,
+        }
+    }
+
     /// Create a piecewise linear waveform.
     pub fn pwl(points: Vec<(f64, f64)>) -> Self {
-        Waveform::Pwl { points }
+        Waveform::Pwl {
+            points,
+            repeat_from: None,
+        }
+    }
+
+    /// Create a piecewise linear waveform that repeats from `repeat_from`
+    /// to its last point (`PWL(...) R=repeat_from`).
+    pub fn pwl_repeat(points: Vec<(f64, f64)>, repeat_from: f64) -> Self {
+        Waveform::Pwl {
+            points,
+            repeat_from: Some(repeat_from),
+        }
     }
 
     /// Evaluate the waveform at a given time.
@@ -138,20 +262,112 @@ impl Waveform {
                 theta,
                 phase,
             } => eval_sin(*vo, *va, *freq, *td, *theta, *phase, time),
-            Waveform::Pwl { points } => eval_pwl(points, time),
+            Waveform::Exp {
+                v1,
+                v2,
+                td1,
+                tau1,
+                td2,
+                tau2,
+            } => eval_exp(*v1, *v2, *td1, *tau1, *td2, *tau2, time),
+            Waveform::Sffm {
+                vo,
+                va,
+                fc,
+                mdi,
+                fs,
+                phasec,
+                phases,
+            } => eval_sffm(*vo, *va, *fc, *mdi, *fs, *phasec, *phases, time),
+            Waveform::Am {
+                va,
+                vo,
+                mf,
+                fc,
+                td,
+                phasem,
+                phasec,
+            } => eval_am(*va, *vo, *mf, *fc, *td, *phasem, *phasec, time),
+            Waveform::Pwl {
+                points,
+                repeat_from,
+            } => eval_pwl(points, *repeat_from, time),
         }
     }
 
     /// Get the DC value (for operating point calculation).
     ///
-    /// For PULSE, returns V1. For SIN, returns VO. For PWL, returns the first value.
+    /// For PULSE, returns V1. For PWL, returns the first value. The others
+    /// return their value at t = 0, so the transient starts continuously
+    /// from the operating point (e.g. `VO + VA·sin(PHASE)` for SIN).
     pub fn dc_value(&self) -> f64 {
         match self {
             Waveform::Dc(v) => *v,
             Waveform::Pulse { v1, .. } => *v1,
-            Waveform::Sin { vo, .. } => *vo,
-            Waveform::Pwl { points } => points.first().map(|(_, v)| *v).unwrap_or(0.0),
+            Waveform::Pwl { points, .. } => points.first().map(|(_, v)| *v).unwrap_or(0.0),
+            Waveform::Sin { .. }
+            | Waveform::Exp { .. }
+            | Waveform::Sffm { .. }
+            | Waveform::Am { .. } => self.value_at(0.0),
+        }
+    }
+
+    /// Corner times before `tstop`: the edges of every PULSE period and
+    /// every PWL point, including repetitions.
+    ///
+    /// The transient solver steps exactly onto these so that no corner
+    /// falls between timepoints. Smooth waveforms have none.
+    pub fn breakpoints(&self, tstop: f64) -> Vec<f64> {
+        let mut times = Vec::new();
+        match self {
+            Waveform::Pulse {
+                td,
+                tr,
+                pw,
+                tf,
+                per,
+                ..
+            } => {
+                let corners = [0.0, *tr, tr + pw, tr + pw + tf];
+                let mut start = *td;
+                while start < tstop {
+                    times.extend(corners.iter().map(|c| start + c));
+                    if *per <= 0.0 {
+                        break;
+                    }
+                    start += per;
+                }
+            }
+            Waveform::Pwl {
+                points,
+                repeat_from,
+            } => {
+                times.extend(points.iter().map(|&(t, _)| t));
+                if let (Some(tr), Some(&(t_last, _))) = (repeat_from, points.last()) {
+                    if *tr < t_last {
+                        // Each repetition replays the points after TR
+                        let period = t_last - tr;
+                        let mut shift = period;
+                        while tr + shift < tstop {
+                            times.extend(
+                                points
+                                    .iter()
+                                    .filter(|&&(t, _)| t > *tr)
+                                    .map(|&(t, _)| t + shift),
+                            );
+                            shift += period;
+                        }
+                    }
+                }
+            }
+            Waveform::Dc(_)
+            | Waveform::Sin { .. }
+            | Waveform::Exp { .. }
+            | Waveform::Sffm { .. }
+            | Waveform::Am { .. } => {}
         }
+        times.retain(|&t| t < tstop);
+        times
     }
 }
 
@@ -165,11 +381,14 @@ fn eval_pulse(v1: f64, v2: f64, td: f64, tr: f64, tf: f64, pw: f64, per: f64, t:
     // Time within the period (or from delay if per=0)
     let t_rel = if per > 0.0 { (t - td) % per } else { t - td };
 
-    // Pulse shape: rise -> high -> fall -> low
-    if t_rel < tr {
+    // Pulse shape: rise -> high -> fall -> low. Each edge starts at its
+    // old level, so a zero rise time still reads v1 at t = td.
+    if t_rel == 0.0 {
+        v1
+    } else if t_rel < tr {
         // Rising edge
         v1 + (v2 - v1) * t_rel / tr
-    } else if t_rel < tr + pw {
+    } else if t_rel <= tr + pw {
         // Pulse high
         v2
     } else if t_rel < tr + pw + tf {
@@ -183,53 +402,99 @@ fn eval_pulse(v1: f64, v2: f64, td: f64, tr: f64, tf: f64, pw: f64, per: f64, t:
 
 /// Evaluate a sinusoidal waveform at time t.
 fn eval_sin(vo: f64, va: f64, freq: f64, td: f64, theta: f64, phase: f64, t: f64) -> f64 {
-    if t < td {
-        return vo;
+    let phase_rad = phase.to_radians();
+
+    // ngspice holds the phase-shifted starting value during the delay
+    if t <= td {
+        return vo + va * phase_rad.sin();
     }
 
+    // Damped sinusoid: vo + va * exp(-theta * t') * sin(2*pi*freq*t' + phase)
     let t_rel = t - td;
-    let phase_rad = phase * PI / 180.0;
+    vo + va * (-theta * t_rel).exp() * (2.0 * PI * freq * t_rel + phase_rad).sin()
+}
 
-    // Damped sinusoid: vo + va * exp(-theta * t) * sin(2*pi*freq*t + phase)
-    let damping = if theta > 0.0 {
-        (-theta * t_rel).exp()
-    } else {
-        1.0
+/// Evaluate an exponential waveform at time t.
+fn eval_exp(v1: f64, v2: f64, td1: f64, tau1: f64, td2: f64, tau2: f64, t: f64) -> f64 {
+    // A zero time constant is an instantaneous step.
+    let approach = |dt: f64, tau: f64| {
+        if tau > 0.0 {
+            1.0 - (-dt / tau).exp()
+        } else {
+            1.0
+        }
     };
 
-    vo + va * damping * (2.0 * PI * freq * t_rel + phase_rad).sin()
+    let mut value = v1;
+    if t > td1 {
+        value += (v2 - v1) * approach(t - td1, tau1);
+    }
+    if t > td2 {
+        value += (v1 - v2) * approach(t - td2, tau2);
+    }
+    value
 }
 
-/// Evaluate a piecewise linear waveform at time t.
-fn eval_pwl(points: &[(f64, f64)], t: f64) -> f64 {
-    if points.is_empty() {
-        return 0.0;
-    }
+/// Evaluate a single-frequency FM waveform at time t.
+#[allow(clippy::too_many_arguments)]
+fn eval_sffm(
+    vo: f64,
+    va: f64,
+    fc: f64,
+    mdi: f64,
+    fs: f64,
+    phasec: f64,
+    phases: f64,
+    t: f64,
+) -> f64 {
+    let signal = (2.0 * PI * fs * t + phases.to_radians()).sin();
+    vo + va * (2.0 * PI * fc * t + phasec.to_radians() + mdi * signal).sin()
+}
 
-    // Before first point: use first value
-    if t <= points[0].0 {
-        return points[0].1;
+/// Evaluate an amplitude-modulated waveform at time t.
+#[allow(clippy::too_many_arguments)]
+fn eval_am(va: f64, vo: f64, mf: f64, fc: f64, td: f64, phasem: f64, phasec: f64, t: f64) -> f64 {
+    if t <= td {
+        return 0.0;
     }
 
-    // After last point: use last value
-    if t >= points[points.len() - 1].0 {
-        return points[points.len() - 1].1;
-    }
+    let t_rel = t - td;
+    let envelope = vo + (2.0 * PI * mf * t_rel + phasem.to_radians()).sin();
+    va * envelope * (2.0 * PI * fc * t_rel + phasec.to_radians()).sin()
+}
 
-    // Find the interval containing t and interpolate
-    for i in 0..points.len() - 1 {
-        let (t0, v0) = points[i];
-        let (t1, v1) = points[i + 1];
+/// Evaluate a piecewise linear waveform at time t.
+fn eval_pwl(points: &[(f64, f64)], repeat_from: Option<f64>, t: f64) -> f64 {
+    let (Some(&(_, v_first)), Some(&(t_last, v_last))) = (points.first(), points.last()) else {
+        return 0.0;
+    };
 
-        if t >= t0 && t <= t1 {
-            // Linear interpolation
-            let frac = (t - t0) / (t1 - t0);
-            return v0 + frac * (v1 - v0);
+    let mut t = t;
+    if t > t_last {
+        match repeat_from {
+            // Fold t back into (TR, t_last]; a whole number of periods lands
+            // on the end of a repetition rather than its start.
+            Some(tr) if tr < t_last => {
+                let period = t_last - tr;
+                let phase = (t - tr) % period;
+                t = if phase > 0.0 { tr + phase } else { t_last };
+            }
+            _ => return v_last,
         }
     }
 
-    // Shouldn't reach here, but return last value as fallback
-    points[points.len() - 1].1
+    // First point at or after t, so a run of equal times (a step) reads the
+    // value before it at exactly that time
+    let i = points.partition_point(|&(ti, _)| ti < t);
+    if i == 0 {
+        return v_first;
+    }
+    if i == points.len() {
+        return v_last;
+    }
+    let (t0, v0) = points[i - 1];
+    let (t1, v1) = points[i];
+    v0 + (v1 - v0) * (t - t0) / (t1 - t0)
 }
 
 #[cfg(test)]
@@ -272,6 +537,38 @@ mod tests {
         assert_eq!(w.dc_value(), 0.0);
     }
 
+    #[test]
+    fn test_pulse_edge_corners() {
+        // PULSE(1 3 1m 2m 4m 3m 20m): rise 1m..3m, high 3m..6m, fall 6m..10m
+        let w = Waveform::pulse(1.0, 3.0, 1e-3, 2e-3, 4e-3, 3e-3, 20e-3);
+        let corners = [
+            (1e-3, 1.0), // start of rise still v1
+            (1.5e-3, 1.5),
+            (2e-3, 2.0),
+            (3e-3, 3.0), // end of rise
+            (6e-3, 3.0), // start of fall still v2
+            (7e-3, 2.5),
+            (9e-3, 1.5),
+            (10e-3, 1.0), // end of fall
+            (21e-3, 1.0), // start of next period
+            (22e-3, 2.0),
+        ];
+        for (t, expected) in corners {
+            assert!(
+                (w.value_at(t) - expected).abs() < 1e-9,
+                "PULSE at t={t}: {} vs {expected}",
+                w.value_at(t)
+            );
+        }
+
+        // Instantaneous edges: each corner holds the old level
+        let step = Waveform::pulse(0.0, 5.0, 1e-3, 0.0, 0.0, 1e-3, 0.0);
+        assert_eq!(step.value_at(1e-3), 0.0);
+        assert_eq!(step.value_at(1.5e-3), 5.0);
+        assert_eq!(step.value_at(2e-3), 5.0);
+        assert_eq!(step.value_at(2.5e-3), 0.0);
+    }
+
     #[test]
     fn test_sin_waveform() {
         // SIN(0 1 1k) - 1V amplitude sine at 1kHz
@@ -293,6 +590,175 @@ mod tests {
         assert_eq!(w.dc_value(), 0.0);
     }
 
+    #[test]
+    fn test_sin_phase_delay_and_damping() {
+        // SIN(1 2 1k 0.5m 200 30): ngspice formula
+        //   t <= TD: VO + VA*sin(PHASE)
+        //   t >  TD: VO + VA*exp(-THETA*(t-TD))*sin(2*pi*FREQ*(t-TD) + PHASE)
+        let (vo, va, freq, td, theta, phase) = (1.0, 2.0, 1e3, 0.5e-3, 200.0, 30.0);
+        let w = Waveform::sin_full(vo, va, freq, td, theta, phase);
+        let ngspice = |t: f64| {
+            let ph = phase * PI / 180.0;
+            if t <= td {
+                vo + va * ph.sin()
+            } else {
+                vo + va * (-theta * (t - td)).exp() * (2.0 * PI * freq * (t - td) + ph).sin()
+            }
+        };
+
+        // During the delay the phase-shifted value is held, not VO.
+        assert!((w.value_at(0.0) - 2.0).abs() < 1e-12);
+        assert!((w.value_at(0.25e-3) - 2.0).abs() < 1e-12);
+        assert!((w.dc_value() - 2.0).abs() < 1e-12);
+
+        for t in [0.5e-3, 0.6e-3, 0.75e-3, 1.1e-3, 2.37e-3, 10e-3] {
+            assert!(
+                (w.value_at(t) - ngspice(t)).abs() < 1e-12,
+                "t = {}: {} vs {}",
+                t,
+                w.value_at(t),
+                ngspice(t)
+            );
+        }
+
+        // Continuous at the end of the delay, and decaying afterwards.
+        assert!((w.value_at(td + 1e-12) - w.value_at(td)).abs() < 1e-6);
+        assert!((w.value_at(20e-3) - vo).abs() < va * (-theta * 19.5e-3).exp() + 1e-12);
+    }
+
+    #[test]
+    fn test_exp_waveform() {
+        // EXP(0 5 1m 0.2m 3m 0.5m)
+        let (v1, v2, td1, tau1, td2, tau2) = (0.0, 5.0, 1e-3, 0.2e-3, 3e-3, 0.5e-3);
+        let w = Waveform::exp(v1, v2, td1, tau1, td2, tau2);
+        let ngspice = |t: f64| {
+            if t <= td1 {
+                v1
+            } else if t <= td2 {
+                v1 + (v2 - v1) * (1.0 - (-(t - td1) / tau1).exp())
+            } else {
+                v1 + (v2 - v1) * (1.0 - (-(t - td1) / tau1).exp())
+                    + (v1 - v2) * (1.0 - (-(t - td2) / tau2).exp())
+            }
+        };
+
+        for t in [0.0, 0.5e-3, 1.1e-3, 1.2e-3, 2.9e-3, 3.4e-3, 6e-3] {
+            assert!((w.value_at(t) - ngspice(t)).abs() < 1e-12, "t = {}", t);
+        }
+        // One time constant into the rise: 63.2% of the way to V2
+        assert!((w.value_at(1.2e-3) - 5.0 * (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+        assert_eq!(w.dc_value(), 0.0);
+    }
+
+    #[test]
+    fn test_exp_waveform_limits() {
+        // EXP(-1 4 1m 10u 2m 20u): both edges settle well within their segments
+        let (v1, v2, td1, tau1, td2, tau2) = (-1.0, 4.0, 1e-3, 10e-6, 2e-3, 20e-6);
+        let w = Waveform::exp(v1, v2, td1, tau1, td2, tau2);
+
+        // Starts at V1 up to and including TD1
+        assert_eq!(w.value_at(0.0), v1);
+        assert_eq!(w.value_at(td1), v1);
+
+        // Reaches V2 before TD2, and the fall starts from there continuously
+        assert!((w.value_at(td2) - v2).abs() < 1e-12);
+        assert!((w.value_at(td2 + 1e-12) - w.value_at(td2)).abs() < 1e-6);
+
+        // The tail decays back to V1
+        assert!((w.value_at(td2 + tau2) - (v1 + (v2 - v1) * (-1.0f64).exp())).abs() < 1e-9);
+        assert!((w.value_at(10e-3) - v1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sffm_waveform() {
+        // SFFM(0.5 1 10k 3 1k 45 90)
+        let (vo, va, fc, mdi, fs, phasec, phases) = (0.5, 1.0, 10e3, 3.0, 1e3, 45.0, 90.0);
+        let w = Waveform::sffm(vo, va, fc, mdi, fs, phasec, phases);
+        let ngspice = |t: f64| {
+            vo + va
+                * (2.0 * PI * fc * t
+                    + phasec * PI / 180.0
+                    + mdi * (2.0 * PI * fs * t + phases * PI / 180.0).sin())
+                .sin()
+        };
+
+        for t in [0.0, 13e-6, 0.1e-3, 0.37e-3, 1.5e-3] {
+            assert!((w.value_at(t) - ngspice(t)).abs() < 1e-12, "t = {}", t);
+        }
+        // At t = 0: VO + VA*sin(PHASEC + MDI*sin(PHASES)) = 0.5 + sin(pi/4 + 3)
+        assert!((w.value_at(0.0) - (0.5 + (PI / 4.0 + 3.0).sin())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sffm_frequency_deviation() {
+        // SFFM(0.5 2 1M 5 1k): peak deviation MDI*FS = 5 kHz
+        let (vo, fc, mdi, fs) = (0.5, 1e6, 5.0, 1e3);
+        let w = Waveform::sffm(vo, 2.0, fc, mdi, fs, 0.0, 0.0);
+        assert_eq!(w.value_at(0.0), vo);
+
+        // Measured carrier frequency over one period starting at t0, from
+        // the spacing of alternate crossings of VO
+        let frequency_at = |t0: f64| {
+            let s = |t: f64| w.value_at(t) - vo;
+            let dt = 1.0 / (64.0 * fc);
+            let mut crossings = Vec::new();
+            // Start an eighth of a period in, clear of the crossing at t0
+            let mut a = t0 + 8.0 * dt;
+            while crossings.len() < 3 {
+                let (mut lo, mut hi) = (a, a + dt);
+                if s(lo) * s(hi) < 0.0 {
+                    for _ in 0..60 {
+                        let mid = 0.5 * (lo + hi);
+                        if s(lo) * s(mid) <= 0.0 {
+                            hi = mid;
+                        } else {
+                            lo = mid;
+                        }
+                    }
+                    crossings.push(0.5 * (lo + hi));
+                }
+                a += dt;
+            }
+            1.0 / (crossings[2] - crossings[0])
+        };
+
+        // The modulating sine's zero crossings are where the phase deviation
+        // MDI*sin(2π·FS·t) changes fastest: ±MDI*FS away from FC
+        let deviation = mdi * fs;
+        let rising = frequency_at(0.0) - fc;
+        let falling = frequency_at(0.5 / fs) - fc;
+        assert!((rising - deviation).abs() < 0.01 * deviation, "{rising} Hz");
+        assert!(
+            (falling + deviation).abs() < 0.01 * deviation,
+            "{falling} Hz"
+        );
+    }
+
+    #[test]
+    fn test_am_waveform() {
+        // AM(2 1 1k 20k 0.1m 90 0)
+        let (va, vo, mf, fc, td, phasem, phasec) = (2.0, 1.0, 1e3, 20e3, 0.1e-3, 90.0, 0.0);
+        let w = Waveform::am(va, vo, mf, fc, td, phasem, phasec);
+        let ngspice = |t: f64| {
+            if t <= td {
+                0.0
+            } else {
+                let tr = t - td;
+                va * (vo + (2.0 * PI * mf * tr + phasem * PI / 180.0).sin())
+                    * (2.0 * PI * fc * tr + phasec * PI / 180.0).sin()
+            }
+        };
+
+        assert_eq!(w.value_at(0.05e-3), 0.0);
+        for t in [0.1e-3, 0.1125e-3, 0.33e-3, 0.6e-3, 1.27e-3] {
+            assert!((w.value_at(t) - ngspice(t)).abs() < 1e-12, "t = {}", t);
+        }
+        // A quarter carrier period after TD the carrier peaks, leaving
+        // VA*(VO + cos(2*pi*MF*12.5us)) with the 90 degree modulation phase.
+        let expected = 2.0 * (1.0 + (2.0 * PI * 1e3 * 12.5e-6).cos());
+        assert!((w.value_at(0.1125e-3) - expected).abs() < 1e-12);
+    }
+
     #[test]
     fn test_pwl_waveform() {
         // PWL(0 0 1m 5 2m 5 3m 0)
@@ -319,4 +785,97 @@ mod tests {
         // After end: hold last value
         assert!((w.value_at(5e-3) - 0.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_pwl_hold_and_steps() {
+        // Starts late, steps 1 -> 4 at 2ms, ends on another step at 3ms
+        let w = Waveform::pwl(vec![
+            (1e-3, 1.0),
+            (2e-3, 1.0),
+            (2e-3, 4.0),
+            (3e-3, 2.0),
+            (3e-3, -1.0),
+        ]);
+
+        // Before the first point: hold the first value
+        assert_eq!(w.value_at(0.0), 1.0);
+        assert_eq!(w.value_at(1e-3), 1.0);
+        assert_eq!(w.dc_value(), 1.0);
+
+        // The step time reads the old level, just after it the new one
+        assert_eq!(w.value_at(2e-3), 1.0);
+        assert!((w.value_at(2e-3 + 1e-12) - 4.0).abs() < 1e-6);
+        assert!((w.value_at(2.5e-3) - 3.0).abs() < 1e-10);
+        assert_eq!(w.value_at(3e-3), 2.0);
+
+        // Past the end: hold the last value
+        assert_eq!(w.value_at(3e-3 + 1e-12), -1.0);
+        assert_eq!(w.value_at(1.0), -1.0);
+
+        assert_eq!(Waveform::pwl(Vec::new()).value_at(1.0), 0.0);
+        assert_eq!(Waveform::pwl(vec![(1e-3, 2.0)]).value_at(5e-3), 2.0);
+    }
+
+    #[test]
+    fn test_pwl_repeat() {
+        // Triangle from 1ms to 3ms, repeated from 1ms: period 2ms
+        let points = vec![(0.0, -1.0), (1e-3, 0.0), (2e-3, 2.0), (3e-3, 0.0)];
+        let w = Waveform::pwl_repeat(points.clone(), 1e-3);
+        let once = Waveform::pwl(points);
+
+        // The first pass is the plain waveform
+        for t in [0.0, 0.5e-3, 1.5e-3, 2.5e-3, 3e-3] {
+            assert_eq!(w.value_at(t), once.value_at(t));
+        }
+
+        // Later passes replay 1ms..3ms, never the lead-in before TR
+        for k in 1..4 {
+            let shift = k as f64 * 2e-3;
+            for t in [1.25e-3, 2e-3, 2.75e-3, 3e-3] {
+                assert!(
+                    (w.value_at(t + shift) - once.value_at(t)).abs() < 1e-9,
+                    "t = {}: {} vs {}",
+                    t + shift,
+                    w.value_at(t + shift),
+                    once.value_at(t)
+                );
+            }
+        }
+        assert!((w.value_at(100e-3) - 2.0).abs() < 1e-9);
+
+        // Repeating from the last point just holds it
+        let hold = Waveform::pwl_repeat(vec![(0.0, 0.0), (1e-3, 5.0)], 1e-3);
+        assert_eq!(hold.value_at(7e-3), 5.0);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        // Two periods of PULSE(0 5 1m 0.1m 0.2m 0.5m 2m) fit before 4.5ms
+        let pulse = Waveform::pulse(0.0, 5.0, 1e-3, 0.1e-3, 0.2e-3, 0.5e-3, 2e-3);
+        let expected = [1.0, 1.1, 1.6, 1.8, 3.0, 3.1, 3.6, 3.8];
+        let times = pulse.breakpoints(4.5e-3);
+        assert_eq!(times.len(), expected.len());
+        for (t, e) in times.iter().zip(expected) {
+            assert!((t - e * 1e-3).abs() < 1e-15, "{} vs {}ms", t, e);
+        }
+
+        // A single pulse stops after its fall
+        let single = Waveform::pulse(0.0, 5.0, 1e-3, 0.1e-3, 0.2e-3, 0.5e-3, 0.0);
+        assert_eq!(single.breakpoints(1.0).len(), 4);
+
+        // PWL points, and the repeated section after TR
+        let points = vec![(0.0, -1.0), (1e-3, 0.0), (2e-3, 2.0), (3e-3, 0.0)];
+        assert_eq!(
+            Waveform::pwl(points.clone()).breakpoints(10e-3),
+            vec![0.0, 1e-3, 2e-3, 3e-3]
+        );
+        let times = Waveform::pwl_repeat(points, 1e-3).breakpoints(6e-3);
+        let expected = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(times.len(), expected.len());
+        for (t, e) in times.iter().zip(expected) {
+            assert!((t - e * 1e-3).abs() < 1e-15, "{} vs {}ms", t, e);
+        }
+
+        assert!(Waveform::sin(0.0, 1.0, 1e3).breakpoints(1.0).is_empty());
+    }
 }
@@ -0,0 +1,72 @@
+/*
+ * C API for the Spicier circuit simulator.
+ *
+ * All handles are opaque and must be released with their matching *_free
+ * function. Every function returns a SpicierStatus; on failure the message is
+ * available from spicier_last_error() until the next call on the same thread.
+ */
+
+#ifndef SPICIER_H
+#define SPICIER_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum SpicierStatus {
+    SPICIER_OK = 0,
+    SPICIER_NULL_POINTER = 1,
+    SPICIER_INVALID_UTF8 = 2,
+    SPICIER_PARSE = 3,
+    SPICIER_SOLVER = 4,
+    SPICIER_UNKNOWN_NODE = 5,
+    SPICIER_OUT_OF_RANGE = 6,
+    SPICIER_PANIC = 7
+} SpicierStatus;
+
+#define SPICIER_AC_LIN 0
+#define SPICIER_AC_DEC 1
+#define SPICIER_AC_OCT 2
+
+typedef struct SpicierCircuit SpicierCircuit;
+typedef struct SpicierDcResult SpicierDcResult;
+typedef struct SpicierAcResult SpicierAcResult;
+typedef struct SpicierTranResult SpicierTranResult;
+
+const char *spicier_last_error(void);
+
+/* Circuit */
+SpicierStatus spicier_circuit_parse(const char *netlist, SpicierCircuit **out);
+void spicier_circuit_free(SpicierCircuit *circuit);
+SpicierStatus spicier_circuit_num_nodes(const SpicierCircuit *circuit, size_t *out);
+
+/* DC operating point */
+SpicierStatus spicier_run_dc(const SpicierCircuit *circuit, SpicierDcResult **out);
+SpicierStatus spicier_dc_voltage(const SpicierDcResult *result, const char *node, double *voltage);
+void spicier_dc_result_free(SpicierDcResult *result);
+
+/* AC sweep */
+SpicierStatus spicier_run_ac(const SpicierCircuit *circuit, int sweep_type, size_t num_points,
+                             double fstart, double fstop, SpicierAcResult **out);
+SpicierStatus spicier_ac_num_points(const SpicierAcResult *result, size_t *out);
+SpicierStatus spicier_ac_frequency(const SpicierAcResult *result, size_t index, double *frequency);
+SpicierStatus spicier_ac_voltage(const SpicierAcResult *result, size_t index, const char *node,
+                                 double *re, double *im);
+void spicier_ac_result_free(SpicierAcResult *result);
+
+/* Transient */
+SpicierStatus spicier_run_transient(const SpicierCircuit *circuit, double tstep, double tstop,
+                                    SpicierTranResult **out);
+SpicierStatus spicier_tran_num_points(const SpicierTranResult *result, size_t *out);
+SpicierStatus spicier_tran_time(const SpicierTranResult *result, size_t index, double *time);
+SpicierStatus spicier_tran_voltage(const SpicierTranResult *result, size_t index,
+                                   const char *node, double *voltage);
+void spicier_tran_result_free(SpicierTranResult *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SPICIER_H */
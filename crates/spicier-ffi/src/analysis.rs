@@ -0,0 +1,95 @@
+//! Netlist-level analysis drivers behind the C API.
+
+use nalgebra::DVector;
+use spicier_core::mna::MnaSystem;
+use spicier_core::netlist::TransientDeviceInfo;
+use spicier_core::{LimitState, Netlist};
+use spicier_solver::{
+    AcParams, AcResult, ConvergenceCriteria, Error, IntegrationMethod, NetlistAcStamper,
+    NetlistTransientStamper, NonlinearStamper, Result, TransientParams, TransientResult,
+    build_transient_state, solve_ac, solve_dc, solve_newton_raphson, solve_transient,
+};
+
+/// Solve the DC operating point, returning the full MNA solution vector.
+pub(crate) fn dc_operating_point(netlist: &Netlist) -> Result<DVector<f64>> {
+    let size = netlist.num_nodes() + netlist.num_current_vars();
+
+    if netlist.has_nonlinear_devices() {
+        let nr = solve_newton_raphson(
+            netlist.num_nodes(),
+            netlist.num_current_vars(),
+            &NonlinearDcStamper { netlist },
+            &ConvergenceCriteria::default(),
+            None,
+        )?;
+        if !nr.converged {
+            return Err(Error::ConvergenceFailed {
+                iterations: nr.iterations,
+            });
+        }
+        Ok(nr.solution)
+    } else {
+        let dc = solve_dc(&netlist.assemble_mna())?;
+        Ok(DVector::from_iterator(
+            size,
+            dc.node_voltages
+                .iter()
+                .chain(dc.branch_currents.iter())
+                .copied(),
+        ))
+    }
+}
+
+/// Run an AC sweep linearized at the DC operating point.
+pub(crate) fn ac_sweep(netlist: &Netlist, params: &AcParams) -> Result<AcResult> {
+    let dc = dc_operating_point(netlist)?;
+    solve_ac(&NetlistAcStamper::new(netlist, Some(&dc)), params)
+}
+
+/// Run a fixed-step trapezoidal transient starting from the DC operating point.
+pub(crate) fn transient(netlist: &Netlist, tstep: f64, tstop: f64) -> Result<TransientResult> {
+    if let Some(device) = netlist.devices().iter().find(|device| {
+        matches!(
+            device.transient_info(),
+            TransientDeviceInfo::MutualInductance { .. }
+        )
+    }) {
+        return Err(Error::SolverError(format!(
+            "device {} is not supported in transient analysis",
+            device.device_name()
+        )));
+    }
+    let (mut caps, mut inds) = build_transient_state(netlist);
+
+    let dc = dc_operating_point(netlist)?;
+    let stamper = NetlistTransientStamper::new(netlist);
+    let params = TransientParams {
+        tstop,
+        tstep,
+        method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: netlist.source_breakpoints(tstop),
+    };
+    solve_transient(&stamper, &mut caps, &mut inds, &params, &dc)
+}
+
+/// Stamps every device linearized at the current Newton iterate.
+struct NonlinearDcStamper<'a> {
+    netlist: &'a Netlist,
+}
+
+impl NonlinearStamper for NonlinearDcStamper<'_> {
+    fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+        self.netlist.stamp_nonlinear_into(mna, solution);
+    }
+
+    fn stamp_at_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        state: &mut LimitState,
+    ) {
+        self.netlist
+            .stamp_nonlinear_into_limited(mna, solution, state);
+    }
+}
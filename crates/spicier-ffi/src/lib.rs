@@ -0,0 +1,595 @@
+//! C ABI for the Spicier circuit simulator.
+//!
+//! Exposes netlist parsing and DC, AC and transient analysis through opaque
+//! handles and `extern "C"` functions so that Spicier can be embedded from C,
+//! Python (ctypes/cffi) or any language with a C FFI. The matching header is
+//! `include/spicier.h`.
+//!
+//! Every function returns a [`SpicierStatus`] code. Panics are caught at the
+//! boundary and reported as [`SpicierStatus::Panic`]; the message for the most
+//! recent failure on the calling thread is available from [`spicier_last_error`].
+
+mod analysis;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use nalgebra::DVector;
+use spicier_core::NodeId;
+use spicier_parser::ParseResult;
+use spicier_solver::{AcParams, AcResult, AcSweepType, TransientResult};
+
+/// Status code returned by every C API function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpicierStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// The netlist could not be parsed.
+    Parse = 3,
+    /// The analysis failed (singular matrix, non-convergence, ...).
+    Solver = 4,
+    /// The requested node does not exist in the circuit.
+    UnknownNode = 5,
+    /// A point index or argument was out of range.
+    OutOfRange = 6,
+    /// A Rust panic was caught at the boundary.
+    Panic = 7,
+}
+
+/// Linear frequency spacing for [`spicier_run_ac`].
+pub const SPICIER_AC_LIN: c_int = 0;
+/// Logarithmic spacing, points per decade, for [`spicier_run_ac`].
+pub const SPICIER_AC_DEC: c_int = 1;
+/// Logarithmic spacing, points per octave, for [`spicier_run_ac`].
+pub const SPICIER_AC_OCT: c_int = 2;
+
+/// Parsed circuit handle.
+pub struct SpicierCircuit {
+    parsed: ParseResult,
+}
+
+/// DC operating point result handle.
+pub struct SpicierDcResult {
+    solution: DVector<f64>,
+    node_map: HashMap<String, NodeId>,
+}
+
+/// AC sweep result handle.
+pub struct SpicierAcResult {
+    result: AcResult,
+    node_map: HashMap<String, NodeId>,
+}
+
+/// Transient result handle.
+pub struct SpicierTranResult {
+    result: TransientResult,
+    node_map: HashMap<String, NodeId>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Error carried to the boundary before being turned into a status code.
+struct FfiError {
+    status: SpicierStatus,
+    message: String,
+}
+
+impl FfiError {
+    fn new(status: SpicierStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn null(arg: &str) -> Self {
+        Self::new(SpicierStatus::NullPointer, format!("{arg} is null"))
+    }
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+}
+
+/// Run `f`, catching panics and recording any error for [`spicier_last_error`].
+fn guard(f: impl FnOnce() -> Result<(), FfiError>) -> SpicierStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => {
+            set_last_error("");
+            SpicierStatus::Ok
+        }
+        Ok(Err(e)) => {
+            set_last_error(&e.message);
+            e.status
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(&format!("panic: {message}"));
+            SpicierStatus::Panic
+        }
+    }
+}
+
+/// Borrow a C string as `&str`.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string valid for `'a`.
+unsafe fn borrow_str<'a>(s: *const c_char, arg: &str) -> Result<&'a str, FfiError> {
+    if s.is_null() {
+        return Err(FfiError::null(arg));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract.
+    unsafe { CStr::from_ptr(s) }.to_str().map_err(|_| {
+        FfiError::new(
+            SpicierStatus::InvalidUtf8,
+            format!("{arg} is not valid UTF-8"),
+        )
+    })
+}
+
+/// Borrow a handle pointer as a reference.
+///
+/// # Safety
+///
+/// `p` must be null or point to a live `T` valid for `'a`.
+unsafe fn borrow<'a, T>(p: *const T, arg: &str) -> Result<&'a T, FfiError> {
+    // SAFETY: either null (rejected) or a live handle per the caller's contract.
+    unsafe { p.as_ref() }.ok_or_else(|| FfiError::null(arg))
+}
+
+/// Write `value` through an output pointer.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes of `T`.
+unsafe fn write_out<T>(out: *mut T, value: T, arg: &str) -> Result<(), FfiError> {
+    if out.is_null() {
+        return Err(FfiError::null(arg));
+    }
+    // SAFETY: non-null and writable per the caller's contract.
+    unsafe { out.write(value) };
+    Ok(())
+}
+
+/// Resolve a node name to its MNA index; `None` means ground.
+fn node_index(node_map: &HashMap<String, NodeId>, name: &str) -> Result<Option<usize>, FfiError> {
+    let id = node_map
+        .get(name)
+        .or_else(|| {
+            node_map
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, id)| id)
+        })
+        .ok_or_else(|| {
+            FfiError::new(SpicierStatus::UnknownNode, format!("unknown node '{name}'"))
+        })?;
+    if id.is_ground() {
+        Ok(None)
+    } else {
+        Ok(Some(id.as_u32() as usize - 1))
+    }
+}
+
+fn check_point(index: usize, len: usize) -> Result<(), FfiError> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(FfiError::new(
+            SpicierStatus::OutOfRange,
+            format!("point index {index} out of range (0..{len})"),
+        ))
+    }
+}
+
+fn solver_error(e: spicier_solver::Error) -> FfiError {
+    FfiError::new(SpicierStatus::Solver, e.to_string())
+}
+
+/// Return the message for the most recent failed call on this thread.
+///
+/// The string is empty after a successful call. The pointer stays valid until
+/// the next API call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn spicier_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+/// Parse a SPICE netlist into a circuit handle.
+///
+/// # Safety
+///
+/// `netlist` must be a NUL-terminated string and `out` must be valid for writes.
+/// The returned handle must be released with [`spicier_circuit_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_circuit_parse(
+    netlist: *const c_char,
+    out: *mut *mut SpicierCircuit,
+) -> SpicierStatus {
+    guard(|| {
+        if out.is_null() {
+            return Err(FfiError::null("out"));
+        }
+        // SAFETY: forwarded from the caller's contract.
+        let text = unsafe { borrow_str(netlist, "netlist") }?;
+        let parsed = spicier_parser::parse_full(text)
+            .map_err(|e| FfiError::new(SpicierStatus::Parse, e.to_string()))?;
+        let handle = Box::into_raw(Box::new(SpicierCircuit { parsed }));
+        // SAFETY: `out` checked non-null above.
+        unsafe { write_out(out, handle, "out") }
+    })
+}
+
+/// Release a circuit handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `circuit` must be null or a handle from [`spicier_circuit_parse`] that has
+/// not already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_circuit_free(circuit: *mut SpicierCircuit) {
+    if !circuit.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` and is freed once.
+        drop(unsafe { Box::from_raw(circuit) });
+    }
+}
+
+/// Write the number of non-ground nodes in the circuit.
+///
+/// # Safety
+///
+/// `circuit` must be a live circuit handle and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_circuit_num_nodes(
+    circuit: *const SpicierCircuit,
+    out: *mut usize,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let circuit = unsafe { borrow(circuit, "circuit") }?;
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(out, circuit.parsed.netlist.num_nodes(), "out") }
+    })
+}
+
+/// Solve the DC operating point.
+///
+/// Circuits with nonlinear devices are solved by Newton-Raphson.
+///
+/// # Safety
+///
+/// `circuit` must be a live circuit handle and `out` must be valid for writes.
+/// The returned handle must be released with [`spicier_dc_result_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_run_dc(
+    circuit: *const SpicierCircuit,
+    out: *mut *mut SpicierDcResult,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let circuit = unsafe { borrow(circuit, "circuit") }?;
+        if out.is_null() {
+            return Err(FfiError::null("out"));
+        }
+        let solution =
+            analysis::dc_operating_point(&circuit.parsed.netlist).map_err(solver_error)?;
+        let handle = Box::into_raw(Box::new(SpicierDcResult {
+            solution,
+            node_map: circuit.parsed.node_map.clone(),
+        }));
+        // SAFETY: `out` checked non-null above.
+        unsafe { write_out(out, handle, "out") }
+    })
+}
+
+/// Write the DC voltage at a named node.
+///
+/// # Safety
+///
+/// `result` must be a live DC result handle, `node` a NUL-terminated string and
+/// `voltage` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_dc_voltage(
+    result: *const SpicierDcResult,
+    node: *const c_char,
+    voltage: *mut f64,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        // SAFETY: forwarded from the caller's contract.
+        let name = unsafe { borrow_str(node, "node") }?;
+        let v = node_index(&result.node_map, name)?.map_or(0.0, |i| result.solution[i]);
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(voltage, v, "voltage") }
+    })
+}
+
+/// Release a DC result handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `result` must be null or a handle from [`spicier_run_dc`] that has not
+/// already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_dc_result_free(result: *mut SpicierDcResult) {
+    if !result.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` and is freed once.
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
+
+/// Run an AC small-signal sweep linearized at the DC operating point.
+///
+/// `sweep_type` is one of [`SPICIER_AC_LIN`], [`SPICIER_AC_DEC`] or
+/// [`SPICIER_AC_OCT`]; `num_points` is the total count for linear sweeps and
+/// the count per decade/octave otherwise.
+///
+/// # Safety
+///
+/// `circuit` must be a live circuit handle and `out` must be valid for writes.
+/// The returned handle must be released with [`spicier_ac_result_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_run_ac(
+    circuit: *const SpicierCircuit,
+    sweep_type: c_int,
+    num_points: usize,
+    fstart: f64,
+    fstop: f64,
+    out: *mut *mut SpicierAcResult,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let circuit = unsafe { borrow(circuit, "circuit") }?;
+        if out.is_null() {
+            return Err(FfiError::null("out"));
+        }
+        let sweep_type = match sweep_type {
+            SPICIER_AC_LIN => AcSweepType::Linear,
+            SPICIER_AC_DEC => AcSweepType::Decade,
+            SPICIER_AC_OCT => AcSweepType::Octave,
+            other => {
+                return Err(FfiError::new(
+                    SpicierStatus::OutOfRange,
+                    format!("invalid AC sweep type {other}"),
+                ));
+            }
+        };
+        if num_points == 0 || !(fstart > 0.0 && fstop >= fstart) {
+            return Err(FfiError::new(
+                SpicierStatus::OutOfRange,
+                "AC sweep needs num_points > 0 and 0 < fstart <= fstop",
+            ));
+        }
+        let params = AcParams {
+            fstart,
+            fstop,
+            num_points,
+            sweep_type,
+        };
+        let result = analysis::ac_sweep(&circuit.parsed.netlist, &params).map_err(solver_error)?;
+        let handle = Box::into_raw(Box::new(SpicierAcResult {
+            result,
+            node_map: circuit.parsed.node_map.clone(),
+        }));
+        // SAFETY: `out` checked non-null above.
+        unsafe { write_out(out, handle, "out") }
+    })
+}
+
+/// Write the number of frequency points in an AC result.
+///
+/// # Safety
+///
+/// `result` must be a live AC result handle and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_ac_num_points(
+    result: *const SpicierAcResult,
+    out: *mut usize,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(out, result.result.points.len(), "out") }
+    })
+}
+
+/// Write the frequency (Hz) of AC point `index`.
+///
+/// # Safety
+///
+/// `result` must be a live AC result handle and `frequency` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_ac_frequency(
+    result: *const SpicierAcResult,
+    index: usize,
+    frequency: *mut f64,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        check_point(index, result.result.points.len())?;
+        let f = result.result.points[index].frequency;
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(frequency, f, "frequency") }
+    })
+}
+
+/// Write the complex voltage at a named node for AC point `index`.
+///
+/// # Safety
+///
+/// `result` must be a live AC result handle, `node` a NUL-terminated string and
+/// `re`/`im` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_ac_voltage(
+    result: *const SpicierAcResult,
+    index: usize,
+    node: *const c_char,
+    re: *mut f64,
+    im: *mut f64,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        // SAFETY: forwarded from the caller's contract.
+        let name = unsafe { borrow_str(node, "node") }?;
+        if re.is_null() || im.is_null() {
+            return Err(FfiError::null(if re.is_null() { "re" } else { "im" }));
+        }
+        check_point(index, result.result.points.len())?;
+        let v = node_index(&result.node_map, name)?.map_or(Default::default(), |i| {
+            result.result.points[index].solution[i]
+        });
+        // SAFETY: both pointers checked non-null above.
+        unsafe {
+            write_out(re, v.re, "re")?;
+            write_out(im, v.im, "im")
+        }
+    })
+}
+
+/// Release an AC result handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `result` must be null or a handle from [`spicier_run_ac`] that has not
+/// already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_ac_result_free(result: *mut SpicierAcResult) {
+    if !result.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` and is freed once.
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
+
+/// Run a fixed-step trapezoidal transient analysis from the DC operating point.
+///
+/// # Safety
+///
+/// `circuit` must be a live circuit handle and `out` must be valid for writes.
+/// The returned handle must be released with [`spicier_tran_result_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_run_transient(
+    circuit: *const SpicierCircuit,
+    tstep: f64,
+    tstop: f64,
+    out: *mut *mut SpicierTranResult,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let circuit = unsafe { borrow(circuit, "circuit") }?;
+        if out.is_null() {
+            return Err(FfiError::null("out"));
+        }
+        if !(tstep > 0.0 && tstop >= tstep) {
+            return Err(FfiError::new(
+                SpicierStatus::OutOfRange,
+                "transient needs 0 < tstep <= tstop",
+            ));
+        }
+        let result =
+            analysis::transient(&circuit.parsed.netlist, tstep, tstop).map_err(solver_error)?;
+        let handle = Box::into_raw(Box::new(SpicierTranResult {
+            result,
+            node_map: circuit.parsed.node_map.clone(),
+        }));
+        // SAFETY: `out` checked non-null above.
+        unsafe { write_out(out, handle, "out") }
+    })
+}
+
+/// Write the number of timepoints in a transient result.
+///
+/// # Safety
+///
+/// `result` must be a live transient result handle and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_tran_num_points(
+    result: *const SpicierTranResult,
+    out: *mut usize,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(out, result.result.points.len(), "out") }
+    })
+}
+
+/// Write the time (s) of transient point `index`.
+///
+/// # Safety
+///
+/// `result` must be a live transient result handle and `time` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_tran_time(
+    result: *const SpicierTranResult,
+    index: usize,
+    time: *mut f64,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        check_point(index, result.result.points.len())?;
+        let t = result.result.points[index].time;
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(time, t, "time") }
+    })
+}
+
+/// Write the voltage at a named node for transient point `index`.
+///
+/// # Safety
+///
+/// `result` must be a live transient result handle, `node` a NUL-terminated
+/// string and `voltage` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_tran_voltage(
+    result: *const SpicierTranResult,
+    index: usize,
+    node: *const c_char,
+    voltage: *mut f64,
+) -> SpicierStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let result = unsafe { borrow(result, "result") }?;
+        // SAFETY: forwarded from the caller's contract.
+        let name = unsafe { borrow_str(node, "node") }?;
+        check_point(index, result.result.points.len())?;
+        let v = node_index(&result.node_map, name)?
+            .map_or(0.0, |i| result.result.points[index].solution[i]);
+        // SAFETY: forwarded from the caller's contract.
+        unsafe { write_out(voltage, v, "voltage") }
+    })
+}
+
+/// Release a transient result handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `result` must be null or a handle from [`spicier_run_transient`] that has
+/// not already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spicier_tran_result_free(result: *mut SpicierTranResult) {
+    if !result.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` and is freed once.
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
@@ -0,0 +1,163 @@
+//! Smoke tests driving the C API the way a foreign caller would.
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use spicier_ffi::*;
+
+fn parse(netlist: &str) -> *mut SpicierCircuit {
+    let text = CString::new(netlist).unwrap();
+    let mut ckt = ptr::null_mut();
+    let status = unsafe { spicier_circuit_parse(text.as_ptr(), &mut ckt) };
+    assert_eq!(status, SpicierStatus::Ok);
+    assert!(!ckt.is_null());
+    ckt
+}
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(spicier_last_error()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_dc_voltage_divider() {
+    let ckt = parse("Divider\nV1 1 0 10\nR1 1 2 1k\nR2 2 0 1k\n.end\n");
+
+    let mut num_nodes = 0;
+    assert_eq!(
+        unsafe { spicier_circuit_num_nodes(ckt, &mut num_nodes) },
+        SpicierStatus::Ok
+    );
+    assert_eq!(num_nodes, 2);
+
+    let mut dc = ptr::null_mut();
+    assert_eq!(unsafe { spicier_run_dc(ckt, &mut dc) }, SpicierStatus::Ok);
+
+    let node = CString::new("2").unwrap();
+    let mut v2 = 0.0;
+    assert_eq!(
+        unsafe { spicier_dc_voltage(dc, node.as_ptr(), &mut v2) },
+        SpicierStatus::Ok
+    );
+    assert!((v2 - 5.0).abs() < 1e-9, "V(2) = {v2}");
+
+    let missing = CString::new("nope").unwrap();
+    assert_eq!(
+        unsafe { spicier_dc_voltage(dc, missing.as_ptr(), &mut v2) },
+        SpicierStatus::UnknownNode
+    );
+    assert!(last_error().contains("nope"));
+
+    unsafe {
+        spicier_dc_result_free(dc);
+        spicier_circuit_free(ckt);
+    }
+}
+
+#[test]
+fn test_ac_rc_lowpass_corner() {
+    let ckt = parse("RC\nV1 1 0 AC 1\nR1 1 2 1k\nC1 2 0 1u\n.end\n");
+    let fc = 1.0 / (2.0 * std::f64::consts::PI * 1e3 * 1e-6);
+
+    let mut ac = ptr::null_mut();
+    assert_eq!(
+        unsafe { spicier_run_ac(ckt, SPICIER_AC_LIN, 1, fc, fc, &mut ac) },
+        SpicierStatus::Ok
+    );
+
+    let mut n = 0;
+    assert_eq!(
+        unsafe { spicier_ac_num_points(ac, &mut n) },
+        SpicierStatus::Ok
+    );
+    assert_eq!(n, 1);
+
+    let node = CString::new("2").unwrap();
+    let (mut re, mut im) = (0.0, 0.0);
+    assert_eq!(
+        unsafe { spicier_ac_voltage(ac, 0, node.as_ptr(), &mut re, &mut im) },
+        SpicierStatus::Ok
+    );
+    let mag = (re * re + im * im).sqrt();
+    assert!(
+        (mag - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6,
+        "|V(2)| = {mag}"
+    );
+
+    let mut f = 0.0;
+    assert_eq!(
+        unsafe { spicier_ac_frequency(ac, 5, &mut f) },
+        SpicierStatus::OutOfRange
+    );
+
+    unsafe {
+        spicier_ac_result_free(ac);
+        spicier_circuit_free(ckt);
+    }
+}
+
+#[test]
+fn test_transient_rc_charging() {
+    let ckt = parse("RC\nV1 1 0 PULSE(0 1 0 1n 1n 1 2)\nR1 1 2 1k\nC1 2 0 1u\n.end\n");
+
+    let mut tran = ptr::null_mut();
+    assert_eq!(
+        unsafe { spicier_run_transient(ckt, 10e-6, 5e-3, &mut tran) },
+        SpicierStatus::Ok
+    );
+
+    let mut n = 0;
+    assert_eq!(
+        unsafe { spicier_tran_num_points(tran, &mut n) },
+        SpicierStatus::Ok
+    );
+    assert!(n > 1);
+
+    let node = CString::new("2").unwrap();
+    let (mut t, mut v) = (0.0, 0.0);
+    unsafe {
+        assert_eq!(spicier_tran_time(tran, n - 1, &mut t), SpicierStatus::Ok);
+        assert_eq!(
+            spicier_tran_voltage(tran, n - 1, node.as_ptr(), &mut v),
+            SpicierStatus::Ok
+        );
+    }
+    // Five time constants: within 1% of the final value.
+    assert!((t - 5e-3).abs() < 1e-9);
+    assert!((v - 1.0).abs() < 0.01, "V(2) at 5τ = {v}");
+
+    unsafe {
+        spicier_tran_result_free(tran);
+        spicier_circuit_free(ckt);
+    }
+}
+
+#[test]
+fn test_error_statuses() {
+    let mut ckt = ptr::null_mut();
+    assert_eq!(
+        unsafe { spicier_circuit_parse(ptr::null(), &mut ckt) },
+        SpicierStatus::NullPointer
+    );
+    assert!(ckt.is_null());
+
+    let bad = CString::new("Bad\nR1 1 0 notanumber\n.end\n").unwrap();
+    assert_eq!(
+        unsafe { spicier_circuit_parse(bad.as_ptr(), &mut ckt) },
+        SpicierStatus::Parse
+    );
+    assert!(!last_error().is_empty());
+
+    let mut dc = ptr::null_mut();
+    assert_eq!(
+        unsafe { spicier_run_dc(ptr::null(), &mut dc) },
+        SpicierStatus::NullPointer
+    );
+
+    // Freeing null handles is a no-op.
+    unsafe {
+        spicier_circuit_free(ptr::null_mut());
+        spicier_dc_result_free(ptr::null_mut());
+    }
+}
@@ -0,0 +1,97 @@
+//! Peak heap usage of `parse_full` versus `parse_streaming`.
+//!
+//! Run with `cargo bench -p spicier-parser --bench parse_memory`. Set
+//! `SPICIER_BENCH_ELEMENTS` to change the circuit size (default 1,000,000).
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Tracks current and peak bytes allocated through the system allocator.
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+/// Run `f`, returning its result and the peak heap growth while it ran.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let result = f();
+    (result, PEAK.load(Ordering::Relaxed) - base)
+}
+
+/// An RC ladder with `n` elements: alternating series resistors and shunt caps.
+fn write_ladder(path: &std::path::Path, n: usize) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "RC ladder")?;
+    writeln!(out, ".MODEL CMIM C (CJ=2e-3)")?;
+    writeln!(out, "V1 n0 0 DC 1")?;
+    for i in 0..n / 2 {
+        writeln!(out, "R{i} n{i} n{} 1k", i + 1)?;
+        writeln!(out, "C{i} n{} 0 1p", i + 1)?;
+    }
+    writeln!(out, ".op")?;
+    writeln!(out, ".end")?;
+    out.flush()
+}
+
+fn main() {
+    let n: usize = std::env::var("SPICIER_BENCH_ELEMENTS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(1_000_000);
+    let path = std::env::temp_dir().join(format!("spicier_ladder_{n}.cir"));
+    write_ladder(&path, n).expect("failed to write netlist");
+    let mib = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+
+    let start = Instant::now();
+    let (devices, peak) = measure(|| {
+        let text = std::fs::read_to_string(&path).unwrap();
+        spicier_parser::parse_full(&text)
+            .unwrap()
+            .netlist
+            .num_devices()
+    });
+    println!(
+        "parse_full:      {devices} devices, peak {:8.1} MiB, {:?}",
+        mib(peak),
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    let (devices, peak) = measure(|| {
+        let mut count = 0;
+        let reader = BufReader::new(File::open(&path).unwrap());
+        spicier_parser::parse_streaming(reader, |_| count += 1).unwrap();
+        count
+    });
+    println!(
+        "parse_streaming: {devices} devices, peak {:8.1} MiB, {:?}",
+        mib(peak),
+        start.elapsed()
+    );
+
+    let _ = std::fs::remove_file(&path);
+}
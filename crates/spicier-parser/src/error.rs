@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::parser::UnsupportedConstruct;
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
@@ -16,6 +18,15 @@ pub enum Error {
 
     #[error("missing node: {0}")]
     MissingNode(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(
+        "netlist uses unsupported devices or directives: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    Unsupported(Vec<UnsupportedConstruct>),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
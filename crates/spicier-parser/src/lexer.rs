@@ -55,6 +55,7 @@ pub struct Lexer<'a> {
     at_line_start: bool,
     /// In SPICE, the first line is ALWAYS the title, even if it starts with '*'.
     /// We only treat '*' as a comment marker after the title line has been processed.
+    /// Leading blank lines don't count, matching the parser's title rule.
     title_line_processed: bool,
 }
 
@@ -85,13 +86,22 @@ impl<'a> Lexer<'a> {
                 column,
             }),
             Some('\n') => {
+                let ends_title = !self.title_line_processed;
+                let blank = self.at_line_start;
                 self.advance();
                 self.line += 1;
                 self.column = 1;
                 self.at_line_start = true;
-                // After the first EOL, the title line has been processed
-                // and subsequent '*' lines are treated as comments
-                self.title_line_processed = true;
+                // After the first non-blank line, the title line has been
+                // processed and subsequent '*' lines are treated as comments
+                if !blank {
+                    self.title_line_processed = true;
+                }
+                // A following '+' line continues this statement, so no Eol.
+                // The title line is never continued.
+                if !ends_title && !blank && self.skip_to_continuation() {
+                    return self.next_token();
+                }
                 Ok(SpannedToken {
                     token: Token::Eol,
                     line,
@@ -245,6 +255,49 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// If the next line that is not blank or a `*` comment starts with `+`,
+    /// consume everything up to and including the `+` and return true.
+    fn skip_to_continuation(&mut self) -> bool {
+        let offset = self
+            .chars
+            .peek()
+            .map(|&(i, _)| i)
+            .unwrap_or(self.input.len());
+        let mut skipped_lines = 0;
+        let mut column = 1;
+        let mut found = false;
+        for line in self.input[offset..].split('\n') {
+            let trimmed = line.trim_start_matches([' ', '\t', '\r']);
+            if trimmed.is_empty() || trimmed.starts_with('*') {
+                skipped_lines += 1;
+                continue;
+            }
+            if trimmed.starts_with('+') {
+                column += line.len() - trimmed.len() + 1;
+                found = true;
+            }
+            break;
+        }
+        if !found {
+            return false;
+        }
+
+        // Skip the blank/comment lines, then the '+' line's indent and '+'.
+        for _ in 0..skipped_lines {
+            self.skip_to_eol();
+            self.advance();
+        }
+        while let Some(c) = self.advance() {
+            if c == '+' {
+                break;
+            }
+        }
+        self.line += skipped_lines;
+        self.column = column;
+        self.at_line_start = false;
+        true
+    }
+
     fn skip_whitespace(&mut self) {
         while let Some(c) = self.peek_char() {
             if c == ' ' || c == '\t' || c == '\r' {
@@ -428,21 +481,45 @@ mod tests {
 
     #[test]
     fn test_continuation() {
-        let input = "R1 1\n+ 0 1k";
+        let input = "Title\nR1 1\n* note\n\n  + 0 1k\nR2 1 0 2k";
+        let lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        let kinds: Vec<_> = tokens.iter().map(|t| t.token.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Token::Name("Title".into()),
+                Token::Eol,
+                Token::Name("R1".into()),
+                Token::Value("1".into()),
+                Token::Value("0".into()),
+                Token::Value("1k".into()),
+                Token::Eol,
+                Token::Name("R2".into()),
+                Token::Value("1".into()),
+                Token::Value("0".into()),
+                Token::Value("2k".into()),
+                Token::Eof,
+            ]
+        );
+        // Positions after the join still point into the '+' line.
+        assert_eq!((tokens[4].line, tokens[4].column), (5, 5));
+        assert_eq!(tokens[7].line, 6);
+    }
+
+    #[test]
+    fn test_title_after_blank_lines_is_not_a_comment() {
+        let input = "\n* Star Title\n* comment\nR1 1 0 1k";
         let lexer = Lexer::new(input);
         let tokens = lexer.tokenize().unwrap();
 
-        // Should have: R1, 1, Eol, 0, 1k, Eof
-        let names: Vec<_> = tokens
-            .iter()
-            .filter_map(|t| match &t.token {
-                Token::Name(n) => Some(n.clone()),
-                Token::Value(v) => Some(v.clone()),
-                _ => None,
-            })
-            .collect();
-
-        assert_eq!(names, vec!["R1", "1", "0", "1k"]);
+        assert_eq!(tokens[1].token, Token::Star);
+        assert!(
+            !tokens
+                .iter()
+                .any(|t| t.token == Token::Name("comment".into()))
+        );
     }
 
     #[test]
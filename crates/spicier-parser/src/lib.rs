@@ -20,11 +20,14 @@
 
 pub mod error;
 pub mod lexer;
+pub mod model_card;
 pub mod parser;
 
 pub use error::{Error, Result};
+pub use model_card::FromModelCard;
 pub use parser::{
     AcSweepType, AnalysisCommand, DcSweepSpec, DcSweepType, InitialCondition, MeasureAnalysis,
     MeasureType, Measurement, OutputVariable, ParseResult, PrintAnalysisType, PrintCommand,
-    StatFunc, TriggerType, parse, parse_full,
+    StatFunc, TriggerType, UnsupportedConstruct, UnsupportedPolicy, parse, parse_full,
+    parse_full_with_parameters, parse_full_with_policy, parse_streaming,
 };
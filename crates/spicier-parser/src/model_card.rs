@@ -0,0 +1,145 @@
+//! Loading a single `.MODEL` card outside a netlist.
+//!
+//! PDKs ship transistor models as `.lib` fragments. [`FromModelCard`] turns
+//! one `.MODEL` card from such a fragment into the device's parameter struct,
+//! so tools can load a model without building a netlist around it:
+//!
+//! ```
+//! use spicier_devices::mosfet::Bsim4Params;
+//! use spicier_parser::FromModelCard;
+//!
+//! let params = Bsim4Params::from_model_card(
+//!     ".MODEL nch NMOS LEVEL=54\n+ VTH0=0.45 TOXE=4.1n",
+//! )
+//! .unwrap();
+//! assert_eq!(params.vth0, 0.45);
+//! ```
+//!
+//! The card is parsed exactly as it would be inside a netlist: `+`
+//! continuation lines, `*` comments and engineering suffixes all work, and
+//! parameters the card leaves out keep their model defaults.
+
+use spicier_devices::mosfet::{Bsim3Params, Bsim4Params};
+
+use crate::error::{Error, Result};
+use crate::parser::{ModelDefinition, parse_models};
+
+/// Device parameters that can be loaded from a standalone `.MODEL` card.
+pub trait FromModelCard: Sized {
+    /// Parse the single `.MODEL` card in `text`.
+    ///
+    /// Fails if `text` does not contain exactly one `.MODEL` card, or if
+    /// its type and `LEVEL` do not select this model.
+    fn from_model_card(text: &str) -> Result<Self>;
+}
+
+impl FromModelCard for Bsim4Params {
+    fn from_model_card(text: &str) -> Result<Self> {
+        match single_model(text)? {
+            (_, ModelDefinition::Nmos54(bp) | ModelDefinition::Pmos54(bp)) => Ok(bp),
+            (name, _) => Err(Error::InvalidValue(format!(
+                "model '{}' is not a BSIM4 (LEVEL=54 or 14) MOSFET",
+                name
+            ))),
+        }
+    }
+}
+
+impl FromModelCard for Bsim3Params {
+    fn from_model_card(text: &str) -> Result<Self> {
+        match single_model(text)? {
+            (_, ModelDefinition::Nmos49(bp) | ModelDefinition::Pmos49(bp)) => Ok(bp),
+            (name, _) => Err(Error::InvalidValue(format!(
+                "model '{}' is not a BSIM3 (LEVEL=49 or 8) MOSFET",
+                name
+            ))),
+        }
+    }
+}
+
+/// The one recognized `.MODEL` card in `text`, with its (uppercase) name.
+fn single_model(text: &str) -> Result<(String, ModelDefinition)> {
+    let mut models = parse_models(text)?;
+    if models.len() != 1 {
+        return Err(Error::InvalidValue(format!(
+            "expected one .MODEL card, found {}",
+            models.len()
+        )));
+    }
+    Ok(models.drain().next().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spicier_devices::mosfet::MosfetType;
+
+    /// A SKY130-style BSIM4 card: lowercase keywords, spaced `=`, one
+    /// parameter per continuation line, and parameters spicier ignores.
+    const SKY130_NFET: &str = "\
+* NMOS model, bin 0
+.model sky130_fd_pr__nfet_01v8__model nmos
++ level = 54
++ version = 4.5
++ binunit = 2
++ tnom = 30.0
++ toxe = 4.148e-009
++ toxp = 4.148e-009
++ toxm = 4.148e-009
+* threshold
++ vth0 = 0.49439
++ k1 = 0.53712
++ k2 = -0.036816
++ u0 = 0.030697
++ vsat = 1.0e+5
++ mobmod = 0
++ capmod = 2
++ cgso = 2.5e-10 cgdo = 2.5e-10
+";
+
+    #[test]
+    fn test_bsim4_from_sky130_card() {
+        let bp = Bsim4Params::from_model_card(SKY130_NFET).unwrap();
+        let defaults = Bsim4Params::nmos_default();
+
+        assert_eq!(bp.mos_type, MosfetType::Nmos);
+        assert_eq!(bp.vth0, 0.49439);
+        assert_eq!(bp.k1, 0.53712);
+        assert_eq!(bp.k2, -0.036816);
+        assert_eq!(bp.u0, 0.030697);
+        assert_eq!(bp.vsat, 1.0e5);
+        assert_eq!(bp.toxe, 4.148e-9);
+        assert_eq!(bp.mobmod, 0);
+        assert_eq!(bp.capmod, 2);
+        assert_eq!(bp.cgdo, 2.5e-10);
+        assert!((bp.tnom - 303.15).abs() < 1e-9);
+
+        // Unspecified parameters keep the NMOS defaults.
+        assert_eq!(bp.ua, defaults.ua);
+        assert_eq!(bp.pclm, defaults.pclm);
+    }
+
+    #[test]
+    fn test_model_card_level_detection() {
+        let bsim3 = ".MODEL p1 PMOS (LEVEL=49 VTH0=-0.4 TOX=9n)";
+        let bp = Bsim3Params::from_model_card(bsim3).unwrap();
+        assert_eq!(bp.mos_type, MosfetType::Pmos);
+        assert_eq!(bp.vth0, -0.4);
+
+        // Right card, wrong level.
+        let err = Bsim4Params::from_model_card(bsim3).unwrap_err();
+        assert!(err.to_string().contains("not a BSIM4"), "{err}");
+        assert!(Bsim4Params::from_model_card(".MODEL n1 NMOS (VTO=0.7)").is_err());
+
+        assert!(Bsim4Params::from_model_card("* nothing here\n").is_err());
+        let two = ".MODEL a NMOS LEVEL=54\n.MODEL b NMOS LEVEL=54\n";
+        assert!(Bsim4Params::from_model_card(two).is_err());
+    }
+
+    #[test]
+    fn test_model_card_error_lines() {
+        let err =
+            Bsim4Params::from_model_card(".model n nmos\n+ level = 54\n+ vth0 = ?\n").unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{err}");
+    }
+}
@@ -1,4 +1,5 @@
-//! Command parsing (.DC, .AC, .TRAN, .IC, .PRINT, .MODEL, .PARAM, .SUBCKT, .ENDS).
+//! Command parsing (.DC, .AC, .TRAN, .IC, .PRINT, .MODEL, .PARAM, .SUBCKT, .ENDS,
+//! .TEMP, .OPTIONS).
 
 use std::collections::{HashMap, HashSet};
 
@@ -68,17 +69,66 @@ impl<'a> Parser<'a> {
             "NOISE" => {
                 self.parse_noise_command(line)?;
             }
-            _ => {
-                // Unknown command - skip to EOL
+            "TEMP" => {
+                self.parse_temp_command(line)?;
+            }
+            "OPTIONS" | "OPTION" | "OPT" => {
+                self.parse_options_command(line)?;
+            }
+            // Output control only; the simulation is unaffected.
+            "SAVE" | "PROBE" | "PLOT" | "WIDTH" => {
                 self.skip_to_eol();
             }
+            _ => {
+                self.skip_unsupported(format!(".{}", cmd), line);
+            }
         }
 
         Ok(())
     }
 
+    /// Parse .TEMP t [t2 ...]
+    ///
+    /// Only the first temperature is used; temperature sweeps are not supported.
+    fn parse_temp_command(&mut self, line: usize) -> Result<()> {
+        let celsius = self.expect_value(line)?;
+        self.temperature = Some(celsius + 273.15);
+        self.skip_to_eol();
+        Ok(())
+    }
+
+    /// Parse .OPTIONS name[=value] ...
+    ///
+    /// Only TEMP is recognized; other options are ignored, whether their
+    /// values are numbers or names (e.g. `METHOD=GEAR`).
+    fn parse_options_command(&mut self, line: usize) -> Result<()> {
+        loop {
+            match self.peek() {
+                Token::Eol | Token::Eof => break,
+                Token::Name(name) => {
+                    let upper = name.to_uppercase();
+                    self.advance();
+                    if matches!(self.peek(), Token::Equals) {
+                        self.advance(); // consume =
+                        if upper == "TEMP" {
+                            let value = self.expect_value(line)?;
+                            self.temperature = Some(value + 273.15);
+                        } else if !matches!(self.peek(), Token::Eol | Token::Eof) {
+                            self.advance(); // skip the value
+                        }
+                    }
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Parse .DC source start stop step [source2 start2 stop2 step2]
     /// Also supports .DC PARAM name start stop step for parameter sweeps
+    /// and .DC TEMP start stop step for temperature sweeps
     fn parse_dc_command(&mut self, line: usize) -> Result<()> {
         let mut sweeps = Vec::new();
 
@@ -103,6 +153,10 @@ impl<'a> Parser<'a> {
                         }
                     };
                     (param_name, DcSweepType::Param)
+                } else if n_upper == "TEMP" {
+                    // .DC TEMP start stop step
+                    self.advance();
+                    ("TEMP".to_string(), DcSweepType::Temp)
                 } else {
                     let n = n.clone();
                     self.advance();
@@ -148,6 +202,9 @@ impl<'a> Parser<'a> {
                     }
                 };
                 (param_name, DcSweepType::Param)
+            } else if n_upper == "TEMP" {
+                self.advance();
+                ("TEMP".to_string(), DcSweepType::Temp)
             } else {
                 let n = n.clone();
                 self.advance();
@@ -646,6 +703,18 @@ impl<'a> Parser<'a> {
             }
         }
 
+        // Apply pinned overrides, then record the card as evaluated.
+        for (name, value) in self.parameter_table.pinned_model_params(&model_name) {
+            match params.iter_mut().find(|(k, _)| *k == name) {
+                Some(param) => param.1 = value,
+                None => params.push((name, value)),
+            }
+        }
+        for (name, value) in &params {
+            self.parameter_table
+                .record_model_param(&model_name, name, *value);
+        }
+
         let model_type_upper = model_type.to_uppercase();
         let model = match model_type_upper.as_str() {
             "C" | "CAP" => {
@@ -662,6 +731,10 @@ impl<'a> Parser<'a> {
                         "CJO" | "CJ0" => dp.cj0 = *v,
                         "VJ" => dp.vj = *v,
                         "BV" => dp.bv = *v,
+                        "IBV" => dp.ibv = *v,
+                        "EG" => dp.eg = *v,
+                        "XTI" => dp.xti = *v,
+                        "TNOM" => dp.tnom = *v + 273.15,
                         _ => {}
                     }
                 }
@@ -866,6 +939,10 @@ impl<'a> Parser<'a> {
                         self.advance();
                         params = self.parse_param_defaults(line)?;
                         break;
+                    } else if self.is_next_equals() {
+                        // Bare `W=1u` defaults without the PARAMS: keyword
+                        params = self.parse_param_defaults(line)?;
+                        break;
                     } else {
                         ports.push(n.clone());
                         self.advance();
@@ -1034,9 +1111,12 @@ impl<'a> Parser<'a> {
                             }
                         })?;
 
-                    // Evaluate immediately (parameters are compile-time constants)
-                    let ctx = EvalContext::params_only(&self.parameters);
-                    let value = expr.eval(&ctx);
+                    // Evaluate immediately (parameters are compile-time
+                    // constants), unless the value is pinned.
+                    let value = self
+                        .parameter_table
+                        .pinned_param(&pname)
+                        .unwrap_or_else(|| expr.eval(&EvalContext::params_only(&self.parameters)));
 
                     self.parameters.insert(pname, value);
                 }
@@ -1106,6 +1186,11 @@ impl<'a> Parser<'a> {
                     tokens.push(v.clone());
                     self.advance();
                 }
+                Token::CurlyExpr(expr) => {
+                    // `.param B={A*5}`: the braces only delimit the expression
+                    tokens.push(format!("({})", expr));
+                    self.advance();
+                }
                 Token::Equals if paren_depth == 0 => {
                     // Unexpected equals outside parens - stop
                     break;
@@ -1120,7 +1205,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Check if the next non-whitespace token is '='.
-    fn is_next_equals(&self) -> bool {
+    pub(super) fn is_next_equals(&self) -> bool {
         // Look at position + 1 for the next token
         if self.pos + 1 < self.tokens.len() {
             matches!(self.tokens[self.pos + 1].token, Token::Equals)
@@ -1,5 +1,6 @@
 //! Element parsing (R, C, L, V, I, D, M, J, Q, K, E, G, F, H, B, T).
 
+use spicier_core::NodeId;
 use spicier_devices::behavioral::{BehavioralCurrentSource, BehavioralVoltageSource};
 use spicier_devices::bjt::{Bjt, BjtParams, BjtType};
 use spicier_devices::controlled::{Cccs, Ccvs, Vccs, Vcvs};
@@ -54,8 +55,7 @@ impl<'a> Parser<'a> {
             'T' => self.parse_transmission_line(name, line),
             'X' => self.parse_subcircuit_instance(name, line),
             _ => {
-                // Unknown element - skip line
-                self.skip_to_eol();
+                self.skip_unsupported(name.to_string(), line);
                 Ok(())
             }
         }
@@ -106,6 +106,7 @@ impl<'a> Parser<'a> {
         parts.join(" ")
     }
 
+    /// Parse Rname n+ n- value [TC1=val] [TC2=val] [TC=tc1[,tc2]] [M=val]
     fn parse_resistor(&mut self, name: &str, line: usize) -> Result<()> {
         self.advance(); // consume name
 
@@ -113,10 +114,25 @@ impl<'a> Parser<'a> {
         let node_neg = self.expect_node(line)?;
         let value = self.expect_value(line)?;
 
-        let resistor = Resistor::new(name, node_pos, node_neg, value);
+        let mut resistor = Resistor::new(name, node_pos, node_neg, value);
+        let multiplier = self.multiplier_and_params_to_eol(line, |parser, pname| {
+            match pname {
+                "TC1" => resistor.tc1 = parser.expect_value(line)?,
+                "TC2" => resistor.tc2 = parser.expect_value(line)?,
+                "TC" => {
+                    // TC=tc1[,tc2]
+                    resistor.tc1 = parser.expect_value(line)?;
+                    if matches!(parser.peek(), Token::Comma) {
+                        parser.advance();
+                        resistor.tc2 = parser.expect_value(line)?;
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+        resistor.multiplier = multiplier;
         self.netlist.add_device(resistor);
-
-        self.skip_to_eol();
         Ok(())
     }
 
@@ -128,9 +144,9 @@ impl<'a> Parser<'a> {
 
         // Try to parse a numeric value first (bare value syntax: C1 n1 n2 10p)
         if let Some(value) = self.try_value() {
-            let capacitor = Capacitor::new(name, node_pos, node_neg, value);
+            let mut capacitor = Capacitor::new(name, node_pos, node_neg, value);
+            capacitor.multiplier = self.multiplier_to_eol(line)?;
             self.netlist.add_device(capacitor);
-            self.skip_to_eol();
             return Ok(());
         }
 
@@ -138,7 +154,7 @@ impl<'a> Parser<'a> {
         let mut params: Option<CapacitorParams> = None;
         if let Token::Name(n) = self.peek() {
             let model_name = n.clone().to_uppercase();
-            if !model_name.contains('=') && model_name != "W" && model_name != "L" {
+            if !model_name.contains('=') && !matches!(model_name.as_str(), "W" | "L" | "M") {
                 self.advance();
                 if let Some(ModelDefinition::Capacitor(cp)) = self.models.get(&model_name) {
                     params = Some(cp.clone());
@@ -147,7 +163,8 @@ impl<'a> Parser<'a> {
         }
 
         if let Some(mut cp) = params {
-            // Parse optional instance parameters: W=val L=val C=val
+            // Parse optional instance parameters: W=val L=val C=val M=val
+            let mut multiplier = 1.0;
             loop {
                 match self.peek() {
                     Token::Eol | Token::Eof => break,
@@ -161,6 +178,7 @@ impl<'a> Parser<'a> {
                                     "W" => cp.w = val,
                                     "L" => cp.l = val,
                                     "C" => cp.c_base = val,
+                                    "M" | "MULT" => multiplier = val,
                                     _ => {}
                                 }
                             }
@@ -172,16 +190,21 @@ impl<'a> Parser<'a> {
                 }
             }
 
-            let capacitor = Capacitor::with_params(name, node_pos, node_neg, cp);
+            if multiplier <= 0.0 {
+                return Err(Self::nonpositive_multiplier(multiplier, line));
+            }
+            let mut capacitor = Capacitor::with_params(name, node_pos, node_neg, cp);
+            capacitor.multiplier = multiplier;
             self.netlist.add_device(capacitor);
+            self.skip_to_eol();
         } else {
             // No model and no value - try to parse as value (will produce error)
             let value = self.expect_value(line)?;
-            let capacitor = Capacitor::new(name, node_pos, node_neg, value);
+            let mut capacitor = Capacitor::new(name, node_pos, node_neg, value);
+            capacitor.multiplier = self.multiplier_to_eol(line)?;
             self.netlist.add_device(capacitor);
         }
 
-        self.skip_to_eol();
         Ok(())
     }
 
@@ -195,10 +218,9 @@ impl<'a> Parser<'a> {
         let current_index = self.next_current_index;
         self.next_current_index += 1;
 
-        let inductor = Inductor::new(name, node_pos, node_neg, value, current_index);
+        let mut inductor = Inductor::new(name, node_pos, node_neg, value, current_index);
+        inductor.multiplier = self.multiplier_to_eol(line)?;
         self.netlist.add_device(inductor);
-
-        self.skip_to_eol();
         Ok(())
     }
 
@@ -211,8 +233,9 @@ impl<'a> Parser<'a> {
         let current_index = self.next_current_index;
         self.next_current_index += 1;
 
-        // Parse source specification: [DC value] [AC mag [phase]] [PULSE|SIN|PWL]
+        // Parse source specification: [DC value] [AC mag [phase]] [PULSE|SIN|EXP|SFFM|AM|PWL]
         let mut dc_value = 0.0;
+        let mut ac_magnitude = None;
         let mut waveform: Option<spicier_devices::Waveform> = None;
 
         // Keep parsing until we hit end of line or no more valid tokens
@@ -226,10 +249,9 @@ impl<'a> Parser<'a> {
                             dc_value = self.expect_value(line)?;
                         }
                         "AC" => {
-                            // Skip AC specification for now (used in AC analysis)
                             self.advance();
-                            let _ = self.expect_value(line)?; // mag
-                            // Optional phase
+                            ac_magnitude = Some(self.expect_value(line)?);
+                            // Optional phase (not yet used by AC analysis)
                             if let Token::Value(_) = self.peek() {
                                 let _ = self.expect_value(line)?;
                             }
@@ -242,6 +264,18 @@ impl<'a> Parser<'a> {
                             self.advance();
                             waveform = Some(self.parse_sin_waveform(line)?);
                         }
+                        "EXP" => {
+                            self.advance();
+                            waveform = Some(self.parse_exp_waveform(line)?);
+                        }
+                        "SFFM" => {
+                            self.advance();
+                            waveform = Some(self.parse_sffm_waveform(line)?);
+                        }
+                        "AM" => {
+                            self.advance();
+                            waveform = Some(self.parse_am_waveform(line)?);
+                        }
                         "PWL" => {
                             self.advance();
                             waveform = Some(self.parse_pwl_waveform(line)?);
@@ -257,10 +291,11 @@ impl<'a> Parser<'a> {
             }
         }
 
-        let vsource = match waveform {
+        let mut vsource = match waveform {
             Some(w) => VoltageSource::with_waveform(name, node_pos, node_neg, w, current_index),
             None => VoltageSource::new(name, node_pos, node_neg, dc_value, current_index),
         };
+        vsource.ac_magnitude = ac_magnitude;
         self.netlist.add_device(vsource);
 
         self.skip_to_eol();
@@ -276,14 +311,13 @@ impl<'a> Parser<'a> {
         // Value can be DC value or just a number
         let value = self.expect_value_or_dc(line)?;
 
-        let isource = CurrentSource::new(name, node_pos, node_neg, value);
+        let mut isource = CurrentSource::new(name, node_pos, node_neg, value);
+        isource.multiplier = self.multiplier_to_eol(line)?;
         self.netlist.add_device(isource);
-
-        self.skip_to_eol();
         Ok(())
     }
 
-    /// Parse D1 anode cathode [modelname]
+    /// Parse D1 anode cathode [modelname] [M=val]
     fn parse_diode(&mut self, name: &str, line: usize) -> Result<()> {
         self.advance(); // consume name
 
@@ -291,7 +325,9 @@ impl<'a> Parser<'a> {
         let node_neg = self.expect_node(line)?; // cathode
 
         // Optional model name
-        let params = if let Token::Name(n) = self.peek() {
+        let params = if let Token::Name(n) = self.peek()
+            && !matches!(n.to_uppercase().as_str(), "M" | "MULT")
+        {
             let model_name = n.clone().to_uppercase();
             self.advance();
             if let Some(ModelDefinition::Diode(dp)) = self.models.get(&model_name) {
@@ -303,10 +339,9 @@ impl<'a> Parser<'a> {
             DiodeParams::default()
         };
 
-        let diode = Diode::with_params(name, node_pos, node_neg, params);
+        let mut diode = Diode::with_params(name, node_pos, node_neg, params);
+        diode.multiplier = self.multiplier_to_eol(line)?;
         self.netlist.add_device(diode);
-
-        self.skip_to_eol();
         Ok(())
     }
 
@@ -580,7 +615,7 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    /// Parse Q1 collector base emitter [modelname]
+    /// Parse Q1 collector base emitter [substrate] [modelname]
     fn parse_bjt(&mut self, name: &str, line: usize) -> Result<()> {
         self.advance(); // consume name
 
@@ -588,6 +623,24 @@ impl<'a> Parser<'a> {
         let node_base = self.expect_node(line)?;
         let node_emitter = self.expect_node(line)?;
 
+        // A fourth node is the substrate when a BJT model name follows it
+        let is_bjt_model = |token: Option<&Token>| match token {
+            Some(Token::Name(n)) => matches!(
+                self.models.get(&n.to_uppercase()),
+                Some(ModelDefinition::Npn(_) | ModelDefinition::Pnp(_))
+            ),
+            _ => false,
+        };
+        let next = self.tokens.get(self.pos + 1).map(|t| &t.token);
+        let node_substrate = if matches!(self.peek(), Token::Name(_) | Token::Value(_))
+            && !is_bjt_model(Some(self.peek()))
+            && is_bjt_model(next)
+        {
+            self.expect_node(line)?
+        } else {
+            NodeId::GROUND
+        };
+
         // Optional model name
         let mut bjt_type = BjtType::Npn;
         let mut params = BjtParams::npn_default();
@@ -618,7 +671,8 @@ impl<'a> Parser<'a> {
             node_emitter,
             bjt_type,
             params,
-        );
+        )
+        .with_substrate(node_substrate);
         self.netlist.add_device(bjt);
 
         self.skip_to_eol();
@@ -669,6 +723,11 @@ impl<'a> Parser<'a> {
         }
 
         let coupling = self.expect_value(line)?;
+        // Branch index and effective inductance of each inductor parsed so far
+        let resolved: Vec<Option<(usize, f64)>> = inductor_names
+            .iter()
+            .map(|l| Some((self.find_branch_index(l)?, self.find_inductance(l)?)))
+            .collect();
 
         // For 2 inductors, create a single mutual inductance (original behavior)
         // For N > 2 inductors, create N*(N-1)/2 pairwise couplings
@@ -677,11 +736,8 @@ impl<'a> Parser<'a> {
                 MutualInductance::new(name, &inductor_names[0], &inductor_names[1], coupling);
 
             // Try to resolve the inductor references immediately if they exist
-            if let (Some(l1_idx), Some(l2_idx)) = (
-                self.netlist.find_vsource_branch_index(&inductor_names[0]),
-                self.netlist.find_vsource_branch_index(&inductor_names[1]),
-            ) {
-                mutual.resolve(l1_idx, l2_idx, 0.0, 0.0);
+            if let (Some((l1_idx, l1)), Some((l2_idx, l2))) = (resolved[0], resolved[1]) {
+                mutual.resolve(l1_idx, l2_idx, l1, l2);
             }
 
             self.netlist.add_device(mutual);
@@ -698,11 +754,8 @@ impl<'a> Parser<'a> {
                     );
 
                     // Try to resolve immediately
-                    if let (Some(li_idx), Some(lj_idx)) = (
-                        self.netlist.find_vsource_branch_index(&inductor_names[i]),
-                        self.netlist.find_vsource_branch_index(&inductor_names[j]),
-                    ) {
-                        mutual.resolve(li_idx, lj_idx, 0.0, 0.0);
+                    if let (Some((li_idx, li)), Some((lj_idx, lj))) = (resolved[i], resolved[j]) {
+                        mutual.resolve(li_idx, lj_idx, li, lj);
                     }
 
                     self.netlist.add_device(mutual);
@@ -770,16 +823,15 @@ impl<'a> Parser<'a> {
 
         // Defer branch index resolution: store the name for now, resolve after parsing
         // For simplicity, look up the vsource branch index from the netlist
-        let branch_idx = self
-            .netlist
-            .find_vsource_branch_index(&vsource_name)
-            .ok_or_else(|| Error::ParseError {
-                line,
-                message: format!(
-                    "CCCS '{}' references unknown voltage source '{}'",
-                    name, vsource_name
-                ),
-            })?;
+        let branch_idx =
+            self.find_branch_index(&vsource_name)
+                .ok_or_else(|| Error::ParseError {
+                    line,
+                    message: format!(
+                        "CCCS '{}' references unknown voltage source '{}'",
+                        name, vsource_name
+                    ),
+                })?;
 
         let cccs = Cccs::new(name, out_pos, out_neg, branch_idx, gain);
         self.netlist.add_device(cccs);
@@ -797,16 +849,15 @@ impl<'a> Parser<'a> {
         let vsource_name = self.expect_name(line)?;
         let gain = self.expect_value(line)?;
 
-        let vsource_branch_idx = self
-            .netlist
-            .find_vsource_branch_index(&vsource_name)
-            .ok_or_else(|| Error::ParseError {
-                line,
-                message: format!(
-                    "CCVS '{}' references unknown voltage source '{}'",
-                    name, vsource_name
-                ),
-            })?;
+        let vsource_branch_idx =
+            self.find_branch_index(&vsource_name)
+                .ok_or_else(|| Error::ParseError {
+                    line,
+                    message: format!(
+                        "CCVS '{}' references unknown voltage source '{}'",
+                        name, vsource_name
+                    ),
+                })?;
 
         let current_index = self.next_current_index;
         self.next_current_index += 1;
@@ -961,6 +1012,56 @@ impl<'a> Parser<'a> {
         self.expect_value(line)
     }
 
+    /// Consume the rest of the line, returning the device multiplier given
+    /// as `M=val` (or `MULT=val`), or 1.0 if there is none.
+    ///
+    /// Other instance parameters on the line are skipped.
+    pub(super) fn multiplier_to_eol(&mut self, line: usize) -> Result<f64> {
+        self.multiplier_and_params_to_eol(line, |_, _| Ok(()))
+    }
+
+    /// Like [`multiplier_to_eol`](Self::multiplier_to_eol), but every other
+    /// `NAME=` parameter is passed to `param` by its uppercase name, with the
+    /// parser positioned at the value. `param` may leave the value unread.
+    pub(super) fn multiplier_and_params_to_eol(
+        &mut self,
+        line: usize,
+        mut param: impl FnMut(&mut Self, &str) -> Result<()>,
+    ) -> Result<f64> {
+        let mut multiplier = 1.0;
+        loop {
+            match self.peek() {
+                Token::Eol | Token::Eof => break,
+                Token::Name(n) => {
+                    let pname = n.to_uppercase();
+                    self.advance();
+                    if !matches!(self.peek(), Token::Equals) {
+                        continue;
+                    }
+                    self.advance(); // consume '='
+                    match pname.as_str() {
+                        "M" | "MULT" => multiplier = self.expect_value(line)?,
+                        _ => param(self, &pname)?,
+                    }
+                }
+                _ => self.advance(),
+            }
+        }
+        self.skip_to_eol();
+
+        if multiplier <= 0.0 {
+            return Err(Self::nonpositive_multiplier(multiplier, line));
+        }
+        Ok(multiplier)
+    }
+
+    fn nonpositive_multiplier(multiplier: f64, line: usize) -> Error {
+        Error::ParseError {
+            line,
+            message: format!("device multiplier M must be positive, got {}", multiplier),
+        }
+    }
+
     /// Parse T1 port1+ port1- port2+ port2- Z0=val TD=val [NL=val]
     fn parse_transmission_line(&mut self, name: &str, line: usize) -> Result<()> {
         self.advance(); // consume name
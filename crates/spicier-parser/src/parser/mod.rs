@@ -1,8 +1,9 @@
 //! SPICE netlist parser.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use spicier_core::{Netlist, NodeId, units::parse_value};
+use spicier_core::{AcDeviceInfo, Netlist, NodeId, ParameterTable, units::parse_value};
 use spicier_devices::bjt::BjtParams;
 use spicier_devices::diode::DiodeParams;
 use spicier_devices::jfet::JfetParams;
@@ -17,6 +18,7 @@ use std::collections::HashSet;
 
 mod commands;
 mod elements;
+mod streaming;
 mod subcircuit;
 pub mod types;
 mod waveforms;
@@ -24,9 +26,12 @@ mod waveforms;
 pub use types::{
     AcSweepType, AnalysisCommand, DcSweepSpec, DcSweepType, InitialCondition, MeasureAnalysis,
     MeasureType, Measurement, OutputVariable, ParseResult, PrintAnalysisType, PrintCommand,
-    RawElementLine, StatFunc, SubcircuitDefinition, TriggerType,
+    RawElementLine, StatFunc, SubcircuitDefinition, TriggerType, UnsupportedConstruct,
+    UnsupportedPolicy,
 };
 
+pub use streaming::parse_streaming;
+
 use types::SubcircuitDefinition as SubcircuitDef;
 
 /// Context for resolving parameter values during subcircuit expansion.
@@ -119,11 +124,87 @@ pub fn parse(input: &str) -> Result<Netlist> {
 }
 
 /// Parse a SPICE netlist string, returning both circuit and analysis commands.
+///
+/// The netlist keeps its `.PARAM` and model card values and can rebuild
+/// itself with new ones; see [`ParameterHandle`](spicier_core::ParameterHandle).
+///
+/// Device letters and directives spicier cannot model are an
+/// [`Error::Unsupported`]; use [`parse_full_with_policy`] to skip them
+/// instead.
 pub fn parse_full(input: &str) -> Result<ParseResult> {
-    let lexer = Lexer::new(input);
-    let tokens = lexer.tokenize()?;
-    let parser = Parser::new(&tokens);
-    parser.parse_all()
+    parse_full_with_parameters(input, &ParameterTable::new())
+}
+
+/// Parse a SPICE netlist string, handling unsupported devices and
+/// directives according to `policy`.
+///
+/// Under [`UnsupportedPolicy::Warn`] the supported part of the circuit is
+/// returned and the skipped lines are listed in [`ParseResult::unsupported`].
+pub fn parse_full_with_policy(input: &str, policy: UnsupportedPolicy) -> Result<ParseResult> {
+    parse_rebuildable(Arc::from(input), &ParameterTable::new(), policy)
+}
+
+/// Parse a SPICE netlist string with `.PARAM` values and model card
+/// parameters replaced by the ones pinned in `overrides`.
+///
+/// Pinned `.PARAM`s take the given value instead of their expression;
+/// ones the netlist does not define are added. Pinned model parameters
+/// replace or extend the card.
+pub fn parse_full_with_parameters(input: &str, overrides: &ParameterTable) -> Result<ParseResult> {
+    parse_rebuildable(Arc::from(input), overrides, UnsupportedPolicy::Error)
+}
+
+/// Parse `input` and install a rebuild function that re-parses it.
+fn parse_rebuildable(
+    input: Arc<str>,
+    overrides: &ParameterTable,
+    policy: UnsupportedPolicy,
+) -> Result<ParseResult> {
+    let tokens = Lexer::new(&input).tokenize()?;
+    let mut parser = Parser::new(&tokens);
+    parser.parameters = overrides
+        .pinned_params()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    parser.parameter_table = overrides.clone();
+    let mut result = parser.parse_all()?.check_unsupported(policy)?;
+
+    result.netlist.parameters_mut().set_rebuild(move |table| {
+        parse_rebuildable(input.clone(), table, policy)
+            .map(|r| r.netlist)
+            .map_err(|e| spicier_core::Error::InvalidCircuit(e.to_string()))
+    });
+    Ok(result)
+}
+
+impl ParseResult {
+    /// Apply `policy` to the constructs the parser skipped.
+    pub(crate) fn check_unsupported(mut self, policy: UnsupportedPolicy) -> Result<Self> {
+        if policy == UnsupportedPolicy::Error && !self.unsupported.is_empty() {
+            return Err(Error::Unsupported(std::mem::take(&mut self.unsupported)));
+        }
+        Ok(self)
+    }
+}
+
+/// Parse only the `.MODEL` and `.PARAM` definitions in `input`, keyed by
+/// uppercase model name. Unlike [`parse_full`], the input has no title line.
+pub(crate) fn parse_models(input: &str) -> Result<HashMap<String, ModelDefinition>> {
+    // The lexer always treats the first line as the title, which can never
+    // be continued, so give it one and shift line numbers back.
+    let input = format!("*\n{}", input);
+    let models = Lexer::new(&input).tokenize().and_then(|tokens| {
+        let mut parser = Parser::new(&tokens);
+        parser.parse_definitions()?;
+        Ok(parser.models)
+    });
+    models.map_err(|e| match e {
+        Error::ParseError { line, message } => Error::ParseError {
+            line: line.saturating_sub(1),
+            message,
+        },
+        e => e,
+    })
 }
 
 /// A model definition from .MODEL command.
@@ -169,8 +250,19 @@ pub(crate) struct Parser<'a> {
     pub(crate) current_subckt: Option<SubcircuitDef>,
     /// Parameters from .PARAM commands (stored as uppercase keys).
     pub(crate) parameters: HashMap<String, f64>,
+    /// Requested overrides, then the model cards as parsed; becomes the
+    /// netlist's parameter table.
+    pub(crate) parameter_table: ParameterTable,
     /// Measurement statements from .MEAS commands.
     pub(crate) measurements: Vec<types::Measurement>,
+    /// Circuit temperature (K) from .TEMP or .OPTIONS TEMP=.
+    pub(crate) temperature: Option<f64>,
+    /// Branch indices of devices already streamed out (uppercase name keys).
+    pub(crate) streamed_branches: HashMap<String, usize>,
+    /// Effective inductances of inductors already streamed out (uppercase name keys).
+    pub(crate) streamed_inductances: HashMap<String, f64>,
+    /// Devices and directives skipped because they cannot be modeled.
+    pub(crate) unsupported: Vec<UnsupportedConstruct>,
 }
 
 impl<'a> Parser<'a> {
@@ -194,7 +286,12 @@ impl<'a> Parser<'a> {
             subcircuits: HashMap::new(),
             current_subckt: None,
             parameters: HashMap::new(),
+            parameter_table: ParameterTable::new(),
             measurements: Vec::new(),
+            temperature: None,
+            streamed_branches: HashMap::new(),
+            streamed_inductances: HashMap::new(),
+            unsupported: Vec::new(),
         }
     }
 
@@ -208,6 +305,17 @@ impl<'a> Parser<'a> {
         // Two-pass parsing to handle forward model references and parameters:
         // Pass 1: Scan for all .MODEL and .PARAM commands first
         let saved_pos = self.pos;
+        self.parse_definitions()?;
+
+        // Reset position and parse everything
+        self.pos = saved_pos;
+        self.parse_statements()?;
+
+        Ok(self.into_result())
+    }
+
+    /// Pass 1: parse the `.MODEL` and `.PARAM` commands, skipping everything else.
+    fn parse_definitions(&mut self) -> Result<()> {
         while !self.is_at_end() {
             self.skip_eol();
             if self.is_at_end() {
@@ -231,10 +339,11 @@ impl<'a> Parser<'a> {
                 self.skip_to_eol();
             }
         }
+        Ok(())
+    }
 
-        // Reset position and parse everything
-        self.pos = saved_pos;
-
+    /// Pass 2: parse elements and commands.
+    fn parse_statements(&mut self) -> Result<()> {
         while !self.is_at_end() {
             self.skip_eol();
             if self.is_at_end() {
@@ -254,8 +363,16 @@ impl<'a> Parser<'a> {
                 }
             }
         }
+        Ok(())
+    }
+
+    fn into_result(mut self) -> ParseResult {
+        for (name, value) in &self.parameters {
+            self.parameter_table.record_param(name, *value);
+        }
+        *self.netlist.parameters_mut() = self.parameter_table;
 
-        Ok(ParseResult {
+        ParseResult {
             netlist: self.netlist,
             analyses: self.analyses,
             initial_conditions: self.initial_conditions,
@@ -264,7 +381,62 @@ impl<'a> Parser<'a> {
             subcircuits: self.subcircuits,
             parameters: self.parameters,
             measurements: self.measurements,
-        })
+            temperature: self.temperature,
+            unsupported: self.unsupported,
+        }
+    }
+
+    /// Note a line that cannot be modeled and skip the rest of it.
+    pub(crate) fn skip_unsupported(&mut self, name: String, line: usize) {
+        self.unsupported.push(UnsupportedConstruct { line, name });
+        self.skip_to_eol();
+    }
+
+    /// Point the parser at a new token slice, keeping everything parsed so far.
+    fn retarget<'b>(self, tokens: &'b [SpannedToken]) -> Parser<'b> {
+        Parser {
+            tokens,
+            pos: 0,
+            netlist: self.netlist,
+            analyses: self.analyses,
+            initial_conditions: self.initial_conditions,
+            print_commands: self.print_commands,
+            node_map: self.node_map,
+            next_current_index: self.next_current_index,
+            models: self.models,
+            subcircuits: self.subcircuits,
+            current_subckt: self.current_subckt,
+            parameters: self.parameters,
+            parameter_table: self.parameter_table,
+            measurements: self.measurements,
+            temperature: self.temperature,
+            streamed_branches: self.streamed_branches,
+            streamed_inductances: self.streamed_inductances,
+            unsupported: self.unsupported,
+        }
+    }
+
+    /// Branch index of a named device, including devices already handed off
+    /// by [`parse_streaming`].
+    pub(crate) fn find_branch_index(&self, name: &str) -> Option<usize> {
+        self.netlist
+            .find_vsource_branch_index(name)
+            .or_else(|| self.streamed_branches.get(&name.to_uppercase()).copied())
+    }
+
+    /// Effective inductance of a named inductor, multiplier included, from
+    /// the netlist or the devices already handed off by [`parse_streaming`].
+    pub(crate) fn find_inductance(&self, name: &str) -> Option<f64> {
+        let name_upper = name.to_uppercase();
+        self.netlist
+            .devices()
+            .iter()
+            .filter(|device| device.device_name().to_uppercase() == name_upper)
+            .find_map(|device| match device.ac_info() {
+                AcDeviceInfo::Inductor { inductance, .. } => Some(inductance),
+                _ => None,
+            })
+            .or_else(|| self.streamed_inductances.get(&name_upper).copied())
     }
 
     fn parse_title(&mut self) -> Option<String> {
@@ -527,8 +699,11 @@ impl<'a> Parser<'a> {
             }
             let id = NodeId::new(num);
             // Check if this ID is already used by a different node name
-            // (can happen if named nodes were assigned sequential IDs)
-            let conflict = self.node_map.iter().any(|(k, &v)| v == id && k != name);
+            // (can happen if named nodes were assigned sequential IDs). IDs above
+            // the current maximum are always free, which skips the scan on the
+            // usual ascending numbering.
+            let conflict = num as usize <= self.netlist.num_nodes()
+                && self.node_map.iter().any(|(k, &v)| v == id && k != name);
             if !conflict {
                 self.node_map.insert(name.to_string(), id);
                 self.netlist.register_node(id);
@@ -537,15 +712,9 @@ impl<'a> Parser<'a> {
             // Fall through to assign a new unique ID if there's a conflict
         }
 
-        // Named node (or numeric node with ID conflict) - assign next available ID
-        let max_id = self
-            .node_map
-            .values()
-            .filter(|id| !id.is_ground())
-            .map(|id| id.as_u32())
-            .max()
-            .unwrap_or(0);
-        let id = NodeId::new(max_id + 1);
+        // Named node (or numeric node with ID conflict) - assign next available ID.
+        // Every mapped node is registered, so the netlist's count is the max ID.
+        let id = NodeId::new(self.netlist.num_nodes() as u32 + 1);
         self.node_map.insert(name.to_string(), id);
         self.netlist.register_node(id);
         id
@@ -562,14 +731,7 @@ impl<'a> Parser<'a> {
         }
 
         // Assign next available ID
-        let max_id = self
-            .node_map
-            .values()
-            .filter(|id| !id.is_ground())
-            .map(|id| id.as_u32())
-            .max()
-            .unwrap_or(0);
-        let id = NodeId::new(max_id + 1);
+        let id = NodeId::new(self.netlist.num_nodes() as u32 + 1);
         self.node_map.insert(name.to_string(), id);
         self.netlist.register_node(id);
         id
@@ -579,6 +741,8 @@ impl<'a> Parser<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nalgebra::DVector;
+    use spicier_core::netlist::AcDeviceInfo;
 
     #[test]
     fn test_parse_simple_circuit() {
@@ -610,6 +774,88 @@ R2 2 0 1k
         assert_eq!(netlist.num_current_vars(), 1); // One voltage source
     }
 
+    #[test]
+    fn test_parse_exp_sffm_am_waveforms() {
+        let input = r#"Waveform Sources
+V1 1 0 SIN(1 2 1k 0.5m 200 30)
+V2 2 0 EXP(0 5 1m 0.2m 3m 0.5m)
+V3 3 0 SFFM(0.5 1 10k 3 1k 45 90)
+V4 4 0 AM(2 1 1k 20k 0.1m 90 0)
+R1 1 0 1k
+R2 2 0 1k
+R3 3 0 1k
+R4 4 0 1k
+.end
+"#;
+
+        let netlist = parse(input).unwrap();
+        let expected = [
+            spicier_devices::Waveform::sin_full(1.0, 2.0, 1e3, 0.5e-3, 200.0, 30.0),
+            spicier_devices::Waveform::exp(0.0, 5.0, 1e-3, 0.2e-3, 3e-3, 0.5e-3),
+            spicier_devices::Waveform::sffm(0.5, 1.0, 10e3, 3.0, 1e3, 45.0, 90.0),
+            spicier_devices::Waveform::am(2.0, 1.0, 1e3, 20e3, 0.1e-3, 90.0, 0.0),
+        ];
+
+        // Each source drives its branch RHS entry with the waveform value.
+        for t in [0.0, 0.3e-3, 1.2e-3, 3.4e-3] {
+            let mut mna =
+                spicier_core::mna::MnaSystem::new(netlist.num_nodes(), netlist.num_current_vars());
+            for device in netlist.devices() {
+                device.stamp_at_time(&mut mna, t);
+            }
+            for (k, w) in expected.iter().enumerate() {
+                let rhs = mna.rhs()[netlist.num_nodes() + k];
+                assert!(
+                    (rhs - w.value_at(t)).abs() < 1e-12,
+                    "V{} at t = {}: {} vs {}",
+                    k + 1,
+                    t,
+                    rhs,
+                    w.value_at(t)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_pwl_repeat() {
+        let input = r#"PWL Repeat
+V1 1 0 PWL(0 0 1m 1 2m 0) R=1m
+V2 2 0 PWL(0 0 1m 1 2m 0 r=0)
+R1 1 0 1k
+R2 2 0 1k
+.end
+"#;
+
+        let netlist = parse(input).unwrap();
+        let v1 =
+            spicier_devices::Waveform::pwl_repeat(vec![(0.0, 0.0), (1e-3, 1.0), (2e-3, 0.0)], 1e-3);
+        let v2 =
+            spicier_devices::Waveform::pwl_repeat(vec![(0.0, 0.0), (1e-3, 1.0), (2e-3, 0.0)], 0.0);
+        for t in [0.5e-3, 2.25e-3, 3.25e-3, 4.5e-3] {
+            let mut mna =
+                spicier_core::mna::MnaSystem::new(netlist.num_nodes(), netlist.num_current_vars());
+            for device in netlist.devices() {
+                device.stamp_at_time(&mut mna, t);
+            }
+            let n = netlist.num_nodes();
+            assert!(
+                (mna.rhs()[n] - v1.value_at(t)).abs() < 1e-12,
+                "V1 at t = {t}"
+            );
+            assert!(
+                (mna.rhs()[n + 1] - v2.value_at(t)).abs() < 1e-12,
+                "V2 at t = {t}"
+            );
+        }
+        // V1 replays 1m..2m (falling), V2 the whole triangle (rising first)
+        assert!((v1.value_at(2.25e-3) - 0.75).abs() < 1e-9);
+        assert!((v2.value_at(2.25e-3) - 0.25).abs() < 1e-9);
+
+        let err = parse("Bad\nV1 1 0 PWL(0 0 1m 1) R=2m\nR1 1 0 1k\n.end\n");
+        assert!(err.is_err());
+    }
+
     #[test]
     fn test_parse_with_comments() {
         let input = r#"Test Circuit
@@ -622,6 +868,60 @@ R1 1 0 1k ; inline comment
         assert_eq!(netlist.num_devices(), 1);
     }
 
+    /// Assert two netlists stamp the same MNA system.
+    fn assert_same_mna(a: &Netlist, b: &Netlist) {
+        let (a, b) = (a.assemble_mna(), b.assemble_mna());
+        assert_eq!(a.to_dense_matrix(), b.to_dense_matrix());
+        assert_eq!(a.rhs(), b.rhs());
+    }
+
+    #[test]
+    fn test_parse_continuation_splits_element() {
+        let input = r#"Continuations
+V1 1 0
++ DC 5
+R1 1
+* the value comes after a comment
+
++ 2
+  + 2k
+R2 2 0 2k
+.end
+"#;
+        let joined = "Continuations\nV1 1 0 DC 5\nR1 1 2 2k\nR2 2 0 2k\n.end\n";
+
+        let netlist = parse(input).unwrap();
+        assert_eq!(netlist.num_devices(), 3);
+        assert_eq!(netlist.num_nodes(), 2);
+        assert_same_mna(&netlist, &parse(joined).unwrap());
+    }
+
+    #[test]
+    fn test_parse_inline_comment_after_element() {
+        let input = "Inline\nV1 1 0 5 ; AC 1\nR1 1 0 1k;R2 1 0 1k\n.op ; .tran 1u 1m\n.end\n";
+        let plain = "Inline\nV1 1 0 5\nR1 1 0 1k\n.op\n.end\n";
+
+        let result = parse_full(input).unwrap();
+        assert_eq!(result.netlist.num_devices(), 2);
+        assert_eq!(result.analyses.len(), 1);
+        assert_same_mna(&result.netlist, &parse(plain).unwrap());
+    }
+
+    #[test]
+    fn test_parse_device_like_first_line_is_title() {
+        let input = "R1 1 0 1k\nV1 1 0 5\nR2 1 0 2k\n.end\n";
+
+        let netlist = parse(input).unwrap();
+        assert_eq!(netlist.title(), Some("R1 1 0 1k"));
+        let names: Vec<_> = netlist.devices().iter().map(|d| d.device_name()).collect();
+        assert_eq!(names, ["V1", "R2"]);
+
+        // Leading blank lines are skipped; a '*' title is still the title.
+        let netlist = parse("\n\n* Star Title\nR1 1 0 1k\n.end\n").unwrap();
+        assert_eq!(netlist.title(), Some("Star Title"));
+        assert_eq!(netlist.num_devices(), 1);
+    }
+
     #[test]
     fn test_parse_ground_aliases() {
         let input = r#"Ground Test
@@ -871,6 +1171,129 @@ D1 1 0 DMOD
         assert!(netlist.has_nonlinear_devices());
     }
 
+    #[test]
+    fn test_parse_diode_temperature_params() {
+        let models = parse_models(".MODEL DT D (IS=1e-14 N=1.05 EG=0.69 XTI=2 TNOM=50)\n").unwrap();
+        let Some(ModelDefinition::Diode(dp)) = models.get("DT") else {
+            panic!("expected a diode model");
+        };
+        assert_eq!(dp.eg, 0.69);
+        assert_eq!(dp.xti, 2.0);
+        assert!((dp.tnom - 323.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parameter_handles() {
+        use spicier_core::ParameterHandle;
+
+        let input = r#"Parameter Handles
+.PARAM rval=2k
+.PARAM rhalf=rval/2
+.MODEL DMOD D (IS=1e-14)
+V1 1 0 5
+R1 1 2 {rval}
+R2 2 3 rhalf
+D1 3 0 DMOD
+.end
+"#;
+        let mut netlist = parse(input).unwrap();
+        let get =
+            |netlist: &Netlist, spec: &str| ParameterHandle::parse(spec).get(netlist).unwrap();
+        let gd = |netlist: &Netlist| {
+            let sol = DVector::from_vec(vec![0.0, 0.0, 0.6, 0.0]);
+            let d1 = netlist.devices().iter().find(|d| d.device_name() == "D1");
+            match d1.unwrap().ac_info_at(&sol) {
+                AcDeviceInfo::Diode { gd, .. } => gd,
+                _ => panic!("expected a diode"),
+            }
+        };
+
+        // Device and source values are edited in place.
+        assert_eq!(get(&netlist, "V1"), 5.0);
+        ParameterHandle::parse("V1").set(&mut netlist, 3.3).unwrap();
+        assert_eq!(get(&netlist, "V1"), 3.3);
+        ParameterHandle::parse("R2")
+            .set(&mut netlist, 10.0)
+            .unwrap();
+        assert_eq!(get(&netlist, "R2"), 10.0);
+
+        // A .PARAM rebuilds the netlist, re-evaluating dependent values.
+        let rval = ParameterHandle::Param("RVAL".into());
+        assert_eq!(rval.get(&netlist).unwrap(), 2e3);
+        rval.set(&mut netlist, 4e3).unwrap();
+        assert_eq!(rval.get(&netlist).unwrap(), 4e3);
+        assert_eq!(get(&netlist, "R1"), 4e3);
+        assert_eq!(get(&netlist, "R2"), 2e3);
+        assert_eq!(get(&netlist, "V1"), 5.0);
+
+        // So does a model parameter; the new value sticks across rebuilds.
+        let is = ParameterHandle::parse("dmod.is");
+        assert_eq!(is.get(&netlist).unwrap(), 1e-14);
+        let gd_before = gd(&netlist);
+        is.set(&mut netlist, 2e-14).unwrap();
+        assert!((gd(&netlist) / gd_before - 2.0).abs() < 1e-9);
+        rval.set(&mut netlist, 1e3).unwrap();
+        assert_eq!(is.get(&netlist).unwrap(), 2e-14);
+        assert_eq!(get(&netlist, "R2"), 500.0);
+    }
+
+    #[test]
+    fn test_parse_device_multiplier() {
+        let input = r#"Multiplier Test
+.MODEL DMOD D (IS=1e-12)
+I1 0 1 DC 1m M=3
+R1 1 0 1k M=2
+C1 1 0 1p m=4
+L1 1 2 1u MULT=2
+D1 2 0 DMOD M=5
+D2 2 0 M=5
+.end
+"#;
+
+        let netlist = parse(input).unwrap();
+        let device = |name| {
+            netlist
+                .devices()
+                .iter()
+                .find(|d| d.device_name() == name)
+                .unwrap()
+        };
+
+        // 3 × 1 mA into node 1.
+        assert!((netlist.assemble_mna().rhs()[0] - 3e-3).abs() < 1e-15);
+        match device("R1").ac_info() {
+            AcDeviceInfo::Resistor { conductance, .. } => {
+                assert!((conductance - 2e-3).abs() < 1e-15)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match device("C1").ac_info() {
+            AcDeviceInfo::Capacitor { capacitance, .. } => {
+                assert!((capacitance - 4e-12).abs() < 1e-24)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match device("L1").ac_info() {
+            AcDeviceInfo::Inductor { inductance, .. } => {
+                assert!((inductance - 0.5e-6).abs() < 1e-18)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // M is taken as the diode's multiplier, not a model name.
+        let sol = DVector::from_vec(vec![0.0, 0.7, 0.0]);
+        let gd = |name| match device(name).ac_info_at(&sol) {
+            AcDeviceInfo::Diode { gd, .. } => gd,
+            _ => panic!("expected a diode"),
+        };
+        let (d1, d2) = (gd("D1"), gd("D2"));
+        assert!(d1 > 0.0 && d2 > 0.0);
+        assert!((d1 / d2 - 100.0).abs() < 1e-6, "IS ratio {}", d1 / d2);
+
+        let err = parse("Bad\nR1 1 0 1k M=0\n.end\n").unwrap_err();
+        assert!(err.to_string().contains("multiplier"), "{err}");
+    }
+
     #[test]
     fn test_parse_mosfet() {
         let input = r#"MOSFET Test
@@ -1091,6 +1514,81 @@ X1 1 2 VDIV
         assert_eq!(result.netlist.num_devices(), 3);
     }
 
+    #[test]
+    fn test_subckt_instances_have_independent_nodes() {
+        let input = r#"Two Dividers
+.SUBCKT DIV top bot
+R1 top mid 1k
+R2 mid bot 1k
+.ENDS DIV
+V1 1 0 10
+V2 2 0 4
+X1 1 0 DIV
+X2 2 0 DIV
+.end
+"#;
+
+        let result = parse_full(input).unwrap();
+        assert_eq!(result.netlist.num_devices(), 6);
+
+        // Each copy gets its own prefixed internal node
+        let mid1 = result.node_map["X1_mid"];
+        let mid2 = result.node_map["X2_mid"];
+        assert_ne!(mid1, mid2);
+        for port in ["1", "2"] {
+            assert_ne!(result.node_map[port], mid1);
+            assert_ne!(result.node_map[port], mid2);
+        }
+        assert!(!result.node_map.contains_key("mid"));
+        assert_eq!(result.netlist.num_nodes(), 4);
+    }
+
+    #[test]
+    fn test_subckt_bare_params() {
+        let input = r#"Bare Params
+.SUBCKT RES a b R=1k
+R1 a b {R}
+.ENDS RES
+X1 1 0 RES R=2k
+X2 1 0 RES
+.end
+"#;
+
+        let result = parse_full(input).unwrap();
+        assert!((result.subcircuits["RES"].params["R"] - 1000.0).abs() < 1e-10);
+
+        let resistance = |name| match result
+            .netlist
+            .devices()
+            .iter()
+            .find(|d| d.device_name() == name)
+            .unwrap()
+            .ac_info()
+        {
+            AcDeviceInfo::Resistor { conductance, .. } => 1.0 / conductance,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert!((resistance("RX1_1") - 2000.0).abs() < 1e-9);
+        assert!((resistance("RX2_1") - 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_subckt_recursion_is_an_error() {
+        let input = r#"Recursive Subcircuits
+.SUBCKT A x
+XB x B
+.ENDS A
+.SUBCKT B x
+XA x A
+.ENDS B
+X1 1 A
+.end
+"#;
+
+        let err = parse_full(input).unwrap_err().to_string();
+        assert!(err.contains("A -> B -> A"), "{}", err);
+    }
+
     #[test]
     fn test_parse_param_simple() {
         let input = r#"Param Test
@@ -1298,6 +1796,32 @@ K1 L1 L2 0.9
         assert_eq!(netlist.num_devices(), 6); // V1, L1, L2, R1, R2, K1
     }
 
+    #[test]
+    fn test_mutual_inductance_uses_effective_inductances() {
+        let input = r#"Coupled Inductors With Multiplier
+V1 1 0 AC 1
+L1 1 0 4m M=2
+L2 2 0 1m
+R1 2 0 1k
+K1 L1 L2 0.5
+.end
+"#;
+
+        let netlist = parse(input).unwrap();
+        let k1 = netlist
+            .devices()
+            .iter()
+            .find(|d| d.device_name() == "K1")
+            .unwrap();
+        // L1 is two 4 mH inductors in parallel: M = 0.5 * sqrt(2m * 1m)
+        match k1.ac_info() {
+            AcDeviceInfo::MutualInductance {
+                mutual_inductance, ..
+            } => assert!((mutual_inductance - 0.5 * (2e-3f64 * 1e-3).sqrt()).abs() < 1e-15),
+            other => panic!("expected mutual inductance, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_multi_winding_transformer() {
         // Test 3-winding transformer (creates 3 pairwise couplings)
@@ -1584,6 +2108,52 @@ R1 1 0 {R_VAL*2}
         // The resistor should have value 2000.0 (1k*2)
     }
 
+    #[test]
+    fn test_curly_expr_values() {
+        let input = r#"Curly Expression Values
+.param A=2 B={A*5}
+.param W=1u L=0.15u
+R1 1 0 {1k*3}
+R2 1 0 {(B - 4) / 2 * 1meg}
+C1 1 0 {W*2}
+.end
+"#;
+
+        let result = parse_full(input).unwrap();
+        // Resolved transitively: B = A*5
+        assert_eq!(result.parameters["A"], 2.0);
+        assert_eq!(result.parameters["B"], 10.0);
+        assert!((result.parameters["L"] - 0.15e-6).abs() < 1e-21);
+
+        let device = |name| {
+            result
+                .netlist
+                .devices()
+                .iter()
+                .find(|d| d.device_name() == name)
+                .unwrap()
+                .ac_info()
+        };
+        match device("R1") {
+            AcDeviceInfo::Resistor { conductance, .. } => {
+                assert!((1.0 / conductance - 3000.0).abs() < 1e-9)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match device("R2") {
+            AcDeviceInfo::Resistor { conductance, .. } => {
+                assert!((1.0 / conductance - 3e6).abs() < 1e-6)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match device("C1") {
+            AcDeviceInfo::Capacitor { capacitance, .. } => {
+                assert!((capacitance - 2e-6).abs() < 1e-18)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
     #[test]
     fn test_subckt_params_defaults() {
         let input = r#"SUBCKT Params Test
@@ -1787,6 +2357,48 @@ R1 1 0 1k
         }
     }
 
+    #[test]
+    fn test_parse_dc_temp_sweep() {
+        let input = r#"DC Temperature Sweep
+V1 1 0 5
+R1 1 0 1k TC1=1e-3 TC2=2e-6
+R2 1 0 1k TC=-1e-3,3e-6 M=2
+.DC TEMP 0 100 10
+.end
+"#;
+
+        let result = parse_full(input).unwrap();
+
+        if let Some(super::types::AnalysisCommand::Dc { sweeps }) = result
+            .analyses
+            .iter()
+            .find(|a| matches!(a, super::types::AnalysisCommand::Dc { .. }))
+        {
+            assert_eq!(sweeps.len(), 1);
+            assert_eq!(sweeps[0].sweep_type, super::types::DcSweepType::Temp);
+            assert_eq!(sweeps[0].source_name, "TEMP");
+            assert_eq!(sweeps[0].stop, 100.0);
+        } else {
+            panic!("Expected DC analysis command");
+        }
+
+        // At 127°C: R1 = 1k·(1 + 0.1 + 0.02), R2 = 1k·(1 − 0.1 + 0.03) / 2
+        let mut netlist = result.netlist;
+        netlist.set_temperature_all(400.15);
+        let resistance = |name| match netlist
+            .devices()
+            .iter()
+            .find(|d| d.device_name() == name)
+            .unwrap()
+            .ac_info()
+        {
+            AcDeviceInfo::Resistor { conductance, .. } => 1.0 / conductance,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert!((resistance("R1") - 1120.0).abs() < 1e-9);
+        assert!((resistance("R2") - 465.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_parse_dc_nested_param_and_source() {
         let input = r#"Nested DC Param+Source Sweep
@@ -2230,4 +2842,78 @@ C2 2 0 CMIM W=10u L=10u
         let result = parse_full(input).unwrap();
         assert_eq!(result.netlist.num_devices(), 3); // V1, C1, C2
     }
+
+    #[test]
+    fn test_parse_streaming_matches_full_parse() {
+        // Forward model reference, comments, a subcircuit,
+        // and controlled sources that look up an already-streamed branch.
+        let input = r#"Streaming Test
+* comment line
+V1 in 0 DC 5
+R1 in mid 1k
+D1 mid 0 DMOD
+.SUBCKT RDIV a b
+R1 a b 2k
+R2 b 0 2k
+.ENDS
+X1 mid out RDIV
+L1 out 0 1m
+L2 in 0 2m
+K1 L1 L2 0.5
+F1 out 0 V1 2
+H1 x 0 V1 100
+R2 x 0 1k
+.MODEL DMOD D IS=1e-14
+.op
+.end
+"#;
+
+        let full = parse_full(input).unwrap();
+        let mut streamed = Vec::new();
+        let result = parse_streaming(std::io::Cursor::new(input), |d| streamed.push(d)).unwrap();
+
+        let names = |devices: &[spicier_core::netlist::BoxedStamper]| {
+            devices
+                .iter()
+                .map(|d| d.device_name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&streamed), names(full.netlist.devices()));
+        assert_eq!(result.netlist.title(), Some("Streaming Test"));
+        assert_eq!(result.netlist.num_devices(), 0);
+        assert_eq!(result.netlist.num_nodes(), full.netlist.num_nodes());
+        assert_eq!(
+            result.netlist.num_current_vars(),
+            full.netlist.num_current_vars()
+        );
+        assert_eq!(result.node_map, full.node_map);
+        assert_eq!(result.analyses.len(), 1);
+
+        let mut mna = spicier_core::mna::MnaSystem::new(
+            result.netlist.num_nodes(),
+            result.netlist.num_current_vars(),
+        );
+        for device in &streamed {
+            device.stamp(&mut mna);
+        }
+        let expected = full.netlist.assemble_mna();
+        assert_eq!(mna.to_dense_matrix(), expected.to_dense_matrix());
+        assert_eq!(mna.rhs(), expected.rhs());
+    }
+
+    #[test]
+    fn test_parse_streaming_reports_source_line() {
+        let input = "Bad\n* comment\nR1 1 0 1k\n\nF1 1 0 VX 2\n.end\n";
+        let err = parse_streaming(std::io::Cursor::new(input), |_| {}).unwrap_err();
+        assert!(matches!(err, Error::ParseError { line: 5, .. }), "{err}");
+    }
+
+    #[test]
+    fn test_parse_streaming_joins_continuations() {
+        let input = "Cont\nR1 1 0\n* between\n+ 2k\n.tran 1u\n+ 1m\n.end\n";
+        let mut devices = Vec::new();
+        let result = parse_streaming(std::io::Cursor::new(input), |d| devices.push(d)).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(result.analyses.len(), 1);
+    }
 }
@@ -0,0 +1,179 @@
+//! Streaming parse for netlists too large to hold in memory.
+
+use std::io::{BufRead, Seek, SeekFrom};
+
+use spicier_core::Netlist;
+use spicier_core::netlist::{AcDeviceInfo, BoxedStamper};
+
+use super::{ParseResult, Parser, UnsupportedPolicy};
+use crate::error::{Error, Result};
+use crate::lexer::{Lexer, SpannedToken};
+
+/// Parse a netlist from `reader`, handing each device to `on_device` as soon
+/// as its line has been parsed.
+///
+/// The reader is read twice: pass 1 collects `.MODEL` and `.PARAM` so
+/// devices may reference models defined further down, then the reader is
+/// rewound and pass 2 streams the elements. Only one statement's tokens are
+/// held at a time, so memory is bounded by the node map and the models
+/// rather than the device count.
+///
+/// The returned [`ParseResult`] carries everything except the devices: its
+/// netlist has the title, node count and current-variable count of the full
+/// circuit, so an MNA system sized from it matches what the streamed devices
+/// stamp into. Devices arrive in the same order, with the same node and
+/// branch indices, as from [`parse_full`](super::parse_full). `+`
+/// continuation lines are joined onto the statement they continue.
+/// Unsupported devices and directives are an error, as with `parse_full`,
+/// though by then the supported devices before them have been handed off.
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let text = "Divider\nV1 1 0 10\nR1 1 2 1k\nR2 2 0 1k\n.op\n.end\n";
+/// let mut names = Vec::new();
+/// let result = spicier_parser::parse_streaming(Cursor::new(text), |device| {
+///     names.push(device.device_name().to_string());
+/// })
+/// .unwrap();
+///
+/// assert_eq!(names, ["V1", "R1", "R2"]);
+/// assert_eq!(result.netlist.num_nodes(), 2);
+/// assert_eq!(result.netlist.num_devices(), 0);
+/// ```
+pub fn parse_streaming<R, F>(mut reader: R, mut on_device: F) -> Result<ParseResult>
+where
+    R: BufRead + Seek,
+    F: FnMut(BoxedStamper),
+{
+    let mut parser = Parser::new(&[]);
+
+    // Pass 1: models and parameters.
+    let mut statements = Statements::new(&mut reader);
+    let title = statements.read_title()?;
+    while let Some((line, text)) = statements.next_statement()? {
+        if !text.trim_start().starts_with('.') {
+            continue;
+        }
+        let tokens = tokenize(&text, line)?;
+        let mut pass = parser.retarget(&tokens);
+        pass.parse_definitions()?;
+        parser = pass.retarget(&[]);
+    }
+
+    // Pass 2: everything else, draining devices after every statement.
+    reader.seek(SeekFrom::Start(0))?;
+    let mut statements = Statements::new(&mut reader);
+    statements.read_title()?;
+    if let Some(title) = title {
+        let tokens = tokenize(&title, 1)?;
+        let mut pass = parser.retarget(&tokens);
+        if let Some(title) = pass.parse_title() {
+            pass.netlist = Netlist::with_title(title);
+        }
+        parser = pass.retarget(&[]);
+    }
+    while let Some((line, text)) = statements.next_statement()? {
+        let tokens = tokenize(&text, line)?;
+        let mut pass = parser.retarget(&tokens);
+        pass.parse_statements()?;
+        for device in pass.netlist.take_devices() {
+            if let Some(branch) = device.branch_index() {
+                pass.streamed_branches
+                    .insert(device.device_name().to_uppercase(), branch);
+            }
+            if let AcDeviceInfo::Inductor { inductance, .. } = device.ac_info() {
+                pass.streamed_inductances
+                    .insert(device.device_name().to_uppercase(), inductance);
+            }
+            on_device(device);
+        }
+        parser = pass.retarget(&[]);
+    }
+
+    parser
+        .into_result()
+        .check_unsupported(UnsupportedPolicy::Error)
+}
+
+/// Tokenize one statement, numbering lines from `first_line`.
+fn tokenize(text: &str, first_line: usize) -> Result<Vec<SpannedToken>> {
+    let offset = first_line - 1;
+    let mut tokens = Lexer::new(text).tokenize().map_err(|e| match e {
+        Error::ParseError { line, message } => Error::ParseError {
+            line: line + offset,
+            message,
+        },
+        e => e,
+    })?;
+    for token in &mut tokens {
+        token.line += offset;
+    }
+    Ok(tokens)
+}
+
+/// Splits a reader into statements: a line with its `+` continuations joined
+/// on, with blank and `*` comment lines dropped.
+struct Statements<R> {
+    reader: R,
+    line: usize,
+    buf: String,
+    pending: Option<(usize, String)>,
+}
+
+impl<R: BufRead> Statements<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            buf: String::new(),
+            pending: None,
+        }
+    }
+
+    /// Read the first line, returning it if it is a title rather than a
+    /// dot command.
+    fn read_title(&mut self) -> Result<Option<String>> {
+        if !self.read_line()? {
+            return Ok(None);
+        }
+        let text = self.buf.trim();
+        if text.starts_with('.') {
+            self.pending = Some((self.line, text.to_string()));
+            return Ok(None);
+        }
+        Ok((!text.is_empty()).then(|| text.to_string()))
+    }
+
+    /// The next statement and its starting line number.
+    fn next_statement(&mut self) -> Result<Option<(usize, String)>> {
+        loop {
+            if !self.read_line()? {
+                return Ok(self.pending.take());
+            }
+            let text = self.buf.trim_end();
+            let trimmed = text.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('*') {
+                continue;
+            }
+            if let Some(rest) = trimmed.strip_prefix('+')
+                && let Some((_, statement)) = &mut self.pending
+            {
+                statement.push(' ');
+                statement.push_str(rest);
+                continue;
+            }
+            let next = (self.line, text.to_string());
+            if let Some(statement) = self.pending.replace(next) {
+                return Ok(Some(statement));
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<bool> {
+        self.buf.clear();
+        let read = self.reader.read_line(&mut self.buf)?;
+        self.line += 1;
+        Ok(read > 0)
+    }
+}
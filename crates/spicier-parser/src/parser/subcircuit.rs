@@ -11,11 +11,11 @@ use spicier_devices::sources::{CurrentSource, VoltageSource};
 use crate::error::{Error, Result};
 use crate::lexer::{Lexer, SpannedToken, Token};
 
-use super::types::RawElementLine;
+use super::types::{RawElementLine, UnsupportedConstruct};
 use super::{ModelDefinition, ParamContext, Parser};
 
 impl<'a> Parser<'a> {
-    /// Parse Xname node1 node2 ... subckt_name [PARAMS: param=value ...]
+    /// Parse Xname node1 node2 ... subckt_name [[PARAMS:] param=value ...]
     ///
     /// If we're inside a subcircuit definition, store as raw line.
     /// Otherwise, expand the subcircuit inline.
@@ -47,6 +47,10 @@ impl<'a> Parser<'a> {
                         // Parse instance parameter overrides
                         instance_params = self.parse_instance_params(line)?;
                         break;
+                    } else if self.is_next_equals() {
+                        // Bare `W=2u` overrides without the PARAMS: keyword
+                        instance_params = self.parse_instance_params(line)?;
+                        break;
                     } else {
                         tokens.push(n.clone());
                         self.advance();
@@ -331,8 +335,9 @@ impl<'a> Parser<'a> {
         }
 
         // Expand nested subcircuit instances
+        let stack = [subckt_name.to_string()];
         for inst in &subckt.instances {
-            self.expand_nested_instance(instance_name, &inst.line, &node_map, &ctx, line)?;
+            self.expand_nested_instance(instance_name, &inst.line, &node_map, &ctx, &stack, line)?;
         }
 
         Ok(())
@@ -410,12 +415,16 @@ impl<'a> Parser<'a> {
     }
 
     /// Expand a nested subcircuit instance line with parameter propagation.
+    ///
+    /// `stack` holds the names of the subcircuits being expanded, outermost
+    /// first; instantiating one of them again is a recursion error.
     fn expand_nested_instance(
         &mut self,
         parent_prefix: &str,
         line: &str,
         node_map: &HashMap<String, String>,
         parent_ctx: &ParamContext,
+        stack: &[String],
         source_line: usize,
     ) -> Result<()> {
         // Parse the nested X instance line
@@ -440,19 +449,24 @@ impl<'a> Parser<'a> {
         };
         let nested_instance_name = format!("{}_{}", parent_prefix, rest);
 
-        // Find PARAMS: position if present
+        // Find where the parameters start: after a PARAMS: keyword, or at
+        // the first bare `name=value`. Holds (end of nodes, first parameter).
         let mut params_idx = None;
-        for (i, part) in parts.iter().enumerate() {
+        for (i, part) in parts.iter().enumerate().skip(1) {
             let upper = part.to_uppercase();
             if upper == "PARAMS" || upper == "PARAMS:" || upper.starts_with("PARAMS:") {
-                params_idx = Some(i);
+                params_idx = Some((i, i + 1));
+                break;
+            }
+            if part.contains('=') || parts.get(i + 1) == Some(&"=") {
+                params_idx = Some((i, i));
                 break;
             }
         }
 
         // Split into node+subckt parts and param parts
-        let node_parts: Vec<&str> = if let Some(idx) = params_idx {
-            parts[1..idx].to_vec()
+        let node_parts: Vec<&str> = if let Some((end, _)) = params_idx {
+            parts[1..end].to_vec()
         } else {
             parts[1..].to_vec()
         };
@@ -465,6 +479,15 @@ impl<'a> Parser<'a> {
         let nested_subckt_name = node_parts.last().unwrap().to_uppercase();
         let connection_names: Vec<&str> = node_parts[..node_parts.len() - 1].to_vec();
 
+        if let Some(pos) = stack.iter().position(|s| *s == nested_subckt_name) {
+            let mut cycle = stack[pos..].to_vec();
+            cycle.push(nested_subckt_name);
+            return Err(Error::ParseError {
+                line: source_line,
+                message: format!("Recursive subcircuit instantiation: {}", cycle.join(" -> ")),
+            });
+        }
+
         // Map connection nodes through parent's node_map
         let mut nested_connections: Vec<String> = Vec::new();
         for conn in &connection_names {
@@ -480,8 +503,8 @@ impl<'a> Parser<'a> {
 
         // Parse nested instance params and evaluate any expressions
         let mut nested_instance_params: HashMap<String, f64> = HashMap::new();
-        if let Some(idx) = params_idx {
-            let mut i = idx + 1;
+        if let Some((_, start)) = params_idx {
+            let mut i = start;
             while i < parts.len() {
                 let part = parts[i];
                 if let Some(eq_pos) = part.find('=') {
@@ -536,6 +559,18 @@ impl<'a> Parser<'a> {
             }
         };
 
+        if nested_connections.len() != nested_subckt.ports.len() {
+            return Err(Error::ParseError {
+                line: source_line,
+                message: format!(
+                    "Subcircuit {} expects {} ports but {} provided",
+                    nested_subckt_name,
+                    nested_subckt.ports.len(),
+                    nested_connections.len()
+                ),
+            });
+        }
+
         // Create child context: parent's merged becomes child's global
         let child_ctx =
             parent_ctx.child_context(nested_subckt.params.clone(), nested_instance_params);
@@ -559,12 +594,15 @@ impl<'a> Parser<'a> {
         }
 
         // Recursively expand any deeper nested instances
+        let mut path = stack.to_vec();
+        path.push(nested_subckt_name);
         for inst in &nested_subckt.instances {
             self.expand_nested_instance(
                 &nested_instance_name,
                 &inst.line,
                 &nested_node_map,
                 &child_ctx,
+                &path,
                 source_line,
             )?;
         }
@@ -835,7 +873,11 @@ impl<'a> Parser<'a> {
                 }
             }
             _ => {
-                // Unknown element type in subcircuit - skip
+                // Only the elements above can be expanded from a subcircuit
+                self.unsupported.push(UnsupportedConstruct {
+                    line: source_line,
+                    name,
+                });
             }
         }
 
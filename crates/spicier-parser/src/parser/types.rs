@@ -1,6 +1,7 @@
 //! Public types for the SPICE parser.
 
 use std::collections::HashMap;
+use std::fmt;
 
 use spicier_core::{Netlist, NodeId};
 
@@ -24,6 +25,8 @@ pub enum DcSweepType {
     Source,
     /// Sweep a parameter value.
     Param,
+    /// Sweep the circuit temperature (°C).
+    Temp,
 }
 
 /// A single DC sweep specification.
@@ -181,6 +184,31 @@ impl SubcircuitDefinition {
     }
 }
 
+/// A device or directive the parser recognized as SPICE but cannot model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedConstruct {
+    /// Source line number.
+    pub line: usize,
+    /// Element name (`Z1`) or directive (`.DISTO`).
+    pub name: String,
+}
+
+impl fmt::Display for UnsupportedConstruct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (line {})", self.name, self.line)
+    }
+}
+
+/// What to do with netlist lines the parser cannot model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsupportedPolicy {
+    /// Fail with [`Error::Unsupported`](crate::Error::Unsupported).
+    #[default]
+    Error,
+    /// Skip them and list them in [`ParseResult::unsupported`].
+    Warn,
+}
+
 /// Result of parsing a SPICE netlist.
 ///
 /// Contains both the circuit (Netlist) and analysis commands.
@@ -202,6 +230,11 @@ pub struct ParseResult {
     pub parameters: HashMap<String, f64>,
     /// Measurement statements from .MEAS commands.
     pub measurements: Vec<Measurement>,
+    /// Circuit temperature (K) from `.TEMP` or `.OPTIONS TEMP=` (given in °C).
+    pub temperature: Option<f64>,
+    /// Lines skipped under [`UnsupportedPolicy::Warn`]; the netlist does
+    /// not model them.
+    pub unsupported: Vec<UnsupportedConstruct>,
 }
 
 // ============================================================================
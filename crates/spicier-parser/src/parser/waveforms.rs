@@ -1,4 +1,4 @@
-//! Waveform parsing (PULSE, SIN, PWL).
+//! Waveform parsing (PULSE, SIN, EXP, SFFM, AM, PWL).
 
 use spicier_devices::Waveform;
 
@@ -71,7 +71,105 @@ impl<'a> Parser<'a> {
         Ok(Waveform::sin_full(vo, va, freq, td, theta, phase))
     }
 
-    /// Parse PWL(t1 v1 t2 v2 ...)
+    /// Parse EXP(v1 v2 [td1 [tau1 [td2 [tau2]]]])
+    ///
+    /// ngspice defaults the time constants from TSTEP, which is not known
+    /// here; as with PULSE, missing values default to 0 (an instantaneous
+    /// step), and TD2 defaults to TD1.
+    pub(super) fn parse_exp_waveform(&mut self, line: usize) -> Result<Waveform> {
+        if !matches!(self.peek(), Token::LParen) {
+            return Err(Error::ParseError {
+                line,
+                message: "expected '(' after EXP".to_string(),
+            });
+        }
+        self.advance();
+
+        let v1 = self.expect_value(line)?;
+        let v2 = self.expect_value(line)?;
+
+        // Optional parameters with defaults
+        let td1 = self.try_expect_value().unwrap_or(0.0);
+        let tau1 = self.try_expect_value().unwrap_or(0.0);
+        let td2 = self.try_expect_value().unwrap_or(td1);
+        let tau2 = self.try_expect_value().unwrap_or(0.0);
+
+        if !matches!(self.peek(), Token::RParen) {
+            return Err(Error::ParseError {
+                line,
+                message: "expected ')' after EXP parameters".to_string(),
+            });
+        }
+        self.advance();
+
+        Ok(Waveform::exp(v1, v2, td1, tau1, td2, tau2))
+    }
+
+    /// Parse SFFM(vo va fc [mdi [fs [phasec [phases]]]])
+    pub(super) fn parse_sffm_waveform(&mut self, line: usize) -> Result<Waveform> {
+        if !matches!(self.peek(), Token::LParen) {
+            return Err(Error::ParseError {
+                line,
+                message: "expected '(' after SFFM".to_string(),
+            });
+        }
+        self.advance();
+
+        let vo = self.expect_value(line)?;
+        let va = self.expect_value(line)?;
+        let fc = self.expect_value(line)?;
+
+        // Optional parameters with defaults
+        let mdi = self.try_expect_value().unwrap_or(0.0);
+        let fs = self.try_expect_value().unwrap_or(0.0);
+        let phasec = self.try_expect_value().unwrap_or(0.0);
+        let phases = self.try_expect_value().unwrap_or(0.0);
+
+        if !matches!(self.peek(), Token::RParen) {
+            return Err(Error::ParseError {
+                line,
+                message: "expected ')' after SFFM parameters".to_string(),
+            });
+        }
+        self.advance();
+
+        Ok(Waveform::sffm(vo, va, fc, mdi, fs, phasec, phases))
+    }
+
+    /// Parse AM(va vo mf fc [td [phasem [phasec]]])
+    pub(super) fn parse_am_waveform(&mut self, line: usize) -> Result<Waveform> {
+        if !matches!(self.peek(), Token::LParen) {
+            return Err(Error::ParseError {
+                line,
+                message: "expected '(' after AM".to_string(),
+            });
+        }
+        self.advance();
+
+        let va = self.expect_value(line)?;
+        let vo = self.expect_value(line)?;
+        let mf = self.expect_value(line)?;
+        let fc = self.expect_value(line)?;
+
+        // Optional parameters with defaults
+        let td = self.try_expect_value().unwrap_or(0.0);
+        let phasem = self.try_expect_value().unwrap_or(0.0);
+        let phasec = self.try_expect_value().unwrap_or(0.0);
+
+        if !matches!(self.peek(), Token::RParen) {
+            return Err(Error::ParseError {
+                line,
+                message: "expected ')' after AM parameters".to_string(),
+            });
+        }
+        self.advance();
+
+        Ok(Waveform::am(va, vo, mf, fc, td, phasem, phasec))
+    }
+
+    /// Parse PWL(t1 v1 t2 v2 ...) [R=tr]
+    ///
+    /// The repeat modifier is accepted inside or after the parentheses.
     pub(super) fn parse_pwl_waveform(&mut self, line: usize) -> Result<Waveform> {
         if !matches!(self.peek(), Token::LParen) {
             return Err(Error::ParseError {
@@ -86,6 +184,7 @@ impl<'a> Parser<'a> {
             let v = self.expect_value(line)?;
             points.push((t, v));
         }
+        let mut repeat_from = self.try_pwl_repeat(line)?;
 
         if points.is_empty() {
             return Err(Error::ParseError {
@@ -101,7 +200,36 @@ impl<'a> Parser<'a> {
             });
         }
         self.advance();
+        if repeat_from.is_none() {
+            repeat_from = self.try_pwl_repeat(line)?;
+        }
 
-        Ok(Waveform::pwl(points))
+        match repeat_from {
+            Some(tr) => {
+                let (t_first, t_last) = (points[0].0, points[points.len() - 1].0);
+                if !(t_first..=t_last).contains(&tr) {
+                    return Err(Error::ParseError {
+                        line,
+                        message: format!(
+                            "PWL repeat time R={tr} outside the waveform ({t_first} to {t_last})"
+                        ),
+                    });
+                }
+                Ok(Waveform::pwl_repeat(points, tr))
+            }
+            None => Ok(Waveform::pwl(points)),
+        }
+    }
+
+    /// Parse an optional PWL `R=value` repeat modifier.
+    fn try_pwl_repeat(&mut self, line: usize) -> Result<Option<f64>> {
+        if !matches!(self.peek(), Token::Name(n) if n.eq_ignore_ascii_case("R"))
+            || !self.is_next_equals()
+        {
+            return Ok(None);
+        }
+        self.advance(); // R
+        self.advance(); // =
+        self.expect_value(line).map(Some)
     }
 }
@@ -349,6 +349,8 @@ C1 2 0 1u
         tstop: 5e-3,
         tstep: 10e-6,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     // Initial condition: capacitor starts at 0V
@@ -439,6 +441,8 @@ fn test_tran_lc_oscillation_frequency() {
         tstop: 5.0 * expected_period,
         tstep: expected_period / 50.0, // 50 points per period
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     let result = solve_transient(&LcOscillatorStamper, &mut caps, &mut inds, &params, &dc)
@@ -1296,6 +1300,8 @@ fn test_tran_rl_time_constant() {
         tstop: 5.0 * tau,
         tstep: tau / 20.0,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     let mut caps = vec![];
@@ -1939,6 +1945,8 @@ fn test_golden_tran_rc_charging() {
             tstop: params.tstop,
             tstep: params.tstep,
             method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
         };
 
         let mut inds = vec![];
@@ -2879,6 +2887,8 @@ C1 2 0 10u
         tstop: 5e-3, // 5 cycles at 1kHz
         tstep: 10e-6,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     // Initial condition: all nodes at 0V
@@ -3004,6 +3014,8 @@ C1 2 0 100n
         tstop: 4e-3, // 4 periods
         tstep: 10e-6,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     // Initial condition: capacitor at 0V
@@ -3679,6 +3691,8 @@ R1 2 0 10k
         tstop: 4e-3,
         tstep: 10e-6,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     let dc_solution = DVector::from_vec(vec![0.0, 0.0, 0.0]);
@@ -4004,6 +4018,37 @@ Q1 2 3 0 QMOD
     );
 }
 
+/// Test NPN common-emitter collector current tracks beta * Ib
+#[test]
+fn test_dc_npn_common_emitter_beta() {
+    // Base current forced by a current source, so Ic = BF * Ib in forward
+    // active. The second form gives the optional substrate node.
+    for q1 in ["Q1 2 3 0 QMOD", "Q1 2 3 0 0 QMOD"] {
+        for ib in [2e-6, 10e-6, 40e-6] {
+            let netlist_str = format!(
+                "NPN Common Emitter Beta
+.MODEL QMOD NPN (IS=1e-15 BF=150 BR=2)
+VCC 1 0 DC 10
+IB 0 3 DC {ib}
+RC 1 2 1k
+{q1}
+.end
+"
+            );
+
+            let netlist = parse(&netlist_str).expect("Parse failed");
+            let solution = solve_dc_nonlinear(&netlist).expect("DC solve failed");
+
+            let ic = (10.0 - solution.voltage(NodeId::new(2))) / 1e3;
+            assert!(
+                (ic - 150.0 * ib).abs() < 1e-3 * 150.0 * ib,
+                "{q1}: Ic = {ic} for Ib = {ib} (expected {})",
+                150.0 * ib
+            );
+        }
+    }
+}
+
 /// Test NPN in cutoff
 #[test]
 fn test_dc_npn_cutoff() {
@@ -2,12 +2,17 @@
 
 use nalgebra::DVector;
 use spicier_core::NodeId;
-use spicier_core::mna::MnaSystem;
-use spicier_core::netlist::TransientDeviceInfo;
-use spicier_parser::{AnalysisCommand, parse, parse_full};
+use spicier_core::mna::{MnaSystem, MnaVariable};
+use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
+use spicier_core::{DegenerateKind, DegeneratePolicy, ValidationOptions};
+use spicier_parser::{
+    AnalysisCommand, Error, UnsupportedPolicy, parse, parse_full, parse_full_with_policy,
+};
 use spicier_solver::{
-    CapacitorState, ConvergenceCriteria, DcSweepParams, DcSweepStamper, IntegrationMethod,
-    NonlinearStamper, TransientParams, TransientStamper, solve_dc, solve_dc_sweep,
+    CapacitorState, ConvergenceCriteria, DcSweepParams, DcSweepResult, DcSweepStamper,
+    IntegrationMethod, NetlistTransientStamper, NonlinearNestedSweepStamper, NonlinearStamper,
+    NonlinearSweepStamper, TransientParams, TransientStamper, build_transient_state, solve_dc,
+    solve_dc_nested_sweep_nonlinear, solve_dc_sweep, solve_dc_sweep_nonlinear,
     solve_newton_raphson, solve_transient,
 };
 
@@ -40,6 +45,344 @@ R2 2 0 1k
     assert!((v2 - 5.0).abs() < 1e-9, "V(2) = {} (expected 5.0)", v2);
 }
 
+/// `M=2` puts two copies of a resistor in parallel.
+#[test]
+fn test_resistor_multiplier_halves_resistance() {
+    let solve = |r1: &str| {
+        let netlist_str = format!("Multiplier\nV1 a 0 DC 10\n{}\nR2 b 0 1k\n.end\n", r1);
+        let result = parse_full(&netlist_str).expect("parse should succeed");
+        let solution = solve_dc(&result.netlist.assemble_mna()).unwrap();
+        (solution.voltage(result.node_map["b"]), solution.current(0))
+    };
+
+    let (v_m, i_m) = solve("R1 a b 1k M=2");
+    let (v_500, i_500) = solve("R1 a b 500");
+    assert!((v_m - 20.0 / 3.0).abs() < 1e-9, "V(b) = {}", v_m);
+    assert!((v_m - v_500).abs() < 1e-12);
+    assert!((i_m - i_500).abs() < 1e-15);
+}
+
+/// A probe in a circuit with an inductor keeps its DC branch index in
+/// transient, where the inductor runs on a companion model.
+#[test]
+fn test_current_probe_in_transient_with_inductor() {
+    // L1 comes first, so its branch index precedes the source's
+    let netlist_str = r#"
+RL Probe
+L1 in a 1m
+V1 in 0 PULSE(0 1 0 1n 1n 1 2)
+R1 a 0 1k
+.end
+"#;
+
+    let mut netlist = parse(netlist_str).expect("parse should succeed");
+    let i_r1 = netlist.add_current_probe("R1").unwrap();
+    assert_eq!(i_r1, 2);
+
+    let dc = solve_dc(&netlist.assemble_mna()).unwrap();
+    let dc = DVector::from_iterator(
+        netlist.num_nodes() + netlist.num_current_vars(),
+        dc.node_voltages
+            .iter()
+            .chain(dc.branch_currents.iter())
+            .copied(),
+    );
+    let (mut caps, mut inds) = build_transient_state(&netlist);
+    let stamper = NetlistTransientStamper::new(&netlist);
+    let params = TransientParams {
+        tstop: 5e-6,
+        tstep: 1e-8,
+        method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
+    };
+    let result = solve_transient(&stamper, &mut caps, &mut inds, &params, &dc).unwrap();
+
+    // tau = L/R = 1µs; I(R1) = V(a)/R1 = (1 - e^(-t/tau)) mA
+    let row = netlist.num_nodes() + i_r1;
+    for point in result.points.iter().skip(10) {
+        let expected = 1e-3 * (1.0 - (-point.time / 1e-6).exp());
+        assert!(
+            (point.solution[row] - expected).abs() < 1e-5,
+            "I(R1) at t={:.2e}: {} (expected {})",
+            point.time,
+            point.solution[row],
+            expected
+        );
+    }
+}
+
+/// A current probe on a resistor reports V/R without changing the circuit.
+#[test]
+fn test_current_probe_through_resistor() {
+    let netlist_str = r#"
+Current Probe
+V1 1 0 DC 12
+R1 1 2 1k
+R2 2 0 3k
+R3 0 2 6k
+.end
+"#;
+
+    let mut netlist = parse(netlist_str).expect("parse should succeed");
+    let before = solve_dc(&netlist.assemble_mna()).unwrap();
+
+    let i_r1 = netlist.add_current_probe("R1").unwrap();
+    let i_r3 = netlist.add_current_probe("r3").unwrap();
+    assert_eq!(netlist.find_vsource_branch_index("R1"), Some(i_r1));
+    assert_eq!(
+        netlist.variable_layout().device_current_index("R1"),
+        Some(netlist.num_nodes() + i_r1)
+    );
+    // V1 already has a branch current
+    assert_eq!(netlist.add_current_probe("V1").unwrap(), 0);
+    assert!(netlist.add_current_probe("R9").is_err());
+
+    let after = solve_dc(&netlist.assemble_mna()).unwrap();
+    let v1 = after.voltage(NodeId::new(1));
+    let v2 = after.voltage(NodeId::new(2));
+    assert!((v2 - before.voltage(NodeId::new(2))).abs() < 1e-12);
+    assert!((v2 - 8.0).abs() < 1e-9, "V(2) = {}", v2);
+
+    // I(R1) flows from node 1 into node 2; R3 runs from ground to node 2,
+    // so its current is negative.
+    let expected_r1 = (v1 - v2) / 1e3;
+    assert!((after.current(i_r1) - expected_r1).abs() < 1e-12);
+    assert!((after.current(i_r1) - 4e-3).abs() < 1e-12);
+    assert!((after.current(i_r3) - (0.0 - v2) / 6e3).abs() < 1e-12);
+}
+
+/// A negative resistor makes node 2 locally active; a positive resistor in
+/// parallel keeps the net conductance positive and the solution finite.
+#[test]
+fn test_negative_resistor_with_stabilizing_resistor() {
+    let netlist_str = r#"
+Negative Resistance
+V1 1 0 DC -10
+I1 2 0 -1m
+R1 1 2 1k
+RNEG 2 0 -2k
+RSTAB 2 0 1k
+.end
+"#;
+
+    let netlist = parse(netlist_str).expect("parse should succeed");
+    let mna = netlist.assemble_mna();
+
+    // Node 2 sees 1/1k - 1/2k + 1/1k: the negative branch subtracts.
+    let g22 = mna.to_dense_matrix()[(1, 1)];
+    assert!((g22 - 1.5e-3).abs() < 1e-15, "G(2,2) = {}", g22);
+
+    // KCL at node 2: (V2 - V1)/1k + V2/(-2k) + V2/1k = 1mA injected by I1.
+    // With |RNEG| instead the answer would be -11/2.5 = -4.4 V.
+    let solution = solve_dc(&mna).expect("DC solve should succeed");
+    let v2 = solution.voltage(NodeId::new(2));
+    assert!((v2 - -6.0).abs() < 1e-9, "V(2) = {} (expected -6.0)", v2);
+
+    // RNEG carries 3 mA from node 2 (at -6 V) up to ground: current flowing
+    // towards the higher potential, so the element delivers power.
+    let i_neg = v2 / -2000.0;
+    assert!((i_neg - 3e-3).abs() < 1e-12);
+}
+
+/// Degenerate two-terminal devices are flagged by netlist validation.
+#[test]
+fn test_validate_degenerate_devices() {
+    let netlist_str = r#"
+Degenerate Devices
+V1 1 0 DC 10
+R1 1 2 1k
+RSELF 2 2 1k
+RSHORT 2 3 0
+R2 3 0 1k
+.end
+"#;
+
+    let mut netlist = parse(netlist_str).expect("parse should succeed");
+    let options = ValidationOptions {
+        policy: DegeneratePolicy::Drop,
+    };
+    let report = netlist
+        .validate(&options)
+        .expect("validation should succeed");
+
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].name, "RSELF");
+    assert_eq!(report[0].kind, DegenerateKind::SameNodeTerminals);
+    assert!(report[0].dropped);
+    assert_eq!(report[1].name, "RSHORT");
+    assert_eq!(report[1].kind, DegenerateKind::ZeroResistance);
+    assert!(!report[1].dropped);
+    assert_eq!(netlist.num_devices(), 4);
+}
+
+/// A network with no connection to node 0 is reported as such, not as a
+/// singular matrix.
+#[test]
+fn test_floating_network_has_no_ground_reference() {
+    let netlist_str = r#"
+Floating Divider
+V1 1 2 DC 10
+R1 1 3 1k
+R2 3 2 1k
+.op
+.end
+"#;
+
+    let netlist = parse(netlist_str).expect("parse should succeed");
+    let err = netlist
+        .check_ground_reference()
+        .expect_err("floating circuit has no ground reference");
+    assert!(matches!(
+        err,
+        spicier_core::Error::NoGroundReference { node } if node == NodeId::new(1)
+    ));
+    assert!(err.to_string().contains("ground"));
+
+    let err =
+        spicier_solver::simulate(netlist_str).expect_err("floating circuit has no DC solution");
+    assert!(matches!(
+        err,
+        spicier_solver::Error::Circuit(spicier_core::Error::NoGroundReference { .. })
+    ));
+}
+
+/// Nets joined by a 0Ω resistor and a 0V source collapse into single nodes.
+#[test]
+fn test_merge_zero_ohm_and_zero_volt_shorts() {
+    let netlist_str = r#"
+Merged Shorts
+V1 in 0 DC 10
+R1 in a 1k
+RJ a b 0
+R2 b mid 1k
+VSHORT mid c 0
+R3 c 0 2k
+.end
+"#;
+
+    let result = parse_full(netlist_str).expect("parse should succeed");
+    let mut netlist = result.netlist;
+    assert_eq!(netlist.num_nodes(), 5);
+    assert_eq!(netlist.num_current_vars(), 2);
+
+    let remap = netlist.merge_shorted_nodes().expect("shorts should merge");
+    assert_eq!(netlist.num_nodes(), 3);
+    assert_eq!(netlist.num_current_vars(), 1);
+    assert_eq!(netlist.num_devices(), 4);
+
+    let node = |name: &str| remap.node(result.node_map[name]);
+    assert_eq!(node("a"), node("b"));
+    assert_eq!(node("mid"), node("c"));
+
+    let solution = solve_dc(&netlist.assemble_mna()).expect("DC solve should succeed");
+
+    // 10V across 1k + 1k + 2k: V(a) = 7.5V, V(c) = 5V
+    let va = solution.voltage(node("a"));
+    let vc = solution.voltage(node("c"));
+    assert!((va - 7.5).abs() < 1e-9, "V(a) = {} (expected 7.5)", va);
+    assert!((vc - 5.0).abs() < 1e-9, "V(c) = {} (expected 5.0)", vc);
+}
+
+/// A 0V source with an AC spec is the AC input, and an observed 0V ammeter
+/// keeps its branch current; neither is merged away.
+#[test]
+fn test_merge_keeps_ac_input_and_observed_ammeter() {
+    let netlist_str = r#"
+AC Input At 0V DC
+VIN in 0 DC 0 AC 2
+R1 in a 1k
+VAMP a b 0
+R2 b 0 1k
+VSHORT b c 0
+R3 c 0 1k
+.end
+"#;
+
+    let result = parse_full(netlist_str).expect("parse should succeed");
+    let mut netlist = result.netlist;
+    assert_eq!(netlist.num_current_vars(), 3);
+
+    let remap = netlist
+        .merge_shorted_nodes_keeping(&["vamp"])
+        .expect("VSHORT should merge");
+    assert_eq!(netlist.num_current_vars(), 2);
+    assert_eq!(netlist.num_devices(), 5);
+    assert!(netlist.find_vsource_branch_index("VIN").is_some());
+    assert!(netlist.find_vsource_branch_index("VAMP").is_some());
+    assert!(netlist.find_vsource_branch_index("VSHORT").is_none());
+
+    let node = |name: &str| remap.node(result.node_map[name]);
+    assert_eq!(node("b"), node("c"));
+    assert_ne!(node("a"), node("b"));
+
+    // The AC input keeps its stimulus
+    let vin = netlist
+        .devices()
+        .iter()
+        .find(|d| d.device_name() == "VIN")
+        .unwrap();
+    assert!(matches!(
+        vin.ac_info(),
+        AcDeviceInfo::VoltageSource { ac_mag, .. } if ac_mag == 2.0
+    ));
+
+    // DC is unchanged: 1k into 1k || 1k with VIN at 0V
+    let solution = solve_dc(&netlist.assemble_mna()).expect("DC solve should succeed");
+    assert!(solution.voltage(node("b")).abs() < 1e-12);
+}
+
+/// The variable layout labels node voltages and each source's branch current.
+#[test]
+fn test_variable_layout_two_sources() {
+    let netlist_str = r#"
+Two Sources
+V1 a 0 DC 10
+R1 a b 1k
+V2 b 0 DC 4
+R2 b 0 1k
+.end
+"#;
+
+    let result = parse_full(netlist_str).expect("parse should succeed");
+    let netlist = result.netlist;
+    let layout = netlist.variable_layout();
+
+    assert_eq!(layout.len(), 4);
+    assert_eq!(layout.num_nodes(), 2);
+    assert_eq!(layout.num_branches(), 2);
+
+    let a = result.node_map["a"];
+    let b = result.node_map["b"];
+    for node in [a, b] {
+        let idx = layout.node_index(node).unwrap();
+        assert_eq!(layout.get(idx), Some(&MnaVariable::NodeVoltage(node)));
+    }
+
+    let i_v1 = layout.device_current_index("V1").unwrap();
+    let i_v2 = layout.device_current_index("v2").unwrap();
+    assert_ne!(i_v1, i_v2);
+    for idx in [i_v1, i_v2] {
+        assert!(idx >= layout.num_nodes());
+        assert!(matches!(
+            layout.get(idx),
+            Some(MnaVariable::BranchCurrent { .. })
+        ));
+    }
+
+    // Read the raw solution vector through the layout
+    let mna = netlist.assemble_mna();
+    let x = mna
+        .to_dense_matrix()
+        .lu()
+        .solve(mna.rhs())
+        .expect("matrix should be nonsingular");
+    assert!((x[layout.node_index(a).unwrap()] - 10.0).abs() < 1e-9);
+    assert!((x[layout.node_index(b).unwrap()] - 4.0).abs() < 1e-9);
+    // 6mA flows out of V1's positive terminal through R1
+    assert!((x[i_v1] + 6e-3).abs() < 1e-12, "I(V1) = {}", x[i_v1]);
+}
+
 /// Parse and simulate a current source with parallel resistors.
 #[test]
 fn test_parse_simulate_current_source() {
@@ -279,6 +622,87 @@ D1 2 0
     );
 }
 
+/// Test: .TEMP / .OPTIONS TEMP= shift the diode forward voltage by about -2mV/°C.
+#[test]
+fn test_global_temperature_shifts_diode_vf() {
+    struct NlStamper<'a>(&'a spicier_core::Netlist);
+    impl NonlinearStamper for NlStamper<'_> {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.0.stamp_nonlinear_into(mna, solution);
+        }
+    }
+
+    let vf_at = |temp_line: &str| {
+        let netlist_str =
+            format!("Diode Temp Test\nV1 1 0 DC 5\nR1 1 2 430k\nD1 2 0\n{temp_line}\n.end\n");
+        let result = parse_full(&netlist_str).expect("parse should succeed");
+        let mut netlist = result.netlist;
+        if let Some(temp) = result.temperature {
+            netlist.set_temperature_all(temp);
+        }
+        let op = solve_newton_raphson(
+            netlist.num_nodes(),
+            netlist.num_current_vars(),
+            &NlStamper(&netlist),
+            &ConvergenceCriteria::default(),
+            None,
+        )
+        .expect("NR should succeed");
+        (result.temperature, op.solution[1])
+    };
+
+    let (t_nom, vf_nom) = vf_at("");
+    let (t_hot, vf_hot) = vf_at(".TEMP 77");
+    let (t_opt, vf_opt) = vf_at(".OPTIONS METHOD=GEAR RELTOL=1e-3 TEMP=77");
+    assert_eq!(t_nom, None);
+    assert!((t_hot.unwrap() - 350.15).abs() < 1e-9);
+    assert_eq!(t_opt, t_hot);
+    assert_eq!(vf_opt, vf_hot);
+
+    let tc = (vf_hot - vf_nom) / 50.0;
+    assert!(
+        (-2.5e-3..-1.5e-3).contains(&tc),
+        "dVf/dT = {} V/°C (expected ≈ -2mV/°C)",
+        tc
+    );
+}
+
+/// Test: a resistor-fed Zener in reverse clamps its node near BV.
+#[test]
+fn test_zener_clamp() {
+    let netlist_str = r#"
+Zener Clamp
+.MODEL DZ D (BV=5.1 IBV=1m)
+V1 1 0 DC 12
+R1 1 2 1k
+D1 0 2 DZ
+.end
+"#;
+
+    let netlist = parse(netlist_str).expect("parse should succeed");
+
+    struct NlStamper<'a>(&'a spicier_core::Netlist);
+    impl NonlinearStamper for NlStamper<'_> {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.0.stamp_nonlinear_into(mna, solution);
+        }
+    }
+
+    let result = solve_newton_raphson(
+        netlist.num_nodes(),
+        netlist.num_current_vars(),
+        &NlStamper(&netlist),
+        &ConvergenceCriteria::default(),
+        None,
+    )
+    .expect("NR should succeed");
+    assert!(result.converged, "Should converge");
+
+    // ~6.9mA through the Zener puts it a few Vt past BV.
+    let v2 = result.solution[1];
+    assert!(v2 > 5.1 && v2 < 5.25, "V(2) = {} (expected ≈ 5.15)", v2);
+}
+
 /// Test: Parsing D element with .MODEL
 #[test]
 fn test_parse_diode_with_model() {
@@ -314,6 +738,227 @@ R1 1 0 10k
     assert!(netlist.has_nonlinear_devices());
 }
 
+/// Nonlinear sweep stamper for a parsed netlist: overrides one voltage source.
+struct SweptNetlist<'a> {
+    netlist: &'a spicier_core::Netlist,
+    branch: usize,
+}
+
+impl NonlinearSweepStamper for SweptNetlist<'_> {
+    fn stamp_at_with_sweep(&self, mna: &mut MnaSystem, solution: &DVector<f64>, value: f64) {
+        self.netlist.stamp_nonlinear_into(mna, solution);
+        mna.rhs_mut()[self.netlist.num_nodes() + self.branch] = value;
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.netlist.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        self.netlist.num_current_vars()
+    }
+}
+
+/// Test: differentiating a MOSFET Id-Vds sweep reproduces the model's gds.
+#[test]
+fn test_mosfet_vds_sweep_gds() {
+    use spicier_devices::{Mosfet, MosfetParams, MosfetType};
+
+    let netlist_str = r#"
+MOSFET Id-Vds
+.MODEL NMOD NMOS (VTO=0.5 KP=1e-4 LAMBDA=0.02)
+VDS d 0 DC 0
+VGS g 0 DC 1.5
+M1 d g 0 0 NMOD W=20u L=1u
+.end
+"#;
+
+    let result = parse_full(netlist_str).expect("parse should succeed");
+    let netlist = &result.netlist;
+    let branch = netlist.find_vsource_branch_index("VDS").unwrap();
+
+    let params = DcSweepParams {
+        source_name: "VDS".to_string(),
+        start: 0.0,
+        stop: 3.0,
+        step: 0.05,
+    };
+    let sweep = solve_dc_sweep_nonlinear(
+        &SweptNetlist { netlist, branch },
+        &params,
+        &ConvergenceCriteria::default(),
+    )
+    .expect("sweep should converge");
+
+    // Drain current flows into VDS's positive terminal, so Id = -I(VDS)
+    let id_curve: Vec<(f64, f64)> = sweep
+        .current_waveform(branch)
+        .into_iter()
+        .map(|(vds, i)| (vds, -i))
+        .collect();
+    let gds_curve = DcSweepResult::derivative(&id_curve);
+    assert_eq!(gds_curve.len(), sweep.sweep_values.len());
+
+    let reference = Mosfet::with_params(
+        "M1",
+        NodeId::new(1),
+        NodeId::new(2),
+        NodeId::GROUND,
+        MosfetType::Nmos,
+        MosfetParams {
+            vto: 0.5,
+            kp: 1e-4,
+            lambda: 0.02,
+            w: 20e-6,
+            l: 1e-6,
+            ..MosfetParams::nmos_default()
+        },
+    );
+
+    for (i, &(vds, gds_numeric)) in gds_curve.iter().enumerate() {
+        let (id, gds, _, _) = reference.evaluate(1.5, vds);
+        assert!(
+            (id_curve[i].1 - id).abs() <= 1e-6 * id.abs() + 1e-12,
+            "Id({}) = {} (model {})",
+            vds,
+            id_curve[i].1,
+            id
+        );
+
+        // Skip the one-sided ends and the knee at Vds = Vgs - Vth, where
+        // d²Id/dVds² jumps and central differences are only first order.
+        let at_knee = (vds - 1.0).abs() < 1.5 * params.step;
+        if i == 0 || i == gds_curve.len() - 1 || at_knee {
+            continue;
+        }
+        assert!(
+            (gds_numeric - gds).abs() <= 0.01 * gds.abs() + 1e-9,
+            "gds({}) = {} (model {})",
+            vds,
+            gds_numeric,
+            gds
+        );
+    }
+}
+
+/// Nonlinear nested sweep stamper for a parsed netlist: overrides two voltage sources.
+struct NestedSweptNetlist<'a> {
+    netlist: &'a spicier_core::Netlist,
+    outer_branch: usize,
+    inner_branch: usize,
+}
+
+impl NonlinearNestedSweepStamper for NestedSweptNetlist<'_> {
+    fn stamp_at_with_sweeps(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        outer: f64,
+        inner: f64,
+    ) {
+        self.netlist.stamp_nonlinear_into(mna, solution);
+        let n = self.netlist.num_nodes();
+        mna.rhs_mut()[n + self.outer_branch] = outer;
+        mna.rhs_mut()[n + self.inner_branch] = inner;
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.netlist.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        self.netlist.num_current_vars()
+    }
+}
+
+/// Test: nested Vgs/Vds sweep produces the MOSFET I-V family with
+/// saturation knees at Vds = Vgs - Vth.
+#[test]
+fn test_mosfet_nested_sweep_family() {
+    let netlist_str = r#"
+MOSFET Family
+.MODEL NMOD NMOS (VTO=0.5 KP=1e-4 LAMBDA=0.02)
+VDS d 0 DC 0
+VGS g 0 DC 0
+M1 d g 0 0 NMOD W=20u L=1u
+.end
+"#;
+
+    let result = parse_full(netlist_str).expect("parse should succeed");
+    let netlist = &result.netlist;
+    let vds_branch = netlist.find_vsource_branch_index("VDS").unwrap();
+    let vgs_branch = netlist.find_vsource_branch_index("VGS").unwrap();
+
+    let outer = DcSweepParams {
+        source_name: "VGS".to_string(),
+        start: 1.0,
+        stop: 2.0,
+        step: 0.5,
+    };
+    let inner = DcSweepParams {
+        source_name: "VDS".to_string(),
+        start: 0.0,
+        stop: 3.0,
+        step: 0.05,
+    };
+    let stamper = NestedSweptNetlist {
+        netlist,
+        outer_branch: vgs_branch,
+        inner_branch: vds_branch,
+    };
+    let sweep =
+        solve_dc_nested_sweep_nonlinear(&stamper, &outer, &inner, &ConvergenceCriteria::default())
+            .expect("nested sweep should converge");
+
+    assert_eq!(sweep.outer_values.len(), 3);
+    assert_eq!(sweep.solutions.len(), 3);
+    assert!(sweep.solutions.iter().all(|row| row.len() == 61));
+
+    let family = sweep.current_family(vds_branch);
+    let mut prev_sat_id = 0.0;
+    for (vgs, curve) in family {
+        // Id = -I(VDS)
+        let id_curve: Vec<(f64, f64)> = curve.into_iter().map(|(v, i)| (v, -i)).collect();
+        let gds = DcSweepResult::derivative(&id_curve);
+
+        // Saturation: Id = beta/2 * Vov^2 * (1 + lambda*Vds), so gds = lambda * Id / (1 + lambda*Vds)
+        let vov = vgs - 0.5;
+        let (vds_end, id_end) = *id_curve.last().unwrap();
+        let gds_sat = 0.02 * id_end / (1.0 + 0.02 * vds_end);
+
+        // Knee: first point where the output conductance drops to near its saturated value
+        let knee = gds
+            .iter()
+            .find(|&&(_, g)| g < 2.0 * gds_sat)
+            .map(|&(vds, _)| vds)
+            .expect("curve should saturate");
+        assert!(
+            (knee - vov).abs() <= 2.0 * inner.step,
+            "Vgs={}: knee at Vds={} (expected ≈ {})",
+            vgs,
+            knee,
+            vov
+        );
+
+        // Well below the knee the device is a resistor; well above it a current source
+        let g_linear = gds[1].1;
+        assert!(
+            g_linear > 50.0 * gds_sat,
+            "Vgs={}: linear gds {}",
+            vgs,
+            g_linear
+        );
+
+        // Curves stack with Vgs
+        assert!(
+            id_end > prev_sat_id,
+            "Vgs={}: Id not increasing with Vgs",
+            vgs
+        );
+        prev_sat_id = id_end;
+    }
+}
+
 /// Test: Parsing controlled sources E/G
 #[test]
 fn test_parse_controlled_sources() {
@@ -441,6 +1086,8 @@ C1 2 0 1u
         tstop: 5e-3,
         tstep: 10e-6,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: Vec::new(),
     };
 
     let result = solve_transient(&stamper, &mut caps, &mut inds, &params, &dc_solution)
@@ -795,6 +1442,65 @@ Rd d 0 10k
     // With Vsg = 1.8 - 0.8 = 1.0V > |Vth| = 0.4V, PMOS is on
 }
 
+/// Test: BSIM3 small-signal report gives ft consistent with gm and gate caps.
+#[test]
+fn test_bsim3_small_signal_report() {
+    let netlist_str = r#"
+BSIM3 small-signal
+.MODEL NMOD NMOS LEVEL=49 VTH0=0.4 U0=400 TOX=9e-9 K1=0.5 VSAT=1.5e5
+M1 d g 0 0 NMOD W=1u L=100n
+Vds d 0 DC 1.0
+Vgs g 0 DC 1.0
+.end
+"#;
+
+    let netlist = parse(netlist_str).expect("parse should succeed");
+
+    struct NlStamper<'a> {
+        netlist: &'a spicier_core::Netlist,
+    }
+    impl NonlinearStamper for NlStamper<'_> {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.netlist.stamp_nonlinear_into(mna, solution);
+        }
+    }
+
+    let result = solve_newton_raphson(
+        netlist.num_nodes(),
+        netlist.num_current_vars(),
+        &NlStamper { netlist: &netlist },
+        &ConvergenceCriteria::default(),
+        None,
+    )
+    .expect("NR should converge");
+
+    let report = netlist.small_signal_report(&result.solution);
+    assert_eq!(report.mosfets.len(), 1);
+    assert!(report.bjts.is_empty() && report.diodes.is_empty());
+
+    let m1 = report.mosfet("M1").expect("M1 in report");
+    assert!(m1.gm > 0.0 && m1.gds > 0.0);
+    assert!(m1.cgs > 0.0, "saturated BSIM3 should report Cgs");
+
+    let ft = m1.ft().expect("BSIM3 reports gate capacitance");
+    let expected = m1.gm / (2.0 * std::f64::consts::PI * (m1.cgs + m1.cgd));
+    assert!((ft - expected).abs() <= 1e-12 * expected);
+    // A 100nm device in saturation sits in the tens-of-GHz range.
+    assert!((1e9..1e12).contains(&ft), "ft = {:.3e} Hz", ft);
+    assert!(m1.intrinsic_gain() > 1.0);
+
+    // Parameters match the device's own linearization.
+    let device = &netlist.devices()[0];
+    match device.ac_info_at(&result.solution) {
+        AcDeviceInfo::Bsim3Mosfet {
+            gm, gds, cgs, cgd, ..
+        } => {
+            assert_eq!((gm, gds, cgs, cgd), (m1.gm, m1.gds, m1.cgs, m1.cgd));
+        }
+        other => panic!("unexpected AC info {:?}", other),
+    }
+}
+
 /// Test: BSIM3 NMOS in linear region.
 #[test]
 fn test_bsim3_nmos_linear() {
@@ -1804,3 +2510,83 @@ Rs out 0 {rs}
 
     println!("\n=== Source Follower Test PASSED ===\n");
 }
+
+/// Devices iterate in declaration order, and repeated parses lay out the
+/// solution vector identically.
+#[test]
+fn test_device_and_solution_ordering_is_deterministic() {
+    let netlist_str = r#"
+Ordering Test
+.SUBCKT DIV top bot
+RA top mid 2k
+RB mid bot 2k
+.ENDS
+Vdd vdd 0 DC 5
+Rload out 0 10k
+X1 vdd out DIV
+L1 out tap 1m
+E1 amp 0 tap 0 2
+Rz amp 0 1k
+I1 0 tap 1m
+.end
+"#;
+
+    let first = parse_full(netlist_str).unwrap();
+    let second = parse_full(netlist_str).unwrap();
+
+    let names = |netlist: &spicier_core::Netlist| -> Vec<String> {
+        netlist
+            .devices()
+            .iter()
+            .map(|d| d.device_name().to_string())
+            .collect()
+    };
+    let expected = ["Vdd", "Rload", "RX1_A", "RX1_B", "L1", "E1", "Rz", "I1"];
+    assert_eq!(names(&first.netlist), expected);
+    assert_eq!(names(&second.netlist), expected);
+
+    assert_eq!(first.node_map, second.node_map);
+    assert_eq!(
+        first.netlist.variable_layout(),
+        second.netlist.variable_layout()
+    );
+
+    let a = solve_dc(&first.netlist.assemble_mna()).unwrap();
+    let b = solve_dc(&second.netlist.assemble_mna()).unwrap();
+    assert_eq!(a.node_voltages, b.node_voltages);
+    assert_eq!(a.branch_currents, b.branch_currents);
+}
+
+/// Unsupported devices and directives fail the parse by default; in warn
+/// mode they are listed and the rest of the circuit still solves.
+#[test]
+fn test_unsupported_constructs() {
+    let netlist_str = r#"
+Divider with extras
+V1 1 0 DC 10
+R1 1 2 1k
+R2 2 0 1k
+Z1 2 0 zmod
+.disto dec 10 1k 100meg
+.op
+.end
+"#;
+
+    let err = parse_full(netlist_str).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Z1 (line 6)"), "{message}");
+    assert!(message.contains(".DISTO (line 7)"), "{message}");
+    match err {
+        Error::Unsupported(list) => assert_eq!(list.len(), 2),
+        other => panic!("expected Error::Unsupported, got {other:?}"),
+    }
+
+    let result = parse_full_with_policy(netlist_str, UnsupportedPolicy::Warn).unwrap();
+    let names: Vec<_> = result.unsupported.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, ["Z1", ".DISTO"]);
+    assert_eq!(result.netlist.num_devices(), 3);
+    assert!(matches!(result.analyses[..], [AnalysisCommand::Op]));
+
+    let solution = solve_dc(&result.netlist.assemble_mna()).unwrap();
+    assert!((solution.voltage(NodeId::new(2)) - 5.0).abs() < 1e-9);
+}
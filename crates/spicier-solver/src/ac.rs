@@ -2,11 +2,14 @@
 
 use std::f64::consts::PI;
 
+use faer::sparse::{SparseColMat, SymbolicSparseColMat};
 use nalgebra::{DMatrix, DVector};
 use num_complex::Complex;
+use serde::{Deserialize, Serialize};
+use spicier_core::mna::TripletPattern;
 
 use crate::dispatch::DispatchConfig;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::gmres::GmresConfig;
 use crate::linear::{CachedSparseLuComplex, SPARSE_THRESHOLD, solve_complex};
 use crate::operator::ComplexOperator;
@@ -14,7 +17,7 @@ use crate::preconditioner::{ComplexJacobiPreconditioner, ComplexPreconditioner};
 use crate::sparse_operator::SparseComplexOperator;
 
 /// AC sweep type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum AcSweepType {
     /// Linear frequency spacing.
@@ -26,7 +29,7 @@ pub enum AcSweepType {
 }
 
 /// AC analysis parameters.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AcParams {
     /// Start frequency (Hz).
     pub fstart: f64,
@@ -301,45 +304,155 @@ pub struct AcResult {
     pub points: Vec<AcPoint>,
     /// Number of nodes (excluding ground).
     pub num_nodes: usize,
+    /// AC stimulus on each branch-current row, indexed like the branch
+    /// currents in [`AcPoint::solution`]: a voltage source's AC value, zero
+    /// for inductors and other non-source branches.
+    pub branch_stimulus: Vec<Complex<f64>>,
 }
 
 impl AcResult {
+    /// Iterate over `(frequency, voltage)` at a node without allocating.
+    ///
+    /// `node_idx` is 0-based (node 1 → index 0). Lets derived quantities
+    /// (magnitude, phase, group delay) be computed in one pass over a large
+    /// sweep; the `Vec`-returning accessors below are built on it.
+    pub fn iter_node(&self, node_idx: usize) -> impl Iterator<Item = (f64, Complex<f64>)> + '_ {
+        self.points
+            .iter()
+            .map(move |p| (p.frequency, p.solution[node_idx]))
+    }
+
     /// Get complex voltage at a node across all frequencies.
     ///
     /// `node_idx` is 0-based (node 1 → index 0).
     pub fn voltage_at(&self, node_idx: usize) -> Vec<(f64, Complex<f64>)> {
-        self.points
-            .iter()
-            .map(|p| (p.frequency, p.solution[node_idx]))
-            .collect()
+        self.iter_node(node_idx).collect()
     }
 
     /// Get voltage magnitude in dB at a node across all frequencies.
     pub fn magnitude_db(&self, node_idx: usize) -> Vec<(f64, f64)> {
-        self.points
-            .iter()
-            .map(|p| {
-                let mag = p.solution[node_idx].norm();
-                (p.frequency, 20.0 * mag.log10())
-            })
+        self.iter_node(node_idx)
+            .map(|(f, v)| (f, 20.0 * v.norm().log10()))
             .collect()
     }
 
     /// Get voltage phase in degrees at a node across all frequencies.
     pub fn phase_deg(&self, node_idx: usize) -> Vec<(f64, f64)> {
-        self.points
+        self.iter_node(node_idx)
+            .map(|(f, v)| (f, v.arg() * 180.0 / PI))
+            .collect()
+    }
+
+    /// Bode plot of the transfer from one node to another.
+    ///
+    /// Returns `(frequency, gain_db, phase_deg)` with gain `20·log10|V_out/V_in|`
+    /// and phase `∠(V_out/V_in)` in (-180°, 180°]. Because the ratio is taken
+    /// point by point, the input's own magnitude and phase (a loaded source,
+    /// an internal stage) drop out. `magnitude_db(output)` is the special case
+    /// of an input driven at 1∠0°. Both indices are 0-based; points where the
+    /// input is exactly zero give an infinite or NaN gain. Errors if either
+    /// index is out of range.
+    pub fn bode(&self, output: usize, input: usize) -> Result<Vec<(f64, f64, f64)>> {
+        self.check_node_index(output)?;
+        self.check_node_index(input)?;
+        Ok(self
+            .points
             .iter()
             .map(|p| {
-                let phase = p.solution[node_idx].arg() * 180.0 / PI;
-                (p.frequency, phase)
+                let h = p.solution[output] / p.solution[input];
+                (p.frequency, 20.0 * h.norm().log10(), h.arg() * 180.0 / PI)
             })
-            .collect()
+            .collect())
+    }
+
+    /// Complex gain from a voltage source to a node across the sweep.
+    ///
+    /// Returns `(frequency, V_out / V_src)`, where `V_src` is the AC value of
+    /// the source on branch `in_src_idx`. Unlike [`bode`](Self::bode), the
+    /// reference is the source itself rather than a node voltage, so output
+    /// noise divided by `|H|²` is referred to that input. `out_node` is 0-based;
+    /// a source with zero AC value gives infinite or NaN gains. Errors if
+    /// either index is out of range.
+    pub fn transfer_function(
+        &self,
+        in_src_idx: usize,
+        out_node: usize,
+    ) -> Result<Vec<(f64, Complex<f64>)>> {
+        let &stimulus = self
+            .branch_stimulus
+            .get(in_src_idx)
+            .ok_or(Error::IndexOutOfRange {
+                what: "source branch",
+                index: in_src_idx,
+                len: self.branch_stimulus.len(),
+            })?;
+        self.check_node_index(out_node)?;
+        Ok(self
+            .iter_node(out_node)
+            .map(|(f, v)| (f, v / stimulus))
+            .collect())
+    }
+
+    /// Check a 0-based node index against [`num_nodes`](Self::num_nodes).
+    fn check_node_index(&self, node_idx: usize) -> Result<()> {
+        if node_idx < self.num_nodes {
+            Ok(())
+        } else {
+            Err(Error::IndexOutOfRange {
+                what: "node",
+                index: node_idx,
+                len: self.num_nodes,
+            })
+        }
     }
 
     /// Get all frequency values.
     pub fn frequencies(&self) -> Vec<f64> {
         self.points.iter().map(|p| p.frequency).collect()
     }
+
+    /// Interpolate the complex voltage at a node to an off-grid frequency.
+    ///
+    /// Between the two bracketing sweep points, log-magnitude and phase are
+    /// interpolated linearly in log-frequency, which follows Bode asymptotes
+    /// exactly. The phase step is taken the short way round, so a ±180° wrap
+    /// between points does not swing through zero. Errors if `freq` is
+    /// outside the swept range.
+    pub fn response_at(&self, node_idx: usize, freq: f64) -> Result<Complex<f64>> {
+        self.check_node_index(node_idx)?;
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return Err(Error::SolverError("AC result has no points".into()));
+        };
+        if !(first.frequency..=last.frequency).contains(&freq) {
+            return Err(Error::SolverError(format!(
+                "frequency {} Hz outside swept range {} to {} Hz",
+                freq, first.frequency, last.frequency
+            )));
+        }
+
+        let hi = self
+            .points
+            .partition_point(|p| p.frequency < freq)
+            .max(1)
+            .min(self.points.len() - 1);
+        let (p0, p1) = (&self.points[hi - 1], &self.points[hi]);
+        let (h0, h1) = (p0.solution[node_idx], p1.solution[node_idx]);
+        if freq == p1.frequency || self.points.len() == 1 {
+            return Ok(h1);
+        }
+        if freq == p0.frequency {
+            return Ok(h0);
+        }
+
+        let t = (freq / p0.frequency).ln() / (p1.frequency / p0.frequency).ln();
+        if h0.norm() == 0.0 || h1.norm() == 0.0 {
+            // No log-magnitude at a transmission zero; fall back to linear.
+            return Ok(h0 + (h1 - h0) * t);
+        }
+        let log_mag = h0.norm().ln() + t * (h1.norm().ln() - h0.norm().ln());
+        let dphase = (h1.arg() - h0.arg() + PI).rem_euclid(2.0 * PI) - PI;
+        Ok(Complex::from_polar(log_mag.exp(), h0.arg() + t * dphase))
+    }
 }
 
 /// Run an AC small-signal analysis.
@@ -355,20 +468,103 @@ impl AcResult {
 /// For large systems (>= SPARSE_THRESHOLD), uses cached symbolic factorization
 /// to speed up repeated solves across frequency points.
 pub fn solve_ac(stamper: &dyn AcStamper, params: &AcParams) -> Result<AcResult> {
+    let mut result = AcResult {
+        points: Vec::new(),
+        num_nodes: stamper.num_nodes(),
+        branch_stimulus: branch_stimulus(stamper),
+    };
+    sweep_ac(stamper, params, |frequency, solution| {
+        result.points.push(AcPoint {
+            frequency,
+            solution,
+        });
+    })?;
+    Ok(result)
+}
+
+/// AC response of a single output node, from [`solve_ac_single_output`].
+#[derive(Debug, Clone)]
+pub struct AcTransferResult {
+    /// Output node index (0-based, node 1 → index 0).
+    pub output_node: usize,
+    /// `(frequency, response)` pairs.
+    pub points: Vec<(f64, Complex<f64>)>,
+}
+
+impl AcTransferResult {
+    /// Get the response magnitude in dB across all frequencies.
+    pub fn magnitude_db(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|&(f, v)| (f, 20.0 * v.norm().log10()))
+            .collect()
+    }
+
+    /// Get the response phase in degrees across all frequencies.
+    pub fn phase_deg(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|&(f, v)| (f, v.arg() * 180.0 / PI))
+            .collect()
+    }
+
+    /// Get all frequency values.
+    pub fn frequencies(&self) -> Vec<f64> {
+        self.points.iter().map(|&(f, _)| f).collect()
+    }
+}
+
+/// Run an AC sweep keeping only the response at `output_node`.
+///
+/// The full system is still solved at every frequency, but each solution
+/// vector is dropped once the output entry is read, so memory grows with the
+/// number of frequencies rather than frequencies × system size.
+///
+/// An adjoint solve (Aᵀy = eₒᵤₜ) does not help here: it costs one
+/// factorization per frequency, the same as the forward solve, and only pays
+/// off when the transfer from many inputs to one output is needed (as in
+/// noise analysis).
+pub fn solve_ac_single_output(
+    stamper: &dyn AcStamper,
+    output_node: usize,
+    params: &AcParams,
+) -> Result<AcTransferResult> {
     let num_nodes = stamper.num_nodes();
-    let num_vsources = stamper.num_vsources();
-    let frequencies = generate_frequencies(params);
-    let mna_size = num_nodes + num_vsources;
+    if output_node >= num_nodes {
+        return Err(Error::IndexOutOfRange {
+            what: "output node",
+            index: output_node,
+            len: num_nodes,
+        });
+    }
 
-    let mut result = AcResult {
-        points: Vec::with_capacity(frequencies.len()),
-        num_nodes,
+    let mut result = AcTransferResult {
+        output_node,
+        points: Vec::new(),
     };
+    sweep_ac(stamper, params, |frequency, solution| {
+        result.points.push((frequency, solution[output_node]));
+    })?;
+    Ok(result)
+}
+
+/// Solve the AC system at each sweep frequency, handing each solution to `visit`.
+///
+/// For large systems (>= SPARSE_THRESHOLD), uses cached symbolic factorization
+/// to speed up repeated solves across frequency points.
+fn sweep_ac(
+    stamper: &dyn AcStamper,
+    params: &AcParams,
+    mut visit: impl FnMut(f64, DVector<Complex<f64>>),
+) -> Result<()> {
+    let num_nodes = stamper.num_nodes();
+    let num_vsources = stamper.num_vsources();
+    let mna_size = num_nodes + num_vsources;
 
     // Cached sparse solver (created on first frequency point if needed)
     let mut cached_solver: Option<CachedSparseLuComplex> = None;
 
-    for &freq in &frequencies {
+    for freq in generate_frequencies(params) {
         let omega = 2.0 * PI * freq;
         let mut mna = ComplexMna::new(num_nodes, num_vsources);
 
@@ -387,13 +583,20 @@ pub fn solve_ac(stamper: &dyn AcStamper, params: &AcParams) -> Result<AcResult>
             solve_complex(&mna.to_dense_matrix(), mna.rhs())?
         };
 
-        result.points.push(AcPoint {
-            frequency: freq,
-            solution,
-        });
+        visit(freq, solution);
     }
 
-    Ok(result)
+    Ok(())
+}
+
+/// AC stimulus on each branch-current row of the stamped system.
+///
+/// Source values do not depend on frequency, so one DC stamp is enough.
+fn branch_stimulus(stamper: &dyn AcStamper) -> Vec<Complex<f64>> {
+    let num_nodes = stamper.num_nodes();
+    let mut mna = ComplexMna::new(num_nodes, stamper.num_vsources());
+    stamper.stamp_ac(&mut mna, 0.0);
+    mna.rhs().as_slice()[num_nodes..].to_vec()
 }
 
 /// Run AC analysis with configurable dispatch.
@@ -418,6 +621,7 @@ pub fn solve_ac_dispatched(
     let mut result = AcResult {
         points: Vec::with_capacity(frequencies.len()),
         num_nodes,
+        branch_stimulus: branch_stimulus(stamper),
     };
 
     // Decide solver strategy based on size
@@ -425,6 +629,8 @@ pub fn solve_ac_dispatched(
 
     // Cached sparse solver for direct LU
     let mut cached_solver: Option<CachedSparseLuComplex> = None;
+    // Cached operator structure and preconditioner for GMRES
+    let mut gmres_cache: Option<AcGmresCache> = None;
 
     for &freq in &frequencies {
         let omega = 2.0 * PI * freq;
@@ -433,8 +639,15 @@ pub fn solve_ac_dispatched(
         stamper.stamp_ac(&mut mna, omega);
 
         let solution = if use_gmres {
-            // Use iterative GMRES with preconditioner
-            solve_ac_gmres(&mna, &config.gmres_config)?
+            // Use iterative GMRES, refilling the cached structure
+            if !gmres_cache
+                .as_ref()
+                .is_some_and(|cache| cache.pattern.matches(&mna.triplets))
+            {
+                gmres_cache = Some(AcGmresCache::new(mna_size, &mna.triplets)?);
+            }
+            let cache = gmres_cache.as_mut().unwrap();
+            cache.solve(&mna, &config.gmres_config)
         } else if mna_size >= SPARSE_THRESHOLD {
             // Use cached sparse direct solver
             let solver = match &cached_solver {
@@ -459,52 +672,84 @@ pub fn solve_ac_dispatched(
     Ok(result)
 }
 
-/// Solve a single AC system using GMRES.
-fn solve_ac_gmres(mna: &ComplexMna, config: &GmresConfig) -> Result<DVector<Complex<f64>>> {
-    use num_complex::Complex64 as C64;
+/// Sparse operator and Jacobi preconditioner for the iterative AC path.
+///
+/// `G + jωC` has the same sparsity pattern at every frequency, so the sorted
+/// CSC structure is built once per sweep. Each frequency then only scatters
+/// its stamped values into that structure and re-inverts the diagonal,
+/// rather than re-sorting the triplets, the AC analogue of the symbolic
+/// reuse in [`CachedSparseLuComplex`].
+struct AcGmresCache {
+    /// Mapping from stamped triplets to CSC value slots.
+    pattern: TripletPattern,
+    operator: SparseComplexOperator,
+    /// CSC value slot of each row's diagonal, if stamped.
+    diag_slots: Vec<Option<usize>>,
+}
 
-    // Build sparse operator from triplets
-    let size = mna.size();
-    let triplets_c64: Vec<_> = mna
-        .triplets
-        .iter()
-        .map(|&(r, c, v)| (r, c, C64::new(v.re, v.im)))
-        .collect();
+impl AcGmresCache {
+    /// Build the structure from one frequency's stamped triplets.
+    fn new(size: usize, triplets: &[(usize, usize, Complex<f64>)]) -> Result<Self> {
+        let pattern = TripletPattern::new(triplets);
+
+        let mut col_ptr = vec![0; size + 1];
+        let mut row_idx = Vec::with_capacity(pattern.nnz());
+        let mut diag_slots = vec![None; size];
+        for (slot, &(row, col)) in pattern.entries().iter().enumerate() {
+            if row >= size || col >= size {
+                return Err(Error::SolverError("Failed to build sparse operator".into()));
+            }
+            col_ptr[col + 1] += 1;
+            row_idx.push(row);
+            if row == col {
+                diag_slots[row] = Some(slot);
+            }
+        }
+        for col in 0..size {
+            col_ptr[col + 1] += col_ptr[col];
+        }
 
-    let operator = SparseComplexOperator::from_triplets(size, &triplets_c64).ok_or_else(|| {
-        crate::error::Error::SolverError("Failed to build sparse operator".into())
-    })?;
+        let symbolic = SymbolicSparseColMat::new_checked(size, size, col_ptr, None, row_idx);
+        let matrix = SparseColMat::new(symbolic, vec![Complex::new(0.0, 0.0); pattern.nnz()]);
 
-    // Build Jacobi preconditioner
-    let preconditioner = ComplexJacobiPreconditioner::from_triplets(size, &triplets_c64);
-
-    // Convert RHS to C64
-    let rhs_c64: Vec<C64> = mna.rhs().iter().map(|&v| C64::new(v.re, v.im)).collect();
-
-    // Solve with preconditioned GMRES
-    let gmres_result = crate::gmres::solve_gmres_preconditioned(
-        &operator as &dyn ComplexOperator,
-        &preconditioner as &dyn ComplexPreconditioner,
-        &rhs_c64,
-        config,
-    );
-
-    if !gmres_result.converged {
-        log::warn!(
-            "AC GMRES did not converge after {} iterations (residual: {:.2e})",
-            gmres_result.iterations,
-            gmres_result.residual
-        );
+        Ok(Self {
+            pattern,
+            operator: SparseComplexOperator::from_matrix(matrix),
+            diag_slots,
+        })
     }
 
-    // Convert back to Complex<f64>
-    let solution: Vec<Complex<f64>> = gmres_result
-        .x
-        .iter()
-        .map(|&v| Complex::new(v.re, v.im))
-        .collect();
+    /// Solve one frequency's system, whose triplets must match the pattern.
+    fn solve(&mut self, mna: &ComplexMna, config: &GmresConfig) -> DVector<Complex<f64>> {
+        self.pattern
+            .sum_into(&mna.triplets, self.operator.values_mut());
+
+        let values = self.operator.matrix().val();
+        let diag: Vec<Complex<f64>> = self
+            .diag_slots
+            .iter()
+            .map(|slot| slot.map_or(Complex::new(0.0, 0.0), |k| values[k]))
+            .collect();
+        let preconditioner = ComplexJacobiPreconditioner::from_diagonal(&diag);
+
+        let rhs: Vec<Complex<f64>> = mna.rhs().iter().copied().collect();
+        let gmres_result = crate::gmres::solve_gmres_preconditioned(
+            &self.operator as &dyn ComplexOperator,
+            &preconditioner as &dyn ComplexPreconditioner,
+            &rhs,
+            config,
+        );
+
+        if !gmres_result.converged {
+            log::warn!(
+                "AC GMRES did not converge after {} iterations (residual: {:.2e})",
+                gmres_result.iterations,
+                gmres_result.residual
+            );
+        }
 
-    Ok(DVector::from_vec(solution))
+        DVector::from_vec(gmres_result.x)
+    }
 }
 
 #[cfg(test)]
@@ -553,6 +798,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gmres_cache_matches_fresh_preconditioner() {
+        // Ladder driven by a 1 V source: series R, shunt R || C at each node.
+        struct RcLadderStamper(usize);
+        impl AcStamper for RcLadderStamper {
+            fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+                mna.stamp_voltage_source(Some(0), None, 0, Complex::new(1.0, 0.0));
+                for i in 0..self.0 - 1 {
+                    mna.stamp_conductance(Some(i), Some(i + 1), 1.0 / 100.0);
+                    let y = Complex::new(1.0 / 1000.0, omega * 1e-9);
+                    mna.stamp_admittance(Some(i + 1), None, y);
+                }
+            }
+            fn num_nodes(&self) -> usize {
+                self.0
+            }
+            fn num_vsources(&self) -> usize {
+                1
+            }
+        }
+
+        let stamper = RcLadderStamper(200);
+        let params = AcParams {
+            fstart: 1e3,
+            fstop: 1e7,
+            num_points: 5,
+            sweep_type: AcSweepType::Decade,
+        };
+        let config = DispatchConfig::default().with_gmres_threshold(0);
+        let cached = solve_ac_dispatched(&stamper, &params, &config).unwrap();
+        let direct = solve_ac(&stamper, &params).unwrap();
+
+        let size = stamper.num_nodes() + stamper.num_vsources();
+        for ((point, fresh), exact) in cached
+            .points
+            .iter()
+            .zip(&generate_frequencies(&params))
+            .zip(&direct.points)
+        {
+            let mut mna = ComplexMna::new(stamper.num_nodes(), stamper.num_vsources());
+            stamper.stamp_ac(&mut mna, 2.0 * PI * fresh);
+            let fresh = AcGmresCache::new(size, &mna.triplets)
+                .unwrap()
+                .solve(&mna, &config.gmres_config);
+
+            let scale = exact.solution.camax();
+            let err = (&point.solution - &fresh).camax();
+            // Same values and preconditioner, so the same iterates.
+            assert!(err < 1e-12 * scale, "cached vs fresh {err:e}");
+            let err = (&point.solution - &exact.solution).camax();
+            // GMRES stops at a 1e-8 relative residual.
+            assert!(err < 1e-4 * scale, "cached vs direct {err:e}");
+        }
+    }
+
     #[test]
     fn test_generate_linear_frequencies() {
         let params = AcParams {
@@ -725,6 +1025,249 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_transfer_function_matches_magnitude_db() {
+        let (r, c) = (1000.0, 1e-6);
+        let f3db = 1.0 / (2.0 * PI * r * c);
+        let params = AcParams {
+            fstart: f3db / 100.0,
+            fstop: f3db * 100.0,
+            num_points: 10,
+            sweep_type: AcSweepType::Decade,
+        };
+        let stamper = RcLowPassStamper {
+            resistance: r,
+            capacitance: c,
+        };
+        let result = solve_ac(&stamper, &params).unwrap();
+        assert_eq!(result.branch_stimulus, vec![Complex::new(1.0, 0.0)]);
+
+        // 10 points per decade from fc/100 puts a point exactly on fc
+        let h = result.transfer_function(0, 1).unwrap();
+        let mag_db = result.magnitude_db(1);
+        let idx = h
+            .iter()
+            .position(|&(f, _)| (f / f3db - 1.0).abs() < 1e-9)
+            .unwrap();
+        let h_db = 20.0 * h[idx].1.norm().log10();
+        assert!((h_db - mag_db[idx].1).abs() < 1e-9);
+        assert!((h_db + 3.0103).abs() < 1e-3, "|H(fc)| = {h_db:.4} dB");
+        assert!((h[idx].1.arg() * 180.0 / PI + 45.0).abs() < 1e-6);
+
+        /// The same filter driven at 2∠90° V.
+        struct Driven(RcLowPassStamper);
+        impl AcStamper for Driven {
+            fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+                self.0.stamp_ac(mna, omega);
+                *mna.rhs_mut() *= Complex::new(0.0, 2.0);
+            }
+            fn num_nodes(&self) -> usize {
+                self.0.num_nodes()
+            }
+            fn num_vsources(&self) -> usize {
+                self.0.num_vsources()
+            }
+        }
+
+        // The gain is independent of the source's own amplitude and phase
+        let driven = solve_ac(&Driven(stamper), &params).unwrap();
+        for ((f, a), (_, b)) in driven.transfer_function(0, 1).unwrap().into_iter().zip(&h) {
+            assert!((a - b).norm() < 1e-12, "at {f} Hz: {a} vs {b}");
+        }
+
+        assert!(matches!(
+            result.transfer_function(1, 1),
+            Err(Error::IndexOutOfRange {
+                index: 1,
+                len: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            result.transfer_function(0, 2),
+            Err(Error::IndexOutOfRange {
+                index: 2,
+                len: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bode_between_internal_nodes() {
+        // Two cascaded RC sections driven by 2∠30° V:
+        //   node0 -- R1 -- node1 -- R2 -- node2, C1 at node1, C2 at node2.
+        // node1 is loaded by the second section, but all current through R2
+        // flows into C2, so V2/V1 = 1/(1 + jωR2C2) exactly.
+        struct CascadedRcStamper;
+        impl AcStamper for CascadedRcStamper {
+            fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+                let source = Complex::from_polar(2.0, 30f64.to_radians());
+                mna.stamp_voltage_source(Some(0), None, 0, source);
+                mna.stamp_conductance(Some(0), Some(1), 1e-3);
+                mna.stamp_admittance(Some(1), None, Complex::new(0.0, omega * 1e-6));
+                mna.stamp_conductance(Some(1), Some(2), 1e-4);
+                mna.stamp_admittance(Some(2), None, Complex::new(0.0, omega * 10e-9));
+            }
+
+            fn num_nodes(&self) -> usize {
+                3
+            }
+
+            fn num_vsources(&self) -> usize {
+                1
+            }
+        }
+
+        let params = AcParams {
+            fstart: 10.0,
+            fstop: 1e5,
+            num_points: 10,
+            sweep_type: AcSweepType::Decade,
+        };
+        let result = solve_ac(&CascadedRcStamper, &params).unwrap();
+        let tau = 1e4 * 10e-9;
+
+        let bode = result.bode(2, 1).unwrap();
+        assert_eq!(bode.len(), result.points.len());
+        for &(f, gain_db, phase_deg) in &bode {
+            let h = Complex::new(1.0, 0.0) / Complex::new(1.0, 2.0 * PI * f * tau);
+            assert!((gain_db - 20.0 * h.norm().log10()).abs() < 1e-9, "f = {f}");
+            assert!((phase_deg - h.arg().to_degrees()).abs() < 1e-9, "f = {f}");
+        }
+
+        // Against the source node, the source's 2∠30° drops out too.
+        for ((_, gain_db, phase_deg), (_, v2)) in
+            result.bode(2, 0).unwrap().iter().zip(result.voltage_at(2))
+        {
+            let h = v2 / Complex::from_polar(2.0, 30f64.to_radians());
+            assert!((gain_db - 20.0 * h.norm().log10()).abs() < 1e-9);
+            assert!((phase_deg - h.arg().to_degrees()).abs() < 1e-9);
+        }
+
+        assert!(matches!(
+            result.bode(3, 0),
+            Err(Error::IndexOutOfRange {
+                what: "node",
+                index: 3,
+                len: 3
+            })
+        ));
+        assert!(matches!(
+            result.bode(2, 7),
+            Err(Error::IndexOutOfRange {
+                what: "node",
+                index: 7,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_single_output_matches_full_solve() {
+        let stamper = RcLowPassStamper {
+            resistance: 1000.0,
+            capacitance: 1e-6,
+        };
+        let params = AcParams {
+            fstart: 1.0,
+            fstop: 1e6,
+            num_points: 20,
+            sweep_type: AcSweepType::Decade,
+        };
+
+        let full = solve_ac(&stamper, &params).unwrap();
+        let single = solve_ac_single_output(&stamper, 1, &params).unwrap();
+
+        assert_eq!(single.output_node, 1);
+        assert_eq!(single.points, full.voltage_at(1));
+        assert_eq!(single.magnitude_db(), full.magnitude_db(1));
+        assert_eq!(single.phase_deg(), full.phase_deg(1));
+
+        assert!(matches!(
+            solve_ac_single_output(&stamper, 2, &params),
+            Err(Error::IndexOutOfRange {
+                what: "output node",
+                index: 2,
+                len: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_iter_node_matches_voltage_at() {
+        let (r, c) = (1000.0, 1e-6);
+        let stamper = RcLowPassStamper {
+            resistance: r,
+            capacitance: c,
+        };
+        let params = AcParams {
+            fstart: 1.0,
+            fstop: 1e6,
+            num_points: 10,
+            sweep_type: AcSweepType::Decade,
+        };
+        let result = solve_ac(&stamper, &params).unwrap();
+
+        for node in 0..2 {
+            let lazy: Vec<_> = result.iter_node(node).collect();
+            assert_eq!(lazy, result.voltage_at(node));
+        }
+
+        // Group delay -dφ/dω in one pass; at low frequency it tends to RC.
+        let mut prev: Option<(f64, f64)> = None;
+        let mut delays = Vec::new();
+        for (f, v) in result.iter_node(1) {
+            let (omega, phase) = (2.0 * PI * f, v.arg());
+            if let Some((w0, p0)) = prev {
+                delays.push(-(phase - p0) / (omega - w0));
+            }
+            prev = Some((omega, phase));
+        }
+        assert_eq!(delays.len(), result.points.len() - 1);
+        assert!((delays[0] - r * c).abs() / (r * c) < 1e-3);
+    }
+
+    #[test]
+    fn test_response_at_interpolates_between_grid_points() {
+        let (r, c) = (1000.0, 1e-6);
+        let stamper = RcLowPassStamper {
+            resistance: r,
+            capacitance: c,
+        };
+        let params = AcParams {
+            fstart: 1.0,
+            fstop: 1e6,
+            num_points: 10,
+            sweep_type: AcSweepType::Decade,
+        };
+        let result = solve_ac(&stamper, &params).unwrap();
+
+        // 1.1 kHz lies between grid points, well into the rolloff.
+        let freq = 1e3 * 1.1;
+        assert!(!result.frequencies().contains(&freq));
+        let h = result.response_at(1, freq).unwrap();
+        let exact = Complex::new(1.0, 0.0) / Complex::new(1.0, 2.0 * PI * freq * r * c);
+        let mag_err_db = 20.0 * (h.norm() / exact.norm()).log10();
+        assert!(mag_err_db.abs() < 0.05, "magnitude error {mag_err_db} dB");
+        assert!((h.arg() - exact.arg()).abs().to_degrees() < 1.0);
+
+        // Grid points come back exactly.
+        let p = &result.points[7];
+        assert_eq!(result.response_at(1, p.frequency).unwrap(), p.solution[1]);
+
+        assert!(result.response_at(1, 0.5).is_err());
+        assert!(result.response_at(1, 2e6).is_err());
+        assert!(matches!(
+            result.response_at(2, 100.0),
+            Err(Error::IndexOutOfRange {
+                what: "node",
+                index: 2,
+                len: 2
+            })
+        ));
+    }
+
     #[test]
     fn test_rc_lowpass_phase() {
         let r = 1000.0;
@@ -0,0 +1,578 @@
+//! BiCGSTAB iterative solver for real and complex linear systems.
+//!
+//! BiCGSTAB handles nonsymmetric systems, such as MNA matrices with
+//! controlled sources, in a fixed amount of memory: a handful of work
+//! vectors instead of GMRES's `restart` Krylov basis vectors. Its
+//! convergence is less smooth than GMRES and it can break down, in which
+//! case it restarts from the current residual.
+//!
+//! [`solve_bicgstab_complex`] solves AC systems the same way, which keeps
+//! memory flat across large frequency sweeps.
+//!
+//! ```ignore
+//! use spicier_solver::{BicgstabConfig, solve_bicgstab_real};
+//!
+//! let result = solve_bicgstab_real(&real_operator, &real_rhs, &BicgstabConfig::default());
+//! ```
+
+use num_complex::Complex64 as C64;
+use spicier_simd::{SimdCapability, complex_conjugate_dot_product, real_dot_product};
+
+use crate::gmres::helpers::{complex_vec_norm, real_vec_norm};
+use crate::operator::{ComplexOperator, RealOperator};
+use crate::preconditioner::ComplexPreconditioner;
+
+/// Relative size below which `rho` or `(r̂, v)` count as a breakdown.
+const BREAKDOWN_TOL: f64 = 1e-14;
+
+/// BiCGSTAB solver configuration.
+#[derive(Debug, Clone)]
+pub struct BicgstabConfig {
+    /// Maximum number of iterations (each applies the operator twice).
+    pub max_iter: usize,
+    /// Convergence tolerance (relative residual).
+    pub tol: f64,
+}
+
+impl Default for BicgstabConfig {
+    fn default() -> Self {
+        Self {
+            max_iter: 500,
+            tol: 1e-8,
+        }
+    }
+}
+
+/// Result of a real-valued BiCGSTAB solve.
+#[derive(Debug, Clone)]
+pub struct BicgstabResult {
+    /// Solution vector.
+    pub x: Vec<f64>,
+    /// Number of iterations performed.
+    pub iterations: usize,
+    /// Final relative residual.
+    pub residual: f64,
+    /// Whether the solver converged.
+    pub converged: bool,
+    /// Number of times the iteration broke down and restarted.
+    pub restarts: usize,
+}
+
+/// Solve A*x = b using BiCGSTAB for real-valued systems.
+///
+/// Uses SIMD-accelerated dot products when available. When `rho = (r̂, r)`
+/// or `(r̂, A·p)` vanishes, or the stabilizing step stalls, the shadow
+/// residual `r̂` is reset to the current residual and the recurrence starts
+/// over. A breakdown straight after a restart cannot be cured that way and
+/// ends the solve unconverged.
+pub fn solve_bicgstab_real(
+    op: &dyn RealOperator,
+    b: &[f64],
+    config: &BicgstabConfig,
+) -> BicgstabResult {
+    let simd_cap = SimdCapability::detect();
+
+    let n = op.dim();
+    assert_eq!(b.len(), n, "RHS dimension mismatch");
+
+    let mut x = vec![0.0; n];
+    let b_norm = real_vec_norm(b, simd_cap);
+    if b_norm < 1e-30 {
+        return BicgstabResult {
+            x,
+            iterations: 0,
+            residual: 0.0,
+            converged: true,
+            restarts: 0,
+        };
+    }
+
+    // x0 = 0, so r0 = b
+    let mut r = b.to_vec();
+    let mut r_hat = r.clone();
+    let mut p = vec![0.0; n];
+    let mut v = vec![0.0; n];
+    let mut s = vec![0.0; n];
+    let mut t = vec![0.0; n];
+    let (mut rho, mut alpha, mut omega) = (1.0, 1.0, 1.0);
+    let mut restarts = 0;
+    let mut fresh = true;
+
+    let result = |x: Vec<f64>, iterations, residual, converged, restarts| BicgstabResult {
+        x,
+        iterations,
+        residual,
+        converged,
+        restarts,
+    };
+
+    for iter in 1..=config.max_iter {
+        let r_norm = real_vec_norm(&r, simd_cap);
+        let rho_new = real_dot_product(&r_hat, &r, simd_cap);
+        if rho_new.abs() < BREAKDOWN_TOL * real_vec_norm(&r_hat, simd_cap) * r_norm {
+            if fresh {
+                return result(x, iter - 1, r_norm / b_norm, false, restarts);
+            }
+            restart(&r, &mut r_hat, &mut p, &mut v);
+            (rho, alpha, omega) = (1.0, 1.0, 1.0);
+            restarts += 1;
+            fresh = true;
+            continue;
+        }
+
+        // p = r + beta * (p - omega * v)
+        let beta = (rho_new / rho) * (alpha / omega);
+        for i in 0..n {
+            p[i] = r[i] + beta * (p[i] - omega * v[i]);
+        }
+
+        op.apply(&p, &mut v);
+        let r_hat_v = real_dot_product(&r_hat, &v, simd_cap);
+        if r_hat_v.abs()
+            < BREAKDOWN_TOL * real_vec_norm(&r_hat, simd_cap) * real_vec_norm(&v, simd_cap)
+        {
+            if fresh {
+                return result(x, iter, r_norm / b_norm, false, restarts);
+            }
+            restart(&r, &mut r_hat, &mut p, &mut v);
+            (rho, alpha, omega) = (1.0, 1.0, 1.0);
+            restarts += 1;
+            fresh = true;
+            continue;
+        }
+        alpha = rho_new / r_hat_v;
+
+        // s = r - alpha * v
+        for i in 0..n {
+            s[i] = r[i] - alpha * v[i];
+        }
+        let s_norm = real_vec_norm(&s, simd_cap);
+        if s_norm / b_norm < config.tol {
+            for i in 0..n {
+                x[i] += alpha * p[i];
+            }
+            return result(x, iter, s_norm / b_norm, true, restarts);
+        }
+
+        op.apply(&s, &mut t);
+        let t_t = real_dot_product(&t, &t, simd_cap);
+        omega = if t_t > 0.0 {
+            real_dot_product(&t, &s, simd_cap) / t_t
+        } else {
+            0.0
+        };
+
+        // x += alpha * p + omega * s, r = s - omega * t
+        for i in 0..n {
+            x[i] += alpha * p[i] + omega * s[i];
+            r[i] = s[i] - omega * t[i];
+        }
+        let r_norm = real_vec_norm(&r, simd_cap);
+        if r_norm / b_norm < config.tol {
+            return result(x, iter, r_norm / b_norm, true, restarts);
+        }
+
+        rho = rho_new;
+        fresh = false;
+        if omega.abs() < BREAKDOWN_TOL {
+            // The stabilizing step made no progress; omega would divide
+            // the next beta.
+            restart(&r, &mut r_hat, &mut p, &mut v);
+            (rho, alpha, omega) = (1.0, 1.0, 1.0);
+            restarts += 1;
+            fresh = true;
+        }
+    }
+
+    let residual = real_vec_norm(&r, simd_cap) / b_norm;
+    result(x, config.max_iter, residual, false, restarts)
+}
+
+/// Result of a complex BiCGSTAB solve.
+#[derive(Debug, Clone)]
+pub struct ComplexBicgstabResult {
+    /// Solution vector.
+    pub x: Vec<C64>,
+    /// Number of iterations performed.
+    pub iterations: usize,
+    /// Final relative residual.
+    pub residual: f64,
+    /// Whether the solver converged.
+    pub converged: bool,
+    /// Number of times the iteration broke down and restarted.
+    pub restarts: usize,
+}
+
+/// Solve A*x = b using BiCGSTAB for complex-valued systems.
+///
+/// Inner products are conjugate dot products (`aᴴb`), SIMD-accelerated
+/// when available. Breakdowns are handled as in [`solve_bicgstab_real`].
+pub fn solve_bicgstab_complex(
+    op: &dyn ComplexOperator,
+    b: &[C64],
+    config: &BicgstabConfig,
+) -> ComplexBicgstabResult {
+    bicgstab_complex(op, None, b, config)
+}
+
+/// Solve A*x = b using right-preconditioned BiCGSTAB for complex systems.
+///
+/// The search directions are preconditioned with `precond` before each
+/// operator application, so the residual tested for convergence is that of
+/// the original system.
+pub fn solve_bicgstab_complex_preconditioned(
+    op: &dyn ComplexOperator,
+    precond: &dyn ComplexPreconditioner,
+    b: &[C64],
+    config: &BicgstabConfig,
+) -> ComplexBicgstabResult {
+    bicgstab_complex(op, Some(precond), b, config)
+}
+
+fn bicgstab_complex(
+    op: &dyn ComplexOperator,
+    precond: Option<&dyn ComplexPreconditioner>,
+    b: &[C64],
+    config: &BicgstabConfig,
+) -> ComplexBicgstabResult {
+    let simd_cap = SimdCapability::detect();
+    let zero = C64::new(0.0, 0.0);
+    let one = C64::new(1.0, 0.0);
+
+    let n = op.dim();
+    assert_eq!(b.len(), n, "RHS dimension mismatch");
+    if let Some(m) = precond {
+        assert_eq!(m.dim(), n, "Preconditioner dimension mismatch");
+    }
+
+    let mut x = vec![zero; n];
+    let b_norm = complex_vec_norm(b, simd_cap);
+    if b_norm < 1e-30 {
+        return ComplexBicgstabResult {
+            x,
+            iterations: 0,
+            residual: 0.0,
+            converged: true,
+            restarts: 0,
+        };
+    }
+
+    let apply_precond = |r: &[C64], z: &mut [C64]| match precond {
+        Some(m) => m.apply(r, z),
+        None => z.copy_from_slice(r),
+    };
+    let dot = |a: &[C64], b: &[C64]| complex_conjugate_dot_product(a, b, simd_cap);
+
+    // x0 = 0, so r0 = b
+    let mut r = b.to_vec();
+    let mut r_hat = r.clone();
+    let mut p = vec![zero; n];
+    let mut p_hat = vec![zero; n];
+    let mut v = vec![zero; n];
+    let mut s = vec![zero; n];
+    let mut s_hat = vec![zero; n];
+    let mut t = vec![zero; n];
+    let (mut rho, mut alpha, mut omega) = (one, one, one);
+    let mut restarts = 0;
+    let mut fresh = true;
+
+    let result = |x: Vec<C64>, iterations, residual, converged, restarts| ComplexBicgstabResult {
+        x,
+        iterations,
+        residual,
+        converged,
+        restarts,
+    };
+
+    for iter in 1..=config.max_iter {
+        let r_norm = complex_vec_norm(&r, simd_cap);
+        let rho_new = dot(&r_hat, &r);
+        if rho_new.norm() < BREAKDOWN_TOL * complex_vec_norm(&r_hat, simd_cap) * r_norm {
+            if fresh {
+                return result(x, iter - 1, r_norm / b_norm, false, restarts);
+            }
+            restart(&r, &mut r_hat, &mut p, &mut v);
+            (rho, alpha, omega) = (one, one, one);
+            restarts += 1;
+            fresh = true;
+            continue;
+        }
+
+        // p = r + beta * (p - omega * v)
+        let beta = (rho_new / rho) * (alpha / omega);
+        for i in 0..n {
+            p[i] = r[i] + beta * (p[i] - omega * v[i]);
+        }
+
+        apply_precond(&p, &mut p_hat);
+        op.apply(&p_hat, &mut v);
+        let r_hat_v = dot(&r_hat, &v);
+        if r_hat_v.norm()
+            < BREAKDOWN_TOL * complex_vec_norm(&r_hat, simd_cap) * complex_vec_norm(&v, simd_cap)
+        {
+            if fresh {
+                return result(x, iter, r_norm / b_norm, false, restarts);
+            }
+            restart(&r, &mut r_hat, &mut p, &mut v);
+            (rho, alpha, omega) = (one, one, one);
+            restarts += 1;
+            fresh = true;
+            continue;
+        }
+        alpha = rho_new / r_hat_v;
+
+        // s = r - alpha * v
+        for i in 0..n {
+            s[i] = r[i] - alpha * v[i];
+        }
+        let s_norm = complex_vec_norm(&s, simd_cap);
+        if s_norm / b_norm < config.tol {
+            for i in 0..n {
+                x[i] += alpha * p_hat[i];
+            }
+            return result(x, iter, s_norm / b_norm, true, restarts);
+        }
+
+        apply_precond(&s, &mut s_hat);
+        op.apply(&s_hat, &mut t);
+        let t_t = dot(&t, &t).re;
+        omega = if t_t > 0.0 { dot(&t, &s) / t_t } else { zero };
+
+        // x += alpha * p̂ + omega * ŝ, r = s - omega * t
+        for i in 0..n {
+            x[i] += alpha * p_hat[i] + omega * s_hat[i];
+            r[i] = s[i] - omega * t[i];
+        }
+        let r_norm = complex_vec_norm(&r, simd_cap);
+        if r_norm / b_norm < config.tol {
+            return result(x, iter, r_norm / b_norm, true, restarts);
+        }
+
+        rho = rho_new;
+        fresh = false;
+        if omega.norm() < BREAKDOWN_TOL {
+            restart(&r, &mut r_hat, &mut p, &mut v);
+            (rho, alpha, omega) = (one, one, one);
+            restarts += 1;
+            fresh = true;
+        }
+    }
+
+    let residual = complex_vec_norm(&r, simd_cap) / b_norm;
+    result(x, config.max_iter, residual, false, restarts)
+}
+
+/// Restart the recurrence from residual `r`: it becomes the new shadow
+/// residual and the search directions are cleared.
+fn restart<T: Copy + Default>(r: &[T], r_hat: &mut [T], p: &mut [T], v: &mut [T]) {
+    r_hat.copy_from_slice(r);
+    p.fill(T::default());
+    v.fill(T::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gmres::{GmresConfig, solve_gmres, solve_gmres_real};
+    use crate::operator::{DenseComplexOperator, DenseRealOperator};
+    use crate::preconditioner::ComplexJacobiPreconditioner;
+    use nalgebra::{DMatrix, DVector};
+
+    #[test]
+    fn bicgstab_diagonal_system() {
+        let diag: Vec<f64> = (1..=10).map(|i| i as f64).collect();
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_row_slice(&diag)));
+
+        let result = solve_bicgstab_real(&op, &diag, &BicgstabConfig::default());
+
+        assert!(result.converged);
+        assert!(result.residual < 1e-8);
+        for xi in &result.x {
+            assert!((xi - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn bicgstab_zero_rhs() {
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_row_slice(&[
+            1.0, 2.0, 3.0,
+        ])));
+        let result = solve_bicgstab_real(&op, &[0.0; 3], &BicgstabConfig::default());
+
+        assert!(result.converged);
+        assert_eq!(result.iterations, 0);
+        assert!(result.x.iter().all(|&xi| xi == 0.0));
+    }
+
+    #[test]
+    fn bicgstab_spd_system() {
+        // Same system as gmres_real_spd_system
+        let op = DenseRealOperator::new(DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 3.0]));
+        let b = [5.0, 4.0];
+
+        let result = solve_bicgstab_real(&op, &b, &BicgstabConfig::default());
+        let gmres = solve_gmres_real(&op, &b, &GmresConfig::default());
+
+        assert!(result.converged);
+        assert!((result.x[0] - 1.0).abs() < 1e-8);
+        assert!((result.x[1] - 1.0).abs() < 1e-8);
+        // Two matrix-vector products per BiCGSTAB iteration, one per GMRES
+        assert!(result.iterations <= gmres.iterations);
+    }
+
+    #[test]
+    fn bicgstab_tridiagonal() {
+        // Same system as gmres_real_tridiagonal
+        let op = DenseRealOperator::new(DMatrix::from_row_slice(
+            3,
+            3,
+            &[2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0],
+        ));
+        let b = [0.0, 0.0, 4.0];
+
+        let result = solve_bicgstab_real(&op, &b, &BicgstabConfig::default());
+        let gmres = solve_gmres_real(&op, &b, &GmresConfig::default());
+
+        assert!(result.converged);
+        assert!((result.x[0] - 1.0).abs() < 1e-8);
+        assert!((result.x[1] - 2.0).abs() < 1e-8);
+        assert!((result.x[2] - 3.0).abs() < 1e-8);
+        assert!(result.iterations <= gmres.iterations);
+    }
+
+    #[test]
+    fn bicgstab_nonsymmetric_chain() {
+        // Convection-diffusion chain, like a ladder with controlled sources:
+        // nonsymmetric but diagonally dominant.
+        let n = 40;
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = 3.0;
+            if i + 1 < n {
+                matrix[(i, i + 1)] = -0.5;
+                matrix[(i + 1, i)] = -1.5;
+            }
+        }
+        let op = DenseRealOperator::new(matrix);
+        let x_true: Vec<f64> = (0..n).map(|i| (0.3 * i as f64).sin()).collect();
+        let mut b = vec![0.0; n];
+        op.apply(&x_true, &mut b);
+
+        let config = BicgstabConfig {
+            max_iter: 200,
+            tol: 1e-10,
+        };
+        let result = solve_bicgstab_real(&op, &b, &config);
+
+        assert!(result.converged, "residual {}", result.residual);
+        for (xi, ti) in result.x.iter().zip(&x_true) {
+            assert!((xi - ti).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn bicgstab_unrecoverable_breakdown_stops() {
+        // A 90° rotation: (r̂, A·r) = 0 on the first step, and restarting
+        // from the same residual cannot help.
+        let op = DenseRealOperator::new(DMatrix::from_row_slice(2, 2, &[0.0, 1.0, -1.0, 0.0]));
+
+        let result = solve_bicgstab_real(&op, &[1.0, 0.0], &BicgstabConfig::default());
+
+        assert!(!result.converged);
+        assert!(result.iterations <= 1);
+        assert!(result.x.iter().all(|xi| xi.is_finite()));
+        assert!(result.residual.is_finite());
+    }
+
+    #[test]
+    fn bicgstab_complex_diagonal_system() {
+        // Same system as gmres_diagonal_system
+        let diag: Vec<C64> = (1..=10)
+            .map(|i| C64::new(i as f64, 0.5 * i as f64))
+            .collect();
+        let op = DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_row_slice(&diag)));
+        let b: Vec<C64> = diag.iter().map(|d| d * C64::new(1.0, 1.0)).collect();
+
+        let result = solve_bicgstab_complex(&op, &b, &BicgstabConfig::default());
+        let gmres = solve_gmres(&op, &b, &GmresConfig::default());
+
+        assert!(result.converged);
+        for (xi, xg) in result.x.iter().zip(&gmres.x) {
+            assert!((xi - C64::new(1.0, 1.0)).norm() < 1e-8);
+            assert!((xi - xg).norm() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn bicgstab_complex_hermitian() {
+        // Same system as gmres_complex_hermitian
+        let op = DenseComplexOperator::new(DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                C64::new(2.0, 0.0),
+                C64::new(1.0, -1.0),
+                C64::new(1.0, 1.0),
+                C64::new(3.0, 0.0),
+            ],
+        ));
+        let b = vec![
+            C64::new(2.0, 0.0) + C64::new(1.0, -1.0),
+            C64::new(1.0, 1.0) + C64::new(3.0, 0.0),
+        ];
+
+        let result = solve_bicgstab_complex(&op, &b, &BicgstabConfig::default());
+
+        assert!(result.converged);
+        assert!((result.x[0] - C64::new(1.0, 0.0)).norm() < 1e-8);
+        assert!((result.x[1] - C64::new(1.0, 0.0)).norm() < 1e-8);
+    }
+
+    #[test]
+    fn bicgstab_complex_preconditioned_rc_ladder() {
+        // RC ladder admittance matrix at one frequency: G + jωC on the
+        // diagonal with widely varying node capacitances.
+        let n = 30;
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            let wc = 10f64.powi((i % 4) as i32);
+            matrix[(i, i)] = C64::new(2.0, wc);
+            if i + 1 < n {
+                matrix[(i, i + 1)] = C64::new(-1.0, 0.0);
+                matrix[(i + 1, i)] = C64::new(-1.0, 0.0);
+            }
+        }
+        let triplets: Vec<(usize, usize, C64)> = (0..n).map(|i| (i, i, matrix[(i, i)])).collect();
+        let op = DenseComplexOperator::new(matrix);
+        let x_true: Vec<C64> = (0..n)
+            .map(|i| C64::from_polar(1.0, 0.2 * i as f64))
+            .collect();
+        let mut b = vec![C64::new(0.0, 0.0); n];
+        op.apply(&x_true, &mut b);
+
+        let config = BicgstabConfig {
+            max_iter: 200,
+            tol: 1e-13,
+        };
+        let plain = solve_bicgstab_complex(&op, &b, &config);
+        let precond = ComplexJacobiPreconditioner::from_triplets(n, &triplets);
+        let result = solve_bicgstab_complex_preconditioned(&op, &precond, &b, &config);
+
+        assert!(plain.converged && result.converged);
+        assert!(result.iterations <= plain.iterations);
+        for (xi, ti) in result.x.iter().zip(&x_true) {
+            assert!((xi - ti).norm() < 1e-8, "{xi} vs {ti}");
+        }
+    }
+
+    #[test]
+    fn bicgstab_complex_zero_rhs() {
+        let op =
+            DenseComplexOperator::new(DMatrix::from_diagonal_element(3, 3, C64::new(1.0, 1.0)));
+        let result =
+            solve_bicgstab_complex(&op, &[C64::new(0.0, 0.0); 3], &BicgstabConfig::default());
+
+        assert!(result.converged);
+        assert_eq!(result.iterations, 0);
+    }
+}
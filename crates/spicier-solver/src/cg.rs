@@ -0,0 +1,264 @@
+//! Conjugate Gradient solver for symmetric positive-definite real systems.
+//!
+//! Purely resistive MNA systems (conductances and grounded current sources)
+//! are symmetric positive definite. CG solves them with one operator
+//! application and a few work vectors per iteration, and converges faster
+//! than GMRES without restarts. [`solve_cg_real_preconditioned`] runs PCG
+//! with an SPD [`RealPreconditioner`] such as Jacobi.
+//!
+//! CG has no safeguard against matrices that are not SPD. When the search
+//! direction shows non-positive curvature (`pᵀAp ≤ 0`) the solve stops and
+//! reports [`CgResult::indefinite`] instead of diverging.
+//!
+//! ```ignore
+//! use spicier_solver::{CgConfig, solve_cg_real};
+//!
+//! let result = solve_cg_real(&real_operator, &real_rhs, &CgConfig::default());
+//! assert!(!result.indefinite);
+//! ```
+
+use spicier_simd::{SimdCapability, real_dot_product};
+
+use crate::gmres::helpers::real_vec_norm;
+use crate::operator::RealOperator;
+use crate::preconditioner::RealPreconditioner;
+
+/// CG solver configuration.
+#[derive(Debug, Clone)]
+pub struct CgConfig {
+    /// Maximum number of iterations.
+    pub max_iter: usize,
+    /// Convergence tolerance (relative residual).
+    pub tol: f64,
+}
+
+impl Default for CgConfig {
+    fn default() -> Self {
+        Self {
+            max_iter: 1000,
+            tol: 1e-8,
+        }
+    }
+}
+
+/// Result of a real-valued CG solve.
+#[derive(Debug, Clone)]
+pub struct CgResult {
+    /// Solution vector.
+    pub x: Vec<f64>,
+    /// Number of iterations performed.
+    pub iterations: usize,
+    /// Final relative residual.
+    pub residual: f64,
+    /// Whether the solver converged.
+    pub converged: bool,
+    /// Whether the operator (or preconditioner) was found not to be
+    /// positive definite. The solve stops at that point with `x` holding
+    /// the last iterate.
+    pub indefinite: bool,
+}
+
+/// Solve A*x = b using Conjugate Gradient for SPD real systems.
+///
+/// Uses SIMD-accelerated dot products when available.
+pub fn solve_cg_real(op: &dyn RealOperator, b: &[f64], config: &CgConfig) -> CgResult {
+    solve_cg(op, None, b, config)
+}
+
+/// Solve A*x = b using preconditioned Conjugate Gradient.
+///
+/// `precond` must be symmetric positive definite too; a non-positive
+/// `rᵀM⁻¹r` is reported as [`CgResult::indefinite`].
+pub fn solve_cg_real_preconditioned(
+    op: &dyn RealOperator,
+    precond: &dyn RealPreconditioner,
+    b: &[f64],
+    config: &CgConfig,
+) -> CgResult {
+    solve_cg(op, Some(precond), b, config)
+}
+
+fn solve_cg(
+    op: &dyn RealOperator,
+    precond: Option<&dyn RealPreconditioner>,
+    b: &[f64],
+    config: &CgConfig,
+) -> CgResult {
+    let simd_cap = SimdCapability::detect();
+
+    let n = op.dim();
+    assert_eq!(b.len(), n, "RHS dimension mismatch");
+    if let Some(m) = precond {
+        assert_eq!(m.dim(), n, "Preconditioner dimension mismatch");
+    }
+
+    let mut x = vec![0.0; n];
+    let b_norm = real_vec_norm(b, simd_cap);
+    if b_norm < 1e-30 {
+        return CgResult {
+            x,
+            iterations: 0,
+            residual: 0.0,
+            converged: true,
+            indefinite: false,
+        };
+    }
+
+    let apply_precond = |r: &[f64], z: &mut [f64]| match precond {
+        Some(m) => m.apply(r, z),
+        None => z.copy_from_slice(r),
+    };
+
+    // x0 = 0, so r0 = b
+    let mut r = b.to_vec();
+    let mut z = vec![0.0; n];
+    apply_precond(&r, &mut z);
+    let mut p = z.clone();
+    let mut ap = vec![0.0; n];
+    let mut rz = real_dot_product(&r, &z, simd_cap);
+
+    let result = |x: Vec<f64>, iterations, residual, converged, indefinite| CgResult {
+        x,
+        iterations,
+        residual,
+        converged,
+        indefinite,
+    };
+
+    if rz <= 0.0 {
+        return result(x, 0, 1.0, false, true);
+    }
+
+    for iter in 1..=config.max_iter {
+        op.apply(&p, &mut ap);
+        let p_ap = real_dot_product(&p, &ap, simd_cap);
+        if p_ap <= 0.0 {
+            let residual = real_vec_norm(&r, simd_cap) / b_norm;
+            return result(x, iter, residual, false, true);
+        }
+
+        let alpha = rz / p_ap;
+        for i in 0..n {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * ap[i];
+        }
+
+        let residual = real_vec_norm(&r, simd_cap) / b_norm;
+        if residual < config.tol {
+            return result(x, iter, residual, true, false);
+        }
+
+        apply_precond(&r, &mut z);
+        let rz_new = real_dot_product(&r, &z, simd_cap);
+        if rz_new <= 0.0 {
+            return result(x, iter, residual, false, true);
+        }
+
+        // p = z + beta * p
+        let beta = rz_new / rz;
+        for i in 0..n {
+            p[i] = z[i] + beta * p[i];
+        }
+        rz = rz_new;
+    }
+
+    let residual = real_vec_norm(&r, simd_cap) / b_norm;
+    result(x, config.max_iter, residual, false, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gmres::{GmresConfig, solve_gmres_real};
+    use crate::operator::DenseRealOperator;
+    use crate::preconditioner::JacobiPreconditioner;
+    use nalgebra::DMatrix;
+
+    /// 1-D Laplacian `tridiag(-1, 2, -1)`: a resistor chain grounded at
+    /// both ends.
+    fn laplacian(n: usize) -> DMatrix<f64> {
+        DMatrix::from_fn(n, n, |i, j| match i.abs_diff(j) {
+            0 => 2.0,
+            1 => -1.0,
+            _ => 0.0,
+        })
+    }
+
+    #[test]
+    fn cg_laplacian_matches_gmres() {
+        let n = 50;
+        let op = DenseRealOperator::new(laplacian(n));
+        let b: Vec<f64> = (0..n).map(|i| (0.2 * i as f64).cos()).collect();
+
+        let config = CgConfig {
+            max_iter: 200,
+            tol: 1e-10,
+        };
+        let result = solve_cg_real(&op, &b, &config);
+        let gmres = solve_gmres_real(
+            &op,
+            &b,
+            &GmresConfig {
+                max_iter: 500,
+                restart: n,
+                tol: 1e-10,
+                ..GmresConfig::default()
+            },
+        );
+
+        assert!(result.converged, "residual {}", result.residual);
+        assert!(!result.indefinite);
+        assert!(gmres.converged);
+        // Exact arithmetic finishes in at most n iterations
+        assert!(result.iterations <= n);
+        for (xc, xg) in result.x.iter().zip(&gmres.x) {
+            assert!((xc - xg).abs() < 1e-7, "{xc} vs {xg}");
+        }
+    }
+
+    #[test]
+    fn pcg_jacobi_on_scaled_laplacian() {
+        // Row/column scaling makes the diagonal uneven; Jacobi undoes it
+        let n = 40;
+        let scale: Vec<f64> = (0..n).map(|i| 1.0 + 10.0 * (i % 3) as f64).collect();
+        let laplacian = laplacian(n);
+        let matrix = DMatrix::from_fn(n, n, |i, j| laplacian[(i, j)] * scale[i] * scale[j]);
+        let triplets: Vec<(usize, usize, f64)> = (0..n).map(|i| (i, i, matrix[(i, i)])).collect();
+        let op = DenseRealOperator::new(matrix);
+        let b = vec![1.0; n];
+        let config = CgConfig {
+            max_iter: 500,
+            tol: 1e-10,
+        };
+
+        let plain = solve_cg_real(&op, &b, &config);
+        let precond = JacobiPreconditioner::from_triplets(n, &triplets);
+        let pcg = solve_cg_real_preconditioned(&op, &precond, &b, &config);
+
+        assert!(plain.converged && pcg.converged);
+        assert!(pcg.iterations < plain.iterations);
+        for (a, b) in pcg.x.iter().zip(&plain.x) {
+            assert!((a - b).abs() < 1e-6 * b.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn cg_flags_indefinite_operator() {
+        let op = DenseRealOperator::new(DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 0.0, -1.0]));
+
+        let result = solve_cg_real(&op, &[0.0, 1.0], &CgConfig::default());
+
+        assert!(result.indefinite);
+        assert!(!result.converged);
+        assert!(result.x.iter().all(|xi| xi.is_finite()));
+    }
+
+    #[test]
+    fn cg_zero_rhs() {
+        let op = DenseRealOperator::new(laplacian(3));
+        let result = solve_cg_real(&op, &[0.0; 3], &CgConfig::default());
+
+        assert!(result.converged);
+        assert_eq!(result.iterations, 0);
+    }
+}
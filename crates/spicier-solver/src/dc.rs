@@ -1,19 +1,22 @@
 //! DC operating point and DC sweep analysis.
 
 use nalgebra::DVector;
-use spicier_core::NodeId;
+use serde::{Deserialize, Serialize};
 use spicier_core::mna::MnaSystem;
+use spicier_core::{Netlist, NodeId};
 
 use crate::dispatch::DispatchConfig;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::gmres::GmresConfig;
 use crate::linear::{SPARSE_THRESHOLD, solve_dense, solve_sparse};
+use crate::netlist_stampers::NetlistNonlinearStamper;
+use crate::newton::{ConvergenceCriteria, NonlinearStamper, solve_newton_raphson};
 use crate::operator::RealOperator;
 use crate::preconditioner::{JacobiPreconditioner, RealPreconditioner};
 use crate::sparse_operator::SparseRealOperator;
 
 /// DC sweep parameters.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DcSweepParams {
     /// Name of the source to sweep.
     pub source_name: String,
@@ -37,6 +40,90 @@ pub trait DcSweepStamper {
     fn num_vsources(&self) -> usize;
 }
 
+/// Callback for stamping a nonlinear circuit with a swept source value.
+pub trait NonlinearSweepStamper {
+    /// Stamp the circuit linearized at `solution` with the swept source set to `value`.
+    fn stamp_at_with_sweep(&self, mna: &mut MnaSystem, solution: &DVector<f64>, value: f64);
+
+    /// Number of nodes (excluding ground).
+    fn num_nodes(&self) -> usize;
+
+    /// Number of branch current variables.
+    fn num_vsources(&self) -> usize;
+}
+
+/// Callback for stamping a nonlinear circuit with two swept source values.
+pub trait NonlinearNestedSweepStamper {
+    /// Stamp the circuit linearized at `solution` with both swept sources set.
+    fn stamp_at_with_sweeps(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        outer: f64,
+        inner: f64,
+    );
+
+    /// Number of nodes (excluding ground).
+    fn num_nodes(&self) -> usize;
+
+    /// Number of branch current variables.
+    fn num_vsources(&self) -> usize;
+}
+
+/// Result of a nested (two-variable) DC sweep.
+#[derive(Debug, Clone)]
+pub struct NestedDcSweepResult {
+    /// Name of the outer (slow) swept source.
+    pub outer_source: String,
+    /// Name of the inner (fast) swept source.
+    pub inner_source: String,
+    /// Outer sweep values.
+    pub outer_values: Vec<f64>,
+    /// Inner sweep values.
+    pub inner_values: Vec<f64>,
+    /// DC solutions indexed as `solutions[outer][inner]`.
+    pub solutions: Vec<Vec<DcSolution>>,
+}
+
+impl NestedDcSweepResult {
+    /// The inner sweep at one outer value as a single-variable sweep result.
+    pub fn curve(&self, outer_idx: usize) -> DcSweepResult {
+        DcSweepResult {
+            source_name: self.inner_source.clone(),
+            sweep_values: self.inner_values.clone(),
+            solutions: self.solutions[outer_idx].clone(),
+        }
+    }
+
+    /// Node voltage vs. inner value, one curve per outer value.
+    pub fn voltage_family(&self, node: NodeId) -> Vec<(f64, Vec<(f64, f64)>)> {
+        self.family(|sol| sol.voltage(node))
+    }
+
+    /// Branch current vs. inner value, one curve per outer value.
+    ///
+    /// For an outer Vgs and inner Vds sweep this is the transistor I-V family.
+    pub fn current_family(&self, index: usize) -> Vec<(f64, Vec<(f64, f64)>)> {
+        self.family(|sol| sol.current(index))
+    }
+
+    fn family(&self, f: impl Fn(&DcSolution) -> f64) -> Vec<(f64, Vec<(f64, f64)>)> {
+        self.outer_values
+            .iter()
+            .zip(&self.solutions)
+            .map(|(&outer, row)| {
+                let curve = self
+                    .inner_values
+                    .iter()
+                    .zip(row)
+                    .map(|(&inner, sol)| (inner, f(sol)))
+                    .collect();
+                (outer, curve)
+            })
+            .collect()
+    }
+}
+
 /// Result of a DC sweep analysis.
 #[derive(Debug, Clone)]
 pub struct DcSweepResult {
@@ -66,6 +153,47 @@ impl DcSweepResult {
             .map(|(&sv, sol)| (sv, sol.current(index)))
             .collect()
     }
+
+    /// Numerical derivative of a node voltage with respect to the swept value.
+    pub fn voltage_derivative(&self, node: NodeId) -> Vec<(f64, f64)> {
+        Self::derivative(&self.voltage_waveform(node))
+    }
+
+    /// Numerical derivative of a branch current with respect to the swept value.
+    ///
+    /// For a drain current swept against Vgs this is gm; against Vds it is gds.
+    pub fn current_derivative(&self, index: usize) -> Vec<(f64, f64)> {
+        Self::derivative(&self.current_waveform(index))
+    }
+
+    /// Differentiate a sampled curve `(x, y)` with respect to `x`.
+    ///
+    /// Uses central differences at interior points (exact for quadratics on
+    /// non-uniform grids) and one-sided differences at the ends. Curves with
+    /// fewer than two points have no derivative and yield an empty result.
+    pub fn derivative(curve: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let n = curve.len();
+        if n < 2 {
+            return Vec::new();
+        }
+
+        (0..n)
+            .map(|i| {
+                let (x, y) = curve[i];
+                let slope = if i == 0 {
+                    (curve[1].1 - y) / (curve[1].0 - x)
+                } else if i == n - 1 {
+                    (y - curve[i - 1].1) / (x - curve[i - 1].0)
+                } else {
+                    let (x0, y0) = curve[i - 1];
+                    let (x2, y2) = curve[i + 1];
+                    let (h0, h1) = (x - x0, x2 - x);
+                    (h0 * h0 * (y2 - y) + h1 * h1 * (y - y0)) / (h0 * h1 * (h0 + h1))
+                };
+                (x, slope)
+            })
+            .collect()
+    }
 }
 
 /// Result of a DC operating point analysis.
@@ -107,6 +235,62 @@ impl DcSolution {
             0.0
         }
     }
+
+    /// Power delivered by the independent sources versus power absorbed by
+    /// every other device in `netlist`, at this operating point.
+    ///
+    /// Each device's terminal currents are recovered from its own stamp
+    /// (linearized here for nonlinear devices) and multiplied by the node
+    /// voltages. By Tellegen's theorem the two totals match for an exact
+    /// solution, so a residual well above the solver tolerance points at a
+    /// device stamp or a solver bug.
+    pub fn power_balance(&self, netlist: &Netlist) -> PowerBalance {
+        let num_nodes = netlist.num_nodes();
+        let solution = DVector::from_iterator(
+            self.node_voltages.len() + self.branch_currents.len(),
+            self.node_voltages
+                .iter()
+                .chain(self.branch_currents.iter())
+                .copied(),
+        );
+
+        let mut balance = PowerBalance::default();
+        let mut mna = MnaSystem::new(num_nodes, netlist.num_current_vars());
+        for device in netlist.devices() {
+            mna.clear();
+            device.stamp_nonlinear(&mut mna, &solution);
+
+            // Current leaving each node into the device is (A·x − b) on
+            // the node rows.
+            let mut absorbed: f64 = (0..num_nodes).map(|n| -solution[n] * mna.rhs[n]).sum();
+            for &(row, col, value) in &mna.triplets {
+                if row < num_nodes {
+                    absorbed += solution[row] * value * solution[col];
+                }
+            }
+
+            if device.is_source() {
+                balance.source_power -= absorbed;
+            } else {
+                balance.dissipated_power += absorbed;
+            }
+        }
+        balance.residual = balance.source_power - balance.dissipated_power;
+        balance
+    }
+}
+
+/// DC power bookkeeping from [`DcSolution::power_balance`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PowerBalance {
+    /// Net power delivered by independent voltage and current sources (W).
+    pub source_power: f64,
+    /// Net power absorbed by all other devices (W). Controlled sources
+    /// that deliver power count negatively.
+    pub dissipated_power: f64,
+    /// `source_power - dissipated_power` (W); zero up to round-off and
+    /// solver tolerance.
+    pub residual: f64,
 }
 
 /// Run a DC sweep analysis.
@@ -120,17 +304,7 @@ pub fn solve_dc_sweep(
     let num_nodes = stamper.num_nodes();
     let num_vsources = stamper.num_vsources();
 
-    // Generate sweep values
-    let mut sweep_values = Vec::new();
-    let direction = if params.step > 0.0 { 1.0 } else { -1.0 };
-    let mut value = params.start;
-    loop {
-        sweep_values.push(value);
-        value += params.step;
-        if direction * value > direction * params.stop * (1.0 + 1e-10) {
-            break;
-        }
-    }
+    let sweep_values = sweep_values(params);
 
     let mut solutions = Vec::with_capacity(sweep_values.len());
 
@@ -148,6 +322,205 @@ pub fn solve_dc_sweep(
     })
 }
 
+/// Run a DC sweep on a circuit with nonlinear devices.
+///
+/// Each point is solved with Newton-Raphson, warm-started from the previous
+/// point's solution, so small steps converge in a few iterations.
+pub fn solve_dc_sweep_nonlinear(
+    stamper: &dyn NonlinearSweepStamper,
+    params: &DcSweepParams,
+    criteria: &ConvergenceCriteria,
+) -> Result<DcSweepResult> {
+    /// Adapts a sweep stamper to a fixed swept value.
+    struct AtValue<'a> {
+        stamper: &'a dyn NonlinearSweepStamper,
+        value: f64,
+    }
+
+    impl NonlinearStamper for AtValue<'_> {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.stamper.stamp_at_with_sweep(mna, solution, self.value);
+        }
+    }
+
+    let num_nodes = stamper.num_nodes();
+    let num_vsources = stamper.num_vsources();
+    let sweep_values = sweep_values(params);
+
+    let mut solutions = Vec::with_capacity(sweep_values.len());
+    let mut guess: Option<DVector<f64>> = None;
+
+    for &sv in &sweep_values {
+        let at_value = AtValue { stamper, value: sv };
+        let nr =
+            solve_newton_raphson(num_nodes, num_vsources, &at_value, criteria, guess.as_ref())?;
+        if !nr.converged {
+            return Err(Error::ConvergenceFailed {
+                iterations: nr.iterations,
+            });
+        }
+
+        solutions.push(DcSolution {
+            node_voltages: nr.solution.rows(0, num_nodes).into_owned(),
+            branch_currents: nr.solution.rows(num_nodes, num_vsources).into_owned(),
+            num_nodes,
+        });
+        guess = Some(nr.solution);
+    }
+
+    Ok(DcSweepResult {
+        source_name: params.source_name.clone(),
+        sweep_values,
+        solutions,
+    })
+}
+
+/// Run a nested (two-variable) DC sweep on a circuit with nonlinear devices.
+///
+/// For each outer value the inner source is swept completely. Each inner point
+/// is warm-started from the previous point, and the first point of each curve
+/// from the first point of the previous curve.
+pub fn solve_dc_nested_sweep_nonlinear(
+    stamper: &dyn NonlinearNestedSweepStamper,
+    outer: &DcSweepParams,
+    inner: &DcSweepParams,
+    criteria: &ConvergenceCriteria,
+) -> Result<NestedDcSweepResult> {
+    /// Adapts a nested sweep stamper to fixed swept values.
+    struct AtValues<'a> {
+        stamper: &'a dyn NonlinearNestedSweepStamper,
+        outer: f64,
+        inner: f64,
+    }
+
+    impl NonlinearStamper for AtValues<'_> {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.stamper
+                .stamp_at_with_sweeps(mna, solution, self.outer, self.inner);
+        }
+    }
+
+    let num_nodes = stamper.num_nodes();
+    let num_vsources = stamper.num_vsources();
+    let outer_values = sweep_values(outer);
+    let inner_values = sweep_values(inner);
+
+    let mut solutions = Vec::with_capacity(outer_values.len());
+    let mut curve_start: Option<DVector<f64>> = None;
+
+    for &ov in &outer_values {
+        let mut row = Vec::with_capacity(inner_values.len());
+        let mut guess = curve_start.clone();
+
+        for (j, &iv) in inner_values.iter().enumerate() {
+            let at_values = AtValues {
+                stamper,
+                outer: ov,
+                inner: iv,
+            };
+            let nr = solve_newton_raphson(
+                num_nodes,
+                num_vsources,
+                &at_values,
+                criteria,
+                guess.as_ref(),
+            )?;
+            if !nr.converged {
+                return Err(Error::ConvergenceFailed {
+                    iterations: nr.iterations,
+                });
+            }
+
+            row.push(DcSolution {
+                node_voltages: nr.solution.rows(0, num_nodes).into_owned(),
+                branch_currents: nr.solution.rows(num_nodes, num_vsources).into_owned(),
+                num_nodes,
+            });
+            if j == 0 {
+                curve_start = Some(nr.solution.clone());
+            }
+            guess = Some(nr.solution);
+        }
+
+        solutions.push(row);
+    }
+
+    Ok(NestedDcSweepResult {
+        outer_source: outer.source_name.clone(),
+        inner_source: inner.source_name.clone(),
+        outer_values,
+        inner_values,
+        solutions,
+    })
+}
+
+/// Run a DC temperature sweep.
+///
+/// `params` gives the temperatures in °C, as in `.DC TEMP`. At each point
+/// every device is moved to that temperature with
+/// [`Netlist::set_temperature_all`], re-deriving its temperature-dependent
+/// parameters, and the operating point is re-solved. Nonlinear circuits are
+/// warm-started from the previous point.
+///
+/// The devices are left at the last temperature of the sweep.
+pub fn solve_dc_temp_sweep(
+    netlist: &mut Netlist,
+    params: &DcSweepParams,
+    criteria: &ConvergenceCriteria,
+) -> Result<DcSweepResult> {
+    let num_nodes = netlist.num_nodes();
+    let num_vsources = netlist.num_current_vars();
+    let sweep_values = sweep_values(params);
+
+    let mut solutions = Vec::with_capacity(sweep_values.len());
+    let mut guess: Option<DVector<f64>> = None;
+
+    for &celsius in &sweep_values {
+        netlist.set_temperature_all(celsius + 273.15);
+
+        if !netlist.has_nonlinear_devices() {
+            solutions.push(solve_dc(&netlist.assemble_mna())?);
+            continue;
+        }
+
+        let stamper = NetlistNonlinearStamper { netlist };
+        let nr = solve_newton_raphson(num_nodes, num_vsources, &stamper, criteria, guess.as_ref())?;
+        if !nr.converged {
+            return Err(Error::ConvergenceFailed {
+                iterations: nr.iterations,
+            });
+        }
+
+        solutions.push(DcSolution {
+            node_voltages: nr.solution.rows(0, num_nodes).into_owned(),
+            branch_currents: nr.solution.rows(num_nodes, num_vsources).into_owned(),
+            num_nodes,
+        });
+        guess = Some(nr.solution);
+    }
+
+    Ok(DcSweepResult {
+        source_name: params.source_name.clone(),
+        sweep_values,
+        solutions,
+    })
+}
+
+/// Generate the swept values from `start` to `stop` (inclusive) in `step` increments.
+pub(crate) fn sweep_values(params: &DcSweepParams) -> Vec<f64> {
+    let mut values = Vec::new();
+    let direction = if params.step > 0.0 { 1.0 } else { -1.0 };
+    let mut value = params.start;
+    loop {
+        values.push(value);
+        value += params.step;
+        if direction * value > direction * params.stop * (1.0 + 1e-10) {
+            break;
+        }
+    }
+    values
+}
+
 /// Solve the DC operating point for a pre-assembled MNA system.
 ///
 /// Automatically selects sparse or dense solver based on system size.
@@ -252,17 +625,7 @@ pub fn solve_dc_sweep_dispatched(
     let num_nodes = stamper.num_nodes();
     let num_vsources = stamper.num_vsources();
 
-    // Generate sweep values
-    let mut sweep_values = Vec::new();
-    let direction = if params.step > 0.0 { 1.0 } else { -1.0 };
-    let mut value = params.start;
-    loop {
-        sweep_values.push(value);
-        value += params.step;
-        if direction * value > direction * params.stop * (1.0 + 1e-10) {
-            break;
-        }
-    }
+    let sweep_values = sweep_values(params);
 
     let mut solutions = Vec::with_capacity(sweep_values.len());
 
@@ -299,6 +662,72 @@ mod tests {
         assert!((solution.voltage(NodeId::new(2)) - 5.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_power_balance_voltage_divider() {
+        use spicier_devices::passive::Resistor;
+        use spicier_devices::sources::VoltageSource;
+
+        // V1 = 10V across R1 = 1k and R2 = 4k in series: I = 2mA.
+        let mut netlist = Netlist::new();
+        netlist.add_device(VoltageSource::new(
+            "V1",
+            NodeId::new(1),
+            NodeId::GROUND,
+            10.0,
+            0,
+        ));
+        netlist.add_device(Resistor::new("R1", NodeId::new(1), NodeId::new(2), 1000.0));
+        netlist.add_device(Resistor::new("R2", NodeId::new(2), NodeId::GROUND, 4000.0));
+        netlist.register_node(NodeId::new(1));
+        netlist.register_node(NodeId::new(2));
+
+        let solution = solve_dc(&netlist.assemble_mna()).unwrap();
+        let balance = solution.power_balance(&netlist);
+
+        let p_r1 = 2e-3 * 2e-3 * 1000.0;
+        let p_r2 = 2e-3 * 2e-3 * 4000.0;
+        assert!((balance.source_power - 20e-3).abs() < 1e-12);
+        assert!((balance.dissipated_power - (p_r1 + p_r2)).abs() < 1e-12);
+        assert!(balance.residual.abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_temp_sweep_diode_forward_drop() {
+        use spicier_devices::diode::Diode;
+        use spicier_devices::sources::CurrentSource;
+
+        // 1 mA forced through a diode to ground
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(1));
+        netlist.add_device(CurrentSource::new(
+            "I1",
+            NodeId::GROUND,
+            NodeId::new(1),
+            1e-3,
+        ));
+        netlist.add_device(Diode::new("D1", NodeId::new(1), NodeId::GROUND));
+
+        let params = DcSweepParams {
+            source_name: "TEMP".to_string(),
+            start: 0.0,
+            stop: 100.0,
+            step: 25.0,
+        };
+        let result =
+            solve_dc_temp_sweep(&mut netlist, &params, &ConvergenceCriteria::default()).unwrap();
+
+        let vd = result.voltage_waveform(NodeId::new(1));
+        assert_eq!(vd.len(), 5);
+        assert!(vd.windows(2).all(|w| w[1].1 < w[0].1));
+
+        let slope = (vd[4].1 - vd[0].1) / 100.0;
+        assert!(
+            (-2.5e-3..-1.5e-3).contains(&slope),
+            "dVd/dT = {:.3} mV/°C",
+            slope * 1e3
+        );
+    }
+
     #[test]
     fn test_voltage_divider() {
         // Simple voltage divider: V1 = 10V, R1 = R2 = 1k
@@ -418,6 +847,24 @@ mod tests {
         assert!((waveform[5].1 - 2.5).abs() < 1e-10); // V(2)=5/2=2.5
     }
 
+    #[test]
+    fn test_sweep_derivative() {
+        // y = x^2 on a non-uniform grid: central differences are exact
+        let xs = [0.0, 0.5, 1.5, 2.0, 3.0];
+        let curve: Vec<(f64, f64)> = xs.iter().map(|&x| (x, x * x)).collect();
+        let d = DcSweepResult::derivative(&curve);
+
+        assert_eq!(d.len(), curve.len());
+        for &(x, dy) in &d[1..d.len() - 1] {
+            assert!((dy - 2.0 * x).abs() < 1e-12, "d/dx at {} = {}", x, dy);
+        }
+        // One-sided at the ends
+        assert!((d[0].1 - 0.5).abs() < 1e-12);
+        assert!((d[4].1 - 5.0).abs() < 1e-12);
+
+        assert!(DcSweepResult::derivative(&curve[..1]).is_empty());
+    }
+
     #[test]
     fn test_ground_voltage() {
         let solution = DcSolution {
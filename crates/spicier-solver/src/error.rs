@@ -14,8 +14,21 @@ pub enum Error {
     #[error("invalid matrix dimensions: expected {expected}, got {actual}")]
     DimensionMismatch { expected: usize, actual: usize },
 
+    #[error("{what} index {index} out of range ({len} available)")]
+    IndexOutOfRange {
+        what: &'static str,
+        index: usize,
+        len: usize,
+    },
+
     #[error("solver error: {0}")]
     SolverError(String),
+
+    #[error(transparent)]
+    Parse(#[from] spicier_parser::Error),
+
+    #[error(transparent)]
+    Circuit(#[from] spicier_core::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -3,10 +3,13 @@
 use crate::operator::ComplexOperator;
 use crate::preconditioner::ComplexPreconditioner;
 use num_complex::Complex64 as C64;
-use spicier_simd::{SimdCapability, complex_conjugate_dot_product};
+use spicier_simd::SimdCapability;
 
 use super::GmresConfig;
-use super::helpers::{complex_givens_rotation, complex_vec_norm};
+use super::helpers::{
+    RestartSchedule, check_dims, complex_givens_rotation, complex_orthogonalize, complex_vec_norm,
+};
+use crate::error::Result;
 
 /// Result of a complex GMRES solve.
 #[derive(Debug, Clone)]
@@ -19,6 +22,12 @@ pub struct GmresResult {
     pub residual: f64,
     /// Whether the solver converged.
     pub converged: bool,
+    /// Restart length of the last cycle; with
+    /// [`GmresConfig::adaptive_restart`] this is where the schedule ended.
+    pub restart: usize,
+    /// Relative residual after each Arnoldi step, across all restart
+    /// cycles. Empty unless [`GmresConfig::track_history`] is set.
+    pub residual_history: Vec<f64>,
 }
 
 /// Solve A*x = b using restarted GMRES.
@@ -26,6 +35,35 @@ pub struct GmresResult {
 /// Uses SIMD-accelerated conjugate dot products for Gram-Schmidt
 /// orthogonalization when available (AVX-512, AVX2 on x86/x86_64).
 pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) -> GmresResult {
+    restarted(op, b, None, config)
+}
+
+/// Solve A*x = b using restarted GMRES, starting from `x0`.
+///
+/// With `x0 = None` this is [`solve_gmres`]. In a frequency sweep the
+/// previous point's solution is usually a good guess. Returns
+/// [`Error::DimensionMismatch`](crate::Error::DimensionMismatch) if `b` or
+/// `x0` does not match the operator size.
+pub fn solve_gmres_with_guess(
+    op: &dyn ComplexOperator,
+    b: &[C64],
+    x0: Option<&[C64]>,
+    config: &GmresConfig,
+) -> Result<GmresResult> {
+    check_dims(
+        op.dim(),
+        std::iter::once(b.len()).chain(x0.map(<[C64]>::len)),
+    )?;
+    Ok(restarted(op, b, x0, config))
+}
+
+/// Restarted GMRES without preconditioning.
+fn restarted(
+    op: &dyn ComplexOperator,
+    b: &[C64],
+    x0: Option<&[C64]>,
+    config: &GmresConfig,
+) -> GmresResult {
     let simd_cap = SimdCapability::detect();
 
     let n = op.dim();
@@ -38,12 +76,19 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
             iterations: 0,
             residual: 0.0,
             converged: true,
+            restart: 0,
+            residual_history: Vec::new(),
         };
     }
 
-    let mut x = vec![C64::new(0.0, 0.0); n];
+    let mut x = match x0 {
+        Some(x0) => x0.to_vec(),
+        None => vec![C64::new(0.0, 0.0); n],
+    };
     let mut total_iter = 0;
 
+    let mut schedule = RestartSchedule::new(config);
+    let mut residual_history = Vec::new();
     for _restart_cycle in 0..config.max_iter {
         // Compute residual r = b - A*x
         let mut ax = vec![C64::new(0.0, 0.0); n];
@@ -61,11 +106,13 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
                 iterations: total_iter,
                 residual: r_norm / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
         // Arnoldi process with modified Gram-Schmidt
-        let m = config.restart.min(n);
+        let m = schedule.next_cycle(n);
         let mut v: Vec<Vec<C64>> = Vec::with_capacity(m + 1);
         let mut h = vec![vec![C64::new(0.0, 0.0); m + 1]; m];
 
@@ -96,27 +143,25 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
             op.apply(&v[k], &mut w);
 
             // Modified Gram-Schmidt
-            for j in 0..=k {
-                let hij = complex_conjugate_dot_product(&v[j], &w, simd_cap);
-                h[k][j] = hij;
-                for i in 0..n {
-                    w[i] -= hij * v[j][i];
-                }
-            }
-
-            let w_norm = complex_vec_norm(&w, simd_cap);
+            let w_norm = complex_orthogonalize(
+                &v[..=k],
+                &mut w,
+                &mut h[k],
+                config.reorthogonalize,
+                simd_cap,
+            );
             h[k][k + 1] = C64::new(w_norm, 0.0);
 
-            if w_norm < 1e-30 {
-                // Lucky breakdown
-                k += 1;
-                break;
+            // Lucky breakdown: the Krylov space is invariant and the
+            // solution is exact. The column is still rotated below so the
+            // back-substitution sees a triangular H.
+            let breakdown = w_norm < 1e-30;
+            if !breakdown {
+                let inv_w = 1.0 / w_norm;
+                let vk1: Vec<C64> = w.iter().map(|&wi| wi * inv_w).collect();
+                v.push(vk1);
             }
 
-            let inv_w = 1.0 / w_norm;
-            let vk1: Vec<C64> = w.iter().map(|&wi| wi * inv_w).collect();
-            v.push(vk1);
-
             // Apply previous Givens rotations to h[k]
             for j in 0..k {
                 let temp = cs[j].conj() * h[k][j] + sn[j].conj() * h[k][j + 1];
@@ -138,7 +183,10 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
             g[k] = temp_g;
 
             let rel_res = g[k + 1].norm() / b_norm;
-            if rel_res < config.tol {
+            if config.track_history {
+                residual_history.push(rel_res);
+            }
+            if breakdown || rel_res < config.tol {
                 k += 1;
                 break;
             }
@@ -181,6 +229,8 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
@@ -190,8 +240,12 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: false,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
+
+        schedule.record(r_norm, final_res);
     }
 
     // Should not reach here
@@ -200,6 +254,8 @@ pub fn solve_gmres(op: &dyn ComplexOperator, b: &[C64], config: &GmresConfig) ->
         iterations: total_iter,
         residual: f64::NAN,
         converged: false,
+        restart: schedule.last_used(),
+        residual_history,
     }
 }
 
@@ -211,6 +267,38 @@ pub fn solve_gmres_preconditioned(
     precond: &dyn ComplexPreconditioner,
     b: &[C64],
     config: &GmresConfig,
+) -> GmresResult {
+    right_preconditioned(op, precond, b, None, config)
+}
+
+/// Solve A*x = b using right-preconditioned GMRES, starting from `x0`.
+///
+/// With `x0 = None` this is [`solve_gmres_preconditioned`]. Returns
+/// [`Error::DimensionMismatch`](crate::Error::DimensionMismatch) if `b`,
+/// `x0` or the preconditioner does not match the operator size.
+pub fn solve_gmres_preconditioned_with_guess(
+    op: &dyn ComplexOperator,
+    precond: &dyn ComplexPreconditioner,
+    b: &[C64],
+    x0: Option<&[C64]>,
+    config: &GmresConfig,
+) -> Result<GmresResult> {
+    check_dims(
+        op.dim(),
+        [b.len(), precond.dim()]
+            .into_iter()
+            .chain(x0.map(<[C64]>::len)),
+    )?;
+    Ok(right_preconditioned(op, precond, b, x0, config))
+}
+
+/// Right-preconditioned restarted GMRES.
+fn right_preconditioned(
+    op: &dyn ComplexOperator,
+    precond: &dyn ComplexPreconditioner,
+    b: &[C64],
+    x0: Option<&[C64]>,
+    config: &GmresConfig,
 ) -> GmresResult {
     let simd_cap = SimdCapability::detect();
 
@@ -225,13 +313,20 @@ pub fn solve_gmres_preconditioned(
             iterations: 0,
             residual: 0.0,
             converged: true,
+            restart: 0,
+            residual_history: Vec::new(),
         };
     }
 
-    let mut x = vec![C64::new(0.0, 0.0); n];
+    let mut x = match x0 {
+        Some(x0) => x0.to_vec(),
+        None => vec![C64::new(0.0, 0.0); n],
+    };
     let mut total_iter = 0;
     let mut precond_work = vec![C64::new(0.0, 0.0); n];
 
+    let mut schedule = RestartSchedule::new(config);
+    let mut residual_history = Vec::new();
     for _restart_cycle in 0..config.max_iter {
         let mut ax = vec![C64::new(0.0, 0.0); n];
         op.apply(&x, &mut ax);
@@ -248,10 +343,12 @@ pub fn solve_gmres_preconditioned(
                 iterations: total_iter,
                 residual: r_norm / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
-        let m = config.restart.min(n);
+        let m = schedule.next_cycle(n);
         let mut v: Vec<Vec<C64>> = Vec::with_capacity(m + 1);
         let mut z: Vec<Vec<C64>> = Vec::with_capacity(m);
         let mut h = vec![vec![C64::new(0.0, 0.0); m + 1]; m];
@@ -281,26 +378,25 @@ pub fn solve_gmres_preconditioned(
             let mut w = vec![C64::new(0.0, 0.0); n];
             op.apply(&z[k], &mut w);
 
-            for j in 0..=k {
-                let hij = complex_conjugate_dot_product(&v[j], &w, simd_cap);
-                h[k][j] = hij;
-                for i in 0..n {
-                    w[i] -= hij * v[j][i];
-                }
-            }
-
-            let w_norm = complex_vec_norm(&w, simd_cap);
+            let w_norm = complex_orthogonalize(
+                &v[..=k],
+                &mut w,
+                &mut h[k],
+                config.reorthogonalize,
+                simd_cap,
+            );
             h[k][k + 1] = C64::new(w_norm, 0.0);
 
-            if w_norm < 1e-30 {
-                k += 1;
-                break;
+            // Lucky breakdown: the Krylov space is invariant and the
+            // solution is exact. The column is still rotated below so the
+            // back-substitution sees a triangular H.
+            let breakdown = w_norm < 1e-30;
+            if !breakdown {
+                let inv_w = 1.0 / w_norm;
+                let vk1: Vec<C64> = w.iter().map(|&wi| wi * inv_w).collect();
+                v.push(vk1);
             }
 
-            let inv_w = 1.0 / w_norm;
-            let vk1: Vec<C64> = w.iter().map(|&wi| wi * inv_w).collect();
-            v.push(vk1);
-
             for j in 0..k {
                 let temp = cs[j].conj() * h[k][j] + sn[j].conj() * h[k][j + 1];
                 h[k][j + 1] = -sn[j] * h[k][j] + cs[j] * h[k][j + 1];
@@ -320,7 +416,10 @@ pub fn solve_gmres_preconditioned(
             g[k] = temp_g;
 
             let rel_res = g[k + 1].norm() / b_norm;
-            if rel_res < config.tol {
+            if config.track_history {
+                residual_history.push(rel_res);
+            }
+            if breakdown || rel_res < config.tol {
                 k += 1;
                 break;
             }
@@ -360,6 +459,8 @@ pub fn solve_gmres_preconditioned(
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
@@ -369,8 +470,12 @@ pub fn solve_gmres_preconditioned(
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: false,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
+
+        schedule.record(r_norm, final_res);
     }
 
     GmresResult {
@@ -378,59 +483,17 @@ pub fn solve_gmres_preconditioned(
         iterations: total_iter,
         residual: f64::NAN,
         converged: false,
+        restart: schedule.last_used(),
+        residual_history,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::operator::DenseComplexOperator;
     use crate::preconditioner::IdentityPreconditioner;
-
-    /// Simple diagonal operator for testing.
-    struct DiagOp {
-        diag: Vec<C64>,
-    }
-
-    impl ComplexOperator for DiagOp {
-        fn dim(&self) -> usize {
-            self.diag.len()
-        }
-
-        fn apply(&self, x: &[C64], y: &mut [C64]) {
-            for i in 0..self.diag.len() {
-                y[i] = self.diag[i] * x[i];
-            }
-        }
-    }
-
-    /// Dense matrix operator for testing.
-    struct DenseOp {
-        matrix: Vec<Vec<C64>>,
-        n: usize,
-    }
-
-    impl DenseOp {
-        fn new(matrix: Vec<Vec<C64>>) -> Self {
-            let n = matrix.len();
-            Self { matrix, n }
-        }
-    }
-
-    impl ComplexOperator for DenseOp {
-        fn dim(&self) -> usize {
-            self.n
-        }
-
-        #[allow(clippy::needless_range_loop)]
-        fn apply(&self, x: &[C64], y: &mut [C64]) {
-            for i in 0..self.n {
-                y[i] = C64::new(0.0, 0.0);
-                for j in 0..self.n {
-                    y[i] += self.matrix[i][j] * x[j];
-                }
-            }
-        }
-    }
+    use nalgebra::{DMatrix, DVector};
 
     #[test]
     fn gmres_diagonal_system() {
@@ -438,7 +501,8 @@ mod tests {
         let diag: Vec<C64> = (1..=n)
             .map(|i| C64::new(i as f64, 0.5 * i as f64))
             .collect();
-        let op = DiagOp { diag: diag.clone() };
+        let op =
+            DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
 
         let b: Vec<C64> = diag.iter().map(|d| d * C64::new(1.0, 1.0)).collect();
 
@@ -453,11 +517,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gmres_with_guess_rejects_mismatched_dimensions() {
+        let n = 4;
+        let op = DenseComplexOperator::new(DMatrix::identity(n, n));
+        let config = GmresConfig::default();
+        let b = vec![C64::new(1.0, 0.0); n];
+        let short = vec![C64::new(0.0, 0.0); n - 1];
+
+        let err = solve_gmres_with_guess(&op, &b, Some(&short), &config).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::DimensionMismatch {
+                expected: 4,
+                actual: 3
+            }
+        ));
+        let precond = IdentityPreconditioner::new(n);
+        let err = solve_gmres_preconditioned_with_guess(&op, &precond, &b, Some(&short), &config)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::DimensionMismatch { actual: 3, .. }
+        ));
+    }
+
     #[test]
     fn gmres_zero_rhs() {
         let n = 5;
         let diag: Vec<C64> = (1..=n).map(|i| C64::new(i as f64, 0.0)).collect();
-        let op = DiagOp { diag };
+        let op = DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag)));
 
         let b = vec![C64::new(0.0, 0.0); n];
         let config = GmresConfig::default();
@@ -474,7 +563,7 @@ mod tests {
     fn gmres_identity_operator() {
         let n = 5;
         let diag = vec![C64::new(1.0, 0.0); n];
-        let op = DiagOp { diag };
+        let op = DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag)));
 
         let b: Vec<C64> = (1..=n)
             .map(|i| C64::new(i as f64, -0.5 * i as f64))
@@ -490,11 +579,17 @@ mod tests {
 
     #[test]
     fn gmres_real_symmetric_positive_definite() {
-        let matrix = vec![
-            vec![C64::new(4.0, 0.0), C64::new(1.0, 0.0)],
-            vec![C64::new(1.0, 0.0), C64::new(3.0, 0.0)],
-        ];
-        let op = DenseOp::new(matrix);
+        let matrix = DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                C64::new(4.0, 0.0),
+                C64::new(1.0, 0.0),
+                C64::new(1.0, 0.0),
+                C64::new(3.0, 0.0),
+            ],
+        );
+        let op = DenseComplexOperator::new(matrix);
 
         let b = vec![C64::new(5.0, 0.0), C64::new(4.0, 0.0)];
         let config = GmresConfig::default();
@@ -507,11 +602,17 @@ mod tests {
 
     #[test]
     fn gmres_complex_hermitian() {
-        let matrix = vec![
-            vec![C64::new(2.0, 0.0), C64::new(1.0, -1.0)],
-            vec![C64::new(1.0, 1.0), C64::new(3.0, 0.0)],
-        ];
-        let op = DenseOp::new(matrix);
+        let matrix = DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                C64::new(2.0, 0.0),
+                C64::new(1.0, -1.0),
+                C64::new(1.0, 1.0),
+                C64::new(3.0, 0.0),
+            ],
+        );
+        let op = DenseComplexOperator::new(matrix);
 
         let b = vec![
             C64::new(2.0, 0.0) + C64::new(1.0, -1.0),
@@ -529,7 +630,8 @@ mod tests {
     fn gmres_restart_behavior() {
         let n = 50;
         let diag: Vec<C64> = (1..=n).map(|i| C64::new(i as f64, 0.5)).collect();
-        let op = DiagOp { diag: diag.clone() };
+        let op =
+            DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
 
         let b: Vec<C64> = diag.iter().map(|d| d * C64::new(1.0, 1.0)).collect();
 
@@ -537,6 +639,7 @@ mod tests {
             max_iter: 200,
             tol: 1e-8,
             restart: 5,
+            ..GmresConfig::default()
         };
         let result = solve_gmres(&op, &b, &config);
 
@@ -550,7 +653,7 @@ mod tests {
         let b_val = C64::new(6.0, 8.0);
         let expected_x = b_val / a;
 
-        let op = DiagOp { diag: vec![a] };
+        let op = DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_vec(vec![a])));
         let b = vec![b_val];
         let config = GmresConfig::default();
         let result = solve_gmres(&op, &b, &config);
@@ -565,7 +668,8 @@ mod tests {
         let diag: Vec<C64> = (1..=n)
             .map(|i| C64::new(i as f64, 0.5 * i as f64))
             .collect();
-        let op = DiagOp { diag: diag.clone() };
+        let op =
+            DenseComplexOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
         let precond = IdentityPreconditioner::new(n);
 
         let b: Vec<C64> = diag.iter().map(|d| d * C64::new(1.0, 1.0)).collect();
@@ -578,4 +682,48 @@ mod tests {
             assert!((xi - C64::new(1.0, 1.0)).norm() < 1e-6);
         }
     }
+
+    #[test]
+    fn gmres_reorthogonalization_rescues_ill_conditioned() {
+        // High-Q-like operator: strongly non-normal, with eigenvalues spread
+        // geometrically from 1 to 1e-8 and rotating in phase.
+        let n = 40;
+        let scale = 1.0 / (n as f64).sqrt();
+        let matrix = DMatrix::from_fn(n, n, |i, j| match j.cmp(&i) {
+            std::cmp::Ordering::Equal => {
+                let t = i as f64 / (n - 1) as f64;
+                C64::from_polar(1e-8_f64.powf(t), 0.5 * t)
+            }
+            std::cmp::Ordering::Greater => {
+                C64::from_polar(scale * ((i * n + j) as f64).sin(), (i + 2 * j) as f64)
+            }
+            std::cmp::Ordering::Less => C64::new(0.0, 0.0),
+        });
+        let op = DenseComplexOperator::new(matrix);
+        let x_true: Vec<C64> = (0..n)
+            .map(|i| C64::new(1.0, 0.5 * (i as f64).cos()))
+            .collect();
+        let mut b = vec![C64::new(0.0, 0.0); n];
+        op.apply(&x_true, &mut b);
+
+        let mut config = GmresConfig {
+            max_iter: 2 * n,
+            tol: 1e-10,
+            restart: n,
+            ..GmresConfig::default()
+        };
+
+        let plain = solve_gmres(&op, &b, &config);
+        assert!(!plain.converged, "MGS residual {:e}", plain.residual);
+
+        config.reorthogonalize = true;
+        let reorth = solve_gmres(&op, &b, &config);
+        assert!(
+            reorth.converged,
+            "reorthogonalized residual {:e}",
+            reorth.residual
+        );
+        assert!(reorth.iterations <= n);
+        assert!(reorth.residual < 1e-12);
+    }
 }
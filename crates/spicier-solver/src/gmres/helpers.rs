@@ -3,6 +3,62 @@
 use num_complex::Complex64 as C64;
 use spicier_simd::{SimdCapability, complex_conjugate_dot_product, real_dot_product};
 
+use super::GmresConfig;
+use crate::error::{Error, Result};
+
+/// Checks that every vector length in `dims` matches the operator size `n`.
+pub(crate) fn check_dims(n: usize, dims: impl IntoIterator<Item = usize>) -> Result<()> {
+    match dims.into_iter().find(|&actual| actual != n) {
+        Some(actual) => Err(Error::DimensionMismatch {
+            expected: n,
+            actual,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// A restart cycle that leaves more than this fraction of its starting
+/// residual counts as stagnating.
+const STAGNATION_RATIO: f64 = 0.5;
+
+/// Restart length for each GMRES cycle, grown on stagnation when
+/// [`GmresConfig::adaptive_restart`] is set.
+pub(crate) struct RestartSchedule {
+    length: usize,
+    cap: usize,
+    adaptive: bool,
+    last_used: usize,
+}
+
+impl RestartSchedule {
+    pub(crate) fn new(config: &GmresConfig) -> Self {
+        Self {
+            length: config.restart,
+            cap: config.max_restart.max(config.restart),
+            adaptive: config.adaptive_restart,
+            last_used: 0,
+        }
+    }
+
+    /// Krylov dimension for the next cycle on an `n`-dimensional system.
+    pub(crate) fn next_cycle(&mut self, n: usize) -> usize {
+        self.last_used = self.length.min(n);
+        self.last_used
+    }
+
+    /// Krylov dimension of the most recent cycle (0 before the first).
+    pub(crate) fn last_used(&self) -> usize {
+        self.last_used
+    }
+
+    /// Record a finished cycle's residual, doubling the length if it stalled.
+    pub(crate) fn record(&mut self, start_residual: f64, end_residual: f64) {
+        if self.adaptive && end_residual > STAGNATION_RATIO * start_residual {
+            self.length = (2 * self.length).min(self.cap);
+        }
+    }
+}
+
 /// Compute the 2-norm of a complex vector using SIMD-accelerated dot product.
 pub fn complex_vec_norm(v: &[C64], cap: SimdCapability) -> f64 {
     complex_conjugate_dot_product(v, v, cap).re.sqrt()
@@ -47,6 +103,77 @@ pub fn real_givens_rotation(a: f64, b: f64) -> (f64, f64) {
     (c, s)
 }
 
+/// DGKS threshold: reorthogonalize when a Gram-Schmidt sweep leaves less
+/// than `1/√2` of the vector's norm.
+const DGKS_ETA: f64 = std::f64::consts::FRAC_1_SQRT_2;
+
+/// Orthogonalize `w` against the Arnoldi basis `v` by modified Gram-Schmidt.
+///
+/// Stores the projection coefficients in `h[..v.len()]` and returns the
+/// norm of the orthogonalized `w`. With `reorthogonalize`, a second sweep is
+/// made when the first cancels most of `w` (the DGKS criterion), and its
+/// coefficients are added to `h`.
+pub fn complex_orthogonalize(
+    v: &[Vec<C64>],
+    w: &mut [C64],
+    h: &mut [C64],
+    reorthogonalize: bool,
+    cap: SimdCapability,
+) -> f64 {
+    let norm_before = complex_vec_norm(w, cap);
+    for (j, vj) in v.iter().enumerate() {
+        let hij = complex_conjugate_dot_product(vj, w, cap);
+        h[j] = hij;
+        for (wi, &vji) in w.iter_mut().zip(vj) {
+            *wi -= hij * vji;
+        }
+    }
+    let mut w_norm = complex_vec_norm(w, cap);
+
+    if reorthogonalize && w_norm < DGKS_ETA * norm_before {
+        for (j, vj) in v.iter().enumerate() {
+            let hij = complex_conjugate_dot_product(vj, w, cap);
+            h[j] += hij;
+            for (wi, &vji) in w.iter_mut().zip(vj) {
+                *wi -= hij * vji;
+            }
+        }
+        w_norm = complex_vec_norm(w, cap);
+    }
+    w_norm
+}
+
+/// Real counterpart of [`complex_orthogonalize`].
+pub fn real_orthogonalize(
+    v: &[Vec<f64>],
+    w: &mut [f64],
+    h: &mut [f64],
+    reorthogonalize: bool,
+    cap: SimdCapability,
+) -> f64 {
+    let norm_before = real_vec_norm(w, cap);
+    for (j, vj) in v.iter().enumerate() {
+        let hij = real_dot_product(vj, w, cap);
+        h[j] = hij;
+        for (wi, &vji) in w.iter_mut().zip(vj) {
+            *wi -= hij * vji;
+        }
+    }
+    let mut w_norm = real_vec_norm(w, cap);
+
+    if reorthogonalize && w_norm < DGKS_ETA * norm_before {
+        for (j, vj) in v.iter().enumerate() {
+            let hij = real_dot_product(vj, w, cap);
+            h[j] += hij;
+            for (wi, &vji) in w.iter_mut().zip(vj) {
+                *wi -= hij * vji;
+            }
+        }
+        w_norm = real_vec_norm(w, cap);
+    }
+    w_norm
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
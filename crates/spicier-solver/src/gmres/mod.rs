@@ -26,8 +26,14 @@ pub mod helpers;
 pub mod real;
 
 // Re-export main types and functions
-pub use complex::{GmresResult, solve_gmres, solve_gmres_preconditioned};
-pub use real::{RealGmresResult, solve_gmres_real, solve_gmres_real_preconditioned};
+pub use complex::{
+    GmresResult, solve_gmres, solve_gmres_preconditioned, solve_gmres_preconditioned_with_guess,
+    solve_gmres_with_guess,
+};
+pub use real::{
+    RealGmresResult, solve_fgmres_real, solve_gmres_real, solve_gmres_real_preconditioned,
+    solve_gmres_real_preconditioned_with_guess, solve_gmres_real_with_guess,
+};
 
 /// GMRES solver configuration.
 #[derive(Debug, Clone)]
@@ -38,6 +44,24 @@ pub struct GmresConfig {
     pub tol: f64,
     /// Restart parameter (Krylov subspace dimension before restart).
     pub restart: usize,
+    /// Reorthogonalize the Arnoldi vectors when a Gram-Schmidt sweep
+    /// cancels most of the new vector (DGKS criterion).
+    ///
+    /// Single-pass modified Gram-Schmidt loses orthogonality on
+    /// ill-conditioned systems such as high-Q AC circuits; the second sweep
+    /// restores it at up to twice the orthogonalization cost.
+    pub reorthogonalize: bool,
+    /// Grow the restart length when GMRES stagnates.
+    ///
+    /// A short restart is cheap on easy systems but can stall on hard ones.
+    /// With this set, `restart` is the starting length and is doubled, up to
+    /// `max_restart`, after every cycle that fails to halve the residual.
+    pub adaptive_restart: bool,
+    /// Largest restart length the adaptive schedule grows to.
+    pub max_restart: usize,
+    /// Record the relative residual of every Arnoldi step in the result's
+    /// `residual_history`. Off by default to keep the solve allocation-free.
+    pub track_history: bool,
 }
 
 impl Default for GmresConfig {
@@ -46,6 +70,10 @@ impl Default for GmresConfig {
             max_iter: 500,
             tol: 1e-8,
             restart: 30,
+            reorthogonalize: false,
+            adaptive_restart: false,
+            max_restart: 240,
+            track_history: false,
         }
     }
 }
@@ -60,5 +88,8 @@ mod tests {
         assert_eq!(config.max_iter, 500);
         assert!((config.tol - 1e-8).abs() < 1e-15);
         assert_eq!(config.restart, 30);
+        assert!(!config.reorthogonalize);
+        assert!(!config.adaptive_restart);
+        assert!(!config.track_history);
     }
 }
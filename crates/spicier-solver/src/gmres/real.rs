@@ -2,10 +2,13 @@
 
 use crate::operator::RealOperator;
 use crate::preconditioner::RealPreconditioner;
-use spicier_simd::{SimdCapability, real_dot_product};
+use spicier_simd::SimdCapability;
 
 use super::GmresConfig;
-use super::helpers::{real_givens_rotation, real_vec_norm};
+use super::helpers::{
+    RestartSchedule, check_dims, real_givens_rotation, real_orthogonalize, real_vec_norm,
+};
+use crate::error::Result;
 
 /// Result of a real-valued GMRES solve.
 #[derive(Debug, Clone)]
@@ -18,6 +21,12 @@ pub struct RealGmresResult {
     pub residual: f64,
     /// Whether the solver converged.
     pub converged: bool,
+    /// Restart length of the last cycle; with
+    /// [`GmresConfig::adaptive_restart`] this is where the schedule ended.
+    pub restart: usize,
+    /// Relative residual after each Arnoldi step, across all restart
+    /// cycles. Empty unless [`GmresConfig::track_history`] is set.
+    pub residual_history: Vec<f64>,
 }
 
 /// Solve A*x = b using restarted GMRES for real-valued systems.
@@ -28,6 +37,35 @@ pub struct RealGmresResult {
 /// This is more efficient than using the complex GMRES for real systems
 /// since it avoids complex arithmetic overhead.
 pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig) -> RealGmresResult {
+    restarted(op, b, None, config)
+}
+
+/// Solve A*x = b using restarted GMRES, starting from `x0`.
+///
+/// With `x0 = None` this is [`solve_gmres_real`]. A good guess, such as the
+/// previous timestep's solution, starts from a smaller residual and needs
+/// fewer iterations. Returns [`Error::DimensionMismatch`](crate::Error::DimensionMismatch)
+/// if `b` or `x0` does not match the operator size.
+pub fn solve_gmres_real_with_guess(
+    op: &dyn RealOperator,
+    b: &[f64],
+    x0: Option<&[f64]>,
+    config: &GmresConfig,
+) -> Result<RealGmresResult> {
+    check_dims(
+        op.dim(),
+        std::iter::once(b.len()).chain(x0.map(<[f64]>::len)),
+    )?;
+    Ok(restarted(op, b, x0, config))
+}
+
+/// Restarted GMRES without preconditioning.
+fn restarted(
+    op: &dyn RealOperator,
+    b: &[f64],
+    x0: Option<&[f64]>,
+    config: &GmresConfig,
+) -> RealGmresResult {
     let simd_cap = SimdCapability::detect();
 
     let n = op.dim();
@@ -40,12 +78,19 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
             iterations: 0,
             residual: 0.0,
             converged: true,
+            restart: 0,
+            residual_history: Vec::new(),
         };
     }
 
-    let mut x = vec![0.0; n];
+    let mut x = match x0 {
+        Some(x0) => x0.to_vec(),
+        None => vec![0.0; n],
+    };
     let mut total_iter = 0;
 
+    let mut schedule = RestartSchedule::new(config);
+    let mut residual_history = Vec::new();
     for _restart_cycle in 0..config.max_iter {
         // Compute residual r = b - A*x
         let mut ax = vec![0.0; n];
@@ -63,11 +108,13 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
                 iterations: total_iter,
                 residual: r_norm / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
         // Arnoldi process with modified Gram-Schmidt
-        let m = config.restart.min(n);
+        let m = schedule.next_cycle(n);
         let mut v: Vec<Vec<f64>> = Vec::with_capacity(m + 1);
         let mut h = vec![vec![0.0; m + 1]; m];
 
@@ -98,27 +145,25 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
             op.apply(&v[k], &mut w);
 
             // Modified Gram-Schmidt
-            for j in 0..=k {
-                let hij = real_dot_product(&v[j], &w, simd_cap);
-                h[k][j] = hij;
-                for i in 0..n {
-                    w[i] -= hij * v[j][i];
-                }
-            }
-
-            let w_norm = real_vec_norm(&w, simd_cap);
+            let w_norm = real_orthogonalize(
+                &v[..=k],
+                &mut w,
+                &mut h[k],
+                config.reorthogonalize,
+                simd_cap,
+            );
             h[k][k + 1] = w_norm;
 
-            if w_norm < 1e-30 {
-                // Lucky breakdown
-                k += 1;
-                break;
+            // Lucky breakdown: the Krylov space is invariant and the
+            // solution is exact. The column is still rotated below so the
+            // back-substitution sees a triangular H.
+            let breakdown = w_norm < 1e-30;
+            if !breakdown {
+                let inv_w = 1.0 / w_norm;
+                let vk1: Vec<f64> = w.iter().map(|&wi| wi * inv_w).collect();
+                v.push(vk1);
             }
 
-            let inv_w = 1.0 / w_norm;
-            let vk1: Vec<f64> = w.iter().map(|&wi| wi * inv_w).collect();
-            v.push(vk1);
-
             // Apply previous Givens rotations to h[k]
             for j in 0..k {
                 let temp = cs[j] * h[k][j] + sn[j] * h[k][j + 1];
@@ -140,7 +185,10 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
             g[k] = temp_g;
 
             let rel_res = g[k + 1].abs() / b_norm;
-            if rel_res < config.tol {
+            if config.track_history {
+                residual_history.push(rel_res);
+            }
+            if breakdown || rel_res < config.tol {
                 k += 1;
                 break;
             }
@@ -183,6 +231,8 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
@@ -192,8 +242,12 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: false,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
+
+        schedule.record(r_norm, final_res);
     }
 
     // Should not reach here
@@ -202,6 +256,8 @@ pub fn solve_gmres_real(op: &dyn RealOperator, b: &[f64], config: &GmresConfig)
         iterations: total_iter,
         residual: f64::NAN,
         converged: false,
+        restart: schedule.last_used(),
+        residual_history,
     }
 }
 
@@ -220,12 +276,84 @@ pub fn solve_gmres_real_preconditioned(
     precond: &dyn RealPreconditioner,
     b: &[f64],
     config: &GmresConfig,
+) -> RealGmresResult {
+    right_preconditioned(
+        op,
+        precond.dim(),
+        &mut |v, z| precond.apply(v, z),
+        b,
+        None,
+        config,
+    )
+}
+
+/// Solve A*x = b using right-preconditioned GMRES, starting from `x0`.
+///
+/// With `x0 = None` this is [`solve_gmres_real_preconditioned`]. Returns
+/// [`Error::DimensionMismatch`](crate::Error::DimensionMismatch) if `b`,
+/// `x0` or the preconditioner does not match the operator size.
+pub fn solve_gmres_real_preconditioned_with_guess(
+    op: &dyn RealOperator,
+    precond: &dyn RealPreconditioner,
+    b: &[f64],
+    x0: Option<&[f64]>,
+    config: &GmresConfig,
+) -> Result<RealGmresResult> {
+    check_dims(
+        op.dim(),
+        [b.len(), precond.dim()]
+            .into_iter()
+            .chain(x0.map(<[f64]>::len)),
+    )?;
+    Ok(right_preconditioned(
+        op,
+        precond.dim(),
+        &mut |v, z| precond.apply(v, z),
+        b,
+        x0,
+        config,
+    ))
+}
+
+/// Solve A*x = b using flexible GMRES (FGMRES) for real systems.
+///
+/// Shares its iteration with [`solve_gmres_real_preconditioned`], which
+/// already keeps each preconditioned vector `z[k] = M_k⁻¹ v[k]` and forms
+/// `x += Z y` rather than `M⁻¹ V y`, so the update stays valid when `M`
+/// changes from one iteration to the next, e.g. an ILU applied through an
+/// inner iterative solve. The difference is that the preconditioner is
+/// called through [`RealPreconditioner::apply_mut`] and may keep state.
+pub fn solve_fgmres_real(
+    op: &dyn RealOperator,
+    precond: &mut dyn RealPreconditioner,
+    b: &[f64],
+    config: &GmresConfig,
+) -> RealGmresResult {
+    let dim = precond.dim();
+    right_preconditioned(
+        op,
+        dim,
+        &mut |v, z| precond.apply_mut(v, z),
+        b,
+        None,
+        config,
+    )
+}
+
+/// Restarted GMRES with the preconditioned basis `Z` stored explicitly.
+fn right_preconditioned(
+    op: &dyn RealOperator,
+    precond_dim: usize,
+    precond: &mut dyn FnMut(&[f64], &mut [f64]),
+    b: &[f64],
+    x0: Option<&[f64]>,
+    config: &GmresConfig,
 ) -> RealGmresResult {
     let simd_cap = SimdCapability::detect();
 
     let n = op.dim();
     assert_eq!(b.len(), n, "RHS dimension mismatch");
-    assert_eq!(precond.dim(), n, "Preconditioner dimension mismatch");
+    assert_eq!(precond_dim, n, "Preconditioner dimension mismatch");
 
     let b_norm = real_vec_norm(b, simd_cap);
     if b_norm < 1e-30 {
@@ -234,15 +362,22 @@ pub fn solve_gmres_real_preconditioned(
             iterations: 0,
             residual: 0.0,
             converged: true,
+            restart: 0,
+            residual_history: Vec::new(),
         };
     }
 
-    let mut x = vec![0.0; n];
+    let mut x = match x0 {
+        Some(x0) => x0.to_vec(),
+        None => vec![0.0; n],
+    };
     let mut total_iter = 0;
 
     // Workspace for preconditioner application
     let mut precond_work = vec![0.0; n];
 
+    let mut schedule = RestartSchedule::new(config);
+    let mut residual_history = Vec::new();
     for _restart_cycle in 0..config.max_iter {
         // Compute residual r = b - A*x
         let mut ax = vec![0.0; n];
@@ -260,11 +395,13 @@ pub fn solve_gmres_real_preconditioned(
                 iterations: total_iter,
                 residual: r_norm / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
         // Arnoldi process with modified Gram-Schmidt
-        let m = config.restart.min(n);
+        let m = schedule.next_cycle(n);
         let mut v: Vec<Vec<f64>> = Vec::with_capacity(m + 1);
         let mut z: Vec<Vec<f64>> = Vec::with_capacity(m); // z[k] = M^(-1) * v[k]
         let mut h = vec![vec![0.0; m + 1]; m];
@@ -292,7 +429,7 @@ pub fn solve_gmres_real_preconditioned(
             }
 
             // z[k] = M^(-1) * v[k]
-            precond.apply(&v[k], &mut precond_work);
+            precond(&v[k], &mut precond_work);
             z.push(precond_work.clone());
 
             // w = A * z[k] = A * M^(-1) * v[k]
@@ -300,26 +437,25 @@ pub fn solve_gmres_real_preconditioned(
             op.apply(&z[k], &mut w);
 
             // Modified Gram-Schmidt
-            for j in 0..=k {
-                let hij = real_dot_product(&v[j], &w, simd_cap);
-                h[k][j] = hij;
-                for i in 0..n {
-                    w[i] -= hij * v[j][i];
-                }
-            }
-
-            let w_norm = real_vec_norm(&w, simd_cap);
+            let w_norm = real_orthogonalize(
+                &v[..=k],
+                &mut w,
+                &mut h[k],
+                config.reorthogonalize,
+                simd_cap,
+            );
             h[k][k + 1] = w_norm;
 
-            if w_norm < 1e-30 {
-                k += 1;
-                break;
+            // Lucky breakdown: the Krylov space is invariant and the
+            // solution is exact. The column is still rotated below so the
+            // back-substitution sees a triangular H.
+            let breakdown = w_norm < 1e-30;
+            if !breakdown {
+                let inv_w = 1.0 / w_norm;
+                let vk1: Vec<f64> = w.iter().map(|&wi| wi * inv_w).collect();
+                v.push(vk1);
             }
 
-            let inv_w = 1.0 / w_norm;
-            let vk1: Vec<f64> = w.iter().map(|&wi| wi * inv_w).collect();
-            v.push(vk1);
-
             // Apply previous Givens rotations to h[k]
             for j in 0..k {
                 let temp = cs[j] * h[k][j] + sn[j] * h[k][j + 1];
@@ -341,7 +477,10 @@ pub fn solve_gmres_real_preconditioned(
             g[k] = temp_g;
 
             let rel_res = g[k + 1].abs() / b_norm;
-            if rel_res < config.tol {
+            if config.track_history {
+                residual_history.push(rel_res);
+            }
+            if breakdown || rel_res < config.tol {
                 k += 1;
                 break;
             }
@@ -384,6 +523,8 @@ pub fn solve_gmres_real_preconditioned(
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: true,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
 
@@ -393,8 +534,12 @@ pub fn solve_gmres_real_preconditioned(
                 iterations: total_iter,
                 residual: final_res / b_norm,
                 converged: false,
+                restart: schedule.last_used(),
+                residual_history,
             };
         }
+
+        schedule.record(r_norm, final_res);
     }
 
     RealGmresResult {
@@ -402,65 +547,23 @@ pub fn solve_gmres_real_preconditioned(
         iterations: total_iter,
         residual: f64::NAN,
         converged: false,
+        restart: schedule.last_used(),
+        residual_history,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::operator::DenseRealOperator;
     use crate::preconditioner::{IdentityPreconditioner, JacobiPreconditioner};
-
-    /// Simple diagonal real operator for testing.
-    struct RealDiagOp {
-        diag: Vec<f64>,
-    }
-
-    impl RealOperator for RealDiagOp {
-        fn dim(&self) -> usize {
-            self.diag.len()
-        }
-
-        fn apply(&self, x: &[f64], y: &mut [f64]) {
-            for i in 0..self.diag.len() {
-                y[i] = self.diag[i] * x[i];
-            }
-        }
-    }
-
-    /// Dense real matrix operator for testing.
-    struct RealDenseOp {
-        matrix: Vec<Vec<f64>>,
-        n: usize,
-    }
-
-    impl RealDenseOp {
-        fn new(matrix: Vec<Vec<f64>>) -> Self {
-            let n = matrix.len();
-            Self { matrix, n }
-        }
-    }
-
-    impl RealOperator for RealDenseOp {
-        fn dim(&self) -> usize {
-            self.n
-        }
-
-        #[allow(clippy::needless_range_loop)]
-        fn apply(&self, x: &[f64], y: &mut [f64]) {
-            for i in 0..self.n {
-                y[i] = 0.0;
-                for j in 0..self.n {
-                    y[i] += self.matrix[i][j] * x[j];
-                }
-            }
-        }
-    }
+    use nalgebra::{DMatrix, DVector};
 
     #[test]
     fn gmres_real_diagonal_system() {
         let n = 10;
         let diag: Vec<f64> = (1..=n).map(|i| i as f64).collect();
-        let op = RealDiagOp { diag: diag.clone() };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
 
         let b: Vec<f64> = diag.iter().map(|&d| d * 1.0).collect();
 
@@ -479,7 +582,7 @@ mod tests {
     fn gmres_real_zero_rhs() {
         let n = 5;
         let diag: Vec<f64> = (1..=n).map(|i| i as f64).collect();
-        let op = RealDiagOp { diag };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag)));
 
         let b = vec![0.0; n];
         let config = GmresConfig::default();
@@ -496,7 +599,7 @@ mod tests {
     fn gmres_real_identity_operator() {
         let n = 5;
         let diag = vec![1.0; n];
-        let op = RealDiagOp { diag };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag)));
 
         let b: Vec<f64> = (1..=n).map(|i| i as f64).collect();
         let config = GmresConfig::default();
@@ -510,8 +613,8 @@ mod tests {
 
     #[test]
     fn gmres_real_spd_system() {
-        let matrix = vec![vec![4.0, 1.0], vec![1.0, 3.0]];
-        let op = RealDenseOp::new(matrix);
+        let matrix = DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 3.0]);
+        let op = DenseRealOperator::new(matrix);
 
         let b = vec![5.0, 4.0];
         let config = GmresConfig::default();
@@ -524,12 +627,9 @@ mod tests {
 
     #[test]
     fn gmres_real_tridiagonal() {
-        let matrix = vec![
-            vec![2.0, -1.0, 0.0],
-            vec![-1.0, 2.0, -1.0],
-            vec![0.0, -1.0, 2.0],
-        ];
-        let op = RealDenseOp::new(matrix);
+        let matrix =
+            DMatrix::from_row_slice(3, 3, &[2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]);
+        let op = DenseRealOperator::new(matrix);
 
         let b = vec![0.0, 0.0, 4.0];
         let config = GmresConfig::default();
@@ -545,7 +645,7 @@ mod tests {
     fn gmres_real_restart_behavior() {
         let n = 50;
         let diag: Vec<f64> = (1..=n).map(|i| i as f64 + 0.5).collect();
-        let op = RealDiagOp { diag: diag.clone() };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
 
         let b: Vec<f64> = diag.iter().map(|&d| d * 1.0).collect();
 
@@ -553,6 +653,7 @@ mod tests {
             max_iter: 200,
             tol: 1e-8,
             restart: 5,
+            ..GmresConfig::default()
         };
         let result = solve_gmres_real(&op, &b, &config);
 
@@ -560,11 +661,102 @@ mod tests {
         assert!(result.residual < 1e-6);
     }
 
+    #[test]
+    fn gmres_real_adaptive_restart_escapes_stagnation() {
+        // Cyclic shift, A e_i = e_(i+1): with b = e_0 every Krylov space
+        // shorter than n is orthogonal to the residual, so restarted GMRES
+        // makes no progress at all until the restart reaches n
+        let n = 20;
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            matrix[((i + 1) % n, i)] = 1.0;
+        }
+        let op = DenseRealOperator::new(matrix);
+        let mut b = vec![0.0; n];
+        b[0] = 1.0;
+
+        let fixed = GmresConfig {
+            max_iter: 200,
+            restart: 5,
+            ..GmresConfig::default()
+        };
+        let stalled = solve_gmres_real(&op, &b, &fixed);
+        assert!(!stalled.converged);
+        assert!((stalled.residual - 1.0).abs() < 1e-12);
+        assert_eq!(stalled.restart, 5);
+
+        let adaptive = GmresConfig {
+            adaptive_restart: true,
+            ..fixed
+        };
+        let precond = IdentityPreconditioner::new(n);
+        for result in [
+            solve_gmres_real(&op, &b, &adaptive),
+            solve_gmres_real_preconditioned(&op, &precond, &b, &adaptive),
+        ] {
+            assert!(result.converged, "residual {:e}", result.residual);
+            // 5 and 10 both stall before 20 = n converges
+            assert_eq!(result.restart, n);
+            assert_eq!(result.iterations, 5 + 10 + n);
+            // x = A⁻¹ e_0 = e_(n-1)
+            assert!((result.x[n - 1] - 1.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn gmres_real_residual_history() {
+        // SPD tridiag(-1, 4, -1), restarted so the history spans several cycles
+        let n = 40;
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = 4.0;
+            if i + 1 < n {
+                matrix[(i, i + 1)] = -1.0;
+                matrix[(i + 1, i)] = -1.0;
+            }
+        }
+        let op = DenseRealOperator::new(matrix);
+        let b: Vec<f64> = (0..n).map(|i| 1.0 + (i % 3) as f64).collect();
+        let config = GmresConfig {
+            max_iter: 500,
+            tol: 1e-10,
+            restart: 10,
+            track_history: true,
+            ..GmresConfig::default()
+        };
+
+        let result = solve_gmres_real(&op, &b, &config);
+
+        assert!(result.converged);
+        assert!(result.iterations > config.restart);
+        assert_eq!(result.residual_history.len(), result.iterations);
+        // GMRES minimizes the residual over a growing Krylov space, so it
+        // cannot increase within a cycle
+        for cycle in result.residual_history.chunks(config.restart) {
+            for pair in cycle.windows(2) {
+                assert!(pair[1] <= pair[0] * (1.0 + 1e-12), "{pair:?}");
+            }
+        }
+        let last = *result.residual_history.last().unwrap();
+        assert!(last < config.tol);
+        assert!((last - result.residual).abs() < 1e-10);
+
+        let untracked = solve_gmres_real(
+            &op,
+            &b,
+            &GmresConfig {
+                track_history: false,
+                ..config
+            },
+        );
+        assert!(untracked.residual_history.is_empty());
+    }
+
     #[test]
     fn preconditioned_gmres_real_with_identity() {
         let n = 10;
         let diag: Vec<f64> = (1..=n).map(|i| i as f64).collect();
-        let op = RealDiagOp { diag: diag.clone() };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
         let precond = IdentityPreconditioner::new(n);
 
         let b: Vec<f64> = diag.iter().map(|&d| d * 1.0).collect();
@@ -582,7 +774,7 @@ mod tests {
     fn preconditioned_gmres_real_with_jacobi() {
         let n = 10;
         let diag: Vec<f64> = (1..=n).map(|i| i as f64).collect();
-        let op = RealDiagOp { diag: diag.clone() };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
         let precond = JacobiPreconditioner::from_diagonal(&diag);
 
         let b: Vec<f64> = diag.iter().map(|&d| d * 1.0).collect();
@@ -599,8 +791,8 @@ mod tests {
 
     #[test]
     fn preconditioned_gmres_real_spd_system() {
-        let matrix = vec![vec![4.0, 1.0], vec![1.0, 3.0]];
-        let op = RealDenseOp::new(matrix);
+        let matrix = DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 3.0]);
+        let op = DenseRealOperator::new(matrix);
         let precond = JacobiPreconditioner::from_diagonal(&[4.0, 3.0]);
 
         let b = vec![5.0, 4.0];
@@ -617,7 +809,7 @@ mod tests {
     fn preconditioned_gmres_zero_rhs() {
         let n = 5;
         let diag: Vec<f64> = (1..=n).map(|i| i as f64).collect();
-        let op = RealDiagOp { diag: diag.clone() };
+        let op = DenseRealOperator::new(DMatrix::from_diagonal(&DVector::from_vec(diag.clone())));
         let precond = JacobiPreconditioner::from_diagonal(&diag);
 
         let b = vec![0.0; n];
@@ -635,8 +827,8 @@ mod tests {
     #[test]
     fn preconditioned_gmres_from_triplets() {
         let triplets = vec![(0, 0, 4.0), (0, 1, 1.0), (1, 0, 1.0), (1, 1, 3.0)];
-        let matrix = vec![vec![4.0, 1.0], vec![1.0, 3.0]];
-        let op = RealDenseOp::new(matrix);
+        let matrix = DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 3.0]);
+        let op = DenseRealOperator::new(matrix);
         let precond = JacobiPreconditioner::from_triplets(2, &triplets);
 
         let b = vec![5.0, 4.0];
@@ -648,4 +840,212 @@ mod tests {
         assert!((result.x[0] - 1.0).abs() < 1e-6);
         assert!((result.x[1] - 1.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_warm_start_reduces_iterations() {
+        // 1D Laplacian-like chain, solved for a slowly drifting RHS as in a
+        // transient where each step is close to the last.
+        let n = 60;
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = 2.1;
+            if i + 1 < n {
+                matrix[(i, i + 1)] = -1.0;
+                matrix[(i + 1, i)] = -1.0;
+            }
+        }
+        let op = DenseRealOperator::new(matrix);
+        let jacobi = JacobiPreconditioner::from_diagonal(&vec![2.1; n]);
+        let config = GmresConfig {
+            max_iter: 1000,
+            tol: 1e-10,
+            restart: 30,
+            ..GmresConfig::default()
+        };
+        let rhs = |k: usize| -> Vec<f64> {
+            (0..n)
+                .map(|i| 1.0 + (0.1 * i as f64 + 0.01 * k as f64).sin())
+                .collect()
+        };
+
+        let (mut cold_iters, mut warm_iters, mut warm_pre_iters) = (0, 0, 0);
+        let mut prev: Option<Vec<f64>> = None;
+        let mut prev_pre: Option<Vec<f64>> = None;
+        for k in 0..20 {
+            let b = rhs(k);
+            let cold = solve_gmres_real(&op, &b, &config);
+            let warm = solve_gmres_real_with_guess(&op, &b, prev.as_deref(), &config).unwrap();
+            let warm_pre = solve_gmres_real_preconditioned_with_guess(
+                &op,
+                &jacobi,
+                &b,
+                prev_pre.as_deref(),
+                &config,
+            )
+            .unwrap();
+            assert!(cold.converged && warm.converged && warm_pre.converged);
+            for i in 0..n {
+                assert!((warm.x[i] - cold.x[i]).abs() < 1e-7);
+                assert!((warm_pre.x[i] - cold.x[i]).abs() < 1e-7);
+            }
+
+            cold_iters += cold.iterations;
+            warm_iters += warm.iterations;
+            warm_pre_iters += warm_pre.iterations;
+            prev = Some(warm.x);
+            prev_pre = Some(warm_pre.x);
+        }
+
+        assert!(
+            warm_iters < cold_iters * 3 / 4,
+            "warm {} vs cold {} iterations",
+            warm_iters,
+            cold_iters
+        );
+        assert!(warm_pre_iters < cold_iters * 3 / 4);
+    }
+
+    #[test]
+    fn gmres_real_with_guess_rejects_mismatched_dimensions() {
+        let n = 4;
+        let op = DenseRealOperator::new(DMatrix::identity(n, n));
+        let config = GmresConfig::default();
+        let b = vec![1.0; n];
+
+        let err = solve_gmres_real_with_guess(&op, &b, Some(&[0.0; 3]), &config).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::DimensionMismatch {
+                expected: 4,
+                actual: 3
+            }
+        ));
+        let err = solve_gmres_real_with_guess(&op, &[1.0; 5], None, &config).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::DimensionMismatch { actual: 5, .. }
+        ));
+
+        let precond = IdentityPreconditioner::new(n + 1);
+        let err = solve_gmres_real_preconditioned_with_guess(&op, &precond, &b, None, &config)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::DimensionMismatch { actual: 5, .. }
+        ));
+        let precond = IdentityPreconditioner::new(n);
+        let err =
+            solve_gmres_real_preconditioned_with_guess(&op, &precond, &b, Some(&[0.0; 6]), &config)
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::DimensionMismatch { actual: 6, .. }
+        ));
+    }
+
+    /// Strongly non-normal upper-triangular operator whose eigenvalues are
+    /// spread geometrically from 1 down to `lo`.
+    fn ill_conditioned_upper(n: usize, lo: f64) -> DMatrix<f64> {
+        let scale = 1.0 / (n as f64).sqrt();
+        DMatrix::from_fn(n, n, |i, j| match j.cmp(&i) {
+            std::cmp::Ordering::Equal => lo.powf(i as f64 / (n - 1) as f64),
+            std::cmp::Ordering::Greater => ((i * n + j) as f64).sin() * scale,
+            std::cmp::Ordering::Less => 0.0,
+        })
+    }
+
+    #[test]
+    fn gmres_real_reorthogonalization_rescues_ill_conditioned() {
+        let n = 40;
+        let op = DenseRealOperator::new(ill_conditioned_upper(n, 1e-8));
+        let x_true: Vec<f64> = (0..n).map(|i| 1.0 + 0.5 * (i as f64).cos()).collect();
+        let mut b = vec![0.0; n];
+        op.apply(&x_true, &mut b);
+
+        let mut config = GmresConfig {
+            max_iter: 2 * n,
+            tol: 1e-10,
+            restart: n,
+            ..GmresConfig::default()
+        };
+
+        // Single-pass MGS loses orthogonality and stalls well above tol,
+        // even with a second restart cycle.
+        let plain = solve_gmres_real(&op, &b, &config);
+        assert!(!plain.converged, "MGS residual {:e}", plain.residual);
+
+        config.reorthogonalize = true;
+        let reorth = solve_gmres_real(&op, &b, &config);
+        assert!(
+            reorth.converged,
+            "reorthogonalized residual {:e}",
+            reorth.residual
+        );
+        assert!(reorth.iterations <= n);
+        assert!(reorth.residual < 1e-12);
+    }
+
+    /// Jacobi with a fresh random scaling of every entry on each call: a
+    /// different `M` at every Arnoldi step.
+    struct JitteredJacobi {
+        inv_diag: Vec<f64>,
+        calls: u64,
+    }
+
+    impl RealPreconditioner for JitteredJacobi {
+        fn apply(&self, x: &[f64], y: &mut [f64]) {
+            for ((yi, xi), d) in y.iter_mut().zip(x).zip(&self.inv_diag) {
+                *yi = xi * d;
+            }
+        }
+
+        fn apply_mut(&mut self, x: &[f64], y: &mut [f64]) {
+            let mut rng = crate::sweep::SampleRng::new(7, self.calls);
+            self.calls += 1;
+            for ((yi, xi), d) in y.iter_mut().zip(x).zip(&self.inv_diag) {
+                *yi = xi * d * (0.5 + rng.next_uniform());
+            }
+        }
+
+        fn dim(&self) -> usize {
+            self.inv_diag.len()
+        }
+    }
+
+    #[test]
+    fn fgmres_converges_with_varying_preconditioner() {
+        // Nonsymmetric chain with a badly scaled diagonal
+        let n = 60;
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = 3.0 * (1.0 + (i % 7) as f64 * 20.0);
+            if i + 1 < n {
+                matrix[(i, i + 1)] = -1.0;
+                matrix[(i + 1, i)] = -2.0;
+            }
+        }
+        let mut precond = JitteredJacobi {
+            inv_diag: (0..n).map(|i| 1.0 / matrix[(i, i)]).collect(),
+            calls: 0,
+        };
+        let op = DenseRealOperator::new(matrix);
+        let x_true: Vec<f64> = (0..n).map(|i| (0.1 * i as f64).sin()).collect();
+        let mut b = vec![0.0; n];
+        op.apply(&x_true, &mut b);
+
+        let config = GmresConfig {
+            max_iter: 200,
+            tol: 1e-10,
+            restart: 20,
+            ..GmresConfig::default()
+        };
+        let result = solve_fgmres_real(&op, &mut precond, &b, &config);
+
+        assert!(result.converged, "residual {:e}", result.residual);
+        // Every Arnoldi step saw a different preconditioner
+        assert_eq!(precond.calls as usize, result.iterations);
+        for (xi, ti) in result.x.iter().zip(&x_true) {
+            assert!((xi - ti).abs() < 1e-8);
+        }
+    }
 }
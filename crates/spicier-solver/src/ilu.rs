@@ -868,6 +868,7 @@ mod tests {
             max_iter: 100,
             tol: 1e-10,
             restart: 30,
+            ..GmresConfig::default()
         };
 
         // Solve without preconditioning
@@ -916,6 +917,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ilu0_beats_jacobi_on_2d_laplacian() {
+        use crate::gmres::{GmresConfig, solve_gmres_real_preconditioned};
+        use crate::preconditioner::JacobiPreconditioner;
+        use crate::sparse_operator::SparseRealOperator;
+
+        // 5-point Laplacian on a 12x12 grid, like a resistor mesh. ILU(0)
+        // drops the fill between grid rows, so unlike the tridiagonal case
+        // it is not exact.
+        let side = 12;
+        let n = side * side;
+        let mut triplets = Vec::new();
+        for row in 0..side {
+            for col in 0..side {
+                let i = row * side + col;
+                triplets.push((i, i, 4.0));
+                if col > 0 {
+                    triplets.push((i, i - 1, -1.0));
+                }
+                if col < side - 1 {
+                    triplets.push((i, i + 1, -1.0));
+                }
+                if row > 0 {
+                    triplets.push((i, i - side, -1.0));
+                }
+                if row < side - 1 {
+                    triplets.push((i, i + side, -1.0));
+                }
+            }
+        }
+
+        let op = SparseRealOperator::from_triplets(n, &triplets).unwrap();
+        let b = vec![1.0; n];
+        let config = GmresConfig {
+            max_iter: 500,
+            tol: 1e-10,
+            restart: 30,
+            ..GmresConfig::default()
+        };
+
+        let jacobi = JacobiPreconditioner::from_triplets(n, &triplets);
+        let result_jacobi = solve_gmres_real_preconditioned(&op, &jacobi, &b, &config);
+        let ilu = Ilu0Preconditioner::from_triplets(n, &triplets).unwrap();
+        let result_ilu = solve_gmres_real_preconditioned(&op, &ilu, &b, &config);
+
+        // No fill-in: the factors keep exactly the input pattern
+        assert_eq!(ilu.nnz(), triplets.len());
+
+        assert!(result_jacobi.converged);
+        assert!(result_ilu.converged);
+        assert!(
+            result_ilu.iterations < result_jacobi.iterations,
+            "ILU ({} iters) should beat Jacobi ({} iters)",
+            result_ilu.iterations,
+            result_jacobi.iterations
+        );
+        for i in 0..n {
+            assert!((result_ilu.x[i] - result_jacobi.x[i]).abs() < 1e-6);
+        }
+    }
+
     #[test]
     fn ilu0_4x4_with_fill_dropping() {
         // A 4x4 matrix where ILU(0) must drop fill-in
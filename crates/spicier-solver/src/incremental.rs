@@ -0,0 +1,327 @@
+//! Incremental re-solve of a linear DC system after component edits.
+//!
+//! Interactive tools change one component at a time. [`IncrementalDcSolver`]
+//! keeps the LU factorization of a base matrix `A0` and folds edits in as a
+//! low-rank correction (Sherman-Morrison-Woodbury): with the accumulated
+//! change written as `P D Pᵀ` over the `k` touched unknowns,
+//!
+//! ```text
+//! x = y - Z (I + D Zₖ)⁻¹ D yₖ,    y = A0⁻¹ b,  Z = A0⁻¹ P
+//! ```
+//!
+//! so a re-solve costs one back-substitution plus a `k × k` dense solve
+//! rather than a full refactorization. Once more than
+//! [`max_rank`](IncrementalDcSolver::with_max_rank) unknowns have been
+//! touched the edits are folded into a fresh factorization.
+//!
+//! ```
+//! use spicier_core::{Netlist, NodeId};
+//! use spicier_devices::passive::Resistor;
+//! use spicier_devices::sources::VoltageSource;
+//! use spicier_solver::IncrementalDcSolver;
+//!
+//! let mut netlist = Netlist::new();
+//! netlist.register_node(NodeId::new(2));
+//! netlist.add_device(VoltageSource::new("V1", NodeId::new(1), NodeId::GROUND, 10.0, 0));
+//! netlist.add_device(Resistor::new("R1", NodeId::new(1), NodeId::new(2), 1e3));
+//! netlist.add_device(Resistor::new("R2", NodeId::new(2), NodeId::GROUND, 1e3));
+//!
+//! let mut solver = IncrementalDcSolver::new(netlist.assemble_mna()).unwrap();
+//! let delta = netlist.set_device_value("R2", 3e3).unwrap();
+//! solver.apply(&delta).unwrap();
+//!
+//! let v2 = solver.solve().unwrap().voltage(NodeId::new(2));
+//! assert!((v2 - 7.5).abs() < 1e-9);
+//! ```
+
+use faer::prelude::*;
+use faer::sparse::linalg::solvers::Lu;
+use faer::sparse::{SparseColMat, Triplet};
+use nalgebra::{DMatrix, DVector, Dyn, LU};
+use spicier_core::mna::MnaSystem;
+
+use crate::dc::DcSolution;
+use crate::error::{Error, Result};
+use crate::linear::SPARSE_THRESHOLD;
+
+/// Default number of touched unknowns before refactoring.
+const DEFAULT_MAX_RANK: usize = 32;
+
+/// A held numeric LU factorization, dense or sparse by system size.
+enum Factorization {
+    Dense(LU<f64, Dyn, Dyn>),
+    Sparse(Box<Lu<usize, f64>>),
+}
+
+impl Factorization {
+    fn new(mna: &MnaSystem) -> Result<Self> {
+        let size = mna.size();
+        if size >= SPARSE_THRESHOLD {
+            let triplets: Vec<_> = mna
+                .triplets
+                .iter()
+                .map(|&(r, c, v)| Triplet::new(r, c, v))
+                .collect();
+            let matrix = SparseColMat::<usize, f64>::try_new_from_triplets(size, size, &triplets)
+                .map_err(|_| Error::SingularMatrix)?;
+            let lu = matrix.sp_lu().map_err(|_| Error::SingularMatrix)?;
+            Ok(Self::Sparse(Box::new(lu)))
+        } else {
+            let lu = mna.to_dense_matrix().lu();
+            if !lu.is_invertible() {
+                return Err(Error::SingularMatrix);
+            }
+            Ok(Self::Dense(lu))
+        }
+    }
+
+    fn solve(&self, b: &DVector<f64>) -> Result<DVector<f64>> {
+        match self {
+            Self::Dense(lu) => lu.solve(b).ok_or(Error::SingularMatrix),
+            Self::Sparse(lu) => {
+                let rhs = Col::<f64>::from_fn(b.len(), |i| b[i]);
+                let x = lu.solve(&rhs);
+                Ok(DVector::from_fn(b.len(), |i, _| x[i]))
+            }
+        }
+    }
+}
+
+/// DC solver for a linear circuit that re-solves cheaply after small edits.
+///
+/// Build it from an assembled system, then pass each change from
+/// [`Netlist::set_device_value`](spicier_core::Netlist::set_device_value) to
+/// [`apply`](Self::apply). Nonlinear circuits need a fresh Newton solve
+/// instead, since every iteration changes the Jacobian.
+pub struct IncrementalDcSolver {
+    /// The current system: base plus every applied edit.
+    mna: MnaSystem,
+    /// Factorization of the base matrix `A0`.
+    factor: Factorization,
+    /// Unknowns touched by edits since the last factorization.
+    touched: Vec<usize>,
+    /// `A0⁻¹ eₖ` for each touched unknown `k`.
+    columns: Vec<DVector<f64>>,
+    /// Accumulated matrix change restricted to the touched unknowns.
+    delta: DMatrix<f64>,
+    max_rank: usize,
+}
+
+impl IncrementalDcSolver {
+    /// Factor an assembled DC system.
+    pub fn new(mna: MnaSystem) -> Result<Self> {
+        let factor = Factorization::new(&mna)?;
+        Ok(Self {
+            mna,
+            factor,
+            touched: Vec::new(),
+            columns: Vec::new(),
+            delta: DMatrix::zeros(0, 0),
+            max_rank: DEFAULT_MAX_RANK,
+        })
+    }
+
+    /// Set how many unknowns edits may touch before refactoring.
+    pub fn with_max_rank(mut self, max_rank: usize) -> Self {
+        self.max_rank = max_rank;
+        self
+    }
+
+    /// Number of unknowns in the pending low-rank correction.
+    pub fn rank(&self) -> usize {
+        self.touched.len()
+    }
+
+    /// The current system, including every applied edit.
+    pub fn system(&self) -> &MnaSystem {
+        &self.mna
+    }
+
+    /// Add a change to the system, e.g. from
+    /// [`Netlist::set_device_value`](spicier_core::Netlist::set_device_value).
+    ///
+    /// Costs one back-substitution per newly touched unknown; refactors if
+    /// the correction would exceed the maximum rank.
+    pub fn apply(&mut self, delta: &MnaSystem) -> Result<()> {
+        if delta.size() != self.mna.size() {
+            return Err(Error::DimensionMismatch {
+                expected: self.mna.size(),
+                actual: delta.size(),
+            });
+        }
+
+        self.mna.triplets.extend_from_slice(&delta.triplets);
+        self.mna.rhs += &delta.rhs;
+        // Sum duplicates first so entries that cancel (a source's ±1
+        // incidence, say) don't grow the correction.
+        let entries = delta.triplet_pattern().compress(&delta.triplets);
+        for (row, col, value) in entries {
+            if value == 0.0 {
+                continue;
+            }
+            let i = self.touch(row)?;
+            let j = self.touch(col)?;
+            self.delta[(i, j)] += value;
+        }
+
+        if self.touched.len() > self.max_rank {
+            self.refactor()?;
+        }
+        Ok(())
+    }
+
+    /// Fold all applied edits into a fresh factorization.
+    pub fn refactor(&mut self) -> Result<()> {
+        self.mna.compress_triplets();
+        self.factor = Factorization::new(&self.mna)?;
+        self.touched.clear();
+        self.columns.clear();
+        self.delta = DMatrix::zeros(0, 0);
+        Ok(())
+    }
+
+    /// Solve the current system.
+    pub fn solve(&self) -> Result<DcSolution> {
+        let mut x = self.factor.solve(&self.mna.rhs)?;
+
+        let k = self.touched.len();
+        if k > 0 {
+            let z_k = DMatrix::from_fn(k, k, |i, j| self.columns[j][self.touched[i]]);
+            let y_k = DVector::from_fn(k, |i, _| x[self.touched[i]]);
+            let s = DMatrix::identity(k, k) + &self.delta * z_k;
+            let w = s
+                .lu()
+                .solve(&(&self.delta * y_k))
+                .ok_or(Error::SingularMatrix)?;
+            for (column, &wj) in self.columns.iter().zip(w.iter()) {
+                x.axpy(-wj, column, 1.0);
+            }
+        }
+
+        let (num_nodes, num_vsources) = (self.mna.num_nodes, self.mna.num_vsources);
+        Ok(DcSolution {
+            node_voltages: x.rows(0, num_nodes).into_owned(),
+            branch_currents: x.rows(num_nodes, num_vsources).into_owned(),
+            num_nodes,
+        })
+    }
+
+    /// Position of unknown `k` in the correction, adding it if new.
+    fn touch(&mut self, k: usize) -> Result<usize> {
+        if let Some(pos) = self.touched.iter().position(|&t| t == k) {
+            return Ok(pos);
+        }
+
+        let mut unit = DVector::zeros(self.mna.size());
+        unit[k] = 1.0;
+        self.columns.push(self.factor.solve(&unit)?);
+        self.touched.push(k);
+
+        let n = self.touched.len();
+        let delta = std::mem::replace(&mut self.delta, DMatrix::zeros(0, 0));
+        self.delta = delta.resize(n, n, 0.0);
+        Ok(n - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dc::solve_dc;
+    use spicier_core::{Netlist, NodeId};
+    use spicier_devices::passive::Resistor;
+    use spicier_devices::sources::{CurrentSource, VoltageSource};
+
+    /// A resistor ladder driven by V1, with a current source injected at the far end.
+    fn ladder(rungs: u32) -> Netlist {
+        let mut netlist = Netlist::new();
+        netlist.add_device(VoltageSource::new(
+            "V1",
+            NodeId::new(1),
+            NodeId::GROUND,
+            5.0,
+            0,
+        ));
+        for i in 1..=rungs {
+            netlist.add_device(Resistor::new(
+                format!("RS{i}"),
+                NodeId::new(i),
+                NodeId::new(i + 1),
+                100.0,
+            ));
+            netlist.add_device(Resistor::new(
+                format!("RP{i}"),
+                NodeId::new(i + 1),
+                NodeId::GROUND,
+                1e3 * f64::from(i),
+            ));
+        }
+        netlist.add_device(CurrentSource::new(
+            "I1",
+            NodeId::GROUND,
+            NodeId::new(rungs + 1),
+            1e-3,
+        ));
+        for i in 1..=rungs + 1 {
+            netlist.register_node(NodeId::new(i));
+        }
+        netlist
+    }
+
+    fn assert_matches_full_solve(solver: &IncrementalDcSolver, netlist: &Netlist) {
+        let incremental = solver.solve().unwrap();
+        let full = solve_dc(&netlist.assemble_mna()).unwrap();
+        let err = (&incremental.node_voltages - &full.node_voltages).amax();
+        assert!(err < 1e-9, "node voltage mismatch {err:e}");
+        let err = (&incremental.branch_currents - &full.branch_currents).amax();
+        assert!(err < 1e-12, "branch current mismatch {err:e}");
+    }
+
+    #[test]
+    fn test_incremental_edits_match_full_solve() {
+        let mut netlist = ladder(10);
+        let mut solver = IncrementalDcSolver::new(netlist.assemble_mna()).unwrap();
+
+        for (name, value) in [("RS3", 470.0), ("RP7", 22.0), ("V1", 3.3), ("I1", -2e-3)] {
+            let delta = netlist.set_device_value(name, value).unwrap();
+            solver.apply(&delta).unwrap();
+            assert_matches_full_solve(&solver, &netlist);
+        }
+        // Sources only change the RHS; the two resistors touch three unknowns.
+        assert_eq!(solver.rank(), 3);
+
+        // Editing the same resistor again reuses its touched unknowns.
+        let delta = netlist.set_device_value("rs3", 1e3).unwrap();
+        solver.apply(&delta).unwrap();
+        assert_eq!(solver.rank(), 3);
+        assert_matches_full_solve(&solver, &netlist);
+    }
+
+    #[test]
+    fn test_incremental_refactors_past_max_rank() {
+        // Large enough to take the sparse factorization path.
+        let mut netlist = ladder(SPARSE_THRESHOLD as u32);
+        let mut solver = IncrementalDcSolver::new(netlist.assemble_mna())
+            .unwrap()
+            .with_max_rank(4);
+
+        for (i, name) in ["RS5", "RS20", "RP40"].into_iter().enumerate() {
+            let delta = netlist
+                .set_device_value(name, 33.0 * (i + 1) as f64)
+                .unwrap();
+            solver.apply(&delta).unwrap();
+            assert_matches_full_solve(&solver, &netlist);
+        }
+        // The third edit pushed the correction past rank 4 and refactored.
+        assert_eq!(solver.rank(), 0);
+    }
+
+    #[test]
+    fn test_set_device_value_errors() {
+        let mut netlist = ladder(2);
+        assert!(matches!(
+            netlist.set_device_value("R99", 1.0),
+            Err(spicier_core::Error::DeviceNotFound(_))
+        ));
+    }
+}
@@ -6,6 +6,8 @@
 //! - **AC Analysis** - Small-signal frequency response
 //! - **Transient Analysis** - Time-domain simulation
 //! - **Newton-Raphson** - Nonlinear circuit convergence
+//! - **Loop Gain** - Middlebrook injection, phase and gain margins
+//! - **S-Parameters** - Two-port scattering parameters and Touchstone export
 //!
 //! # Analysis Types
 //!
@@ -65,6 +67,9 @@
 //! let mag_db = result.magnitude_db(1);  // Get magnitude in dB at node 1
 //! ```
 //!
+//! For a parsed netlist, [`NetlistAcStamper`] builds the stamper from each
+//! device's small-signal model at the DC operating point.
+//!
 //! ## Transient Analysis
 //!
 //! Time-domain simulation with capacitors and inductors:
@@ -95,12 +100,20 @@
 //!     tstop: 5e-3,  // 5ms
 //!     tstep: 1e-4,  // 100µs
 //!     method: IntegrationMethod::Trapezoidal,
+//!     be_startup_steps: 0,
+//!     breakpoints: Vec::new(),
 //! };
 //!
 //! let result = solve_transient(&RcCircuit, &mut caps, &mut vec![], &params, &dc)
 //!     .expect("transient solve failed");
 //! ```
 //!
+//! ## Whole Netlists
+//!
+//! [`simulate`] parses SPICE text and runs every `.OP`, `.DC`, `.AC` and
+//! `.TRAN` directive in it, returning [`SimulationResults`] addressable by
+//! node name.
+//!
 //! # Solver Selection
 //!
 //! The crate automatically selects between:
@@ -108,45 +121,73 @@
 //! - **Sparse LU** - For medium circuits (100-10000 nodes)
 //! - **GMRES** - For large circuits (> 10000 nodes)
 //!
-//! Use [`DispatchConfig`] to customize solver selection.
+//! [`solve_bicgstab_real`] and [`solve_bicgstab_complex`] are available as
+//! lower-memory alternatives to GMRES for callers driving an iterative solve
+//! directly, and
+//! [`solve_cg_real`] for symmetric positive-definite systems such as purely
+//! resistive networks.
+//!
+//! Use [`DispatchConfig`] to customize solver selection. For interactive
+//! edits to a linear circuit, [`IncrementalDcSolver`] re-solves after each
+//! component change with a low-rank update instead of a refactorization.
 //!
 //! # Convergence Aids
 //!
 //! For difficult nonlinear circuits:
 //! - [`solve_with_source_stepping`] - Gradually ramp sources
 //! - [`solve_with_gmin_stepping`] - Add minimum conductance
+//! - [`solve_with_strategy`] - Try aids in order until one converges
+//! - [`solve_dc_multistart`] - Find multiple DC solutions from random starts
 
 pub mod ac;
 pub mod backend;
 pub mod batched_newton;
+pub mod bicgstab;
+pub mod cg;
 pub mod dc;
 pub mod dispatch;
 pub mod error;
 pub mod gmres;
 pub mod ilu;
+pub mod incremental;
 pub mod linear;
+pub mod loop_gain;
 pub mod measure;
+pub mod netlist_ac;
+pub mod netlist_stampers;
 pub mod newton;
 pub mod noise;
 pub mod operator;
 pub mod parallel;
 pub mod preconditioner;
+pub mod rawfile;
 pub mod sensitivity;
+pub mod setup;
+pub mod simulate;
 pub mod solver_select;
+pub mod sparams;
 pub mod sparse_operator;
 pub mod spectral;
+pub mod state_space;
 pub mod sweep;
 pub mod transient;
 
 pub use ac::{
-    AcParams, AcResult, AcStamper, AcSweepType, ComplexMna, generate_frequencies, solve_ac,
-    solve_ac_dispatched,
+    AcParams, AcResult, AcStamper, AcSweepType, AcTransferResult, ComplexMna, generate_frequencies,
+    solve_ac, solve_ac_dispatched, solve_ac_single_output,
 };
 pub use backend::ComputeBackend;
 pub use batched_newton::{BatchedNonlinearDevices, LinearStamper, solve_batched_newton_raphson};
+pub use bicgstab::{
+    BicgstabConfig, BicgstabResult, ComplexBicgstabResult, solve_bicgstab_complex,
+    solve_bicgstab_complex_preconditioned, solve_bicgstab_real,
+};
+pub use cg::{CgConfig, CgResult, solve_cg_real, solve_cg_real_preconditioned};
 pub use dc::{
-    DcSolution, DcSweepParams, DcSweepResult, DcSweepStamper, solve_dc, solve_dc_dispatched,
-    solve_dc_sweep, solve_dc_sweep_dispatched,
+    DcSolution, DcSweepParams, DcSweepResult, DcSweepStamper, NestedDcSweepResult,
+    NonlinearNestedSweepStamper, NonlinearSweepStamper, PowerBalance, solve_dc,
+    solve_dc_dispatched, solve_dc_nested_sweep_nonlinear, solve_dc_sweep,
+    solve_dc_sweep_dispatched, solve_dc_sweep_nonlinear, solve_dc_temp_sweep,
 };
 pub use dispatch::{
     DispatchConfig, DispatchedSolveInfo, GpuBatchConfig, IluConfig, PreconditionerType,
@@ -154,24 +195,35 @@ pub use dispatch::{
 };
 pub use error::{Error, Result};
 pub use gmres::{
-    GmresConfig, GmresResult, RealGmresResult, solve_gmres, solve_gmres_preconditioned,
-    solve_gmres_real, solve_gmres_real_preconditioned,
+    GmresConfig, GmresResult, RealGmresResult, solve_fgmres_real, solve_gmres,
+    solve_gmres_preconditioned, solve_gmres_preconditioned_with_guess, solve_gmres_real,
+    solve_gmres_real_preconditioned, solve_gmres_real_preconditioned_with_guess,
+    solve_gmres_real_with_guess, solve_gmres_with_guess,
 };
 pub use ilu::{ComplexIlu0Preconditioner, Ilu0Preconditioner, IluError};
+pub use incremental::IncrementalDcSolver;
 #[cfg(all(target_os = "macos", feature = "accelerate"))]
 pub use linear::{CachedDenseLu, CachedDenseLuComplex};
-pub use linear::{CachedSparseLu, CachedSparseLuComplex};
+pub use linear::{CachedSparseLu, CachedSparseLuComplex, Precision};
+pub use loop_gain::{InjectionPoint, LoopGainCrossing, LoopGainResult, solve_loop_gain};
 pub use measure::{MeasureError, MeasureEvaluator, MeasureResult};
+pub use netlist_ac::NetlistAcStamper;
+pub use netlist_stampers::{
+    NestedSweepStamper, NetlistNonlinearStamper, NetlistNonlinearSweepStamper, NetlistSweepStamper,
+    NetlistTransientStamper, build_transient_state,
+};
 pub use newton::{
-    ConvergenceCriteria, GminSteppingParams, GminSteppingResult, NonlinearStamper, NrResult,
-    ScaledNonlinearStamper, SourceSteppingParams, SourceSteppingResult, solve_newton_raphson,
-    solve_with_gmin_stepping, solve_with_source_stepping,
+    ConvergenceAid, ConvergenceCriteria, DcSolverStrategy, GminSteppingParams, GminSteppingResult,
+    MultistartParams, MultistartResult, MultistartSolution, NonlinearStamper, NrResult,
+    ScaledNonlinearStamper, SourceSteppingParams, SourceSteppingResult, StrategyResult,
+    solve_dc_multistart, solve_newton_raphson, solve_with_gmin_stepping,
+    solve_with_source_stepping, solve_with_strategy,
 };
 pub use noise::{
     NoiseConfig, NoiseContribution, NoiseResult, NoiseSource, NoiseSourceType, NoiseStamper,
     NoiseSweepType, compute_noise,
 };
-pub use operator::{ComplexOperator, RealOperator};
+pub use operator::{ComplexOperator, DenseComplexOperator, DenseRealOperator, RealOperator};
 pub use parallel::{
     ParallelTripletAccumulator, parallel_ranges, stamp_conductance_triplets,
     stamp_current_source_rhs,
@@ -180,24 +232,33 @@ pub use preconditioner::{
     ComplexJacobiPreconditioner, ComplexPreconditioner, IdentityPreconditioner,
     JacobiPreconditioner, RealPreconditioner,
 };
+pub use rawfile::{RawEncoding, RawValues, RawVariable, write_raw, write_transient_raw};
 pub use sensitivity::{
     AcSensitivityResult, AcSensitivityStamper, DcSensitivityResult, DcSensitivityStamper,
-    SensitivityConfig, SensitivityOutput, SensitivityParam, compute_ac_sensitivity,
-    compute_ac_sensitivity_sweep, compute_dc_sensitivity,
+    ElementSensitivity, SensitivityConfig, SensitivityOutput, SensitivityParam, SensitivityResult,
+    compute_ac_sensitivity, compute_ac_sensitivity_sweep, compute_dc_sensitivity,
+    solve_dc_sensitivity,
 };
+pub use setup::{SimulationAnalysis, SimulationSetup};
+pub use simulate::{AnalysisContext, AnalysisResult, SimulationResults, simulate};
 pub use solver_select::{SolveResult, SolverConfig, SolverStrategy, solve_auto};
+pub use sparams::{
+    DEFAULT_REFERENCE_IMPEDANCE, SParamPoint, SParamPort, SParamResult, TwoPort, solve_sparameters,
+};
 pub use sparse_operator::{SparseComplexOperator, SparseRealOperator};
 pub use spectral::{
     HarmonicInfo, SpectralConfig, SpectralResult, ThdResult, WindowFunction, compute_fft,
     compute_fft_from_samples, compute_thd, compute_thd_from_samples, resample_uniform,
 };
+pub use state_space::{StateSpace, StateSpaceInput, StateSpaceOutput, solve_state_space};
 pub use sweep::{
-    BatchedSweepResult, CornerGenerator, LinearSweepGenerator, MonteCarloGenerator,
-    ParameterVariation, SweepPoint, SweepPointGenerator, SweepStamper, SweepStamperFactory,
-    SweepStatistics, solve_batched_sweep,
+    AcSweepStamperFactory, BatchedAcResult, BatchedSweepResult, CornerGenerator,
+    LinearSweepGenerator, MonteCarloGenerator, ParameterVariation, SweepPoint, SweepPointGenerator,
+    SweepStamper, SweepStamperFactory, SweepStatistics, solve_batched_ac, solve_batched_sweep,
 };
 pub use transient::{
     AdaptiveTransientParams, AdaptiveTransientResult, CapacitorState, InductorState,
-    InitialConditions, IntegrationMethod, TransientParams, TransientResult, TransientStamper,
-    solve_transient, solve_transient_adaptive, solve_transient_dispatched,
+    InitialConditions, IntegrationMethod, MaxStepWindow, TransientParams, TransientResult,
+    TransientStamper, TransmissionLineState, solve_transient, solve_transient_adaptive,
+    solve_transient_dispatched, solve_transient_probed, solve_transient_with_progress,
 };
@@ -334,6 +334,24 @@ impl CachedDenseLuComplex {
 pub struct CachedSparseLu {
     symbolic: SymbolicLu<usize>,
     size: usize,
+    precision: Precision,
+    refinement_steps: usize,
+}
+
+/// Floating-point precision of the numeric factorization on the CPU path.
+///
+/// [`Precision::Single`] halves the memory traffic of the factorization and
+/// triangular solves, which dominates for very large, well-conditioned
+/// networks. Inputs and results stay `f64`; pair it with iterative
+/// refinement ([`CachedSparseLu::with_refinement_steps`]) to recover
+/// accuracy close to a double-precision solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Precision {
+    /// Factor and solve in `f64`.
+    #[default]
+    Double,
+    /// Factor and solve in `f32`, converting at the boundaries.
+    Single,
 }
 
 impl CachedSparseLu {
@@ -355,7 +373,7 @@ impl CachedSparseLu {
         let symbolic = SymbolicLu::try_new(sparse_mat.symbolic())
             .map_err(|e| Error::SolverError(format!("Symbolic factorization failed: {:?}", e)))?;
 
-        Ok(Self { symbolic, size })
+        Ok(Self::with_symbolic(symbolic, size))
     }
 
     /// Create from an existing symbolic sparse matrix structure.
@@ -364,7 +382,37 @@ impl CachedSparseLu {
         let symbolic = SymbolicLu::try_new(symbolic_mat.as_ref())
             .map_err(|e| Error::SolverError(format!("Symbolic factorization failed: {:?}", e)))?;
 
-        Ok(Self { symbolic, size })
+        Ok(Self::with_symbolic(symbolic, size))
+    }
+
+    fn with_symbolic(symbolic: SymbolicLu<usize>, size: usize) -> Self {
+        Self {
+            symbolic,
+            size,
+            precision: Precision::Double,
+            refinement_steps: 0,
+        }
+    }
+
+    /// Set the precision of the numeric factorization (default `f64`).
+    pub fn with_precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set the number of iterative refinement steps after each solve.
+    ///
+    /// Each step computes the residual `b - Ax` in `f64` and solves for a
+    /// correction with the existing factors. Only useful with
+    /// [`Precision::Single`]; defaults to 0.
+    pub fn with_refinement_steps(mut self, steps: usize) -> Self {
+        self.refinement_steps = steps;
+        self
+    }
+
+    /// Precision of the numeric factorization.
+    pub fn precision(&self) -> Precision {
+        self.precision
     }
 
     /// Solve Ax = b using the cached symbolic factorization.
@@ -382,25 +430,60 @@ impl CachedSparseLu {
             });
         }
 
-        // Build sparse matrix with new values
+        let x = match self.precision {
+            Precision::Double => {
+                let lu = self.factor::<f64>(triplets, |v| v)?;
+                self.solve_refined(triplets, rhs, |r| {
+                    let x = lu.solve(&Col::<f64>::from_fn(self.size, |i| r[i]));
+                    DVector::from_fn(self.size, |i, _| x[i])
+                })
+            }
+            Precision::Single => {
+                let lu = self.factor::<f32>(triplets, |v| v as f32)?;
+                self.solve_refined(triplets, rhs, |r| {
+                    let x = lu.solve(&Col::<f32>::from_fn(self.size, |i| r[i] as f32));
+                    DVector::from_fn(self.size, |i, _| f64::from(x[i]))
+                })
+            }
+        };
+        Ok(x)
+    }
+
+    /// Numeric factorization using the cached symbolic structure.
+    fn factor<T: faer::traits::ComplexField>(
+        &self,
+        triplets: &[(usize, usize, f64)],
+        convert: impl Fn(f64) -> T,
+    ) -> Result<Lu<usize, T>> {
         let faer_triplets: Vec<_> = triplets
             .iter()
-            .map(|&(r, c, v)| Triplet::new(r, c, v))
+            .map(|&(r, c, v)| Triplet::new(r, c, convert(v)))
             .collect();
 
         let sparse_mat =
-            SparseColMat::<usize, f64>::try_new_from_triplets(self.size, self.size, &faer_triplets)
+            SparseColMat::<usize, T>::try_new_from_triplets(self.size, self.size, &faer_triplets)
                 .map_err(|_| Error::SingularMatrix)?;
 
-        // Numeric factorization using cached symbolic
-        let lu = Lu::try_new_with_symbolic(self.symbolic.clone(), sparse_mat.as_ref())
-            .map_err(|_| Error::SingularMatrix)?;
-
-        // Convert RHS and solve
-        let faer_rhs = Col::<f64>::from_fn(self.size, |i| rhs[i]);
-        let faer_x = lu.solve(&faer_rhs);
+        Lu::try_new_with_symbolic(self.symbolic.clone(), sparse_mat.as_ref())
+            .map_err(|_| Error::SingularMatrix)
+    }
 
-        Ok(DVector::from_fn(self.size, |i, _| faer_x[i]))
+    /// Solve with `solve_lu`, then apply the configured refinement steps.
+    fn solve_refined(
+        &self,
+        triplets: &[(usize, usize, f64)],
+        rhs: &DVector<f64>,
+        solve_lu: impl Fn(&DVector<f64>) -> DVector<f64>,
+    ) -> DVector<f64> {
+        let mut x = solve_lu(rhs);
+        for _ in 0..self.refinement_steps {
+            let mut residual = rhs.clone();
+            for &(r, c, v) in triplets {
+                residual[r] -= v * x[c];
+            }
+            x += solve_lu(&residual);
+        }
+        x
     }
 
     /// Get the system size.
@@ -445,10 +528,21 @@ impl CachedSparseLuComplex {
         triplets: &[(usize, usize, Complex<f64>)],
         rhs: &DVector<Complex<f64>>,
     ) -> Result<DVector<Complex<f64>>> {
-        if self.size != rhs.len() {
+        let mut solutions = self.solve_multiple(triplets, std::slice::from_ref(rhs))?;
+        Ok(solutions.remove(0))
+    }
+
+    /// Solve Ax = b for several right-hand sides with one numeric
+    /// factorization of A.
+    pub fn solve_multiple(
+        &self,
+        triplets: &[(usize, usize, Complex<f64>)],
+        rhs: &[DVector<Complex<f64>>],
+    ) -> Result<Vec<DVector<Complex<f64>>>> {
+        if let Some(b) = rhs.iter().find(|b| b.len() != self.size) {
             return Err(Error::DimensionMismatch {
                 expected: self.size,
-                actual: rhs.len(),
+                actual: b.len(),
             });
         }
 
@@ -464,12 +558,14 @@ impl CachedSparseLuComplex {
         let lu = Lu::try_new_with_symbolic(self.symbolic.clone(), sparse_mat.as_ref())
             .map_err(|_| Error::SingularMatrix)?;
 
-        let faer_rhs = Col::<c64>::from_fn(self.size, |i| c64::new(rhs[i].re, rhs[i].im));
-        let faer_x = lu.solve(&faer_rhs);
-
-        Ok(DVector::from_fn(self.size, |i, _| {
-            Complex::new(faer_x[i].re, faer_x[i].im)
-        }))
+        Ok(rhs
+            .iter()
+            .map(|b| {
+                let faer_rhs = Col::<c64>::from_fn(self.size, |i| c64::new(b[i].re, b[i].im));
+                let faer_x = lu.solve(&faer_rhs);
+                DVector::from_fn(self.size, |i, _| Complex::new(faer_x[i].re, faer_x[i].im))
+            })
+            .collect())
     }
 
     /// Get the system size.
@@ -899,6 +995,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cached_sparse_lu_single_precision_refinement() {
+        // Diagonally dominant resistor-like ladder: well conditioned.
+        let size = 200;
+        let mut triplets = Vec::new();
+        for i in 0..size {
+            triplets.push((i, i, 4.0 + 0.01 * i as f64));
+            if i + 1 < size {
+                triplets.push((i, i + 1, -1.0));
+                triplets.push((i + 1, i, -1.0));
+            }
+        }
+        let b = DVector::from_fn(size, |i, _| ((i * 7) % 11) as f64 / 3.0);
+
+        let double = CachedSparseLu::new(size, &triplets).unwrap();
+        assert_eq!(double.precision(), Precision::Double);
+        let x_double = double.solve(&triplets, &b).unwrap();
+
+        let single = CachedSparseLu::new(size, &triplets)
+            .unwrap()
+            .with_precision(Precision::Single);
+        let err_single = (single.solve(&triplets, &b).unwrap() - &x_double).amax();
+
+        let refined = single.with_refinement_steps(2);
+        let err_refined = (refined.solve(&triplets, &b).unwrap() - &x_double).amax();
+
+        assert!(err_refined < 1e-6, "refined error {err_refined:e}");
+        assert!(
+            err_refined < err_single,
+            "refinement should improve on plain f32: {err_refined:e} vs {err_single:e}"
+        );
+    }
+
     #[test]
     fn test_cached_sparse_lu_matches_uncached() {
         // Build a 20x20 system and verify cached == uncached
@@ -997,6 +1126,38 @@ mod tests {
         assert!((ax1 - b2[1]).norm() < 1e-10, "Ax2[1] mismatch");
     }
 
+    #[test]
+    fn test_cached_sparse_lu_complex_multiple_rhs() {
+        let triplets = vec![
+            (0, 0, Complex::new(2.0, 1.0)),
+            (0, 1, Complex::new(1.0, 0.0)),
+            (1, 0, Complex::new(1.0, 0.0)),
+            (1, 1, Complex::new(3.0, -1.0)),
+        ];
+        let rhs = [
+            dvector![Complex::new(5.0, 1.0), Complex::new(6.0, 0.0)],
+            dvector![Complex::new(0.0, 1.0), Complex::new(-2.0, 0.5)],
+        ];
+
+        let cached = CachedSparseLuComplex::new(2, &triplets).unwrap();
+        let solutions = cached.solve_multiple(&triplets, &rhs).unwrap();
+        assert_eq!(solutions.len(), 2);
+        for (x, b) in solutions.iter().zip(&rhs) {
+            let single = cached.solve(&triplets, b).unwrap();
+            assert!((x - single).norm() < 1e-12);
+        }
+
+        // Every right-hand side is checked against the system size
+        let short = [rhs[0].clone(), dvector![Complex::new(1.0, 0.0)]];
+        assert!(matches!(
+            cached.solve_multiple(&triplets, &short),
+            Err(Error::DimensionMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
     // ========================================================================
     // Cached Dense LU Tests (Accelerate)
     // ========================================================================
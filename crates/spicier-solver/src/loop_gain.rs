@@ -0,0 +1,528 @@
+//! Loop-gain analysis by Middlebrook double injection.
+//!
+//! The loop is broken at a zero-volt voltage source placed in series with
+//! the feedback path, between node `x` (driving the forward path) and node
+//! `y` (returned from the loop). Two AC solves per frequency, with every
+//! other source switched off, give
+//!
+//! ```text
+//! voltage injection (vz at the source):  Tv = -vy / vx
+//! current injection (iz into x):         Ti =  iy / ix
+//! T = (Tv Ti - 1) / (Tv + Ti + 2)
+//! ```
+//!
+//! where `ix` and `iy` are the currents leaving the break into each side.
+//! The combination is exact for any impedances on either side of the break
+//! (Middlebrook 1975, Tian et al. 2001), so the source does not have to sit
+//! between a low-impedance output and a high-impedance input.
+//!
+//! The sweep brackets the unity-gain and -180° crossings; each is then
+//! refined by bisection on frequency with fresh solves, so the margins do
+//! not depend on the sweep density.
+
+use std::f64::consts::PI;
+
+use nalgebra::DVector;
+use num_complex::Complex;
+
+use crate::ac::{AcParams, AcStamper, ComplexMna, generate_frequencies};
+use crate::error::{Error, Result};
+use crate::linear::{CachedSparseLuComplex, SPARSE_THRESHOLD, solve_complex};
+
+/// Relative frequency tolerance for crossover bisection.
+const BISECTION_RTOL: f64 = 1e-9;
+
+/// Bisection iteration cap.
+const BISECTION_MAX_ITER: usize = 100;
+
+/// Where the loop is broken: a zero-volt voltage source in series with the loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InjectionPoint {
+    /// Node on the forward-path side, the source's positive terminal (0-based).
+    pub node_x: usize,
+    /// Node on the return side, the source's negative terminal (0-based).
+    pub node_y: usize,
+    /// Branch index of the injection source (0-based).
+    pub branch_idx: usize,
+}
+
+/// A crossing of the loop gain, refined by bisection.
+#[derive(Debug, Clone, Copy)]
+pub struct LoopGainCrossing {
+    /// Frequency of the crossing (Hz).
+    pub frequency: f64,
+    /// Loop gain at the crossing.
+    pub gain: Complex<f64>,
+    /// Unwrapped phase of the loop gain at the crossing (degrees).
+    pub phase_deg: f64,
+}
+
+/// Result of [`solve_loop_gain`].
+#[derive(Debug, Clone)]
+pub struct LoopGainResult {
+    /// `(frequency, T(jω))` at each sweep frequency.
+    pub points: Vec<(f64, Complex<f64>)>,
+    /// First point where |T| falls through 1, if the sweep brackets one.
+    pub crossover: Option<LoopGainCrossing>,
+    /// First point where the phase falls through -180°, if the sweep brackets one.
+    pub phase_crossover: Option<LoopGainCrossing>,
+}
+
+impl LoopGainResult {
+    /// Loop-gain magnitude in dB across all frequencies.
+    pub fn magnitude_db(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|&(f, t)| (f, 20.0 * t.norm().log10()))
+            .collect()
+    }
+
+    /// Loop-gain phase in degrees, unwrapped from the first frequency.
+    pub fn phase_deg(&self) -> Vec<(f64, f64)> {
+        let phases = unwrapped_phases(self.points.iter().map(|&(_, t)| t));
+        self.points.iter().map(|&(f, _)| f).zip(phases).collect()
+    }
+
+    /// Get all frequency values.
+    pub fn frequencies(&self) -> Vec<f64> {
+        self.points.iter().map(|&(f, _)| f).collect()
+    }
+
+    /// Unity-gain (crossover) frequency in Hz.
+    pub fn crossover_frequency(&self) -> Option<f64> {
+        self.crossover.map(|c| c.frequency)
+    }
+
+    /// Phase margin in degrees: 180° plus the loop phase at crossover.
+    pub fn phase_margin(&self) -> Option<f64> {
+        self.crossover.map(|c| 180.0 + c.phase_deg)
+    }
+
+    /// Frequency in Hz where the loop phase reaches -180°.
+    pub fn phase_crossover_frequency(&self) -> Option<f64> {
+        self.phase_crossover.map(|c| c.frequency)
+    }
+
+    /// Gain margin in dB: how far |T| is below 1 where the phase reaches -180°.
+    pub fn gain_margin(&self) -> Option<f64> {
+        self.phase_crossover.map(|c| -20.0 * c.gain.norm().log10())
+    }
+}
+
+/// Compute the loop gain T(jω) of a feedback loop broken at `injection`.
+///
+/// `stamper` is the small-signal circuit at its operating point, including
+/// the zero-volt injection source. Whatever right-hand side it stamps is
+/// discarded: the two injections are the only excitations. The sign is
+/// chosen so that T is positive at DC for negative feedback, making the
+/// closed-loop sensitivity 1/(1 + T).
+pub fn solve_loop_gain(
+    stamper: &dyn AcStamper,
+    injection: InjectionPoint,
+    params: &AcParams,
+) -> Result<LoopGainResult> {
+    let num_nodes = stamper.num_nodes();
+    let num_vsources = stamper.num_vsources();
+    for node in [injection.node_x, injection.node_y] {
+        if node >= num_nodes {
+            return Err(Error::IndexOutOfRange {
+                what: "injection node",
+                index: node,
+                len: num_nodes,
+            });
+        }
+    }
+    if injection.node_x == injection.node_y {
+        return Err(Error::SolverError(
+            "injection source must connect two different nodes".into(),
+        ));
+    }
+    if injection.branch_idx >= num_vsources {
+        return Err(Error::IndexOutOfRange {
+            what: "injection branch",
+            index: injection.branch_idx,
+            len: num_vsources,
+        });
+    }
+
+    let mut solver = LoopGainSolver {
+        stamper,
+        injection,
+        cached: None,
+    };
+
+    let mut points = Vec::new();
+    for freq in generate_frequencies(params) {
+        points.push((freq, solver.loop_gain(freq)?));
+    }
+
+    let phases = unwrapped_phases(points.iter().map(|&(_, t)| t));
+    let bracket = |i: usize| ((points[i].0, points[i].1, phases[i]), points[i + 1].0);
+
+    let crossover = match points
+        .windows(2)
+        .position(|w| w[0].1.norm() >= 1.0 && w[1].1.norm() < 1.0)
+    {
+        Some(i) => {
+            let (lo, hi) = bracket(i);
+            Some(solver.bisect(lo, hi, |t, _| t.norm() >= 1.0)?)
+        }
+        None => None,
+    };
+
+    let phase_crossover = match phases
+        .windows(2)
+        .position(|w| w[0] > -180.0 && w[1] <= -180.0)
+    {
+        Some(i) => {
+            let (lo, hi) = bracket(i);
+            Some(solver.bisect(lo, hi, |_, phase| phase > -180.0)?)
+        }
+        None => None,
+    };
+
+    Ok(LoopGainResult {
+        points,
+        crossover,
+        phase_crossover,
+    })
+}
+
+/// Solves the two injections at one frequency at a time.
+struct LoopGainSolver<'a> {
+    stamper: &'a dyn AcStamper,
+    injection: InjectionPoint,
+    cached: Option<CachedSparseLuComplex>,
+}
+
+impl LoopGainSolver<'_> {
+    /// T(jω) at `freq` from a voltage and a current injection.
+    fn loop_gain(&mut self, freq: f64) -> Result<Complex<f64>> {
+        let num_nodes = self.stamper.num_nodes();
+        let mut mna = ComplexMna::new(num_nodes, self.stamper.num_vsources());
+        self.stamper.stamp_ac(&mut mna, 2.0 * PI * freq);
+
+        let InjectionPoint {
+            node_x,
+            node_y,
+            branch_idx,
+        } = self.injection;
+        let one = Complex::new(1.0, 0.0);
+
+        let mut rhs = DVector::zeros(mna.size());
+        rhs[num_nodes + branch_idx] = one;
+        let v = self.solve(&mna, &rhs)?;
+        let (vx, vy) = (v[node_x], v[node_y]);
+
+        rhs.fill(Complex::new(0.0, 0.0));
+        rhs[node_x] = one;
+        let i = self.solve(&mna, &rhs)?;
+        // The branch current flows from x through the source into y.
+        let iy = i[num_nodes + branch_idx];
+        let ix = one - iy;
+
+        // (Tv Ti - 1) / (Tv + Ti + 2) with Tv = -vy/vx and Ti = iy/ix,
+        // cleared of fractions so an ideal side (ix = 0 or vx = 0) stays finite.
+        Ok(-(vy * iy + vx * ix) / (vx * iy - vy * ix + 2.0 * vx * ix))
+    }
+
+    /// Narrow `[lo, hi]` to where `before` stops holding.
+    ///
+    /// `lo` is `(frequency, T, unwrapped phase)` at a frequency where
+    /// `before` holds; it must not hold at `hi`. Phases at new points are
+    /// unwrapped against `lo`.
+    fn bisect(
+        &mut self,
+        mut lo: (f64, Complex<f64>, f64),
+        mut hi: f64,
+        before: impl Fn(Complex<f64>, f64) -> bool,
+    ) -> Result<LoopGainCrossing> {
+        for _ in 0..BISECTION_MAX_ITER {
+            if hi - lo.0 <= BISECTION_RTOL * hi {
+                break;
+            }
+            let mid = midpoint(lo.0, hi);
+            let t = self.loop_gain(mid)?;
+            let phase = lo.2 + wrap_deg((t.arg() - lo.1.arg()).to_degrees());
+            if before(t, phase) {
+                lo = (mid, t, phase);
+            } else {
+                hi = mid;
+            }
+        }
+
+        let frequency = midpoint(lo.0, hi);
+        let gain = self.loop_gain(frequency)?;
+        Ok(LoopGainCrossing {
+            frequency,
+            gain,
+            phase_deg: lo.2 + wrap_deg((gain.arg() - lo.1.arg()).to_degrees()),
+        })
+    }
+
+    fn solve(
+        &mut self,
+        mna: &ComplexMna,
+        rhs: &DVector<Complex<f64>>,
+    ) -> Result<DVector<Complex<f64>>> {
+        if mna.size() >= SPARSE_THRESHOLD {
+            let solver = match &self.cached {
+                Some(s) => s,
+                None => {
+                    self.cached = Some(CachedSparseLuComplex::new(mna.size(), &mna.triplets)?);
+                    self.cached.as_ref().unwrap()
+                }
+            };
+            solver.solve(&mna.triplets, rhs)
+        } else {
+            solve_complex(&mna.to_dense_matrix(), rhs)
+        }
+    }
+}
+
+/// Bisection midpoint: geometric for positive frequencies, so log sweeps
+/// converge in a predictable number of steps.
+fn midpoint(lo: f64, hi: f64) -> f64 {
+    if lo > 0.0 {
+        (lo * hi).sqrt()
+    } else {
+        0.5 * (lo + hi)
+    }
+}
+
+/// Wrap an angle in degrees to (-180, 180].
+fn wrap_deg(angle: f64) -> f64 {
+    angle - 360.0 * ((angle - 180.0) / 360.0).ceil()
+}
+
+/// Phases in degrees, unwrapped so consecutive values differ by at most 180°.
+fn unwrapped_phases(values: impl Iterator<Item = Complex<f64>>) -> Vec<f64> {
+    let mut phases: Vec<f64> = Vec::new();
+    for t in values {
+        let phase = t.arg().to_degrees();
+        phases.push(match phases.last() {
+            Some(&prev) => prev + wrap_deg(phase - prev),
+            None => phase,
+        });
+    }
+    phases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ac::AcSweepType;
+
+    /// A loop of inverting transconductance stages, each loaded by R ∥ C.
+    ///
+    /// Node 0 is `x`, nodes `1..=stages` are the stage outputs. With `r_out`
+    /// the last stage drives `y` (one node further) through a series
+    /// resistor; `r_in` loads `x` to ground. The injection source is
+    /// branch 0, from `x` to `y`.
+    struct StageLoop {
+        stages: usize,
+        gm: f64,
+        r: f64,
+        c: f64,
+        r_in: Option<f64>,
+        r_out: Option<f64>,
+    }
+
+    impl StageLoop {
+        fn node_y(&self) -> usize {
+            self.stages + usize::from(self.r_out.is_some())
+        }
+
+        fn injection(&self) -> InjectionPoint {
+            InjectionPoint {
+                node_x: 0,
+                node_y: self.node_y(),
+                branch_idx: 0,
+            }
+        }
+
+        /// Stage output impedance R / (1 + jωRC).
+        fn z_stage(&self, omega: f64) -> Complex<f64> {
+            Complex::new(self.r, 0.0) / Complex::new(1.0, omega * self.r * self.c)
+        }
+    }
+
+    impl AcStamper for StageLoop {
+        fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+            for stage in 1..=self.stages {
+                // gm·V(in) is drawn out of the stage output.
+                mna.stamp_vccs(Some(stage), None, Some(stage - 1), None, self.gm);
+                mna.stamp_conductance(Some(stage), None, 1.0 / self.r);
+                mna.stamp_admittance(Some(stage), None, Complex::new(0.0, omega * self.c));
+            }
+            if let Some(r_out) = self.r_out {
+                mna.stamp_conductance(Some(self.stages), Some(self.node_y()), 1.0 / r_out);
+            }
+            if let Some(r_in) = self.r_in {
+                mna.stamp_conductance(Some(0), None, 1.0 / r_in);
+            }
+            mna.stamp_voltage_source(Some(0), Some(self.node_y()), 0, Complex::new(0.0, 0.0));
+        }
+
+        fn num_nodes(&self) -> usize {
+            self.node_y() + 1
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    fn sweep(fstart: f64, fstop: f64) -> AcParams {
+        AcParams {
+            sweep_type: AcSweepType::Decade,
+            num_points: 10,
+            fstart,
+            fstop,
+        }
+    }
+
+    #[test]
+    fn test_one_pole_loop_phase_margin() {
+        // T = gm·R / (1 + jωRC): A0 = 1000, pole at 1 kHz.
+        let r = 10e3;
+        let c = 1.0 / (2.0 * PI * 1e3 * r);
+        let a0: f64 = 1000.0;
+        let circuit = StageLoop {
+            stages: 1,
+            gm: a0 / r,
+            r,
+            c,
+            r_in: None,
+            r_out: None,
+        };
+
+        let result = solve_loop_gain(&circuit, circuit.injection(), &sweep(10.0, 1e8)).unwrap();
+
+        let (f0, t0) = result.points[0];
+        let expected = circuit.z_stage(2.0 * PI * f0) * circuit.gm;
+        assert!((t0 - expected).norm() < 1e-9 * expected.norm());
+
+        let fc = 1e3 * (a0 * a0 - 1.0).sqrt();
+        let crossover = result.crossover_frequency().unwrap();
+        assert!((crossover - fc).abs() < 1e-6 * fc, "crossover {crossover}");
+
+        let pm = result.phase_margin().unwrap();
+        let expected_pm = 180.0 - (a0 * a0 - 1.0).sqrt().atan().to_degrees();
+        assert!((pm - expected_pm).abs() < 1e-6, "phase margin {pm}");
+        assert!((pm - 90.0).abs() < 0.1);
+
+        // A single pole never reaches -180°.
+        assert!(result.gain_margin().is_none());
+    }
+
+    #[test]
+    fn test_loop_gain_exact_with_loaded_break() {
+        // The break sits between two comparable impedances, where voltage
+        // injection alone would overestimate T by 1 + Zy/Zx.
+        let circuit = StageLoop {
+            stages: 1,
+            gm: 0.1,
+            r: 10e3,
+            c: 1e-9,
+            r_in: Some(5e3),
+            r_out: Some(2e3),
+        };
+
+        let result = solve_loop_gain(&circuit, circuit.injection(), &sweep(100.0, 1e7)).unwrap();
+
+        for &(f, t) in &result.points {
+            let omega = 2.0 * PI * f;
+            let z = circuit.z_stage(omega);
+            let (r_in, r_out) = (5e3, 2e3);
+            let expected = circuit.gm * z * r_in / (z + r_in + r_out);
+            assert!(
+                (t - expected).norm() < 1e-9 * expected.norm(),
+                "f = {f}: {t} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_three_pole_loop_gain_margin() {
+        // T = A / (1 + jω/ωp)³ with A = 4: the phase reaches -180° at
+        // √3·fp, where |T| = A / 8.
+        let r = 1e3;
+        let fp = 1e4;
+        let a: f64 = 4.0;
+        let circuit = StageLoop {
+            stages: 3,
+            gm: a.cbrt() / r,
+            r,
+            c: 1.0 / (2.0 * PI * fp * r),
+            r_in: None,
+            r_out: None,
+        };
+
+        let result = solve_loop_gain(&circuit, circuit.injection(), &sweep(100.0, 1e7)).unwrap();
+
+        let f180 = result.phase_crossover_frequency().unwrap();
+        assert!((f180 - 3f64.sqrt() * fp).abs() < 1e-6 * fp, "f180 {f180}");
+        let gm = result.gain_margin().unwrap();
+        let expected_gm = 20.0 * (8.0 / a).log10();
+        assert!((gm - expected_gm).abs() < 1e-6, "gain margin {gm}");
+
+        let wc = (a.powf(2.0 / 3.0) - 1.0).sqrt();
+        let pm = result.phase_margin().unwrap();
+        let expected_pm = 180.0 - 3.0 * wc.atan().to_degrees();
+        assert!((pm - expected_pm).abs() < 1e-6, "phase margin {pm}");
+
+        // The unwrapped phase keeps falling past -180° towards -270°.
+        let (_, last) = *result.phase_deg().last().unwrap();
+        assert!(last < -260.0 && last > -270.0, "final phase {last}");
+    }
+
+    #[test]
+    fn test_injection_point_validation() {
+        let circuit = StageLoop {
+            stages: 1,
+            gm: 1e-3,
+            r: 1e3,
+            c: 1e-9,
+            r_in: None,
+            r_out: None,
+        };
+        let params = sweep(1e3, 1e4);
+        let bad = [
+            InjectionPoint {
+                node_x: 0,
+                node_y: 2,
+                branch_idx: 0,
+            },
+            InjectionPoint {
+                node_x: 1,
+                node_y: 1,
+                branch_idx: 0,
+            },
+            InjectionPoint {
+                node_x: 0,
+                node_y: 1,
+                branch_idx: 1,
+            },
+        ];
+        for injection in bad {
+            assert!(solve_loop_gain(&circuit, injection, &params).is_err());
+        }
+        assert!(matches!(
+            solve_loop_gain(&circuit, bad[0], &params),
+            Err(Error::IndexOutOfRange {
+                what: "injection node",
+                index: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            solve_loop_gain(&circuit, bad[2], &params),
+            Err(Error::IndexOutOfRange {
+                what: "injection branch",
+                index: 1,
+                ..
+            })
+        ));
+    }
+}
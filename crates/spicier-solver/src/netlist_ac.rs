@@ -0,0 +1,473 @@
+//! Small-signal AC stamping for a parsed netlist.
+//!
+//! [`NetlistAcStamper`] asks every device for its [`AcDeviceInfo`] once,
+//! linearized at a DC operating point, and splits the result into a
+//! frequency-independent part and a part proportional to `jω`. Each
+//! frequency point then only scales the reactive entries instead of walking
+//! the device list again.
+//!
+//! ```
+//! use spicier_solver::{AcParams, AcSweepType, NetlistAcStamper, solve_ac};
+//!
+//! let netlist = spicier_parser::parse(
+//!     "RC lowpass\nV1 1 0 DC 0 AC 1\nR1 1 2 1k\nC1 2 0 159.155n\n.end\n",
+//! )
+//! .unwrap();
+//!
+//! let stamper = NetlistAcStamper::new(&netlist, None);
+//! let params = AcParams {
+//!     fstart: 1e3,
+//!     fstop: 1e3,
+//!     num_points: 1,
+//!     sweep_type: AcSweepType::Linear,
+//! };
+//! let result = solve_ac(&stamper, &params).unwrap();
+//! assert!((result.magnitude_db(1)[0].1 + 3.0103).abs() < 1e-3);
+//! ```
+
+use nalgebra::DVector;
+use num_complex::Complex;
+use spicier_core::Netlist;
+use spicier_core::netlist::AcDeviceInfo;
+
+use crate::ac::{AcStamper, ComplexMna};
+
+/// [`AcStamper`] for a [`Netlist`], linearized at a DC operating point.
+///
+/// Built once from the devices' small-signal models; reusable across
+/// frequency sweeps and analyses.
+#[derive(Clone)]
+pub struct NetlistAcStamper {
+    /// Entries that do not depend on frequency, plus the AC stimulus.
+    fixed: ComplexMna,
+    /// Entries proportional to `ω`, stored at `ω = 1`.
+    reactive: ComplexMna,
+}
+
+impl NetlistAcStamper {
+    /// Stamp the small-signal model of every device in `netlist`.
+    ///
+    /// `dc_solution` is the full DC solution vector (node voltages followed by
+    /// branch currents) that nonlinear devices are linearized at. Pass `None`
+    /// for a linear circuit.
+    pub fn new(netlist: &Netlist, dc_solution: Option<&DVector<f64>>) -> Self {
+        let num_nodes = netlist.num_nodes();
+        let num_vsources = netlist.num_current_vars();
+        let mut stamper = Self {
+            fixed: ComplexMna::new(num_nodes, num_vsources),
+            reactive: ComplexMna::new(num_nodes, num_vsources),
+        };
+        for device in netlist.devices() {
+            let info = match dc_solution {
+                Some(solution) => device.ac_info_at(solution),
+                None => device.ac_info(),
+            };
+            stamper.stamp_device(info);
+        }
+        stamper
+    }
+
+    fn stamp_device(&mut self, info: AcDeviceInfo) {
+        let fixed = &mut self.fixed;
+        let reactive = &mut self.reactive;
+        let num_nodes = fixed.num_nodes();
+
+        match info {
+            AcDeviceInfo::Resistor {
+                node_pos,
+                node_neg,
+                conductance,
+            } => fixed.stamp_conductance(node_pos, node_neg, conductance),
+            AcDeviceInfo::Capacitor {
+                node_pos,
+                node_neg,
+                capacitance,
+            } => reactive.stamp_admittance(node_pos, node_neg, Complex::new(0.0, capacitance)),
+            AcDeviceInfo::Inductor {
+                node_pos,
+                node_neg,
+                inductance,
+                branch_idx,
+            } => stamp_inductor(fixed, reactive, node_pos, node_neg, branch_idx, inductance),
+            AcDeviceInfo::VoltageSource {
+                node_pos,
+                node_neg,
+                branch_idx,
+                ac_mag,
+            } => fixed.stamp_voltage_source(
+                node_pos,
+                node_neg,
+                branch_idx,
+                Complex::new(ac_mag, 0.0),
+            ),
+            AcDeviceInfo::CurrentSource {
+                node_pos,
+                node_neg,
+                ac_mag,
+            } => {
+                if ac_mag != 0.0 {
+                    fixed.stamp_current_source(node_pos, node_neg, Complex::new(ac_mag, 0.0));
+                }
+            }
+            AcDeviceInfo::Vcvs {
+                out_pos,
+                out_neg,
+                ctrl_pos,
+                ctrl_neg,
+                branch_idx,
+                gain,
+            } => {
+                let br = num_nodes + branch_idx;
+                stamp_branch_terminals(fixed, out_pos, out_neg, br);
+                if let Some(i) = ctrl_pos {
+                    fixed.add_element(br, i, Complex::new(-gain, 0.0));
+                }
+                if let Some(i) = ctrl_neg {
+                    fixed.add_element(br, i, Complex::new(gain, 0.0));
+                }
+            }
+            AcDeviceInfo::Vccs {
+                out_pos,
+                out_neg,
+                ctrl_pos,
+                ctrl_neg,
+                gm,
+            } => fixed.stamp_vccs(out_pos, out_neg, ctrl_pos, ctrl_neg, gm),
+            AcDeviceInfo::Cccs {
+                out_pos,
+                out_neg,
+                vsource_branch_idx,
+                gain,
+            } => {
+                let ctrl_br = num_nodes + vsource_branch_idx;
+                if let Some(i) = out_pos {
+                    fixed.add_element(i, ctrl_br, Complex::new(gain, 0.0));
+                }
+                if let Some(i) = out_neg {
+                    fixed.add_element(i, ctrl_br, Complex::new(-gain, 0.0));
+                }
+            }
+            AcDeviceInfo::Ccvs {
+                out_pos,
+                out_neg,
+                vsource_branch_idx,
+                branch_idx,
+                gain,
+            } => {
+                let br = num_nodes + branch_idx;
+                stamp_branch_terminals(fixed, out_pos, out_neg, br);
+                fixed.add_element(br, num_nodes + vsource_branch_idx, Complex::new(-gain, 0.0));
+            }
+            AcDeviceInfo::Diode {
+                node_pos,
+                node_neg,
+                gd,
+            } => fixed.stamp_conductance(node_pos, node_neg, gd),
+            AcDeviceInfo::Mosfet {
+                drain,
+                gate,
+                source,
+                gds,
+                gm,
+                ..
+            }
+            | AcDeviceInfo::Jfet {
+                drain,
+                gate,
+                source,
+                gds,
+                gm,
+            } => {
+                // gds from drain to source, plus gm*Vgs drawn from drain to source.
+                fixed.stamp_conductance(drain, source, gds);
+                fixed.stamp_vccs(drain, source, gate, source, gm);
+            }
+            AcDeviceInfo::Bsim1Mosfet {
+                drain,
+                gate,
+                source,
+                bulk,
+                gds,
+                gm,
+                gmbs,
+                ..
+            } => {
+                // DC model only - no intrinsic capacitances.
+                fixed.stamp_conductance(drain, source, gds);
+                fixed.stamp_vccs(drain, source, gate, source, gm);
+                fixed.stamp_vccs(drain, source, bulk, source, gmbs);
+            }
+            AcDeviceInfo::Bsim3Mosfet {
+                drain,
+                gate,
+                source,
+                bulk,
+                gds,
+                gm,
+                gmbs,
+                cgs,
+                cgd,
+                cgb,
+                cbs,
+                cbd,
+                ..
+            } => {
+                fixed.stamp_conductance(drain, source, gds);
+                fixed.stamp_vccs(drain, source, gate, source, gm);
+                fixed.stamp_vccs(drain, source, bulk, source, gmbs);
+                for (a, b, c) in [
+                    (gate, source, cgs),
+                    (gate, drain, cgd),
+                    (gate, bulk, cgb),
+                    (bulk, source, cbs),
+                    (bulk, drain, cbd),
+                ] {
+                    if c > 0.0 {
+                        reactive.stamp_admittance(a, b, Complex::new(0.0, c));
+                    }
+                }
+            }
+            AcDeviceInfo::Bjt {
+                collector,
+                base,
+                emitter,
+                gm,
+                gpi,
+                gmu,
+                go,
+            } => {
+                // Hybrid-π: gpi across base-emitter, gmu across base-collector,
+                // go across collector-emitter, and gm*Vbe drawn from collector
+                // to emitter.
+                fixed.stamp_conductance(base, emitter, gpi);
+                fixed.stamp_conductance(base, collector, gmu);
+                fixed.stamp_conductance(collector, emitter, go);
+                fixed.stamp_vccs(collector, emitter, base, emitter, gm);
+            }
+            AcDeviceInfo::MutualInductance {
+                l1_branch_idx,
+                l2_branch_idx,
+                mutual_inductance,
+            } => {
+                // The inductors stamp their own jωL terms; add the jωM coupling.
+                let jm = Complex::new(0.0, mutual_inductance);
+                let br1 = num_nodes + l1_branch_idx;
+                let br2 = num_nodes + l2_branch_idx;
+                reactive.add_element(br1, br2, jm);
+                reactive.add_element(br2, br1, jm);
+            }
+            AcDeviceInfo::TransmissionLine {
+                port1_pos,
+                port2_pos,
+                z0,
+                td,
+                num_sections,
+                internal_nodes,
+                current_base_index,
+                ..
+            } => {
+                // Lumped LC ladder from port 1 to port 2, with a shunt capacitor
+                // to ground after each series inductor (common ground assumed).
+                let l_section = z0 * td / num_sections as f64;
+                let c_section = td / (z0 * num_sections as f64);
+
+                let mut node_chain = Vec::with_capacity(num_sections + 1);
+                node_chain.push(port1_pos);
+                node_chain.extend(internal_nodes);
+                node_chain.push(port2_pos);
+
+                for (i, pair) in node_chain.windows(2).take(num_sections).enumerate() {
+                    let (left, right) = (pair[0], pair[1]);
+                    stamp_inductor(
+                        fixed,
+                        reactive,
+                        left,
+                        right,
+                        current_base_index + i,
+                        l_section,
+                    );
+                    reactive.stamp_admittance(right, None, Complex::new(0.0, c_section));
+                }
+            }
+            AcDeviceInfo::None | _ => {}
+        }
+    }
+}
+
+impl AcStamper for NetlistAcStamper {
+    fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+        mna.triplets.extend_from_slice(&self.fixed.triplets);
+        mna.triplets.extend(
+            self.reactive
+                .triplets
+                .iter()
+                .map(|&(row, col, value)| (row, col, value * omega)),
+        );
+        *mna.rhs_mut() += self.fixed.rhs();
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.fixed.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        self.fixed.size() - self.fixed.num_nodes()
+    }
+}
+
+/// Stamp an inductor's branch incidence into `fixed` and its `-jL` impedance
+/// term into `reactive`.
+fn stamp_inductor(
+    fixed: &mut ComplexMna,
+    reactive: &mut ComplexMna,
+    node_pos: Option<usize>,
+    node_neg: Option<usize>,
+    branch_idx: usize,
+    inductance: f64,
+) {
+    let br = fixed.num_nodes() + branch_idx;
+    stamp_branch_terminals(fixed, node_pos, node_neg, br);
+    reactive.add_element(br, br, Complex::new(0.0, -inductance));
+}
+
+/// Stamp the ±1 incidence entries tying a branch current to its terminals.
+fn stamp_branch_terminals(
+    mna: &mut ComplexMna,
+    node_pos: Option<usize>,
+    node_neg: Option<usize>,
+    br: usize,
+) {
+    let one = Complex::new(1.0, 0.0);
+    if let Some(i) = node_pos {
+        mna.add_element(i, br, one);
+        mna.add_element(br, i, one);
+    }
+    if let Some(i) = node_neg {
+        mna.add_element(i, br, -one);
+        mna.add_element(br, i, -one);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ac::{AcParams, AcSweepType, solve_ac};
+    use crate::dc::solve_dc;
+    use std::f64::consts::PI;
+
+    /// The stamper matches a direct per-frequency stamp of the same circuit.
+    struct HandRc;
+
+    impl AcStamper for HandRc {
+        fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, Complex::new(1.0, 0.0));
+            mna.stamp_conductance(Some(0), Some(1), 1e-3);
+            mna.stamp_admittance(Some(1), None, Complex::new(0.0, omega * 1e-6));
+            mna.stamp_inductor(Some(1), Some(2), 1, omega, 1e-3);
+            mna.stamp_conductance(Some(2), None, 1e-2);
+        }
+        fn num_nodes(&self) -> usize {
+            3
+        }
+        fn num_vsources(&self) -> usize {
+            2
+        }
+    }
+
+    fn dense_at(stamper: &dyn AcStamper, omega: f64) -> ComplexMna {
+        let mut mna = ComplexMna::new(stamper.num_nodes(), stamper.num_vsources());
+        stamper.stamp_ac(&mut mna, omega);
+        mna
+    }
+
+    #[test]
+    fn test_netlist_stamper_matches_hand_stamp() {
+        let netlist = spicier_parser::parse(
+            "RLC\nV1 1 0 DC 5 AC 1\nR1 1 2 1k\nC1 2 0 1u\nL1 2 3 1m\nR2 3 0 100\n.end\n",
+        )
+        .unwrap();
+        let stamper = NetlistAcStamper::new(&netlist, None);
+        assert_eq!(stamper.num_nodes(), 3);
+        assert_eq!(stamper.num_vsources(), 2);
+
+        for omega in [0.0, 1.0, 2.0 * PI * 1e3, 1e7] {
+            let ours = dense_at(&stamper, omega);
+            let hand = dense_at(&HandRc, omega);
+            let diff = (ours.to_dense_matrix() - hand.to_dense_matrix())
+                .iter()
+                .map(|v| v.norm())
+                .fold(0.0, f64::max);
+            assert!(diff < 1e-12, "matrix mismatch {diff:e} at ω = {omega}");
+            assert_eq!(ours.rhs(), hand.rhs());
+        }
+    }
+
+    #[test]
+    fn test_netlist_rc_lowpass_at_operating_point() {
+        let netlist = spicier_parser::parse(
+            "RC lowpass\nV1 1 0 DC 2 AC 1\nR1 1 2 1k\nC1 2 0 1u\n.ac dec 10 1 100k\n.end\n",
+        )
+        .unwrap();
+        let dc = solve_dc(&netlist.assemble_mna()).unwrap();
+        let op = DVector::from_iterator(
+            netlist.num_nodes() + netlist.num_current_vars(),
+            dc.node_voltages
+                .iter()
+                .chain(dc.branch_currents.iter())
+                .copied(),
+        );
+        let stamper = NetlistAcStamper::new(&netlist, Some(&op));
+
+        let params = AcParams {
+            fstart: 1.0,
+            fstop: 1e5,
+            num_points: 10,
+            sweep_type: AcSweepType::Decade,
+        };
+        let result = solve_ac(&stamper, &params).unwrap();
+
+        let tau = 1e3 * 1e-6;
+        for (f, v2) in result.voltage_at(1) {
+            let expected = Complex::new(1.0, 0.0) / Complex::new(1.0, 2.0 * PI * f * tau);
+            assert!(
+                (v2 - expected).norm() < 1e-9,
+                "V(2) at {f} Hz: {v2} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bjt_small_signal_matches_dc_jacobian() {
+        use spicier_core::NodeId;
+        use spicier_core::mna::MnaSystem;
+        use spicier_devices::bjt::Bjt;
+
+        // A saturated NPN, where the base-collector conductance is large
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(1));
+        netlist.register_node(NodeId::new(2));
+        netlist.add_device(Bjt::npn(
+            "Q1",
+            NodeId::new(1),
+            NodeId::new(2),
+            NodeId::GROUND,
+        ));
+        let op = DVector::from_vec(vec![0.1, 0.75]);
+
+        let mut jacobian = MnaSystem::new(2, 0);
+        netlist.devices()[0].stamp_nonlinear(&mut jacobian, &op);
+        let jacobian = jacobian.to_dense_matrix();
+        let ac = dense_at(&NetlistAcStamper::new(&netlist, Some(&op)), 0.0).to_dense_matrix();
+
+        // Equal up to the Gmin shunts the DC stamp adds
+        for i in 0..2 {
+            for j in 0..2 {
+                let (g, y) = (jacobian[(i, j)], ac[(i, j)]);
+                assert!(
+                    (g - y.re).abs() <= 1e-9 * g.abs() + 1e-11 && y.im == 0.0,
+                    "Y[{i},{j}] = {y} vs {g}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Stamper implementations for connecting parsed netlists to solver traits.
+//!
+//! The solver entry points take stamper traits so they can run on any
+//! circuit representation; these adapt a [`Netlist`](spicier_core::Netlist)
+//! to them for DC operating points, DC sweeps and transient analysis.
+
+use std::cell::RefCell;
+
+use nalgebra::DVector;
+use spicier_core::LimitState;
+use spicier_core::mna::MnaSystem;
+use spicier_core::netlist::TransientDeviceInfo;
+
+use crate::dc::{DcSweepStamper, NonlinearNestedSweepStamper, NonlinearSweepStamper};
+use crate::newton::{NonlinearStamper, ScaledNonlinearStamper};
+use crate::transient::{CapacitorState, InductorState, TransientStamper, TransmissionLineState};
+
+/// DC sweep stamper that re-assembles the netlist with a modified source value.
+///
+/// Since the Netlist uses trait objects, we re-assemble the entire MNA and then
+/// patch the voltage source RHS entry for the swept source.
+pub struct NetlistSweepStamper<'a> {
+    pub netlist: &'a spicier_core::Netlist,
+    pub source_name: String,
+}
+
+impl DcSweepStamper for NetlistSweepStamper<'_> {
+    fn stamp_with_sweep(&self, mna: &mut MnaSystem, _source_name: &str, value: f64) {
+        // First, stamp all devices normally
+        self.netlist.stamp_into(mna);
+
+        // Then override the swept source's value in the RHS.
+        // For a voltage source, the RHS entry at (num_nodes + branch_idx) contains the voltage.
+        // Look up the branch index by source name.
+        if let Some(idx) = self.netlist.find_vsource_branch_index(&self.source_name) {
+            let bi = self.netlist.num_nodes() + idx;
+            mna.rhs_mut()[bi] = value;
+        }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.netlist.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        self.netlist.num_current_vars()
+    }
+}
+
+/// DC sweep stamper for netlists with nonlinear devices.
+///
+/// Stamps all devices linearized at the current Newton iterate, then patches
+/// the swept source's RHS entry like [`NetlistSweepStamper`].
+pub struct NetlistNonlinearSweepStamper<'a> {
+    pub netlist: &'a spicier_core::Netlist,
+    pub source_name: String,
+}
+
+impl NonlinearSweepStamper for NetlistNonlinearSweepStamper<'_> {
+    fn stamp_at_with_sweep(&self, mna: &mut MnaSystem, solution: &DVector<f64>, value: f64) {
+        self.netlist.stamp_nonlinear_into(mna, solution);
+
+        if let Some(idx) = self.netlist.find_vsource_branch_index(&self.source_name) {
+            let bi = self.netlist.num_nodes() + idx;
+            mna.rhs_mut()[bi] = value;
+        }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.netlist.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        self.netlist.num_current_vars()
+    }
+}
+
+/// Stamper for nested DC sweeps - stamps with two swept source values.
+pub struct NestedSweepStamper<'a> {
+    pub netlist: &'a spicier_core::Netlist,
+    pub source_name1: String,
+    pub source_name2: String,
+}
+
+impl NestedSweepStamper<'_> {
+    pub fn stamp_with_two_sweeps(&self, mna: &mut MnaSystem, value1: f64, value2: f64) {
+        // First stamp all devices normally
+        self.netlist.stamp_into(mna);
+
+        // Then override both swept sources' values in the RHS
+        if let Some(idx1) = self.netlist.find_vsource_branch_index(&self.source_name1) {
+            let bi1 = self.netlist.num_nodes() + idx1;
+            mna.rhs_mut()[bi1] = value1;
+        }
+
+        if let Some(idx2) = self.netlist.find_vsource_branch_index(&self.source_name2) {
+            let bi2 = self.netlist.num_nodes() + idx2;
+            mna.rhs_mut()[bi2] = value2;
+        }
+    }
+}
+
+impl NonlinearNestedSweepStamper for NestedSweepStamper<'_> {
+    fn stamp_at_with_sweeps(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        outer: f64,
+        inner: f64,
+    ) {
+        self.netlist.stamp_nonlinear_into(mna, solution);
+
+        if let Some(idx1) = self.netlist.find_vsource_branch_index(&self.source_name1) {
+            mna.rhs_mut()[self.netlist.num_nodes() + idx1] = outer;
+        }
+        if let Some(idx2) = self.netlist.find_vsource_branch_index(&self.source_name2) {
+            mna.rhs_mut()[self.netlist.num_nodes() + idx2] = inner;
+        }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.netlist.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        self.netlist.num_current_vars()
+    }
+}
+
+/// Nonlinear stamper for Newton-Raphson DC analysis.
+///
+/// At each NR iteration, stamps all devices linearized at the current solution.
+pub struct NetlistNonlinearStamper<'a> {
+    pub netlist: &'a spicier_core::Netlist,
+}
+
+impl NonlinearStamper for NetlistNonlinearStamper<'_> {
+    fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+        self.netlist.stamp_nonlinear_into(mna, solution);
+    }
+
+    fn stamp_at_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        state: &mut LimitState,
+    ) {
+        self.netlist
+            .stamp_nonlinear_into_limited(mna, solution, state);
+    }
+}
+
+impl ScaledNonlinearStamper for NetlistNonlinearStamper<'_> {
+    fn stamp_at_scaled(&self, mna: &mut MnaSystem, solution: &DVector<f64>, source_factor: f64) {
+        self.netlist
+            .stamp_nonlinear_into_scaled(mna, solution, source_factor);
+    }
+}
+
+/// Transient stamper that stamps all non-reactive devices from a netlist.
+///
+/// Transmission lines are stamped here too, as Branin companion models
+/// whose wave history is recorded through [`TransientStamper::accept_step`].
+///
+/// The transient system keeps the netlist's full branch numbering, so branch
+/// indices (including [`add_current_probe`](spicier_core::Netlist::add_current_probe)
+/// sense sources) mean the same as in DC. Branches owned by inductors and
+/// transmission lines carry no equation under their companion models and are
+/// pinned to zero.
+pub struct NetlistTransientStamper<'a> {
+    pub netlist: &'a spicier_core::Netlist,
+    lines: RefCell<Vec<TransmissionLineState>>,
+}
+
+impl<'a> NetlistTransientStamper<'a> {
+    /// Create a transient stamper for `netlist`.
+    pub fn new(netlist: &'a spicier_core::Netlist) -> Self {
+        let lines = netlist
+            .devices()
+            .iter()
+            .filter_map(|device| match device.transient_info() {
+                TransientDeviceInfo::TransmissionLine {
+                    port1_pos,
+                    port1_neg,
+                    port2_pos,
+                    port2_neg,
+                    z0,
+                    td,
+                    num_sections: _,
+                    internal_nodes,
+                    current_base_index,
+                } => Some(TransmissionLineState::new(
+                    z0,
+                    td,
+                    port1_pos,
+                    port1_neg,
+                    port2_pos,
+                    port2_neg,
+                    internal_nodes,
+                    current_base_index,
+                )),
+                _ => None,
+            })
+            .collect();
+        Self {
+            netlist,
+            lines: RefCell::new(lines),
+        }
+    }
+}
+
+impl TransientStamper for NetlistTransientStamper<'_> {
+    fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
+        // Stamp all devices that are NOT capacitors, inductors, or transmission lines.
+        // Capacitors and inductors are handled by companion models; transmission
+        // lines are stamped from their wave history below.
+        // For time-varying sources (PULSE, SIN), evaluate at the given time.
+        for device in self.netlist.devices() {
+            match device.transient_info() {
+                TransientDeviceInfo::Capacitor { .. } => {
+                    // Skip reactive devices; their companion models are stamped separately
+                }
+                TransientDeviceInfo::Inductor { branch_index, .. } => {
+                    pin_branches(mna, branch_index, 1);
+                }
+                TransientDeviceInfo::TransmissionLine {
+                    num_sections,
+                    current_base_index,
+                    ..
+                } => {
+                    pin_branches(mna, current_base_index, num_sections);
+                }
+                TransientDeviceInfo::None | _ => {
+                    device.stamp_at_time(mna, time);
+                }
+            }
+        }
+        for line in self.lines.borrow().iter() {
+            line.stamp(mna, time);
+        }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.netlist.num_nodes()
+    }
+
+    fn num_vsources(&self) -> usize {
+        // Every branch keeps its DC index; see pin_branches for the unused ones
+        self.netlist.num_current_vars()
+    }
+
+    fn start_transient(&self, dc_solution: &DVector<f64>) {
+        let num_nodes = self.num_nodes();
+        for line in self.lines.borrow_mut().iter_mut() {
+            line.init_from_dc(dc_solution, num_nodes);
+        }
+    }
+
+    fn accept_step(&self, time: f64, solution: &DVector<f64>) {
+        for line in self.lines.borrow_mut().iter_mut() {
+            line.accept(time, solution);
+        }
+    }
+
+    fn max_timestep(&self) -> Option<f64> {
+        self.lines
+            .borrow()
+            .iter()
+            .map(|line| line.td)
+            .reduce(f64::min)
+    }
+}
+
+/// Fix `count` branch currents starting at `first` to zero.
+///
+/// Inductor and transmission-line section currents only have equations in
+/// the DC model; in transient their companion models stamp node currents
+/// instead, leaving these rows empty.
+fn pin_branches(mna: &mut MnaSystem, first: usize, count: usize) {
+    for branch in first..first + count {
+        let row = mna.num_nodes + branch;
+        mna.add_element(row, row, 1.0);
+    }
+}
+
+/// Build capacitor and inductor state vectors from the netlist for transient analysis.
+///
+/// Transmission lines keep their own state in [`NetlistTransientStamper`].
+pub fn build_transient_state(
+    netlist: &spicier_core::Netlist,
+) -> (Vec<CapacitorState>, Vec<InductorState>) {
+    let mut caps = Vec::new();
+    let mut inds = Vec::new();
+
+    for device in netlist.devices() {
+        match device.transient_info() {
+            TransientDeviceInfo::Capacitor {
+                node_pos,
+                node_neg,
+                capacitance,
+            } => {
+                caps.push(CapacitorState::new(capacitance, node_pos, node_neg));
+            }
+            TransientDeviceInfo::Inductor {
+                node_pos,
+                node_neg,
+                inductance,
+                branch_index,
+            } => {
+                inds.push(InductorState::new(
+                    inductance,
+                    node_pos,
+                    node_neg,
+                    branch_index,
+                ));
+            }
+            TransientDeviceInfo::None | _ => {}
+        }
+    }
+
+    (caps, inds)
+}
@@ -1,13 +1,17 @@
 //! Newton-Raphson nonlinear solver.
 
 use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+use spicier_core::LimitState;
 use spicier_core::mna::MnaSystem;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::linear::{CachedSparseLu, SPARSE_THRESHOLD, solve_dense};
+use crate::sweep::SampleRng;
 
 /// Convergence criteria for Newton-Raphson iteration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ConvergenceCriteria {
     /// Absolute voltage tolerance (V).
     pub v_abstol: f64,
@@ -42,6 +46,22 @@ impl Default for ConvergenceCriteria {
 pub trait NonlinearStamper {
     /// Re-stamp the MNA system for the current solution.
     fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>);
+
+    /// Re-stamp for the current solution, limiting how far device junction
+    /// voltages move from where they were linearized last iteration.
+    ///
+    /// `state` is owned by the Newton loop and empty on the first iteration.
+    /// Netlist-backed stampers forward this to
+    /// [`Netlist::stamp_nonlinear_into_limited`](spicier_core::Netlist::stamp_nonlinear_into_limited).
+    /// The default implementation ignores `state`.
+    fn stamp_at_limited(
+        &self,
+        mna: &mut MnaSystem,
+        solution: &DVector<f64>,
+        _state: &mut LimitState,
+    ) {
+        self.stamp_at(mna, solution);
+    }
 }
 
 /// Result of Newton-Raphson iteration.
@@ -87,10 +107,14 @@ pub fn solve_newton_raphson(
     // Cached sparse solver (created on first iteration if needed)
     let mut cached_solver: Option<CachedSparseLu> = None;
 
+    // Junction voltages devices linearized at; the first stamp has none, so
+    // a warm-start guess is used as given.
+    let mut limits = LimitState::new();
+
     for iteration in 0..criteria.max_iterations {
         // Clear and re-stamp at current operating point
         mna.clear();
-        stamper.stamp_at(&mut mna, &solution);
+        stamper.stamp_at_limited(&mut mna, &solution, &mut limits);
 
         // Solve the linearized system
         let new_solution = if size >= SPARSE_THRESHOLD {
@@ -170,7 +194,7 @@ pub trait ScaledNonlinearStamper: NonlinearStamper {
 }
 
 /// Parameters for Gmin stepping convergence aid.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GminSteppingParams {
     /// Initial Gmin value (default 1e-3).
     pub initial_gmin: f64,
@@ -207,7 +231,7 @@ pub struct GminSteppingResult {
 }
 
 /// Parameters for source stepping convergence aid.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SourceSteppingParams {
     /// Initial source factor (default 0.1).
     pub initial_factor: f64,
@@ -396,6 +420,16 @@ pub fn solve_with_gmin_stepping(
             // Then add Gmin from each node to ground
             mna.stamp_gmin(self.gmin);
         }
+
+        fn stamp_at_limited(
+            &self,
+            mna: &mut MnaSystem,
+            solution: &DVector<f64>,
+            state: &mut LimitState,
+        ) {
+            self.inner.stamp_at_limited(mna, solution, state);
+            mna.stamp_gmin(self.gmin);
+        }
     }
 
     while current_gmin >= params.final_gmin * 0.99 {
@@ -458,6 +492,228 @@ pub fn solve_with_gmin_stepping(
     })
 }
 
+/// A DC convergence aid tried by [`solve_with_strategy`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ConvergenceAid {
+    /// Plain Newton-Raphson from a zero initial guess.
+    Newton,
+    /// Gmin stepping (see [`solve_with_gmin_stepping`]).
+    GminStepping(GminSteppingParams),
+    /// Source stepping (see [`solve_with_source_stepping`]).
+    SourceStepping(SourceSteppingParams),
+}
+
+impl std::fmt::Display for ConvergenceAid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvergenceAid::Newton => write!(f, "Newton-Raphson"),
+            ConvergenceAid::GminStepping(_) => write!(f, "Gmin stepping"),
+            ConvergenceAid::SourceStepping(_) => write!(f, "source stepping"),
+        }
+    }
+}
+
+/// Ordered fallback sequence of DC convergence aids.
+///
+/// The default follows ngspice: plain Newton-Raphson, then Gmin stepping,
+/// then source stepping.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcSolverStrategy {
+    /// Aids to try, in order, until one converges.
+    pub aids: Vec<ConvergenceAid>,
+}
+
+impl Default for DcSolverStrategy {
+    fn default() -> Self {
+        Self {
+            aids: vec![
+                ConvergenceAid::Newton,
+                ConvergenceAid::GminStepping(GminSteppingParams::default()),
+                ConvergenceAid::SourceStepping(SourceSteppingParams::default()),
+            ],
+        }
+    }
+}
+
+/// Result of [`solve_with_strategy`].
+#[derive(Debug, Clone)]
+pub struct StrategyResult {
+    /// Converged solution.
+    pub solution: DVector<f64>,
+    /// Newton-Raphson iterations across all aids tried.
+    pub total_iterations: usize,
+    /// The aid that converged.
+    pub aid: ConvergenceAid,
+    /// Aids that were tried and failed before `aid`.
+    pub failed: Vec<ConvergenceAid>,
+}
+
+/// Solve a nonlinear DC system, trying each aid in `strategy` until one converges.
+///
+/// An aid that errors (e.g. a singular matrix without Gmin) counts as a
+/// failure and the next aid is tried. Returns
+/// [`Error::ConvergenceFailed`] if every aid fails.
+pub fn solve_with_strategy(
+    num_nodes: usize,
+    num_vsources: usize,
+    stamper: &dyn ScaledNonlinearStamper,
+    criteria: &ConvergenceCriteria,
+    strategy: &DcSolverStrategy,
+) -> Result<StrategyResult> {
+    let mut total_iterations = 0;
+    let mut failed = Vec::new();
+
+    for aid in &strategy.aids {
+        let attempt = match aid {
+            ConvergenceAid::Newton => {
+                solve_newton_raphson(num_nodes, num_vsources, stamper, criteria, None)
+                    .map(|r| (r.solution, r.iterations, r.converged))
+            }
+            ConvergenceAid::GminStepping(params) => {
+                solve_with_gmin_stepping(num_nodes, num_vsources, stamper, criteria, params)
+                    .map(|r| (r.solution, r.total_iterations, r.converged))
+            }
+            ConvergenceAid::SourceStepping(params) => {
+                solve_with_source_stepping(num_nodes, num_vsources, stamper, criteria, params)
+                    .map(|r| (r.solution, r.total_iterations, r.converged))
+            }
+        };
+
+        match attempt {
+            Ok((solution, iterations, true)) => {
+                return Ok(StrategyResult {
+                    solution,
+                    total_iterations: total_iterations + iterations,
+                    aid: aid.clone(),
+                    failed,
+                });
+            }
+            Ok((_, iterations, false)) => total_iterations += iterations,
+            Err(_) => {}
+        }
+        failed.push(aid.clone());
+    }
+
+    Err(Error::ConvergenceFailed {
+        iterations: total_iterations,
+    })
+}
+
+/// Parameters for [`solve_dc_multistart`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultistartParams {
+    /// Number of randomized initial guesses (default 20).
+    pub num_starts: usize,
+    /// Node voltage guesses are drawn uniformly from `[-voltage_range, voltage_range]`
+    /// (default 5.0 V). Branch currents start at zero.
+    pub voltage_range: f64,
+    /// Solutions whose node voltages are within this L2 distance (V) are
+    /// treated as the same solution (default 1e-3).
+    pub cluster_tolerance: f64,
+    /// Random seed, so runs are reproducible (default 0).
+    pub seed: u64,
+}
+
+impl Default for MultistartParams {
+    fn default() -> Self {
+        Self {
+            num_starts: 20,
+            voltage_range: 5.0,
+            cluster_tolerance: 1e-3,
+            seed: 0,
+        }
+    }
+}
+
+/// A distinct DC solution found by [`solve_dc_multistart`].
+#[derive(Debug, Clone)]
+pub struct MultistartSolution {
+    /// Solution vector from the first start that reached it.
+    pub solution: DVector<f64>,
+    /// Number of starts that converged to this solution.
+    pub count: usize,
+}
+
+/// Result of [`solve_dc_multistart`].
+#[derive(Debug, Clone)]
+pub struct MultistartResult {
+    /// Distinct converged solutions, in the order they were found.
+    pub solutions: Vec<MultistartSolution>,
+    /// Number of starts that did not converge.
+    pub num_failed: usize,
+}
+
+impl MultistartResult {
+    /// Whether more than one distinct DC solution was found.
+    pub fn is_multistable(&self) -> bool {
+        self.solutions.len() > 1
+    }
+}
+
+/// Solve a nonlinear DC system from several random initial guesses.
+///
+/// Newton-Raphson converges to whichever solution's basin holds the initial
+/// guess, so a circuit with several valid operating points (a latch, a
+/// Schmitt trigger) silently yields just one of them. This runs Newton from
+/// `params.num_starts` randomized guesses and clusters the converged
+/// solutions by the L2 distance between their node voltages.
+///
+/// Unstable equilibria (e.g. a latch's metastable midpoint) are reported too;
+/// Newton does not distinguish them from stable ones.
+pub fn solve_dc_multistart(
+    num_nodes: usize,
+    num_vsources: usize,
+    stamper: &dyn NonlinearStamper,
+    criteria: &ConvergenceCriteria,
+    params: &MultistartParams,
+) -> Result<MultistartResult> {
+    let size = num_nodes + num_vsources;
+    let mut result = MultistartResult {
+        solutions: Vec::new(),
+        num_failed: 0,
+    };
+
+    for start in 0..params.num_starts {
+        let mut rng = SampleRng::new(params.seed, start as u64);
+        let guess = DVector::from_fn(size, |i, _| {
+            if i < num_nodes {
+                (2.0 * rng.next_uniform() - 1.0) * params.voltage_range
+            } else {
+                0.0
+            }
+        });
+
+        let nr =
+            match solve_newton_raphson(num_nodes, num_vsources, stamper, criteria, Some(&guess)) {
+                Ok(nr) if nr.converged => nr,
+                _ => {
+                    result.num_failed += 1;
+                    continue;
+                }
+            };
+
+        let distance = |other: &DVector<f64>| {
+            nr.solution
+                .rows(0, num_nodes)
+                .metric_distance(&other.rows(0, num_nodes))
+        };
+        match result
+            .solutions
+            .iter_mut()
+            .find(|s| distance(&s.solution) <= params.cluster_tolerance)
+        {
+            Some(existing) => existing.count += 1,
+            None => result.solutions.push(MultistartSolution {
+                solution: nr.solution,
+                count: 1,
+            }),
+        }
+    }
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -547,6 +803,83 @@ mod tests {
         println!("  I(diode)  = {:.4} mA", (5.0 - vd) / 1000.0 * 1000.0);
     }
 
+    /// Resistor + diode with an unprotected Shockley exponential, limited
+    /// with `pnjlim` only when `limit` is set.
+    struct RawDiodeStamper {
+        v_source: f64,
+        resistance: f64,
+        limit: bool,
+    }
+
+    impl RawDiodeStamper {
+        const IS: f64 = 1e-14;
+        const NVT: f64 = 0.02585;
+
+        fn stamp_with_vd(&self, mna: &mut MnaSystem, vd: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, self.v_source);
+            mna.stamp_conductance(Some(0), Some(1), 1.0 / self.resistance);
+            let exp_term = (vd / Self::NVT).exp();
+            let id = Self::IS * (exp_term - 1.0);
+            let gd = Self::IS * exp_term / Self::NVT + 1e-12;
+            mna.stamp_conductance(Some(1), None, gd);
+            mna.stamp_current_source(Some(1), None, id - gd * vd);
+        }
+    }
+
+    impl NonlinearStamper for RawDiodeStamper {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.stamp_with_vd(mna, solution[1]);
+        }
+
+        fn stamp_at_limited(
+            &self,
+            mna: &mut MnaSystem,
+            solution: &DVector<f64>,
+            state: &mut LimitState,
+        ) {
+            let mut vd = solution[1];
+            let history = state.device(0);
+            if let (true, &[vd_old]) = (self.limit, history.as_slice()) {
+                let vcrit = Self::NVT * (Self::NVT / (std::f64::consts::SQRT_2 * Self::IS)).ln();
+                vd = spicier_devices::limiting::pnjlim(vd, vd_old, Self::NVT, vcrit);
+            }
+            *history = vec![vd];
+            self.stamp_with_vd(mna, vd);
+        }
+    }
+
+    #[test]
+    fn test_newton_junction_limiting_from_large_forward_bias() {
+        // The first step from an off diode puts ~30V across the junction,
+        // where exp(vd/nvt) overflows.
+        let criteria = ConvergenceCriteria::default();
+        let unlimited = RawDiodeStamper {
+            v_source: 30.0,
+            resistance: 100.0,
+            limit: false,
+        };
+        assert!((30.0 / RawDiodeStamper::NVT).exp().is_infinite());
+        let diverged = match solve_newton_raphson(2, 1, &unlimited, &criteria, None) {
+            Ok(result) => !result.converged || !result.solution.iter().all(|v| v.is_finite()),
+            Err(_) => true,
+        };
+        assert!(diverged, "unlimited Newton should not converge");
+
+        let limited = RawDiodeStamper {
+            limit: true,
+            ..unlimited
+        };
+        let result = solve_newton_raphson(2, 1, &limited, &criteria, None).unwrap();
+        assert!(result.converged, "limited Newton should converge");
+
+        // KCL at the junction: (V - vd)/R = Is·(exp(vd/nvt) - 1).
+        let vd = result.solution[1];
+        let i_r = (30.0 - vd) / 100.0;
+        let i_d = RawDiodeStamper::IS * ((vd / RawDiodeStamper::NVT).exp() - 1.0);
+        assert!((i_r - i_d).abs() < 1e-6 * i_r, "I_R = {i_r}, I_D = {i_d}");
+        assert!(vd > 0.7 && vd < 0.9, "vd = {vd}");
+    }
+
     #[test]
     fn test_convergence_check() {
         let old = DVector::from_vec(vec![1.0, 2.0, 0.001]);
@@ -665,4 +998,116 @@ mod tests {
             result.num_levels, result.total_iterations
         );
     }
+
+    #[test]
+    fn test_strategy_falls_back_to_gmin_stepping() {
+        // Sized for three nodes, the diode circuit leaves node 2 unconnected,
+        // so the matrix is singular without Gmin.
+        let stamper = ScaledDiodeCircuitStamper {
+            v_source: 5.0,
+            resistance: 1000.0,
+            is: 1e-14,
+            nvt: 0.02585,
+        };
+        let criteria = ConvergenceCriteria::default();
+
+        // Plain Newton alone fails on the singular system.
+        let newton_only = DcSolverStrategy {
+            aids: vec![ConvergenceAid::Newton],
+        };
+        assert!(matches!(
+            solve_with_strategy(3, 1, &stamper, &criteria, &newton_only),
+            Err(Error::ConvergenceFailed { .. })
+        ));
+
+        // The default sequence falls through to Gmin stepping.
+        let result = solve_with_strategy(3, 1, &stamper, &criteria, &DcSolverStrategy::default())
+            .expect("Gmin stepping should succeed");
+        assert!(matches!(result.aid, ConvergenceAid::GminStepping(_)));
+        assert_eq!(result.failed, vec![ConvergenceAid::Newton]);
+        assert_eq!(result.aid.to_string(), "Gmin stepping");
+
+        let vd = result.solution[1];
+        assert!(vd > 0.5 && vd < 0.8, "V(diode) = {} (expected 0.5-0.8)", vd);
+        assert!(result.solution[2].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_strategy_plain_newton_first() {
+        let stamper = ScaledDiodeCircuitStamper {
+            v_source: 5.0,
+            resistance: 1000.0,
+            is: 1e-14,
+            nvt: 0.02585,
+        };
+        let result = solve_with_strategy(
+            2,
+            1,
+            &stamper,
+            &ConvergenceCriteria::default(),
+            &DcSolverStrategy::default(),
+        )
+        .unwrap();
+        assert_eq!(result.aid, ConvergenceAid::Newton);
+        assert!(result.failed.is_empty());
+    }
+
+    /// Two cross-coupled inverters modeled as transconductors driving 1S loads:
+    /// I(node i) = G·(f(v_j) − v_i), with f(v) = (VDD/2)·(1 − tanh(k·(v − VDD/2))).
+    struct LatchStamper {
+        vdd: f64,
+        gain: f64,
+    }
+
+    impl LatchStamper {
+        fn inverter(&self, v: f64) -> (f64, f64) {
+            let t = (self.gain * (v - self.vdd / 2.0)).tanh();
+            let f = self.vdd / 2.0 * (1.0 - t);
+            let df = -self.vdd / 2.0 * self.gain * (1.0 - t * t);
+            (f, df)
+        }
+    }
+
+    impl NonlinearStamper for LatchStamper {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            for (out, inp) in [(0, 1), (1, 0)] {
+                // Linearize f(v_in) ≈ f0 + df·(v_in − v0).
+                let (f0, df) = self.inverter(solution[inp]);
+                mna.add_element(out, out, 1.0);
+                mna.add_element(out, inp, -df);
+                mna.add_rhs(out, f0 - df * solution[inp]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multistart_finds_both_latch_states() {
+        let stamper = LatchStamper {
+            vdd: 5.0,
+            gain: 4.0,
+        };
+        let criteria = ConvergenceCriteria::default();
+        let params = MultistartParams::default();
+
+        let result = solve_dc_multistart(2, 0, &stamper, &criteria, &params).unwrap();
+        assert!(result.is_multistable());
+
+        let has_state = |hi: usize, lo: usize| {
+            result
+                .solutions
+                .iter()
+                .any(|s| s.solution[hi] > 4.9 && s.solution[lo] < 0.1)
+        };
+        assert!(has_state(0, 1), "missing Q=1 state: {:?}", result.solutions);
+        assert!(has_state(1, 0), "missing Q=0 state: {:?}", result.solutions);
+
+        // Distinct solutions only, and every start is accounted for.
+        let total: usize = result.solutions.iter().map(|s| s.count).sum();
+        assert_eq!(total + result.num_failed, params.num_starts);
+        assert!(result.solutions.len() <= 3);
+
+        // Same seed, same answer.
+        let again = solve_dc_multistart(2, 0, &stamper, &criteria, &params).unwrap();
+        assert_eq!(again.solutions.len(), result.solutions.len());
+    }
 }
@@ -673,6 +673,69 @@ mod tests {
         assert!((mid - 2.5).abs() < 0.1);
     }
 
+    /// A single resistor from node 0 to ground.
+    struct ResistorStamper {
+        resistance: f64,
+    }
+
+    impl NoiseStamper for ResistorStamper {
+        fn stamp_ac(&self, mna: &mut ComplexMna, _omega: f64) {
+            mna.stamp_conductance(Some(0), None, 1.0 / self.resistance);
+        }
+
+        fn noise_sources(&self) -> Vec<NoiseSource> {
+            vec![NoiseSource::thermal("R1", Some(0), None, self.resistance)]
+        }
+
+        fn num_nodes(&self) -> usize {
+            1
+        }
+
+        fn num_vsources(&self) -> usize {
+            0
+        }
+
+        fn input_gain(
+            &self,
+            _omega: f64,
+            _input_source_idx: usize,
+            _output_node: usize,
+            _output_ref_node: Option<usize>,
+        ) -> Result<Complex<f64>> {
+            Ok(Complex::new(1.0, 0.0))
+        }
+    }
+
+    #[test]
+    fn test_single_resistor_output_noise_is_4ktr() {
+        let stamper = ResistorStamper { resistance: 10e3 };
+        let config = NoiseConfig {
+            fstart: 10.0,
+            fstop: 1e5,
+            num_points: 2,
+            ..Default::default()
+        };
+        let result = compute_noise(&stamper, &config).unwrap();
+
+        // The noise current 4kT/R sees the resistor itself: Sv = 4kTR, flat
+        let expected_sq = 4.0 * crate::noise::sources::BOLTZMANN * 300.0 * 10e3;
+        assert_eq!(result.contributions.len(), 1);
+        for (&sv, &vn) in result.output_noise_sq.iter().zip(&result.output_noise) {
+            assert!((sv / expected_sq - 1.0).abs() < 1e-12);
+            assert!((vn - expected_sq.sqrt()).abs() < 1e-15);
+        }
+        assert_eq!(
+            result.contributions[0].output_noise_sq,
+            result.output_noise_sq
+        );
+        assert!(
+            result.contributions[0]
+                .contribution_percent
+                .iter()
+                .all(|&p| (p - 100.0).abs() < 1e-9)
+        );
+    }
+
     #[test]
     fn test_noise_figure_calculation() {
         // Create a mock NoiseResult to test noise figure methods
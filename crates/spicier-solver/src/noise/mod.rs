@@ -9,6 +9,8 @@
 //!   with spectral density Sv = 4kTR (V²/Hz)
 //! - **Shot noise**: Current through PN junctions generates noise with
 //!   spectral density Si = 2qI (A²/Hz)
+//! - **Channel thermal noise**: MOSFET channels generate drain current noise
+//!   with spectral density Si = 8kT·gm/3 (A²/Hz)
 //! - **Flicker noise** (1/f): MOSFETs and BJTs exhibit low-frequency noise
 //!   with spectral density proportional to 1/f
 //!
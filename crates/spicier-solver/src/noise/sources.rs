@@ -15,6 +15,8 @@ pub enum NoiseSourceType {
     Shot,
     /// Flicker (1/f) noise: Si = Kf * I^Af / f
     Flicker,
+    /// MOSFET channel thermal noise: Si = 8kT * gm / 3
+    ChannelThermal,
 }
 
 /// A noise source in the circuit.
@@ -31,6 +33,7 @@ pub struct NoiseSource {
     /// For thermal noise: resistance in Ohms.
     /// For shot noise: DC current in Amps.
     /// For flicker noise: coefficient Kf.
+    /// For channel thermal noise: transconductance gm in Siemens.
     pub value: f64,
     /// For flicker noise: current exponent Af (typically 1 or 2).
     pub flicker_af: f64,
@@ -115,6 +118,33 @@ impl NoiseSource {
         }
     }
 
+    /// Create a MOSFET channel thermal noise source.
+    ///
+    /// The saturated channel contributes Si = (2/3)·4kT·gm between drain
+    /// and source, rather than the 4kT·gds an equivalent resistor would.
+    ///
+    /// # Arguments
+    /// * `name` - Source identifier
+    /// * `drain` - Drain node (None for ground)
+    /// * `source` - Source node (None for ground)
+    /// * `gm` - Transconductance at the operating point in Siemens
+    pub fn channel_thermal(
+        name: impl Into<String>,
+        drain: Option<usize>,
+        source: Option<usize>,
+        gm: f64,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            source_type: NoiseSourceType::ChannelThermal,
+            node_pos: drain,
+            node_neg: source,
+            value: gm.abs(),
+            flicker_af: 0.0,
+            flicker_current: 0.0,
+        }
+    }
+
     /// Compute the noise current spectral density Si (A²/Hz) at a given frequency.
     ///
     /// # Arguments
@@ -146,6 +176,10 @@ impl NoiseSource {
                     0.0
                 }
             }
+            NoiseSourceType::ChannelThermal => {
+                // Channel thermal noise: Si = 8kT * gm / 3 (A²/Hz)
+                8.0 * BOLTZMANN * temperature * self.value / 3.0
+            }
         }
     }
 
@@ -257,6 +291,17 @@ mod tests {
         assert!((si - 3.2e-22).abs() < 0.1e-22);
     }
 
+    #[test]
+    fn test_channel_thermal_noise_source() {
+        let source = NoiseSource::channel_thermal("M1", Some(0), None, 1e-3);
+        let si = source.current_spectral_density(1000.0, 300.0);
+        // Si = 8kT * gm / 3 = 8 * 1.38e-23 * 300 * 1e-3 / 3 = 1.1045e-23 A²/Hz
+        assert!((si - 1.1045e-23).abs() < 0.001e-23);
+        // Two thirds of the thermal noise of a 1/gm resistor
+        let resistor = NoiseSource::thermal("R1", Some(0), None, 1e3);
+        assert!((si / resistor.current_spectral_density(1000.0, 300.0) - 2.0 / 3.0).abs() < 1e-12);
+    }
+
     #[test]
     fn test_flicker_noise() {
         // Flicker noise with Kf=1e-24, Af=1, I=1mA at 100Hz
@@ -4,7 +4,9 @@
 //! and other backends can provide implementations. Two separate traits handle
 //! real-valued (DC/transient) and complex-valued (AC) operations.
 
+use nalgebra::{DMatrix, DVectorView, DVectorViewMut};
 use num_complex::Complex64 as C64;
+use spicier_core::mna::MnaSystem;
 
 /// A linear operator that computes y = A * x for real (f64) vectors.
 ///
@@ -33,9 +35,96 @@ pub trait ComplexOperator: Send + Sync {
     fn apply(&self, x: &[C64], y: &mut [C64]);
 }
 
+/// A dense real matrix as a [`RealOperator`].
+///
+/// Small systems gain nothing from sparse storage, so this is the simplest
+/// way to run an iterative solver on them, e.g. to exercise the GMRES path
+/// below the dispatch threshold.
+#[derive(Debug, Clone)]
+pub struct DenseRealOperator {
+    matrix: DMatrix<f64>,
+}
+
+impl DenseRealOperator {
+    /// Wrap a square matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not square.
+    pub fn new(matrix: DMatrix<f64>) -> Self {
+        assert!(matrix.is_square(), "operator matrix must be square");
+        Self { matrix }
+    }
+
+    /// The assembled matrix of an MNA system.
+    pub fn from_mna(mna: &MnaSystem) -> Self {
+        Self::new(mna.to_dense_matrix())
+    }
+
+    /// The wrapped matrix.
+    pub fn matrix(&self) -> &DMatrix<f64> {
+        &self.matrix
+    }
+}
+
+impl RealOperator for DenseRealOperator {
+    fn dim(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    fn apply(&self, x: &[f64], y: &mut [f64]) {
+        let n = self.dim();
+        let mut y = DVectorViewMut::from_slice(y, n);
+        y.gemv(1.0, &self.matrix, &DVectorView::from_slice(x, n), 0.0);
+    }
+}
+
+/// A dense complex matrix as a [`ComplexOperator`], the AC counterpart of
+/// [`DenseRealOperator`].
+#[derive(Debug, Clone)]
+pub struct DenseComplexOperator {
+    matrix: DMatrix<C64>,
+}
+
+impl DenseComplexOperator {
+    /// Wrap a square matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not square.
+    pub fn new(matrix: DMatrix<C64>) -> Self {
+        assert!(matrix.is_square(), "operator matrix must be square");
+        Self { matrix }
+    }
+
+    /// The wrapped matrix.
+    pub fn matrix(&self) -> &DMatrix<C64> {
+        &self.matrix
+    }
+}
+
+impl ComplexOperator for DenseComplexOperator {
+    fn dim(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    fn apply(&self, x: &[C64], y: &mut [C64]) {
+        let n = self.dim();
+        let mut y = DVectorViewMut::from_slice(y, n);
+        y.gemv(
+            C64::new(1.0, 0.0),
+            &self.matrix,
+            &DVectorView::from_slice(x, n),
+            C64::new(0.0, 0.0),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dc::solve_dc;
+    use crate::gmres::{GmresConfig, solve_gmres_real};
 
     /// Simple diagonal real operator for testing.
     struct DiagReal {
@@ -104,10 +193,61 @@ mod tests {
         assert!((y[1] - C64::new(0.0, 2.0)).norm() < 1e-15);
     }
 
+    #[test]
+    fn dense_complex_operator_applies_matrix() {
+        let i = C64::new(0.0, 1.0);
+        let op = DenseComplexOperator::new(DMatrix::from_row_slice(
+            2,
+            2,
+            &[C64::new(1.0, 0.0), i, -i, C64::new(2.0, 0.0)],
+        ));
+        assert_eq!(op.dim(), 2);
+
+        let mut y = vec![C64::new(0.0, 0.0); 2];
+        op.apply(&[C64::new(1.0, 0.0), i], &mut y);
+
+        // [1, i; -i, 2] * [1, i] = [1 - 1, -i + 2i]
+        assert!((y[0] - C64::new(0.0, 0.0)).norm() < 1e-15);
+        assert!((y[1] - i).norm() < 1e-15);
+    }
+
     #[test]
     fn operator_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<DiagReal>();
         assert_send_sync::<DiagComplex>();
+        assert_send_sync::<DenseRealOperator>();
+        assert_send_sync::<DenseComplexOperator>();
+    }
+
+    #[test]
+    fn dense_operator_gmres_matches_solve_dc() {
+        // V1 = 10 V -- R1 = 1k -- node 2 -- R2 = 3k -- ground
+        let mut mna = MnaSystem::new(2, 1);
+        mna.stamp_voltage_source(Some(0), None, 0, 10.0);
+        mna.stamp_conductance(Some(0), Some(1), 1.0 / 1000.0);
+        mna.stamp_conductance(Some(1), None, 1.0 / 3000.0);
+
+        let op = DenseRealOperator::from_mna(&mna);
+        assert_eq!(op.dim(), 3);
+        let config = GmresConfig {
+            tol: 1e-12,
+            ..GmresConfig::default()
+        };
+        let result = solve_gmres_real(&op, mna.rhs().as_slice(), &config);
+        let direct = solve_dc(&mna).unwrap();
+
+        assert!(result.converged);
+        assert!(result.iterations <= op.dim());
+        let expected: Vec<f64> = direct
+            .node_voltages
+            .iter()
+            .chain(direct.branch_currents.iter())
+            .copied()
+            .collect();
+        for (x, e) in result.x.iter().zip(&expected) {
+            assert!((x - e).abs() < 1e-9 * e.abs().max(1e-3), "{x} vs {e}");
+        }
+        assert!((result.x[1] - 7.5).abs() < 1e-9);
     }
 }
@@ -14,6 +14,15 @@ pub trait RealPreconditioner: Send + Sync {
     /// Apply the preconditioner: y = M^(-1) * x.
     fn apply(&self, x: &[f64], y: &mut [f64]);
 
+    /// Apply the preconditioner, allowing it to change between calls.
+    ///
+    /// Only flexible GMRES ([`solve_fgmres_real`](crate::gmres::solve_fgmres_real))
+    /// calls this. Preconditioners that run an inner iterative solve or keep
+    /// other state override it; the default is [`apply`](Self::apply).
+    fn apply_mut(&mut self, x: &[f64], y: &mut [f64]) {
+        self.apply(x, y);
+    }
+
     /// Dimension of the preconditioner.
     fn dim(&self) -> usize;
 }
@@ -39,10 +48,14 @@ pub trait ComplexPreconditioner: Send + Sync {
 /// # Construction
 ///
 /// From triplets, extracts the diagonal elements. Zero or near-zero diagonal
-/// entries are replaced with 1.0 to avoid division issues.
+/// entries, such as the branch rows of ideal voltage sources, are replaced
+/// with 1.0 to avoid division issues; [`clamped_rows`](Self::clamped_rows)
+/// lists them.
 pub struct JacobiPreconditioner {
     /// Inverse of diagonal elements.
     inv_diag: Vec<f64>,
+    /// Rows whose diagonal was near zero and pass through unscaled.
+    clamped_rows: Vec<usize>,
 }
 
 impl JacobiPreconditioner {
@@ -60,29 +73,39 @@ impl JacobiPreconditioner {
             }
         }
 
-        // Compute inverses, handling near-zero entries
+        Self::from_diagonal(&diag)
+    }
+
+    /// Create from a diagonal vector.
+    ///
+    /// Near-zero entries (< 1e-30) are treated as 1.0.
+    pub fn from_diagonal(diag: &[f64]) -> Self {
+        let mut clamped_rows = Vec::new();
         let inv_diag: Vec<f64> = diag
             .iter()
-            .map(|&d| {
+            .enumerate()
+            .map(|(i, &d)| {
                 if d.abs() < 1e-30 {
-                    1.0 // Don't scale entries with zero diagonal
+                    // Don't scale entries with zero diagonal
+                    clamped_rows.push(i);
+                    1.0
                 } else {
                     1.0 / d
                 }
             })
             .collect();
 
-        Self { inv_diag }
+        Self {
+            inv_diag,
+            clamped_rows,
+        }
     }
 
-    /// Create from a diagonal vector.
-    pub fn from_diagonal(diag: &[f64]) -> Self {
-        let inv_diag: Vec<f64> = diag
-            .iter()
-            .map(|&d| if d.abs() < 1e-30 { 1.0 } else { 1.0 / d })
-            .collect();
-
-        Self { inv_diag }
+    /// Rows whose diagonal was near zero, in ascending order.
+    ///
+    /// The preconditioner passes these components through unchanged.
+    pub fn clamped_rows(&self) -> &[usize] {
+        &self.clamped_rows
     }
 }
 
@@ -247,6 +270,21 @@ mod tests {
         // Zero diagonal treated as 1.0
         assert!((y[0] - 5.0).abs() < 1e-15);
         assert!((y[1] - 2.0).abs() < 1e-15);
+        assert_eq!(precond.clamped_rows(), &[0]);
+    }
+
+    #[test]
+    fn jacobi_from_diagonal_passes_zero_rows_through() {
+        // Node rows followed by a voltage-source branch row with no diagonal
+        let precond = JacobiPreconditioner::from_diagonal(&[2.0, 4.0, 0.0, 1e-40]);
+
+        let x = vec![2.0, 2.0, 3.0, -7.0];
+        let mut y = vec![0.0; 4];
+        precond.apply(&x, &mut y);
+
+        assert!(y.iter().all(|yi| yi.is_finite()));
+        assert_eq!(y, [1.0, 0.5, 3.0, -7.0]);
+        assert_eq!(precond.clamped_rows(), &[2, 3]);
     }
 
     #[test]
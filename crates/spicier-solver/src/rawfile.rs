@@ -0,0 +1,354 @@
+//! ngspice rawfile output.
+//!
+//! A rawfile is a text header followed by the data, either as ASCII text
+//! (`Values:`) or as little-endian `f64`s (`Binary:`):
+//!
+//! ```text
+//! Title: RC charging
+//! Plotname: Transient Analysis
+//! Flags: real
+//! No. Variables: 2
+//! No. Points: 101
+//! Variables:
+//!     0   time    time
+//!     1   v(out)  voltage
+//! Values:
+//!  0  0.000000e0
+//!     0.000000e0
+//! ...
+//! ```
+//!
+//! [`write_raw`] writes any plot from its variables, real or complex.
+//! [`write_transient_raw`] builds the variables from a transient result:
+//! time first, then a caller-supplied map of solution index to name, in
+//! index order.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+use num_complex::Complex;
+
+use crate::error::Error;
+use crate::transient::TransientResult;
+
+/// Encoding of the rawfile data section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RawEncoding {
+    /// Human-readable `Values:` section.
+    #[default]
+    Ascii,
+    /// Little-endian `f64` `Binary:` section, as ngspice writes by default.
+    Binary,
+}
+
+/// Values of one rawfile variable.
+#[derive(Debug, Clone, Copy)]
+pub enum RawValues<'a> {
+    Real(&'a [f64]),
+    Complex(&'a [Complex<f64>]),
+}
+
+impl RawValues<'_> {
+    fn len(&self) -> usize {
+        match self {
+            RawValues::Real(v) => v.len(),
+            RawValues::Complex(v) => v.len(),
+        }
+    }
+
+    /// Value `i` as `(re, im)`, with a zero imaginary part for real data.
+    fn get(&self, i: usize) -> (f64, f64) {
+        match self {
+            RawValues::Real(v) => (v[i], 0.0),
+            RawValues::Complex(v) => (v[i].re, v[i].im),
+        }
+    }
+}
+
+/// One variable of a rawfile plot.
+#[derive(Debug, Clone, Copy)]
+pub struct RawVariable<'a> {
+    /// Variable name, e.g. `v(out)`.
+    pub name: &'a str,
+    /// ngspice variable type: `time`, `frequency`, `voltage`, `current`, ...
+    pub kind: &'a str,
+    /// One value per point.
+    pub values: RawValues<'a>,
+}
+
+/// Write one plot as a rawfile.
+///
+/// The first variable is the plot's scale. If any variable is complex, the
+/// plot is flagged `complex` and every value, real ones included, is
+/// written as a real/imaginary pair. Variables of different lengths are an
+/// [`io::ErrorKind::InvalidInput`] error.
+pub fn write_raw(
+    out: &mut impl Write,
+    title: &str,
+    plotname: &str,
+    variables: &[RawVariable<'_>],
+    encoding: RawEncoding,
+) -> io::Result<()> {
+    let num_points = variables.first().map_or(0, |v| v.values.len());
+    if let Some(v) = variables.iter().find(|v| v.values.len() != num_points) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "variable {} has {} points, expected {}",
+                v.name,
+                v.values.len(),
+                num_points
+            ),
+        ));
+    }
+    let complex = variables
+        .iter()
+        .any(|v| matches!(v.values, RawValues::Complex(_)));
+
+    writeln!(out, "Title: {}", title)?;
+    writeln!(out, "Plotname: {}", plotname)?;
+    writeln!(out, "Flags: {}", if complex { "complex" } else { "real" })?;
+    writeln!(out, "No. Variables: {}", variables.len())?;
+    writeln!(out, "No. Points: {}", num_points)?;
+    writeln!(out, "Variables:")?;
+    for (i, v) in variables.iter().enumerate() {
+        writeln!(out, "\t{}\t{}\t{}", i, v.name, v.kind)?;
+    }
+
+    match encoding {
+        RawEncoding::Ascii => {
+            writeln!(out, "Values:")?;
+            for i in 0..num_points {
+                for (j, v) in variables.iter().enumerate() {
+                    let (re, im) = v.values.get(i);
+                    let value = if complex {
+                        format!("{:e},{:e}", re, im)
+                    } else {
+                        format!("{:e}", re)
+                    };
+                    if j == 0 {
+                        writeln!(out, " {}\t{}", i, value)?;
+                    } else {
+                        writeln!(out, "\t{}", value)?;
+                    }
+                }
+            }
+        }
+        RawEncoding::Binary => {
+            writeln!(out, "Binary:")?;
+            for i in 0..num_points {
+                for v in variables {
+                    let (re, im) = v.values.get(i);
+                    out.write_all(&re.to_le_bytes())?;
+                    if complex {
+                        out.write_all(&im.to_le_bytes())?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write `result` as a rawfile.
+///
+/// `names` maps solution indices to variable names such as `v(out)` or
+/// `i(v1)`. Indices below `result.num_nodes` are written as voltages and
+/// the rest as currents. An index past the end of a solution vector is an
+/// [`io::ErrorKind::InvalidInput`] error wrapping
+/// [`Error::IndexOutOfRange`](crate::Error::IndexOutOfRange).
+pub fn write_transient_raw(
+    out: &mut impl Write,
+    result: &TransientResult,
+    title: &str,
+    names: &BTreeMap<usize, String>,
+    encoding: RawEncoding,
+) -> io::Result<()> {
+    if let Some(&index) = names.keys().next_back() {
+        if let Some(point) = result.points.iter().find(|p| index >= p.solution.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                Error::IndexOutOfRange {
+                    what: "solution",
+                    index,
+                    len: point.solution.len(),
+                },
+            ));
+        }
+    }
+
+    let time = result.times();
+    let columns: Vec<Vec<f64>> = names
+        .keys()
+        .map(|&index| result.points.iter().map(|p| p.solution[index]).collect())
+        .collect();
+    let variables: Vec<RawVariable<'_>> = std::iter::once(RawVariable {
+        name: "time",
+        kind: "time",
+        values: RawValues::Real(&time),
+    })
+    .chain(
+        names
+            .iter()
+            .zip(&columns)
+            .map(|((&index, name), values)| RawVariable {
+                name,
+                kind: if index < result.num_nodes {
+                    "voltage"
+                } else {
+                    "current"
+                },
+                values: RawValues::Real(values),
+            }),
+    )
+    .collect();
+    write_raw(out, title, "Transient Analysis", &variables, encoding)
+}
+
+impl TransientResult {
+    /// Write the result to an ngspice rawfile at `path`.
+    ///
+    /// See [`write_transient_raw`] for how `names` selects the variables.
+    pub fn write_rawfile(
+        &self,
+        path: impl AsRef<Path>,
+        names: &BTreeMap<usize, String>,
+        encoding: RawEncoding,
+    ) -> io::Result<()> {
+        let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+        write_transient_raw(&mut out, self, "spicier", names, encoding)?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transient::TimePoint;
+    use nalgebra::DVector;
+
+    fn result() -> TransientResult {
+        TransientResult {
+            points: (0..3)
+                .map(|i| TimePoint {
+                    time: i as f64 * 1e-3,
+                    solution: DVector::from_vec(vec![1.0, 0.5 * i as f64, -1e-3]),
+                })
+                .collect(),
+            num_nodes: 2,
+        }
+    }
+
+    fn names() -> BTreeMap<usize, String> {
+        BTreeMap::from([(1, "v(out)".to_string()), (2, "i(v1)".to_string())])
+    }
+
+    #[test]
+    fn test_ascii_rawfile() {
+        let mut out = Vec::new();
+        write_transient_raw(&mut out, &result(), "RC", &names(), RawEncoding::Ascii).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.starts_with("Title: RC\nPlotname: Transient Analysis\nFlags: real\n"));
+        assert!(text.contains("No. Variables: 3\nNo. Points: 3\n"));
+        assert!(text.contains("\t0\ttime\ttime\n\t1\tv(out)\tvoltage\n\t2\ti(v1)\tcurrent\n"));
+        assert!(text.ends_with(" 2\t2e-3\n\t1e0\n\t-1e-3\n"));
+    }
+
+    #[test]
+    fn test_binary_rawfile() {
+        let mut out = Vec::new();
+        write_transient_raw(&mut out, &result(), "RC", &names(), RawEncoding::Binary).unwrap();
+
+        let marker = b"Binary:\n";
+        let start = out.windows(marker.len()).position(|w| w == marker).unwrap() + marker.len();
+        let values: Vec<f64> = out[start..]
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(
+            values,
+            [0.0, 0.0, -1e-3, 1e-3, 0.5, -1e-3, 2e-3, 1.0, -1e-3]
+        );
+    }
+
+    #[test]
+    fn test_complex_rawfile() {
+        let frequency = [1.0, 10.0];
+        let gain = [Complex::new(1.0, -0.5), Complex::new(0.25, 0.0)];
+        let variables = [
+            RawVariable {
+                name: "frequency",
+                kind: "frequency",
+                values: RawValues::Real(&frequency),
+            },
+            RawVariable {
+                name: "v(out)",
+                kind: "voltage",
+                values: RawValues::Complex(&gain),
+            },
+        ];
+
+        let mut out = Vec::new();
+        write_raw(
+            &mut out,
+            "RC",
+            "AC Analysis",
+            &variables,
+            RawEncoding::Ascii,
+        )
+        .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Plotname: AC Analysis\nFlags: complex\n"));
+        // The real scale is written as a pair too
+        assert!(text.ends_with(" 1\t1e1,0e0\n\t2.5e-1,0e0\n"));
+
+        let mut out = Vec::new();
+        write_raw(
+            &mut out,
+            "RC",
+            "AC Analysis",
+            &variables,
+            RawEncoding::Binary,
+        )
+        .unwrap();
+        let marker = b"Binary:\n";
+        let start = out.windows(marker.len()).position(|w| w == marker).unwrap() + marker.len();
+        assert_eq!(out.len() - start, 2 * 2 * 2 * 8);
+
+        // Ragged variables are rejected
+        let short = [
+            variables[0],
+            RawVariable {
+                values: RawValues::Real(&frequency[..1]),
+                ..variables[1]
+            },
+        ];
+        let err = write_raw(
+            &mut Vec::new(),
+            "RC",
+            "AC Analysis",
+            &short,
+            RawEncoding::Ascii,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_rawfile_rejects_out_of_range_index() {
+        let names = BTreeMap::from([(3, "v(x)".to_string())]);
+        let err = write_transient_raw(&mut Vec::new(), &result(), "RC", &names, RawEncoding::Ascii)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(matches!(
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::IndexOutOfRange {
+                what: "solution",
+                index: 3,
+                ..
+            })
+        ));
+    }
+}
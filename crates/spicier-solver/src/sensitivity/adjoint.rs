@@ -0,0 +1,288 @@
+//! DC sensitivity analysis using the adjoint method.
+//!
+//! For a linear system `A x = b` and an output `y = eₖᵀ x`, one solve of
+//! the transposed system `Aᵀ λ = eₖ` gives the derivative with respect to
+//! every element value `p`:
+//!
+//! dy/dp = λᵀ (db/dp − (dA/dp) x)
+//!
+//! A resistor between nodes `i` and `j` contributes `dy/dG = −(λᵢ − λⱼ)(xᵢ − xⱼ)`,
+//! a voltage source `dy/dV = λ_branch` and a current source from `i` to `j`
+//! `dy/dI = λⱼ − λᵢ`. Unlike [`compute_dc_sensitivity`](super::compute_dc_sensitivity),
+//! the cost does not grow with the number of parameters.
+
+use nalgebra::DVector;
+use spicier_core::mna::MnaSystem;
+use spicier_core::{AcDeviceInfo, Netlist, Stamper};
+
+use crate::error::{Error, Result};
+use crate::linear::solve_dense;
+
+/// Sensitivity of the output to one element.
+#[derive(Debug, Clone)]
+pub struct ElementSensitivity {
+    /// Element name (e.g., "R1").
+    pub name: String,
+    /// Nominal element value: resistance (Ω) or source value (V or A).
+    pub value: f64,
+    /// dOutput/dValue.
+    pub absolute: f64,
+    /// Percent change of the output per percent change of the value:
+    /// (dOutput/dValue) * (Value/Output).
+    pub normalized: f64,
+}
+
+/// Result of an adjoint DC sensitivity analysis.
+#[derive(Debug, Clone)]
+pub struct SensitivityResult {
+    /// Output node index (0-based, excluding ground).
+    pub output: usize,
+    /// Nominal output voltage.
+    pub output_value: f64,
+    /// Per-element sensitivities, in netlist order.
+    pub elements: Vec<ElementSensitivity>,
+}
+
+impl SensitivityResult {
+    /// Look up an element's sensitivity by name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&ElementSensitivity> {
+        self.elements
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Compute the sensitivity of node voltage `output` to every resistor and
+/// independent source in `netlist`.
+///
+/// `mna` is the netlist's assembled DC system, as returned by
+/// [`Netlist::assemble_mna`]. Only linear circuits are supported; resistor
+/// sensitivities are reported per ohm of resistance. An `output` past the
+/// last node gives [`Error::IndexOutOfRange`].
+pub fn solve_dc_sensitivity(
+    mna: &MnaSystem,
+    netlist: &Netlist,
+    output: usize,
+) -> Result<SensitivityResult> {
+    if netlist.has_nonlinear_devices() {
+        return Err(Error::SolverError(
+            "adjoint DC sensitivity requires a linear circuit".to_string(),
+        ));
+    }
+    let num_nodes = netlist.num_nodes();
+    if mna.size() != num_nodes + netlist.num_current_vars() {
+        return Err(Error::DimensionMismatch {
+            expected: num_nodes + netlist.num_current_vars(),
+            actual: mna.size(),
+        });
+    }
+    if output >= num_nodes {
+        return Err(Error::IndexOutOfRange {
+            what: "output node",
+            index: output,
+            len: num_nodes,
+        });
+    }
+
+    let a = mna.to_dense_matrix();
+    let x = solve_dense(&a, mna.rhs())?;
+    let mut unit = DVector::zeros(mna.size());
+    unit[output] = 1.0;
+    let lambda = solve_dense(&a.transpose(), &unit)?;
+
+    let output_value = x[output];
+    let at = |v: &DVector<f64>, node: Option<usize>| node.map_or(0.0, |i| v[i]);
+
+    let mut elements = Vec::new();
+    for device in netlist.devices() {
+        let (value, absolute) = match device.ac_info() {
+            AcDeviceInfo::Resistor {
+                node_pos,
+                node_neg,
+                conductance,
+            } => {
+                let dlambda = at(&lambda, node_pos) - at(&lambda, node_neg);
+                let dv = at(&x, node_pos) - at(&x, node_neg);
+                // dy/dR = dy/dG · dG/dR = −dy/dG · G²
+                (1.0 / conductance, dlambda * dv * conductance * conductance)
+            }
+            AcDeviceInfo::VoltageSource { branch_idx, .. } => {
+                let row = num_nodes + branch_idx;
+                (source_value(device.as_ref(), mna, row), lambda[row])
+            }
+            AcDeviceInfo::CurrentSource {
+                node_pos, node_neg, ..
+            } => {
+                // The stamp puts −I on node_pos and +I on node_neg
+                let value = match (node_neg, node_pos) {
+                    (Some(j), _) => source_value(device.as_ref(), mna, j),
+                    (None, Some(i)) => -source_value(device.as_ref(), mna, i),
+                    (None, None) => 0.0,
+                };
+                (value, at(&lambda, node_neg) - at(&lambda, node_pos))
+            }
+            _ => continue,
+        };
+
+        let normalized = if output_value.abs() > 1e-20 {
+            absolute * value / output_value
+        } else {
+            0.0
+        };
+        elements.push(ElementSensitivity {
+            name: device.device_name().to_string(),
+            value,
+            absolute,
+            normalized,
+        });
+    }
+
+    Ok(SensitivityResult {
+        output,
+        output_value,
+        elements,
+    })
+}
+
+/// Right-hand side entry `row` of `device` stamped on its own.
+fn source_value(device: &dyn Stamper, mna: &MnaSystem, row: usize) -> f64 {
+    let mut own = MnaSystem::new(mna.num_nodes, mna.num_vsources);
+    device.stamp(&mut own);
+    own.rhs()[row]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensitivity::{
+        DcSensitivityStamper, SensitivityConfig, SensitivityOutput, SensitivityParam,
+        compute_dc_sensitivity,
+    };
+    use spicier_core::NodeId;
+    use spicier_devices::passive::Resistor;
+    use spicier_devices::sources::{CurrentSource, VoltageSource};
+
+    /// V1 = 10 V -- R1 -- node 2 -- R2 -- ground, with I1 injecting into node 2.
+    fn divider(r1: f64, r2: f64, i1: f64) -> Netlist {
+        let mut netlist = Netlist::new();
+        netlist.register_node(NodeId::new(2));
+        netlist.add_device(VoltageSource::new(
+            "V1",
+            NodeId::new(1),
+            NodeId::GROUND,
+            10.0,
+            0,
+        ));
+        netlist.add_device(Resistor::new("R1", NodeId::new(1), NodeId::new(2), r1));
+        netlist.add_device(Resistor::new("R2", NodeId::new(2), NodeId::GROUND, r2));
+        netlist.add_device(CurrentSource::new("I1", NodeId::GROUND, NodeId::new(2), i1));
+        netlist
+    }
+
+    #[test]
+    fn test_voltage_divider_adjoint_sensitivity() {
+        let (r1, r2, i1) = (1e3, 3e3, 1e-3);
+        let netlist = divider(r1, r2, i1);
+        let result = solve_dc_sensitivity(&netlist.assemble_mna(), &netlist, 1).unwrap();
+
+        // V(2) = (10/R1 + I1) · R1 R2 / (R1 + R2)
+        let rp = r1 * r2 / (r1 + r2);
+        let v2 = (10.0 / r1 + i1) * rp;
+        assert!((result.output_value - v2).abs() < 1e-9);
+
+        // dV(2)/dR2 = (10/R1 + I1) · R1² / (R1 + R2)²
+        let dv_dr2 = (10.0 / r1 + i1) * r1 * r1 / (r1 + r2).powi(2);
+        let sr2 = result.get("R2").unwrap();
+        assert!(
+            (sr2.absolute - dv_dr2).abs() < 1e-12,
+            "dV(2)/dR2 = {} (expected {})",
+            sr2.absolute,
+            dv_dr2
+        );
+        assert!((sr2.normalized - dv_dr2 * r2 / v2).abs() < 1e-9);
+
+        // The sources see plain transfer ratios
+        let sv1 = result.get("V1").unwrap();
+        assert!((sv1.absolute - r2 / (r1 + r2)).abs() < 1e-12);
+        assert_eq!(sv1.value, 10.0);
+        let si1 = result.get("I1").unwrap();
+        assert!((si1.absolute - rp).abs() < 1e-9);
+        assert_eq!(si1.value, i1);
+    }
+
+    struct DividerStamper;
+
+    impl DcSensitivityStamper for DividerStamper {
+        fn stamp_nominal(&self, mna: &mut MnaSystem) {
+            divider(1e3, 3e3, 1e-3).stamp_into(mna);
+        }
+
+        fn stamp_perturbed(&self, mna: &mut MnaSystem, param: &SensitivityParam) {
+            let mut netlist = divider(1e3, 3e3, 1e-3);
+            netlist
+                .set_device_value(&param.name(), param.value())
+                .unwrap();
+            netlist.stamp_into(mna);
+        }
+
+        fn num_nodes(&self) -> usize {
+            2
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_adjoint_matches_finite_differences() {
+        let netlist = divider(1e3, 3e3, 1e-3);
+        let adjoint = solve_dc_sensitivity(&netlist.assemble_mna(), &netlist, 1).unwrap();
+
+        let params = adjoint
+            .elements
+            .iter()
+            .map(|e| match &e.name[..1] {
+                "R" => SensitivityParam::Resistance {
+                    name: e.name.clone(),
+                    value: e.value,
+                },
+                "V" => SensitivityParam::VoltageSource {
+                    name: e.name.clone(),
+                    value: e.value,
+                },
+                _ => SensitivityParam::CurrentSource {
+                    name: e.name.clone(),
+                    value: e.value,
+                },
+            })
+            .collect();
+        let config = SensitivityConfig::new(params, vec![SensitivityOutput::voltage(1)]);
+        let fd = compute_dc_sensitivity(&DividerStamper, &config).unwrap();
+
+        assert_eq!(fd.len(), 4);
+        for r in &fd {
+            let a = adjoint.get(&r.param_name()).unwrap();
+            assert!(
+                (a.absolute - r.value).abs() <= 1e-5 * r.value.abs(),
+                "{}: adjoint {} vs finite difference {}",
+                a.name,
+                a.absolute,
+                r.value
+            );
+        }
+    }
+
+    #[test]
+    fn test_adjoint_sensitivity_rejects_bad_output() {
+        let netlist = divider(1e3, 1e3, 0.0);
+        assert!(matches!(
+            solve_dc_sensitivity(&netlist.assemble_mna(), &netlist, 2),
+            Err(Error::IndexOutOfRange {
+                what: "output node",
+                index: 2,
+                len: 2
+            })
+        ));
+    }
+}
@@ -1,12 +1,14 @@
 //! Sensitivity analysis for circuit simulation.
 //!
-//! This module provides sensitivity analysis using forward finite differences.
-//! It computes how circuit outputs (voltages, currents) change with respect
-//! to parameter variations.
+//! This module provides sensitivity analysis using forward finite differences,
+//! plus an adjoint solver for linear DC circuits. It computes how circuit
+//! outputs (voltages, currents) change with respect to parameter variations.
 //!
 //! # Features
 //!
 //! - **DC Sensitivity** - Operating point sensitivity to parameter changes
+//! - **Adjoint DC Sensitivity** - One transposed solve for every resistor and
+//!   source of a linear circuit (`solve_dc_sensitivity`)
 //! - **AC Sensitivity** - Frequency response sensitivity
 //! - **Normalized Sensitivity** - Dimensionless relative sensitivities
 //!
@@ -36,11 +38,13 @@
 //! ```
 
 mod ac;
+mod adjoint;
 mod config;
 mod dc;
 
 pub use ac::{
     AcSensitivityResult, AcSensitivityStamper, compute_ac_sensitivity, compute_ac_sensitivity_sweep,
 };
+pub use adjoint::{ElementSensitivity, SensitivityResult, solve_dc_sensitivity};
 pub use config::{SensitivityConfig, SensitivityOutput, SensitivityParam};
 pub use dc::{DcSensitivityResult, DcSensitivityStamper, compute_dc_sensitivity};
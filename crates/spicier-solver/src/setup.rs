@@ -0,0 +1,304 @@
+//! Serializable simulation setup (netlist + analyses + options).
+//!
+//! A [`SimulationSetup`] captures everything needed to rerun a simulation so
+//! it can be saved to disk or sent to a worker as JSON. Circuit devices are
+//! trait objects, so the netlist is carried as its SPICE source and re-parsed
+//! on load; this guarantees a deserialized setup builds exactly the same
+//! [`Netlist`](spicier_core::Netlist) as the original text.
+//!
+//! Enable serde_json's `float_roundtrip` feature so tolerances and timesteps
+//! reload bit-for-bit; the default float parser can be off by one ULP.
+
+use serde::{Deserialize, Serialize};
+use spicier_parser::{AnalysisCommand, ParseResult};
+
+use crate::ac::{AcParams, AcSweepType};
+use crate::dc::DcSweepParams;
+use crate::newton::ConvergenceCriteria;
+use crate::transient::{IntegrationMethod, TransientParams};
+
+/// A single analysis to run on the circuit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SimulationAnalysis {
+    /// DC operating point.
+    Op,
+    /// DC sweep; the first entry is the outer sweep when two are given.
+    Dc { sweeps: Vec<DcSweepParams> },
+    /// AC small-signal sweep.
+    Ac { params: AcParams },
+    /// Transient analysis.
+    Tran {
+        params: TransientParams,
+        /// Start of the output window (s).
+        #[serde(default)]
+        tstart: f64,
+        /// Skip the DC operating point and start from `.IC` values.
+        #[serde(default)]
+        uic: bool,
+    },
+}
+
+/// A complete, serializable simulation definition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationSetup {
+    /// SPICE netlist source.
+    pub netlist: String,
+    /// Analyses to run, in order.
+    #[serde(default)]
+    pub analyses: Vec<SimulationAnalysis>,
+    /// Newton-Raphson tolerances for DC operating points and sweeps.
+    #[serde(default)]
+    pub tolerances: ConvergenceCriteria,
+}
+
+impl SimulationSetup {
+    /// Build a setup from SPICE text, taking the analyses from its dot commands.
+    ///
+    /// `.TRAN` maps to Trapezoidal integration, matching the CLI. `.NOISE`
+    /// is not captured.
+    pub fn from_spice(netlist: impl Into<String>) -> spicier_parser::Result<Self> {
+        let netlist = netlist.into();
+        let parsed = spicier_parser::parse_full(&netlist)?;
+        let analyses = parsed
+            .analyses
+            .iter()
+            .filter_map(SimulationAnalysis::from_command)
+            .collect();
+        Ok(Self {
+            netlist,
+            analyses,
+            tolerances: ConvergenceCriteria::default(),
+        })
+    }
+
+    /// Set the Newton-Raphson tolerances.
+    pub fn with_tolerances(mut self, tolerances: ConvergenceCriteria) -> Self {
+        self.tolerances = tolerances;
+        self
+    }
+
+    /// Parse the netlist source into a circuit.
+    pub fn parse(&self) -> spicier_parser::Result<ParseResult> {
+        spicier_parser::parse_full(&self.netlist)
+    }
+}
+
+impl SimulationAnalysis {
+    /// Convert a parsed dot command, returning `None` for unsupported analyses.
+    pub fn from_command(cmd: &AnalysisCommand) -> Option<Self> {
+        match cmd {
+            AnalysisCommand::Op => Some(Self::Op),
+            AnalysisCommand::Dc { sweeps } => Some(Self::Dc {
+                sweeps: sweeps
+                    .iter()
+                    .map(|s| DcSweepParams {
+                        source_name: s.source_name.clone(),
+                        start: s.start,
+                        stop: s.stop,
+                        step: s.step,
+                    })
+                    .collect(),
+            }),
+            AnalysisCommand::Ac {
+                sweep_type,
+                num_points,
+                fstart,
+                fstop,
+            } => Some(Self::Ac {
+                params: AcParams {
+                    fstart: *fstart,
+                    fstop: *fstop,
+                    num_points: *num_points,
+                    sweep_type: match sweep_type {
+                        spicier_parser::AcSweepType::Dec => AcSweepType::Decade,
+                        spicier_parser::AcSweepType::Oct => AcSweepType::Octave,
+                        _ => AcSweepType::Linear,
+                    },
+                },
+            }),
+            AnalysisCommand::Tran {
+                tstep,
+                tstop,
+                tstart,
+                uic,
+            } => Some(Self::Tran {
+                params: TransientParams {
+                    tstop: *tstop,
+                    tstep: *tstep,
+                    method: IntegrationMethod::Trapezoidal,
+                    be_startup_steps: 0,
+                    breakpoints: Vec::new(),
+                },
+                tstart: *tstart,
+                uic: *uic,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transient::{CapacitorState, TransientStamper, solve_transient};
+    use crate::{NonlinearStamper, solve_newton_raphson};
+    use nalgebra::DVector;
+    use spicier_core::mna::MnaSystem;
+    use spicier_core::netlist::TransientDeviceInfo;
+    use spicier_core::{LimitState, Netlist};
+
+    const RC_DIODE: &str = "RC with clamp
+V1 1 0 PULSE(0 5 0 1u 1u 1m 2m)
+R1 1 2 1k
+C1 2 0 100n
+D1 2 0 DMOD
+.model DMOD D IS=1e-14
+.tran 10u 1m
+.end
+";
+
+    struct TranStamper<'a>(&'a Netlist);
+
+    impl TransientStamper for TranStamper<'_> {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
+            for device in self.0.devices() {
+                if matches!(device.transient_info(), TransientDeviceInfo::None) {
+                    device.stamp_at_time(mna, time);
+                }
+            }
+        }
+        fn num_nodes(&self) -> usize {
+            self.0.num_nodes()
+        }
+        fn num_vsources(&self) -> usize {
+            self.0.num_current_vars()
+        }
+    }
+
+    struct DcStamper<'a>(&'a Netlist);
+
+    impl NonlinearStamper for DcStamper<'_> {
+        fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+            self.0.stamp_nonlinear_into(mna, solution);
+        }
+
+        fn stamp_at_limited(
+            &self,
+            mna: &mut MnaSystem,
+            solution: &DVector<f64>,
+            state: &mut LimitState,
+        ) {
+            self.0.stamp_nonlinear_into_limited(mna, solution, state);
+        }
+    }
+
+    /// Run the setup's operating point and transient, returning V(2) samples.
+    fn run(setup: &SimulationSetup) -> (DVector<f64>, Vec<f64>) {
+        let parsed = setup.parse().unwrap();
+        let netlist = &parsed.netlist;
+        let op = solve_newton_raphson(
+            netlist.num_nodes(),
+            netlist.num_current_vars(),
+            &DcStamper(netlist),
+            &setup.tolerances,
+            None,
+        )
+        .unwrap();
+
+        let Some(SimulationAnalysis::Tran { params, .. }) = setup.analyses.first() else {
+            panic!("expected a transient analysis");
+        };
+        let mut caps: Vec<_> = netlist
+            .devices()
+            .iter()
+            .filter_map(|d| match d.transient_info() {
+                TransientDeviceInfo::Capacitor {
+                    node_pos,
+                    node_neg,
+                    capacitance,
+                } => Some(CapacitorState::new(capacitance, node_pos, node_neg)),
+                _ => None,
+            })
+            .collect();
+        let result = solve_transient(
+            &TranStamper(netlist),
+            &mut caps,
+            &mut [],
+            params,
+            &op.solution,
+        )
+        .unwrap();
+        let v2 = result.points.iter().map(|p| p.solution[1]).collect();
+        (op.solution, v2)
+    }
+
+    #[test]
+    fn test_setup_json_round_trip() {
+        let tolerances = ConvergenceCriteria {
+            v_abstol: 1e-9,
+            v_reltol: 1e-6,
+            i_abstol: 1e-15,
+            max_iterations: 200,
+            gmin: 1e-15,
+        };
+        let mut setup = SimulationSetup::from_spice(RC_DIODE)
+            .unwrap()
+            .with_tolerances(tolerances.clone());
+        if let Some(SimulationAnalysis::Tran { params, .. }) = setup.analyses.first_mut() {
+            params.be_startup_steps = 2;
+        }
+
+        let json = serde_json::to_string_pretty(&setup).unwrap();
+        let loaded: SimulationSetup = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, setup);
+        assert_eq!(loaded.tolerances, tolerances);
+        assert!(matches!(
+            loaded.analyses.as_slice(),
+            [SimulationAnalysis::Tran { params, .. }]
+                if (params.tstep - 10e-6).abs() < 1e-15 && params.be_startup_steps == 2
+        ));
+
+        // The reloaded setup must simulate bit-for-bit like the original.
+        let (op_a, tran_a) = run(&setup);
+        let (op_b, tran_b) = run(&loaded);
+        assert_eq!(op_a, op_b);
+        assert_eq!(tran_a, tran_b);
+        assert!(tran_a.len() > 50);
+    }
+
+    #[test]
+    fn test_setup_defaults_when_fields_missing() {
+        let json = r#"{
+            "netlist": "Divider\nV1 1 0 10\nR1 1 2 1k\nR2 2 0 1k\n.end\n",
+            "analyses": [
+                {"type": "op"},
+                {"type": "ac", "params": {"fstart": 1.0, "fstop": 1e6, "num_points": 10, "sweep_type": "Decade"}},
+                {"type": "tran", "params": {"tstop": 1e-3, "tstep": 1e-6, "method": "TrBdf2"}}
+            ],
+            "tolerances": {"v_reltol": 1e-4}
+        }"#;
+        let setup: SimulationSetup = serde_json::from_str(json).unwrap();
+
+        assert_eq!(setup.tolerances.v_reltol, 1e-4);
+        assert_eq!(
+            setup.tolerances.max_iterations,
+            ConvergenceCriteria::default().max_iterations
+        );
+        match &setup.analyses[2] {
+            SimulationAnalysis::Tran {
+                params,
+                tstart,
+                uic,
+            } => {
+                assert_eq!(params.method, IntegrationMethod::TrBdf2);
+                assert_eq!(params.be_startup_steps, 0);
+                assert_eq!(*tstart, 0.0);
+                assert!(!uic);
+            }
+            other => panic!("unexpected analysis {other:?}"),
+        }
+        assert_eq!(setup.parse().unwrap().netlist.num_nodes(), 2);
+    }
+}
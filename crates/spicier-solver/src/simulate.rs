@@ -0,0 +1,437 @@
+//! One-call simulation of a SPICE netlist.
+//!
+//! [`simulate`] parses a netlist, runs every `.OP`, `.DC`, `.AC` and `.TRAN`
+//! directive in it and collects the results, addressable by node name:
+//!
+//! ```rust
+//! let results = spicier_solver::simulate(
+//!     "Divider
+//! V1 in 0 10
+//! R1 in out 1k
+//! R2 out 0 1k
+//! .op
+//! .end
+//! ",
+//! )
+//! .unwrap();
+//! assert!((results.op_voltage("out").unwrap() - 5.0).abs() < 1e-9);
+//! ```
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use nalgebra::DVector;
+use num_complex::Complex;
+use spicier_core::{Netlist, NodeId, ParameterHandle};
+use spicier_parser::{InitialCondition, ParseResult};
+
+use crate::ac::{AcResult, solve_ac};
+use crate::dc::{
+    DcSolution, DcSweepParams, DcSweepResult, NestedDcSweepResult, solve_dc, sweep_values,
+};
+use crate::error::{Error, Result};
+use crate::netlist_ac::NetlistAcStamper;
+use crate::netlist_stampers::{
+    NetlistNonlinearStamper, NetlistTransientStamper, build_transient_state,
+};
+use crate::newton::{
+    ConvergenceCriteria, DcSolverStrategy, solve_newton_raphson, solve_with_strategy,
+};
+use crate::setup::{SimulationAnalysis, SimulationSetup};
+use crate::transient::solve_transient_with_progress;
+use crate::transient::{InitialConditions, TimePoint, TransientParams, TransientResult};
+
+/// The result of one analysis directive.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AnalysisResult {
+    /// `.OP` operating point.
+    Op(DcSolution),
+    /// `.DC` sweep of one variable.
+    Dc(DcSweepResult),
+    /// `.DC` sweep of two variables.
+    NestedDc(NestedDcSweepResult),
+    /// `.AC` small-signal sweep.
+    Ac(AcResult),
+    /// `.TRAN` waveforms, starting at the directive's `tstart`.
+    Tran(TransientResult),
+}
+
+/// Results of every analysis in a netlist, in directive order.
+#[derive(Debug, Clone)]
+pub struct SimulationResults {
+    /// Node name to node id mapping from the parsed netlist.
+    pub node_map: HashMap<String, NodeId>,
+    /// One entry per analysis, in the order they appear in the netlist.
+    pub analyses: Vec<AnalysisResult>,
+}
+
+/// What an analysis needs from the parsed netlist besides the circuit.
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisContext<'a> {
+    /// `.IC` node voltages, applied to the start of a transient run.
+    pub initial_conditions: &'a [InitialCondition],
+    /// Node name to node id mapping, used to resolve `.IC` node names.
+    pub node_map: &'a HashMap<String, NodeId>,
+    /// Newton-Raphson tolerances for operating points and sweeps.
+    pub tolerances: &'a ConvergenceCriteria,
+}
+
+/// Parse `netlist` and run every analysis it contains.
+///
+/// Shorthand for [`SimulationSetup::from_spice`] followed by
+/// [`SimulationSetup::run`] with default tolerances.
+pub fn simulate(netlist: &str) -> Result<SimulationResults> {
+    SimulationSetup::from_spice(netlist)?.run()
+}
+
+impl SimulationSetup {
+    /// Parse the netlist and run each analysis in order.
+    ///
+    /// DC sweeps address their variables with [`ParameterHandle`], so
+    /// sources, passive values and `.PARAM`s can all be swept; the swept
+    /// value is restored before the next analysis runs.
+    pub fn run(&self) -> Result<SimulationResults> {
+        let ParseResult {
+            mut netlist,
+            initial_conditions,
+            node_map,
+            ..
+        } = self.parse()?;
+
+        let context = AnalysisContext {
+            initial_conditions: &initial_conditions,
+            node_map: &node_map,
+            tolerances: &self.tolerances,
+        };
+        let analyses = self
+            .analyses
+            .iter()
+            .map(|analysis| analysis.run(&mut netlist, &context))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SimulationResults { node_map, analyses })
+    }
+}
+
+impl SimulationAnalysis {
+    /// Run this analysis on an already-built circuit.
+    ///
+    /// This is the step [`SimulationSetup::run`] repeats for each directive;
+    /// callers that prepare the netlist themselves (temperature, probes,
+    /// topology repair) run their analyses through it directly. A DC sweep
+    /// restores the swept values before returning.
+    pub fn run(
+        &self,
+        netlist: &mut Netlist,
+        context: &AnalysisContext<'_>,
+    ) -> Result<AnalysisResult> {
+        self.run_with_progress(netlist, context, &mut |_| ControlFlow::Continue(()))
+    }
+
+    /// [`run`](Self::run), calling `on_step` after every transient timepoint.
+    ///
+    /// Returning [`ControlFlow::Break`] stops a transient run early, keeping
+    /// the points computed so far. Other analyses never call `on_step`.
+    /// A node with no connection to ground fails up front with
+    /// [`spicier_core::Error::NoGroundReference`].
+    pub fn run_with_progress(
+        &self,
+        netlist: &mut Netlist,
+        context: &AnalysisContext<'_>,
+        on_step: &mut dyn FnMut(&TimePoint) -> ControlFlow<()>,
+    ) -> Result<AnalysisResult> {
+        netlist.check_ground_reference()?;
+        Ok(match self {
+            SimulationAnalysis::Op => AnalysisResult::Op(dc_solution(
+                &operating_point(netlist, context.tolerances, None)?,
+                netlist.num_nodes(),
+            )),
+            SimulationAnalysis::Dc { sweeps } => dc_sweep(netlist, sweeps, context.tolerances)?,
+            SimulationAnalysis::Ac { params } => {
+                let op = operating_point(netlist, context.tolerances, None)?;
+                AnalysisResult::Ac(solve_ac(
+                    &NetlistAcStamper::new(netlist, Some(&op)),
+                    params,
+                )?)
+            }
+            SimulationAnalysis::Tran {
+                params,
+                tstart,
+                uic,
+            } => AnalysisResult::Tran(transient(netlist, params, *tstart, *uic, context, on_step)?),
+        })
+    }
+}
+
+impl SimulationResults {
+    /// Look up a node by name, falling back to a case-insensitive match.
+    pub fn node(&self, name: &str) -> Option<NodeId> {
+        self.node_map
+            .get(name)
+            .or_else(|| {
+                self.node_map
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                    .map(|(_, id)| id)
+            })
+            .copied()
+    }
+
+    /// The first `.OP` result.
+    pub fn op(&self) -> Option<&DcSolution> {
+        self.analyses.iter().find_map(|a| match a {
+            AnalysisResult::Op(sol) => Some(sol),
+            _ => None,
+        })
+    }
+
+    /// The first single-variable `.DC` result.
+    pub fn dc(&self) -> Option<&DcSweepResult> {
+        self.analyses.iter().find_map(|a| match a {
+            AnalysisResult::Dc(sweep) => Some(sweep),
+            _ => None,
+        })
+    }
+
+    /// The first two-variable `.DC` result.
+    pub fn nested_dc(&self) -> Option<&NestedDcSweepResult> {
+        self.analyses.iter().find_map(|a| match a {
+            AnalysisResult::NestedDc(sweep) => Some(sweep),
+            _ => None,
+        })
+    }
+
+    /// The first `.AC` result.
+    pub fn ac(&self) -> Option<&AcResult> {
+        self.analyses.iter().find_map(|a| match a {
+            AnalysisResult::Ac(ac) => Some(ac),
+            _ => None,
+        })
+    }
+
+    /// The first `.TRAN` result.
+    pub fn tran(&self) -> Option<&TransientResult> {
+        self.analyses.iter().find_map(|a| match a {
+            AnalysisResult::Tran(tran) => Some(tran),
+            _ => None,
+        })
+    }
+
+    /// Operating-point voltage of a named node.
+    pub fn op_voltage(&self, node: &str) -> Option<f64> {
+        Some(self.op()?.voltage(self.node(node)?))
+    }
+
+    /// `(sweep value, voltage)` of a named node over the first `.DC` sweep.
+    pub fn dc_voltage(&self, node: &str) -> Option<Vec<(f64, f64)>> {
+        Some(self.dc()?.voltage_waveform(self.node(node)?))
+    }
+
+    /// `(frequency, voltage)` of a named node over the first `.AC` sweep.
+    pub fn ac_voltage(&self, node: &str) -> Option<Vec<(f64, Complex<f64>)>> {
+        let ac = self.ac()?;
+        match node_index(self.node(node)?) {
+            Some(idx) => Some(ac.voltage_at(idx)),
+            None => Some(
+                ac.frequencies()
+                    .into_iter()
+                    .map(|f| (f, Complex::new(0.0, 0.0)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// `(time, voltage)` of a named node over the first `.TRAN` run.
+    pub fn tran_voltage(&self, node: &str) -> Option<Vec<(f64, f64)>> {
+        let tran = self.tran()?;
+        match node_index(self.node(node)?) {
+            Some(idx) => Some(tran.voltage_waveform(idx)),
+            None => Some(tran.times().into_iter().map(|t| (t, 0.0)).collect()),
+        }
+    }
+}
+
+/// MNA row of a node, or `None` for ground.
+fn node_index(node: NodeId) -> Option<usize> {
+    (!node.is_ground()).then(|| node.as_u32() as usize - 1)
+}
+
+/// Split a full solution vector into node voltages and branch currents.
+fn dc_solution(solution: &DVector<f64>, num_nodes: usize) -> DcSolution {
+    DcSolution {
+        node_voltages: solution.rows(0, num_nodes).into_owned(),
+        branch_currents: solution
+            .rows(num_nodes, solution.len() - num_nodes)
+            .into_owned(),
+        num_nodes,
+    }
+}
+
+/// Solve the DC operating point, returning the full solution vector.
+///
+/// A nonlinear circuit first tries Newton-Raphson from `guess`, if given,
+/// then the default convergence aids (Gmin and source stepping).
+fn operating_point(
+    netlist: &Netlist,
+    criteria: &ConvergenceCriteria,
+    guess: Option<&DVector<f64>>,
+) -> Result<DVector<f64>> {
+    if netlist.has_nonlinear_devices() {
+        let (num_nodes, num_vsources) = (netlist.num_nodes(), netlist.num_current_vars());
+        let stamper = NetlistNonlinearStamper { netlist };
+        if let Some(guess) = guess {
+            match solve_newton_raphson(num_nodes, num_vsources, &stamper, criteria, Some(guess)) {
+                Ok(nr) if nr.converged => return Ok(nr.solution),
+                _ => {}
+            }
+        }
+        let strategy = DcSolverStrategy::default();
+        Ok(solve_with_strategy(num_nodes, num_vsources, &stamper, criteria, &strategy)?.solution)
+    } else {
+        let dc = solve_dc(&netlist.assemble_mna())?;
+        let mut full = DVector::zeros(dc.num_nodes + dc.branch_currents.len());
+        full.rows_mut(0, dc.num_nodes).copy_from(&dc.node_voltages);
+        full.rows_mut(dc.num_nodes, dc.branch_currents.len())
+            .copy_from(&dc.branch_currents);
+        Ok(full)
+    }
+}
+
+/// Handle for a swept name: a `.PARAM` if one is defined and no device
+/// shares the name, otherwise a source, device or model parameter.
+fn sweep_handle(netlist: &Netlist, name: &str) -> ParameterHandle {
+    let is_device = netlist
+        .devices()
+        .iter()
+        .any(|d| d.device_name().eq_ignore_ascii_case(name));
+    if !is_device && netlist.parameters().param(name).is_some() {
+        ParameterHandle::Param(name.to_string())
+    } else {
+        ParameterHandle::parse(name)
+    }
+}
+
+/// Run a one- or two-variable DC sweep, warm-starting each point from the
+/// previous one, and restore the swept values afterwards.
+fn dc_sweep(
+    netlist: &mut Netlist,
+    sweeps: &[DcSweepParams],
+    criteria: &ConvergenceCriteria,
+) -> Result<AnalysisResult> {
+    let handles: Vec<_> = sweeps
+        .iter()
+        .map(|s| sweep_handle(netlist, &s.source_name))
+        .collect();
+    let originals = handles
+        .iter()
+        .map(|h| h.get(netlist))
+        .collect::<spicier_core::Result<Vec<_>>>()?;
+
+    let mut guess: Option<DVector<f64>> = None;
+    let mut solve_at = |netlist: &mut Netlist, values: &[(usize, f64)]| -> Result<DcSolution> {
+        for &(i, value) in values {
+            handles[i].set(netlist, value)?;
+        }
+        let x = operating_point(netlist, criteria, guess.as_ref())?;
+        let sol = dc_solution(&x, netlist.num_nodes());
+        guess = Some(x);
+        Ok(sol)
+    };
+
+    let result = match sweeps {
+        [sweep] => {
+            let values = sweep_values(sweep);
+            values
+                .iter()
+                .map(|&v| solve_at(netlist, &[(0, v)]))
+                .collect::<Result<Vec<_>>>()
+                .map(|solutions| {
+                    AnalysisResult::Dc(DcSweepResult {
+                        source_name: sweep.source_name.clone(),
+                        sweep_values: values,
+                        solutions,
+                    })
+                })
+        }
+        [outer, inner] => {
+            let outer_values = sweep_values(outer);
+            let inner_values = sweep_values(inner);
+            outer_values
+                .iter()
+                .map(|&ov| {
+                    inner_values
+                        .iter()
+                        .map(|&iv| solve_at(netlist, &[(0, ov), (1, iv)]))
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(|solutions| {
+                    AnalysisResult::NestedDc(NestedDcSweepResult {
+                        outer_source: outer.source_name.clone(),
+                        inner_source: inner.source_name.clone(),
+                        outer_values,
+                        inner_values,
+                        solutions,
+                    })
+                })
+        }
+        _ => Err(Error::SolverError(format!(
+            ".DC needs one or two sweep variables, got {}",
+            sweeps.len()
+        ))),
+    };
+
+    for (handle, value) in handles.iter().zip(originals) {
+        handle.set(netlist, value)?;
+    }
+    result
+}
+
+/// Run a transient analysis: start from the operating point (or zero with
+/// `uic`), apply `.IC` values, and drop points before `tstart`.
+fn transient(
+    netlist: &Netlist,
+    params: &TransientParams,
+    tstart: f64,
+    uic: bool,
+    context: &AnalysisContext<'_>,
+    on_step: &mut dyn FnMut(&TimePoint) -> ControlFlow<()>,
+) -> Result<TransientResult> {
+    let mut initial = if uic {
+        DVector::zeros(netlist.num_nodes() + netlist.num_current_vars())
+    } else {
+        operating_point(netlist, context.tolerances, None)?
+    };
+
+    if !context.initial_conditions.is_empty() {
+        let mut ic = InitialConditions::new();
+        for parsed in context.initial_conditions {
+            ic.set_voltage(&parsed.node, parsed.voltage);
+        }
+        let index_map: HashMap<String, usize> = context
+            .node_map
+            .iter()
+            .filter_map(|(name, &id)| node_index(id).map(|idx| (name.clone(), idx)))
+            .collect();
+        ic.apply(&mut initial, &index_map);
+    }
+
+    let (mut caps, mut inds) = build_transient_state(netlist);
+    let stamper = NetlistTransientStamper::new(netlist);
+
+    // Step onto every source corner as well as the requested breakpoints
+    let mut params = params.clone();
+    params
+        .breakpoints
+        .extend(netlist.source_breakpoints(params.tstop));
+
+    // The full DC vector goes in: inductors and transmission lines read their
+    // initial currents from the branch currents the transient system drops.
+    let mut result =
+        solve_transient_with_progress(&stamper, &mut caps, &mut inds, &params, &initial, on_step)?;
+    result
+        .points
+        .retain(|point| point.time >= tstart - params.tstep * 0.5);
+    Ok(result)
+}
@@ -0,0 +1,382 @@
+//! Two-port scattering parameters from AC analysis.
+//!
+//! Both ports are terminated in the reference impedance `Z0`. Each port in
+//! turn is driven by a matched source, a Thevenin voltage `Vs` behind `Z0`,
+//! and the power waves at every port follow from its voltage `V` and the
+//! current `I` flowing into the circuit:
+//!
+//! ```text
+//! a = (V + Z0·I) / (2√Z0)     b = (V - Z0·I) / (2√Z0)
+//! S_ij = b_i / a_j            (all other ports matched, a_k = 0)
+//! ```
+//!
+//! The two excitations share one matrix per frequency, so the sweep costs
+//! one factorization and two solves per point.
+
+use std::f64::consts::PI;
+use std::fmt::Write as _;
+
+use nalgebra::DVector;
+use num_complex::Complex;
+
+use crate::ac::{AcParams, AcStamper, ComplexMna, generate_frequencies};
+use crate::error::{Error, Result};
+use crate::linear::{CachedSparseLuComplex, SPARSE_THRESHOLD};
+
+/// Default reference impedance (Ω).
+pub const DEFAULT_REFERENCE_IMPEDANCE: f64 = 50.0;
+
+/// A port: the node the wave enters at and its return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SParamPort {
+    /// Positive terminal (0-based node index).
+    pub node_pos: usize,
+    /// Negative terminal (0-based node index), or None for ground.
+    pub node_neg: Option<usize>,
+}
+
+impl SParamPort {
+    /// A port from `node_pos` to ground.
+    pub fn grounded(node_pos: usize) -> Self {
+        Self {
+            node_pos,
+            node_neg: None,
+        }
+    }
+}
+
+/// Ports and reference impedance for [`solve_sparameters`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TwoPort {
+    /// Port 1.
+    pub port1: SParamPort,
+    /// Port 2.
+    pub port2: SParamPort,
+    /// Reference impedance for both ports (Ω).
+    pub z0: f64,
+}
+
+impl TwoPort {
+    /// A two-port referenced to [`DEFAULT_REFERENCE_IMPEDANCE`].
+    pub fn new(port1: SParamPort, port2: SParamPort) -> Self {
+        Self {
+            port1,
+            port2,
+            z0: DEFAULT_REFERENCE_IMPEDANCE,
+        }
+    }
+
+    /// Use a different reference impedance.
+    pub fn with_z0(mut self, z0: f64) -> Self {
+        self.z0 = z0;
+        self
+    }
+}
+
+/// S-parameters at one frequency.
+#[derive(Debug, Clone, Copy)]
+pub struct SParamPoint {
+    /// Frequency (Hz).
+    pub frequency: f64,
+    /// Input reflection coefficient.
+    pub s11: Complex<f64>,
+    /// Forward transmission coefficient.
+    pub s21: Complex<f64>,
+    /// Reverse transmission coefficient.
+    pub s12: Complex<f64>,
+    /// Output reflection coefficient.
+    pub s22: Complex<f64>,
+}
+
+/// Result of [`solve_sparameters`].
+#[derive(Debug, Clone)]
+pub struct SParamResult {
+    /// Reference impedance the parameters are normalized to (Ω).
+    pub z0: f64,
+    /// S-parameters at each sweep frequency.
+    pub points: Vec<SParamPoint>,
+}
+
+impl SParamResult {
+    /// Get all frequency values.
+    pub fn frequencies(&self) -> Vec<f64> {
+        self.points.iter().map(|p| p.frequency).collect()
+    }
+
+    /// `(frequency, |S21|)` in dB across all frequencies.
+    pub fn s21_db(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|p| (p.frequency, 20.0 * p.s21.norm().log10()))
+            .collect()
+    }
+
+    /// Format as a Touchstone 1.x `.s2p` file in real/imaginary form.
+    ///
+    /// Each data line holds the frequency followed by S11, S21, S12 and
+    /// S22, the column order Touchstone uses for two-ports.
+    pub fn touchstone_string(&self) -> String {
+        let mut s2p = String::from("! Two-port S-parameters\n");
+        let _ = writeln!(s2p, "# Hz S RI R {}", self.z0);
+        for p in &self.points {
+            let _ = write!(s2p, "{:.9e}", p.frequency);
+            for s in [p.s11, p.s21, p.s12, p.s22] {
+                let _ = write!(s2p, " {:.9e} {:.9e}", s.re, s.im);
+            }
+            s2p.push('\n');
+        }
+        s2p
+    }
+
+    /// Write a Touchstone `.s2p` file.
+    pub fn to_touchstone(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.touchstone_string())
+    }
+}
+
+/// Compute the S-parameters of a two-port across an AC sweep.
+///
+/// `stamper` is the small-signal circuit without port terminations; the
+/// reference impedances are added here. Whatever right-hand side it stamps
+/// is discarded, so independent AC sources do not disturb the waves.
+pub fn solve_sparameters(
+    stamper: &dyn AcStamper,
+    two_port: TwoPort,
+    params: &AcParams,
+) -> Result<SParamResult> {
+    let num_nodes = stamper.num_nodes();
+    let ports = [two_port.port1, two_port.port2];
+    for port in ports {
+        for node in std::iter::once(port.node_pos).chain(port.node_neg) {
+            if node >= num_nodes {
+                return Err(Error::IndexOutOfRange {
+                    what: "port node",
+                    index: node,
+                    len: num_nodes,
+                });
+            }
+        }
+        if port.node_neg == Some(port.node_pos) {
+            return Err(Error::SolverError(
+                "port must connect two different nodes".into(),
+            ));
+        }
+    }
+    if two_port.z0.is_nan() || two_port.z0 <= 0.0 {
+        return Err(Error::SolverError(format!(
+            "reference impedance must be positive, got {}",
+            two_port.z0
+        )));
+    }
+
+    let z0 = two_port.z0;
+    let sqrt_z0 = z0.sqrt();
+    let mut cached = None;
+    let mut points = Vec::new();
+
+    for frequency in generate_frequencies(params) {
+        let mut mna = ComplexMna::new(num_nodes, stamper.num_vsources());
+        stamper.stamp_ac(&mut mna, 2.0 * PI * frequency);
+        for port in ports {
+            mna.stamp_conductance(Some(port.node_pos), port.node_neg, 1.0 / z0);
+        }
+
+        // Drive each port in turn with a 1V source behind Z0, as its Norton
+        // equivalent; both excitations share one factorization
+        let vs = 1.0;
+        let excitations: Vec<_> = ports
+            .iter()
+            .map(|driven| {
+                let mut rhs = DVector::zeros(mna.size());
+                rhs[driven.node_pos] += Complex::new(vs / z0, 0.0);
+                if let Some(neg) = driven.node_neg {
+                    rhs[neg] -= Complex::new(vs / z0, 0.0);
+                }
+                rhs
+            })
+            .collect();
+        let solutions = solve(&mna, &excitations, &mut cached)?;
+
+        // s[i][j] = b_i / a_j with port j driven
+        let mut s = [[Complex::new(0.0, 0.0); 2]; 2];
+        for (j, solution) in solutions.iter().enumerate() {
+            let a_j = vs / (2.0 * sqrt_z0);
+            for (i, port) in ports.iter().enumerate() {
+                let v = solution[port.node_pos]
+                    - port
+                        .node_neg
+                        .map_or(Complex::new(0.0, 0.0), |n| solution[n]);
+                // Current into the circuit: what the source side delivers
+                // minus what the termination draws
+                let source = if i == j { vs / z0 } else { 0.0 };
+                let current = source - v / z0;
+                let b_i = (v - z0 * current) / (2.0 * sqrt_z0);
+                s[i][j] = b_i / a_j;
+            }
+        }
+
+        points.push(SParamPoint {
+            frequency,
+            s11: s[0][0],
+            s21: s[1][0],
+            s12: s[0][1],
+            s22: s[1][1],
+        });
+    }
+
+    Ok(SParamResult { z0, points })
+}
+
+/// Solve every excitation with one factorization of the system, reusing
+/// the sparse factorization's symbolic analysis across frequencies.
+fn solve(
+    mna: &ComplexMna,
+    rhs: &[DVector<Complex<f64>>],
+    cached: &mut Option<CachedSparseLuComplex>,
+) -> Result<Vec<DVector<Complex<f64>>>> {
+    if mna.size() >= SPARSE_THRESHOLD {
+        let solver = match cached {
+            Some(s) => s,
+            None => cached.insert(CachedSparseLuComplex::new(mna.size(), &mna.triplets)?),
+        };
+        solver.solve_multiple(&mna.triplets, rhs)
+    } else {
+        let lu = mna.to_dense_matrix().lu();
+        rhs.iter()
+            .map(|b| lu.solve(b).ok_or(Error::SingularMatrix))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ac::AcSweepType;
+
+    /// A series R + jωL from node 0 to node 1.
+    struct SeriesZ {
+        r: f64,
+        l: f64,
+    }
+
+    impl AcStamper for SeriesZ {
+        fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+            let z = Complex::new(self.r, omega * self.l);
+            mna.stamp_admittance(Some(0), Some(1), z.inv());
+        }
+
+        fn num_nodes(&self) -> usize {
+            2
+        }
+
+        fn num_vsources(&self) -> usize {
+            0
+        }
+    }
+
+    fn params() -> AcParams {
+        AcParams {
+            fstart: 1e3,
+            fstop: 1e9,
+            num_points: 2,
+            sweep_type: AcSweepType::Decade,
+        }
+    }
+
+    #[test]
+    fn test_series_resistor_sparameters() {
+        // S21 = 2·Z0 / (2·Z0 + R), S11 = R / (2·Z0 + R)
+        let circuit = SeriesZ { r: 50.0, l: 0.0 };
+        let two_port = TwoPort::new(SParamPort::grounded(0), SParamPort::grounded(1));
+        let result = solve_sparameters(&circuit, two_port, &params()).unwrap();
+
+        assert_eq!(result.z0, 50.0);
+        assert_eq!(result.points.len(), 13);
+        for p in &result.points {
+            assert!((p.s21 - Complex::new(2.0 / 3.0, 0.0)).norm() < 1e-12);
+            assert!((p.s11 - Complex::new(1.0 / 3.0, 0.0)).norm() < 1e-12);
+            // Reciprocal and symmetric
+            assert!((p.s12 - p.s21).norm() < 1e-12);
+            assert!((p.s22 - p.s11).norm() < 1e-12);
+        }
+        for (_, db) in result.s21_db() {
+            assert!((db - 20.0 * (2.0f64 / 3.0).log10()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_reference_impedance_and_lossless_series_inductor() {
+        // A series R of 75Ω in a 75Ω system: S21 = 2/3 again.
+        let circuit = SeriesZ { r: 75.0, l: 0.0 };
+        let two_port = TwoPort::new(SParamPort::grounded(0), SParamPort::grounded(1)).with_z0(75.0);
+        let result = solve_sparameters(&circuit, two_port, &params()).unwrap();
+        assert!((result.points[0].s21.re - 2.0 / 3.0).abs() < 1e-12);
+
+        // A series inductor is lossless: |S11|² + |S21|² = 1.
+        let circuit = SeriesZ { r: 0.0, l: 100e-9 };
+        let two_port = TwoPort::new(SParamPort::grounded(0), SParamPort::grounded(1));
+        let result = solve_sparameters(&circuit, two_port, &params()).unwrap();
+        for p in &result.points {
+            assert!((p.s11.norm_sqr() + p.s21.norm_sqr() - 1.0).abs() < 1e-9);
+        }
+        // Transparent at low frequency, reflecting at high frequency
+        assert!((result.points[0].s21.norm() - 1.0).abs() < 1e-6);
+        assert!(result.points.last().unwrap().s11.norm() > 0.9);
+    }
+
+    #[test]
+    fn test_touchstone_output() {
+        let circuit = SeriesZ { r: 50.0, l: 0.0 };
+        let two_port = TwoPort::new(SParamPort::grounded(0), SParamPort::grounded(1));
+        let result = solve_sparameters(
+            &circuit,
+            two_port,
+            &AcParams {
+                fstart: 1e6,
+                fstop: 2e6,
+                num_points: 2,
+                sweep_type: AcSweepType::Linear,
+            },
+        )
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!("spicier-sparams-{}.s2p", std::process::id()));
+        result.to_touchstone(&path).unwrap();
+        let s2p = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut lines = s2p.lines().filter(|l| !l.starts_with('!'));
+        assert_eq!(lines.next(), Some("# Hz S RI R 50"));
+        let data: Vec<Vec<f64>> = lines
+            .map(|l| l.split_whitespace().map(|v| v.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].len(), 9);
+        assert!((data[1][0] - 2e6).abs() < 1e-3);
+        // S11, S21, S12, S22 real parts
+        for (col, expected) in [
+            (1, 1.0 / 3.0),
+            (3, 2.0 / 3.0),
+            (5, 2.0 / 3.0),
+            (7, 1.0 / 3.0),
+        ] {
+            assert!((data[0][col] - expected).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_port_validation() {
+        let circuit = SeriesZ { r: 50.0, l: 0.0 };
+        let bad_node = TwoPort::new(SParamPort::grounded(0), SParamPort::grounded(2));
+        assert!(matches!(
+            solve_sparameters(&circuit, bad_node, &params()),
+            Err(Error::IndexOutOfRange {
+                what: "port node",
+                index: 2,
+                len: 2
+            })
+        ));
+        let bad_z0 = TwoPort::new(SParamPort::grounded(0), SParamPort::grounded(1)).with_z0(0.0);
+        assert!(solve_sparameters(&circuit, bad_z0, &params()).is_err());
+    }
+}
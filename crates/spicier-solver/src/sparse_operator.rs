@@ -12,28 +12,96 @@ use num_complex::Complex64 as C64;
 ///
 /// Wraps a faer `SparseColMat<usize, f64>` and implements `RealOperator`,
 /// enabling its use with real-valued iterative solvers.
+///
+/// An operator built from a triplet pattern remembers where each triplet
+/// lands in the compressed value array, so a new set of values in the same
+/// order (say, the next transient timestep's stamps) can be loaded with
+/// [`update_values`](Self::update_values) without rebuilding the structure.
 pub struct SparseRealOperator {
     matrix: SparseColMat<usize, f64>,
+    /// `(row, col)` of each triplet the operator was built from.
+    pattern: Vec<(usize, usize)>,
+    /// Index into the CSC value array for each entry of `pattern`.
+    slots: Vec<usize>,
 }
 
 impl SparseRealOperator {
     /// Create from an existing sparse matrix.
+    ///
+    /// The operator has no triplet pattern, so
+    /// [`update_values`](Self::update_values) always fails on it.
     pub fn from_matrix(matrix: SparseColMat<usize, f64>) -> Self {
-        Self { matrix }
+        Self {
+            matrix,
+            pattern: Vec::new(),
+            slots: Vec::new(),
+        }
+    }
+
+    /// Create the structure for a triplet pattern, with all values zero.
+    ///
+    /// Positions may repeat; their values are summed by
+    /// [`update_values`](Self::update_values).
+    pub fn from_pattern(size: usize, pattern: &[(usize, usize)]) -> Option<Self> {
+        let faer_triplets: Vec<_> = pattern
+            .iter()
+            .map(|&(r, c)| Triplet::new(r, c, 0.0))
+            .collect();
+        let matrix =
+            SparseColMat::<usize, f64>::try_new_from_triplets(size, size, &faer_triplets).ok()?;
+
+        let mat_ref = matrix.as_ref();
+        let (col_ptrs, row_indices) = (mat_ref.col_ptr(), mat_ref.row_idx());
+        let slots = pattern
+            .iter()
+            .map(|&(r, c)| {
+                let start = col_ptrs[c];
+                row_indices[start..col_ptrs[c + 1]]
+                    .iter()
+                    .position(|&i| i == r)
+                    .map(|offset| start + offset)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            matrix,
+            pattern: pattern.to_vec(),
+            slots,
+        })
     }
 
     /// Create from triplets (row, col, value).
     ///
     /// Duplicate entries at the same position are summed.
     pub fn from_triplets(size: usize, triplets: &[(usize, usize, f64)]) -> Option<Self> {
-        let faer_triplets: Vec<_> = triplets
-            .iter()
-            .map(|&(r, c, v)| Triplet::new(r, c, v))
-            .collect();
+        let pattern: Vec<_> = triplets.iter().map(|&(r, c, _)| (r, c)).collect();
+        let mut op = Self::from_pattern(size, &pattern)?;
+        op.update_values(triplets);
+        Some(op)
+    }
 
-        SparseColMat::<usize, f64>::try_new_from_triplets(size, size, &faer_triplets)
-            .ok()
-            .map(|matrix| Self { matrix })
+    /// Replace the matrix values with `triplets`, keeping the structure.
+    ///
+    /// `triplets` must list the same positions in the same order as the
+    /// pattern the operator was built from. Returns `false`, leaving the
+    /// values untouched, if they do not; the caller should then rebuild
+    /// with [`from_triplets`](Self::from_triplets).
+    pub fn update_values(&mut self, triplets: &[(usize, usize, f64)]) -> bool {
+        if triplets.len() != self.pattern.len()
+            || triplets
+                .iter()
+                .zip(&self.pattern)
+                .any(|(&(r, c, _), &pos)| (r, c) != pos)
+        {
+            return false;
+        }
+
+        let values = self.matrix.val_mut();
+        values.fill(0.0);
+        for (&(_, _, v), &slot) in triplets.iter().zip(&self.slots) {
+            values[slot] += v;
+        }
+        true
     }
 
     /// Get a reference to the underlying matrix.
@@ -108,6 +176,14 @@ impl SparseComplexOperator {
     pub fn matrix(&self) -> &SparseColMat<usize, c64> {
         &self.matrix
     }
+
+    /// Mutable access to the stored values, in CSC order.
+    ///
+    /// Lets a caller refill the matrix for a new set of values with the
+    /// same sparsity pattern without rebuilding its structure.
+    pub fn values_mut(&mut self) -> &mut [c64] {
+        self.matrix.val_mut()
+    }
 }
 
 impl ComplexOperator for SparseComplexOperator {
@@ -193,6 +269,50 @@ mod tests {
         assert!((y[2] - 4.0).abs() < 1e-15);
     }
 
+    #[test]
+    fn sparse_real_update_values_matches_rebuild() {
+        // Same positions as an MNA stamp, including a repeated diagonal
+        let stamp = |g: f64| {
+            vec![
+                (0, 0, g),
+                (0, 1, -g),
+                (1, 0, -g),
+                (1, 1, g),
+                (1, 1, 2.0 * g),
+                (2, 1, 1.0),
+                (1, 2, 1.0),
+            ]
+        };
+        let x = vec![0.5, -1.5, 2.0];
+
+        let mut op = SparseRealOperator::from_triplets(3, &stamp(1.0)).unwrap();
+        for g in [1e-3, 2.5, 40.0] {
+            assert!(op.update_values(&stamp(g)));
+            let fresh = SparseRealOperator::from_triplets(3, &stamp(g)).unwrap();
+
+            let mut y = vec![0.0; 3];
+            let mut y_fresh = vec![0.0; 3];
+            op.apply(&x, &mut y);
+            fresh.apply(&x, &mut y_fresh);
+            assert_eq!(y, y_fresh);
+        }
+    }
+
+    #[test]
+    fn sparse_real_update_values_rejects_other_pattern() {
+        let pattern = [(0, 0), (1, 1)];
+        let mut op = SparseRealOperator::from_pattern(2, &pattern).unwrap();
+        assert!(op.update_values(&[(0, 0, 2.0), (1, 1, 3.0)]));
+
+        assert!(!op.update_values(&[(1, 1, 3.0), (0, 0, 2.0)]));
+        assert!(!op.update_values(&[(0, 0, 2.0)]));
+
+        // The rejected updates left the values alone
+        let mut y = vec![0.0; 2];
+        op.apply(&[1.0, 1.0], &mut y);
+        assert_eq!(y, vec![2.0, 3.0]);
+    }
+
     #[test]
     fn sparse_complex_identity() {
         let triplets = vec![(0, 0, C64::new(1.0, 0.0)), (1, 1, C64::new(1.0, 0.0))];
@@ -0,0 +1,417 @@
+//! Linearized state-space model at the operating point.
+//!
+//! The small-signal MNA system is the descriptor system
+//!
+//! ```text
+//! C ẋ + G x = Bu u,    y = L x
+//! ```
+//!
+//! where `G + jωC` is what an [`AcStamper`] stamps. [`solve_state_space`]
+//! splits `C` with an SVD into its dynamic and algebraic parts, eliminates
+//! the algebraic unknowns (assuming the algebraic block of `G` is
+//! invertible, i.e. an index-1 circuit), and returns the explicit model
+//!
+//! ```text
+//! ẋs = A xs + B u,    y = C xs + D u
+//! ```
+//!
+//! The states `xs` are a rotation of the capacitor voltages and inductor
+//! currents rather than the raw MNA unknowns; the input-output behaviour and
+//! the eigenvalues of `A` are what carry over to a control toolbox.
+
+use nalgebra::{DMatrix, DVector};
+use num_complex::Complex;
+
+use crate::ac::{AcStamper, ComplexMna};
+use crate::error::{Error, Result};
+
+/// An input to the state-space model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateSpaceInput {
+    /// The value of a voltage source, by branch index.
+    Voltage {
+        /// Branch index (0-based).
+        branch_idx: usize,
+    },
+    /// A current injected from `node_neg` into `node_pos` (`None` is ground).
+    Current {
+        /// Node receiving the current.
+        node_pos: Option<usize>,
+        /// Node the current is drawn from.
+        node_neg: Option<usize>,
+    },
+}
+
+/// An output of the state-space model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateSpaceOutput {
+    /// Node voltage V(node_idx).
+    Voltage {
+        /// Node index (0-based, excluding ground).
+        node_idx: usize,
+    },
+    /// Voltage difference V(node_pos) - V(node_neg).
+    VoltageDiff {
+        /// Positive node index.
+        node_pos: usize,
+        /// Negative node index.
+        node_neg: usize,
+    },
+    /// Branch current I(branch_idx).
+    Current {
+        /// Branch index (0-based).
+        branch_idx: usize,
+    },
+}
+
+/// Explicit state-space model `ẋ = A x + B u`, `y = C x + D u`.
+#[derive(Debug, Clone)]
+pub struct StateSpace {
+    /// State matrix (n × n).
+    pub a: DMatrix<f64>,
+    /// Input matrix (n × inputs).
+    pub b: DMatrix<f64>,
+    /// Output matrix (outputs × n).
+    pub c: DMatrix<f64>,
+    /// Feedthrough matrix (outputs × inputs).
+    pub d: DMatrix<f64>,
+}
+
+impl StateSpace {
+    /// Number of states.
+    pub fn num_states(&self) -> usize {
+        self.a.nrows()
+    }
+
+    /// Poles of the model: the eigenvalues of `A`.
+    pub fn poles(&self) -> Vec<Complex<f64>> {
+        self.a.complex_eigenvalues().iter().copied().collect()
+    }
+
+    /// Transfer matrix `C (jωI - A)⁻¹ B + D` at angular frequency `omega`.
+    pub fn transfer(&self, omega: f64) -> Result<DMatrix<Complex<f64>>> {
+        let n = self.num_states();
+        let to_complex = |m: &DMatrix<f64>| m.map(|v| Complex::new(v, 0.0));
+        let lhs =
+            DMatrix::from_diagonal_element(n, n, Complex::new(0.0, omega)) - to_complex(&self.a);
+        let x = lhs
+            .lu()
+            .solve(&to_complex(&self.b))
+            .ok_or(Error::SingularMatrix)?;
+        Ok(to_complex(&self.c) * x + to_complex(&self.d))
+    }
+}
+
+/// Build the state-space model of a small-signal circuit.
+///
+/// `stamper` is the circuit linearized at its operating point (the same
+/// stamper AC analysis uses); its RHS is ignored, and `inputs` defines the
+/// input vector instead. Returns [`Error::SingularMatrix`] if the algebraic
+/// part cannot be eliminated, e.g. for a loop of capacitors and voltage
+/// sources, and [`Error::IndexOutOfRange`] for an input or output that
+/// names a node or branch the circuit does not have.
+pub fn solve_state_space(
+    stamper: &dyn AcStamper,
+    inputs: &[StateSpaceInput],
+    outputs: &[StateSpaceOutput],
+) -> Result<StateSpace> {
+    let num_nodes = stamper.num_nodes();
+    let num_branches = stamper.num_vsources();
+    let size = num_nodes + num_branches;
+
+    // Row/column of a node or branch unknown, checked against the circuit.
+    let node = |index: usize| {
+        if index < num_nodes {
+            Ok(index)
+        } else {
+            Err(Error::IndexOutOfRange {
+                what: "node",
+                index,
+                len: num_nodes,
+            })
+        }
+    };
+    let branch = |index: usize| {
+        if index < num_branches {
+            Ok(num_nodes + index)
+        } else {
+            Err(Error::IndexOutOfRange {
+                what: "branch",
+                index,
+                len: num_branches,
+            })
+        }
+    };
+
+    // Y(ω) = G + jωC, so ω = 0 gives G and ω = 1 adds C.
+    let stamp = |omega: f64| {
+        let mut mna = ComplexMna::new(num_nodes, num_branches);
+        stamper.stamp_ac(&mut mna, omega);
+        mna.to_dense_matrix()
+    };
+    let y0 = stamp(0.0);
+    let y1 = stamp(1.0);
+    let g = y0.map(|v| v.re);
+    let c = DMatrix::from_fn(size, size, |i, j| y1[(i, j)].im - y0[(i, j)].im);
+
+    let mut bu = DMatrix::zeros(size, inputs.len());
+    for (col, input) in inputs.iter().enumerate() {
+        match *input {
+            StateSpaceInput::Voltage { branch_idx } => bu[(branch(branch_idx)?, col)] = 1.0,
+            StateSpaceInput::Current { node_pos, node_neg } => {
+                let node_pos = node_pos.map(node).transpose()?;
+                let node_neg = node_neg.map(node).transpose()?;
+                let mut column = ComplexMna::new(num_nodes, num_branches);
+                column.stamp_current_source(node_pos, node_neg, Complex::new(1.0, 0.0));
+                bu.set_column(col, &column.rhs().map(|v| v.re));
+            }
+        }
+    }
+
+    let mut l = DMatrix::zeros(outputs.len(), size);
+    for (row, output) in outputs.iter().enumerate() {
+        match *output {
+            StateSpaceOutput::Voltage { node_idx } => l[(row, node(node_idx)?)] = 1.0,
+            StateSpaceOutput::VoltageDiff { node_pos, node_neg } => {
+                l[(row, node(node_pos)?)] += 1.0;
+                l[(row, node(node_neg)?)] -= 1.0;
+            }
+            StateSpaceOutput::Current { branch_idx } => l[(row, branch(branch_idx)?)] = 1.0,
+        }
+    }
+
+    // C = U Σ Vᵀ; in the rotated unknowns z = Vᵀx the first `r` equations are
+    // dynamic and the rest purely algebraic.
+    let svd = c.svd(true, true);
+    let (Some(u), Some(v_t)) = (svd.u, svd.v_t) else {
+        return Err(Error::SolverError("SVD of the C matrix failed".into()));
+    };
+    let sigma = svd.singular_values;
+    let tol = sigma.max() * size as f64 * f64::EPSILON;
+    let r = sigma.iter().filter(|&&s| s > tol).count();
+    let v = v_t.transpose();
+
+    let g_hat = u.transpose() * &g * &v;
+    let b_hat = u.transpose() * &bu;
+    let l_hat = &l * &v;
+    let k = size - r;
+
+    let g11 = g_hat.view((0, 0), (r, r));
+    let g12 = g_hat.view((0, r), (r, k));
+    let g21 = g_hat.view((r, 0), (k, r));
+    let g22 = g_hat.view((r, r), (k, k)).clone_owned();
+    let b1 = b_hat.rows(0, r);
+    let b2 = b_hat.rows(r, k);
+    let l1 = l_hat.columns(0, r);
+    let l2 = l_hat.columns(r, k);
+
+    // Algebraic part: z2 = G22⁻¹ (B2 u - G21 z1).
+    let g22_lu = g22.lu();
+    let solve_g22 = |m: DMatrix<f64>| g22_lu.solve(&m).ok_or(Error::SingularMatrix);
+    let g22_g21 = solve_g22(g21.clone_owned())?;
+    let g22_b2 = solve_g22(b2.clone_owned())?;
+
+    let sigma_inv = DMatrix::from_diagonal(&DVector::from_fn(r, |i, _| 1.0 / sigma[i]));
+    Ok(StateSpace {
+        a: -&sigma_inv * (g11 - g12 * &g22_g21),
+        b: &sigma_inv * (b1 - g12 * &g22_b2),
+        c: l1 - l2 * &g22_g21,
+        d: l2 * &g22_b2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ac::{AcParams, AcSweepType, solve_ac};
+
+    /// V1 (branch 0) at node 0, R from 0 to 1, C from 1 to ground.
+    struct Rc {
+        r: f64,
+        c: f64,
+    }
+
+    impl AcStamper for Rc {
+        fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, Complex::new(1.0, 0.0));
+            mna.stamp_conductance(Some(0), Some(1), 1.0 / self.r);
+            mna.stamp_admittance(Some(1), None, Complex::new(0.0, omega * self.c));
+        }
+        fn num_nodes(&self) -> usize {
+            2
+        }
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    /// Series RLC driven by V1, with a floating capacitor between nodes.
+    struct Rlc;
+
+    impl AcStamper for Rlc {
+        fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, Complex::new(1.0, 0.0));
+            mna.stamp_conductance(Some(0), Some(1), 1.0 / 50.0);
+            mna.stamp_inductor(Some(1), Some(2), 1, omega, 1e-3);
+            mna.stamp_admittance(Some(2), Some(3), Complex::new(0.0, omega * 1e-6));
+            mna.stamp_conductance(Some(3), None, 1.0 / 10.0);
+            mna.stamp_admittance(Some(3), None, Complex::new(0.0, omega * 2e-6));
+        }
+        fn num_nodes(&self) -> usize {
+            4
+        }
+        fn num_vsources(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn test_rc_single_pole() {
+        let (r, c) = (1e3, 1e-6);
+        let ss = solve_state_space(
+            &Rc { r, c },
+            &[StateSpaceInput::Voltage { branch_idx: 0 }],
+            &[StateSpaceOutput::Voltage { node_idx: 1 }],
+        )
+        .unwrap();
+
+        assert_eq!(ss.num_states(), 1);
+        let pole = ss.poles()[0];
+        assert!((pole.re + 1.0 / (r * c)).abs() < 1e-6, "pole = {pole}");
+        assert!(pole.im.abs() < 1e-12);
+        // Unity DC gain, no feedthrough.
+        assert!((ss.c[(0, 0)] * ss.b[(0, 0)] / -ss.a[(0, 0)] - 1.0).abs() < 1e-12);
+        assert!(ss.d[(0, 0)].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transfer_matches_ac_analysis() {
+        let ss = solve_state_space(
+            &Rlc,
+            &[StateSpaceInput::Voltage { branch_idx: 0 }],
+            &[
+                StateSpaceOutput::Voltage { node_idx: 3 },
+                StateSpaceOutput::VoltageDiff {
+                    node_pos: 1,
+                    node_neg: 2,
+                },
+                StateSpaceOutput::Current { branch_idx: 1 },
+            ],
+        )
+        .unwrap();
+        // Inductor current plus two capacitor voltages.
+        assert_eq!(ss.num_states(), 3);
+        assert!(ss.poles().iter().all(|p| p.re < 0.0));
+
+        let params = AcParams {
+            sweep_type: AcSweepType::Decade,
+            num_points: 5,
+            fstart: 10.0,
+            fstop: 1e6,
+        };
+        let ac = solve_ac(&Rlc, &params).unwrap();
+        for point in &ac.points {
+            let omega = 2.0 * std::f64::consts::PI * point.frequency;
+            let h = ss.transfer(omega).unwrap();
+            let expected = [
+                point.solution[3],
+                point.solution[1] - point.solution[2],
+                point.solution[4 + 1],
+            ];
+            for (row, want) in expected.iter().enumerate() {
+                let err = (h[(row, 0)] - want).norm() / want.norm().max(1e-12);
+                assert!(err < 1e-8, "f = {}, output {row}: {err:e}", point.frequency);
+            }
+        }
+    }
+
+    #[test]
+    fn test_current_input_is_feedthrough_for_resistor() {
+        // A resistor alone: no states, V = R·I straight through D.
+        struct R;
+        impl AcStamper for R {
+            fn stamp_ac(&self, mna: &mut ComplexMna, _omega: f64) {
+                mna.stamp_conductance(Some(0), None, 1e-3);
+            }
+            fn num_nodes(&self) -> usize {
+                1
+            }
+            fn num_vsources(&self) -> usize {
+                0
+            }
+        }
+        let ss = solve_state_space(
+            &R,
+            &[StateSpaceInput::Current {
+                node_pos: Some(0),
+                node_neg: None,
+            }],
+            &[StateSpaceOutput::Voltage { node_idx: 0 }],
+        )
+        .unwrap();
+        assert_eq!(ss.num_states(), 0);
+        assert!((ss.d[(0, 0)] - 1e3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_out_of_range_ports_are_errors() {
+        let rc = Rc { r: 1e3, c: 1e-6 };
+        let input = [StateSpaceInput::Voltage { branch_idx: 0 }];
+        let output = [StateSpaceOutput::Voltage { node_idx: 1 }];
+
+        let err = solve_state_space(&rc, &[StateSpaceInput::Voltage { branch_idx: 1 }], &output)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::IndexOutOfRange {
+                what: "branch",
+                index: 1,
+                len: 1
+            }
+        ));
+        let err = solve_state_space(
+            &rc,
+            &[StateSpaceInput::Current {
+                node_pos: Some(2),
+                node_neg: None,
+            }],
+            &output,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::IndexOutOfRange {
+                what: "node",
+                index: 2,
+                ..
+            }
+        ));
+        let err = solve_state_space(
+            &rc,
+            &input,
+            &[StateSpaceOutput::VoltageDiff {
+                node_pos: 0,
+                node_neg: 5,
+            }],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::IndexOutOfRange {
+                what: "node",
+                index: 5,
+                ..
+            }
+        ));
+        let err = solve_state_space(&rc, &input, &[StateSpaceOutput::Current { branch_idx: 3 }])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::IndexOutOfRange {
+                what: "branch",
+                index: 3,
+                ..
+            }
+        ));
+    }
+}
@@ -6,10 +6,14 @@
 //! - Parameter sweeps with parallel execution
 
 use nalgebra::DVector;
+use num_complex::Complex;
+use std::f64::consts::PI;
+use std::ops::Range;
 use std::sync::Arc;
 
+use crate::ac::{AcStamper, ComplexMna};
 use crate::error::Result;
-use crate::linear::solve_dense;
+use crate::linear::{solve_complex, solve_dense};
 use crate::newton::ConvergenceCriteria;
 
 /// A parameter variation for sweep analysis.
@@ -71,6 +75,8 @@ pub struct BatchedSweepResult {
     pub converged_count: usize,
     /// Total number of points.
     pub total_count: usize,
+    /// Indices of points that failed to solve; their solutions are zero.
+    pub failed_indices: Vec<usize>,
 }
 
 impl BatchedSweepResult {
@@ -89,6 +95,62 @@ impl BatchedSweepResult {
         let voltages = self.node_voltages(node_index);
         SweepStatistics::from_samples(&voltages)
     }
+
+    /// Index and solution of every point that solved.
+    fn converged(&self) -> impl Iterator<Item = (usize, &DVector<f64>)> {
+        self.solutions
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.failed_indices.binary_search(i).is_err())
+    }
+
+    /// Fraction of converged samples whose metric lies within `spec`.
+    ///
+    /// `metric` maps a solution to the figure of merit (e.g. an output
+    /// voltage or a gain computed from several nodes). The range is half-open,
+    /// matching [`Range::contains`]. Failed points are left out of both the
+    /// count and the total. Returns 0 if no point converged.
+    pub fn yield_against(&self, spec: Range<f64>, metric: impl Fn(&DVector<f64>) -> f64) -> f64 {
+        let (mut passing, mut total) = (0, 0);
+        for (_, s) in self.converged() {
+            total += 1;
+            if spec.contains(&metric(s)) {
+                passing += 1;
+            }
+        }
+        if total == 0 {
+            return 0.0;
+        }
+        passing as f64 / total as f64
+    }
+
+    /// The `n` converged samples whose metric lies furthest from the mean,
+    /// worst first.
+    ///
+    /// Returns each sample's index and parameter values so the offending
+    /// parameter combinations can be investigated or re-simulated. Failed
+    /// points are neither ranked nor included in the mean.
+    pub fn worst_samples(
+        &self,
+        n: usize,
+        metric: impl Fn(&DVector<f64>) -> f64,
+    ) -> Vec<(usize, &[f64])> {
+        let (indices, values): (Vec<usize>, Vec<f64>) =
+            self.converged().map(|(i, s)| (i, metric(s))).unzip();
+        let mean = SweepStatistics::from_samples(&values).mean;
+
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_by(|&a, &b| {
+            (values[b] - mean)
+                .abs()
+                .total_cmp(&(values[a] - mean).abs())
+        });
+        order
+            .into_iter()
+            .take(n)
+            .map(|k| (indices[k], self.points[indices[k]].parameters.as_slice()))
+            .collect()
+    }
 }
 
 /// Statistics for a sweep result.
@@ -143,6 +205,10 @@ pub trait SweepPointGenerator: Send + Sync {
 }
 
 /// Monte Carlo point generator with random sampling.
+///
+/// Every sample index draws from its own RNG stream derived from `seed` and
+/// the index, so sample `i` gets the same parameters no matter how many
+/// samples are generated, in what order, or on which thread.
 #[derive(Debug, Clone)]
 pub struct MonteCarloGenerator {
     /// Number of samples to generate.
@@ -165,40 +231,65 @@ impl MonteCarloGenerator {
         self.seed = seed;
         self
     }
+
+    /// Generate the sample at `index`.
+    ///
+    /// Independent of every other sample, so callers may generate samples
+    /// in parallel or out of order.
+    pub fn sample(&self, index: usize, variations: &[ParameterVariation]) -> SweepPoint {
+        let mut rng = SampleRng::new(self.seed, index as u64);
+        let parameters = variations
+            .iter()
+            .map(|v| {
+                let z = rng.next_normal();
+                (v.nominal + z * v.sigma * v.nominal).clamp(v.min, v.max)
+            })
+            .collect();
+        SweepPoint { parameters }
+    }
 }
 
 impl SweepPointGenerator for MonteCarloGenerator {
     fn generate(&self, variations: &[ParameterVariation]) -> Vec<SweepPoint> {
-        // Simple LCG random number generator for reproducibility
-        let mut rng_state = self.seed;
-        let mut next_random = || -> f64 {
-            rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-            // Convert to [0, 1)
-            (rng_state >> 33) as f64 / (1u64 << 31) as f64
-        };
-
-        // Box-Muller transform for normal distribution
-        let mut next_normal = || -> f64 {
-            let u1 = next_random().max(1e-10);
-            let u2 = next_random();
-            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
-        };
-
         (0..self.num_samples)
-            .map(|_| {
-                let parameters: Vec<f64> = variations
-                    .iter()
-                    .map(|v| {
-                        let z = next_normal();
-                        (v.nominal + z * v.sigma * v.nominal).clamp(v.min, v.max)
-                    })
-                    .collect();
-                SweepPoint { parameters }
-            })
+            .map(|i| self.sample(i, variations))
             .collect()
     }
 }
 
+/// SplitMix64 stream seeded per sample index.
+pub(crate) struct SampleRng {
+    state: u64,
+}
+
+impl SampleRng {
+    pub(crate) fn new(seed: u64, index: u64) -> Self {
+        Self {
+            state: splitmix64(seed ^ splitmix64(index)),
+        }
+    }
+
+    /// Uniform value in [0, 1).
+    pub(crate) fn next_uniform(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        (splitmix64(self.state) >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Standard normal value via the Box-Muller transform.
+    fn next_normal(&mut self) -> f64 {
+        let u1 = self.next_uniform().max(1e-10);
+        let u2 = self.next_uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
 /// Corner analysis generator (all combinations of min/max).
 #[derive(Debug, Clone)]
 pub struct CornerGenerator;
@@ -343,11 +434,11 @@ pub fn solve_batched_sweep(
     let points = generator.generate(variations);
     let total_count = points.len();
     let mut solutions = Vec::with_capacity(total_count);
-    let mut converged_count = 0;
+    let mut failed_indices = Vec::new();
 
     // For linear circuits, we can parallelize across points
     // Note: Using sequential for now; could use rayon for parallel execution
-    for point in &points {
+    for (i, point) in points.iter().enumerate() {
         let stamper = factory.create_stamper(&point.parameters);
         let size = stamper.num_nodes() + stamper.num_vsources();
 
@@ -357,13 +448,11 @@ pub fn solve_batched_sweep(
         stamper.stamp_linear(&mut matrix, &mut rhs);
 
         match solve_dense(&matrix, &rhs) {
-            Ok(solution) => {
-                solutions.push(solution);
-                converged_count += 1;
-            }
+            Ok(solution) => solutions.push(solution),
             Err(_) => {
                 // Use zeros for failed point
                 solutions.push(DVector::zeros(size));
+                failed_indices.push(i);
             }
         }
     }
@@ -371,8 +460,154 @@ pub fn solve_batched_sweep(
     Ok(BatchedSweepResult {
         solutions,
         points,
-        converged_count,
+        converged_count: total_count - failed_indices.len(),
         total_count,
+        failed_indices,
+    })
+}
+
+/// Stamper factory for creating AC stampers with varied parameters.
+///
+/// The AC counterpart of [`SweepStamperFactory`], used by [`solve_batched_ac`].
+pub trait AcSweepStamperFactory: Send + Sync {
+    /// Create an AC stamper for the given parameter values.
+    fn create_ac_stamper(&self, parameters: &[f64]) -> Box<dyn AcStamper>;
+}
+
+/// Result of a batched AC sweep across parameter samples.
+#[derive(Debug, Clone)]
+pub struct BatchedAcResult {
+    /// Frequencies (Hz) at which every sample was solved.
+    pub frequencies: Vec<f64>,
+    /// Complex solutions indexed as `solutions[frequency][sample]`.
+    pub solutions: Vec<Vec<DVector<Complex<f64>>>>,
+    /// Parameter values for each sample.
+    pub points: Vec<SweepPoint>,
+    /// Number of samples that solved at every frequency.
+    pub converged_count: usize,
+    /// Total number of samples.
+    pub total_count: usize,
+    /// Indices of samples that were singular at one or more frequencies.
+    pub failed_indices: Vec<usize>,
+}
+
+impl BatchedAcResult {
+    /// Number of frequency points.
+    pub fn num_frequencies(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    /// Get the complex solution of one sample at one frequency.
+    pub fn solution(&self, freq_idx: usize, sample: usize) -> Option<&DVector<Complex<f64>>> {
+        self.solutions.get(freq_idx).and_then(|s| s.get(sample))
+    }
+
+    /// Get the voltage magnitude at a node for every sample at one frequency.
+    pub fn magnitudes(&self, freq_idx: usize, node_idx: usize) -> Vec<f64> {
+        self.solutions[freq_idx]
+            .iter()
+            .map(|s| s[node_idx].norm())
+            .collect()
+    }
+
+    /// Get the voltage phase (degrees) at a node for every sample at one frequency.
+    pub fn phases_deg(&self, freq_idx: usize, node_idx: usize) -> Vec<f64> {
+        self.solutions[freq_idx]
+            .iter()
+            .map(|s| s[node_idx].arg() * 180.0 / PI)
+            .collect()
+    }
+
+    /// Magnitude distribution at a node for one frequency.
+    pub fn magnitude_statistics(&self, freq_idx: usize, node_idx: usize) -> SweepStatistics {
+        SweepStatistics::from_samples(&self.magnitudes(freq_idx, node_idx))
+    }
+
+    /// Phase distribution (degrees) at a node for one frequency.
+    pub fn phase_statistics(&self, freq_idx: usize, node_idx: usize) -> SweepStatistics {
+        SweepStatistics::from_samples(&self.phases_deg(freq_idx, node_idx))
+    }
+
+    /// Magnitude distributions at a node across all frequencies.
+    pub fn magnitude_statistics_sweep(&self, node_idx: usize) -> Vec<(f64, SweepStatistics)> {
+        self.frequencies
+            .iter()
+            .enumerate()
+            .map(|(fi, &f)| (f, self.magnitude_statistics(fi, node_idx)))
+            .collect()
+    }
+}
+
+/// Execute a batched AC sweep across parameter samples.
+///
+/// One stamper is created per sample. For each frequency, the complex MNA
+/// system of every sample is assembled and solved as a batch, so results for
+/// a frequency are available together for distribution analysis (e.g. the
+/// yield of a filter's passband gain).
+///
+/// Samples that are singular at some frequency get a zero solution there and
+/// are listed in [`BatchedAcResult::failed_indices`].
+///
+/// This is the CPU reference path, solving one sample at a time with a
+/// dense complex LU. `spicier_batched_sweep::solve_batched_ac_gpu` hands each
+/// frequency's samples to a batched LU backend in a single call instead.
+///
+/// # Arguments
+/// * `factory` - Factory for creating AC stampers with varied parameters
+/// * `generator` - Generator for sweep points
+/// * `variations` - Parameter variations to sweep
+/// * `frequencies` - Frequencies (Hz) to solve at, e.g. from [`generate_frequencies`](crate::ac::generate_frequencies)
+pub fn solve_batched_ac(
+    factory: &dyn AcSweepStamperFactory,
+    generator: &dyn SweepPointGenerator,
+    variations: &[ParameterVariation],
+    frequencies: &[f64],
+) -> Result<BatchedAcResult> {
+    let points = generator.generate(variations);
+    let total_count = points.len();
+
+    let stampers: Vec<Box<dyn AcStamper>> = points
+        .iter()
+        .map(|p| factory.create_ac_stamper(&p.parameters))
+        .collect();
+
+    let mut failed = vec![false; total_count];
+    let mut solutions = Vec::with_capacity(frequencies.len());
+
+    for &freq in frequencies {
+        let omega = 2.0 * PI * freq;
+        let mut batch = Vec::with_capacity(total_count);
+
+        for (i, stamper) in stampers.iter().enumerate() {
+            let num_nodes = stamper.num_nodes();
+            let num_vsources = stamper.num_vsources();
+            let mut mna = ComplexMna::new(num_nodes, num_vsources);
+            stamper.stamp_ac(&mut mna, omega);
+
+            match solve_complex(&mna.to_dense_matrix(), mna.rhs()) {
+                Ok(solution) => batch.push(solution),
+                Err(_) => {
+                    failed[i] = true;
+                    batch.push(DVector::from_element(
+                        num_nodes + num_vsources,
+                        Complex::new(0.0, 0.0),
+                    ));
+                }
+            }
+        }
+
+        solutions.push(batch);
+    }
+
+    let failed_indices: Vec<usize> = (0..total_count).filter(|&i| failed[i]).collect();
+
+    Ok(BatchedAcResult {
+        frequencies: frequencies.to_vec(),
+        solutions,
+        points,
+        converged_count: total_count - failed_indices.len(),
+        total_count,
+        failed_indices,
     })
 }
 
@@ -403,6 +638,196 @@ mod tests {
         assert_eq!(points[0].parameters, points2[0].parameters);
     }
 
+    #[test]
+    fn test_monte_carlo_samples_independent_of_order() {
+        let generator = MonteCarloGenerator::new(64).with_seed(7);
+        let variations = vec![
+            ParameterVariation::new("R1", 1000.0).with_sigma(0.1),
+            ParameterVariation::new("C1", 1e-9).with_sigma(0.2),
+        ];
+        let points = generator.generate(&variations);
+
+        // Sample i is the same whether drawn alone, in reverse, or from a
+        // longer run.
+        for i in (0..64).rev() {
+            assert_eq!(
+                generator.sample(i, &variations).parameters,
+                points[i].parameters
+            );
+        }
+        let longer = MonteCarloGenerator::new(128)
+            .with_seed(7)
+            .generate(&variations);
+        assert_eq!(
+            &longer[..64]
+                .iter()
+                .map(|p| &p.parameters)
+                .collect::<Vec<_>>(),
+            &points.iter().map(|p| &p.parameters).collect::<Vec<_>>()
+        );
+
+        // Samples drawn from several threads match the sequential run.
+        let threaded: Vec<SweepPoint> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|t| {
+                    let (generator, variations) = (&generator, &variations);
+                    s.spawn(move || {
+                        (t..64)
+                            .step_by(4)
+                            .map(|i| (i, generator.sample(i, variations)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            let mut all: Vec<_> = handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect();
+            all.sort_by_key(|(i, _)| *i);
+            all.into_iter().map(|(_, p)| p).collect()
+        });
+        for (a, b) in threaded.iter().zip(&points) {
+            assert_eq!(a.parameters, b.parameters);
+        }
+
+        // Different samples and seeds draw different values.
+        assert_ne!(points[0].parameters, points[1].parameters);
+        let other = MonteCarloGenerator::new(1)
+            .with_seed(8)
+            .sample(0, &variations);
+        assert_ne!(other.parameters, points[0].parameters);
+    }
+
+    #[test]
+    fn test_yield_matches_gaussian_cdf() {
+        // One node driven by a 1 A source into a parameterized resistor, so
+        // V(0) equals the sampled resistance.
+        struct ResistorFactory;
+
+        impl SweepStamperFactory for ResistorFactory {
+            fn create_stamper(&self, parameters: &[f64]) -> Arc<dyn SweepStamper> {
+                Arc::new(ResistorStamper(parameters[0]))
+            }
+        }
+
+        struct ResistorStamper(f64);
+
+        impl SweepStamper for ResistorStamper {
+            fn stamp_linear(&self, matrix: &mut nalgebra::DMatrix<f64>, rhs: &mut DVector<f64>) {
+                matrix[(0, 0)] += 1.0 / self.0;
+                rhs[0] += 1.0;
+            }
+
+            fn num_nodes(&self) -> usize {
+                1
+            }
+
+            fn num_vsources(&self) -> usize {
+                0
+            }
+        }
+
+        // Standard normal CDF via the Abramowitz-Stegun 7.1.26 erf fit.
+        fn phi(z: f64) -> f64 {
+            let x = z.abs() / std::f64::consts::SQRT_2;
+            let t = 1.0 / (1.0 + 0.3275911 * x);
+            let poly = t
+                * (0.254829592
+                    + t * (-0.284496736
+                        + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+            let erf = 1.0 - poly * (-x * x).exp();
+            0.5 * (1.0 + erf.copysign(z))
+        }
+
+        let variations = vec![
+            ParameterVariation::new("R1", 1000.0)
+                .with_sigma(0.1)
+                .with_bounds(0.0, 1e6),
+        ];
+        let result = solve_batched_sweep(
+            &ResistorFactory,
+            &MonteCarloGenerator::new(20_000).with_seed(99),
+            &variations,
+            &ConvergenceCriteria::default(),
+        )
+        .unwrap();
+
+        // Spec 900..1150 is -1σ..+1.5σ.
+        let measured = result.yield_against(900.0..1150.0, |s| s[0]);
+        let expected = phi(1.5) - phi(-1.0);
+        assert!(
+            (measured - expected).abs() < 0.01,
+            "yield {measured} vs analytical {expected}"
+        );
+        assert_eq!(result.yield_against(0.0..f64::INFINITY, |s| s[0]), 1.0);
+
+        let worst = result.worst_samples(5, |s| s[0]);
+        assert_eq!(worst.len(), 5);
+        let mean = result.statistics(0).mean;
+        let deviation = |r: f64| (r - mean).abs();
+        for pair in worst.windows(2) {
+            assert!(deviation(pair[0].1[0]) >= deviation(pair[1].1[0]));
+        }
+        // No sample outside the returned set lies further out than the fifth-worst.
+        let cutoff = deviation(worst[4].1[0]);
+        let further = result
+            .points
+            .iter()
+            .filter(|p| deviation(p.parameters[0]) > cutoff)
+            .count();
+        assert_eq!(further, 4);
+        assert!(deviation(worst[0].1[0]) > 300.0);
+    }
+
+    #[test]
+    fn test_yield_skips_failed_points() {
+        struct ConductanceFactory;
+
+        impl SweepStamperFactory for ConductanceFactory {
+            fn create_stamper(&self, parameters: &[f64]) -> Arc<dyn SweepStamper> {
+                Arc::new(ConductanceStamper(parameters[0]))
+            }
+        }
+
+        /// 1 A into a conductance G to ground: V = 1/G, singular at G = 0.
+        struct ConductanceStamper(f64);
+
+        impl SweepStamper for ConductanceStamper {
+            fn stamp_linear(&self, matrix: &mut nalgebra::DMatrix<f64>, rhs: &mut DVector<f64>) {
+                matrix[(0, 0)] += self.0;
+                rhs[0] += 1.0;
+            }
+
+            fn num_nodes(&self) -> usize {
+                1
+            }
+
+            fn num_vsources(&self) -> usize {
+                0
+            }
+        }
+
+        // G = 0, 1, 2 gives a failed point, then V = 1 and V = 0.5.
+        let variations = vec![ParameterVariation::new("G", 1.0).with_bounds(0.0, 2.0)];
+        let result = solve_batched_sweep(
+            &ConductanceFactory,
+            &LinearSweepGenerator::new(3),
+            &variations,
+            &ConvergenceCriteria::default(),
+        )
+        .unwrap();
+        assert_eq!(result.failed_indices, vec![0]);
+        assert_eq!(result.converged_count, 2);
+
+        // The failed point's zero solution would fall inside this spec.
+        assert_eq!(result.yield_against(-1.0..0.75, |s| s[0]), 0.5);
+
+        let worst = result.worst_samples(3, |s| s[0]);
+        let mut indices: Vec<usize> = worst.iter().map(|&(i, _)| i).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, vec![1, 2]);
+    }
+
     #[test]
     fn test_corner_generator() {
         let generator = CornerGenerator;
@@ -546,4 +971,80 @@ mod tests {
             v1_mid
         );
     }
+
+    #[test]
+    fn test_batched_ac_rc_lowpass() {
+        // RC lowpass with Monte Carlo variation on R: |H| at the nominal
+        // corner frequency is spread around 1/sqrt(2), and ~1 at low frequency.
+
+        struct RcFactory {
+            capacitance: f64,
+        }
+
+        impl AcSweepStamperFactory for RcFactory {
+            fn create_ac_stamper(&self, parameters: &[f64]) -> Box<dyn AcStamper> {
+                Box::new(RcStamper {
+                    r: parameters[0],
+                    c: self.capacitance,
+                })
+            }
+        }
+
+        struct RcStamper {
+            r: f64,
+            c: f64,
+        }
+
+        impl AcStamper for RcStamper {
+            fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
+                mna.stamp_voltage_source(Some(0), None, 0, Complex::new(1.0, 0.0));
+                mna.stamp_conductance(Some(0), Some(1), 1.0 / self.r);
+                mna.stamp_admittance(Some(1), None, Complex::new(0.0, omega * self.c));
+            }
+
+            fn num_nodes(&self) -> usize {
+                2
+            }
+
+            fn num_vsources(&self) -> usize {
+                1
+            }
+        }
+
+        let r_nom = 1000.0;
+        let c = 1e-6;
+        let fc = 1.0 / (2.0 * PI * r_nom * c);
+
+        let factory = RcFactory { capacitance: c };
+        let generator = MonteCarloGenerator::new(200).with_seed(7);
+        let variations = vec![ParameterVariation::new("R1", r_nom).with_sigma(0.05)];
+
+        let result = solve_batched_ac(&factory, &generator, &variations, &[1.0, fc])
+            .expect("batched AC should succeed");
+
+        assert_eq!(result.num_frequencies(), 2);
+        assert_eq!(result.total_count, 200);
+        assert_eq!(result.converged_count, 200);
+        assert!(result.failed_indices.is_empty());
+
+        let low = result.magnitude_statistics(0, 1);
+        assert!((low.mean - 1.0).abs() < 1e-3);
+        assert!(low.std_dev < 1e-4);
+
+        let corner = result.magnitude_statistics(1, 1);
+        assert!((corner.mean - std::f64::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!(corner.std_dev > 1e-3, "R variation should spread |H| at fc");
+
+        let phase = result.phase_statistics(1, 1);
+        assert!((phase.mean + 45.0).abs() < 1.0);
+
+        // Each sample matches the analytical response for its own R
+        for (i, point) in result.points.iter().enumerate() {
+            let r = point.parameters[0];
+            let wrc = 2.0 * PI * fc * r * c;
+            let expected = 1.0 / (1.0 + wrc * wrc).sqrt();
+            let got = result.solution(1, i).unwrap()[1].norm();
+            assert!((got - expected).abs() < 1e-9);
+        }
+    }
 }
@@ -1,10 +1,50 @@
 //! Companion models for reactive elements in transient analysis.
 
+use std::collections::VecDeque;
+
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 
 use super::types::{IntegrationMethod, TRBDF2_GAMMA};
 
+/// Coefficients of the TR-BDF2 BDF2 stage over a full step `h`.
+///
+/// The stage is a variable-step BDF2 from `t` and `t + γh` to `t + h`:
+/// `y(t+h) = a1·y(t+γh) + a2·y(t) + b·y'(t+h)`, returned as `(a1, a2, b)`.
+fn trbdf2_bdf2_coefficients(h: f64) -> (f64, f64, f64) {
+    let gamma = TRBDF2_GAMMA;
+    // BDF2 coefficients for non-uniform step: h1 = γ*h, h2 = (1-γ)*h
+    // The step we're taking is h2 = (1-γ)*h
+    let h2 = (1.0 - gamma) * h;
+    let h1 = gamma * h;
+    let rho = h2 / h1; // ratio of step sizes
+
+    // BDF2 for non-uniform steps:
+    // y_{n+1} = a1 * y_n + a2 * y_{n-1} + b0 * h2 * y'_{n+1}
+    // where:
+    //   a1 = (1+ρ)² / (1+2ρ)
+    //   a2 = -ρ² / (1+2ρ)
+    //   b0 = (1+ρ) / (1+2ρ)
+    let denom = 1.0 + 2.0 * rho;
+    let a1 = (1.0 + rho).powi(2) / denom;
+    let a2 = -rho * rho / denom;
+    let b0 = (1.0 + rho) / denom;
+    (a1, a2, b0 * h2)
+}
+
+/// TR-BDF2 local truncation error from the derivative at the three stage
+/// points `t`, `t + γh` and `t + h`.
+///
+/// Their second divided difference estimates y''', so the error is
+/// `2k·h·(f₀/γ − f_γ/(γ(1−γ)) + f₁/(1−γ))` with
+/// `k = (−3γ² + 4γ − 2) / (12(2−γ))` (Bank et al., 1985).
+fn trbdf2_lte(f0: f64, f_gamma: f64, f1: f64, h: f64) -> f64 {
+    let gamma = TRBDF2_GAMMA;
+    let k = (-3.0 * gamma * gamma + 4.0 * gamma - 2.0) / (12.0 * (2.0 - gamma));
+    let second_difference = f0 / gamma - f_gamma / (gamma * (1.0 - gamma)) + f1 / (1.0 - gamma);
+    (2.0 * k * h * second_difference).abs()
+}
+
 /// State of a capacitor for companion model.
 #[derive(Debug, Clone)]
 pub struct CapacitorState {
@@ -14,7 +54,7 @@ pub struct CapacitorState {
     pub v_prev: f64,
     /// Current at previous timestep (for trapezoidal).
     pub i_prev: f64,
-    /// Voltage at two timesteps ago (for TR-BDF2).
+    /// Voltage at two timesteps ago (for TR-BDF2 and Gear2).
     pub v_prev_prev: f64,
     /// Positive node MNA index (None for ground).
     pub node_pos: Option<usize>,
@@ -58,23 +98,39 @@ impl CapacitorState {
         mna.stamp_current_source(self.node_neg, self.node_pos, ieq);
     }
 
+    /// Stamp the companion model for Gear2 (BDF2) with a uniform step.
+    ///
+    /// C is replaced by: G_eq = 3C/(2h) in parallel with
+    /// I_eq = G_eq * (4/3 V_prev - 1/3 V_prev_prev)
+    pub fn stamp_gear2(&self, mna: &mut MnaSystem, h: f64) {
+        let geq = 1.5 * self.capacitance / h;
+        let ieq = geq * self.gear2_history();
+
+        mna.stamp_conductance(self.node_pos, self.node_neg, geq);
+        mna.stamp_current_source(self.node_neg, self.node_pos, ieq);
+    }
+
+    /// Gear2 history term `4/3 V_prev - 1/3 V_prev_prev`.
+    fn gear2_history(&self) -> f64 {
+        (4.0 * self.v_prev - self.v_prev_prev) / 3.0
+    }
+
     /// Update state after solving a timestep.
     pub fn update(&mut self, v_new: f64, h: f64, method: IntegrationMethod) {
         match method {
             IntegrationMethod::BackwardEuler => {
                 self.i_prev = self.capacitance / h * (v_new - self.v_prev);
             }
+            IntegrationMethod::Gear2 => {
+                self.i_prev = 1.5 * self.capacitance / h * (v_new - self.gear2_history());
+            }
             IntegrationMethod::Trapezoidal => {
                 self.i_prev = 2.0 * self.capacitance / h * (v_new - self.v_prev) - self.i_prev;
             }
             IntegrationMethod::TrBdf2 => {
                 // TR-BDF2 update after full step completion
                 // Current is computed from the BDF2 formula
-                let gamma = TRBDF2_GAMMA;
-                let alpha = (1.0 - gamma) / (gamma * (2.0 - gamma));
-                self.i_prev = self.capacitance / h
-                    * ((1.0 + alpha) * v_new - (1.0 + 2.0 * alpha) * self.v_prev
-                        + alpha * self.v_prev_prev);
+                self.i_prev = self.trbdf2_current(v_new, h);
             }
         }
         self.v_prev_prev = self.v_prev;
@@ -99,27 +155,11 @@ impl CapacitorState {
     ///
     /// Uses v_prev (at γ*h) and v_prev_prev (at 0) for BDF2 formula.
     pub fn stamp_trbdf2_bdf2(&self, mna: &mut MnaSystem, h: f64) {
-        let gamma = TRBDF2_GAMMA;
-        // BDF2 coefficients for non-uniform step: h1 = γ*h, h2 = (1-γ)*h
-        // The step we're taking is h2 = (1-γ)*h
-        let h2 = (1.0 - gamma) * h;
-        let h1 = gamma * h;
-        let rho = h2 / h1; // ratio of step sizes
-
-        // BDF2 for non-uniform steps:
-        // y_{n+1} = a1 * y_n + a2 * y_{n-1} + b0 * h2 * y'_{n+1}
-        // where:
-        //   a1 = (1+ρ)² / (1+2ρ)
-        //   a2 = -ρ² / (1+2ρ)
-        //   b0 = (1+ρ) / (1+2ρ)
-        let denom = 1.0 + 2.0 * rho;
-        let a1 = (1.0 + rho).powi(2) / denom;
-        let a2 = -rho * rho / denom;
-        let b0 = (1.0 + rho) / denom;
+        let (a1, a2, b) = trbdf2_bdf2_coefficients(h);
 
         // For capacitor: i = C * dv/dt
         // Geq = C / (b0 * h2)
-        let geq = self.capacitance / (b0 * h2);
+        let geq = self.capacitance / b;
         // Ieq represents the history terms: current = Geq * (a1*v_n + a2*v_{n-1})
         let ieq = geq * (a1 * self.v_prev + a2 * self.v_prev_prev);
 
@@ -127,6 +167,32 @@ impl CapacitorState {
         mna.stamp_current_source(self.node_neg, self.node_pos, ieq);
     }
 
+    /// Capacitor current at the end of a TR-BDF2 step, from the BDF2 stage.
+    ///
+    /// Valid between [`update_trbdf2_intermediate`](Self::update_trbdf2_intermediate)
+    /// and the final [`update`](Self::update).
+    fn trbdf2_current(&self, v_new: f64, h: f64) -> f64 {
+        let (a1, a2, b) = trbdf2_bdf2_coefficients(h);
+        self.capacitance / b * (v_new - a1 * self.v_prev - a2 * self.v_prev_prev)
+    }
+
+    /// Estimate the TR-BDF2 Local Truncation Error of the capacitor voltage.
+    ///
+    /// The embedded estimate compares the capacitor current at the start,
+    /// the TR stage and the BDF2 stage of the step, so no second method has
+    /// to be run. Call it after
+    /// [`update_trbdf2_intermediate`](Self::update_trbdf2_intermediate)
+    /// with the voltage solved by the BDF2 stage.
+    pub fn estimate_lte_trbdf2(&self, v_new: f64, h: f64) -> f64 {
+        // The TR stage overwrote i_prev; recover the starting current from
+        // i_γ = 2C/(γh)·(v_γ − v₀) − i₀.
+        let i_gamma = self.i_prev;
+        let i0 = 2.0 * self.capacitance / (TRBDF2_GAMMA * h) * (self.v_prev - self.v_prev_prev)
+            - i_gamma;
+        let i1 = self.trbdf2_current(v_new, h);
+        trbdf2_lte(i0, i_gamma, i1, h) / self.capacitance
+    }
+
     /// Estimate Local Truncation Error for the capacitor voltage.
     ///
     /// Uses the difference between Trapezoidal and Backward Euler predictions.
@@ -164,7 +230,7 @@ pub struct InductorState {
     pub i_prev: f64,
     /// Voltage at previous timestep (for trapezoidal).
     pub v_prev: f64,
-    /// Current at two timesteps ago (for TR-BDF2).
+    /// Current one step before `i_prev` (for TR-BDF2 and Gear2).
     pub i_prev_prev: f64,
     /// Positive node MNA index (None for ground).
     pub node_pos: Option<usize>,
@@ -219,26 +285,40 @@ impl InductorState {
         mna.stamp_current_source(self.node_pos, self.node_neg, ieq);
     }
 
+    /// Stamp the companion model for Gear2 (BDF2) with a uniform step.
+    ///
+    /// L is replaced by: G_eq = 2h/(3L) in parallel with
+    /// I_eq = 4/3 I_prev - 1/3 I_prev_prev
+    /// The inductor current flows from node_pos to node_neg.
+    pub fn stamp_gear2(&self, mna: &mut MnaSystem, h: f64) {
+        let geq = 2.0 * h / (3.0 * self.inductance);
+        let ieq = (4.0 * self.i_prev - self.i_prev_prev) / 3.0;
+
+        mna.stamp_conductance(self.node_pos, self.node_neg, geq);
+        mna.stamp_current_source(self.node_pos, self.node_neg, ieq);
+    }
+
     /// Update state after solving a timestep.
     pub fn update(&mut self, v_new: f64, h: f64, method: IntegrationMethod) {
+        let i_old = self.i_prev;
         match method {
             IntegrationMethod::BackwardEuler => {
                 self.i_prev += h / self.inductance * v_new;
             }
+            IntegrationMethod::Gear2 => {
+                self.i_prev = (4.0 * self.i_prev - self.i_prev_prev) / 3.0
+                    + 2.0 * h / (3.0 * self.inductance) * v_new;
+            }
             IntegrationMethod::Trapezoidal => {
                 self.i_prev += h / (2.0 * self.inductance) * (v_new + self.v_prev);
             }
             IntegrationMethod::TrBdf2 => {
-                // TR-BDF2 update after full step completion
-                let gamma = TRBDF2_GAMMA;
-                let alpha = (1.0 - gamma) / (gamma * (2.0 - gamma));
-                let di = h / self.inductance
-                    * ((1.0 + alpha) * v_new - (1.0 + 2.0 * alpha) * self.v_prev
-                        + alpha * self.v_prev);
-                self.i_prev += di;
+                // TR-BDF2 update after full step completion: the BDF2 stage
+                // from i_prev (at γh) and i_prev_prev (at the step start)
+                self.i_prev = self.trbdf2_current(v_new, h);
             }
         }
-        self.i_prev_prev = self.i_prev;
+        self.i_prev_prev = i_old;
         self.v_prev = v_new;
     }
 
@@ -255,21 +335,13 @@ impl InductorState {
 
     /// Stamp companion model for TR-BDF2 BDF2 stage.
     pub fn stamp_trbdf2_bdf2(&self, mna: &mut MnaSystem, h: f64) {
-        let gamma = TRBDF2_GAMMA;
-        let h2 = (1.0 - gamma) * h;
-        let h1 = gamma * h;
-        let rho = h2 / h1;
-
         // BDF2 coefficients for non-uniform steps
         // i_{n+1} = a1 * i_n + a2 * i_{n-1} + b0 * h2 / L * v_{n+1}
-        let denom = 1.0 + 2.0 * rho;
-        let a1 = (1.0 + rho).powi(2) / denom;
-        let a2 = -rho * rho / denom;
-        let b0 = (1.0 + rho) / denom;
+        let (a1, a2, b) = trbdf2_bdf2_coefficients(h);
 
         // For inductor: L * di/dt = v
         // Geq = b0 * h2 / L (conductance seen by the circuit)
-        let geq = b0 * h2 / self.inductance;
+        let geq = b / self.inductance;
         // Ieq represents the history terms
         let ieq = a1 * self.i_prev + a2 * self.i_prev_prev;
 
@@ -278,6 +350,26 @@ impl InductorState {
         mna.stamp_current_source(self.node_pos, self.node_neg, ieq);
     }
 
+    /// Inductor current at the end of a TR-BDF2 step, from the BDF2 stage.
+    fn trbdf2_current(&self, v_new: f64, h: f64) -> f64 {
+        let (a1, a2, b) = trbdf2_bdf2_coefficients(h);
+        a1 * self.i_prev + a2 * self.i_prev_prev + b / self.inductance * v_new
+    }
+
+    /// Estimate the TR-BDF2 Local Truncation Error of the inductor current.
+    ///
+    /// The inductor counterpart of
+    /// [`CapacitorState::estimate_lte_trbdf2`], comparing the inductor
+    /// voltage at the three stage points. Call it after the TR stage, with
+    /// `v_prev` set to the TR-stage voltage.
+    pub fn estimate_lte_trbdf2(&self, v_new: f64, h: f64) -> f64 {
+        // Recover the starting voltage from i_γ = i₀ + γh/(2L)·(v_γ + v₀).
+        let v_gamma = self.v_prev;
+        let v0 =
+            2.0 * self.inductance / (TRBDF2_GAMMA * h) * (self.i_prev - self.i_prev_prev) - v_gamma;
+        trbdf2_lte(v0, v_gamma, v_new, h) / self.inductance
+    }
+
     /// Estimate Local Truncation Error for the inductor current.
     ///
     /// Uses the difference between Trapezoidal and Backward Euler predictions.
@@ -300,3 +392,146 @@ impl InductorState {
         vp - vn
     }
 }
+
+/// State of a lossless transmission line for the Branin companion model.
+///
+/// Each port is a conductance `1/Z0` in parallel with a history current
+/// source. The wave launched into the line at a port, `a = v + Z0·i`,
+/// reaches the other port `TD` later, so port 2 sees `E2(t) = a1(t - TD)`
+/// behind `Z0` and port 1 sees `E1(t) = a2(t - TD)`. That is exact for any
+/// step up to `TD`, so the solvers keep steps within it (see
+/// [`TransientStamper::max_timestep`](super::TransientStamper::max_timestep)).
+///
+/// Accepted waves are kept in a ring buffer covering the last `TD` seconds,
+/// about `TD/h` samples, and interpolated linearly between timepoints. The
+/// internal nodes of the lumped DC model are tied through a unit
+/// conductance to the line voltage at their position `x`,
+/// `(a1(t - x·TD) + a2(t - (1-x)·TD)) / 2`.
+#[derive(Debug, Clone)]
+pub struct TransmissionLineState {
+    /// Characteristic impedance (Ohms).
+    pub z0: f64,
+    /// Propagation delay (seconds).
+    pub td: f64,
+    /// Port 1 positive node MNA index (None for ground).
+    pub port1_pos: Option<usize>,
+    /// Port 1 negative node MNA index (None for ground).
+    pub port1_neg: Option<usize>,
+    /// Port 2 positive node MNA index (None for ground).
+    pub port2_pos: Option<usize>,
+    /// Port 2 negative node MNA index (None for ground).
+    pub port2_neg: Option<usize>,
+    /// Internal nodes of the lumped DC model, evenly spaced along the line.
+    pub internal_nodes: Vec<Option<usize>>,
+    /// Branch index of the first section current in the DC solution.
+    pub current_base_index: usize,
+    /// Accepted `(time, a1, a2)` samples, oldest first.
+    history: VecDeque<(f64, f64, f64)>,
+}
+
+impl TransmissionLineState {
+    /// Create a new transmission line state with an empty history.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        z0: f64,
+        td: f64,
+        port1_pos: Option<usize>,
+        port1_neg: Option<usize>,
+        port2_pos: Option<usize>,
+        port2_neg: Option<usize>,
+        internal_nodes: Vec<Option<usize>>,
+        current_base_index: usize,
+    ) -> Self {
+        Self {
+            z0,
+            td,
+            port1_pos,
+            port1_neg,
+            port2_pos,
+            port2_neg,
+            internal_nodes,
+            current_base_index,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Seed the history with the DC operating point.
+    ///
+    /// At DC the line is a short carrying the first section's branch
+    /// current from port 1 to port 2; it is taken as zero when
+    /// `dc_solution` has no branch currents.
+    pub fn init_from_dc(&mut self, dc_solution: &DVector<f64>, num_nodes: usize) {
+        let (v1, v2) = self.port_voltages(dc_solution);
+        let branch_idx = num_nodes + self.current_base_index;
+        let i = dc_solution.get(branch_idx).copied().unwrap_or(0.0);
+
+        self.history.clear();
+        self.history
+            .push_back((0.0, v1 + self.z0 * i, v2 - self.z0 * i));
+    }
+
+    /// Stamp the companion model at `time`.
+    pub fn stamp(&self, mna: &mut MnaSystem, time: f64) {
+        let g = 1.0 / self.z0;
+        let (e1, e2) = self.incident_at(time);
+
+        // i_k = (v_k - E_k)/Z0 flows into the line at the positive terminal
+        mna.stamp_conductance(self.port1_pos, self.port1_neg, g);
+        mna.stamp_current_source(self.port1_neg, self.port1_pos, g * e1);
+        mna.stamp_conductance(self.port2_pos, self.port2_neg, g);
+        mna.stamp_current_source(self.port2_neg, self.port2_pos, g * e2);
+
+        let sections = (self.internal_nodes.len() + 1) as f64;
+        for (k, &node) in self.internal_nodes.iter().enumerate() {
+            let x = (k + 1) as f64 / sections;
+            let (a1, _) = self.waves_at(time - x * self.td);
+            let (_, a2) = self.waves_at(time - (1.0 - x) * self.td);
+            mna.stamp_conductance(node, None, 1.0);
+            mna.stamp_current_source(None, node, 0.5 * (a1 + a2));
+        }
+    }
+
+    /// Record the waves launched at an accepted timepoint.
+    pub fn accept(&mut self, time: f64, solution: &DVector<f64>) {
+        let (v1, v2) = self.port_voltages(solution);
+        let (e1, e2) = self.incident_at(time);
+        // a_k = v_k + Z0·i_k with Z0·i_k = v_k - E_k
+        self.history.push_back((time, 2.0 * v1 - e1, 2.0 * v2 - e2));
+
+        // Keep the newest sample at or before time - TD for interpolation
+        let horizon = time - self.td;
+        while self.history.get(1).is_some_and(|&(t, _, _)| t <= horizon) {
+            self.history.pop_front();
+        }
+    }
+
+    /// Port voltages `(v1, v2)` from a solution vector.
+    fn port_voltages(&self, solution: &DVector<f64>) -> (f64, f64) {
+        let v = |node: Option<usize>| node.map(|i| solution[i]).unwrap_or(0.0);
+        (
+            v(self.port1_pos) - v(self.port1_neg),
+            v(self.port2_pos) - v(self.port2_neg),
+        )
+    }
+
+    /// Incident sources `(E1, E2)` behind each port at `time`.
+    fn incident_at(&self, time: f64) -> (f64, f64) {
+        let (a1, a2) = self.waves_at(time - self.td);
+        (a2, a1)
+    }
+
+    /// Launched waves `(a1, a2)` at `time`, held at the oldest and newest
+    /// samples outside the recorded range.
+    fn waves_at(&self, time: f64) -> (f64, f64) {
+        let k = self.history.partition_point(|&(t, _, _)| t <= time);
+        let before = k.checked_sub(1).and_then(|i| self.history.get(i));
+        match (before, self.history.get(k)) {
+            (Some(&(t0, a1_0, a2_0)), Some(&(t1, a1_1, a2_1))) => {
+                let s = (time - t0) / (t1 - t0);
+                (a1_0 + s * (a1_1 - a1_0), a2_0 + s * (a2_1 - a2_0))
+            }
+            (Some(&(_, a1, a2)), None) | (None, Some(&(_, a1, a2))) => (a1, a2),
+            (None, None) => (0.0, 0.0),
+        }
+    }
+}
@@ -6,11 +6,12 @@
 //! - **Backward Euler**: First-order, A-stable, most robust
 //! - **Trapezoidal**: Second-order, A-stable, good for oscillators
 //! - **TR-BDF2**: Second-order, L-stable, good for stiff circuits
+//! - **Gear2 (BDF2)**: Second-order, L-stable, one solve per step
 //!
 //! # Module Structure
 //!
 //! - [`types`] - Configuration types and parameters
-//! - [`companion`] - Companion models for capacitors and inductors
+//! - [`companion`] - Companion models for capacitors, inductors and transmission lines
 //! - [`result`] - Result types with interpolation support
 //! - [`solver`] - Main solver functions
 
@@ -20,19 +21,22 @@ pub mod solver;
 pub mod types;
 
 // Re-export main types and functions
-pub use companion::{CapacitorState, InductorState};
+pub use companion::{CapacitorState, InductorState, TransmissionLineState};
 pub use result::{AdaptiveTransientResult, TimePoint, TransientResult};
 pub use solver::{
     TransientStamper, solve_transient, solve_transient_adaptive, solve_transient_dispatched,
+    solve_transient_probed, solve_transient_with_progress,
 };
 pub use types::{
-    AdaptiveTransientParams, InitialConditions, IntegrationMethod, TRBDF2_GAMMA, TransientParams,
+    AdaptiveTransientParams, BREAKPOINT_TOL, InitialConditions, IntegrationMethod, MaxStepWindow,
+    TRBDF2_GAMMA, TransientParams,
 };
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dispatch::DispatchConfig;
+    use crate::error::Error;
     use nalgebra::DVector;
     use spicier_core::mna::MnaSystem;
 
@@ -58,6 +62,8 @@ mod tests {
             tstop: 1e-3,
             tstep: 100e-6,
             method: IntegrationMethod::BackwardEuler,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
         };
         let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
         let config = DispatchConfig::default();
@@ -72,6 +78,100 @@ mod tests {
         assert!(result.points.last().unwrap().solution[1] > 0.0);
     }
 
+    /// Current-driven RC ladder: 1 Ω series links, small shunt capacitors,
+    /// and the far end grounded through 1 Ω.
+    struct StiffLadderStamper {
+        nodes: usize,
+    }
+
+    impl TransientStamper for StiffLadderStamper {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, _time: f64) {
+            mna.stamp_current_source(None, Some(0), 1e-3);
+            for i in 0..self.nodes - 1 {
+                mna.stamp_conductance(Some(i), Some(i + 1), 1.0);
+            }
+            mna.stamp_conductance(Some(self.nodes - 1), None, 1.0);
+        }
+
+        fn num_nodes(&self) -> usize {
+            self.nodes
+        }
+
+        fn num_vsources(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn test_transient_gmres_preconditioner_choice() {
+        use crate::dispatch::{PreconditionerType, SolverDispatchStrategy};
+        use crate::gmres::{GmresConfig, solve_gmres_real_preconditioned};
+        use crate::ilu::Ilu0Preconditioner;
+        use crate::preconditioner::JacobiPreconditioner;
+        use crate::sparse_operator::SparseRealOperator;
+
+        let stamper = StiffLadderStamper { nodes: 200 };
+        let caps = || {
+            (0..stamper.nodes)
+                .map(|i| CapacitorState::new(1e-9, Some(i), None))
+                .collect::<Vec<_>>()
+        };
+        let params = TransientParams {
+            tstop: 5e-6,
+            tstep: 1e-6,
+            method: IntegrationMethod::BackwardEuler,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        };
+        let dc = DVector::zeros(stamper.nodes);
+        let gmres_config = GmresConfig {
+            max_iter: 100,
+            tol: 1e-10,
+            restart: 30,
+            ..GmresConfig::default()
+        };
+
+        // One backward-Euler step's matrix: Jacobi leaves the ladder's
+        // Laplacian ill-conditioned, ILU(0) on a tridiagonal is exact.
+        let mut mna = MnaSystem::new(stamper.nodes, 0);
+        stamper.stamp_at_time(&mut mna, params.tstep);
+        for cap in caps().iter() {
+            cap.stamp_be(&mut mna, params.tstep);
+        }
+        let size = mna.size();
+        let operator = SparseRealOperator::from_triplets(size, &mna.triplets).unwrap();
+        let rhs: Vec<f64> = mna.rhs().iter().copied().collect();
+        let jacobi = JacobiPreconditioner::from_triplets(size, &mna.triplets);
+        let stalled = solve_gmres_real_preconditioned(&operator, &jacobi, &rhs, &gmres_config);
+        assert!(!stalled.converged);
+        assert!(stalled.iterations >= gmres_config.max_iter);
+        let ilu = Ilu0Preconditioner::from_triplets(size, &mna.triplets).unwrap();
+        let converged = solve_gmres_real_preconditioned(&operator, &ilu, &rhs, &gmres_config);
+        assert!(converged.converged);
+
+        let run = |config: DispatchConfig| {
+            solve_transient_dispatched(&stamper, &mut caps(), &mut [], &params, &dc, &config)
+                .unwrap()
+        };
+        let direct = run(DispatchConfig::default().with_strategy(SolverDispatchStrategy::DirectLU));
+        let gmres = DispatchConfig::default()
+            .with_strategy(SolverDispatchStrategy::IterativeGmres)
+            .with_gmres_config(gmres_config);
+        let with_ilu = run(gmres.clone().with_preconditioner(PreconditionerType::Ilu0));
+        let with_jacobi = run(gmres.with_preconditioner(PreconditionerType::Jacobi));
+
+        let error = |result: &TransientResult| {
+            let expected = &direct.points.last().unwrap().solution;
+            (&result.points.last().unwrap().solution - expected).amax() / expected.amax()
+        };
+        assert!(error(&with_ilu) < 1e-8, "ILU(0) error {}", error(&with_ilu));
+        assert!(
+            error(&with_jacobi) > 1e-6,
+            "Jacobi error {}",
+            error(&with_jacobi)
+        );
+    }
+
     /// Simple RC circuit stamper: V1 -- R -- node0 -- C -- GND
     struct RcCircuitStamper {
         voltage: f64,
@@ -112,6 +212,8 @@ mod tests {
             tstop: 5e-3,  // 5 time constants
             tstep: 10e-6, // 10us steps
             method: IntegrationMethod::BackwardEuler,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
         };
 
         let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]); // V(0)=5, V(1)=0, I(V1)=-5mA
@@ -151,6 +253,8 @@ mod tests {
             tstop: 5e-3,
             tstep: 10e-6,
             method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
         };
 
         let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
@@ -226,6 +330,9 @@ mod tests {
             reltol: 1e-3,
             abstol: 1e-6,
             method: IntegrationMethod::Trapezoidal,
+            record_lte: false,
+            max_step_windows: Vec::new(),
+            breakpoints: Vec::new(),
         };
 
         let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
@@ -249,6 +356,14 @@ mod tests {
             result.total_steps
         );
 
+        // One stored point per accepted step, plus the initial point
+        let accepted = result.accepted_steps();
+        assert_eq!(accepted, result.total_steps - result.rejected_steps);
+        assert_eq!(result.points.len(), accepted + 1);
+        let average = result.average_timestep();
+        assert!((average - params.tstop / accepted as f64).abs() < 1e-12 * average);
+        assert!(average > result.min_step_used && average < result.max_step_used);
+
         // Timestep should increase as capacitor approaches steady state
         assert!(
             result.max_step_used > params.h_init * 10.0,
@@ -263,6 +378,386 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_adaptive_rc_resampled_to_uniform_grid() {
+        // RC circuit: V1=5V, R=1k, C=1uF, tau=1ms
+        let stamper = RcCircuitStamper {
+            voltage: 5.0,
+            resistance: 1000.0,
+        };
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+        let params = AdaptiveTransientParams {
+            tstop: 5e-3,
+            h_init: 1e-7,
+            h_min: 1e-9,
+            h_max: 1e-4,
+            reltol: 1e-3,
+            abstol: 1e-6,
+            method: IntegrationMethod::Trapezoidal,
+            record_lte: false,
+            max_step_windows: Vec::new(),
+            breakpoints: Vec::new(),
+        };
+        let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
+        let result = solve_transient_adaptive(&stamper, &mut caps, &mut [], &params, &dc).unwrap();
+
+        let tau = 1e-3;
+        let sampled = result.sample_at_times(1e-4, None, None);
+        assert_eq!(sampled.points.len(), 51);
+        assert_eq!(sampled.num_nodes, result.num_nodes);
+        for (k, tp) in sampled.points.iter().enumerate() {
+            assert!((tp.time - k as f64 * 1e-4).abs() < 1e-15);
+            let expected = 5.0 * (1.0 - (-tp.time / tau).exp());
+            assert!(
+                (tp.solution[1] - expected).abs() < 0.02,
+                "V(cap) at t={:.1e}: {} (expected {})",
+                tp.time,
+                tp.solution[1],
+                expected
+            );
+        }
+
+        // Times outside the run clamp to its endpoints
+        let last = result.points.last().unwrap();
+        assert_eq!(result.voltage_at(1, -1e-3), Some(0.0));
+        assert_eq!(result.voltage_at(1, 1.0), Some(last.solution[1]));
+    }
+
+    /// RC circuit driven by a 0 → 5V step at `t_step`.
+    struct SteppedRcStamper {
+        t_step: f64,
+    }
+
+    impl TransientStamper for SteppedRcStamper {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
+            let v = if time >= self.t_step { 5.0 } else { 0.0 };
+            mna.stamp_voltage_source(Some(0), None, 0, v);
+            mna.stamp_conductance(Some(0), Some(1), 1.0 / 1000.0);
+        }
+
+        fn num_nodes(&self) -> usize {
+            2
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_adaptive_lte_history() {
+        // tau = 1ms, source steps at 1ms, run out to 10ms (9 tau after the step)
+        let t_step = 1e-3;
+        let stamper = SteppedRcStamper { t_step };
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+
+        let params = AdaptiveTransientParams {
+            tstop: 10e-3,
+            h_init: 1e-7,
+            h_min: 1e-9,
+            h_max: 1e-4,
+            reltol: 1e-3,
+            abstol: 1e-6,
+            method: IntegrationMethod::Trapezoidal,
+            record_lte: true,
+            max_step_windows: Vec::new(),
+            breakpoints: Vec::new(),
+        };
+
+        let dc = DVector::from_vec(vec![0.0, 0.0, 0.0]);
+        let result = solve_transient_adaptive(&stamper, &mut caps, &mut [], &params, &dc).unwrap();
+
+        assert_eq!(result.lte_history.len(), result.points.len());
+        assert_eq!(result.lte_history[0], 0.0);
+
+        let max_in = |lo: f64, hi: f64| {
+            result
+                .points
+                .iter()
+                .zip(&result.lte_history)
+                .filter(|(p, _)| p.time > lo && p.time <= hi)
+                .map(|(_, &lte)| lte)
+                .fold(0.0_f64, f64::max)
+        };
+
+        let before = max_in(0.0, t_step);
+        let after_step = max_in(t_step, t_step + 0.5e-3);
+        let settled = max_in(8e-3, 10e-3);
+
+        // Quiescent before the step, largest just after it, small once settled
+        assert!(before < 1e-12, "LTE before step = {:e}", before);
+        assert!(
+            after_step > 100.0 * settled,
+            "LTE after step {:e} should dwarf settled LTE {:e}",
+            after_step,
+            settled
+        );
+
+        // Without the flag nothing is recorded
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+        let params = AdaptiveTransientParams {
+            record_lte: false,
+            ..params
+        };
+        let result = solve_transient_adaptive(&stamper, &mut caps, &mut [], &params, &dc).unwrap();
+        assert!(result.lte_history.is_empty());
+    }
+
+    #[test]
+    fn test_max_step_window_densifies_edge() {
+        // Source steps at 1ms; cap h at 1us over [0.9ms, 1.2ms] only.
+        let t_step = 1e-3;
+        let stamper = SteppedRcStamper { t_step };
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+
+        let params = AdaptiveTransientParams {
+            tstop: 5e-3,
+            h_init: 1e-7,
+            h_min: 1e-9,
+            h_max: 1e-4,
+            reltol: 1e-3,
+            abstol: 1e-6,
+            method: IntegrationMethod::Trapezoidal,
+            record_lte: false,
+            max_step_windows: vec![MaxStepWindow::new(0.9e-3, 1.2e-3, 1e-6)],
+            breakpoints: Vec::new(),
+        };
+
+        let dc = DVector::from_vec(vec![0.0, 0.0, 0.0]);
+        let result = solve_transient_adaptive(&stamper, &mut caps, &mut [], &params, &dc).unwrap();
+
+        let times: Vec<f64> = result.points.iter().map(|p| p.time).collect();
+        let count_in = |lo: f64, hi: f64| times.iter().filter(|&&t| t > lo && t <= hi).count();
+
+        // The step landing on the window start is kept exactly
+        assert!(times.iter().any(|&t| (t - 0.9e-3).abs() < 1e-15));
+
+        // No step inside the window exceeds its cap
+        for w in times.windows(2) {
+            if w[0] >= 0.9e-3 && w[1] <= 1.2e-3 {
+                assert!(w[1] - w[0] <= 1e-6 * (1.0 + 1e-9), "step {:e}", w[1] - w[0]);
+            }
+        }
+
+        // ~300 points across the 0.3ms window, far fewer over the 3.8ms after it
+        let dense = count_in(0.9e-3, 1.2e-3);
+        let sparse = count_in(1.2e-3, 5e-3);
+        assert!(dense >= 299, "{} points in window", dense);
+        assert!(
+            sparse < dense / 2,
+            "{} points outside vs {} inside",
+            sparse,
+            dense
+        );
+
+        // Without the window the stepper never gets that fine
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+        let params = AdaptiveTransientParams {
+            max_step_windows: Vec::new(),
+            breakpoints: Vec::new(),
+            ..params
+        };
+        let result = solve_transient_adaptive(&stamper, &mut caps, &mut [], &params, &dc).unwrap();
+        let plain = result
+            .points
+            .iter()
+            .filter(|p| p.time > 0.9e-3 && p.time <= 1.2e-3)
+            .count();
+        assert!(plain < dense / 4, "{} points without window", plain);
+    }
+
+    /// RC low-pass driven by a PULSE source whose edges fall between the
+    /// regular timesteps.
+    struct PulseRcStamper {
+        pulse: spicier_devices::waveforms::Waveform,
+    }
+
+    impl TransientStamper for PulseRcStamper {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, self.pulse.value_at(time));
+            mna.stamp_conductance(Some(0), Some(1), 1e-3);
+        }
+        fn num_nodes(&self) -> usize {
+            2
+        }
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_breakpoints_land_on_pulse_edges() {
+        use spicier_devices::waveforms::Waveform;
+
+        // PULSE(0 5 1.03m 10u 10u 0.5m): the rise starts 30us past a
+        // 100us grid point and is over before the next one.
+        let (td, tr, tf, pw) = (1.03e-3, 10e-6, 10e-6, 0.5e-3);
+        let stamper = PulseRcStamper {
+            pulse: Waveform::pulse(0.0, 5.0, td, tr, tf, pw, 0.0),
+        };
+        let edges = vec![td, td + tr, td + tr + pw, td + tr + pw + tf];
+        let dc = DVector::zeros(3);
+        let has_point = |times: &[f64], t: f64| times.iter().any(|&p| (p - t).abs() < 1e-15);
+
+        let mut params = TransientParams {
+            tstop: 3e-3,
+            tstep: 100e-6,
+            method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        };
+        let run = |params: &TransientParams| {
+            let mut caps = vec![CapacitorState::new(100e-9, Some(1), None)];
+            solve_transient(&stamper, &mut caps, &mut [], params, &dc).unwrap()
+        };
+
+        // Without breakpoints the grid skips right over the rise
+        let plain = run(&params);
+        let times: Vec<f64> = plain.points.iter().map(|p| p.time).collect();
+        assert!(!has_point(&times, td));
+        assert_eq!(times.len(), 31);
+
+        params.breakpoints = edges.clone();
+        // A breakpoint on a grid point adds nothing
+        params.breakpoints.push(2e-3 + 1e-16);
+        let result = run(&params);
+        let times: Vec<f64> = result.points.iter().map(|p| p.time).collect();
+        for &edge in &edges {
+            assert!(has_point(&times, edge), "no timepoint at {:e}", edge);
+        }
+        assert_eq!(times.len(), 31 + edges.len());
+        assert!(times.windows(2).all(|w| w[1] > w[0]));
+
+        // The source reads v1 at the start of the rise and v2 at its end
+        let at = |t: f64| result.points.iter().find(|p| (p.time - t).abs() < 1e-15);
+        assert_eq!(at(td).unwrap().solution[0], 0.0);
+        assert_eq!(at(td + tr).unwrap().solution[0], 5.0);
+
+        // Adaptive: lands on each edge and restarts from h_min after it
+        let adaptive = AdaptiveTransientParams {
+            tstop: 3e-3,
+            h_init: 1e-6,
+            h_min: 1e-9,
+            h_max: 100e-6,
+            breakpoints: edges.clone(),
+            ..Default::default()
+        };
+        let mut caps = vec![CapacitorState::new(100e-9, Some(1), None)];
+        let result =
+            solve_transient_adaptive(&stamper, &mut caps, &mut [], &adaptive, &dc).unwrap();
+        let times: Vec<f64> = result.points.iter().map(|p| p.time).collect();
+        for &edge in &edges {
+            let k = times
+                .iter()
+                .position(|&p| p == edge)
+                .unwrap_or_else(|| panic!("no timepoint at {:e}", edge));
+            assert!(
+                times[k + 1] - edge <= 2.0 * adaptive.h_min,
+                "step after {:e} was {:e}",
+                edge,
+                times[k + 1] - edge
+            );
+        }
+        assert!((result.points.last().unwrap().solution[1]).abs() < 1e-3);
+
+        // Each accepted point sees the source at its own time, not at the
+        // start of the step that reached it.
+        for p in &result.points {
+            let v = stamper.pulse.value_at(p.time);
+            assert!(
+                (p.solution[0] - v).abs() < 1e-9,
+                "V(src) at {:e} = {} (expected {})",
+                p.time,
+                p.solution[0],
+                v
+            );
+        }
+    }
+
+    /// 1V step into two independent RC branches: node 1 with τ = 1ms and
+    /// node 2 with τ = 1µs. The fast branch settles almost immediately and
+    /// makes the system stiff for the rest of the run.
+    struct StiffRcPairStamper;
+
+    impl StiffRcPairStamper {
+        const TAU_SLOW: f64 = 1e-3;
+        const TAU_FAST: f64 = 1e-6;
+
+        fn caps() -> Vec<CapacitorState> {
+            vec![
+                CapacitorState::new(1e-6, Some(1), None),
+                CapacitorState::new(1e-7, Some(2), None),
+            ]
+        }
+    }
+
+    impl TransientStamper for StiffRcPairStamper {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, _time: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, 1.0);
+            mna.stamp_conductance(Some(0), Some(1), 1.0 / 1000.0);
+            mna.stamp_conductance(Some(0), Some(2), 1.0 / 10.0);
+        }
+
+        fn num_nodes(&self) -> usize {
+            3
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_adaptive_trbdf2_stiff_fewer_steps() {
+        let run = |method| {
+            let params = AdaptiveTransientParams {
+                tstop: 5e-3,
+                h_init: 1e-9,
+                h_min: 1e-15,
+                h_max: 1e-4,
+                reltol: 1e-3,
+                abstol: 1e-6,
+                method,
+                record_lte: false,
+                max_step_windows: Vec::new(),
+                breakpoints: Vec::new(),
+            };
+            let dc = DVector::zeros(4);
+            let mut caps = StiffRcPairStamper::caps();
+            solve_transient_adaptive(&StiffRcPairStamper, &mut caps, &mut [], &params, &dc).unwrap()
+        };
+        let max_error = |result: &AdaptiveTransientResult| {
+            result
+                .points
+                .iter()
+                .flat_map(|p| {
+                    [
+                        (p.solution[1], StiffRcPairStamper::TAU_SLOW),
+                        (p.solution[2], StiffRcPairStamper::TAU_FAST),
+                    ]
+                    .map(|(v, tau)| (v - (1.0 - (-p.time / tau).exp())).abs())
+                })
+                .fold(0.0_f64, f64::max)
+        };
+
+        let trap = run(IntegrationMethod::Trapezoidal);
+        let trbdf2 = run(IntegrationMethod::TrBdf2);
+        let (trap_error, trbdf2_error) = (max_error(&trap), max_error(&trbdf2));
+
+        // Both track the analytic response to the same accuracy...
+        assert!(trap_error < 2e-3, "trapezoidal error {:e}", trap_error);
+        assert!(trbdf2_error < 2e-3, "TR-BDF2 error {:e}", trbdf2_error);
+
+        // ...but Trapezoidal's estimate keeps seeing the undamped fast mode,
+        // while the L-stable TR-BDF2 steps over it.
+        assert!(
+            2 * trbdf2.total_steps < trap.total_steps,
+            "TR-BDF2 took {} steps, Trapezoidal {}",
+            trbdf2.total_steps,
+            trap.total_steps
+        );
+    }
+
     #[test]
     fn test_lte_estimation() {
         // Test that LTE estimate is reasonable for a smooth (constant rate) change.
@@ -340,6 +835,53 @@ mod tests {
         // Test voltage_at helper
         assert!((result.voltage_at(0, 0.5).unwrap() - 0.5).abs() < 1e-10);
         assert!((result.voltage_at(1, 1.5).unwrap() - 3.0).abs() < 1e-10);
+
+        // Non-finite times have no sample
+        assert!(result.interpolate_at(f64::NAN).is_none());
+        assert!(result.interpolate_at(f64::INFINITY).is_none());
+        assert!(result.interpolate_at_quadratic(f64::NAN).is_none());
+
+        // A corrupt first timepoint fails softly instead of indexing before
+        // the start
+        let mut corrupt = result.clone();
+        corrupt.points[0].time = f64::NAN;
+        assert!(corrupt.interpolate_at(0.5).is_none());
+    }
+
+    #[test]
+    fn test_interpolate_at_quadratic() {
+        // v(t) = t² on an uneven grid
+        let f = |t: f64| t * t;
+        let points = [0.0, 0.4, 1.0, 1.5, 3.0]
+            .into_iter()
+            .map(|time| TimePoint {
+                time,
+                solution: DVector::from_vec(vec![f(time)]),
+            })
+            .collect();
+        let result = TransientResult {
+            points,
+            num_nodes: 1,
+        };
+
+        for t in [0.1, 0.7, 1.2, 2.2, 2.9] {
+            let quadratic = result.interpolate_at_quadratic(t).unwrap()[0];
+            let linear = result.interpolate_at(t).unwrap()[0];
+            assert!((quadratic - f(t)).abs() < 1e-12, "t={t}: {quadratic}");
+            assert!((linear - f(t)).abs() > 1e-3, "t={t}: {linear}");
+        }
+
+        // Stored points are reproduced and out-of-range times clamp
+        assert!((result.interpolate_at_quadratic(1.5).unwrap()[0] - f(1.5)).abs() < 1e-12);
+        assert_eq!(result.interpolate_at_quadratic(-1.0).unwrap()[0], 0.0);
+        assert_eq!(result.interpolate_at_quadratic(4.0).unwrap()[0], 9.0);
+
+        // Two points only: linear
+        let short = TransientResult {
+            points: result.points[..2].to_vec(),
+            num_nodes: 1,
+        };
+        assert!((short.interpolate_at_quadratic(0.2).unwrap()[0] - 0.08).abs() < 1e-12);
     }
 
     #[test]
@@ -397,6 +939,8 @@ mod tests {
             tstop: 5e-3,
             tstep: 10e-6,
             method: IntegrationMethod::TrBdf2,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
         };
 
         let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
@@ -425,6 +969,124 @@ mod tests {
         );
     }
 
+    /// A 5 V/ms ramp from rest (node 0) through 1 kΩ into node 1, which
+    /// the test loads with a 1 µF capacitor or a 1 H inductor (τ = 1 ms).
+    /// The zero initial state is consistent, so the exact response holds
+    /// from the first step.
+    struct RampDrivenStamper;
+
+    impl TransientStamper for RampDrivenStamper {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, 5e3 * time);
+            mna.stamp_conductance(Some(0), Some(1), 1e-3);
+        }
+
+        fn num_nodes(&self) -> usize {
+            2
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_trbdf2_fixed_step_converges_second_order() {
+        let tau = 1e-3;
+        let decay = |t: f64| tau * (1.0 - (-t / tau).exp());
+        let dc = DVector::from_vec(vec![0.0, 0.0, 0.0]);
+
+        // Largest deviation of V(1) from the exact response
+        let max_error = |tstep: f64, inductive: bool| {
+            let mut caps = Vec::new();
+            let mut inds = Vec::new();
+            if inductive {
+                inds.push(InductorState::new(1.0, Some(1), None, 0));
+            } else {
+                caps.push(CapacitorState::new(1e-6, Some(1), None));
+            }
+            let params = TransientParams {
+                tstop: 2e-3,
+                tstep,
+                method: IntegrationMethod::TrBdf2,
+                be_startup_steps: 0,
+                breakpoints: Vec::new(),
+            };
+            let result =
+                solve_transient(&RampDrivenStamper, &mut caps, &mut inds, &params, &dc).unwrap();
+            result
+                .points
+                .iter()
+                .map(|tp| {
+                    let exact = if inductive {
+                        5e3 * decay(tp.time)
+                    } else {
+                        5e3 * (tp.time - decay(tp.time))
+                    };
+                    (tp.solution[1] - exact).abs()
+                })
+                .fold(0.0, f64::max)
+        };
+
+        // Halving the step should cut the error about 4x
+        for inductive in [false, true] {
+            let errors: Vec<f64> = [100e-6, 50e-6, 25e-6]
+                .into_iter()
+                .map(|tstep| max_error(tstep, inductive))
+                .collect();
+            for pair in errors.windows(2) {
+                let ratio = pair[0] / pair[1];
+                assert!(
+                    ratio > 3.5,
+                    "inductive={}: error ratio {} on halving the step (errors {:?})",
+                    inductive,
+                    ratio,
+                    errors
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rc_charging_gear2() {
+        let stamper = RcCircuitStamper {
+            voltage: 5.0,
+            resistance: 1000.0,
+        };
+
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+
+        let params = TransientParams {
+            tstop: 5e-3,
+            tstep: 10e-6,
+            method: IntegrationMethod::Gear2,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        };
+
+        let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
+
+        let result = solve_transient(&stamper, &mut caps, &mut [], &params, &dc).unwrap();
+
+        let final_voltage = result.points.last().unwrap().solution[1];
+        assert!(
+            (final_voltage - 5.0).abs() < 0.05,
+            "Final V(cap) = {} (expected ≈ 5.0)",
+            final_voltage
+        );
+
+        // Second order after the Backward Euler first step
+        let tau_step = (1e-3 / params.tstep).round() as usize;
+        let v_at_tau = result.points[tau_step].solution[1];
+        let expected_v_tau = 5.0 * (1.0 - (-1.0_f64).exp());
+        assert!(
+            (v_at_tau - expected_v_tau).abs() < 0.02,
+            "V(cap) at tau = {} (expected ≈ {}) [Gear2]",
+            v_at_tau,
+            expected_v_tau
+        );
+    }
+
     /// Simple LC circuit stamper for oscillation test.
     /// Circuit: Initial voltage on capacitor, connected to inductor.
     /// Node 0: capacitor top / inductor top
@@ -447,6 +1109,181 @@ mod tests {
         }
     }
 
+    /// 1V step through R into a parallel LC tank (node 1), from a zero state.
+    struct SteppedRlcTankStamper;
+
+    impl TransientStamper for SteppedRlcTankStamper {
+        fn stamp_at_time(&self, mna: &mut MnaSystem, _time: f64) {
+            mna.stamp_voltage_source(Some(0), None, 0, 1.0);
+            mna.stamp_conductance(Some(0), Some(1), 1.0 / 10.0);
+        }
+
+        fn num_nodes(&self) -> usize {
+            2
+        }
+
+        fn num_vsources(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_be_startup_reduces_trapezoidal_ringing() {
+        // R = 10Ω, C = 1µF gives a 10µs RC mode that a 50µs step cannot
+        // resolve; L = 100mH rings slowly (period ≈ 2ms).
+        let run = |tstep: f64, be_startup_steps: usize| -> TransientResult {
+            let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+            let mut inds = vec![InductorState::new(100e-3, Some(1), None, 1)];
+            let params = TransientParams {
+                tstop: 1e-3,
+                tstep,
+                method: IntegrationMethod::Trapezoidal,
+                be_startup_steps,
+                breakpoints: Vec::new(),
+            };
+            let dc = DVector::zeros(3);
+            solve_transient(&SteppedRlcTankStamper, &mut caps, &mut inds, &params, &dc).unwrap()
+        };
+
+        // A passive network can never push node 1 above the 1V source.
+        // Trapezoidal's undamped RC mode overshoots; a BE startup does not.
+        let peak = |result: &TransientResult| {
+            result
+                .points
+                .iter()
+                .map(|p| p.solution[1])
+                .fold(f64::NEG_INFINITY, f64::max)
+        };
+
+        let trap_only = peak(&run(50e-6, 0));
+        let with_be = peak(&run(50e-6, 2));
+
+        assert!(
+            trap_only > 1.05,
+            "expected trapezoidal overshoot, peak = {}",
+            trap_only
+        );
+        assert!(
+            with_be <= 1.0,
+            "BE startup should not overshoot, peak = {}",
+            with_be
+        );
+    }
+
+    #[test]
+    fn test_progress_hook_stops_run_with_partial_result() {
+        let params = TransientParams {
+            tstop: 1e-3,
+            tstep: 10e-6,
+            method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 2,
+            breakpoints: Vec::new(),
+        };
+        let run = |on_step: &mut dyn FnMut(&TimePoint) -> std::ops::ControlFlow<()>| {
+            let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+            let mut inds = vec![InductorState::new(100e-3, Some(1), None, 1)];
+            let dc = DVector::zeros(3);
+            solve_transient_with_progress(
+                &SteppedRlcTankStamper,
+                &mut caps,
+                &mut inds,
+                &params,
+                &dc,
+                on_step,
+            )
+            .unwrap()
+        };
+
+        let full = run(&mut |_| std::ops::ControlFlow::Continue(()));
+        assert_eq!(full.points.len(), 101);
+
+        let mut seen = 0;
+        let partial = run(&mut |_| {
+            seen += 1;
+            if seen == 5 {
+                std::ops::ControlFlow::Break(())
+            } else {
+                std::ops::ControlFlow::Continue(())
+            }
+        });
+        // The initial point plus the five reported steps, identical to the full run.
+        assert_eq!(partial.points.len(), 6);
+        for (p, f) in partial.points.iter().zip(&full.points) {
+            assert_eq!(p.time, f.time);
+            assert_eq!(p.solution, f.solution);
+        }
+    }
+
+    #[test]
+    fn test_probed_run_matches_full_run() {
+        let params = TransientParams {
+            tstop: 1e-3,
+            tstep: 10e-6,
+            method: IntegrationMethod::TrBdf2,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        };
+        let state = || {
+            (
+                vec![CapacitorState::new(1e-6, Some(1), None)],
+                vec![InductorState::new(100e-3, Some(1), None, 1)],
+            )
+        };
+        let dc = DVector::zeros(3);
+
+        let (mut caps, mut inds) = state();
+        let full =
+            solve_transient(&SteppedRlcTankStamper, &mut caps, &mut inds, &params, &dc).unwrap();
+
+        // Keep only V(out) = node 1
+        let (mut caps, mut inds) = state();
+        let probed = solve_transient_probed(
+            &SteppedRlcTankStamper,
+            &mut caps,
+            &mut inds,
+            &params,
+            &dc,
+            &[1],
+        )
+        .unwrap();
+
+        assert_eq!(probed.points.len(), full.points.len());
+        assert!(probed.points.iter().all(|p| p.solution.len() == 1));
+        assert_eq!(probed.voltage_waveform(0), full.voltage_waveform(1));
+        assert_eq!(probed.num_nodes, 1);
+
+        // V(out) then the source's branch current: one voltage, one current
+        let (mut caps, mut inds) = state();
+        let mixed = solve_transient_probed(
+            &SteppedRlcTankStamper,
+            &mut caps,
+            &mut inds,
+            &params,
+            &dc,
+            &[1, 2],
+        )
+        .unwrap();
+        assert_eq!(mixed.num_nodes, 1);
+        assert_eq!(mixed.voltage_waveform(1), full.voltage_waveform(2));
+
+        let (mut caps, mut inds) = state();
+        assert!(matches!(
+            solve_transient_probed(
+                &SteppedRlcTankStamper,
+                &mut caps,
+                &mut inds,
+                &params,
+                &dc,
+                &[3],
+            ),
+            Err(Error::IndexOutOfRange {
+                what: "probe",
+                index: 3,
+                len: 3
+            })
+        ));
+    }
+
     #[test]
     fn test_lc_oscillation() {
         // LC circuit: L = 1mH, C = 1µF
@@ -473,6 +1310,8 @@ mod tests {
             tstop: 5.0 * expected_period,
             tstep: expected_period / 50.0, // 50 points per period
             method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
         };
 
         let result =
@@ -540,4 +1379,124 @@ mod tests {
             measured_period * 1e6
         );
     }
+
+    #[test]
+    fn test_lc_rms_and_average() {
+        // Same 5 V LC tank as test_lc_oscillation
+        let period = 2.0 * std::f64::consts::PI * (1e-3_f64 * 1e-6).sqrt();
+        let mut caps = vec![CapacitorState::new(1e-6, Some(0), None)];
+        let mut inds = vec![InductorState::new(1e-3, Some(0), None, 0)];
+        let params = TransientParams {
+            tstop: 5.0 * period,
+            tstep: period / 50.0,
+            method: IntegrationMethod::Trapezoidal,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        };
+        let dc = DVector::from_vec(vec![5.0]);
+        let result =
+            solve_transient(&LcOscillatorStamper, &mut caps, &mut inds, &params, &dc).unwrap();
+
+        assert!((result.max_voltage(0).unwrap() - 5.0).abs() < 0.05);
+        assert!((result.min_voltage(0).unwrap() + 5.0).abs() < 0.05);
+
+        // Four whole periods, starting between timepoints
+        let (tstart, tstop) = (0.33 * period, 4.33 * period);
+        let rms = result.rms_voltage(0, tstart, tstop).unwrap();
+        let average = result.average_voltage(0, tstart, tstop).unwrap();
+        assert!(
+            (rms - 5.0 / 2f64.sqrt()).abs() < 0.02,
+            "RMS {} (expected {})",
+            rms,
+            5.0 / 2f64.sqrt()
+        );
+        assert!(average.abs() < 0.02, "average {}", average);
+
+        // The window clips to the simulated range
+        let clipped = result.rms_voltage(0, -period, 10.0 * period).unwrap();
+        let full = result.rms_voltage(0, 0.0, params.tstop).unwrap();
+        assert!((clipped - full).abs() < 1e-12);
+        assert!(
+            result
+                .average_voltage(0, 6.0 * period, 7.0 * period)
+                .is_none()
+        );
+        assert!(result.rms_voltage(0, f64::NAN, period).is_none());
+    }
+
+    #[test]
+    fn test_lc_oscillation_gear2() {
+        // Same 1mH / 1µF tank as test_lc_oscillation. BDF2 damps the
+        // oscillation slightly, but the frequency should still be right.
+        let (inductance, capacitance) = (1e-3_f64, 1e-6_f64);
+        let expected_freq = 1.0 / (2.0 * std::f64::consts::PI * (inductance * capacitance).sqrt());
+        let expected_period = 1.0 / expected_freq;
+
+        let dc = DVector::from_vec(vec![5.0]);
+        let mut caps = vec![CapacitorState::new(capacitance, Some(0), None)];
+        let mut inds = vec![InductorState::new(inductance, Some(0), None, 0)];
+        let params = TransientParams {
+            tstop: 5.0 * expected_period,
+            tstep: expected_period / 50.0,
+            method: IntegrationMethod::Gear2,
+            be_startup_steps: 0,
+            breakpoints: Vec::new(),
+        };
+
+        let result =
+            solve_transient(&LcOscillatorStamper, &mut caps, &mut inds, &params, &dc).unwrap();
+
+        // Positive-to-negative zero crossings, linearly interpolated
+        let crossings: Vec<f64> = result
+            .points
+            .windows(2)
+            .filter(|w| w[0].solution[0] > 0.0 && w[1].solution[0] <= 0.0)
+            .map(|w| {
+                let (v0, v1) = (w[0].solution[0], w[1].solution[0]);
+                w[0].time + v0 * (w[1].time - w[0].time) / (v0 - v1)
+            })
+            .collect();
+        assert!(crossings.len() >= 3, "{} zero crossings", crossings.len());
+
+        let measured_freq =
+            (crossings.len() - 1) as f64 / (crossings.last().unwrap() - crossings[0]);
+        let freq_error = (measured_freq - expected_freq).abs() / expected_freq;
+        assert!(
+            freq_error < 0.05,
+            "Gear2 LC frequency {} Hz differs from expected {} Hz by {:.1}%",
+            measured_freq,
+            expected_freq,
+            freq_error * 100.0
+        );
+
+        // Damped, but not wiped out, after 5 periods
+        let last_period = result
+            .points
+            .iter()
+            .filter(|p| p.time >= 4.0 * expected_period)
+            .map(|p| p.solution[0].abs())
+            .fold(0.0_f64, f64::max);
+        assert!(
+            last_period > 2.5 && last_period <= 5.0,
+            "Gear2 LC amplitude {} after 4 periods",
+            last_period
+        );
+    }
+
+    #[test]
+    fn test_adaptive_rejects_gear2() {
+        let stamper = RcCircuitStamper {
+            voltage: 5.0,
+            resistance: 1000.0,
+        };
+        let mut caps = vec![CapacitorState::new(1e-6, Some(1), None)];
+        let params = AdaptiveTransientParams {
+            method: IntegrationMethod::Gear2,
+            ..AdaptiveTransientParams::for_tstop(5e-3)
+        };
+        let dc = DVector::from_vec(vec![5.0, 0.0, -0.005]);
+
+        let err = solve_transient_adaptive(&stamper, &mut caps, &mut [], &params, &dc).unwrap_err();
+        assert!(err.to_string().contains("Gear2"), "{err}");
+    }
 }
@@ -36,36 +36,23 @@ impl TransientResult {
 
     /// Interpolate the solution at a specific time.
     ///
-    /// Uses linear interpolation between the two nearest timepoints.
-    /// Returns None if time is outside the simulation range.
+    /// Uses linear interpolation between the two nearest timepoints. Times
+    /// outside the simulated range clamp to the first or last point; returns
+    /// None if the result has no points or `time` is not finite.
     pub fn interpolate_at(&self, time: f64) -> Option<DVector<f64>> {
-        if self.points.is_empty() {
-            return None;
-        }
-
-        // Handle boundary cases
-        if time <= self.points[0].time {
-            return Some(self.points[0].solution.clone());
-        }
-        if time >= self.points.last()?.time {
-            return Some(self.points.last()?.solution.clone());
-        }
-
-        // Find the interval containing time
-        for i in 0..self.points.len() - 1 {
-            let t0 = self.points[i].time;
-            let t1 = self.points[i + 1].time;
-
-            if time >= t0 && time <= t1 {
-                // Linear interpolation
-                let alpha = (time - t0) / (t1 - t0);
-                let v0 = &self.points[i].solution;
-                let v1 = &self.points[i + 1].solution;
-                return Some(v0 * (1.0 - alpha) + v1 * alpha);
-            }
-        }
+        interpolate_points(&self.points, time)
+    }
 
-        None
+    /// Interpolate the solution at a specific time with a parabola.
+    ///
+    /// Fits the three stored points nearest `time` (the two bracketing it
+    /// plus the closer neighbour) and evaluates the fit, which tracks the
+    /// curvature that second-order integrators resolve, e.g. when locating
+    /// a peak between steps. Falls back to [`interpolate_at`](Self::interpolate_at)
+    /// when fewer than three points are stored, and clamps out-of-range
+    /// times the same way.
+    pub fn interpolate_at_quadratic(&self, time: f64) -> Option<DVector<f64>> {
+        interpolate_points_quadratic(&self.points, time)
     }
 
     /// Sample the waveform at evenly-spaced times.
@@ -82,21 +69,8 @@ impl TransientResult {
         tstart: Option<f64>,
         tstop: Option<f64>,
     ) -> TransientResult {
-        let tstart = tstart.unwrap_or(0.0);
-        let tstop = tstop.unwrap_or_else(|| self.points.last().map(|p| p.time).unwrap_or(0.0));
-
-        let mut sampled_points = Vec::new();
-        let mut t = tstart;
-
-        while t <= tstop + tstep * 0.001 {
-            if let Some(solution) = self.interpolate_at(t) {
-                sampled_points.push(TimePoint { time: t, solution });
-            }
-            t += tstep;
-        }
-
         TransientResult {
-            points: sampled_points,
+            points: sample_points(&self.points, tstep, tstart, tstop),
             num_nodes: self.num_nodes,
         }
     }
@@ -105,6 +79,78 @@ impl TransientResult {
     pub fn voltage_at(&self, node_idx: usize, time: f64) -> Option<f64> {
         self.interpolate_at(time).map(|sol| sol[node_idx])
     }
+
+    /// Largest stored voltage at a node (None if there are no points).
+    pub fn max_voltage(&self, node_idx: usize) -> Option<f64> {
+        self.points
+            .iter()
+            .map(|tp| tp.solution[node_idx])
+            .reduce(f64::max)
+    }
+
+    /// Smallest stored voltage at a node (None if there are no points).
+    pub fn min_voltage(&self, node_idx: usize) -> Option<f64> {
+        self.points
+            .iter()
+            .map(|tp| tp.solution[node_idx])
+            .reduce(f64::min)
+    }
+
+    /// RMS voltage at a node over `[tstart, tstop]`.
+    ///
+    /// Integrates `v²` with the trapezoidal rule over the stored (possibly
+    /// non-uniform) timepoints, interpolating at the window edges. The
+    /// window is clipped to the simulated range; returns None if nothing of
+    /// it remains.
+    pub fn rms_voltage(&self, node_idx: usize, tstart: f64, tstop: f64) -> Option<f64> {
+        let (span, integral) = self.integrate_window(node_idx, tstart, tstop, |v| v * v)?;
+        Some((integral / span).sqrt())
+    }
+
+    /// Time-averaged voltage at a node over `[tstart, tstop]`.
+    ///
+    /// Uses the same trapezoidal integration and window clipping as
+    /// [`rms_voltage`](Self::rms_voltage).
+    pub fn average_voltage(&self, node_idx: usize, tstart: f64, tstop: f64) -> Option<f64> {
+        let (span, integral) = self.integrate_window(node_idx, tstart, tstop, |v| v)?;
+        Some(integral / span)
+    }
+
+    /// Trapezoidal integral of `f(v)` for a node over the clipped window,
+    /// with the window's length (None if either bound is NaN).
+    fn integrate_window(
+        &self,
+        node_idx: usize,
+        tstart: f64,
+        tstop: f64,
+        f: impl Fn(f64) -> f64,
+    ) -> Option<(f64, f64)> {
+        // `f64::max`/`min` skip a NaN operand, which would widen the window
+        if tstart.is_nan() || tstop.is_nan() {
+            return None;
+        }
+        let tstart = tstart.max(self.points.first()?.time);
+        let tstop = tstop.min(self.points.last()?.time);
+        if tstop <= tstart {
+            return None;
+        }
+
+        let inner = self
+            .points
+            .iter()
+            .filter(|tp| tp.time > tstart && tp.time < tstop)
+            .map(|tp| (tp.time, tp.solution[node_idx]));
+        let samples: Vec<(f64, f64)> =
+            std::iter::once((tstart, self.voltage_at(node_idx, tstart)?))
+                .chain(inner)
+                .chain(std::iter::once((tstop, self.voltage_at(node_idx, tstop)?)))
+                .collect();
+        let integral = samples
+            .windows(2)
+            .map(|w| (w[1].0 - w[0].0) * (f(w[0].1) + f(w[1].1)) / 2.0)
+            .sum();
+        Some((tstop - tstart, integral))
+    }
 }
 
 /// Result of adaptive transient simulation with statistics.
@@ -122,9 +168,37 @@ pub struct AdaptiveTransientResult {
     pub min_step_used: f64,
     /// Maximum timestep used.
     pub max_step_used: f64,
+    /// Estimated local truncation error of each accepted step, aligned with
+    /// `points` (the initial point has an LTE of 0).
+    ///
+    /// Empty unless [`AdaptiveTransientParams::record_lte`](super::AdaptiveTransientParams::record_lte)
+    /// is set.
+    pub lte_history: Vec<f64>,
 }
 
 impl AdaptiveTransientResult {
+    /// Number of accepted timesteps.
+    ///
+    /// Every accepted step is stored, so `points` holds this many entries
+    /// plus the initial point.
+    pub fn accepted_steps(&self) -> usize {
+        self.total_steps - self.rejected_steps
+    }
+
+    /// Effective average timestep: the simulated time span divided by the
+    /// number of accepted steps (0.0 if no step was accepted).
+    ///
+    /// Compare with the fixed `tstep` a run would otherwise need; together
+    /// with [`rejected_steps`](Self::rejected_steps) it shows whether the
+    /// tolerances are forcing the stepper into small steps.
+    pub fn average_timestep(&self) -> f64 {
+        let accepted = self.accepted_steps();
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) if accepted > 0 => (last.time - first.time) / accepted as f64,
+            _ => 0.0,
+        }
+    }
+
     /// Get the voltage at a node across all timepoints.
     pub fn voltage_waveform(&self, node_idx: usize) -> Vec<(f64, f64)> {
         self.points
@@ -140,43 +214,19 @@ impl AdaptiveTransientResult {
 
     /// Interpolate the solution at a specific time.
     ///
-    /// Uses linear interpolation between the two nearest timepoints.
-    /// Returns None if time is outside the simulation range.
+    /// Uses linear interpolation between the two nearest timepoints. Times
+    /// outside the simulated range clamp to the first or last point; returns
+    /// None if the result has no points or `time` is not finite.
     pub fn interpolate_at(&self, time: f64) -> Option<DVector<f64>> {
-        if self.points.is_empty() {
-            return None;
-        }
-
-        // Handle boundary cases
-        if time <= self.points[0].time {
-            return Some(self.points[0].solution.clone());
-        }
-        if time >= self.points.last()?.time {
-            return Some(self.points.last()?.solution.clone());
-        }
-
-        // Find the interval containing time
-        for i in 0..self.points.len() - 1 {
-            let t0 = self.points[i].time;
-            let t1 = self.points[i + 1].time;
-
-            if time >= t0 && time <= t1 {
-                // Linear interpolation
-                let alpha = (time - t0) / (t1 - t0);
-                let v0 = &self.points[i].solution;
-                let v1 = &self.points[i + 1].solution;
-                return Some(v0 * (1.0 - alpha) + v1 * alpha);
-            }
-        }
-
-        None
+        interpolate_points(&self.points, time)
     }
 
     /// Sample the waveform at evenly-spaced times.
     ///
-    /// Returns a new TransientResult with timepoints at regular intervals.
-    /// This is useful for producing output at uniform time steps from an
-    /// adaptive simulation that used variable step sizes.
+    /// Returns a new TransientResult with timepoints at regular intervals,
+    /// linearly interpolated between the accepted steps. This is useful for
+    /// producing output at uniform time steps from an adaptive simulation
+    /// that used variable step sizes.
     ///
     /// # Arguments
     /// * `tstep` - Time step between samples
@@ -188,21 +238,8 @@ impl AdaptiveTransientResult {
         tstart: Option<f64>,
         tstop: Option<f64>,
     ) -> TransientResult {
-        let tstart = tstart.unwrap_or(0.0);
-        let tstop = tstop.unwrap_or_else(|| self.points.last().map(|p| p.time).unwrap_or(0.0));
-
-        let mut sampled_points = Vec::new();
-        let mut t = tstart;
-
-        while t <= tstop + tstep * 0.001 {
-            if let Some(solution) = self.interpolate_at(t) {
-                sampled_points.push(TimePoint { time: t, solution });
-            }
-            t += tstep;
-        }
-
         TransientResult {
-            points: sampled_points,
+            points: sample_points(&self.points, tstep, tstart, tstop),
             num_nodes: self.num_nodes,
         }
     }
@@ -212,3 +249,75 @@ impl AdaptiveTransientResult {
         self.interpolate_at(time).map(|sol| sol[node_idx])
     }
 }
+
+/// Linear interpolation of `points` at `time`, clamped to the endpoints.
+fn interpolate_points(points: &[TimePoint], time: f64) -> Option<DVector<f64>> {
+    if !time.is_finite() {
+        return None;
+    }
+    let (first, last) = (points.first()?, points.last()?);
+    if time <= first.time {
+        return Some(first.solution.clone());
+    }
+    if time >= last.time {
+        return Some(last.solution.clone());
+    }
+
+    // First point strictly after `time`; 0 only if the first stored time
+    // is NaN
+    let i = points.partition_point(|p| p.time <= time);
+    let (p0, p1) = (&points[i.checked_sub(1)?], points.get(i)?);
+    let alpha = (time - p0.time) / (p1.time - p0.time);
+    Some(&p0.solution * (1.0 - alpha) + &p1.solution * alpha)
+}
+
+/// Quadratic (Lagrange) interpolation of `points` at `time` through the
+/// bracketing pair and its nearer neighbour, clamped to the endpoints.
+fn interpolate_points_quadratic(points: &[TimePoint], time: f64) -> Option<DVector<f64>> {
+    let (first, last) = (points.first()?, points.last()?);
+    if points.len() < 3 || time <= first.time || time >= last.time {
+        return interpolate_points(points, time);
+    }
+
+    let i = points.partition_point(|p| p.time <= time);
+    if i == 0 || i >= points.len() {
+        return None;
+    }
+    // Third point: whichever outer neighbour of [i-1, i] is closer to `time`
+    let use_left = match (i.checked_sub(2), points.get(i + 1)) {
+        (Some(left), Some(right)) => time - points[left].time <= right.time - time,
+        (left, _) => left.is_some(),
+    };
+    let start = if use_left { i - 2 } else { i - 1 };
+    let [p0, p1, p2] = [&points[start], &points[start + 1], &points[start + 2]];
+    let (t0, t1, t2) = (p0.time, p1.time, p2.time);
+    let l0 = (time - t1) * (time - t2) / ((t0 - t1) * (t0 - t2));
+    let l1 = (time - t0) * (time - t2) / ((t1 - t0) * (t1 - t2));
+    let l2 = (time - t0) * (time - t1) / ((t2 - t0) * (t2 - t1));
+    Some(&p0.solution * l0 + &p1.solution * l1 + &p2.solution * l2)
+}
+
+/// Samples of `points` every `tstep` from `tstart` (default 0.0) through
+/// `tstop` (default: the last point's time).
+fn sample_points(
+    points: &[TimePoint],
+    tstep: f64,
+    tstart: Option<f64>,
+    tstop: Option<f64>,
+) -> Vec<TimePoint> {
+    let tstart = tstart.unwrap_or(0.0);
+    let tstop = tstop.unwrap_or_else(|| points.last().map(|p| p.time).unwrap_or(0.0));
+    if tstep <= 0.0 || tstop < tstart {
+        return Vec::new();
+    }
+
+    // Index the grid rather than accumulating `tstep`, so rounding error
+    // does not drift the sample times over long runs
+    let count = ((tstop - tstart) / tstep + 1e-3).floor() as usize + 1;
+    (0..count)
+        .filter_map(|k| {
+            let time = tstart + k as f64 * tstep;
+            interpolate_points(points, time).map(|solution| TimePoint { time, solution })
+        })
+        .collect()
+}
@@ -1,14 +1,16 @@
 //! Transient simulation solver functions.
 
+use std::ops::ControlFlow;
+
 use nalgebra::DVector;
 use spicier_core::mna::MnaSystem;
 
-use crate::dispatch::DispatchConfig;
-use crate::error::Result;
-use crate::gmres::GmresConfig;
+use crate::dispatch::{DispatchConfig, PreconditionerType};
+use crate::error::{Error, Result};
+use crate::ilu::Ilu0Preconditioner;
 use crate::linear::{CachedSparseLu, SPARSE_THRESHOLD, solve_dense};
 use crate::operator::RealOperator;
-use crate::preconditioner::{JacobiPreconditioner, RealPreconditioner};
+use crate::preconditioner::{IdentityPreconditioner, JacobiPreconditioner, RealPreconditioner};
 use crate::sparse_operator::SparseRealOperator;
 
 use super::companion::{CapacitorState, InductorState};
@@ -28,6 +30,39 @@ pub trait TransientStamper {
 
     /// Get the number of voltage source current variables.
     fn num_vsources(&self) -> usize;
+
+    /// Seed any history the stamper keeps from the initial operating point.
+    ///
+    /// Called once before the first timestep with the `dc_solution` passed to
+    /// the solver. Stampers whose devices remember past timepoints (such as
+    /// transmission lines) keep that state behind interior mutability.
+    fn start_transient(&self, _dc_solution: &DVector<f64>) {}
+
+    /// Record an accepted timepoint.
+    ///
+    /// Called once per accepted step with its full solution, never for
+    /// rejected adaptive steps or the intermediate TR-BDF2 stage.
+    fn accept_step(&self, _time: f64, _solution: &DVector<f64>) {}
+
+    /// Longest timestep the stamper's devices can represent, if limited.
+    ///
+    /// Transmission lines are only exact for steps up to their delay. The
+    /// adaptive solver caps its steps here; the fixed-step solvers reject a
+    /// `tstep` above it.
+    fn max_timestep(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// Reject a fixed `tstep` longer than the stamper's [`max_timestep`](TransientStamper::max_timestep).
+fn check_max_timestep(stamper: &dyn TransientStamper, tstep: f64) -> Result<()> {
+    match stamper.max_timestep() {
+        Some(max) if tstep > max * (1.0 + 1e-9) => Err(Error::SolverError(format!(
+            "timestep {tstep:e} s exceeds the circuit's maximum of {max:e} s \
+             (the shortest transmission line delay)"
+        ))),
+        _ => Ok(()),
+    }
 }
 
 /// Run a transient simulation.
@@ -44,10 +79,90 @@ pub fn solve_transient(
     inds: &mut [InductorState],
     params: &TransientParams,
     dc_solution: &DVector<f64>,
+) -> Result<TransientResult> {
+    solve_transient_with_progress(stamper, caps, inds, params, dc_solution, &mut |_| {
+        ControlFlow::Continue(())
+    })
+}
+
+/// Run a transient simulation, reporting each accepted timepoint to `on_step`.
+///
+/// Returning [`ControlFlow::Break`] from `on_step` stops the run cleanly: the
+/// result holds every point computed so far, ending with the one just
+/// reported. Use this to enforce wall-clock or step budgets, or to stream
+/// progress out of a long run.
+pub fn solve_transient_with_progress(
+    stamper: &dyn TransientStamper,
+    caps: &mut [CapacitorState],
+    inds: &mut [InductorState],
+    params: &TransientParams,
+    dc_solution: &DVector<f64>,
+    on_step: &mut dyn FnMut(&TimePoint) -> ControlFlow<()>,
+) -> Result<TransientResult> {
+    run_fixed_step(stamper, caps, inds, params, dc_solution, None, on_step)
+}
+
+/// Run a transient simulation, keeping only the `probes` entries of the
+/// solution at each timepoint.
+///
+/// Each stored [`TimePoint::solution`] has one entry per probe, in the order
+/// given, so `result.voltage_waveform(k)` is the waveform of `probes[k]`.
+/// The result's `num_nodes` counts the leading probes that are node
+/// voltages, so, as in a full result, entries below it are voltages and the
+/// rest branch currents; list node probes first to keep that true.
+/// The simulation itself is identical to [`solve_transient`]; only the
+/// stored history shrinks, which matters for large circuits where just a
+/// few outputs are of interest.
+pub fn solve_transient_probed(
+    stamper: &dyn TransientStamper,
+    caps: &mut [CapacitorState],
+    inds: &mut [InductorState],
+    params: &TransientParams,
+    dc_solution: &DVector<f64>,
+    probes: &[usize],
+) -> Result<TransientResult> {
+    let mna_size = stamper.num_nodes() + stamper.num_vsources();
+    if let Some(&bad) = probes.iter().find(|&&i| i >= mna_size) {
+        return Err(Error::IndexOutOfRange {
+            what: "probe",
+            index: bad,
+            len: mna_size,
+        });
+    }
+    let mut result = run_fixed_step(
+        stamper,
+        caps,
+        inds,
+        params,
+        dc_solution,
+        Some(probes),
+        &mut |_| ControlFlow::Continue(()),
+    )?;
+    result.num_nodes = probes
+        .iter()
+        .take_while(|&&i| i < stamper.num_nodes())
+        .count();
+    Ok(result)
+}
+
+/// Fixed-step transient loop shared by the public entry points.
+///
+/// With `probes`, each stored point holds only those solution entries.
+fn run_fixed_step(
+    stamper: &dyn TransientStamper,
+    caps: &mut [CapacitorState],
+    inds: &mut [InductorState],
+    params: &TransientParams,
+    dc_solution: &DVector<f64>,
+    probes: Option<&[usize]>,
+    on_step: &mut dyn FnMut(&TimePoint) -> ControlFlow<()>,
 ) -> Result<TransientResult> {
     let num_nodes = stamper.num_nodes();
     let num_vsources = stamper.num_vsources();
-    let h = params.tstep;
+    let keep = |solution: &DVector<f64>| match probes {
+        Some(probes) => DVector::from_iterator(probes.len(), probes.iter().map(|&i| solution[i])),
+        None => solution.clone(),
+    };
 
     // Initialize reactive element states from DC solution
     for cap in caps.iter_mut() {
@@ -91,6 +206,9 @@ pub fn solve_transient(
         }
     }
 
+    check_max_timestep(stamper, params.tstep)?;
+    stamper.start_transient(dc_solution);
+
     let mut result = TransientResult {
         points: Vec::new(),
         num_nodes,
@@ -99,65 +217,39 @@ pub fn solve_transient(
     // Store initial point
     result.points.push(TimePoint {
         time: 0.0,
-        solution: solution.clone(),
+        solution: keep(&solution),
     });
 
-    let num_steps = (params.tstop / h).ceil() as usize;
     let mna_size = num_nodes + num_vsources;
 
     // Cached sparse solver (created on first timestep if needed)
     let mut cached_solver: Option<CachedSparseLu> = None;
 
-    for step in 1..=num_steps {
-        let t = (step as f64) * h;
+    let mut t_prev = 0.0;
+    let mut h_prev = params.tstep;
+    for (step, t) in (1..).zip(params.step_times()) {
+        let h = t - t_prev;
+        let method = params.method_at_step(step, h, h_prev);
+        (t_prev, h_prev) = (t, h);
 
         // Build MNA system for this timestep
         let mut mna = MnaSystem::new(num_nodes, num_vsources);
 
-        // Stamp static elements (resistors, sources)
-        stamper.stamp_at_time(&mut mna, t);
+        // Stamp static elements (resistors, sources). TR-BDF2's first stage
+        // ends at γh into the step, so its sources are evaluated there.
+        let t_stamp = if method == IntegrationMethod::TrBdf2 {
+            t - (1.0 - TRBDF2_GAMMA) * h
+        } else {
+            t
+        };
+        stamper.stamp_at_time(&mut mna, t_stamp);
 
         // Stamp companion models for reactive elements and solve
-        match params.method {
-            IntegrationMethod::BackwardEuler => {
-                for cap in caps.iter() {
-                    cap.stamp_be(&mut mna, h);
-                }
-                for ind in inds.iter() {
-                    ind.stamp_be(&mut mna, h);
-                }
-
-                // Solve
-                solution = if mna_size >= SPARSE_THRESHOLD {
-                    let solver = match &cached_solver {
-                        Some(s) => s,
-                        None => {
-                            cached_solver = Some(CachedSparseLu::new(mna_size, &mna.triplets)?);
-                            cached_solver.as_ref().unwrap()
-                        }
-                    };
-                    solver.solve(&mna.triplets, mna.rhs())?
-                } else {
-                    solve_dense(&mna.to_dense_matrix(), mna.rhs())?
-                };
-
-                // Update state
-                for cap in caps.iter_mut() {
-                    let v = cap.voltage_from_solution(&solution);
-                    cap.update(v, h, params.method);
-                }
-                for ind in inds.iter_mut() {
-                    let v = ind.voltage_from_solution(&solution);
-                    ind.update(v, h, params.method);
-                }
-            }
-            IntegrationMethod::Trapezoidal => {
-                for cap in caps.iter() {
-                    cap.stamp_trap(&mut mna, h);
-                }
-                for ind in inds.iter() {
-                    ind.stamp_trap(&mut mna, h);
-                }
+        match method {
+            IntegrationMethod::BackwardEuler
+            | IntegrationMethod::Trapezoidal
+            | IntegrationMethod::Gear2 => {
+                stamp_companions(caps, inds, &mut mna, h, method);
 
                 // Solve
                 solution = if mna_size >= SPARSE_THRESHOLD {
@@ -173,26 +265,19 @@ pub fn solve_transient(
                     solve_dense(&mna.to_dense_matrix(), mna.rhs())?
                 };
 
-                // Update state
-                for cap in caps.iter_mut() {
-                    let v = cap.voltage_from_solution(&solution);
-                    cap.update(v, h, params.method);
-                }
-                for ind in inds.iter_mut() {
-                    let v = ind.voltage_from_solution(&solution);
-                    ind.update(v, h, params.method);
-                }
+                update_companions(caps, inds, &solution, h, method);
             }
             IntegrationMethod::TrBdf2 => {
                 // TR-BDF2: Two-stage method
                 // Stage 1: Trapezoidal step for γ*h
                 let h_gamma = TRBDF2_GAMMA * h;
-                for cap in caps.iter() {
-                    cap.stamp_trap(&mut mna, h_gamma);
-                }
-                for ind in inds.iter() {
-                    ind.stamp_trap(&mut mna, h_gamma);
-                }
+                stamp_companions(
+                    caps,
+                    inds,
+                    &mut mna,
+                    h_gamma,
+                    IntegrationMethod::Trapezoidal,
+                );
 
                 // Solve stage 1
                 let solution_gamma = if mna_size >= SPARSE_THRESHOLD {
@@ -240,21 +325,19 @@ pub fn solve_transient(
                 };
 
                 // Final state update
-                for cap in caps.iter_mut() {
-                    let v = cap.voltage_from_solution(&solution);
-                    cap.update(v, h, params.method);
-                }
-                for ind in inds.iter_mut() {
-                    let v = ind.voltage_from_solution(&solution);
-                    ind.update(v, h, params.method);
-                }
+                update_companions(caps, inds, &solution, h, method);
             }
         }
 
+        stamper.accept_step(t, &solution);
         result.points.push(TimePoint {
             time: t,
-            solution: solution.clone(),
+            solution: keep(&solution),
         });
+
+        if on_step(&result.points[step]).is_break() {
+            break;
+        }
     }
 
     Ok(result)
@@ -282,7 +365,6 @@ pub fn solve_transient_dispatched(
 ) -> Result<TransientResult> {
     let num_nodes = stamper.num_nodes();
     let num_vsources = stamper.num_vsources();
-    let h = params.tstep;
     let mna_size = num_nodes + num_vsources;
 
     // Decide solver strategy based on size
@@ -321,6 +403,9 @@ pub fn solve_transient_dispatched(
         }
     }
 
+    check_max_timestep(stamper, params.tstep)?;
+    stamper.start_transient(dc_solution);
+
     let mut result = TransientResult {
         points: Vec::new(),
         num_nodes,
@@ -331,81 +416,68 @@ pub fn solve_transient_dispatched(
         solution: solution.clone(),
     });
 
-    let num_steps = (params.tstop / h).ceil() as usize;
-
     // Cached sparse solver for direct LU
     let mut cached_solver: Option<CachedSparseLu> = None;
-
-    for step in 1..=num_steps {
-        let t = (step as f64) * h;
-
+    // GMRES operator, built on the first step and refilled on later ones
+    let mut gmres_operator: Option<SparseRealOperator> = None;
+
+    let mut t_prev = 0.0;
+    let mut h_prev = params.tstep;
+    for (step, t) in (1..).zip(params.step_times()) {
+        let h = t - t_prev;
+        let method = params.method_at_step(step, h, h_prev);
+        (t_prev, h_prev) = (t, h);
+
+        // As in run_fixed_step, TR-BDF2's first stage takes its sources at
+        // the stage end
+        let t_stamp = if method == IntegrationMethod::TrBdf2 {
+            t - (1.0 - TRBDF2_GAMMA) * h
+        } else {
+            t
+        };
         let mut mna = MnaSystem::new(num_nodes, num_vsources);
-        stamper.stamp_at_time(&mut mna, t);
-
-        // Helper closure for solving
-        let solve_mna =
-            |mna: &MnaSystem, cached: &mut Option<CachedSparseLu>| -> Result<DVector<f64>> {
-                if use_gmres {
-                    solve_transient_gmres(mna, &config.gmres_config)
-                } else if mna_size >= SPARSE_THRESHOLD {
-                    let solver = match cached.as_ref() {
-                        Some(s) => s,
-                        None => {
-                            *cached = Some(CachedSparseLu::new(mna_size, &mna.triplets)?);
-                            cached.as_ref().unwrap()
-                        }
-                    };
-                    solver.solve(&mna.triplets, mna.rhs())
-                } else {
-                    solve_dense(&mna.to_dense_matrix(), mna.rhs())
-                }
-            };
-
-        match params.method {
-            IntegrationMethod::BackwardEuler => {
-                for cap in caps.iter() {
-                    cap.stamp_be(&mut mna, h);
-                }
-                for ind in inds.iter() {
-                    ind.stamp_be(&mut mna, h);
-                }
-                solution = solve_mna(&mna, &mut cached_solver)?;
-                for cap in caps.iter_mut() {
-                    let v = cap.voltage_from_solution(&solution);
-                    cap.update(v, h, params.method);
-                }
-                for ind in inds.iter_mut() {
-                    let v = ind.voltage_from_solution(&solution);
-                    ind.update(v, h, params.method);
-                }
+        stamper.stamp_at_time(&mut mna, t_stamp);
+
+        // Helper closure for solving; GMRES warm-starts from `guess`
+        let mut solve_mna = |mna: &MnaSystem,
+                             cached: &mut Option<CachedSparseLu>,
+                             guess: &DVector<f64>|
+         -> Result<DVector<f64>> {
+            if use_gmres {
+                solve_transient_gmres(mna, config, &mut gmres_operator, guess)
+            } else if mna_size >= SPARSE_THRESHOLD {
+                let solver = match cached.as_ref() {
+                    Some(s) => s,
+                    None => {
+                        *cached = Some(CachedSparseLu::new(mna_size, &mna.triplets)?);
+                        cached.as_ref().unwrap()
+                    }
+                };
+                solver.solve(&mna.triplets, mna.rhs())
+            } else {
+                solve_dense(&mna.to_dense_matrix(), mna.rhs())
             }
-            IntegrationMethod::Trapezoidal => {
-                for cap in caps.iter() {
-                    cap.stamp_trap(&mut mna, h);
-                }
-                for ind in inds.iter() {
-                    ind.stamp_trap(&mut mna, h);
-                }
-                solution = solve_mna(&mna, &mut cached_solver)?;
-                for cap in caps.iter_mut() {
-                    let v = cap.voltage_from_solution(&solution);
-                    cap.update(v, h, params.method);
-                }
-                for ind in inds.iter_mut() {
-                    let v = ind.voltage_from_solution(&solution);
-                    ind.update(v, h, params.method);
-                }
+        };
+
+        match method {
+            IntegrationMethod::BackwardEuler
+            | IntegrationMethod::Trapezoidal
+            | IntegrationMethod::Gear2 => {
+                stamp_companions(caps, inds, &mut mna, h, method);
+                solution = solve_mna(&mna, &mut cached_solver, &solution)?;
+                update_companions(caps, inds, &solution, h, method);
             }
             IntegrationMethod::TrBdf2 => {
                 // Stage 1: Trapezoidal for γ*h
                 let h_gamma = TRBDF2_GAMMA * h;
-                for cap in caps.iter() {
-                    cap.stamp_trap(&mut mna, h_gamma);
-                }
-                for ind in inds.iter() {
-                    ind.stamp_trap(&mut mna, h_gamma);
-                }
-                let solution_gamma = solve_mna(&mna, &mut cached_solver)?;
+                stamp_companions(
+                    caps,
+                    inds,
+                    &mut mna,
+                    h_gamma,
+                    IntegrationMethod::Trapezoidal,
+                );
+                let solution_gamma = solve_mna(&mna, &mut cached_solver, &solution)?;
 
                 // Update to intermediate state
                 for cap in caps.iter_mut() {
@@ -427,20 +499,14 @@ pub fn solve_transient_dispatched(
                 for ind in inds.iter() {
                     ind.stamp_trbdf2_bdf2(&mut mna2, h);
                 }
-                solution = solve_mna(&mna2, &mut cached_solver)?;
+                solution = solve_mna(&mna2, &mut cached_solver, &solution_gamma)?;
 
                 // Final state update
-                for cap in caps.iter_mut() {
-                    let v = cap.voltage_from_solution(&solution);
-                    cap.update(v, h, params.method);
-                }
-                for ind in inds.iter_mut() {
-                    let v = ind.voltage_from_solution(&solution);
-                    ind.update(v, h, params.method);
-                }
+                update_companions(caps, inds, &solution, h, method);
             }
         }
 
+        stamper.accept_step(t, &solution);
         result.points.push(TimePoint {
             time: t,
             solution: solution.clone(),
@@ -451,22 +517,61 @@ pub fn solve_transient_dispatched(
 }
 
 /// Solve a transient timestep using GMRES.
-fn solve_transient_gmres(mna: &MnaSystem, config: &GmresConfig) -> Result<DVector<f64>> {
+///
+/// The preconditioner comes from [`DispatchConfig::select_preconditioner`]
+/// and is rebuilt from the step's triplets, since the companion conductances
+/// change with the timestep and integration method. If ILU(0) cannot be
+/// formed (a zero pivot, or a voltage-source row with no diagonal), the step
+/// falls back to Jacobi.
+///
+/// The iteration starts from `guess`, normally the previous step's solution,
+/// which is already close when the waveform changes slowly.
+///
+/// `operator` caches the sparse matrix between steps. The stamp pattern is
+/// the same every step, so only the values are refilled; it is rebuilt if
+/// the pattern ever changes.
+fn solve_transient_gmres(
+    mna: &MnaSystem,
+    config: &DispatchConfig,
+    operator: &mut Option<SparseRealOperator>,
+    guess: &DVector<f64>,
+) -> Result<DVector<f64>> {
     let size = mna.size();
 
-    let operator = SparseRealOperator::from_triplets(size, &mna.triplets).ok_or_else(|| {
-        crate::error::Error::SolverError("Failed to build sparse operator".into())
-    })?;
+    let reused = operator
+        .as_mut()
+        .is_some_and(|op| op.dim() == size && op.update_values(&mna.triplets));
+    if !reused {
+        *operator = Some(
+            SparseRealOperator::from_triplets(size, &mna.triplets).ok_or_else(|| {
+                crate::error::Error::SolverError("Failed to build sparse operator".into())
+            })?,
+        );
+    }
+    let operator = operator.as_ref().unwrap();
 
-    let preconditioner = JacobiPreconditioner::from_triplets(size, &mna.triplets);
+    let preconditioner: Box<dyn RealPreconditioner> = match config.select_preconditioner(size) {
+        PreconditionerType::None => Box::new(IdentityPreconditioner::new(size)),
+        PreconditionerType::Jacobi => {
+            Box::new(JacobiPreconditioner::from_triplets(size, &mna.triplets))
+        }
+        PreconditionerType::Ilu0 => match Ilu0Preconditioner::from_triplets(size, &mna.triplets) {
+            Ok(ilu) => Box::new(ilu),
+            Err(e) => {
+                log::warn!("Transient ILU(0) preconditioner failed ({e}), using Jacobi");
+                Box::new(JacobiPreconditioner::from_triplets(size, &mna.triplets))
+            }
+        },
+    };
     let rhs: Vec<f64> = mna.rhs().iter().copied().collect();
 
-    let gmres_result = crate::gmres::solve_gmres_real_preconditioned(
-        &operator as &dyn RealOperator,
-        &preconditioner as &dyn RealPreconditioner,
+    let gmres_result = crate::gmres::solve_gmres_real_preconditioned_with_guess(
+        operator as &dyn RealOperator,
+        preconditioner.as_ref(),
         &rhs,
-        config,
-    );
+        Some(guess.as_slice()),
+        &config.gmres_config,
+    )?;
 
     if !gmres_result.converged {
         log::warn!(
@@ -485,6 +590,14 @@ fn solve_transient_gmres(mna: &MnaSystem, config: &GmresConfig) -> Result<DVecto
 /// the timestep. Larger steps are taken when the solution is smooth,
 /// smaller steps when it changes rapidly.
 ///
+/// With [`IntegrationMethod::TrBdf2`] each step is a TR-BDF2 step and the
+/// LTE comes from its embedded estimate over the TR and BDF2 stages. Being
+/// L-stable, it takes much larger steps than Trapezoidal on stiff circuits,
+/// where Trapezoidal's undamped fast modes keep its error estimate high.
+/// Backward Euler integrates with Trapezoidal. Gear2 is rejected: its
+/// companion models assume evenly spaced history points, which an adaptive
+/// run almost never has.
+///
 /// # Arguments
 /// * `stamper` - Stamps resistive elements and sources
 /// * `caps` - Capacitor companion model states
@@ -501,6 +614,11 @@ pub fn solve_transient_adaptive(
     let num_nodes = stamper.num_nodes();
     let num_vsources = stamper.num_vsources();
     let mna_size = num_nodes + num_vsources;
+    if params.method == IntegrationMethod::Gear2 {
+        return Err(Error::SolverError(
+            "adaptive transient does not support Gear2; use TrBdf2 or Trapezoidal".into(),
+        ));
+    }
 
     let mut t = 0.0;
     let mut h = params.h_init;
@@ -538,6 +656,8 @@ pub fn solve_transient_adaptive(
         }
     }
 
+    stamper.start_transient(dc_solution);
+
     let mut result = AdaptiveTransientResult {
         points: Vec::new(),
         num_nodes,
@@ -545,6 +665,7 @@ pub fn solve_transient_adaptive(
         rejected_steps: 0,
         min_step_used: f64::INFINITY,
         max_step_used: 0.0,
+        lte_history: Vec::new(),
     };
 
     // Store initial point
@@ -552,66 +673,129 @@ pub fn solve_transient_adaptive(
         time: 0.0,
         solution: solution.clone(),
     });
+    if params.record_lte {
+        result.lte_history.push(0.0);
+    }
 
     // Cached sparse solver
     let mut cached_solver: Option<CachedSparseLu> = None;
 
+    // TR-BDF2 carries its own embedded error estimate; Backward Euler
+    // runs as Trapezoidal with the Trapezoidal/Backward Euler comparison.
+    // The estimates scale as h³ and h² respectively.
+    let method = match params.method {
+        IntegrationMethod::TrBdf2 => IntegrationMethod::TrBdf2,
+        _ => IntegrationMethod::Trapezoidal,
+    };
+    let exponent = if method == IntegrationMethod::TrBdf2 {
+        1.0 / 3.0
+    } else {
+        0.5
+    };
+
     // Save states for potential rollback
     let mut saved_cap_states: Vec<(f64, f64)> = caps.iter().map(|c| (c.v_prev, c.i_prev)).collect();
     let mut saved_ind_states: Vec<(f64, f64)> = inds.iter().map(|i| (i.i_prev, i.v_prev)).collect();
 
+    // Steps may not outrun the stamper's devices, e.g. a transmission line
+    check_max_timestep(stamper, params.h_min)?;
+    let h_max = stamper
+        .max_timestep()
+        .map_or(params.h_max, |max| params.h_max.min(max));
+
     while t < params.tstop {
         // Clamp timestep
-        h = h.clamp(params.h_min, params.h_max);
+        h = h.clamp(params.h_min, h_max);
+        h = params.limit_step(t, h);
 
         // Don't overshoot tstop
         if t + h > params.tstop {
             h = params.tstop - t;
         }
 
-        // Build MNA system for this timestep
-        let mut mna = MnaSystem::new(num_nodes, num_vsources);
-        stamper.stamp_at_time(&mut mna, t);
+        let (new_solution, max_lte, max_ref) = if method == IntegrationMethod::TrBdf2 {
+            // TR stage to t + γh, then BDF2 stage to t + h
+            let h_gamma = TRBDF2_GAMMA * h;
+            let mut mna = MnaSystem::new(num_nodes, num_vsources);
+            stamper.stamp_at_time(&mut mna, t + h_gamma);
+            for cap in caps.iter() {
+                cap.stamp_trap(&mut mna, h_gamma);
+            }
+            for ind in inds.iter() {
+                ind.stamp_trap(&mut mna, h_gamma);
+            }
+            let solution_gamma = solve_cached(&mut cached_solver, &mna)?;
 
-        // Stamp companion models (using Trapezoidal for better accuracy)
-        for cap in caps.iter() {
-            cap.stamp_trap(&mut mna, h);
-        }
-        for ind in inds.iter() {
-            ind.stamp_trap(&mut mna, h);
-        }
+            for cap in caps.iter_mut() {
+                let v = cap.voltage_from_solution(&solution_gamma);
+                cap.update_trbdf2_intermediate(v, h);
+            }
+            for ind in inds.iter_mut() {
+                let v = ind.voltage_from_solution(&solution_gamma);
+                ind.update_trbdf2_intermediate(v, h);
+                ind.v_prev = v;
+            }
 
-        // Solve
-        let new_solution = if mna_size >= SPARSE_THRESHOLD {
-            let solver = match &cached_solver {
-                Some(s) => s,
-                None => {
-                    cached_solver = Some(CachedSparseLu::new(mna_size, &mna.triplets)?);
-                    cached_solver.as_ref().unwrap()
-                }
-            };
-            solver.solve(&mna.triplets, mna.rhs())?
+            let mut mna = MnaSystem::new(num_nodes, num_vsources);
+            stamper.stamp_at_time(&mut mna, t + h);
+            for cap in caps.iter() {
+                cap.stamp_trbdf2_bdf2(&mut mna, h);
+            }
+            for ind in inds.iter() {
+                ind.stamp_trbdf2_bdf2(&mut mna, h);
+            }
+            let new_solution = solve_cached(&mut cached_solver, &mna)?;
+
+            // Embedded estimate from the three stage points
+            let mut max_lte = 0.0_f64;
+            let mut max_ref = 0.0_f64;
+            for cap in caps.iter() {
+                let v_new = cap.voltage_from_solution(&new_solution);
+                max_lte = max_lte.max(cap.estimate_lte_trbdf2(v_new, h));
+                max_ref = max_ref.max(v_new.abs());
+            }
+            for ind in inds.iter() {
+                let v_new = ind.voltage_from_solution(&new_solution);
+                max_lte = max_lte.max(ind.estimate_lte_trbdf2(v_new, h));
+                max_ref = max_ref.max(ind.i_prev.abs());
+            }
+            (new_solution, max_lte, max_ref)
         } else {
-            solve_dense(&mna.to_dense_matrix(), mna.rhs())?
-        };
+            // Build MNA system for the step ending at t + h. Trapezoidal
+            // solves for the state at the end of the step, so the sources
+            // must be evaluated there too, as in the fixed-step and TR-BDF2
+            // paths.
+            let mut mna = MnaSystem::new(num_nodes, num_vsources);
+            stamper.stamp_at_time(&mut mna, t + h);
+
+            // Stamp companion models (using Trapezoidal for better accuracy)
+            for cap in caps.iter() {
+                cap.stamp_trap(&mut mna, h);
+            }
+            for ind in inds.iter() {
+                ind.stamp_trap(&mut mna, h);
+            }
+            let new_solution = solve_cached(&mut cached_solver, &mna)?;
 
-        // Estimate LTE for all reactive elements
-        let mut max_lte = 0.0_f64;
-        let mut max_ref = 0.0_f64; // Reference value for relative error
+            // Estimate LTE for all reactive elements
+            let mut max_lte = 0.0_f64;
+            let mut max_ref = 0.0_f64; // Reference value for relative error
 
-        for cap in caps.iter() {
-            let v_new = cap.voltage_from_solution(&new_solution);
-            let lte = cap.estimate_lte(v_new, h);
-            max_lte = max_lte.max(lte);
-            max_ref = max_ref.max(v_new.abs());
-        }
+            for cap in caps.iter() {
+                let v_new = cap.voltage_from_solution(&new_solution);
+                let lte = cap.estimate_lte(v_new, h);
+                max_lte = max_lte.max(lte);
+                max_ref = max_ref.max(v_new.abs());
+            }
 
-        for ind in inds.iter() {
-            let v_new = ind.voltage_from_solution(&new_solution);
-            let lte = ind.estimate_lte(v_new, h);
-            max_lte = max_lte.max(lte);
-            max_ref = max_ref.max(ind.i_prev.abs());
-        }
+            for ind in inds.iter() {
+                let v_new = ind.voltage_from_solution(&new_solution);
+                let lte = ind.estimate_lte(v_new, h);
+                max_lte = max_lte.max(lte);
+                max_ref = max_ref.max(ind.i_prev.abs());
+            }
+            (new_solution, max_lte, max_ref)
+        };
 
         // Compute tolerance: max(abstol, reltol * max_ref)
         let tol = params.abstol.max(params.reltol * max_ref);
@@ -633,22 +817,16 @@ pub fn solve_transient_adaptive(
             }
 
             // Reduce timestep (safety factor of 0.8)
-            let factor = (tol / max_lte).sqrt().min(0.5);
+            let factor = (tol / max_lte).powf(exponent).min(0.5);
             h *= factor.max(0.1); // Don't reduce by more than 10x
         } else {
             // Accept step
             t += h;
             solution = new_solution;
+            stamper.accept_step(t, &solution);
 
             // Update reactive element states
-            for cap in caps.iter_mut() {
-                let v_new = cap.voltage_from_solution(&solution);
-                cap.update(v_new, h, IntegrationMethod::Trapezoidal);
-            }
-            for ind in inds.iter_mut() {
-                let v_new = ind.voltage_from_solution(&solution);
-                ind.update(v_new, h, IntegrationMethod::Trapezoidal);
-            }
+            update_companions(caps, inds, &solution, h, method);
 
             // Save states for potential rollback
             saved_cap_states = caps.iter().map(|c| (c.v_prev, c.i_prev)).collect();
@@ -663,14 +841,87 @@ pub fn solve_transient_adaptive(
                 time: t,
                 solution: solution.clone(),
             });
+            if params.record_lte {
+                result.lte_history.push(max_lte);
+            }
 
             // Increase timestep for next step if LTE is small
-            if max_lte < tol * 0.5 && h < params.h_max {
-                let factor = (tol / max_lte.max(1e-20)).sqrt().min(2.0);
+            if max_lte < tol * 0.5 && h < h_max {
+                let factor = (tol / max_lte.max(1e-20)).powf(exponent).min(2.0);
                 h *= factor.min(1.5); // Don't increase by more than 1.5x
             }
+
+            // Past a breakpoint the waveform may turn a sharp corner; creep
+            // up on it again from the smallest step.
+            if let Some(bp) = params.breakpoint_at(t) {
+                t = bp;
+                result.points.last_mut().unwrap().time = bp;
+                h = params.h_min;
+            }
         }
     }
 
     Ok(result)
 }
+
+/// Stamp every reactive element's companion model for a single-stage
+/// method. TR-BDF2 stamps its two stages separately.
+fn stamp_companions(
+    caps: &[CapacitorState],
+    inds: &[InductorState],
+    mna: &mut MnaSystem,
+    h: f64,
+    method: IntegrationMethod,
+) {
+    for cap in caps {
+        match method {
+            IntegrationMethod::BackwardEuler => cap.stamp_be(mna, h),
+            IntegrationMethod::Trapezoidal => cap.stamp_trap(mna, h),
+            IntegrationMethod::Gear2 => cap.stamp_gear2(mna, h),
+            IntegrationMethod::TrBdf2 => unreachable!("TR-BDF2 stamps its stages separately"),
+        }
+    }
+    for ind in inds {
+        match method {
+            IntegrationMethod::BackwardEuler => ind.stamp_be(mna, h),
+            IntegrationMethod::Trapezoidal => ind.stamp_trap(mna, h),
+            IntegrationMethod::Gear2 => ind.stamp_gear2(mna, h),
+            IntegrationMethod::TrBdf2 => unreachable!("TR-BDF2 stamps its stages separately"),
+        }
+    }
+}
+
+/// Advance every reactive element's state to the end of a solved step.
+fn update_companions(
+    caps: &mut [CapacitorState],
+    inds: &mut [InductorState],
+    solution: &DVector<f64>,
+    h: f64,
+    method: IntegrationMethod,
+) {
+    for cap in caps {
+        let v = cap.voltage_from_solution(solution);
+        cap.update(v, h, method);
+    }
+    for ind in inds {
+        let v = ind.voltage_from_solution(solution);
+        ind.update(v, h, method);
+    }
+}
+
+/// Solve one timestep's MNA system, reusing the sparse symbolic
+/// factorization across steps.
+fn solve_cached(
+    cached_solver: &mut Option<CachedSparseLu>,
+    mna: &MnaSystem,
+) -> Result<DVector<f64>> {
+    if mna.size() >= SPARSE_THRESHOLD {
+        let solver = match cached_solver {
+            Some(s) => s,
+            None => cached_solver.insert(CachedSparseLu::new(mna.size(), &mna.triplets)?),
+        };
+        solver.solve(&mna.triplets, mna.rhs())
+    } else {
+        solve_dense(&mna.to_dense_matrix(), mna.rhs())
+    }
+}
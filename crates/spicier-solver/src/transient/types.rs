@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 
 use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
 
 /// TR-BDF2 gamma parameter: γ = 2 - √2 ≈ 0.5858 for the fraction of step using Trapezoidal.
 ///
@@ -10,6 +11,10 @@ use nalgebra::DVector;
 /// This value maximizes order of accuracy while maintaining L-stability.
 pub const TRBDF2_GAMMA: f64 = 2.0 - std::f64::consts::SQRT_2;
 
+/// Times closer than this (s) are the same timepoint when breakpoints are
+/// merged into the step schedule.
+pub const BREAKPOINT_TOL: f64 = 1e-15;
+
 /// Initial conditions for transient analysis.
 ///
 /// Stores node name -> voltage mappings from .IC commands.
@@ -48,7 +53,7 @@ impl InitialConditions {
 }
 
 /// Integration method for transient analysis.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IntegrationMethod {
     /// Backward Euler (first order, A-stable).
     BackwardEuler,
@@ -60,10 +65,16 @@ pub enum IntegrationMethod {
     /// then BDF2 for the remaining (1-γ)*h. Provides L-stability
     /// without the numerical ringing issues of pure Trapezoidal.
     TrBdf2,
+    /// Gear2 / BDF2 (second order, L-stable).
+    ///
+    /// Uses the two previous timepoints directly instead of an intermediate
+    /// stage, so each step costs one solve. The first step has only one
+    /// history point and is taken with Backward Euler.
+    Gear2,
 }
 
 /// Transient analysis parameters.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransientParams {
     /// Stop time (s).
     pub tstop: f64,
@@ -71,6 +82,71 @@ pub struct TransientParams {
     pub tstep: f64,
     /// Integration method.
     pub method: IntegrationMethod,
+    /// Number of initial steps taken with Backward Euler before switching to
+    /// `method`.
+    ///
+    /// BE damps the startup transient that Trapezoidal would otherwise carry
+    /// as undamped ringing, and fills the history needed by multi-step methods.
+    /// SPICE does the same at the start of a transient; 0 disables it.
+    #[serde(default)]
+    pub be_startup_steps: usize,
+    /// Times (s) the solver must step to exactly, such as source edges.
+    ///
+    /// Merged with the regular `tstep` grid, so a PULSE edge between grid
+    /// points still gets a timepoint on its corner.
+    #[serde(default)]
+    pub breakpoints: Vec<f64>,
+}
+
+impl TransientParams {
+    /// Method used for fixed step number `step` (1-based) of size `h`,
+    /// following a step of size `h_prev`.
+    ///
+    /// Backward Euler during the startup steps, and for Gear2 steps without
+    /// two evenly spaced history points: the first step, and a step whose
+    /// size differs from the last one around a breakpoint.
+    pub(crate) fn method_at_step(&self, step: usize, h: f64, h_prev: f64) -> IntegrationMethod {
+        let uneven = step == 1 || (h - h_prev).abs() > 1e-9 * h_prev;
+        if step <= self.be_startup_steps || (uneven && self.method == IntegrationMethod::Gear2) {
+            IntegrationMethod::BackwardEuler
+        } else {
+            self.method
+        }
+    }
+
+    /// End times of every fixed step: the `tstep` grid merged with the
+    /// breakpoints inside the run, in order and deduplicated within
+    /// [`BREAKPOINT_TOL`].
+    ///
+    /// Lazy, since budgeted runs may ask for far more steps than they take.
+    pub(crate) fn step_times(&self) -> impl Iterator<Item = f64> + use<> {
+        let tstep = self.tstep;
+        let num_steps = (self.tstop / tstep).ceil() as usize;
+        let mut grid = (1..=num_steps).map(move |k| k as f64 * tstep).peekable();
+        let mut breakpoints: Vec<f64> = self
+            .breakpoints
+            .iter()
+            .copied()
+            .filter(|&bp| bp > 0.0 && bp < self.tstop)
+            .collect();
+        breakpoints.sort_by(f64::total_cmp);
+        let mut breakpoints = breakpoints.into_iter().peekable();
+
+        let mut last = 0.0;
+        std::iter::from_fn(move || {
+            loop {
+                let next = match (grid.peek(), breakpoints.peek()) {
+                    (Some(&g), Some(&bp)) if bp < g => breakpoints.next(),
+                    (Some(_), _) => grid.next(),
+                    (None, _) => breakpoints.next(),
+                }?;
+                if next - last > BREAKPOINT_TOL {
+                    last = next;
+                    return Some(next);
+                }
+            }
+        })
+    }
 }
 
 /// Parameters for adaptive timestep control.
@@ -88,8 +164,45 @@ pub struct AdaptiveTransientParams {
     pub reltol: f64,
     /// Absolute tolerance for LTE.
     pub abstol: f64,
-    /// Integration method.
+    /// Integration method: [`IntegrationMethod::TrBdf2`] uses its embedded
+    /// error estimate, anything else runs as Trapezoidal.
     pub method: IntegrationMethod,
+    /// Record the estimated LTE of every accepted step in
+    /// [`AdaptiveTransientResult::lte_history`](super::AdaptiveTransientResult::lte_history).
+    pub record_lte: bool,
+    /// Per-window timestep caps, applied on top of `h_max`.
+    ///
+    /// Like SPICE's max-step windows: a tight cap around a fast edge gives
+    /// dense points there without forcing a small `h_max` on the whole run.
+    /// Steps are shortened to land on each window's start.
+    pub max_step_windows: Vec<MaxStepWindow>,
+    /// Times (s) the stepper must land on exactly, such as source edges.
+    ///
+    /// A step that would cross a breakpoint is shortened to end on it, and
+    /// the next step restarts from `h_min` to resolve the edge.
+    pub breakpoints: Vec<f64>,
+}
+
+/// A time window in which the adaptive stepper may not exceed `max_step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxStepWindow {
+    /// Window start time (s).
+    pub start: f64,
+    /// Window end time (s).
+    pub end: f64,
+    /// Maximum timestep inside the window (s).
+    pub max_step: f64,
+}
+
+impl MaxStepWindow {
+    /// Create a window capping the timestep at `max_step` over `[start, end]`.
+    pub fn new(start: f64, end: f64, max_step: f64) -> Self {
+        Self {
+            start,
+            end,
+            max_step,
+        }
+    }
 }
 
 impl Default for AdaptiveTransientParams {
@@ -102,6 +215,9 @@ impl Default for AdaptiveTransientParams {
             reltol: 1e-3,
             abstol: 1e-6,
             method: IntegrationMethod::Trapezoidal,
+            record_lte: false,
+            max_step_windows: Vec::new(),
+            breakpoints: Vec::new(),
         }
     }
 }
@@ -115,4 +231,35 @@ impl AdaptiveTransientParams {
             ..Default::default()
         }
     }
+
+    /// Limit a proposed step `h` from time `t` by the max-step windows and
+    /// breakpoints.
+    ///
+    /// A step overlapping a window is capped at its `max_step`; a step that
+    /// would jump into a window is shortened to end at the window start, and
+    /// one crossing a breakpoint to end on the breakpoint.
+    pub(crate) fn limit_step(&self, t: f64, mut h: f64) -> f64 {
+        for w in &self.max_step_windows {
+            if t < w.start && t + h > w.start {
+                h = (w.start - t).max(self.h_min);
+            }
+            if t < w.end && t + h > w.start {
+                h = h.min(w.max_step);
+            }
+        }
+        for &bp in &self.breakpoints {
+            if t < bp - BREAKPOINT_TOL && t + h > bp {
+                h = bp - t;
+            }
+        }
+        h
+    }
+
+    /// The breakpoint `t` has landed on, if any.
+    pub(crate) fn breakpoint_at(&self, t: f64) -> Option<f64> {
+        self.breakpoints
+            .iter()
+            .copied()
+            .find(|&bp| (t - bp).abs() <= BREAKPOINT_TOL)
+    }
 }
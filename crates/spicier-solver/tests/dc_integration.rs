@@ -1,10 +1,13 @@
 //! Integration tests for DC analysis.
 
+use nalgebra::DVector;
+use spicier_core::mna::MnaSystem;
 use spicier_core::{Netlist, NodeId};
+use spicier_devices::Mosfet;
 use spicier_devices::passive::Resistor;
 use spicier_devices::sources::{CurrentSource, VoltageSource};
 use spicier_devices::tline::TransmissionLine;
-use spicier_solver::solve_dc;
+use spicier_solver::{ConvergenceCriteria, NonlinearStamper, solve_dc, solve_newton_raphson};
 
 /// Test a simple voltage divider circuit:
 ///
@@ -363,3 +366,56 @@ fn test_transmission_line_current_vars() {
     // Should now have 1 (V1) + 5 (T1) = 6 current variables
     assert_eq!(netlist.num_current_vars(), 6);
 }
+
+/// Newton stamper that linearizes every device in a netlist.
+struct NetlistStamper<'a>(&'a Netlist);
+
+impl NonlinearStamper for NetlistStamper<'_> {
+    fn stamp_at(&self, mna: &mut MnaSystem, solution: &DVector<f64>) {
+        self.0.stamp_nonlinear_into(mna, solution);
+    }
+}
+
+/// Two stacked NMOS transistors with both gates grounded:
+///
+/// ```text
+///   VDD = 5V ── M1 (off) ── mid ── M2 (off) ── GND
+/// ```
+///
+/// `mid` connects only to the two cut-off drains and sources, so it is held
+/// by nothing but their Gmin shunts; the matrix must stay nonsingular and
+/// `mid` settle halfway between the rails.
+#[test]
+fn test_cutoff_mosfet_stack_gmin() {
+    let mut netlist = Netlist::with_title("Off NMOS stack");
+
+    let vdd = NodeId::new(1);
+    let mid = NodeId::new(2);
+    let gnd = NodeId::GROUND;
+    netlist.register_node(vdd);
+    netlist.register_node(mid);
+
+    let v1 = VoltageSource::new("V1", vdd, gnd, 5.0, netlist.next_current_index());
+    netlist.add_device(v1);
+    netlist.add_device(Mosfet::nmos("M1", vdd, gnd, mid));
+    netlist.add_device(Mosfet::nmos("M2", mid, gnd, gnd));
+
+    let result = solve_newton_raphson(
+        netlist.num_nodes(),
+        netlist.num_current_vars(),
+        &NetlistStamper(&netlist),
+        &ConvergenceCriteria::default(),
+        None,
+    )
+    .expect("off-state solve should succeed");
+
+    assert!(result.converged);
+    let v_mid = result.solution[1];
+    assert!(
+        (v_mid - 2.5).abs() < 1e-6,
+        "V(mid) = {v_mid} (expected 2.5)"
+    );
+    // Only picoamps leak through the stack.
+    let i_v1 = result.solution[2];
+    assert!(i_v1.abs() < 1e-10, "I(V1) = {i_v1}");
+}
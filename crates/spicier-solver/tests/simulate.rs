@@ -0,0 +1,224 @@
+//! Integration tests for end-to-end netlist simulation.
+
+use nalgebra::DVector;
+use spicier_solver::{
+    AdaptiveTransientParams, AnalysisResult, NetlistTransientStamper, simulate,
+    solve_transient_adaptive,
+};
+
+/// RC charging from a 10V source through a divider:
+///
+/// ```text
+/// V1 ─ R1 (1k) ─ out ─┬─ R2 (1k) ─ GND
+///                     └─ C1 (1µ) ─ GND
+/// ```
+///
+/// The operating point is V(out) = 5V. With UIC and `.IC V(in)=10` the
+/// capacitor starts discharged and charges toward 5V with
+/// τ = (R1 ∥ R2)·C = 0.5ms.
+const RC_DIVIDER: &str = "RC divider
+V1 in 0 10
+R1 in out 1k
+R2 out 0 1k
+C1 out 0 1u
+.op
+.ic V(in)=10
+.tran 10u 3m uic
+.end
+";
+
+#[test]
+fn test_simulate_op_and_tran() {
+    let results = simulate(RC_DIVIDER).unwrap();
+
+    assert_eq!(results.analyses.len(), 2);
+    assert!(matches!(results.analyses[0], AnalysisResult::Op(_)));
+    assert!(matches!(results.analyses[1], AnalysisResult::Tran(_)));
+
+    assert!((results.op_voltage("in").unwrap() - 10.0).abs() < 1e-9);
+    assert!((results.op_voltage("OUT").unwrap() - 5.0).abs() < 1e-9);
+    assert_eq!(results.op_voltage("0"), Some(0.0));
+    assert_eq!(results.op_voltage("missing"), None);
+
+    let tau = 0.5e-3;
+    let waveform = results.tran_voltage("out").unwrap();
+    assert!(waveform[0].1.abs() < 1e-9);
+    // The trapezoidal start assumes no capacitor current at t=0, which lags
+    // the waveform by about half a step; allow 1% of the final value.
+    for &(t, v) in &waveform {
+        let expected = 5.0 * (1.0 - (-t / tau).exp());
+        assert!(
+            (v - expected).abs() < 0.05,
+            "V(out) at t={t}: {v} vs {expected}"
+        );
+    }
+    let (t_end, v_end) = *waveform.last().unwrap();
+    assert!((t_end - 3e-3).abs() < 10e-6);
+    assert!((v_end - 5.0).abs() < 0.02);
+
+    assert!(results.dc().is_none());
+    assert!(results.ac().is_none());
+}
+
+#[test]
+fn test_simulate_tran_steps_onto_pulse_edges() {
+    // The rise starts 30µs past a 100µs grid point and ends before the next
+    let results = simulate(
+        "Pulse edges
+V1 in 0 PULSE(0 5 1.03m 10u 10u 0.5m)
+R1 in out 1k
+C1 out 0 100n
+.tran 100u 3m
+.end
+",
+    )
+    .unwrap();
+
+    let waveform = results.tran_voltage("in").unwrap();
+    for (edge, v) in [
+        (1.03e-3, 0.0),
+        (1.04e-3, 5.0),
+        (1.54e-3, 5.0),
+        (1.55e-3, 0.0),
+    ] {
+        let &(_, v_edge) = waveform
+            .iter()
+            .find(|(t, _)| (t - edge).abs() < 1e-15)
+            .unwrap_or_else(|| panic!("no timepoint at {edge:e}"));
+        assert!((v_edge - v).abs() < 1e-9, "V(in) at {edge:e}: {v_edge}");
+    }
+}
+
+#[test]
+fn test_simulate_dc_and_ac_sweeps() {
+    let results = simulate(
+        "Sweeps
+.PARAM rload=1k
+V1 in 0 DC 1 AC 1
+R1 in out 1k
+R2 out 0 {rload}
+C1 out 0 1u
+.dc V1 0 4 1
+.dc rload 1k 3k 1k
+.ac dec 10 1 1meg
+.op
+.end
+",
+    )
+    .unwrap();
+
+    let vout = results.dc_voltage("out").unwrap();
+    assert_eq!(vout.len(), 5);
+    for (v1, vo) in vout {
+        assert!((vo - v1 / 2.0).abs() < 1e-9);
+    }
+
+    let AnalysisResult::Dc(param_sweep) = &results.analyses[1] else {
+        panic!("expected a .PARAM sweep");
+    };
+    let out = results.node("out").unwrap();
+    for (rload, vo) in param_sweep.voltage_waveform(out) {
+        assert!((vo - rload / (1e3 + rload)).abs() < 1e-9);
+    }
+
+    // AC at 1Hz is the divider ratio; the sweep restored rload to 1k.
+    let ac = results.ac_voltage("out").unwrap();
+    assert!((ac[0].1.norm() - 0.5).abs() < 1e-3);
+    assert!((results.op_voltage("out").unwrap() - 0.5).abs() < 1e-9);
+}
+
+type Waveform = Vec<(f64, f64)>;
+
+/// A 2V step through 50Ω into a 50Ω, 1ns line, matched or open at the far
+/// end. Returns the near and far end voltages.
+fn tline_step(load: &str) -> (Waveform, Waveform) {
+    let results = simulate(&format!(
+        "Line step
+V1 in 0 PULSE(0 2 0 1p 1p 1 2)
+RS in near 50
+T1 near 0 far 0 Z0=50 TD=1n
+{load}
+.tran 10p 4n
+.end
+"
+    ))
+    .unwrap();
+    (
+        results.tran_voltage("near").unwrap(),
+        results.tran_voltage("far").unwrap(),
+    )
+}
+
+#[test]
+fn test_simulate_tline_rejects_step_beyond_delay() {
+    // A 2ns step would skip over the 1ns line's wave in flight
+    let err = simulate(
+        "Coarse line
+V1 in 0 1
+T1 in 0 out 0 Z0=50 TD=1n
+RL out 0 50
+.tran 2n 10n
+.end
+",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("transmission line"), "{err}");
+}
+
+#[test]
+fn test_adaptive_tline_steps_within_delay() {
+    // Nothing changes, so the stepper grows toward h_max = 10ns but must
+    // stay within the 1ns delay
+    let netlist = spicier_parser::parse(
+        "Quiet line
+V1 in 0 1
+RS in near 50
+T1 near 0 far 0 Z0=50 TD=1n
+RL far 0 50
+.end
+",
+    )
+    .unwrap();
+    let stamper = NetlistTransientStamper::new(&netlist);
+    let params = AdaptiveTransientParams {
+        tstop: 50e-9,
+        h_max: 10e-9,
+        ..Default::default()
+    };
+    let dc = DVector::zeros(netlist.num_nodes() + netlist.num_current_vars());
+    let result = solve_transient_adaptive(&stamper, &mut [], &mut [], &params, &dc).unwrap();
+    assert!(
+        result.max_step_used <= 1e-9 * (1.0 + 1e-9),
+        "{}",
+        result.max_step_used
+    );
+    assert!(result.points.last().unwrap().time >= 50e-9 * (1.0 - 1e-9));
+}
+
+/// Value of a waveform at the timepoint nearest `t`.
+fn at(waveform: &[(f64, f64)], t: f64) -> f64 {
+    waveform
+        .iter()
+        .min_by(|a, b| (a.0 - t).abs().total_cmp(&(b.0 - t).abs()))
+        .unwrap()
+        .1
+}
+
+#[test]
+fn test_simulate_tline_matched_vs_open() {
+    // Matched: the 1V incident wave is absorbed at the far end, which
+    // follows the near end TD later with no reflection.
+    let (near, far) = tline_step("RL far 0 50");
+    for (t, v_near, v_far) in [(0.5e-9, 1.0, 0.0), (1.5e-9, 1.0, 1.0), (3.5e-9, 1.0, 1.0)] {
+        assert!((at(&near, t) - v_near).abs() < 1e-6, "V(near) at {t}");
+        assert!((at(&far, t) - v_far).abs() < 1e-6, "V(far) at {t}");
+    }
+
+    // Open: the far end doubles at TD, and the reflection lifts the near end
+    // to the full 2V after 2·TD.
+    let (near, far) = tline_step("");
+    for (t, v_near, v_far) in [(0.5e-9, 1.0, 0.0), (1.5e-9, 1.0, 2.0), (2.5e-9, 2.0, 2.0)] {
+        assert!((at(&near, t) - v_near).abs() < 1e-6, "V(near) at {t}");
+        assert!((at(&far, t) - v_far).abs() < 1e-6, "V(far) at {t}");
+    }
+}
@@ -62,31 +62,21 @@ pub fn compare_ac(
 
         let sp_phase = spicier.phase_deg(&name).unwrap_or_default();
 
-        // Compare at each frequency point
-        // Find worst case deviation
+        // Compare at each ngspice frequency point, interpolating spicier's
+        // sweep in log-frequency so the two grids need not coincide
         let mut worst_mag_error = 0.0;
         let mut worst_mag_point: Option<WorstPointInfo> = None;
         let mut worst_phase_error = 0.0;
         let mut worst_phase_point: Option<WorstPointInfo> = None;
         let mut all_passed = true;
+        let mut compared = 0;
 
-        // Align frequency points between ngspice and spicier
         for (i, &freq) in ngspice.frequencies.iter().enumerate() {
-            // Find closest frequency in spicier results
-            let (j, &(sp_freq, sp_mag_val)) = sp_mag
-                .iter()
-                .enumerate()
-                .min_by(|(_, a), (_, b)| {
-                    let da = (a.0 - freq).abs();
-                    let db = (b.0 - freq).abs();
-                    da.partial_cmp(&db).unwrap()
-                })
-                .unwrap_or((0, &(0.0, 0.0)));
-
-            // Skip if frequencies are too different (more than 1% relative)
-            if (sp_freq - freq).abs() / freq > 0.01 {
+            // Skip frequencies outside spicier's sweep
+            let Some(sp_mag_val) = interpolate_log_freq(&sp_mag, freq, false) else {
                 continue;
-            }
+            };
+            compared += 1;
 
             let ng_mag_val = ng_mag[i];
             let mag_error = (sp_mag_val - ng_mag_val).abs();
@@ -106,15 +96,11 @@ pub fn compare_ac(
             }
 
             // Compare phase
-            if i < ng_phase.len() && j < sp_phase.len() {
-                let ng_phase_val = ng_phase[i];
-                let sp_phase_val = sp_phase[j].1;
-
+            if let (Some(&ng_phase_val), Some(sp_phase_val)) =
+                (ng_phase.get(i), interpolate_log_freq(&sp_phase, freq, true))
+            {
                 // Handle phase wrapping
-                let mut phase_error = (sp_phase_val - ng_phase_val).abs();
-                if phase_error > 180.0 {
-                    phase_error = 360.0 - phase_error;
-                }
+                let phase_error = wrap_phase(sp_phase_val - ng_phase_val).abs();
 
                 if phase_error > tolerances.phase_deg {
                     all_passed = false;
@@ -132,6 +118,18 @@ pub fn compare_ac(
             }
         }
 
+        if compared == 0 {
+            report.add_comparison(VariableComparison {
+                name: name.clone(),
+                passed: false,
+                expected: format!("{} points", ng_mag.len()),
+                actual: format!("{} points", sp_mag.len()),
+                error: "no overlapping frequencies".to_string(),
+                worst_point: None,
+            });
+            continue;
+        }
+
         // Create comparison entry for magnitude
         report.add_comparison(VariableComparison {
             name: format!("{}|mag", name),
@@ -156,3 +154,65 @@ pub fn compare_ac(
     report.finalize();
     report
 }
+
+/// Relative slack when deciding whether a frequency lies inside a sweep.
+const FREQ_MATCH_TOL: f64 = 1e-6;
+
+/// Interpolate `(frequency, value)` samples at `freq`, linearly in
+/// log-frequency. Returns `None` outside the sampled range. With `phase`,
+/// the step between neighbouring samples is unwrapped first.
+fn interpolate_log_freq(points: &[(f64, f64)], freq: f64, phase: bool) -> Option<f64> {
+    let (first, last) = (points.first()?, points.last()?);
+    if freq < first.0 * (1.0 - FREQ_MATCH_TOL) || freq > last.0 * (1.0 + FREQ_MATCH_TOL) {
+        return None;
+    }
+    let hi = points
+        .iter()
+        .position(|&(f, _)| f >= freq)
+        .unwrap_or(points.len() - 1);
+    if hi == 0 || points[hi].0 == freq {
+        return Some(points[hi].1);
+    }
+
+    let (f0, y0) = points[hi - 1];
+    let (f1, y1) = points[hi];
+    let t = (freq / f0).ln() / (f1 / f0).ln();
+    let dy = if phase { wrap_phase(y1 - y0) } else { y1 - y0 };
+    Some(y0 + t * dy)
+}
+
+/// Wrap a phase difference into (-180°, 180°].
+fn wrap_phase(deg: f64) -> f64 {
+    let wrapped = deg.rem_euclid(360.0);
+    if wrapped > 180.0 {
+        wrapped - 360.0
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate_log_freq() {
+        let points = [(10.0, 0.0), (100.0, -20.0), (1000.0, -40.0)];
+
+        assert_eq!(interpolate_log_freq(&points, 10.0, false), Some(0.0));
+        assert_eq!(interpolate_log_freq(&points, 1000.0, false), Some(-40.0));
+        let mid = interpolate_log_freq(&points, 10f64.powf(2.5), false).unwrap();
+        assert!((mid + 30.0).abs() < 1e-9);
+        assert_eq!(interpolate_log_freq(&points, 5.0, false), None);
+        assert_eq!(interpolate_log_freq(&points, 2000.0, false), None);
+    }
+
+    #[test]
+    fn test_interpolate_phase_across_wrap() {
+        // -170° to 170° is a 20° step through ±180°, not 340°
+        let points = [(10.0, -170.0), (100.0, 170.0)];
+        let mid = interpolate_log_freq(&points, 10f64.powf(1.5), true).unwrap();
+        assert!((wrap_phase(mid) - 180.0).abs() < 1e-9);
+        assert!((wrap_phase(-190.0) - 170.0).abs() < 1e-12);
+    }
+}
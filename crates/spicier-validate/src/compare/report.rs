@@ -120,6 +120,21 @@ impl ComparisonReport {
         };
     }
 
+    /// The comparison with the largest worst-point error, if any has one.
+    ///
+    /// Errors are compared as plain numbers, so for AC reports the magnitude
+    /// (dB) and phase (degrees) entries compete on raw value.
+    pub fn worst_comparison(&self) -> Option<&VariableComparison> {
+        self.comparisons
+            .iter()
+            .filter(|c| c.worst_point.is_some())
+            .max_by(|a, b| {
+                let ea = a.worst_point.as_ref().map_or(0.0, |w| w.error);
+                let eb = b.worst_point.as_ref().map_or(0.0, |w| w.error);
+                ea.total_cmp(&eb)
+            })
+    }
+
     /// Format as human-readable text.
     pub fn to_text(&self) -> String {
         let mut out = String::new();
@@ -152,6 +167,14 @@ impl ComparisonReport {
             }
         }
 
+        if let Some(comp) = self.worst_comparison() {
+            let worst = comp.worst_point.as_ref().unwrap();
+            out.push_str(&format!(
+                "\nWorst deviation: {} at {:.6e} (error={:.6e})\n",
+                comp.name, worst.at, worst.error
+            ));
+        }
+
         if !self.passed {
             out.push_str("\nFailed variables:\n");
             for comp in &self.comparisons {
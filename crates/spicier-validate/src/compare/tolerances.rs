@@ -51,7 +51,8 @@ pub struct TransientTolerances {
     pub voltage_abs: f64,
     /// Relative voltage tolerance (fraction).
     pub voltage_rel: f64,
-    /// Maximum time shift tolerance (s).
+    /// Maximum time shift tolerance (s). Each ngspice sample is matched
+    /// against the closest spicier value within this distance in time.
     pub time_shift: f64,
 }
 
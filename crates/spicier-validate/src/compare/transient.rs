@@ -68,22 +68,12 @@ pub fn compare_transient(
         for (i, &time) in ngspice.times.iter().enumerate() {
             let ng_value = ng_values[i];
 
-            // Get spicier value at this time (interpolated)
-            let sp_value = match spicier.voltage_at(&name, time) {
-                Some(v) => v,
-                None => {
-                    // Time outside range - find closest
-                    sp_waveform
-                        .iter()
-                        .min_by(|a, b| {
-                            let da = (a.0 - time).abs();
-                            let db = (b.0 - time).abs();
-                            da.partial_cmp(&db).unwrap()
-                        })
-                        .map(|&(_, v)| v)
-                        .unwrap_or(0.0)
-                }
-            };
+            // Get spicier value at this time (interpolated), or the closest
+            // one within the time-shift window
+            let sp_value =
+                closest_in_window(&sp_waveform, time, tolerances.time_shift, ng_value, |t| {
+                    spicier.voltage_at(&name, t)
+                });
 
             let passed = values_match(
                 ng_value,
@@ -130,3 +120,77 @@ pub fn compare_transient(
     report.finalize();
     report
 }
+
+/// Spicier value closest to `target` within `shift` of `time`.
+///
+/// The resampled waveform is piecewise linear, so it takes every value
+/// between its extremes over the window; those extremes occur at the window
+/// edges or at spicier timepoints inside it.
+fn closest_in_window(
+    waveform: &[(f64, f64)],
+    time: f64,
+    shift: f64,
+    target: f64,
+    sample: impl Fn(f64) -> Option<f64>,
+) -> f64 {
+    let sample = |t: f64| {
+        sample(t).unwrap_or_else(|| {
+            // Time outside range - find closest
+            waveform
+                .iter()
+                .min_by(|a, b| {
+                    let da = (a.0 - t).abs();
+                    let db = (b.0 - t).abs();
+                    da.partial_cmp(&db).unwrap()
+                })
+                .map(|&(_, v)| v)
+                .unwrap_or(0.0)
+        })
+    };
+
+    let at_time = sample(time);
+    if shift <= 0.0 {
+        return at_time;
+    }
+
+    let (lo, hi) = waveform
+        .iter()
+        .filter(|&&(t, _)| (t - time).abs() < shift)
+        .map(|&(_, v)| v)
+        .chain([sample(time - shift), at_time, sample(time + shift)])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+    target.clamp(lo, hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(t: f64) -> Option<f64> {
+        Some(t.clamp(0.0, 1.0))
+    }
+
+    #[test]
+    fn test_closest_in_window() {
+        let waveform = [(0.0, 0.0), (1.0, 1.0)];
+
+        // No shift: plain interpolation
+        assert_eq!(closest_in_window(&waveform, 0.5, 0.0, 0.6, ramp), 0.5);
+        // A lagging target is matched within the window
+        assert_eq!(closest_in_window(&waveform, 0.5, 0.2, 0.6, ramp), 0.6);
+        // Beyond the window the nearest edge is used
+        assert!((closest_in_window(&waveform, 0.5, 0.2, 0.9, ramp) - 0.7).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_closest_in_window_includes_interior_peak() {
+        // A spike between window edges that bracket the target from one side
+        let waveform = [(0.0, 0.0), (0.5, 2.0), (1.0, 0.0)];
+        let triangle = |t: f64| Some(2.0 - 4.0 * (t - 0.5).abs());
+
+        assert_eq!(closest_in_window(&waveform, 0.5, 0.0, 3.0, triangle), 2.0);
+        assert_eq!(closest_in_window(&waveform, 0.4, 0.2, 1.9, triangle), 1.9);
+    }
+}
@@ -0,0 +1,121 @@
+//! Closed-form AC references for circuits with known transfer functions.
+//!
+//! These generate golden [`AcPoint`]s without ngspice, for use in a
+//! [`GoldenAnalysis::Ac`](super::GoldenAnalysis::Ac) checked by
+//! [`validate_against_golden`](crate::validate_against_golden). Phases are
+//! wrapped to (-180°, 180°], as the AC comparison expects.
+
+use std::f64::consts::PI;
+
+use num_complex::Complex;
+
+use super::format::AcPoint;
+
+/// Golden points for a transfer function evaluated at `s = j2πf`.
+fn sample(freqs: &[f64], h: impl Fn(Complex<f64>) -> Complex<f64>) -> Vec<AcPoint> {
+    freqs
+        .iter()
+        .map(|&freq| {
+            let value = h(Complex::new(0.0, 2.0 * PI * freq));
+            AcPoint {
+                freq,
+                mag_db: 20.0 * value.norm().log10(),
+                phase_deg: value.arg().to_degrees(),
+            }
+        })
+        .collect()
+}
+
+/// Single-pole RC low-pass, `H(s) = 1 / (1 + sRC)`.
+///
+/// The output is the capacitor voltage of a series R driving a grounded C,
+/// so the -3 dB point is `1 / (2πRC)`.
+pub fn analytical_rc_lowpass(r: f64, c: f64, freqs: &[f64]) -> Vec<AcPoint> {
+    sample(freqs, |s| 1.0 / (1.0 + s * r * c))
+}
+
+/// Unloaded RC ladder of `stages` identical series-R, shunt-C sections.
+///
+/// Chains the sections' ABCD matrices; with the output open, the transfer
+/// is `1 / A`. One stage is [`analytical_rc_lowpass`].
+pub fn analytical_rc_ladder(r: f64, c: f64, stages: usize, freqs: &[f64]) -> Vec<AcPoint> {
+    sample(freqs, |s| {
+        let y = s * c;
+        // [A B; C D] of one section: series R then shunt Y
+        let (a1, b1, c1, d1) = (1.0 + r * y, Complex::from(r), y, Complex::from(1.0));
+        let (mut a, mut b, mut cc, mut d) = (
+            Complex::from(1.0),
+            Complex::from(0.0),
+            Complex::from(0.0),
+            Complex::from(1.0),
+        );
+        for _ in 0..stages {
+            (a, b, cc, d) = (
+                a * a1 + b * c1,
+                a * b1 + b * d1,
+                cc * a1 + d * c1,
+                cc * b1 + d * d1,
+            );
+        }
+        1.0 / a
+    })
+}
+
+/// Second-order low-pass with unity DC gain,
+/// `H(s) = ωn² / (s² + s·ωn/Q + ωn²)`.
+///
+/// Covers the unity-gain Sallen-Key section, where
+/// `ωn = 1/√(R1R2C1C2)` and `Q = √(R1R2C1C2) / (C2(R1 + R2))`, and with it
+/// the second-order Butterworth (`Q = 1/√2`) and Chebyshev responses.
+/// `wn` is in rad/s.
+pub fn analytical_second_order(wn: f64, q: f64, freqs: &[f64]) -> Vec<AcPoint> {
+    sample(freqs, |s| wn * wn / (s * s + s * wn / q + wn * wn))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rc_lowpass_cutoff() {
+        let (r, c) = (1e3, 1e-6);
+        let fc = 1.0 / (2.0 * PI * r * c);
+        let points = analytical_rc_lowpass(r, c, &[fc / 1000.0, fc, fc * 1000.0]);
+
+        assert!(points[0].mag_db.abs() < 1e-5);
+        assert!((points[1].mag_db + 10.0 * 2f64.log10()).abs() < 1e-12);
+        assert!((points[1].phase_deg + 45.0).abs() < 1e-12);
+        // -20 dB/decade well above the corner
+        assert!((points[2].mag_db + 60.0).abs() < 1e-5);
+
+        let ladder = analytical_rc_ladder(r, c, 1, &[fc]);
+        assert!((ladder[0].mag_db - points[1].mag_db).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rc_ladder_three_stages() {
+        // Three stages cross -180° where ω²R²C² = 6, with |H| = 1/29
+        let (r, c) = (1e3, 1e-6);
+        let f = 6f64.sqrt() / (2.0 * PI * r * c);
+        let points = analytical_rc_ladder(r, c, 3, &[f]);
+
+        assert!((points[0].mag_db - 20.0 * (1.0 / 29.0f64).log10()).abs() < 1e-9);
+        assert!((points[0].phase_deg.abs() - 180.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_second_order_at_natural_frequency() {
+        let wn = 2.0 * PI * 1e3;
+        let f0 = 1e3;
+
+        // |H(jωn)| = Q and the phase is -90° for any Q
+        for q in [0.5, 1.0 / 2f64.sqrt(), 2.0] {
+            let points = analytical_second_order(wn, q, &[f0 / 100.0, f0, f0 * 100.0]);
+            assert!(points[0].mag_db.abs() < 1e-3);
+            assert!((points[1].mag_db - 20.0 * q.log10()).abs() < 1e-9);
+            assert!((points[1].phase_deg + 90.0).abs() < 1e-9);
+            // -40 dB/decade
+            assert!((points[2].mag_db + 80.0).abs() < 0.01);
+        }
+    }
+}
@@ -95,6 +95,9 @@ pub struct GoldenAcTolerances {
 pub struct GoldenTranTolerances {
     /// Voltage tolerance (V).
     pub voltage: f64,
+    /// Time shift tolerated when aligning waveforms (s).
+    #[serde(default)]
+    pub time_shift: f64,
 }
 
 /// AC sweep parameters.
@@ -3,12 +3,14 @@
 //! This module provides functionality for loading and using golden reference
 //! data files to validate spicier simulation results.
 
+pub mod analytical;
 pub mod format;
 
 use std::path::Path;
 
 use crate::error::{Error, Result};
 
+pub use analytical::{analytical_rc_ladder, analytical_rc_lowpass, analytical_second_order};
 pub use format::{
     AcPoint, AcSweepParams, GoldenAcTolerances, GoldenAnalysis, GoldenCircuit, GoldenDataFile,
     GoldenDcTolerances, GoldenTranTolerances, TranParams, TranPoint,
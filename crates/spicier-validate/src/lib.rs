@@ -53,8 +53,8 @@ pub use error::{Error, Result};
 
 pub use golden::{
     AcPoint, AcSweepParams, GoldenAcTolerances, GoldenAnalysis, GoldenCircuit, GoldenDataFile,
-    GoldenDcTolerances, GoldenTranTolerances, TranParams, TranPoint, load_golden_directory,
-    load_golden_file,
+    GoldenDcTolerances, GoldenTranTolerances, TranParams, TranPoint, analytical_rc_ladder,
+    analytical_rc_lowpass, analytical_second_order, load_golden_directory, load_golden_file,
 };
 
 pub use ngspice::{
@@ -177,7 +177,7 @@ pub fn validate_against_golden(circuit: &GoldenCircuit) -> Result<ComparisonRepo
             let tol = TransientTolerances {
                 voltage_abs: tolerances.voltage,
                 voltage_rel: 1e-3,
-                time_shift: 0.0,
+                time_shift: tolerances.time_shift,
             };
             Ok(compare_transient(
                 &ng,
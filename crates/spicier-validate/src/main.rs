@@ -7,8 +7,8 @@ use std::process::ExitCode;
 
 use clap::{Parser, Subcommand};
 use spicier_validate::{
-    ComparisonConfig, DcTolerances, NgspiceConfig, is_ngspice_available, load_golden_directory,
-    ngspice_version, validate_against_golden,
+    AcTolerances, ComparisonConfig, DcTolerances, NgspiceConfig, is_ngspice_available,
+    load_golden_directory, ngspice_version, validate_against_golden,
 };
 
 #[derive(Parser)]
@@ -35,6 +35,14 @@ enum Commands {
         #[arg(long, default_value = "1e-9")]
         current_tol: f64,
 
+        /// AC magnitude tolerance (dB), used when the netlist has `.ac`
+        #[arg(long, default_value = "0.1")]
+        mag_tol: f64,
+
+        /// AC phase tolerance (degrees), used when the netlist has `.ac`
+        #[arg(long, default_value = "1.0")]
+        phase_tol: f64,
+
         /// Output results as JSON
         #[arg(long)]
         json: bool,
@@ -77,8 +85,19 @@ fn main() -> ExitCode {
             netlist,
             voltage_tol,
             current_tol,
+            mag_tol,
+            phase_tol,
+            json,
+        } => cmd_compare(
+            netlist,
+            voltage_tol,
+            current_tol,
+            AcTolerances {
+                magnitude_db: mag_tol,
+                phase_deg: phase_tol,
+            },
             json,
-        } => cmd_compare(netlist, voltage_tol, current_tol, json),
+        ),
         Commands::Suite {
             golden_dir,
             filter,
@@ -89,7 +108,13 @@ fn main() -> ExitCode {
     }
 }
 
-fn cmd_compare(netlist_path: PathBuf, voltage_tol: f64, current_tol: f64, json: bool) -> ExitCode {
+fn cmd_compare(
+    netlist_path: PathBuf,
+    voltage_tol: f64,
+    current_tol: f64,
+    ac_tol: AcTolerances,
+    json: bool,
+) -> ExitCode {
     // Check ngspice availability
     let ng_config = NgspiceConfig::default();
     if !is_ngspice_available(&ng_config) {
@@ -107,14 +132,17 @@ fn cmd_compare(netlist_path: PathBuf, voltage_tol: f64, current_tol: f64, json:
     };
 
     // Configure tolerances
-    let config = ComparisonConfig::default().with_dc_tolerances(DcTolerances {
-        voltage_abs: voltage_tol,
-        voltage_rel: 1e-4,
-        current_abs: current_tol,
-        current_rel: 1e-4,
-    });
-
-    // Run comparison
+    let config = ComparisonConfig::default()
+        .with_dc_tolerances(DcTolerances {
+            voltage_abs: voltage_tol,
+            voltage_rel: 1e-4,
+            current_abs: current_tol,
+            current_rel: 1e-4,
+        })
+        .with_ac_tolerances(ac_tol);
+
+    // Run comparison; the analysis (.op, .ac or .tran) is detected from
+    // the netlist and both simulators' results are routed to its comparator
     match spicier_validate::compare_simulators(&netlist, &config) {
         Ok(report) => {
             if json {
@@ -9,18 +9,14 @@ use nalgebra::DVector;
 use num_complex::Complex;
 use spicier_core::NodeId;
 use spicier_core::mna::MnaSystem;
-use spicier_core::netlist::{AcDeviceInfo, TransientDeviceInfo};
 use spicier_parser::{AcSweepType, AnalysisCommand, ParseResult, parse_full};
-use spicier_solver::AcStamper;
-use spicier_solver::ac::{
-    AcParams, AcResult, AcSweepType as SolverAcSweepType, ComplexMna, solve_ac,
-};
+use spicier_solver::ac::{AcParams, AcResult, AcSweepType as SolverAcSweepType, solve_ac};
 use spicier_solver::dc::{DcSolution, solve_dc};
 use spicier_solver::newton::{ConvergenceCriteria, NonlinearStamper, solve_newton_raphson};
 use spicier_solver::transient::{
-    CapacitorState, InductorState, IntegrationMethod, TransientParams, TransientResult,
-    TransientStamper, solve_transient,
+    IntegrationMethod, TransientParams, TransientResult, solve_transient,
 };
+use spicier_solver::{NetlistAcStamper, NetlistTransientStamper, build_transient_state};
 
 use crate::error::{Error, Result};
 
@@ -324,196 +320,6 @@ fn run_dc_op(parse_result: &ParseResult) -> Result<SpicierResult> {
     }))
 }
 
-/// Stamper for AC analysis that wraps a ParseResult.
-struct AcCircuitStamper<'a> {
-    netlist: &'a spicier_core::Netlist,
-}
-
-impl AcStamper for AcCircuitStamper<'_> {
-    fn stamp_ac(&self, mna: &mut ComplexMna, omega: f64) {
-        for device in self.netlist.devices() {
-            let ac_info = device.ac_info();
-            match ac_info {
-                AcDeviceInfo::Resistor {
-                    node_pos,
-                    node_neg,
-                    conductance,
-                } => {
-                    mna.stamp_conductance(node_pos, node_neg, conductance);
-                }
-                AcDeviceInfo::Capacitor {
-                    node_pos,
-                    node_neg,
-                    capacitance,
-                } => {
-                    let yc = Complex::new(0.0, omega * capacitance);
-                    mna.stamp_admittance(node_pos, node_neg, yc);
-                }
-                AcDeviceInfo::Inductor {
-                    node_pos,
-                    node_neg,
-                    inductance,
-                    branch_idx,
-                } => {
-                    mna.stamp_inductor(node_pos, node_neg, branch_idx, omega, inductance);
-                }
-                AcDeviceInfo::VoltageSource {
-                    node_pos,
-                    node_neg,
-                    branch_idx,
-                    ac_mag,
-                } => {
-                    mna.stamp_voltage_source(
-                        node_pos,
-                        node_neg,
-                        branch_idx,
-                        Complex::new(ac_mag, 0.0),
-                    );
-                }
-                AcDeviceInfo::CurrentSource {
-                    node_pos,
-                    node_neg,
-                    ac_mag,
-                } => {
-                    if ac_mag.abs() > 0.0 {
-                        mna.stamp_current_source(node_pos, node_neg, Complex::new(ac_mag, 0.0));
-                    }
-                }
-                AcDeviceInfo::Vcvs {
-                    out_pos,
-                    out_neg,
-                    ctrl_pos,
-                    ctrl_neg,
-                    branch_idx,
-                    gain,
-                } => {
-                    let br = mna.num_nodes() + branch_idx;
-                    if let Some(i) = out_pos {
-                        mna.add_element(i, br, Complex::new(1.0, 0.0));
-                    }
-                    if let Some(i) = out_neg {
-                        mna.add_element(i, br, Complex::new(-1.0, 0.0));
-                    }
-                    if let Some(i) = out_pos {
-                        mna.add_element(br, i, Complex::new(1.0, 0.0));
-                    }
-                    if let Some(i) = out_neg {
-                        mna.add_element(br, i, Complex::new(-1.0, 0.0));
-                    }
-                    if let Some(i) = ctrl_pos {
-                        mna.add_element(br, i, Complex::new(-gain, 0.0));
-                    }
-                    if let Some(i) = ctrl_neg {
-                        mna.add_element(br, i, Complex::new(gain, 0.0));
-                    }
-                }
-                AcDeviceInfo::Vccs {
-                    out_pos,
-                    out_neg,
-                    ctrl_pos,
-                    ctrl_neg,
-                    gm,
-                } => {
-                    if let Some(i) = out_pos {
-                        if let Some(j) = ctrl_pos {
-                            mna.add_element(i, j, Complex::new(gm, 0.0));
-                        }
-                        if let Some(j) = ctrl_neg {
-                            mna.add_element(i, j, Complex::new(-gm, 0.0));
-                        }
-                    }
-                    if let Some(i) = out_neg {
-                        if let Some(j) = ctrl_pos {
-                            mna.add_element(i, j, Complex::new(-gm, 0.0));
-                        }
-                        if let Some(j) = ctrl_neg {
-                            mna.add_element(i, j, Complex::new(gm, 0.0));
-                        }
-                    }
-                }
-                AcDeviceInfo::Cccs {
-                    out_pos,
-                    out_neg,
-                    vsource_branch_idx,
-                    gain,
-                } => {
-                    let br = mna.num_nodes() + vsource_branch_idx;
-                    if let Some(i) = out_pos {
-                        mna.add_element(i, br, Complex::new(gain, 0.0));
-                    }
-                    if let Some(i) = out_neg {
-                        mna.add_element(i, br, Complex::new(-gain, 0.0));
-                    }
-                }
-                AcDeviceInfo::Ccvs {
-                    out_pos,
-                    out_neg,
-                    vsource_branch_idx,
-                    branch_idx,
-                    gain,
-                } => {
-                    let br = mna.num_nodes() + branch_idx;
-                    let ctrl_br = mna.num_nodes() + vsource_branch_idx;
-                    if let Some(i) = out_pos {
-                        mna.add_element(i, br, Complex::new(1.0, 0.0));
-                    }
-                    if let Some(i) = out_neg {
-                        mna.add_element(i, br, Complex::new(-1.0, 0.0));
-                    }
-                    if let Some(i) = out_pos {
-                        mna.add_element(br, i, Complex::new(1.0, 0.0));
-                    }
-                    if let Some(i) = out_neg {
-                        mna.add_element(br, i, Complex::new(-1.0, 0.0));
-                    }
-                    mna.add_element(br, ctrl_br, Complex::new(-gain, 0.0));
-                }
-                AcDeviceInfo::Diode {
-                    node_pos,
-                    node_neg,
-                    gd,
-                } => {
-                    mna.stamp_conductance(node_pos, node_neg, gd);
-                }
-                AcDeviceInfo::Mosfet {
-                    drain,
-                    gate,
-                    source,
-                    gds,
-                    gm,
-                } => {
-                    mna.stamp_conductance(drain, source, gds);
-                    if let Some(d) = drain {
-                        if let Some(g) = gate {
-                            mna.add_element(d, g, Complex::new(gm, 0.0));
-                        }
-                        if let Some(s) = source {
-                            mna.add_element(d, s, Complex::new(-gm, 0.0));
-                        }
-                    }
-                    if let Some(s) = source {
-                        if let Some(g) = gate {
-                            mna.add_element(s, g, Complex::new(-gm, 0.0));
-                        }
-                        if let Some(s2) = source {
-                            mna.add_element(s, s2, Complex::new(gm, 0.0));
-                        }
-                    }
-                }
-                AcDeviceInfo::None | _ => {}
-            }
-        }
-    }
-
-    fn num_nodes(&self) -> usize {
-        self.netlist.num_nodes()
-    }
-
-    fn num_vsources(&self) -> usize {
-        self.netlist.num_current_vars()
-    }
-}
-
 /// Run AC analysis.
 fn run_ac(
     parse_result: &ParseResult,
@@ -522,9 +328,7 @@ fn run_ac(
     fstart: f64,
     fstop: f64,
 ) -> Result<SpicierResult> {
-    let stamper = AcCircuitStamper {
-        netlist: &parse_result.netlist,
-    };
+    let stamper = NetlistAcStamper::new(&parse_result.netlist, None);
 
     let solver_sweep_type = match sweep_type {
         AcSweepType::Dec => SolverAcSweepType::Decade,
@@ -547,84 +351,6 @@ fn run_ac(
     }))
 }
 
-/// Stamper for transient analysis.
-struct TransientCircuitStamper<'a> {
-    netlist: &'a spicier_core::Netlist,
-}
-
-impl TransientStamper for TransientCircuitStamper<'_> {
-    fn stamp_at_time(&self, mna: &mut MnaSystem, time: f64) {
-        // Stamp all devices that are NOT capacitors or inductors.
-        // Capacitors and inductors are handled by companion models.
-        for device in self.netlist.devices() {
-            match device.transient_info() {
-                TransientDeviceInfo::Capacitor { .. } | TransientDeviceInfo::Inductor { .. } => {
-                    // Skip reactive devices
-                }
-                TransientDeviceInfo::None | _ => {
-                    device.stamp_at_time(mna, time);
-                }
-            }
-        }
-    }
-
-    fn num_nodes(&self) -> usize {
-        self.netlist.num_nodes()
-    }
-
-    fn num_vsources(&self) -> usize {
-        // Count only voltage source current vars, not inductor branch currents
-        let mut vs_count = 0;
-        for device in self.netlist.devices() {
-            match device.transient_info() {
-                TransientDeviceInfo::Inductor { .. } => {
-                    // Inductor companion model doesn't need branch current var
-                }
-                _ => {
-                    vs_count += device.num_current_vars();
-                }
-            }
-        }
-        vs_count
-    }
-}
-
-/// Build capacitor and inductor state vectors from the netlist for transient analysis.
-fn build_transient_state(
-    netlist: &spicier_core::Netlist,
-) -> (Vec<CapacitorState>, Vec<InductorState>) {
-    let mut caps = Vec::new();
-    let mut inds = Vec::new();
-
-    for device in netlist.devices() {
-        match device.transient_info() {
-            TransientDeviceInfo::Capacitor {
-                node_pos,
-                node_neg,
-                capacitance,
-            } => {
-                caps.push(CapacitorState::new(capacitance, node_pos, node_neg));
-            }
-            TransientDeviceInfo::Inductor {
-                node_pos,
-                node_neg,
-                inductance,
-                branch_index,
-            } => {
-                inds.push(InductorState::new(
-                    inductance,
-                    node_pos,
-                    node_neg,
-                    branch_index,
-                ));
-            }
-            TransientDeviceInfo::None | _ => {}
-        }
-    }
-
-    (caps, inds)
-}
-
 /// Run transient analysis.
 fn run_transient(
     parse_result: &ParseResult,
@@ -650,11 +376,13 @@ fn run_transient(
             .copied(),
     );
 
-    let stamper = TransientCircuitStamper { netlist };
+    let stamper = NetlistTransientStamper::new(netlist);
     let params = TransientParams {
         tstop,
         tstep,
         method: IntegrationMethod::Trapezoidal,
+        be_startup_steps: 0,
+        breakpoints: netlist.source_breakpoints(tstop),
     };
 
     let result = solve_transient(&stamper, &mut caps, &mut inds, &params, &dc_vec)?;
@@ -2,7 +2,11 @@
 //!
 //! These tests require ngspice to be installed.
 
-use spicier_validate::{ComparisonConfig, NgspiceConfig, compare_simulators, is_ngspice_available};
+use spicier_validate::{
+    AcSweepParams, ComparisonConfig, GoldenAcTolerances, GoldenAnalysis, GoldenCircuit,
+    GoldenTranTolerances, NgspiceConfig, TranParams, TranPoint, analytical_rc_lowpass,
+    analytical_second_order, compare_simulators, is_ngspice_available, validate_against_golden,
+};
 
 fn ngspice_available() -> bool {
     is_ngspice_available(&NgspiceConfig::default())
@@ -84,6 +88,54 @@ fn test_spicier_only_dc() {
     }
 }
 
+#[test]
+fn test_rawfile_round_trip() {
+    use std::collections::BTreeMap;
+
+    use spicier_solver::{AnalysisResult, RawEncoding, simulate};
+
+    let results = simulate(
+        "RC charging
+V1 in 0 DC 5
+R1 in out 1k
+C1 out 0 1u
+.ic V(out)=0
+.tran 50u 2m uic
+.end
+",
+    )
+    .unwrap();
+    let Some(AnalysisResult::Tran(tran)) = results.analyses.first() else {
+        panic!("expected a transient result");
+    };
+    let index = |name| results.node(name).unwrap().as_u32() as usize - 1;
+    let names = BTreeMap::from([
+        (index("in"), "v(in)".to_string()),
+        (index("out"), "v(out)".to_string()),
+    ]);
+
+    for encoding in [RawEncoding::Ascii, RawEncoding::Binary] {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        tran.write_rawfile(file.path(), &names, encoding).unwrap();
+        let raw = spicier_validate::parse_rawfile(&std::fs::read(file.path()).unwrap()).unwrap();
+
+        assert_eq!(raw.header.is_binary, encoding == RawEncoding::Binary);
+        assert_eq!(raw.header.num_points, tran.points.len());
+        let variables: Vec<&str> = raw.header.variables.iter().map(|v| &*v.name).collect();
+        assert_eq!(variables[0], "time");
+        assert!(variables[1..].iter().eq(names.values()));
+        assert_eq!(raw.real_data.len(), tran.points.len());
+
+        for (row, point) in raw.real_data.iter().zip(&tran.points) {
+            assert!((row[0] - point.time).abs() <= 1e-12 * point.time);
+            for (value, &index) in row[1..].iter().zip(names.keys()) {
+                let expected = point.solution[index];
+                assert!((value - expected).abs() <= 1e-12 * expected.abs().max(1.0));
+            }
+        }
+    }
+}
+
 #[test]
 fn test_values_match_function() {
     use spicier_validate::values_match;
@@ -178,6 +230,82 @@ fn test_ac_vcvs_amplifier() {
     assert!(report.passed, "AC VCVS amplifier should match ngspice");
 }
 
+#[test]
+fn test_ac_rc_lowpass_golden() {
+    // Analytical H = 1/(1 + j f/fc) around fc = 1/(2*pi*R*C) = 159.15 Hz.
+    // None of the golden frequencies fall on spicier's dec 10 grid.
+    let fc = 1.0 / (2.0 * std::f64::consts::PI * 1e3 * 1e-6);
+    let freqs: Vec<f64> = [0.1, 0.5, 1.0, 2.0, 10.0].iter().map(|r| r * fc).collect();
+    let results = analytical_rc_lowpass(1e3, 1e-6, &freqs);
+
+    let circuit = GoldenCircuit {
+        name: "rc_lowpass_ac".to_string(),
+        description: "RC low-pass -3dB point".to_string(),
+        netlist: "RC Lowpass\nV1 1 0 DC 0 AC 1\nR1 1 2 1k\nC1 2 0 1u\n.ac dec 10 10 10k\n.end\n"
+            .to_string(),
+        analysis: GoldenAnalysis::Ac {
+            sweep: AcSweepParams {
+                sweep_type: "dec".to_string(),
+                points: 10,
+                fstart: 10.0,
+                fstop: 10e3,
+            },
+            node: "V(2)".to_string(),
+            results,
+            tolerances: GoldenAcTolerances {
+                mag_db: 0.05,
+                phase_deg: 0.5,
+            },
+        },
+    };
+
+    let report = validate_against_golden(&circuit).unwrap();
+    println!("AC Report:\n{}", report.to_text());
+    assert!(report.passed);
+    assert_eq!(report.comparisons.len(), 2);
+}
+
+#[test]
+fn test_ac_sallen_key_golden() {
+    // Unity-gain Sallen-Key with R1 = R2 = 1k, C1 = 2u, C2 = 1u:
+    // ωn = 1/√(R1R2C1C2) and Q = √(R1R2C1C2)/(C2(R1+R2)) = 1/√2 (Butterworth)
+    let (r, c1, c2): (f64, f64, f64) = (1e3, 2e-6, 1e-6);
+    let wn = 1.0 / (r * r * c1 * c2).sqrt();
+    let q = (r * r * c1 * c2).sqrt() / (c2 * 2.0 * r);
+    let fn_hz = wn / (2.0 * std::f64::consts::PI);
+    let freqs: Vec<f64> = [0.1, 0.5, 1.0, 2.0, 10.0]
+        .iter()
+        .map(|r| r * fn_hz)
+        .collect();
+    let results = analytical_second_order(wn, q, &freqs);
+    assert!((results[2].mag_db + 3.0103).abs() < 1e-3);
+
+    let circuit = GoldenCircuit {
+        name: "sallen_key_ac".to_string(),
+        description: "Butterworth Sallen-Key low-pass".to_string(),
+        netlist: "Sallen-Key\nV1 1 0 DC 0 AC 1\nR1 1 2 1k\nR2 2 3 1k\nC1 2 4 2u\nC2 3 0 1u\nE1 4 0 3 0 1\n.ac dec 20 1 10k\n.end\n"
+            .to_string(),
+        analysis: GoldenAnalysis::Ac {
+            sweep: AcSweepParams {
+                sweep_type: "dec".to_string(),
+                points: 20,
+                fstart: 1.0,
+                fstop: 10e3,
+            },
+            node: "V(4)".to_string(),
+            results,
+            tolerances: GoldenAcTolerances {
+                mag_db: 0.05,
+                phase_deg: 0.5,
+            },
+        },
+    };
+
+    let report = validate_against_golden(&circuit).unwrap();
+    println!("AC Report:\n{}", report.to_text());
+    assert!(report.passed);
+}
+
 // ============================================================================
 // Transient Analysis Cross-Simulator Tests
 // ============================================================================
@@ -199,6 +327,59 @@ fn test_tran_rc_charging() {
     assert!(report.passed, "Transient RC charging should match ngspice");
 }
 
+#[test]
+fn test_tran_rc_charging_golden() {
+    // Analytical v(2) = 5 (1 - exp(-t/RC)) with RC = 1ms. The source steps
+    // within spicier's first 10us timestep, which shows up as a lag of about
+    // half a step that the time-shift tolerance absorbs.
+    let tau = 1e-3;
+    let results: Vec<TranPoint> = (0..=20)
+        .map(|i| {
+            let time = i as f64 * 0.25e-3;
+            TranPoint {
+                time,
+                value: 5.0 * (1.0 - (-time / tau).exp()),
+            }
+        })
+        .collect();
+
+    let circuit = |time_shift| GoldenCircuit {
+        name: "rc_charging_tran".to_string(),
+        description: "RC charging against the analytical waveform".to_string(),
+        netlist:
+            "RC Charging\nV1 1 0 PULSE(0 5 0 1n 1n 1 2)\nR1 1 2 1k\nC1 2 0 1u\n.tran 10u 5m\n.end\n"
+                .to_string(),
+        analysis: GoldenAnalysis::Tran {
+            params: TranParams {
+                tstep: 10e-6,
+                tstop: 5e-3,
+                tstart: 0.0,
+                uic: false,
+            },
+            node: "V(2)".to_string(),
+            results: results.clone(),
+            tolerances: GoldenTranTolerances {
+                voltage: 5e-3,
+                time_shift,
+            },
+        },
+    };
+
+    // Point-by-point, the lag is the worst deviation and is reported
+    let report = validate_against_golden(&circuit(0.0)).unwrap();
+    println!("Transient Report:\n{}", report.to_text());
+    let worst = report.worst_comparison().unwrap();
+    assert_eq!(worst.name, "V(2)");
+    let worst = worst.worst_point.as_ref().unwrap();
+    assert!(worst.at > 0.0 && worst.error < 0.05);
+    assert!(report.to_text().contains("Worst deviation: V(2)"));
+
+    // Allowing one timestep of shift, the waveforms agree
+    let report = validate_against_golden(&circuit(10e-6)).unwrap();
+    println!("Transient Report:\n{}", report.to_text());
+    assert!(report.passed);
+}
+
 #[test]
 #[ignore = "requires ngspice"]
 fn test_tran_pulse_response() {
@@ -134,6 +134,9 @@ pub use spicier_solver::{
     IntegrationMethod,
     // Operators
     RealOperator,
+    // Serializable setup
+    SimulationAnalysis,
+    SimulationSetup,
     SolverConfig,
     // Solver selection
     SolverStrategy,
@@ -146,8 +149,10 @@ pub use spicier_solver::{
     solve_dc,
     // Dispatched solvers
     solve_dc_dispatched,
+    solve_dc_nested_sweep_nonlinear,
     // DC sweep
     solve_dc_sweep,
+    solve_dc_sweep_nonlinear,
     // Newton-Raphson
     solve_newton_raphson,
     // Transient analysis